@@ -0,0 +1,76 @@
+#!/usr/bin/env node
+
+/**
+ * Reports drift between registry bodySchema declarations and a Harness
+ * OpenAPI spec. Exits non-zero when a declared field is missing upstream,
+ * has a different type, or an upstream-required field is not declared.
+ *
+ * Usage:
+ *   node scripts/check-openapi-drift.js <spec.json|spec.yaml|url> [--toolset <name>]
+ *
+ * Requires a build (`pnpm build`) — resource definitions are loaded from build/.
+ */
+
+import { readFileSync } from "node:fs";
+import { fileURLToPath } from "node:url";
+import { dirname, join } from "node:path";
+import { parse as parseYaml } from "yaml";
+import { checkResourcesAgainstSpec } from "./openapi-drift-lib.js";
+
+const __dirname = dirname(fileURLToPath(import.meta.url));
+const ROOT = join(__dirname, "..");
+
+async function loadSpec(source) {
+  const text = /^https?:\/\//.test(source)
+    ? await fetch(source).then((res) => {
+        if (!res.ok) throw new Error(`Failed to fetch ${source}: ${res.status} ${res.statusText}`);
+        return res.text();
+      })
+    : readFileSync(source, "utf-8");
+  return text.trimStart().startsWith("{") ? JSON.parse(text) : parseYaml(text);
+}
+
+async function main() {
+  const args = process.argv.slice(2);
+  const toolsetIndex = args.indexOf("--toolset");
+  const toolset = toolsetIndex !== -1 ? args[toolsetIndex + 1] : undefined;
+  const source = args.find((arg, i) => !arg.startsWith("--") && i !== toolsetIndex + 1);
+  if (!source) {
+    console.error("Usage: node scripts/check-openapi-drift.js <spec.json|spec.yaml|url> [--toolset <name>]");
+    process.exit(2);
+  }
+
+  const { Registry } = await import(join(ROOT, "build", "registry", "index.js"));
+  const registry = new Registry({
+    HARNESS_API_KEY: "pat.drift.check.token",
+    HARNESS_ACCOUNT_ID: "drift",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_TOOLSETS: toolset,
+    LOG_LEVEL: "error",
+  });
+  const resources = registry.getAllToolsets().flatMap((ts) => ts.resources);
+
+  const spec = await loadSpec(source);
+  const { drift, unmatched } = checkResourcesAgainstSpec(spec, resources);
+
+  console.log(`Checked ${resources.length} resource types against ${source}`);
+  if (unmatched.length > 0) {
+    console.log(`\n${unmatched.length} endpoint(s) with a bodySchema are not in this spec (skipped).`);
+  }
+  if (drift.length === 0) {
+    console.log("\nNo bodySchema drift found.");
+    return;
+  }
+  console.error(`\n${drift.length} drift item(s):`);
+  for (const d of drift) {
+    console.error(`  ${d.resourceType}.${d.operation} (${d.specPath}) ${d.field}: ${d.issue}`);
+  }
+  process.exit(1);
+}
+
+main().catch((err) => {
+  console.error(err);
+  process.exit(1);
+});
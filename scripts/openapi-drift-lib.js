@@ -0,0 +1,151 @@
+/**
+ * Pure helpers used by scripts/check-openapi-drift.js and unit tests.
+ *
+ * Compares the hand-written `bodySchema` declarations on registry endpoints
+ * with the request body schemas in a Harness OpenAPI (3.x) or Swagger (2.0)
+ * spec, so declarations that lag the upstream API show up as drift.
+ */
+
+const HTTP_METHODS = new Set(["get", "post", "put", "patch", "delete"]);
+
+/** Placeholder names differ between the registry and specs — compare shapes only. */
+export function normalizeApiPath(path) {
+  return path.replace(/\{[^}]+\}/g, "{}").replace(/\/+$/, "") || "/";
+}
+
+/**
+ * Find the spec operation for a registry endpoint. Registry paths carry the
+ * gateway prefix (`/ng/api`, `/pipeline/api`, ...) that specs usually put in
+ * `servers`, so the longest spec path that is a suffix of the registry path wins.
+ */
+export function findOperation(spec, method, path) {
+  const target = normalizeApiPath(path);
+  const verb = method.toLowerCase();
+  let best;
+  for (const [specPath, item] of Object.entries(spec.paths ?? {})) {
+    const normalized = normalizeApiPath(specPath);
+    if (!item?.[verb] || !HTTP_METHODS.has(verb)) continue;
+    if (target !== normalized && !target.endsWith(normalized)) continue;
+    if (!best || normalized.length > best.normalized.length) {
+      best = { specPath, normalized, operation: item[verb] };
+    }
+  }
+  return best && { path: best.specPath, operation: best.operation };
+}
+
+/** Follow local `$ref`s (`#/components/schemas/X`, `#/definitions/X`, requestBodies). */
+export function resolveRef(spec, node, seen = new Set()) {
+  if (!node || typeof node !== "object" || typeof node.$ref !== "string") return node;
+  if (seen.has(node.$ref) || !node.$ref.startsWith("#/")) return {};
+  seen.add(node.$ref);
+  const target = node.$ref
+    .slice(2)
+    .split("/")
+    .reduce((obj, key) => obj?.[key.replace(/~1/g, "/").replace(/~0/g, "~")], spec);
+  return resolveRef(spec, target ?? {}, seen);
+}
+
+/** Flatten `allOf` compositions into one object schema (properties and required merged). */
+export function flattenSchema(spec, node) {
+  const schema = resolveRef(spec, node) ?? {};
+  if (!Array.isArray(schema.allOf)) return schema;
+  const merged = { type: "object", properties: { ...(schema.properties ?? {}) }, required: [...(schema.required ?? [])] };
+  for (const part of schema.allOf) {
+    const flat = flattenSchema(spec, part);
+    Object.assign(merged.properties, flat.properties ?? {});
+    merged.required.push(...(flat.required ?? []));
+  }
+  return merged;
+}
+
+/** The JSON request body schema of an operation, for OpenAPI 3 or Swagger 2 specs. */
+export function requestBodySchema(spec, operation) {
+  const requestBody = resolveRef(spec, operation.requestBody);
+  const content = requestBody?.content;
+  if (content) {
+    const media = content["application/json"] ?? content["application/yaml"] ?? Object.values(content)[0];
+    return media?.schema ? flattenSchema(spec, media.schema) : undefined;
+  }
+  const bodyParam = (operation.parameters ?? [])
+    .map((p) => resolveRef(spec, p))
+    .find((p) => p?.in === "body");
+  return bodyParam?.schema ? flattenSchema(spec, bodyParam.schema) : undefined;
+}
+
+function upstreamType(spec, node) {
+  const schema = flattenSchema(spec, node);
+  if (schema.type === "integer") return "number";
+  if (schema.type) return schema.type;
+  if (schema.properties || schema.additionalProperties) return "object";
+  return undefined;
+}
+
+function typeMatches(declared, upstream) {
+  if (!upstream) return true;
+  if (declared === "yaml") return upstream === "string" || upstream === "object";
+  return declared === upstream;
+}
+
+/**
+ * Drift between declared body fields and an upstream schema, recursing into
+ * nested object fields. Upstream-required fields that are not declared are
+ * reported too, since agents cannot discover them via harness_describe.
+ */
+export function compareBodySchema(spec, fields, schema, prefix = "") {
+  const drift = [];
+  const properties = schema.properties ?? {};
+  for (const field of fields) {
+    const path = `${prefix}${field.name}`;
+    const upstream = properties[field.name];
+    if (!upstream) {
+      if (!schema.additionalProperties) drift.push({ field: path, issue: "not in upstream schema" });
+      continue;
+    }
+    const type = upstreamType(spec, upstream);
+    if (!typeMatches(field.type, type)) {
+      drift.push({ field: path, issue: `declared ${field.type}, upstream ${type}` });
+      continue;
+    }
+    if (field.type === "object" && field.fields) {
+      drift.push(...compareBodySchema(spec, field.fields, flattenSchema(spec, upstream), `${path}.`));
+    }
+  }
+  const declared = new Set(fields.map((f) => f.name));
+  for (const name of schema.required ?? []) {
+    if (!declared.has(name)) drift.push({ field: `${prefix}${name}`, issue: "required upstream but not declared" });
+  }
+  return drift;
+}
+
+/**
+ * Check every registry endpoint that declares a bodySchema and has a static
+ * path. `resources` is `ResourceDefinition[]`; endpoints the spec does not
+ * cover are listed separately rather than reported as drift.
+ */
+export function checkResourcesAgainstSpec(spec, resources) {
+  const drift = [];
+  const unmatched = [];
+  for (const resource of resources) {
+    const endpoints = [
+      ...Object.entries(resource.operations ?? {}),
+      ...Object.entries(resource.executeActions ?? {}).map(([action, endpoint]) => [`execute:${action}`, endpoint]),
+    ];
+    for (const [operation, endpoint] of endpoints) {
+      if (!endpoint.bodySchema || endpoint.pathBuilder) continue;
+      const match = findOperation(spec, endpoint.method, endpoint.path);
+      if (!match) {
+        unmatched.push({ resourceType: resource.resourceType, operation, path: endpoint.path });
+        continue;
+      }
+      let schema = requestBodySchema(spec, match.operation);
+      if (!schema) continue;
+      if (endpoint.bodyWrapperKey && schema.properties?.[endpoint.bodyWrapperKey]) {
+        schema = flattenSchema(spec, schema.properties[endpoint.bodyWrapperKey]);
+      }
+      for (const item of compareBodySchema(spec, endpoint.bodySchema.fields, schema)) {
+        drift.push({ resourceType: resource.resourceType, operation, specPath: match.path, ...item });
+      }
+    }
+  }
+  return { drift, unmatched };
+}
@@ -1,5 +1,6 @@
 import type { AuditEvent, AuditSink } from "./types.js";
 import { createLogger } from "../utils/logger.js";
+import { redactSecretValues } from "../utils/redact.js";
 
 const log = createLogger("audit-manager");
 
@@ -16,7 +17,13 @@ export class AuditManager {
     log.debug(`Audit sink registered: ${sink.name}`);
   }
 
-  emit(event: AuditEvent): void {
+  emit(rawEvent: AuditEvent): void {
+    // Error text and paths can echo tokens or credentials — scrub once before any sink sees them
+    const event: AuditEvent = {
+      ...rawEvent,
+      ...(rawEvent.error !== undefined ? { error: redactSecretValues(rawEvent.error) } : {}),
+      ...(rawEvent.http_path !== undefined ? { http_path: redactSecretValues(rawEvent.http_path) } : {}),
+    };
     for (const sink of this.sinks) {
       try {
         const result = sink.emit(event);
@@ -35,6 +35,8 @@ function buildAttributes(event: AuditEvent): Record<string, string | number> {
   if (event.http_status) attrs["audit.http_status"] = event.http_status;
   if (event.http_method) attrs["audit.http_method"] = event.http_method;
   if (event.http_path) attrs["audit.http_path"] = event.http_path;
+  if (event.correlation_id) attrs["audit.correlation_id"] = event.correlation_id;
+  if (event.trace_id) attrs["audit.trace_id"] = event.trace_id;
   if (event.org_id) attrs["audit.org_id"] = event.org_id;
   if (event.project_id) attrs["audit.project_id"] = event.project_id;
   return attrs;
@@ -64,6 +64,10 @@ export interface AuditEvent {
   http_status?: number;
   http_method?: string;
   http_path?: string;
+  /** Harness correlation ID from the API response — quote it when contacting Harness support. */
+  correlation_id?: string;
+  /** Trace ID of the tool call that made the request (also in the tool result's `_meta.trace_id`). */
+  trace_id?: string;
 }
 
 /**
@@ -0,0 +1,241 @@
+/**
+ * Server-side gating of experimental toolsets and resource types by the
+ * Harness account's own feature flags (HARNESS_FF_SDK_KEY).
+ *
+ * Definitions that declare `featureFlag` are only registered for accounts the
+ * flag is on for, so Harness can roll new capabilities out account by account
+ * without shipping a new server build. Flag configurations and target
+ * segments are polled from the Feature Flags client API — the same endpoints
+ * the server SDKs use — and evaluated locally with the account ID as the
+ * target identifier.
+ *
+ * Evaluation covers the flag state, individual target mappings, and rules on
+ * the target's identifier or name, including segment membership. Percentage
+ * rollouts and prerequisites are not evaluated: such a rule does not match and
+ * the flag falls through to its default serve. Until the first successful
+ * poll, and whenever a flag is unknown, gated definitions stay hidden.
+ */
+import { createLogger } from "../utils/logger.js";
+import { asRecord, asString, isRecord } from "../utils/type-guards.js";
+
+const log = createLogger("feature-flags");
+
+/** Anything that can answer "is this flag on for this account?" — the Registry only needs this. */
+export interface FeatureFlagEvaluator {
+  isEnabled(flag: string, accountId: string): boolean;
+}
+
+interface Clause {
+  attribute: string;
+  op: string;
+  values: string[];
+}
+
+interface ServingRule {
+  priority: number;
+  clauses: Clause[];
+  serve: { variation?: string };
+}
+
+interface FlagConfig {
+  state: "on" | "off";
+  variations: Map<string, string>;
+  offVariation: string;
+  defaultServe: { variation?: string };
+  targets: Map<string, string>;
+  rules: ServingRule[];
+}
+
+interface Segment {
+  included: Set<string>;
+  excluded: Set<string>;
+  rules: Clause[];
+}
+
+interface Target {
+  identifier: string;
+  name: string;
+}
+
+function parseClauses(raw: unknown): Clause[] {
+  return (Array.isArray(raw) ? raw : []).flatMap((c) => {
+    const clause = asRecord(c);
+    if (!clause) return [];
+    const values = Array.isArray(clause.values) ? clause.values.map(String) : [];
+    return [{ attribute: asString(clause.attribute) ?? "", op: asString(clause.op) ?? "", values }];
+  });
+}
+
+function parseFlag(raw: Record<string, unknown>): FlagConfig {
+  const variations = new Map<string, string>();
+  for (const v of Array.isArray(raw.variations) ? raw.variations : []) {
+    const variation = asRecord(v);
+    const id = asString(variation?.identifier);
+    if (id) variations.set(id, String(variation?.value));
+  }
+  const targets = new Map<string, string>();
+  for (const m of Array.isArray(raw.variationToTargetMap) ? raw.variationToTargetMap : []) {
+    const mapping = asRecord(m);
+    const variation = asString(mapping?.variation);
+    if (!variation) continue;
+    for (const t of Array.isArray(mapping?.targets) ? mapping.targets : []) {
+      const id = asString(asRecord(t)?.identifier);
+      if (id) targets.set(id, variation);
+    }
+  }
+  const rules = (Array.isArray(raw.rules) ? raw.rules : []).flatMap((r) => {
+    const rule = asRecord(r);
+    if (!rule) return [];
+    return [{
+      priority: typeof rule.priority === "number" ? rule.priority : 0,
+      clauses: parseClauses(rule.clauses),
+      serve: { variation: asString(asRecord(rule.serve)?.variation) },
+    }];
+  }).sort((a, b) => a.priority - b.priority);
+  return {
+    state: raw.state === "on" ? "on" : "off",
+    variations,
+    offVariation: asString(raw.offVariation) ?? "",
+    defaultServe: { variation: asString(asRecord(raw.defaultServe)?.variation) },
+    targets,
+    rules,
+  };
+}
+
+function parseSegment(raw: Record<string, unknown>): Segment {
+  const ids = (list: unknown) => new Set((Array.isArray(list) ? list : []).map((t) => asString(asRecord(t)?.identifier)).filter((id): id is string => Boolean(id)));
+  return { included: ids(raw.included), excluded: ids(raw.excluded), rules: parseClauses(raw.rules) };
+}
+
+/**
+ * Polled flag state plus local evaluation. `refresh` fetches once; `start`
+ * keeps refreshing in the background. A failed poll keeps the last good state.
+ */
+export class FeatureFlagGate implements FeatureFlagEvaluator {
+  private flags = new Map<string, FlagConfig>();
+  private segments = new Map<string, Segment>();
+  private auth: { token: string; environment: string; cluster: string } | undefined;
+  private timer: ReturnType<typeof setInterval> | undefined;
+  private loaded = false;
+
+  constructor(
+    private readonly sdkKey: string,
+    private readonly baseUrl: string,
+    private readonly pollIntervalMs: number,
+  ) {}
+
+  /** True once flag configurations have been fetched at least once. */
+  get ready(): boolean {
+    return this.loaded;
+  }
+
+  isEnabled(flag: string, accountId: string): boolean {
+    const config = this.flags.get(flag);
+    if (!config) return false;
+    return this.evaluate(config, { identifier: accountId, name: accountId }) === "true";
+  }
+
+  /** Variation value served to `target` (boolean flags serve "true" / "false"). */
+  private evaluate(config: FlagConfig, target: Target): string | undefined {
+    const serve = (variation: string | undefined) => (variation ? config.variations.get(variation) : undefined);
+    if (config.state === "off") return serve(config.offVariation);
+    const mapped = config.targets.get(target.identifier);
+    if (mapped) return serve(mapped);
+    for (const rule of config.rules) {
+      // Percentage rollouts have no fixed variation; skip them rather than guess a bucket
+      if (rule.serve.variation && rule.clauses.every((c) => this.matches(c, target))) return serve(rule.serve.variation);
+    }
+    return serve(config.defaultServe.variation);
+  }
+
+  private matches(clause: Clause, target: Target): boolean {
+    if (clause.op === "segmentMatch") return clause.values.some((id) => this.inSegment(id, target));
+    const value = clause.attribute === "name" ? target.name : clause.attribute === "identifier" ? target.identifier : undefined;
+    if (value === undefined) return false;
+    const [first = ""] = clause.values;
+    switch (clause.op) {
+      case "in": return clause.values.includes(value);
+      case "equal": return value.toLowerCase() === first.toLowerCase();
+      case "equal_sensitive": return value === first;
+      case "starts_with": return value.startsWith(first);
+      case "ends_with": return value.endsWith(first);
+      case "contains": return value.includes(first);
+      default: return false;
+    }
+  }
+
+  private inSegment(id: string, target: Target): boolean {
+    const segment = this.segments.get(id);
+    if (!segment || segment.excluded.has(target.identifier)) return false;
+    return segment.included.has(target.identifier) || segment.rules.some((c) => this.matches(c, target));
+  }
+
+  private async authenticate(): Promise<NonNullable<FeatureFlagGate["auth"]>> {
+    const response = await fetch(`${this.baseUrl}/client/auth`, {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ apiKey: this.sdkKey, target: { identifier: "harness-mcp-server", name: "harness-mcp-server" } }),
+      signal: AbortSignal.timeout(10_000),
+    });
+    if (!response.ok) throw new Error(`Feature Flags authentication failed (${response.status})`);
+    const token = asString(asRecord(await response.json())?.authToken);
+    // The token's claims name the environment and cluster the key belongs to
+    const claims = token ? asRecord(JSON.parse(Buffer.from(token.split(".")[1] ?? "", "base64url").toString("utf-8"))) : undefined;
+    const environment = asString(claims?.environment);
+    if (!token || !environment) throw new Error("Feature Flags authentication returned no usable token");
+    return { token, environment, cluster: asString(claims?.clusterIdentifier) ?? "1" };
+  }
+
+  private async get(path: string): Promise<unknown[]> {
+    this.auth ??= await this.authenticate();
+    const { token, environment, cluster } = this.auth;
+    const response = await fetch(`${this.baseUrl}/client/env/${encodeURIComponent(environment)}/${path}?cluster=${encodeURIComponent(cluster)}`, {
+      headers: { Authorization: `Bearer ${token}` },
+      signal: AbortSignal.timeout(10_000),
+    });
+    if (response.status === 401 || response.status === 403) {
+      // Expired token — authenticate again on the next poll
+      this.auth = undefined;
+    }
+    if (!response.ok) throw new Error(`Feature Flags ${path} request failed (${response.status})`);
+    const body = await response.json();
+    return Array.isArray(body) ? body : [];
+  }
+
+  /** Fetch flag configurations and segments once. Never throws; failures are logged. */
+  async refresh(): Promise<void> {
+    try {
+      const [flags, segments] = await Promise.all([this.get("feature-configs"), this.get("target-segments")]);
+      const nextFlags = new Map<string, FlagConfig>();
+      for (const f of flags) {
+        const id = isRecord(f) ? asString(f.feature) : undefined;
+        if (id) nextFlags.set(id, parseFlag(f as Record<string, unknown>));
+      }
+      const nextSegments = new Map<string, Segment>();
+      for (const s of segments) {
+        const id = isRecord(s) ? asString(s.identifier) : undefined;
+        if (id) nextSegments.set(id, parseSegment(s as Record<string, unknown>));
+      }
+      const changed = [...new Set([...nextFlags.keys(), ...this.flags.keys()])]
+        .filter((id) => nextFlags.get(id)?.state !== this.flags.get(id)?.state);
+      this.flags = nextFlags;
+      this.segments = nextSegments;
+      if (!this.loaded || changed.length > 0) log.info("Feature flags updated", { flags: nextFlags.size, changed });
+      this.loaded = true;
+    } catch (err) {
+      log.warn("Feature flag poll failed; keeping the last known flags", { error: err instanceof Error ? err.message : String(err) });
+    }
+  }
+
+  /** Poll every `pollIntervalMs` until `stop`. Does not keep the process alive. */
+  start(): void {
+    if (this.timer) return;
+    this.timer = setInterval(() => void this.refresh(), this.pollIntervalMs);
+    this.timer.unref?.();
+  }
+
+  stop(): void {
+    if (this.timer) clearInterval(this.timer);
+    this.timer = undefined;
+  }
+}
@@ -1,10 +1,22 @@
+import { createHash } from "node:crypto";
 import { type Config, isPlaceholderCredential, resolveFmeApiKey } from "../config.js";
 import type { RequestOptions } from "./types.js";
-import { HarnessApiError } from "../utils/errors.js";
+import { HarnessApiError, correlationIdFromHeaders, tagCorrelationId } from "../utils/errors.js";
+import { withGuidance } from "../utils/error-guidance.js";
+import { currentPrincipal, principalHeaders } from "../utils/principal.js";
+import { invalidatePermissions, permissionPrincipalKey } from "../utils/permission-cache.js";
 import { RateLimiter } from "../utils/rate-limiter.js";
 import { createLogger } from "../utils/logger.js";
+import { beginInFlight } from "../utils/live-diagnostics.js";
+import { ApiBudgetExceededError, currentApiBudget } from "../utils/api-budget.js";
+import { currentTraceId, TRACE_ID_HEADER } from "../utils/trace-id.js";
 import { redactJsonString } from "../utils/redact.js";
+import { recordApiCall } from "../utils/server-metrics.js";
 import { isFormDataBody } from "../utils/type-guards.js";
+import { applyRoute, clusterFromBaseUrl, resolveRouteMap, type RouteMap } from "./routing.js";
+import { Http2Pool } from "./http2-pool.js";
+import { sharedDnsLookup } from "./network.js";
+import { mockHarnessApi, type MockHarnessApi } from "./mock-api.js";
 
 const log = createLogger("harness-client");
 
@@ -129,6 +141,9 @@ function enrichErrorMessage(
  */
 export type AccountIdResolver = () => string | undefined;
 
+/** Observer for every HTTP exchange the client makes (the `fixtures record` subcommand). Receives a clone of the response. */
+export type ExchangeRecorder = (exchange: { method: string; url: string; response: Response }) => void;
+
 export class HarnessClient {
   private readonly baseUrl: string;
   private readonly token: string;
@@ -139,13 +154,23 @@ export class HarnessClient {
   private readonly logUnsafeBodies: boolean;
   private readonly fmeApiKey: string | undefined;
   private readonly mcpMode: Config["HARNESS_MCP_MODE"];
+  private readonly routes: RouteMap;
+  private readonly cluster: string | undefined;
+  /** Pooled HTTP/2 sessions when HARNESS_HTTP2 is on; otherwise calls go through the global fetch. */
+  private readonly http2: Http2Pool | undefined;
+  /** Canned fixture responses when HARNESS_MOCK is on; nothing leaves the process. */
+  private readonly mock: MockHarnessApi | undefined;
+  /** Short hash of the API key, for per-credential cache partitions. Never the key itself. */
+  readonly credentialId: string;
   private accountIdResolver?: AccountIdResolver;
+  private exchangeRecorder?: ExchangeRecorder;
   private currentUserId?: string;
   private currentUserPromise?: Promise<string>;
 
   constructor(config: Config) {
     this.baseUrl = config.HARNESS_BASE_URL.replace(/\/$/, "");
     this.token = config.HARNESS_API_KEY;
+    this.credentialId = createHash("sha256").update(this.token ?? "").digest("hex").slice(0, 16);
     this.accountId = config.HARNESS_ACCOUNT_ID;
     this.timeout = config.HARNESS_API_TIMEOUT_MS;
     this.maxRetries = config.HARNESS_MAX_RETRIES;
@@ -153,6 +178,10 @@ export class HarnessClient {
     this.logUnsafeBodies = config.HARNESS_LOG_UNSAFE_BODIES;
     this.fmeApiKey = resolveFmeApiKey(config);
     this.mcpMode = config.HARNESS_MCP_MODE;
+    this.routes = resolveRouteMap(config.HARNESS_DEPLOYMENT ?? "saas", config.HARNESS_ROUTE_MAP);
+    this.cluster = config.HARNESS_CLUSTER ?? clusterFromBaseUrl(this.baseUrl);
+    this.http2 = config.HARNESS_HTTP2 ? new Http2Pool({ lookup: sharedDnsLookup() }) : undefined;
+    this.mock = config.HARNESS_MOCK ? mockHarnessApi(config.HARNESS_MOCK_FIXTURES_DIR, this.baseUrl) : undefined;
   }
 
   /**
@@ -163,6 +192,11 @@ export class HarnessClient {
     this.accountIdResolver = resolver;
   }
 
+  /** Observe every response from now on, e.g. to record fixtures. Pass undefined to stop. */
+  setExchangeRecorder(recorder: ExchangeRecorder | undefined): void {
+    this.exchangeRecorder = recorder;
+  }
+
   /** Resolve the account ID: per-request override → static config fallback. */
   private resolveAccountId(): string {
     return this.accountIdResolver?.() ?? this.accountId;
@@ -179,8 +213,14 @@ export class HarnessClient {
   private buildHeaders(options: RequestOptions): Record<string, string> {
     const isFme = options.product === "fme";
     const accountId = this.resolveAccountId();
+    const traceId = currentTraceId();
     const headers: Record<string, string> = {
       ...(isFme ? {} : { "Harness-Account": accountId }),
+      // Identity of the JWT-authenticated HTTP caller this request is made for.
+      // Never sent to the third-party FME (Split.io) API.
+      ...(isFme ? {} : principalHeaders(currentPrincipal())),
+      // Tool call this request belongs to, for matching Harness-side logs to ours
+      ...(traceId && !isFme ? { [TRACE_ID_HEADER]: traceId } : {}),
       ...options.headers,
     };
     // gRPC-proxy services (query-service, schema-service, config-service) require x-tenant-id,
@@ -276,7 +316,34 @@ export class HarnessClient {
     }
   }
 
+  /** Count one outbound request against the calling session's API budget; throws when it is spent. */
+  private chargeApiBudget(): void {
+    const refusal = currentApiBudget()?.consume(this.account);
+    if (refusal) throw new ApiBudgetExceededError(refusal);
+  }
+
   async request<T>(options: RequestOptions): Promise<T> {
+    this.chargeApiBudget();
+    const end = beginInFlight("api", `${options.method ?? "GET"} ${options.path}`);
+    try {
+      const result = await this.performRequest<T>(options);
+      recordApiCall();
+      return result;
+    } catch (err) {
+      recordApiCall(err instanceof HarnessApiError ? err.statusCode : 0);
+      throw err;
+    } finally {
+      end();
+    }
+  }
+
+  private async send(url: string, init: { method: string; headers: Record<string, string>; body?: BodyInit; signal: AbortSignal }): Promise<Response> {
+    const response = await (this.mock ? this.mock.fetch(url, init) : this.http2 ? this.http2.fetch(url, init) : fetch(url, init));
+    this.exchangeRecorder?.({ method: init.method, url, response: response.clone() });
+    return response;
+  }
+
+  private async performRequest<T>(options: RequestOptions): Promise<T> {
     await this.rateLimiter.acquire();
 
     const method = options.method ?? "GET";
@@ -329,7 +396,7 @@ export class HarnessClient {
           });
         }
 
-        const response = await fetch(url, {
+        const response = await this.send(url, {
           method,
           headers,
           body: fetchBody,
@@ -339,6 +406,8 @@ export class HarnessClient {
         clearTimeout(timer);
 
         if (!response.ok) {
+          // A 403 means cached RBAC decisions for this principal are stale.
+          if (response.status === 403) invalidatePermissions(permissionPrincipalKey(this.resolveAccountId(), this.credentialId));
           const body = await response.text();
           let parsed: { message?: string; code?: string; correlationId?: string } = {};
           try {
@@ -351,15 +420,23 @@ export class HarnessClient {
           const rawMessage = isGarbageMessage(parsed.message)
               ? humanizeHttpError(response.status, body)
               : parsed.message!;
-          const message = enrichErrorMessage(rawMessage, parsed, options.path);
+          const message = withGuidance({
+            status: response.status,
+            path: options.path,
+            message: enrichErrorMessage(rawMessage, parsed, options.path),
+            code: parsed.code,
+            params: options.params,
+          });
+          const correlationId = parsed.correlationId ?? correlationIdFromHeaders(response.headers);
           log.debug(`HTTP ${response.status} error`, {
+            correlationId,
             body: this.logUnsafeBodies ? body.slice(0, 1000) : redactJsonString(body),
           });
           const error = new HarnessApiError(
             message,
             response.status,
             parsed.code,
-            parsed.correlationId,
+            correlationId,
           );
 
           if (
@@ -408,7 +485,11 @@ export class HarnessClient {
         log.debug("Response body", {
           body: this.logUnsafeBodies ? text.slice(0, 1000) : redactJsonString(text),
         });
-        return data as T;
+        const bodyCorrelationId = (data as { correlationId?: unknown } | null)?.correlationId;
+        return tagCorrelationId(
+          data,
+          correlationIdFromHeaders(response.headers) ?? (typeof bodyCorrelationId === "string" ? bodyCorrelationId : undefined),
+        ) as T;
       } catch (err) {
         if (err instanceof HarnessApiError) throw err;
         if (err instanceof Error && err.name === "AbortError") {
@@ -440,6 +521,7 @@ export class HarnessClient {
    * (before body consumption). Caller is responsible for reading the body.
    */
   async requestStream(options: RequestOptions): Promise<Response> {
+    this.chargeApiBudget();
     await this.rateLimiter.acquire();
 
     const method = options.method ?? "POST";
@@ -483,11 +565,13 @@ export class HarnessClient {
 
         log.debug(`STREAM ${method} ${url}`);
 
-        const response = await fetch(url, { method, headers, body: fetchBody, signal });
+        const response = await this.send(url, { method, headers, body: fetchBody, signal });
 
         clearTimeout(timer);
 
         if (!response.ok) {
+          // A 403 means cached RBAC decisions for this principal are stale.
+          if (response.status === 403) invalidatePermissions(permissionPrincipalKey(this.resolveAccountId(), this.credentialId));
           const body = await response.text();
           let parsed: { message?: string; code?: string; correlationId?: string } = {};
           try { parsed = JSON.parse(body); } catch { /* non-JSON */ }
@@ -495,8 +579,19 @@ export class HarnessClient {
           const rawMessage = isGarbageMessage(parsed.message)
               ? humanizeHttpError(response.status, body)
               : parsed.message!;
-          const message = enrichErrorMessage(rawMessage, parsed, options.path);
-          const error = new HarnessApiError(message, response.status, parsed.code, parsed.correlationId);
+          const message = withGuidance({
+            status: response.status,
+            path: options.path,
+            message: enrichErrorMessage(rawMessage, parsed, options.path),
+            code: parsed.code,
+            params: options.params,
+          });
+          const error = new HarnessApiError(
+            message,
+            response.status,
+            parsed.code,
+            parsed.correlationId ?? correlationIdFromHeaders(response.headers),
+          );
 
           if (
             RETRYABLE_STATUS_CODES.has(response.status) &&
@@ -588,7 +683,11 @@ export class HarnessClient {
     });
     const queryString = queryParts.join('&');
 
-    const url = queryString ? `${baseUrl}${path}?${queryString}` : `${baseUrl}${path}`;
+    // Route map applies to the Harness gateway only — per-request base URLs (e.g. FME) are left as-is
+    const routed = options.baseUrl
+      ? { baseUrl, path }
+      : applyRoute(baseUrl, path, this.routes, { account: this.resolveAccountId(), cluster: this.cluster });
+    const url = queryString ? `${routed.baseUrl}${routed.path}?${queryString}` : `${routed.baseUrl}${routed.path}`;
     log.debug(`Built URL: ${url}`);
     return url;
   }
@@ -0,0 +1,230 @@
+/**
+ * Opt-in HTTP/2 transport for the Harness API client (HARNESS_HTTP2=true).
+ *
+ * Keeps one multiplexed HTTP/2 session per origin so the dozens of
+ * sequential calls an agent makes share a single TLS handshake. Sessions size
+ * their receive window from the measured bandwidth-delay product, so log and
+ * YAML downloads are not throttled by the 64 KB protocol default; they are
+ * kept warm with PING frames and close after sitting idle. Origins that do not negotiate h2
+ * (and multipart bodies, which need fetch's encoder) fall back to the global
+ * fetch, whose HTTP/1.1 agent already keeps connections alive per host.
+ */
+import { connect, constants, type ClientHttp2Session, type IncomingHttpHeaders } from "node:http2";
+import type { LookupFunction } from "node:net";
+import { Readable } from "node:stream";
+import { createLogger } from "../utils/logger.js";
+import { recordConnectionEvent } from "../utils/server-metrics.js";
+
+const log = createLogger("http2");
+
+const DEFAULT_IDLE_TIMEOUT_MS = 60_000;
+const DEFAULT_PING_INTERVAL_MS = 30_000;
+/** Receive window a session starts with; grown as the bandwidth-delay product estimate rises. */
+const INITIAL_WINDOW_BYTES = 1024 * 1024;
+/** Upper bound for the adaptive receive window. */
+const DEFAULT_MAX_WINDOW_BYTES = 16 * 1024 * 1024;
+/** Headers HTTP/2 forbids (RFC 9113 §8.2.2); fetch callers may still set them. */
+const CONNECTION_HEADERS = new Set(["connection", "keep-alive", "proxy-connection", "transfer-encoding", "upgrade", "host"]);
+
+export interface Http2PoolOptions {
+  idleTimeoutMs?: number;
+  pingIntervalMs?: number;
+  /** Cap for the adaptive receive window. */
+  maxWindowBytes?: number;
+  /** Allow h2c (HTTP/2 without TLS) for http:// origins, e.g. a local gateway. */
+  cleartext?: boolean;
+  /** Resolver for new sessions (the shared DNS cache when enabled). */
+  lookup?: LookupFunction;
+}
+
+export interface Http2RequestInit {
+  method: string;
+  headers: Record<string, string>;
+  body?: BodyInit;
+  signal?: AbortSignal;
+}
+
+interface PoolEntry {
+  ready: Promise<ClientHttp2Session | undefined>;
+}
+
+/**
+ * BDP-style window sizing, as in gRPC: when data arrives and no sample is
+ * running, send a PING and count the bytes received until its ACK. If that
+ * comes close to the current window, the window is what limits throughput,
+ * so it grows to twice the sample.
+ */
+class WindowTuner {
+  private window = INITIAL_WINDOW_BYTES;
+  private sampleBytes = 0;
+  private sampling = false;
+
+  constructor(private readonly session: ClientHttp2Session, private readonly maxWindow: number) {}
+
+  get size(): number {
+    return this.window;
+  }
+
+  received(bytes: number): void {
+    this.sampleBytes += bytes;
+    if (this.sampling || this.window >= this.maxWindow || this.session.closed || this.session.destroyed) return;
+    this.sampling = true;
+    this.sampleBytes = bytes;
+    this.session.ping((err) => {
+      this.sampling = false;
+      if (err || this.session.closed || this.session.destroyed) return;
+      if (this.sampleBytes * 3 < this.window * 2) return;
+      this.window = Math.min(this.maxWindow, Math.max(this.window * 2, this.sampleBytes * 2));
+      this.session.setLocalWindowSize(this.window);
+      this.session.settings({ initialWindowSize: this.window });
+      log.debug("HTTP/2 receive window grown", { windowBytes: this.window });
+    });
+  }
+}
+
+/** Pools holding open sessions, so shutdown can close them. */
+const poolsWithSessions = new Set<Http2Pool>();
+
+/** Close every pooled HTTP/2 session in the process. Called on server shutdown. */
+export function closeHttp2Pools(): void {
+  for (const pool of [...poolsWithSessions]) pool.close();
+}
+
+export class Http2Pool {
+  private sessions = new Map<string, PoolEntry>();
+  /** Origins that did not negotiate h2 — sent over fetch from then on. */
+  private http1Origins = new Set<string>();
+  private readonly idleTimeoutMs: number;
+  private readonly pingIntervalMs: number;
+  private readonly maxWindowBytes: number;
+  private tuners = new WeakMap<ClientHttp2Session, WindowTuner>();
+  private readonly cleartext: boolean;
+  private readonly lookup: LookupFunction | undefined;
+
+  constructor(options: Http2PoolOptions = {}) {
+    this.idleTimeoutMs = options.idleTimeoutMs ?? DEFAULT_IDLE_TIMEOUT_MS;
+    this.pingIntervalMs = options.pingIntervalMs ?? DEFAULT_PING_INTERVAL_MS;
+    this.maxWindowBytes = options.maxWindowBytes ?? DEFAULT_MAX_WINDOW_BYTES;
+    this.cleartext = options.cleartext ?? false;
+    this.lookup = options.lookup;
+  }
+
+  /** Same contract as `fetch`, over a pooled HTTP/2 session when the origin supports it. */
+  async fetch(url: string, init: Http2RequestInit): Promise<Response> {
+    const target = new URL(url);
+    const eligible = target.protocol === "https:" || (this.cleartext && target.protocol === "http:");
+    const session = eligible && !this.http1Origins.has(target.origin) && (init.body === undefined || typeof init.body === "string")
+      ? await this.session(target.origin)
+      : undefined;
+    if (!session) {
+      recordConnectionEvent("http1_request");
+      return fetch(url, init);
+    }
+    return this.request(session, target, init);
+  }
+
+  /** Close every pooled session (server shutdown, tests). */
+  close(): void {
+    for (const entry of this.sessions.values()) {
+      void entry.ready.then((s) => s?.close());
+    }
+    this.sessions.clear();
+    poolsWithSessions.delete(this);
+  }
+
+  private session(origin: string): Promise<ClientHttp2Session | undefined> {
+    const existing = this.sessions.get(origin);
+    if (existing) {
+      recordConnectionEvent("http2_reused");
+      return existing.ready;
+    }
+    const entry = {} as PoolEntry;
+    this.sessions.set(origin, entry);
+    poolsWithSessions.add(this);
+    entry.ready = this.open(origin, () => {
+      if (this.sessions.get(origin) === entry) this.sessions.delete(origin);
+      if (this.sessions.size === 0) poolsWithSessions.delete(this);
+    });
+    return entry.ready;
+  }
+
+  private open(origin: string, forget: () => void): Promise<ClientHttp2Session | undefined> {
+    return new Promise((resolve) => {
+      const session = connect(origin, {
+        settings: { initialWindowSize: INITIAL_WINDOW_BYTES },
+        ...(this.lookup ? { lookup: this.lookup } : {}),
+      });
+      let connected = false;
+      const ping = setInterval(() => {
+        if (!session.closed && !session.destroyed) session.ping(() => {});
+      }, this.pingIntervalMs);
+      ping.unref();
+      const release = () => {
+        clearInterval(ping);
+        forget();
+      };
+
+      session.once("connect", () => {
+        connected = true;
+        session.setLocalWindowSize(INITIAL_WINDOW_BYTES);
+        this.tuners.set(session, new WindowTuner(session, this.maxWindowBytes));
+        recordConnectionEvent("http2_session_opened");
+        log.debug("HTTP/2 session opened", { origin });
+        resolve(session);
+      });
+      session.on("error", (err) => {
+        release();
+        if (!connected) {
+          // No h2 via ALPN (or no connection at all) — let fetch handle this origin from now on
+          this.http1Origins.add(origin);
+          log.debug("HTTP/2 unavailable, falling back to HTTP/1.1", { origin, error: err.message });
+          resolve(undefined);
+        }
+      });
+      session.once("goaway", release);
+      session.once("close", () => {
+        release();
+        if (connected) recordConnectionEvent("http2_session_closed");
+      });
+      session.setTimeout(this.idleTimeoutMs, () => session.close());
+      // Pooled sessions never keep the process alive on their own
+      session.unref();
+    });
+  }
+
+  private request(session: ClientHttp2Session, target: URL, init: Http2RequestInit): Promise<Response> {
+    const headers: Record<string, string> = {
+      [constants.HTTP2_HEADER_METHOD]: init.method,
+      [constants.HTTP2_HEADER_PATH]: `${target.pathname}${target.search}`,
+    };
+    for (const [name, value] of Object.entries(init.headers)) {
+      const lower = name.toLowerCase();
+      if (!CONNECTION_HEADERS.has(lower)) headers[lower] = value;
+    }
+
+    recordConnectionEvent("http2_request");
+    return new Promise((resolve, reject) => {
+      const stream = session.request(headers, { signal: init.signal, endStream: init.body === undefined });
+      stream.once("response", (responseHeaders: IncomingHttpHeaders) => {
+        const status = Number(responseHeaders[constants.HTTP2_HEADER_STATUS] ?? 0);
+        const out = new Headers();
+        for (const [name, value] of Object.entries(responseHeaders)) {
+          if (name.startsWith(":") || value === undefined) continue;
+          for (const v of Array.isArray(value) ? value : [String(value)]) out.append(name, v);
+        }
+        const bodyless = status === 204 || status === 304 || init.method === "HEAD";
+        if (bodyless) stream.resume();
+        const body = bodyless ? null : (Readable.toWeb(stream) as ReadableStream<Uint8Array>);
+        // Attached after toWeb so its backpressure still governs the stream
+        const tuner = this.tuners.get(session);
+        if (tuner && body) stream.on("data", (chunk: Buffer) => tuner.received(chunk.length));
+        resolve(new Response(body, { status, headers: out }));
+      });
+      stream.on("error", (err) => {
+        // Surface aborts the way fetch does so the client's retry logic treats them alike
+        reject(init.signal?.aborted ? new DOMException("The operation was aborted", "AbortError") : err);
+      });
+      if (typeof init.body === "string") stream.end(init.body);
+    });
+  }
+}
@@ -0,0 +1,182 @@
+/**
+ * Offline stand-in for the Harness API (`--mock` / HARNESS_MOCK=true).
+ *
+ * Requests the client would send are answered from canned fixtures instead of
+ * the network, so demos and CI can drive the full read surface without
+ * credentials. Fixtures use WireMock's stub mapping format, so one fixture
+ * directory serves both this mode and a WireMock server: each `*.json` file
+ * holds a single mapping or `{ "mappings": [...] }`. Mappings from
+ * HARNESS_MOCK_FIXTURES_DIR take precedence over the embedded demo account.
+ */
+import { readdirSync, readFileSync } from "node:fs";
+import { join, resolve } from "node:path";
+import { EMBEDDED_MOCK_MAPPINGS } from "../data/mock-fixtures.js";
+import { createLogger } from "../utils/logger.js";
+
+const log = createLogger("mock-api");
+
+/** WireMock's default priority — lower numbers match first. */
+const DEFAULT_PRIORITY = 5;
+
+export interface MockValueMatcher {
+  equalTo?: string;
+  contains?: string;
+  matches?: string;
+}
+
+/** One WireMock stub mapping (the subset mock mode understands). */
+export interface MockMapping {
+  priority?: number;
+  request: {
+    /** HTTP method, or `ANY`. Defaults to `ANY`. */
+    method?: string;
+    /** Exact path and query string. */
+    url?: string;
+    /** Regex over path and query string. */
+    urlPattern?: string;
+    /** Exact path, query ignored. */
+    urlPath?: string;
+    /** Regex over the path, query ignored. */
+    urlPathPattern?: string;
+    queryParameters?: Record<string, MockValueMatcher>;
+  };
+  response: {
+    status?: number;
+    headers?: Record<string, string>;
+    jsonBody?: unknown;
+    body?: string;
+    /** Binary body (e.g. a log archive), base64-encoded. */
+    base64Body?: string;
+  };
+}
+
+function matchesValue(value: string | null, matcher: MockValueMatcher): boolean {
+  if (value === null) return false;
+  if (matcher.equalTo !== undefined && value !== matcher.equalTo) return false;
+  if (matcher.contains !== undefined && !value.includes(matcher.contains)) return false;
+  if (matcher.matches !== undefined && !new RegExp(`^(?:${matcher.matches})$`).test(value)) return false;
+  return true;
+}
+
+function matchesRequest(mapping: MockMapping, method: string, path: string, query: URLSearchParams): boolean {
+  const req = mapping.request;
+  if (req.method && req.method !== "ANY" && req.method.toUpperCase() !== method) return false;
+  const search = query.toString();
+  const pathAndQuery = search ? `${path}?${search}` : path;
+  if (req.url !== undefined && req.url !== pathAndQuery) return false;
+  if (req.urlPattern !== undefined && !new RegExp(`^(?:${req.urlPattern})$`).test(pathAndQuery)) return false;
+  if (req.urlPath !== undefined && req.urlPath !== path) return false;
+  if (req.urlPathPattern !== undefined && !new RegExp(`^(?:${req.urlPathPattern})$`).test(path)) return false;
+  for (const [name, matcher] of Object.entries(req.queryParameters ?? {})) {
+    if (!matchesValue(query.get(name), matcher)) return false;
+  }
+  return true;
+}
+
+function toResponse(mapping: MockMapping): Response {
+  const { status = 200, headers = {}, jsonBody, body, base64Body } = mapping.response;
+  const out = new Headers(headers);
+  if (jsonBody !== undefined) {
+    if (!out.has("content-type")) out.set("content-type", "application/json");
+    return new Response(JSON.stringify(jsonBody), { status, headers: out });
+  }
+  if (base64Body !== undefined) {
+    if (!out.has("content-type")) out.set("content-type", "application/octet-stream");
+    return new Response(Buffer.from(base64Body, "base64"), { status, headers: out });
+  }
+  if (body !== undefined && !out.has("content-type")) out.set("content-type", "text/plain");
+  return new Response(body ?? null, { status, headers: out });
+}
+
+/** Parse the mappings in a fixture file's JSON: a single mapping or a WireMock `{ mappings: [...] }` document. */
+export function parseMockMappings(raw: unknown, source: string): MockMapping[] {
+  const list = raw !== null && typeof raw === "object" && Array.isArray((raw as { mappings?: unknown }).mappings)
+    ? (raw as { mappings: unknown[] }).mappings
+    : [raw];
+  return list.map((m, i) => {
+    const mapping = m as Partial<MockMapping> | null;
+    if (!mapping || typeof mapping.request !== "object" || typeof mapping.response !== "object") {
+      throw new Error(`Invalid mock fixture in ${source} (mapping ${i}): expected "request" and "response" objects.`);
+    }
+    return mapping as MockMapping;
+  });
+}
+
+/** Read every `*.json` mapping file in `dir` (not recursive), in file name order. */
+export function loadMockFixtureDir(dir: string): MockMapping[] {
+  const root = resolve(dir);
+  const files = readdirSync(root).filter((f) => f.endsWith(".json")).sort();
+  const mappings = files.flatMap((file) => {
+    const path = join(root, file);
+    let raw: unknown;
+    try {
+      raw = JSON.parse(readFileSync(path, "utf-8"));
+    } catch (err) {
+      throw new Error(`Invalid mock fixture ${path}: ${err instanceof Error ? err.message : String(err)}`);
+    }
+    return parseMockMappings(raw, path);
+  });
+  log.info("Loaded mock fixtures", { dir: root, files: files.length, mappings: mappings.length });
+  return mappings;
+}
+
+export class MockHarnessApi {
+  private readonly mappings: MockMapping[];
+
+  /**
+   * @param mappings Checked by priority, then in the given order.
+   * @param basePath Path prefix of HARNESS_BASE_URL, stripped before matching so fixtures stay portable.
+   */
+  constructor(mappings: MockMapping[], private readonly basePath = "") {
+    this.mappings = mappings
+      .map((mapping, index) => ({ mapping, index }))
+      .sort((a, b) => (a.mapping.priority ?? DEFAULT_PRIORITY) - (b.mapping.priority ?? DEFAULT_PRIORITY) || a.index - b.index)
+      .map(({ mapping }) => mapping);
+  }
+
+  /** Same contract as `fetch`; unmatched requests get a Harness-style 404 naming the request. */
+  async fetch(url: string, init: { method: string; signal?: AbortSignal }): Promise<Response> {
+    init.signal?.throwIfAborted();
+    const target = new URL(url);
+    const path = this.basePath && target.pathname.startsWith(`${this.basePath}/`)
+      ? target.pathname.slice(this.basePath.length)
+      : target.pathname;
+    const method = init.method.toUpperCase();
+    // Account scoping is the same on every call; fixtures should not have to repeat it
+    const query = new URLSearchParams(target.search);
+    for (const key of ["accountIdentifier", "routingId", "accountID"]) query.delete(key);
+
+    const mapping = this.mappings.find((m) => matchesRequest(m, method, path, query));
+    if (mapping) return toResponse(mapping);
+    log.debug("No mock fixture for request", { method, path });
+    return toResponse({
+      request: {},
+      response: {
+        status: 404,
+        jsonBody: {
+          status: "ERROR",
+          code: "RESOURCE_NOT_FOUND",
+          message: `Mock mode: no fixture matches ${method} ${path}. Add a mapping for it under HARNESS_MOCK_FIXTURES_DIR, or capture one with \`fixtures record\`.`,
+        },
+      },
+    });
+  }
+}
+
+const instances = new Map<string, MockHarnessApi>();
+
+/**
+ * Shared mock for a fixture directory (or the embedded fixtures alone) and
+ * base URL. Fixture files are read once, not per client.
+ */
+export function mockHarnessApi(fixturesDir: string | undefined, baseUrl: string): MockHarnessApi {
+  const basePath = new URL(baseUrl).pathname.replace(/\/$/, "");
+  const key = `${fixturesDir ?? ""}\n${basePath}`;
+  let api = instances.get(key);
+  if (!api) {
+    const own = fixturesDir ? loadMockFixtureDir(fixturesDir) : [];
+    api = new MockHarnessApi([...own, ...EMBEDDED_MOCK_MAPPINGS], basePath);
+    instances.set(key, api);
+  }
+  return api;
+}
@@ -0,0 +1,129 @@
+/**
+ * Process-wide outbound network settings: DNS answer ordering, happy-eyeballs
+ * (IPv4/IPv6 racing) for every socket the process opens, and a shared DNS
+ * cache for the connections this server opens itself (HTTP/2 sessions). The
+ * global `fetch` path does not use the cache.
+ *
+ * Containers often resolve AAAA records without a working IPv6 route, or sit
+ * behind a slow resolver; both show up as intermittent multi-second stalls on
+ * the first call to a host. Racing the address families and caching answers
+ * (including failures, briefly) removes those stalls.
+ */
+import dns, { type LookupAddress } from "node:dns";
+import net, { type LookupFunction } from "node:net";
+import { createLogger } from "../utils/logger.js";
+
+const log = createLogger("network");
+
+export type DnsResultOrder = "ipv4first" | "ipv6first" | "verbatim";
+
+/** Lookup errors that are answers (the name does not resolve), cached for the negative TTL. */
+const NEGATIVE_CODES = new Set(["ENOTFOUND", "ENODATA", "EAI_AGAIN", "EAI_NONAME"]);
+
+interface CacheEntry {
+  expiresAt: number;
+  addresses?: LookupAddress[];
+  error?: NodeJS.ErrnoException;
+}
+
+export class DnsCache {
+  private entries = new Map<string, CacheEntry>();
+  private pending = new Map<string, Promise<LookupAddress[]>>();
+
+  constructor(
+    private readonly ttlMs: number,
+    private readonly negativeTtlMs: number,
+    private readonly resolver: (hostname: string, family: number) => Promise<LookupAddress[]> =
+      (hostname, family) => dns.promises.lookup(hostname, { all: true, family }),
+  ) {}
+
+  /** All addresses for `hostname` (family 0 = both), from cache when fresh. Concurrent misses share one lookup. */
+  async resolve(hostname: string, family = 0): Promise<LookupAddress[]> {
+    const key = `${family}:${hostname}`;
+    const cached = this.entries.get(key);
+    if (cached && cached.expiresAt > Date.now()) {
+      if (cached.error) throw cached.error;
+      return cached.addresses!;
+    }
+    const inFlight = this.pending.get(key);
+    if (inFlight) return inFlight;
+
+    const lookup = this.resolver(hostname, family).then(
+      (addresses) => {
+        this.entries.set(key, { expiresAt: Date.now() + this.ttlMs, addresses });
+        return addresses;
+      },
+      (err: NodeJS.ErrnoException) => {
+        if (err.code && NEGATIVE_CODES.has(err.code) && this.negativeTtlMs > 0) {
+          this.entries.set(key, { expiresAt: Date.now() + this.negativeTtlMs, error: err });
+        }
+        throw err;
+      },
+    ).finally(() => this.pending.delete(key));
+    this.pending.set(key, lookup);
+    return lookup;
+  }
+
+  /** Drop every cached answer. */
+  clear(): void {
+    this.entries.clear();
+  }
+
+  /** Drop-in `lookup` option for net/tls/http2 connect. */
+  readonly lookup: LookupFunction = (hostname, options, callback) => {
+    const opts = options ?? {};
+    const family = opts.family === "IPv6" ? 6 : opts.family === "IPv4" ? 4 : Number(opts.family ?? 0);
+    this.resolve(hostname, family).then(
+      (addresses) => {
+        if (opts.all) {
+          callback(null, addresses);
+          return;
+        }
+        const first = addresses[0];
+        if (!first) {
+          const err: NodeJS.ErrnoException = new Error(`getaddrinfo ENOTFOUND ${hostname}`);
+          err.code = "ENOTFOUND";
+          callback(err, "");
+          return;
+        }
+        callback(null, first.address, first.family);
+      },
+      (err: NodeJS.ErrnoException) => callback(err, ""),
+    );
+  };
+}
+
+let sharedCache: DnsCache | undefined;
+
+export interface NetworkOptions {
+  /** Cache successful lookups this long; 0 disables the shared cache. */
+  dnsCacheTtlMs: number;
+  /** Cache failed lookups this long. */
+  dnsNegativeTtlMs: number;
+  dnsResultOrder?: DnsResultOrder;
+  /** Per-address attempt timeout while racing IPv4/IPv6; 0 turns happy-eyeballs off. */
+  happyEyeballsTimeoutMs: number;
+}
+
+/** Apply network settings once at startup, before any client connects. */
+export function configureNetwork(options: NetworkOptions): void {
+  if (options.dnsResultOrder) dns.setDefaultResultOrder(options.dnsResultOrder);
+  if (options.happyEyeballsTimeoutMs > 0) {
+    net.setDefaultAutoSelectFamily(true);
+    // Node rejects attempt timeouts under 10 ms
+    net.setDefaultAutoSelectFamilyAttemptTimeout(Math.max(10, options.happyEyeballsTimeoutMs));
+  } else {
+    net.setDefaultAutoSelectFamily(false);
+  }
+  sharedCache = options.dnsCacheTtlMs > 0 ? new DnsCache(options.dnsCacheTtlMs, options.dnsNegativeTtlMs) : undefined;
+  log.debug("Network configured", {
+    dnsCacheTtlMs: options.dnsCacheTtlMs,
+    dnsResultOrder: options.dnsResultOrder ?? "(node default)",
+    happyEyeballsTimeoutMs: options.happyEyeballsTimeoutMs,
+  });
+}
+
+/** The shared cached `lookup`, or undefined when DNS caching is off. */
+export function sharedDnsLookup(): LookupFunction | undefined {
+  return sharedCache?.lookup;
+}
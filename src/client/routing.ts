@@ -0,0 +1,202 @@
+/**
+ * Per-service request routing for non-SaaS gateway layouts.
+ *
+ * Registry paths are written against the SaaS ingress (`/ng/api/...`,
+ * `/pipeline/api/...`). Self-Managed Platform (SMP) installs and custom
+ * reverse proxies sometimes expose the same services under different path
+ * prefixes, or on a different port. A route map rewrites the leading path
+ * prefix — and optionally the port — of each request before it is sent.
+ *
+ * Route map format (HARNESS_ROUTE_MAP, JSON object keyed by path prefix):
+ *   { "/ng/api": "/gateway/ng/api",                       // prefix rewrite
+ *     "/log-service": { "port": 8079 },                    // port override
+ *     "/code/api": { "prefix": "/code/api", "port": 3000 },
+ *     "/sto/api": { "url": "https://sto.{cluster}.example.com" } }  // base URL template
+ *
+ * URL templates may use `{account}` (the account of the request) and
+ * `{cluster}` (HARNESS_CLUSTER, or the first label of the HARNESS_BASE_URL
+ * host, e.g. `app3` for app3.harness.io).
+ */
+
+export interface RouteTarget {
+  /** Replacement for the matched prefix. Default: unchanged. */
+  prefix?: string;
+  /** Port override for the base URL. Default: unchanged. */
+  port?: number;
+  /** Base URL template replacing HARNESS_BASE_URL, e.g. `https://{cluster}-logs.example.com`. */
+  url?: string;
+}
+
+/** Values substituted into route URL templates. */
+export interface RouteVariables {
+  account?: string;
+  cluster?: string;
+}
+
+const TEMPLATE_PLACEHOLDER = /\{(\w+)\}/g;
+const TEMPLATE_VARIABLES = new Set(["account", "cluster"]);
+
+export type RouteMap = Record<string, RouteTarget>;
+
+/**
+ * Built-in SMP layout: the core NextGen services sit behind the `/gateway`
+ * ingress, while Code, GitOps, and log-service keep their SaaS prefixes.
+ */
+export const SMP_ROUTE_PRESET: RouteMap = {
+  "/ng/api": { prefix: "/gateway/ng/api" },
+  "/pipeline/api": { prefix: "/gateway/pipeline/api" },
+  "/template/api": { prefix: "/gateway/template/api" },
+  "/cv/api": { prefix: "/gateway/cv/api" },
+  "/ccm/api": { prefix: "/gateway/ccm/api" },
+  "/sto/api": { prefix: "/gateway/sto/api" },
+  "/iacm/api": { prefix: "/gateway/iacm/api" },
+  "/chaos/manager/api": { prefix: "/gateway/chaos/manager/api" },
+  "/authz/api": { prefix: "/gateway/authz/api" },
+  "/resourcegroup/api": { prefix: "/gateway/resourcegroup/api" },
+  "/audit/api": { prefix: "/gateway/audit/api" },
+  "/pm/api": { prefix: "/gateway/pm/api" },
+};
+
+/**
+ * Normalize HARNESS_BASE_URL to the gateway origin. People often paste the
+ * address bar of the Harness UI (`https://app3.harness.io/ng/account/abc/...`
+ * or `https://vanity.harness.io/ng/#/...`); the UI path and fragment are
+ * dropped so API paths are not appended after them. Other paths (e.g. a
+ * reverse-proxy mount or `/gateway`) are kept.
+ */
+export function normalizeBaseUrl(raw: string): string {
+  let url: URL;
+  try {
+    url = new URL(raw);
+  } catch {
+    return raw.replace(/\/+$/, "");
+  }
+  url.hash = "";
+  url.search = "";
+  url.pathname = url.pathname.replace(/\/ng(\/.*)?$/, "").replace(/\/+$/, "");
+  return url.toString().replace(/\/+$/, "");
+}
+
+/** Default `{cluster}` value: the first DNS label of the base URL host. */
+export function clusterFromBaseUrl(baseUrl: string): string | undefined {
+  try {
+    const host = new URL(baseUrl).hostname;
+    return /^[\d.]+$/.test(host) || host.startsWith("[") ? undefined : host.split(".")[0];
+  } catch {
+    return undefined;
+  }
+}
+
+function normalizePrefix(prefix: string): string {
+  const withSlash = prefix.startsWith("/") ? prefix : `/${prefix}`;
+  return withSlash.replace(/\/+$/, "");
+}
+
+function validateUrlTemplate(key: string, url: unknown): void {
+  if (typeof url !== "string") {
+    throw new Error(`HARNESS_ROUTE_MAP["${key}"].url must be a string.`);
+  }
+  const unknown = [...url.matchAll(TEMPLATE_PLACEHOLDER)].map((m) => m[1]!).filter((name) => !TEMPLATE_VARIABLES.has(name));
+  if (unknown.length > 0) {
+    throw new Error(`HARNESS_ROUTE_MAP["${key}"].url has unknown placeholder(s) ${unknown.map((n) => `{${n}}`).join(", ")}. Supported: {account}, {cluster}.`);
+  }
+  let parsed: URL | undefined;
+  try {
+    parsed = new URL(url.replace(TEMPLATE_PLACEHOLDER, "x"));
+  } catch {
+    parsed = undefined;
+  }
+  if (!parsed || (parsed.protocol !== "https:" && parsed.protocol !== "http:")) {
+    throw new Error(`HARNESS_ROUTE_MAP["${key}"].url must be an http(s) URL template (got "${url}").`);
+  }
+}
+
+/**
+ * Parse a HARNESS_ROUTE_MAP JSON string. Throws with a descriptive message on
+ * malformed input so misconfiguration fails at startup, not on first request.
+ */
+export function parseRouteMap(json: string): RouteMap {
+  let raw: unknown;
+  try {
+    raw = JSON.parse(json);
+  } catch (err) {
+    throw new Error(`HARNESS_ROUTE_MAP is not valid JSON: ${err instanceof Error ? err.message : String(err)}`);
+  }
+  if (typeof raw !== "object" || raw === null || Array.isArray(raw)) {
+    throw new Error("HARNESS_ROUTE_MAP must be a JSON object keyed by path prefix.");
+  }
+
+  const routes: RouteMap = {};
+  for (const [key, value] of Object.entries(raw as Record<string, unknown>)) {
+    const source = normalizePrefix(key);
+    if (typeof value === "string") {
+      routes[source] = { prefix: normalizePrefix(value) };
+      continue;
+    }
+    if (typeof value !== "object" || value === null || Array.isArray(value)) {
+      throw new Error(`HARNESS_ROUTE_MAP["${key}"] must be a prefix string or an object with "prefix" and/or "port".`);
+    }
+    const { prefix, port, url } = value as Record<string, unknown>;
+    if (prefix !== undefined && typeof prefix !== "string") {
+      throw new Error(`HARNESS_ROUTE_MAP["${key}"].prefix must be a string.`);
+    }
+    if (port !== undefined && (!Number.isInteger(port) || (port as number) < 1 || (port as number) > 65535)) {
+      throw new Error(`HARNESS_ROUTE_MAP["${key}"].port must be an integer between 1 and 65535.`);
+    }
+    if (url !== undefined) validateUrlTemplate(key, url);
+    routes[source] = {
+      prefix: prefix !== undefined ? normalizePrefix(prefix) : undefined,
+      port: port as number | undefined,
+      ...(url !== undefined ? { url: (url as string).replace(/\/+$/, "") } : {}),
+    };
+  }
+  return routes;
+}
+
+/**
+ * Combine the deployment preset with user overrides. User entries win for
+ * the same prefix.
+ */
+export function resolveRouteMap(deployment: "saas" | "smp", routeMapJson?: string): RouteMap {
+  const preset = deployment === "smp" ? SMP_ROUTE_PRESET : {};
+  const custom = routeMapJson ? parseRouteMap(routeMapJson) : {};
+  return { ...preset, ...custom };
+}
+
+function expandTemplate(template: string, vars: RouteVariables): string {
+  return template.replace(TEMPLATE_PLACEHOLDER, (placeholder, name: string) => {
+    const value = vars[name as keyof RouteVariables];
+    if (!value) throw new Error(`Cannot resolve ${placeholder} in route URL "${template}". Set HARNESS_CLUSTER or use a base URL with a host name.`);
+    return encodeURIComponent(value);
+  });
+}
+
+/**
+ * Rewrite a request's base URL and path using the longest matching prefix.
+ * Prefixes match whole path segments only (`/ng/api` does not match `/ng/apix`).
+ * When the base URL already ends with the gateway prefix a route adds (e.g.
+ * HARNESS_BASE_URL=https://host/gateway with the SMP preset), the duplicate
+ * prefix is dropped from the path.
+ */
+export function applyRoute(baseUrl: string, path: string, routes: RouteMap, vars: RouteVariables = {}): { baseUrl: string; path: string } {
+  let best: string | undefined;
+  for (const source of Object.keys(routes)) {
+    if ((path === source || path.startsWith(`${source}/`)) && (!best || source.length > best.length)) {
+      best = source;
+    }
+  }
+  if (!best) return { baseUrl, path };
+
+  const target = routes[best]!;
+  const routedBase = target.url !== undefined ? expandTemplate(target.url, vars) : baseUrl;
+  let routedPath = target.prefix !== undefined ? `${target.prefix}${path.slice(best.length)}` : path;
+  const basePath = new URL(routedBase).pathname.replace(/\/$/, "");
+  if (basePath && routedPath.startsWith(`${basePath}/`)) {
+    routedPath = routedPath.slice(basePath.length);
+  }
+  if (target.port === undefined) return { baseUrl: routedBase, path: routedPath };
+
+  const url = new URL(routedBase);
+  url.port = String(target.port);
+  return { baseUrl: url.toString().replace(/\/$/, ""), path: routedPath };
+}
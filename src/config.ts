@@ -1,5 +1,13 @@
 import * as z from "zod/v4";
 import { normalizeHttpAllowedHost } from "./utils/http-hosts.js";
+import { normalizeHttpOrigin } from "./utils/http-cors.js";
+import { normalizeBaseUrl, parseRouteMap } from "./client/routing.js";
+import { parseToolTimeouts } from "./utils/tool-timeouts.js";
+import { parseToolsetPermissions } from "./utils/toolset-permissions.js";
+import { parseRedactPatterns } from "./utils/redact.js";
+import { assertTimeZone } from "./utils/time-format.js";
+import { validateHttpTlsConfig } from "./utils/http-tls.js";
+import { validateHttpJwtConfig } from "./utils/http-jwt.js";
 
 /**
  * Coerce a string env var to a boolean.
@@ -37,6 +45,28 @@ function validateAllowedHosts(rawHosts: string | undefined): string | undefined
   return hosts.join(",");
 }
 
+function validateAllowedOrigins(rawOrigins: string | undefined): string | undefined {
+  if (rawOrigins === undefined) return undefined;
+
+  const origins: string[] = [];
+  const invalidOrigins: string[] = [];
+  for (const value of rawOrigins.split(",")) {
+    const origin = normalizeHttpOrigin(value);
+    if (!origin) {
+      invalidOrigins.push(value.trim());
+    } else if (!origins.includes(origin)) {
+      origins.push(origin);
+    }
+  }
+
+  if (invalidOrigins.length > 0) {
+    const quotedOrigins = invalidOrigins.map((origin) => `"${origin}"`).join(", ");
+    throw new Error(`Invalid HARNESS_MCP_ALLOWED_ORIGINS entries: ${quotedOrigins}. Use origins like "https://app.example.com" or "http://localhost:5173".`);
+  }
+
+  return origins.join(",");
+}
+
 const ACCOUNT_SCOPED_API_KEY_PREFIXES = new Set(["pat", "sat"]);
 
 /**
@@ -78,20 +108,62 @@ const RawConfigSchema = z.object({
   HARNESS_DEFAULT_PROJECT_ID: optionalStringFromEnv,
   HARNESS_API_TIMEOUT_MS: z.coerce.number().default(30000),
   HARNESS_MAX_RETRIES: z.coerce.number().default(3),
+  // JSON object of per-call tool deadlines in ms, keyed by "default", a tool
+  // name, or "tool:resource_type" — e.g. {"default":60000,"harness_get:execution_log":300000}.
+  // Merged over the built-in defaults in src/utils/tool-timeouts.ts; 0 disables a deadline.
+  HARNESS_TOOL_TIMEOUTS: optionalStringFromEnv,
+  HARNESS_REDACT_PATTERNS: optionalStringFromEnv,
+  HARNESS_LOCALIZE_TIMES: booleanFromEnv.default(false),
+  HARNESS_TIMEZONE: optionalStringFromEnv,
+  // harness_get / harness_diagnose results longer than this many characters are delivered in chunks (0 disables)
+  HARNESS_RESULT_CHUNK_CHARS: z.preprocess(emptyStringAsUndefined, z.coerce.number().int().min(0).default(400_000)),
   // Idle HTTP sessions are reaped after this many ms once no request or SSE
   // stream is active. Kept generous (30 min) so interactive clients (e.g. the
   // claude.ai connector, which does not hold a persistent SSE stream between
   // prompts) are not evicted mid-conversation, which surfaces to users as
   // repeated "Session not found" → re-authenticate prompts.
   MCP_SESSION_TTL_MS: z.coerce.number().min(1).default(30 * 60_000),
+  // SSE comment heartbeat on open streams so load balancers don't drop them mid-poll (0 disables)
+  MCP_KEEPALIVE_INTERVAL_MS: z.coerce.number().int().min(0).default(25_000),
+  // Server-initiated MCP ping to sessions holding a GET stream; unanswered pings close the session (0 disables)
+  MCP_PING_INTERVAL_MS: z.coerce.number().int().min(0).default(60_000),
+  MCP_PING_TIMEOUT_MS: z.coerce.number().int().min(1).default(10_000),
   LOG_LEVEL: z.preprocess(
     (val) => (val === "" ? undefined : val),
     z.enum(["debug", "info", "warn", "error"]).default("info"),
   ),
+  LOG_FILE: optionalStringFromEnv,
+  HARNESS_DEBUG_FRAMES: booleanFromEnv.default(false),
   HARNESS_TOOLSETS: optionalStringFromEnv,
+  // Multi-user HTTP: JSON object of toolset name to the {resourceType, permission} a session's caller needs to see it
+  HARNESS_TOOLSET_PERMISSIONS: optionalStringFromEnv,
   HARNESS_MAX_BODY_SIZE_MB: z.coerce.number().default(10),
   HARNESS_RATE_LIMIT_RPS: z.coerce.number().default(10),
+  // Send Harness API calls over pooled HTTP/2 sessions (one per host, kept
+  // warm with PINGs). Hosts without h2 fall back to keep-alive HTTP/1.1.
+  HARNESS_HTTP2: booleanFromEnv.default(false),
+  // Outbound DNS: with HARNESS_HTTP2, answers are cached for the HTTP/2
+  // sessions — successes for DNS_CACHE_TTL_MS (0 disables), failures for
+  // DNS_NEGATIVE_TTL_MS. RESULT_ORDER picks which address family is tried
+  // first; HAPPY_EYEBALLS_MS is the per-address attempt timeout when racing
+  // IPv4 and IPv6 on every connection (0 disables racing).
+  HARNESS_DNS_CACHE_TTL_MS: z.coerce.number().int().min(0).default(30_000),
+  HARNESS_DNS_NEGATIVE_TTL_MS: z.coerce.number().int().min(0).default(2_000),
+  HARNESS_DNS_RESULT_ORDER: z.preprocess(emptyStringAsUndefined, z.enum(["ipv4first", "ipv6first", "verbatim"]).optional()),
+  HARNESS_HAPPY_EYEBALLS_MS: z.coerce.number().int().min(0).default(250),
+  // Offline mock: answer API calls from canned fixtures (embedded demo data,
+  // plus WireMock mappings from MOCK_FIXTURES_DIR) and never contact Harness.
+  // Implies HARNESS_READ_ONLY; no API key is needed.
+  HARNESS_MOCK: booleanFromEnv.default(false),
+  HARNESS_MOCK_FIXTURES_DIR: optionalStringFromEnv,
   HARNESS_READ_ONLY: booleanFromEnv.default(false),
+  HARNESS_STRICT_SCHEMAS: booleanFromEnv.default(false),
+  HARNESS_TWO_PHASE_DELETE: booleanFromEnv.default(false),
+  HARNESS_PERMISSION_PRECHECK: booleanFromEnv.default(false),
+  HARNESS_ACCOUNT_MISMATCH: z.preprocess(emptyStringAsUndefined, z.enum(["reject", "ignore"]).default("reject")),
+  // API call budgets for registry operations (0 = unlimited); once spent, writes are refused and reads fall back to cached responses
+  HARNESS_API_BUDGET_PER_SESSION: z.preprocess(emptyStringAsUndefined, z.coerce.number().int().min(0).default(0)),
+  HARNESS_API_BUDGET_PER_HOUR: z.preprocess(emptyStringAsUndefined, z.coerce.number().int().min(0).default(0)),
   HARNESS_SKIP_ELICITATION: booleanFromEnv.default(false),
   HARNESS_AUTO_APPROVE_RISK: z.preprocess(
     emptyStringAsUndefined,
@@ -99,7 +171,22 @@ const RawConfigSchema = z.object({
   ),
   HARNESS_ALLOW_HTTP: booleanFromEnv.default(false),
   HARNESS_MCP_ALLOWED_HOSTS: optionalStringFromEnv.transform(validateAllowedHosts),
+  HARNESS_MCP_ALLOWED_ORIGINS: optionalStringFromEnv.transform(validateAllowedOrigins),
   HARNESS_MCP_AUTH_TOKEN: optionalStringFromEnv,
+  // Separate credential for /admin/debug; required for it in multi-user mode
+  HARNESS_MCP_ADMIN_TOKEN: z.preprocess(emptyStringAsUndefined, z.string().min(16).optional()),
+  // PEM file paths for TLS on the HTTP transport; a CA enables client certificate verification (mTLS)
+  HARNESS_MCP_TLS_CERT: optionalStringFromEnv,
+  HARNESS_MCP_TLS_KEY: optionalStringFromEnv,
+  HARNESS_MCP_TLS_CA: optionalStringFromEnv,
+  // JWT bearer validation for the HTTP transport: HMAC secret or PEM public key path,
+  // expected issuer/audience (comma-separated), clock-skew leeway, and allowed algorithms
+  HARNESS_MCP_JWT_SECRET: optionalStringFromEnv,
+  HARNESS_MCP_JWT_PUBLIC_KEY: optionalStringFromEnv,
+  HARNESS_MCP_JWT_ISSUER: optionalStringFromEnv,
+  HARNESS_MCP_JWT_AUDIENCE: optionalStringFromEnv,
+  HARNESS_MCP_JWT_LEEWAY_SECONDS: z.preprocess(emptyStringAsUndefined, z.coerce.number().int().min(0).max(600).default(30)),
+  HARNESS_MCP_JWT_ALGORITHMS: optionalStringFromEnv,
   HARNESS_MCP_ALLOW_UNAUTHENTICATED_HTTP: booleanFromEnv.default(false),
   // Number of proxy hops to trust for client IP resolution (Express `trust
   // proxy`). Set to the count of reverse proxies / load balancers in front of
@@ -137,6 +224,80 @@ const RawConfigSchema = z.object({
   // JSON object, e.g.: {"Authorization":"Bearer tok"} or {"x-api-key":"key","x-harness-token":"svc"}
   // Supports any auth scheme: Bearer tokens, API keys, internal service-to-service headers.
   HARNESS_SEARCH_SERVICE_HEADERS: optionalStringFromEnv,
+  // How compacted harness_list output treats fields outside the compaction
+  // whitelist: "strip" drops them (default, smallest responses); "include"
+  // keeps them under `_extra` for parity with the raw Harness API payload.
+  HARNESS_UNKNOWN_FIELDS: z.preprocess(
+    emptyStringAsUndefined,
+    z.enum(["strip", "include"]).default("strip"),
+  ),
+  // Directory for the persistent disk cache (connector catalogue, live entity
+  // YAML schemas). Unset disables disk caching. Use a persistent volume so
+  // restarts skip refetching slow-changing catalog data.
+  HARNESS_CACHE_DIR: optionalStringFromEnv,
+  // Directory where tools write generated files (cost exports). Results point
+  // at the file with a resource_link. Unset disables file-producing tools.
+  HARNESS_OUTPUT_DIR: optionalStringFromEnv,
+  // Downloads larger than this many MB (e.g. execution log archives) spill to
+  // a temp file in HARNESS_OUTPUT_DIR instead of staying in memory.
+  HARNESS_SPILL_THRESHOLD_MB: z.coerce.number().positive().default(1),
+  // Hard cap on a single spilled download; reading stops there and the
+  // partial file is deleted rather than kept.
+  HARNESS_SPILL_MAX_MB: z.coerce.number().positive().default(256),
+  // Largest JSON-RPC message accepted on the stdio and socket transports;
+  // bigger frames are rejected and their bytes discarded.
+  HARNESS_STDIO_MAX_FRAME_MB: z.coerce.number().positive().default(64),
+  // Background refresh interval for the tier-1 entity catalog (pipelines,
+  // services, environments, connectors) used by harness_search. 0 disables
+  // refresh — entities are indexed once at startup and expire after 30 minutes.
+  // Values below 60000 are raised to 60000. Single-user mode only.
+  HARNESS_ENTITY_REFRESH_INTERVAL_MS: z.preprocess(
+    emptyStringAsUndefined,
+    z.coerce.number().int().min(0).default(0),
+  ),
+  // Harness deployment layout. "smp" applies the Self-Managed Platform
+  // gateway routing preset (see src/client/routing.ts); also set by --smp.
+  HARNESS_DEPLOYMENT: z.preprocess(
+    emptyStringAsUndefined,
+    z.enum(["saas", "smp"]).default("saas"),
+  ),
+  // JSON object of per-service path prefix rewrites and port overrides, merged
+  // over the deployment preset. E.g. {"/ng/api":"/gateway/ng/api","/log-service":{"port":8079}}
+  HARNESS_ROUTE_MAP: optionalStringFromEnv,
+  // Value of {cluster} in HARNESS_ROUTE_MAP URL templates. Defaults to the
+  // first label of the HARNESS_BASE_URL host (e.g. "app3" for app3.harness.io).
+  HARNESS_CLUSTER: optionalStringFromEnv,
+  // Harness UI origin for openInHarness links in tool results, e.g. a vanity
+  // domain when HARNESS_BASE_URL points at an API gateway or proxy. Defaults
+  // to HARNESS_BASE_URL.
+  HARNESS_UI_URL: z.preprocess(emptyStringAsUndefined, z.string().url().optional()),
+  // Probe module APIs at startup and hide toolsets whose endpoints return 404
+  // (e.g. modules not deployed on a self-managed Harness install). Adds a few
+  // list calls to startup, bounded by HARNESS_CAPABILITY_PROBE_TIMEOUT_MS.
+  // Single-user mode only.
+  HARNESS_CAPABILITY_PROBE: booleanFromEnv.default(false),
+  HARNESS_CAPABILITY_PROBE_TIMEOUT_MS: z.preprocess(
+    emptyStringAsUndefined,
+    z.coerce.number().int().min(1000).default(10_000),
+  ),
+  // Harness Feature Flags server SDK key. When set, toolsets and resource
+  // types that declare a featureFlag are only shown to accounts the flag is
+  // on for; flags are polled every HARNESS_FF_POLL_INTERVAL_MS.
+  HARNESS_FF_SDK_KEY: optionalStringFromEnv,
+  HARNESS_FF_BASE_URL: urlFromEnv("https://config.ff.harness.io/api/1.0"),
+  HARNESS_FF_POLL_INTERVAL_MS: z.preprocess(
+    emptyStringAsUndefined,
+    z.coerce.number().int().min(10_000).default(60_000),
+  ),
+  // NATS server carrying Harness events (nats://[user:pass@]host:4222 or
+  // tls://...). When set, execution-finished and approval events for the
+  // session's account are pushed to MCP clients as notifications.
+  HARNESS_EVENTS_NATS_URL: z.preprocess(emptyStringAsUndefined, z.string().url().optional()),
+  // Comma-separated NATS subjects to subscribe to (wildcards allowed).
+  HARNESS_EVENTS_SUBJECTS: z.preprocess(emptyStringAsUndefined, z.string().default("harness.events.>")),
+  // Shared secret for the Harness notification webhook receiver (HTTP
+  // transport, POST /webhooks/harness). Unset disables the route.
+  HARNESS_WEBHOOK_SECRET: z.preprocess(emptyStringAsUndefined, z.string().min(16).optional()),
   // Directory for @huggingface/transformers model cache (local search provider).
   // Use a persistent volume in production; Docker image bakes models into /app/.cache/hf.
   HARNESS_HF_CACHE_DIR: z.preprocess(
@@ -145,9 +306,16 @@ const RawConfigSchema = z.object({
   ),
 });
 
+/** Stand-in credential for mock mode so single-user config validates without an API key. Never sent anywhere. */
+const MOCK_PLACEHOLDER_API_KEY = "pat.mock.offline.placeholder";
+
 export const ConfigSchema = RawConfigSchema.transform((data) => {
   const isMultiUser = data.HARNESS_MCP_MODE === "multi-user";
 
+  if (data.HARNESS_MOCK && !isMultiUser && !data.HARNESS_API_KEY) {
+    data = { ...data, HARNESS_API_KEY: MOCK_PLACEHOLDER_API_KEY };
+  }
+
   if (isMultiUser && data.HARNESS_API_KEY) {
     throw new Error(
       "HARNESS_API_KEY must not be set in multi-user mode. " +
@@ -187,6 +355,37 @@ export const ConfigSchema = RawConfigSchema.transform((data) => {
     );
   }
 
+  if (data.HARNESS_DEPLOYMENT === "smp" && data.HARNESS_BASE_URL.replace(/\/$/, "") === "https://app.harness.io") {
+    throw new Error(
+      "HARNESS_DEPLOYMENT=smp (or --smp) requires HARNESS_BASE_URL to point at your Self-Managed Platform, " +
+      "e.g. HARNESS_BASE_URL=https://harness.example.com.",
+    );
+  }
+
+  if (data.HARNESS_ROUTE_MAP) {
+    // Fail fast on a malformed map rather than on the first API call
+    parseRouteMap(data.HARNESS_ROUTE_MAP);
+  }
+
+  if (data.HARNESS_TOOL_TIMEOUTS) {
+    parseToolTimeouts(data.HARNESS_TOOL_TIMEOUTS);
+  }
+
+  if (data.HARNESS_TOOLSET_PERMISSIONS) {
+    parseToolsetPermissions(data.HARNESS_TOOLSET_PERMISSIONS);
+  }
+
+  if (data.HARNESS_REDACT_PATTERNS) {
+    parseRedactPatterns(data.HARNESS_REDACT_PATTERNS);
+  }
+
+  if (data.HARNESS_TIMEZONE) {
+    assertTimeZone(data.HARNESS_TIMEZONE);
+  }
+
+  validateHttpTlsConfig(data);
+  validateHttpJwtConfig(data);
+
   if (data.HARNESS_FME_BASE_URL && !data.HARNESS_FME_BASE_URL.startsWith("https://") && !data.HARNESS_ALLOW_HTTP) {
     throw new Error(
       `HARNESS_FME_BASE_URL must use HTTPS (got "${data.HARNESS_FME_BASE_URL}"). ` +
@@ -194,6 +393,13 @@ export const ConfigSchema = RawConfigSchema.transform((data) => {
     );
   }
 
+  if (data.HARNESS_FF_SDK_KEY && !data.HARNESS_FF_BASE_URL.startsWith("https://") && !data.HARNESS_ALLOW_HTTP) {
+    throw new Error(
+      `HARNESS_FF_BASE_URL must use HTTPS (got "${data.HARNESS_FF_BASE_URL}"). ` +
+      "If you need HTTP for local development, set HARNESS_ALLOW_HTTP=true.",
+    );
+  }
+
   if (data.HARNESS_AUDIT_WEBHOOK_URL && !data.HARNESS_AUDIT_WEBHOOK_URL.startsWith("https://") && !data.HARNESS_ALLOW_HTTP) {
     throw new Error(
       `HARNESS_AUDIT_WEBHOOK_URL must use HTTPS (got "${data.HARNESS_AUDIT_WEBHOOK_URL}"). ` +
@@ -224,11 +430,22 @@ export const ConfigSchema = RawConfigSchema.transform((data) => {
   // Remove deprecated keys from output, expose only the canonical names
   const { HARNESS_DEFAULT_ORG_ID: _oldOrg, HARNESS_DEFAULT_PROJECT_ID: _oldProject, ...rest } = data;
 
-  return { ...rest, HARNESS_API_KEY: data.HARNESS_API_KEY ?? "", HARNESS_ACCOUNT_ID: accountId, HARNESS_ORG, HARNESS_PROJECT, HARNESS_AUTO_APPROVE_RISK };
+  return { ...rest, HARNESS_BASE_URL: normalizeBaseUrl(data.HARNESS_BASE_URL), HARNESS_UI_URL: data.HARNESS_UI_URL ? normalizeBaseUrl(data.HARNESS_UI_URL) : undefined, HARNESS_API_KEY: data.HARNESS_API_KEY ?? "", HARNESS_ACCOUNT_ID: accountId, HARNESS_ORG, HARNESS_PROJECT, HARNESS_AUTO_APPROVE_RISK, HARNESS_READ_ONLY: data.HARNESS_READ_ONLY || data.HARNESS_MOCK };
 });
 
 export type Config = z.infer<typeof ConfigSchema>;
 
+/**
+ * Every environment variable the config schema reads, with its default when
+ * one applies. Derived from the schema so `config env` cannot drift from it.
+ */
+export function configEnvFields(): Array<{ name: string; defaultValue?: unknown }> {
+  return Object.entries(RawConfigSchema.shape).map(([name, schema]) => {
+    const parsed = (schema as z.ZodType).safeParse(undefined);
+    return parsed.success && parsed.data !== undefined ? { name, defaultValue: parsed.data } : { name };
+  });
+}
+
 /**
  * Some integrations use literal placeholder credentials (for example "dummy").
  * Those placeholders are not valid external Split/FME API credentials.
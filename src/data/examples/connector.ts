@@ -0,0 +1,338 @@
+import { registerExamples } from "./index.js";
+import type { ResourceExample } from "./types.js";
+
+/**
+ * Skeleton connector specs, one per common connector type. The `connector`
+ * block maps directly onto the harness_create(resource_type='connector') body.
+ * Replace <angle-bracket> placeholders; `*Ref` fields take secret references
+ * (e.g. account.github_pat).
+ */
+const examples: ResourceExample[] = [
+  {
+    name: "connector-k8s-cluster",
+    resourceType: "connector",
+    description: "Kubernetes cluster connector inheriting credentials from a delegate running in the cluster",
+    tags: ["K8sCluster", "kubernetes", "k8s", "cloud_provider", "delegate"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: K8sCluster
+  spec:
+    credential:
+      type: InheritFromDelegate
+    delegateSelectors:
+      - <delegate-selector>`,
+  },
+  {
+    name: "connector-k8s-cluster-manual",
+    resourceType: "connector",
+    description: "Kubernetes cluster connector with a master URL and service account token",
+    tags: ["K8sCluster", "kubernetes", "k8s", "cloud_provider", "service account"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: K8sCluster
+  spec:
+    credential:
+      type: ManualConfig
+      spec:
+        masterUrl: https://<cluster-api-server>
+        auth:
+          type: ServiceAccount
+          spec:
+            serviceAccountTokenRef: <secret-ref>
+            caCertRef: <secret-ref>
+    delegateSelectors:
+      - <delegate-selector>`,
+  },
+  {
+    name: "connector-github",
+    resourceType: "connector",
+    description: "GitHub account connector using a personal access token for clone and API access",
+    tags: ["Github", "git", "code_repo", "scm", "token"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Github
+  spec:
+    url: https://github.com/<org>
+    validationRepo: <repo>
+    type: Account
+    authentication:
+      type: Http
+      spec:
+        type: UsernameToken
+        spec:
+          username: <username>
+          tokenRef: <secret-ref>
+    apiAccess:
+      type: Token
+      spec:
+        tokenRef: <secret-ref>
+    executeOnDelegate: false`,
+  },
+  {
+    name: "connector-gitlab",
+    resourceType: "connector",
+    description: "GitLab account connector using a personal access token",
+    tags: ["Gitlab", "git", "code_repo", "scm", "token"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Gitlab
+  spec:
+    url: https://gitlab.com/<group>
+    validationRepo: <repo>
+    type: Account
+    authentication:
+      type: Http
+      spec:
+        type: UsernameToken
+        spec:
+          username: <username>
+          tokenRef: <secret-ref>
+    apiAccess:
+      type: Token
+      spec:
+        tokenRef: <secret-ref>
+    executeOnDelegate: false`,
+  },
+  {
+    name: "connector-bitbucket",
+    resourceType: "connector",
+    description: "Bitbucket account connector using an app password",
+    tags: ["Bitbucket", "git", "code_repo", "scm"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Bitbucket
+  spec:
+    url: https://bitbucket.org/<workspace>
+    validationRepo: <repo>
+    type: Account
+    authentication:
+      type: Http
+      spec:
+        type: UsernamePassword
+        spec:
+          username: <username>
+          passwordRef: <secret-ref>
+    apiAccess:
+      type: UsernameToken
+      spec:
+        username: <username>
+        tokenRef: <secret-ref>
+    executeOnDelegate: false`,
+  },
+  {
+    name: "connector-git",
+    resourceType: "connector",
+    description: "Generic Git connector for a single repository over HTTPS",
+    tags: ["Git", "git", "code_repo", "scm", "https"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Git
+  spec:
+    url: https://<git-host>/<org>/<repo>.git
+    connectionType: Repo
+    type: Http
+    spec:
+      username: <username>
+      passwordRef: <secret-ref>
+    executeOnDelegate: true
+    delegateSelectors:
+      - <delegate-selector>`,
+  },
+  {
+    name: "connector-docker-registry",
+    resourceType: "connector",
+    description: "Docker registry connector (Docker Hub or any v2 registry) with username/password",
+    tags: ["DockerRegistry", "docker", "artifactory", "container", "registry"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: DockerRegistry
+  spec:
+    dockerRegistryUrl: https://index.docker.io/v2/
+    providerType: DockerHub
+    auth:
+      type: UsernamePassword
+      spec:
+        username: <username>
+        passwordRef: <secret-ref>
+    executeOnDelegate: false`,
+  },
+  {
+    name: "connector-aws",
+    resourceType: "connector",
+    description: "AWS connector with an access key and secret key",
+    tags: ["Aws", "aws", "cloud_provider", "access key"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Aws
+  spec:
+    credential:
+      type: ManualConfig
+      spec:
+        accessKey: <access-key-id>
+        secretKeyRef: <secret-ref>
+      region: us-east-1
+    executeOnDelegate: false`,
+  },
+  {
+    name: "connector-gcp",
+    resourceType: "connector",
+    description: "GCP connector with a service account key stored as a secret",
+    tags: ["Gcp", "gcp", "google", "cloud_provider", "service account"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Gcp
+  spec:
+    credential:
+      type: ManualConfig
+      spec:
+        secretKeyRef: <secret-ref>
+    executeOnDelegate: false`,
+  },
+  {
+    name: "connector-azure",
+    resourceType: "connector",
+    description: "Azure connector with an app registration (client ID, tenant ID, client secret)",
+    tags: ["Azure", "azure", "cloud_provider", "service principal"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Azure
+  spec:
+    credential:
+      type: ManualConfig
+      spec:
+        applicationId: <application-id>
+        tenantId: <tenant-id>
+        auth:
+          type: Secret
+          spec:
+            secretRef: <secret-ref>
+    azureEnvironmentType: AZURE
+    executeOnDelegate: false`,
+  },
+  {
+    name: "connector-artifactory",
+    resourceType: "connector",
+    description: "JFrog Artifactory connector with username/password",
+    tags: ["Artifactory", "jfrog", "artifactory", "artifact"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Artifactory
+  spec:
+    artifactoryServerUrl: https://<company>.jfrog.io/artifactory
+    auth:
+      type: UsernamePassword
+      spec:
+        username: <username>
+        passwordRef: <secret-ref>
+    executeOnDelegate: false`,
+  },
+  {
+    name: "connector-http-helm-repo",
+    resourceType: "connector",
+    description: "HTTP Helm chart repository connector with anonymous access",
+    tags: ["HttpHelmRepo", "helm", "artifactory", "charts"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: HttpHelmRepo
+  spec:
+    helmRepoUrl: https://<charts-host>
+    auth:
+      type: Anonymous`,
+  },
+  {
+    name: "connector-jira",
+    resourceType: "connector",
+    description: "Jira connector with an account email and API token",
+    tags: ["Jira", "jira", "ticketing", "atlassian"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Jira
+  spec:
+    jiraUrl: https://<company>.atlassian.net/
+    auth:
+      type: UsernamePassword
+      spec:
+        username: <email>
+        passwordRef: <secret-ref>`,
+  },
+  {
+    name: "connector-servicenow",
+    resourceType: "connector",
+    description: "ServiceNow connector with username/password",
+    tags: ["ServiceNow", "servicenow", "ticketing", "itsm"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: ServiceNow
+  spec:
+    serviceNowUrl: https://<instance>.service-now.com/
+    auth:
+      type: UsernamePassword
+      spec:
+        username: <username>
+        passwordRef: <secret-ref>`,
+  },
+  {
+    name: "connector-vault",
+    resourceType: "connector",
+    description: "HashiCorp Vault secret manager connector with token auth on a KV v2 engine",
+    tags: ["Vault", "vault", "hashicorp", "secret_manager"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Vault
+  spec:
+    vaultUrl: https://<vault-host>:8200
+    basePath: harness
+    accessType: TOKEN
+    authToken: <secret-ref>
+    secretEngineName: secret
+    secretEngineVersion: 2
+    renewalIntervalMinutes: 10
+    default: false`,
+  },
+  {
+    name: "connector-prometheus",
+    resourceType: "connector",
+    description: "Prometheus monitoring connector for continuous verification",
+    tags: ["Prometheus", "prometheus", "monitoring", "verification"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Prometheus
+  spec:
+    url: http://<prometheus-host>:9090/
+    delegateSelectors:
+      - <delegate-selector>`,
+  },
+  {
+    name: "connector-datadog",
+    resourceType: "connector",
+    description: "Datadog monitoring connector with API and application keys",
+    tags: ["Datadog", "datadog", "monitoring", "verification"],
+    yaml: `connector:
+  name: <name>
+  identifier: <identifier>
+  type: Datadog
+  spec:
+    url: https://app.datadoghq.com/api/
+    apiKeyRef: <secret-ref>
+    applicationKeyRef: <secret-ref>`,
+  },
+];
+
+registerExamples(examples);
+export default examples;
@@ -2,3 +2,4 @@
 // Import this file once at startup to ensure examples are available.
 import "./pipeline.js";
 import "./pipeline-v1.js";
+import "./connector.js";
@@ -0,0 +1,230 @@
+import type { MockMapping } from "../client/mock-api.js";
+
+/**
+ * Embedded demo account for mock mode: one org (`default`) with one project
+ * (`demo`), two pipelines and their recent executions, a service, two
+ * environments, and two connectors. List fixtures ignore scope query params,
+ * so any org/project shows the same demo data. Everything here is made up.
+ */
+
+const ORG = "default";
+const PROJECT = "demo";
+const CREATED = 1_735_689_600_000; // 2025-01-01T00:00:00Z
+const scope = { orgIdentifier: ORG, projectIdentifier: PROJECT };
+
+const ok = (data: unknown) => ({ status: 200, jsonBody: { status: "SUCCESS", data, metaData: null, correlationId: "mock" } });
+const page = (content: unknown[]) => ok({
+  content,
+  totalElements: content.length,
+  totalItems: content.length,
+  totalPages: 1,
+  pageIndex: 0,
+  pageSize: Math.max(content.length, 20),
+  empty: content.length === 0,
+});
+
+const organization = { identifier: ORG, name: "Default", description: "Demo organization", tags: {} };
+const project = { ...scope, identifier: PROJECT, name: "Demo", color: "#0063F7", modules: ["CD", "CI"], description: "Mock-mode demo project", tags: {} };
+
+const pipelines = [
+  {
+    ...scope,
+    identifier: "build_and_test",
+    name: "Build and Test",
+    description: "Builds the web app and runs unit tests",
+    tags: { team: "web" },
+    storeType: "INLINE",
+    stageCount: 1,
+    stageNames: ["Build"],
+    modules: ["ci"],
+    createdAt: CREATED,
+    lastUpdatedAt: CREATED + 86_400_000,
+    executionSummaryInfo: { lastExecutionStatus: "Success", lastExecutionId: "exec_build_1", lastExecutionTs: CREATED + 172_800_000 },
+    yaml: `pipeline:
+  name: Build and Test
+  identifier: build_and_test
+  orgIdentifier: ${ORG}
+  projectIdentifier: ${PROJECT}
+  properties:
+    ci:
+      codebase:
+        connectorRef: github_demo
+        repoName: web-app
+        build: <+input>
+  stages:
+    - stage:
+        name: Build
+        identifier: Build
+        type: CI
+        spec:
+          cloneCodebase: true
+          platform:
+            os: Linux
+            arch: Amd64
+          runtime:
+            type: Cloud
+            spec: {}
+          execution:
+            steps:
+              - step:
+                  type: Run
+                  name: Unit Tests
+                  identifier: Unit_Tests
+                  spec:
+                    shell: Sh
+                    command: npm ci && npm test
+`,
+  },
+  {
+    ...scope,
+    identifier: "deploy_prod",
+    name: "Deploy to Production",
+    description: "Rolling deployment of the web service to production",
+    tags: { team: "web" },
+    storeType: "INLINE",
+    stageCount: 1,
+    stageNames: ["Deploy"],
+    modules: ["cd"],
+    createdAt: CREATED,
+    lastUpdatedAt: CREATED + 86_400_000,
+    executionSummaryInfo: { lastExecutionStatus: "Failed", lastExecutionId: "exec_deploy_1", lastExecutionTs: CREATED + 259_200_000 },
+    yaml: `pipeline:
+  name: Deploy to Production
+  identifier: deploy_prod
+  orgIdentifier: ${ORG}
+  projectIdentifier: ${PROJECT}
+  stages:
+    - stage:
+        name: Deploy
+        identifier: Deploy
+        type: Deployment
+        spec:
+          deploymentType: Kubernetes
+          service:
+            serviceRef: web
+          environment:
+            environmentRef: production
+            infrastructureDefinitions:
+              - identifier: prod_cluster
+          execution:
+            steps:
+              - step:
+                  type: K8sRollingDeploy
+                  name: Rolling Deploy
+                  identifier: Rolling_Deploy
+                  spec:
+                    skipDryRun: false
+                  timeout: 10m
+`,
+  },
+];
+
+const executions = [
+  {
+    ...scope,
+    planExecutionId: "exec_deploy_1",
+    pipelineIdentifier: "deploy_prod",
+    name: "Deploy to Production",
+    runSequence: 12,
+    status: "Failed",
+    startTs: CREATED + 259_200_000,
+    endTs: CREATED + 259_380_000,
+    executionTriggerInfo: { triggerType: "MANUAL", triggeredBy: { identifier: "Demo User" } },
+    failureInfo: { message: "Deployment exceeded its progress deadline: deployment \"web\" has 0/3 ready pods" },
+    moduleInfo: { cd: { serviceIdentifiers: ["web"], envIdentifiers: ["production"] } },
+  },
+  {
+    ...scope,
+    planExecutionId: "exec_build_1",
+    pipelineIdentifier: "build_and_test",
+    name: "Build and Test",
+    runSequence: 57,
+    status: "Success",
+    startTs: CREATED + 172_800_000,
+    endTs: CREATED + 173_100_000,
+    executionTriggerInfo: { triggerType: "WEBHOOK", triggeredBy: { identifier: "GitHub" } },
+    moduleInfo: { ci: { branch: "main", ciExecutionInfoDTO: { author: { name: "Demo User" } } } },
+  },
+];
+
+const service = {
+  ...scope,
+  identifier: "web",
+  name: "web",
+  description: "Customer-facing web service",
+  tags: { team: "web" },
+  yaml: `service:
+  name: web
+  identifier: web
+  serviceDefinition:
+    type: Kubernetes
+    spec:
+      manifests:
+        - manifest:
+            identifier: web_manifests
+            type: K8sManifest
+            spec:
+              store:
+                type: Github
+                spec:
+                  connectorRef: github_demo
+                  repoName: web-app
+                  branch: main
+                  paths:
+                    - deploy/k8s
+`,
+};
+
+const environments = [
+  { ...scope, identifier: "staging", name: "Staging", type: "PreProduction", description: "Pre-production environment", tags: {} },
+  { ...scope, identifier: "production", name: "Production", type: "Production", description: "Production environment", tags: {} },
+];
+
+const connectors = [
+  {
+    ...scope,
+    identifier: "github_demo",
+    name: "GitHub (demo)",
+    type: "Github",
+    spec: { url: "https://github.com/example", type: "Account", authentication: { type: "Http" } },
+  },
+  {
+    ...scope,
+    identifier: "prod_cluster",
+    name: "Production cluster",
+    type: "K8sCluster",
+    spec: { credential: { type: "InheritFromDelegate" }, delegateSelectors: ["prod-delegate"] },
+  },
+];
+
+const get = (urlPath: string, response: MockMapping["response"]): MockMapping => ({ request: { method: "GET", urlPath }, response });
+const post = (urlPath: string, response: MockMapping["response"]): MockMapping => ({ request: { method: "POST", urlPath }, response });
+
+export const EMBEDDED_MOCK_MAPPINGS: MockMapping[] = [
+  get("/ng/api/user/currentUser", ok({ uuid: "mock-user-uuid", name: "Demo User", email: "demo.user@example.com" })),
+
+  get("/ng/api/organizations", page([{ organization, createdAt: CREATED, lastModifiedAt: CREATED }])),
+  get(`/ng/api/organizations/${ORG}`, ok({ organization, createdAt: CREATED, lastModifiedAt: CREATED })),
+  get("/ng/api/projects", page([{ project, createdAt: CREATED, lastModifiedAt: CREATED }])),
+  get(`/ng/api/projects/${PROJECT}`, ok({ project, createdAt: CREATED, lastModifiedAt: CREATED })),
+
+  post("/pipeline/api/pipelines/list", page(pipelines.map(({ yaml: _yaml, ...summary }) => summary))),
+  ...pipelines.map((p) => get(`/pipeline/api/pipelines/${p.identifier}`, ok({
+    yamlPipeline: p.yaml,
+    entityValidityDetails: { valid: true },
+    modules: p.modules,
+    storeType: p.storeType,
+  }))),
+
+  post("/pipeline/api/pipelines/execution/summary", page(executions)),
+  ...executions.map((e) => get(`/pipeline/api/pipelines/execution/v2/${e.planExecutionId}`, ok({ pipelineExecutionSummary: e }))),
+
+  get("/ng/api/servicesV2", page([{ service, createdAt: CREATED, lastModifiedAt: CREATED }])),
+  get(`/ng/api/servicesV2/${service.identifier}`, ok({ service, createdAt: CREATED, lastModifiedAt: CREATED })),
+
+  get("/ng/api/environmentsV2", page(environments.map((environment) => ({ environment, createdAt: CREATED, lastModifiedAt: CREATED })))),
+  ...environments.map((environment) => get(`/ng/api/environmentsV2/${environment.identifier}`, ok({ environment, createdAt: CREATED, lastModifiedAt: CREATED }))),
+
+  post("/ng/api/connectors/listV2", page(connectors.map((connector) => ({ connector, status: { status: "SUCCESS" }, createdAt: CREATED })))),
+  ...connectors.map((connector) => get(`/ng/api/connectors/${connector.identifier}`, ok({ connector, status: { status: "SUCCESS" }, createdAt: CREATED }))),
+];
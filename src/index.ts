@@ -2,32 +2,82 @@
 
 import { randomUUID } from "node:crypto";
 import { appendFileSync } from "node:fs";
+import type { IncomingHttpHeaders } from "node:http";
+import { createServer as createHttpsServer } from "node:https";
 import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
-import { StdioServerTransport } from "@modelcontextprotocol/sdk/server/stdio.js";
 import { StreamableHTTPServerTransport } from "@modelcontextprotocol/sdk/server/streamableHttp.js";
-import { json } from "express";
+import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import type { CallToolResult } from "@modelcontextprotocol/sdk/types.js";
+import { json, type Response } from "express";
 import { loadConfig, type Config } from "./config.js";
-import { setLogLevel, createLogger } from "./utils/logger.js";
+import { setLogLevel, createLogger, configureLogOutput, guardStdout } from "./utils/logger.js";
+import { inFlightSnapshot, installDiagnosticsSignal, toggleAndDump } from "./utils/live-diagnostics.js";
+import { attachFrameDump } from "./utils/frame-dump.js";
+import { attachProtocolAdapter } from "./utils/protocol-version.js";
+import { attachMcpLogging } from "./utils/mcp-logging.js";
 import { HarnessClient } from "./client/harness-client.js";
-import { Registry } from "./registry/index.js";
+import { ALL_TOOLSET_NAMES, Registry } from "./registry/index.js";
+import { probePlatformCapabilities, type PlatformCapabilities } from "./registry/capabilities.js";
+import { FeatureFlagGate } from "./client/feature-flags.js";
 import { registerAllTools } from "./tools/index.js";
 import { registerAllResources } from "./resources/index.js";
 import { registerAllPrompts } from "./prompts/index.js";
-import { parseArgs, resolvePort, getVersion } from "./utils/cli.js";
+import { createArgumentCompleter } from "./utils/completions.js";
+import { assertTransportSupportsMode, parseArgs, resolvePort, getVersion } from "./utils/cli.js";
+import { renderConfigEnv } from "./utils/config-env.js";
+import { generateCompletions } from "./utils/cli-completions.js";
+import { callServerTool, listServerTools, loadCatalogConfig, renderCallResult, renderToolList, renderToolSchema, withInMemoryClient } from "./utils/tool-catalog.js";
+import { FixtureRecorder, runReadTools, writeFixtureFiles } from "./utils/fixture-recorder.js";
+import { renderSelfTestReport, runConnectivityChecks, runProtocolChecks, selfTestPassed } from "./utils/self-test.js";
 import { configureElicitation } from "./utils/elicitation.js";
+import { configureToolTimeouts, parseToolTimeouts } from "./utils/tool-timeouts.js";
+import { configureRedaction, parseRedactPatterns } from "./utils/redact.js";
+import { configureStrictSchemas } from "./utils/strict-schemas.js";
+import { configureTimeFormatting } from "./utils/time-format.js";
+import { configureResultChunking } from "./utils/result-chunks.js";
+import { configureSpillBuffers } from "./utils/spill-buffer.js";
+import { configureNetwork } from "./client/network.js";
+import { closeHttp2Pools } from "./client/http2-pool.js";
+import { StdioTransport } from "./utils/stdio-transport.js";
+import { createCorsMiddleware, resolveAllowedOrigins } from "./utils/http-cors.js";
+import { loadHttpTlsOptions } from "./utils/http-tls.js";
+import { attachHarnessEvents, startHarnessEventConsumer } from "./utils/harness-events.js";
+import { createGrpcServer, GrpcError, GrpcStatus, TOOL_DISPATCH_SERVICE } from "./utils/grpc-server.js";
+import { loadJwtValidationOptions } from "./utils/http-jwt.js";
+import { attachPrincipal, principalFromClaims, runWithPrincipal } from "./utils/principal.js";
+import { hiddenToolsetsFor, parseToolsetPermissions } from "./utils/toolset-permissions.js";
+import { recordServerTransport } from "./utils/server-metrics.js";
+import { TOOL_OUTPUT_SCHEMAS } from "./tools/output-schemas.js";
 import { resolveHttpHostValidationOptions } from "./utils/http-hosts.js";
-import { createHttpAuthMiddleware, validateHttpAuthForBindHost } from "./utils/http-auth.js";
+import { adminRoutesEnabled, authenticateHttpHeaders, createAdminAuthMiddleware, createHttpAuthMiddleware, validateHttpAuthForBindHost } from "./utils/http-auth.js";
+import { createHarnessWebhookHandler, HARNESS_WEBHOOK_PATH } from "./utils/http-webhooks.js";
 import { loadEnvFile } from "./utils/env.js";
 import { createAuditManager, type AuditManager } from "./audit/index.js";
 import { SearchManager } from "./search/index.js";
 import { mergeConfigWithSessionHeaders, MissingSessionCredentialsError } from "./utils/session-headers.js";
 import { buildHttpHealthResponse } from "./utils/http-health.js";
-import { beginSessionRequest, endSessionRequest, isSessionExpired, type HttpSessionActivity } from "./utils/http-sessions.js";
+import {
+  beginSessionRequest,
+  endSessionRequest,
+  isSessionExpired,
+  MAX_FAILED_PINGS,
+  pingClient,
+  startSseKeepalive,
+  type HttpSessionActivity,
+} from "./utils/http-sessions.js";
 import { createHarnessHttpExpressApp } from "./utils/http-app.js";
+import { listenOnUnixSocket, removeSocketPath } from "./utils/unix-socket.js";
+import { ClientPool } from "./utils/client-pool.js";
 
 
 const log = createLogger("main");
 
+/** Set once at startup when HARNESS_CAPABILITY_PROBE is enabled; shared by every Registry this process builds. */
+let platformCapabilities: PlatformCapabilities | undefined;
+
+/** Set at startup when HARNESS_FF_SDK_KEY is configured; keeps polling flag state for new sessions. */
+let featureFlagGate: FeatureFlagGate | undefined;
+
 interface HarnessServerResult {
   server: McpServer;
   auditManager: AuditManager;
@@ -37,11 +87,21 @@ interface HarnessServerResult {
 /**
  * Create a fully-configured MCP server instance with all tools, resources, and prompts.
  * @param sharedAuditManager When set (HTTP mode), reuse this manager instead of creating one per session.
+ * @param clientPool When set (HTTP mode), share one API client per account + credential; release it with the session.
+ * @param hiddenToolsets Toolsets this session's caller may not see (HARNESS_TOOLSET_PERMISSIONS).
+ * @param events When false, do not push Harness events to the session — for one-shot servers that cannot receive them (gRPC calls).
  */
-function createHarnessServer(config: Config, sharedAuditManager?: AuditManager, sharedSearchManager?: SearchManager): HarnessServerResult {
+function createHarnessServer(
+  config: Config,
+  sharedAuditManager?: AuditManager,
+  sharedSearchManager?: SearchManager,
+  clientPool?: ClientPool<HarnessClient>,
+  hiddenToolsets?: string[],
+  events = true,
+): HarnessServerResult {
   const auditManager = sharedAuditManager ?? createAuditManager(config);
-  const client = new HarnessClient(config);
-  const registry = new Registry(config, { auditManager });
+  const client = clientPool ? clientPool.acquire(config) : new HarnessClient(config);
+  const registry = new Registry(config, { auditManager, capabilities: platformCapabilities, hiddenToolsets, featureFlags: featureFlagGate });
   const searchManager = sharedSearchManager ?? new SearchManager(config);
 
   const server = new McpServer(
@@ -79,7 +139,7 @@ function createHarnessServer(config: Config, sharedAuditManager?: AuditManager,
     },
   );
 
-  configureElicitation({ autoApproveRisk: config.HARNESS_AUTO_APPROVE_RISK as import("./registry/types.js").AutoApproveRisk });
+  configureToolBehavior(config);
   // Initialize search provider only if we created it (shared instances are pre-initialized)
   if (!sharedSearchManager) {
     searchManager.initialize().then(async () => {
@@ -88,6 +148,8 @@ function createHarnessServer(config: Config, sharedAuditManager?: AuditManager,
       await searchManager.indexStaticContent(registry);
       // Pre-index tier-1 resources only in single-user mode where account is known
       if (config.HARNESS_MCP_MODE !== "multi-user") {
+        // Start the refresher first so the initial pass is indexed with the refresh TTL
+        searchManager.startEntityRefresh(registry, client, config.HARNESS_ENTITY_REFRESH_INTERVAL_MS);
         await searchManager.initializeIndex(registry, client);
       }
     }).catch((err) => {
@@ -96,12 +158,42 @@ function createHarnessServer(config: Config, sharedAuditManager?: AuditManager,
   }
 
   registerAllTools(server, registry, client, config, undefined, searchManager);
-  registerAllResources(server, registry, client, config);
-  registerAllPrompts(server);
+  const complete = createArgumentCompleter(registry, client, config);
+  registerAllResources(server, registry, client, config, undefined, complete);
+  registerAllPrompts(server, complete);
+  if (events && (config.HARNESS_EVENTS_NATS_URL || config.HARNESS_WEBHOOK_SECRET)) attachHarnessEvents(server, config);
 
   return { server, auditManager, searchManager };
 }
 
+/** Apply config to the module-level settings tool handlers read (elicitation, timeouts, redaction, ...). */
+function configureToolBehavior(config: Config): void {
+  configureElicitation({ autoApproveRisk: config.HARNESS_AUTO_APPROVE_RISK as import("./registry/types.js").AutoApproveRisk });
+  configureToolTimeouts(config.HARNESS_TOOL_TIMEOUTS ? parseToolTimeouts(config.HARNESS_TOOL_TIMEOUTS) : {});
+  configureStrictSchemas({ enabled: config.HARNESS_STRICT_SCHEMAS, outputSchemas: TOOL_OUTPUT_SCHEMAS });
+  configureRedaction({ patterns: config.HARNESS_REDACT_PATTERNS ? parseRedactPatterns(config.HARNESS_REDACT_PATTERNS) : [] });
+  configureTimeFormatting({ enabled: config.HARNESS_LOCALIZE_TIMES, timeZone: config.HARNESS_TIMEZONE });
+  configureResultChunking({ chunkChars: config.HARNESS_RESULT_CHUNK_CHARS });
+  configureSpillBuffers({
+    dir: config.HARNESS_OUTPUT_DIR,
+    thresholdBytes: config.HARNESS_SPILL_THRESHOLD_MB * 1024 * 1024,
+    maxDiskBytes: config.HARNESS_SPILL_MAX_MB * 1024 * 1024,
+  });
+}
+
+/**
+ * Server with only the tools registered, for the `tools` and `call`
+ * subcommands. No search indexing or capability probe runs, so building it
+ * does not contact Harness.
+ */
+function createToolCatalogServer(config: Config): { server: McpServer; registry: Registry; client: HarnessClient } {
+  const registry = new Registry(config);
+  const client = new HarnessClient(config);
+  const server = new McpServer({ name: "harness-mcp-server", version: getVersion() });
+  registerAllTools(server, registry, client, config);
+  return { server, registry, client };
+}
+
 /**
  * Write a diagnostic line directly to a log file.
  * Used during disconnect/crash when stderr (console.error) may already be dead
@@ -129,13 +221,27 @@ function logToFile(message: string, data?: Record<string, unknown>): void {
   }
 }
 
+/**
+ * Connect a server to an MCP transport and install the per-connection layers
+ * every transport shares: JSON-RPC frame dump, protocol-revision shaping, and
+ * client log forwarding. A new transport (WebSocket, Unix socket, ...) only
+ * has to construct its `Transport` and call this.
+ */
+async function connectHarnessServer(server: McpServer, transport: Transport, config: Config): Promise<void> {
+  await server.connect(transport);
+  if (config.HARNESS_DEBUG_FRAMES) attachFrameDump(transport);
+  // After the frame dump so dumped frames show what the client actually receives
+  attachProtocolAdapter(transport);
+  attachMcpLogging(server, transport);
+}
+
 /**
  * Start the server in stdio mode — single persistent connection.
  */
 async function startStdio(config: Config): Promise<void> {
-  const { server, auditManager } = createHarnessServer(config);
-  const transport = new StdioServerTransport();
-  await server.connect(transport);
+  const { server, auditManager, searchManager } = createHarnessServer(config);
+  const transport = new StdioTransport(process.stdin, process.stdout, config.HARNESS_STDIO_MAX_FRAME_MB * 1024 * 1024);
+  await connectHarnessServer(server, transport, config);
   log.info("harness-mcp-server connected via stdio", {
     pid: process.pid,
     node_version: process.version,
@@ -178,6 +284,7 @@ async function startStdio(config: Config): Promise<void> {
 
   const shutdown = async (signal: string): Promise<void> => {
     if (keepaliveTimer) clearInterval(keepaliveTimer);
+    searchManager.stopEntityRefresh();
     log.info(`Received ${signal}, closing stdio transport...`, {
       idle_ms: Date.now() - lastActivityTs,
       uptime_s: Math.round(process.uptime()),
@@ -185,6 +292,7 @@ async function startStdio(config: Config): Promise<void> {
     await auditManager.close();
     await transport.close();
     await server.close();
+    closeHttp2Pools();
     log.info("Stdio server closed");
     process.exit(0);
   };
@@ -225,16 +333,166 @@ async function startStdio(config: Config): Promise<void> {
   keepaliveTimer.unref();
 }
 
+/**
+ * Start the server on a Unix domain socket — one MCP server per connection,
+ * framed like stdio (newline-delimited or Content-Length JSON-RPC). Audit and
+ * search managers are shared across connections.
+ */
+async function startSocket(config: Config, socketPath: string): Promise<void> {
+  let shared: { auditManager: AuditManager; searchManager: SearchManager } | undefined;
+  const connections = new Set<McpServer>();
+
+  const listener = await listenOnUnixSocket(socketPath, (socket) => {
+    const result = createHarnessServer(config, shared?.auditManager, shared?.searchManager);
+    shared ??= { auditManager: result.auditManager, searchManager: result.searchManager };
+    const { server } = result;
+    connections.add(server);
+    socket.once("close", () => {
+      connections.delete(server);
+      server.close().catch(() => {});
+      log.info("Socket client disconnected", { remaining: connections.size });
+    });
+    socket.on("error", (err) => log.warn("Socket client error", { error: err.message }));
+
+    connectHarnessServer(server, new StdioTransport(socket, socket, config.HARNESS_STDIO_MAX_FRAME_MB * 1024 * 1024), config)
+      .then(() => log.info("Socket client connected", { total: connections.size }))
+      .catch((err) => {
+        log.error("Failed to start socket session", { error: String(err) });
+        socket.destroy();
+      });
+  });
+  log.info("harness-mcp-server listening on Unix socket", { socketPath, pid: process.pid });
+
+  const shutdown = async (signal: string): Promise<void> => {
+    log.info(`Received ${signal}, closing socket transport...`);
+    listener.close();
+    shared?.searchManager.stopEntityRefresh();
+    await Promise.allSettled([...connections].map((server) => server.close()));
+    await shared?.auditManager.close().catch(() => {});
+    closeHttp2Pools();
+    removeSocketPath(socketPath);
+    process.exit(0);
+  };
+  process.on("SIGINT", () => { shutdown("SIGINT").catch(() => process.exit(1)); });
+  process.on("SIGTERM", () => { shutdown("SIGTERM").catch(() => process.exit(1)); });
+}
+
+/**
+ * Start the gRPC transport — unary ListTools / CallTool RPCs for internal
+ * service-to-service callers (see proto/harness/mcp/v1/tool_dispatch.proto).
+ * Uses the HTTP transport's TLS / client certificate and auth settings; in
+ * multi-user mode each call carries its credentials as metadata, like the
+ * HTTP session headers. Calls are stateless: each one builds a server for the
+ * caller's config and closes it afterwards, sharing API clients by account.
+ */
+async function startGrpc(config: Config, port: number): Promise<void> {
+  const host = process.env.HOST || "127.0.0.1";
+  validateHttpAuthForBindHost(host, config);
+  const tlsOptions = loadHttpTlsOptions(config);
+  const jwtOptions = loadJwtValidationOptions(config);
+
+  const sharedAuditManager = createAuditManager(config);
+  const sharedSearchManager = new SearchManager(config);
+  const clientPool = new ClientPool((sessionConfig) => new HarnessClient(sessionConfig));
+  sharedSearchManager.initialize().then(async () => {
+    if (sharedSearchManager.getProvider().isAvailable()) {
+      await sharedSearchManager.indexStaticContent(new Registry(config, { capabilities: platformCapabilities }));
+    }
+  }).catch((err) => {
+    log.error("Shared SearchManager initialization failed", { error: String(err) });
+  });
+
+  /** Run `fn` against a server built for the caller's metadata, as the JWT principal when there is one. */
+  const withCallServer = async <T>(metadata: IncomingHttpHeaders, jwtClaims: unknown, fn: (server: McpServer) => Promise<T>): Promise<T> => {
+    let sessionConfig: Config;
+    try {
+      sessionConfig = mergeConfigWithSessionHeaders(config, metadata);
+    } catch (err) {
+      if (err instanceof MissingSessionCredentialsError) throw new GrpcError(GrpcStatus.UNAUTHENTICATED, err.message);
+      throw err;
+    }
+    const { server } = createHarnessServer(sessionConfig, sharedAuditManager, sharedSearchManager, clientPool, undefined, false);
+    const principal = principalFromClaims(jwtClaims);
+    try {
+      return await (principal ? runWithPrincipal(principal, () => fn(server)) : fn(server));
+    } finally {
+      await server.close().catch(() => {});
+      clientPool.release(sessionConfig);
+    }
+  };
+
+  const grpcServer = createGrpcServer({
+    authorize: (metadata) => {
+      const result = authenticateHttpHeaders(metadata, config.HARNESS_MCP_AUTH_TOKEN, jwtOptions);
+      if (!result.ok) throw new GrpcError(GrpcStatus.UNAUTHENTICATED, result.message);
+      return result.jwtClaims;
+    },
+    listTools: (metadata, jwtClaims) => withCallServer(metadata, jwtClaims, listServerTools),
+    callTool: (name, args, metadata, jwtClaims, signal) => withCallServer(metadata, jwtClaims, (server) =>
+      withInMemoryClient(server, async (client) => {
+        const { tools } = await client.listTools();
+        if (!tools.some((t) => t.name === name)) {
+          throw new GrpcError(GrpcStatus.NOT_FOUND, `Unknown tool "${name}". Available: ${tools.map((t) => t.name).join(", ")}`);
+        }
+        return await client.callTool({ name, arguments: args }, undefined, { signal }) as CallToolResult;
+      })),
+  }, {
+    ...(tlsOptions ? { tls: tlsOptions } : {}),
+    maxMessageBytes: config.HARNESS_MAX_BODY_SIZE_MB * 1024 * 1024,
+  });
+  grpcServer.listen(port, host, () => {
+    log.info(`harness-mcp-server listening for gRPC on ${host}:${port}`, {
+      tls: Boolean(tlsOptions),
+      client_certificates: Boolean(config.HARNESS_MCP_TLS_CA),
+      services: [TOOL_DISPATCH_SERVICE, "grpc.health.v1.Health"],
+    });
+  });
+
+  const shutdown = async (signal: string): Promise<void> => {
+    log.info(`Received ${signal}, closing gRPC transport...`);
+    grpcServer.close();
+    sharedSearchManager.stopEntityRefresh();
+    await sharedAuditManager.close().catch(() => {});
+    closeHttp2Pools();
+    process.exit(0);
+  };
+  process.on("SIGINT", () => { shutdown("SIGINT").catch(() => process.exit(1)); });
+  process.on("SIGTERM", () => { shutdown("SIGTERM").catch(() => process.exit(1)); });
+}
+
 // ---------------------------------------------------------------------------
 // Session store — maps session IDs to their MCP server + transport instances.
 // ---------------------------------------------------------------------------
 interface Session extends HttpSessionActivity {
   server: McpServer;
   transport: StreamableHTTPServerTransport;
+  /** Open GET /mcp SSE streams — only these sessions are pinged. */
+  openStreams: number;
+  failedPings: number;
+  /** Return the session's pooled API client. */
+  releaseClient: () => void;
+  /** JWT subject that initialized the session; later requests must present the same subject. */
+  principalId?: string;
 }
 
 const REAP_INTERVAL_MS = 60_000; // check every minute
 
+/**
+ * Reject a request on a JWT-bound session when its token names a different
+ * subject, so one caller cannot act through another caller's session.
+ */
+function rejectForeignPrincipal(session: Session, res: Response): boolean {
+  if (session.principalId === undefined) return false;
+  const principal = principalFromClaims(res.locals.jwtClaims);
+  if (principal?.id === session.principalId) return false;
+  res.status(403).json({
+    jsonrpc: "2.0",
+    error: { code: -32001, message: "Session belongs to a different principal." },
+    id: null,
+  });
+  return true;
+}
+
 /**
  * Start the server in HTTP mode — stateful, session-based.
  * Each `initialize` request creates a persistent session (server + transport).
@@ -248,6 +506,9 @@ async function startHttp(config: Config, port: number): Promise<void> {
   const host = process.env.HOST || "127.0.0.1";
 
   validateHttpAuthForBindHost(host, config);
+  // Read certificates before binding so a bad path fails startup, not the first handshake
+  const tlsOptions = loadHttpTlsOptions(config);
+  const scheme = tlsOptions ? "https" : "http";
 
   const app = createHarnessHttpExpressApp(resolveHttpHostValidationOptions(host, config));
 
@@ -259,17 +520,16 @@ async function startHttp(config: Config, port: number): Promise<void> {
     app.set("trust proxy", config.HARNESS_MCP_TRUST_PROXY);
   }
 
-  // CORS — allow GET, POST, DELETE for session-based MCP
-  app.use((_req, res, next) => {
-    res.setHeader("Access-Control-Allow-Origin", `http://${host}:${port}`);
-    res.setHeader("Access-Control-Allow-Methods", "GET, POST, DELETE, OPTIONS");
-    res.setHeader("Access-Control-Allow-Headers", "Authorization, Content-Type, mcp-session-id, x-harness-api-key, x-harness-account-id, x-harness-org, x-harness-project, x-harness-pipeline-version, x-harness-auto-approve-risk");
-    res.setHeader("Access-Control-Expose-Headers", "mcp-session-id");
-    next();
-  });
+  // CORS + Origin validation — browser requests only from the server's own or configured origins
+  app.use(createCorsMiddleware(resolveAllowedOrigins(host, port, config.HARNESS_MCP_ALLOWED_ORIGINS, scheme)));
+
+  // Harness notification webhooks carry their own secret, not the MCP bearer token
+  if (config.HARNESS_WEBHOOK_SECRET) {
+    app.post(HARNESS_WEBHOOK_PATH, json({ limit: "1mb" }), createHarnessWebhookHandler(config.HARNESS_WEBHOOK_SECRET));
+  }
 
   // Auth gate before body parsing — reject unauthenticated requests without allocating body memory
-  app.use(createHttpAuthMiddleware(config.HARNESS_MCP_AUTH_TOKEN));
+  app.use(createHttpAuthMiddleware(config.HARNESS_MCP_AUTH_TOKEN, loadJwtValidationOptions(config)));
 
   // Simple per-IP rate limiting: 60 requests per minute
   const ipHits = new Map<string, { count: number; resetAt: number }>();
@@ -303,8 +563,17 @@ async function startHttp(config: Config, port: number): Promise<void> {
   const sessions = new Map<string, Session>();
   const sharedAuditManager = createAuditManager(config);
   const sharedSearchManager = new SearchManager(config);
+  // One API client per account + credential, shared by that user's sessions
+  const clientPool = new ClientPool((sessionConfig) => new HarnessClient(sessionConfig));
+  // Per-caller toolset visibility only applies when each session brings its own credentials
+  if (config.HARNESS_TOOLSET_PERMISSIONS && config.HARNESS_MCP_MODE !== "multi-user") {
+    log.warn("HARNESS_TOOLSET_PERMISSIONS is ignored outside multi-user mode (every session shares the server's credentials)");
+  }
+  const toolsetPermissions = config.HARNESS_TOOLSET_PERMISSIONS && config.HARNESS_MCP_MODE === "multi-user"
+    ? parseToolsetPermissions(config.HARNESS_TOOLSET_PERMISSIONS, ALL_TOOLSET_NAMES)
+    : undefined;
   // In HTTP mode: initialize + index static content using a baseline registry (no account needed)
-  const baseRegistry = new Registry(config, { auditManager: sharedAuditManager });
+  const baseRegistry = new Registry(config, { auditManager: sharedAuditManager, capabilities: platformCapabilities });
   sharedSearchManager.initialize().then(async () => {
     if (sharedSearchManager.getProvider().isAvailable()) {
       await sharedSearchManager.indexStaticContent(baseRegistry);
@@ -317,6 +586,7 @@ async function startHttp(config: Config, port: number): Promise<void> {
     const session = sessions.get(sessionId);
     if (!session) return;
     sessions.delete(sessionId);
+    session.releaseClient();
     await session.transport.close().catch(() => {});
     await session.server.close().catch(() => {});
     log.info("Session destroyed", { sessionId, remaining: sessions.size });
@@ -340,15 +610,53 @@ async function startHttp(config: Config, port: number): Promise<void> {
   }, REAP_INTERVAL_MS);
   reaper.unref();
 
+  // Liveness pings — a GET stream keeps its session out of the reaper, so a
+  // client that vanished without closing the connection would hold it forever
+  const pinger = config.MCP_PING_INTERVAL_MS > 0
+    ? setInterval(() => {
+      for (const [id, session] of sessions) {
+        if (session.openStreams === 0) continue;
+        pingClient(session.server.server, config.MCP_PING_TIMEOUT_MS).then((ok) => {
+          if (ok) {
+            session.failedPings = 0;
+            return;
+          }
+          session.failedPings++;
+          log.warn("Client did not answer ping", { sessionId: id, failedPings: session.failedPings });
+          if (session.failedPings >= MAX_FAILED_PINGS) {
+            log.info("Closing unresponsive session", { sessionId: id });
+            destroySession(id);
+          }
+        });
+      }
+    }, config.MCP_PING_INTERVAL_MS)
+    : undefined;
+  pinger?.unref();
+
   // ---- Routes ----
 
   // Health check (includes session count and search readiness for observability)
   app.get("/health", (_req, res) => {
     const search = sharedSearchManager.getReadiness();
-    const health = buildHttpHealthResponse(search, sessions.size);
+    const health = buildHttpHealthResponse(search, sessions.size, clientPool.stats());
     res.status(health.statusCode).json(health.body);
   });
 
+  // Live diagnostics — same as SIGUSR1, for deployments where signals can't reach the process.
+  // Behind the auth middleware like /mcp, and only served when a credential
+  // actually gates it (see adminRoutesEnabled).
+  if (adminRoutesEnabled(config)) {
+    const adminAuth = createAdminAuthMiddleware(config.HARNESS_MCP_ADMIN_TOKEN);
+    app.get("/admin/debug", adminAuth, (_req, res) => {
+      res.json(inFlightSnapshot());
+    });
+    app.post("/admin/debug", adminAuth, (_req, res) => {
+      res.json(toggleAndDump("POST /admin/debug"));
+    });
+  } else {
+    log.info("/admin/debug is disabled: set HARNESS_MCP_ADMIN_TOKEN (required in multi-user mode) or HARNESS_MCP_AUTH_TOKEN to enable it");
+  }
+
   // POST /mcp — initialize new sessions or route to existing session
   app.post("/mcp", async (req, res) => {
     const sessionId = req.headers["mcp-session-id"] as string | undefined;
@@ -364,7 +672,9 @@ async function startHttp(config: Config, port: number): Promise<void> {
         });
         return;
       }
+      if (rejectForeignPrincipal(session, res)) return;
       beginSessionRequest(session);
+      startSseKeepalive(res, config.MCP_KEEPALIVE_INTERVAL_MS);
       try {
         await session.transport.handleRequest(req, res, req.body);
       } catch (err) {
@@ -385,9 +695,30 @@ async function startHttp(config: Config, port: number): Promise<void> {
     // No session header — must be an initialize request. Create a new session.
     let server: McpServer | undefined;
     let transport: StreamableHTTPServerTransport | undefined;
+    let releaseClient = (): void => {};
+    const principal = principalFromClaims(res.locals.jwtClaims);
     try {
       const sessionConfig = mergeConfigWithSessionHeaders(config, req.headers);
-      const result = createHarnessServer(sessionConfig, sharedAuditManager, sharedSearchManager);
+      let hiddenToolsets: string[] | undefined;
+      if (toolsetPermissions) {
+        // Check with the session's own credentials before the registry is built
+        const aclClient = clientPool.acquire(sessionConfig);
+        try {
+          const permissions = toolsetPermissions;
+          const check = () => hiddenToolsetsFor(aclClient, permissions);
+          hiddenToolsets = await (principal ? runWithPrincipal(principal, check) : check());
+        } finally {
+          clientPool.release(sessionConfig);
+        }
+        if (hiddenToolsets.length > 0) log.info("Toolsets hidden for session caller", { hiddenToolsets });
+      }
+      const result = createHarnessServer(sessionConfig, sharedAuditManager, sharedSearchManager, clientPool, hiddenToolsets);
+      let released = false;
+      releaseClient = () => {
+        if (released) return;
+        released = true;
+        clientPool.release(sessionConfig);
+      };
       server = result.server;
       transport = new StreamableHTTPServerTransport({
         sessionIdGenerator: () => randomUUID(),
@@ -397,8 +728,17 @@ async function startHttp(config: Config, port: number): Promise<void> {
             transport: transport!,
             lastActivity: Date.now(),
             activeRequests: 0,
+            openStreams: 0,
+            failedPings: 0,
+            releaseClient,
+            ...(principal ? { principalId: principal.id } : {}),
+          });
+          log.info("Session created", {
+            sessionId: id,
+            total: sessions.size,
+            ...(config.HARNESS_MCP_MODE === "multi-user" ? { accountId: sessionConfig.HARNESS_ACCOUNT_ID } : {}),
+            ...(principal ? { principal: principal.id } : {}),
           });
-          log.info("Session created", { sessionId: id, total: sessions.size });
         },
       });
 
@@ -408,7 +748,9 @@ async function startHttp(config: Config, port: number): Promise<void> {
         }
       };
 
-      await server.connect(transport);
+      await connectHarnessServer(server, transport, config);
+      if (principal) attachPrincipal(transport, principal);
+      startSseKeepalive(res, config.MCP_KEEPALIVE_INTERVAL_MS);
       await transport.handleRequest(req, res, req.body);
     } catch (err) {
       if (err instanceof MissingSessionCredentialsError) {
@@ -430,6 +772,7 @@ async function startHttp(config: Config, port: number): Promise<void> {
           id: null,
         });
       }
+      releaseClient();
       await transport?.close();
       await server?.close();
     }
@@ -456,15 +799,19 @@ async function startHttp(config: Config, port: number): Promise<void> {
       });
       return;
     }
+    if (rejectForeignPrincipal(session, res)) return;
 
     beginSessionRequest(session);
+    session.openStreams++;
     let streamClosed = false;
     const markStreamClosed = (): void => {
       if (streamClosed) return;
       streamClosed = true;
+      session.openStreams = Math.max(0, session.openStreams - 1);
       endSessionRequest(session);
     };
     res.once("close", markStreamClosed);
+    startSseKeepalive(res, config.MCP_KEEPALIVE_INTERVAL_MS);
     try {
       await session.transport.handleRequest(req, res);
     } catch (err) {
@@ -505,6 +852,7 @@ async function startHttp(config: Config, port: number): Promise<void> {
       });
       return;
     }
+    if (rejectForeignPrincipal(session, res)) return;
 
     beginSessionRequest(session);
     try {
@@ -518,13 +866,20 @@ async function startHttp(config: Config, port: number): Promise<void> {
   });
 
   // Graceful shutdown — drain in-flight requests, then close all sessions
-  const httpServer = app.listen(port, host, () => {
-    log.info(`harness-mcp-server listening on http://${host}:${port}`);
+  const onListening = (): void => {
+    log.info(`harness-mcp-server listening on ${scheme}://${host}:${port}`, {
+      ...(tlsOptions ? { tls: true, client_certificates: Boolean(config.HARNESS_MCP_TLS_CA) } : {}),
+    });
     log.info(`  POST   /mcp    — MCP endpoint (session-based, DNS rebinding protected)`);
     log.info(`  GET    /mcp    — SSE stream (progress, elicitation)`);
     log.info(`  DELETE /mcp    — Terminate session`);
     log.info(`  GET    /health — Health check`);
-  });
+    log.info(`  GET    /admin/debug — In-flight requests; POST toggles debug logging`);
+    if (config.HARNESS_WEBHOOK_SECRET) log.info(`  POST   ${HARNESS_WEBHOOK_PATH} — Harness notification webhooks`);
+  };
+  const httpServer = tlsOptions
+    ? createHttpsServer(tlsOptions, app).listen(port, host, onListening)
+    : app.listen(port, host, onListening);
 
   let draining = false;
 
@@ -549,10 +904,12 @@ async function startHttp(config: Config, port: number): Promise<void> {
 
     // 3. Close all sessions (terminates SSE streams, notifies transports)
     clearInterval(reaper);
+    if (pinger) clearInterval(pinger);
     await Promise.allSettled(
       [...sessions.keys()].map((id) => destroySession(id)),
     );
     await sharedAuditManager.close().catch(() => {});
+    closeHttp2Pools();
 
     // 4. Allow in-flight responses to flush, then exit
     const DRAIN_TIMEOUT_MS = 10_000;
@@ -581,11 +938,98 @@ async function startHttp(config: Config, port: number): Promise<void> {
 
 async function main(): Promise<void> {
   // Parse CLI args first to get env file path
-  const { transport, envFile } = parseArgs();
+  const { transport, envFile, smp, socketPath, strictSchemas, toolsets, mock, mockFixtures, command } = parseArgs();
 
   // Load .env file (custom path if specified, otherwise .env in current directory)
   loadEnvFile(envFile);
 
+  // --smp wins over the environment so the flag alone is enough to switch layouts
+  if (smp) {
+    process.env.HARNESS_DEPLOYMENT = "smp";
+  }
+  if (toolsets) {
+    process.env.HARNESS_TOOLSETS = toolsets;
+  }
+  if (mock) {
+    process.env.HARNESS_MOCK = "true";
+  }
+  if (mockFixtures) {
+    process.env.HARNESS_MOCK_FIXTURES_DIR = mockFixtures;
+  }
+
+  if (command?.name === "completions") {
+    process.stdout.write(generateCompletions(command.shell, getVersion()));
+    return;
+  }
+  if (command?.name === "config-env") {
+    process.stdout.write(`${renderConfigEnv()}\n`);
+    return;
+  }
+  if (command?.name === "tools-list" || command?.name === "tools-schema") {
+    const { server, registry } = createToolCatalogServer(loadCatalogConfig());
+    const tools = await listServerTools(server);
+    const output = command.name === "tools-list" ? renderToolList(tools, registry) : renderToolSchema(tools, command.tool);
+    process.stdout.write(`${output}\n`);
+    return;
+  }
+  if (command?.name === "call") {
+    const config = loadConfig();
+    if (config.HARNESS_MCP_MODE === "multi-user") {
+      throw new Error("call needs server credentials; it is not available with HARNESS_MCP_MODE=multi-user.");
+    }
+    setLogLevel(config.LOG_LEVEL);
+    configureLogOutput({ file: config.LOG_FILE });
+    configureToolBehavior(config);
+    const { server } = createToolCatalogServer(config);
+    const result = await callServerTool(server, command.tool, command.args);
+    process.stdout.write(`${renderCallResult(result)}\n`);
+    if (result.isError) process.exitCode = 1;
+    return;
+  }
+  if (command?.name === "fixtures-record") {
+    const config = loadConfig();
+    if (config.HARNESS_MCP_MODE === "multi-user" || config.HARNESS_MOCK) {
+      throw new Error("fixtures record needs live server credentials; it is not available with HARNESS_MCP_MODE=multi-user or HARNESS_MOCK.");
+    }
+    setLogLevel(config.LOG_LEVEL);
+    configureLogOutput({ file: config.LOG_FILE });
+    configureToolBehavior(config);
+    // Recording only reads; make sure nothing the tools do can write
+    const { server, registry, client } = createToolCatalogServer({ ...config, HARNESS_READ_ONLY: true });
+    const recorder = new FixtureRecorder(config.HARNESS_ACCOUNT_ID, config.HARNESS_BASE_URL);
+    client.setExchangeRecorder(recorder.record);
+    const summary = await withInMemoryClient(server, (mcp) => runReadTools(mcp, registry, (message) => process.stderr.write(`${message}\n`)));
+    const files = await writeFixtureFiles(command.dir, await recorder.mappings());
+    for (const failure of summary.failures) process.stderr.write(`skipped: ${failure}\n`);
+    process.stdout.write(`Recorded ${files.length} fixtures in ${command.dir} from ${summary.calls} tool calls (${summary.failures.length} failed).\n`);
+    return;
+  }
+  if (command?.name === "self-test") {
+    // Without an API key the protocol checks still run (against a placeholder credential); connectivity is skipped
+    const config = loadCatalogConfig();
+    const hasCredentials = Boolean(process.env.HARNESS_API_KEY) || config.HARNESS_MOCK;
+    setLogLevel(config.LOG_LEVEL);
+    configureLogOutput({ file: config.LOG_FILE });
+    // An uninitialized search manager: the self-test must not index the account
+    const { server, auditManager } = createHarnessServer(config, undefined, new SearchManager(config));
+    const protocol = await runProtocolChecks((transport) => connectHarnessServer(server, transport, config));
+    await auditManager.close().catch(() => {});
+    const target = config.HARNESS_MOCK ? "mock fixtures" : hasCredentials ? `${config.HARNESS_BASE_URL}, account ${config.HARNESS_ACCOUNT_ID}` : config.HARNESS_BASE_URL;
+    const report = config.HARNESS_MCP_MODE === "multi-user"
+      ? { protocol, target, skipReason: "multi-user mode has no server credentials" }
+      : !hasCredentials
+        ? { protocol, target, skipReason: "HARNESS_API_KEY is not set" }
+        : { protocol, target, connectivity: await runConnectivityChecks(new Registry(config), new HarnessClient(config), config.HARNESS_CAPABILITY_PROBE_TIMEOUT_MS) };
+    process.stdout.write(`${renderSelfTestReport(report)}\n`);
+    if (!selfTestPassed(report)) process.exitCode = 1;
+    return;
+  }
+  if (strictSchemas) {
+    process.env.HARNESS_STRICT_SCHEMAS = "true";
+  }
+
+  recordServerTransport(transport);
+
   // Resolve the HTTP port after dotenv is loaded so --env-file PORT is honored.
   const port = resolvePort();
 
@@ -609,26 +1053,74 @@ async function main(): Promise<void> {
 
   const config = loadConfig();
   setLogLevel(config.LOG_LEVEL);
-
-  if (config.HARNESS_MCP_MODE === "multi-user" && transport === "stdio") {
-    throw new Error(
-      "Multi-user mode is only supported with HTTP transport. " +
-      "Use --transport http or set HARNESS_MCP_MODE=single-user for stdio.",
-    );
+  configureLogOutput({ file: config.LOG_FILE });
+  // The DNS cache only serves the server's own HTTP/2 sessions; the default
+  // fetch path resolves through the system resolver
+  if (!config.HARNESS_HTTP2 && process.env.HARNESS_DNS_CACHE_TTL_MS !== undefined) {
+    log.warn("HARNESS_DNS_CACHE_TTL_MS is ignored without HARNESS_HTTP2=true (only HTTP/2 sessions use the DNS cache)");
+  }
+  configureNetwork({
+    dnsCacheTtlMs: config.HARNESS_HTTP2 ? config.HARNESS_DNS_CACHE_TTL_MS : 0,
+    dnsNegativeTtlMs: config.HARNESS_DNS_NEGATIVE_TTL_MS,
+    dnsResultOrder: config.HARNESS_DNS_RESULT_ORDER,
+    happyEyeballsTimeoutMs: config.HARNESS_HAPPY_EYEBALLS_MS,
+  });
+  if (transport === "stdio") {
+    // stdout carries JSON-RPC frames — nothing else may write to it
+    guardStdout();
   }
+  // kill -USR1 <pid>: toggle debug logging and dump in-flight requests
+  installDiagnosticsSignal();
+
+  assertTransportSupportsMode(transport, config.HARNESS_MCP_MODE);
 
   log.info("Starting harness-mcp-server", {
     transport,
     mode: config.HARNESS_MCP_MODE,
     baseUrl: config.HARNESS_BASE_URL,
+    deployment: config.HARNESS_DEPLOYMENT,
     accountId: config.HARNESS_ACCOUNT_ID || "(per-session)",
     defaultOrg: config.HARNESS_ORG ?? "(none)",
     defaultProject: config.HARNESS_PROJECT ?? "(none)",
     toolsets: config.HARNESS_TOOLSETS ?? "(all)",
   });
+  if (config.HARNESS_MOCK) {
+    log.warn("Mock mode: API calls are answered from fixtures and never reach Harness (read-only)", {
+      fixturesDir: config.HARNESS_MOCK_FIXTURES_DIR ?? "(embedded only)",
+    });
+  }
+
+  if (config.HARNESS_CAPABILITY_PROBE) {
+    if (config.HARNESS_MCP_MODE === "multi-user") {
+      log.warn("HARNESS_CAPABILITY_PROBE is ignored in multi-user mode (no server-wide credentials to probe with)");
+    } else {
+      platformCapabilities = await probePlatformCapabilities(
+        new Registry(config),
+        new HarnessClient(config),
+        config.HARNESS_CAPABILITY_PROBE_TIMEOUT_MS,
+      );
+      log.info("Platform capability probe complete", {
+        version: platformCapabilities.version ?? "(unknown)",
+        unavailableToolsets: platformCapabilities.unavailableToolsets,
+      });
+    }
+  }
+
+  if (config.HARNESS_FF_SDK_KEY) {
+    featureFlagGate = new FeatureFlagGate(config.HARNESS_FF_SDK_KEY, config.HARNESS_FF_BASE_URL, config.HARNESS_FF_POLL_INTERVAL_MS);
+    await featureFlagGate.refresh();
+    featureFlagGate.start();
+    if (!featureFlagGate.ready) log.warn("Feature flags unavailable; flag-gated toolsets stay hidden until a poll succeeds");
+  }
+
+  startHarnessEventConsumer(config);
 
   if (transport === "stdio") {
     await startStdio(config);
+  } else if (transport === "socket") {
+    await startSocket(config, socketPath!);
+  } else if (transport === "grpc") {
+    await startGrpc(config, port);
   } else {
     await startHttp(config, port);
   }
@@ -28,6 +28,7 @@ Steps:
 4. Call harness_list with resource_type="pr_activity", repo_id="${repoId}", pr_number="${prNumber}"${projectArg} to see review activity
 5. Call harness_list with resource_type="commit", repo_id="${repoId}", git_ref="refs/pullreq/${prNumber}/head"${projectArg} to list the PR's commits
 6. Use harness_execute with resource_type="commit", action="diff_stats", repo_id="${repoId}", range="<target_branch>..<source_branch>"${projectArg} to see what files changed and scope of changes
+7. Call harness_diagnose with resource_type="pr_review", options={repo_id: "${repoId}", pr_number: "${prNumber}"}${projectArg} to read the diff — it is split into chunks; request each further chunk with options.chunk until no _hint remains
 
 Analyze the PR and provide:
 
@@ -1,4 +1,5 @@
 import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { withCompletingPrompts, type ArgumentCompleter } from "../utils/completions.js";
 
 import { registerDebugPipelinePrompt } from "./debug-pipeline.js";
 import { registerCreatePipelinePrompt } from "./create-pipeline.js";
@@ -51,7 +52,10 @@ import { registerAddMetricPrompt } from "./add-metric.js";
 import { registerRunEvaluationPrompt } from "./run-evaluation.js";
 import { registerCreateEvalSuitePrompt } from "./create-eval-suite.js";
 
-export function registerAllPrompts(server: McpServer): void {
+export function registerAllPrompts(rootServer: McpServer, complete?: ArgumentCompleter): void {
+  // Entity-naming arguments (orgId, projectId, pipelineId, repoId) autocomplete from the live catalog
+  const server = complete ? withCompletingPrompts(rootServer, complete) : rootServer;
+
   // Existing prompts
   registerDebugPipelinePrompt(server);
   registerCreatePipelinePrompt(server);
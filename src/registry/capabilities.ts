@@ -0,0 +1,72 @@
+/**
+ * Startup capability probe — detects which module APIs exist on the target
+ * Harness install so self-managed (SMP) deployments don't expose resource
+ * types whose endpoints 404.
+ *
+ * Each toolset may declare `probeResource`; the probe lists one item of that
+ * resource. A 404 means the module's API is not deployed and the toolset is
+ * marked unavailable. Any other outcome — success, 401/403 (exists but not
+ * permitted), 5xx, timeouts — keeps the toolset: hiding a module because of a
+ * transient failure would be worse than exposing one extra 404.
+ */
+import type { HarnessClient } from "../client/harness-client.js";
+import type { Registry } from "./index.js";
+import { HarnessApiError } from "../utils/errors.js";
+import { createLogger } from "../utils/logger.js";
+import { asRecord, asString } from "../utils/type-guards.js";
+
+const log = createLogger("capabilities");
+
+export interface PlatformCapabilities {
+  /** Harness platform version reported by NG manager, when available. */
+  version?: string;
+  probedAt: string;
+  /** Toolsets whose probe returned 404 — hidden from the registry. */
+  unavailableToolsets: string[];
+}
+
+async function fetchPlatformVersion(client: HarnessClient, signal: AbortSignal): Promise<string | undefined> {
+  try {
+    const raw = asRecord(await client.request<unknown>({ method: "GET", path: "/ng/api/version", signal }));
+    const info = asRecord(asRecord(raw?.resource)?.versionInfo) ?? asRecord(asRecord(raw?.data)?.versionInfo);
+    return asString(info?.version);
+  } catch (err) {
+    log.debug("Platform version unavailable", { error: String(err) });
+    return undefined;
+  }
+}
+
+/**
+ * Probe every enabled toolset that declares `probeResource`, in parallel,
+ * bounded by `timeoutMs` overall.
+ */
+export async function probePlatformCapabilities(
+  registry: Registry,
+  client: HarnessClient,
+  timeoutMs: number,
+): Promise<PlatformCapabilities> {
+  const controller = new AbortController();
+  const timer = setTimeout(() => controller.abort(), timeoutMs);
+  try {
+    const probes = registry.getProbeTargets();
+    const [version, results] = await Promise.all([
+      fetchPlatformVersion(client, controller.signal),
+      Promise.all(probes.map(async ({ toolset, resourceType }) => {
+        try {
+          await registry.dispatch(client, resourceType, "list", { page: 0, size: 1, limit: 1 }, controller.signal);
+          return undefined;
+        } catch (err) {
+          if (err instanceof HarnessApiError && err.statusCode === 404) {
+            log.info(`Toolset "${toolset}" unavailable on this Harness install (probe ${resourceType} returned 404)`);
+            return toolset;
+          }
+          return undefined;
+        }
+      })),
+    ]);
+    const unavailableToolsets = results.filter((t): t is string => t !== undefined);
+    return { version, probedAt: new Date().toISOString(), unavailableToolsets };
+  } finally {
+    clearTimeout(timer);
+  }
+}
@@ -4,6 +4,7 @@
  */
 import { isRecord } from "../utils/type-guards.js";
 import { parseZipCsv } from "../utils/zip-csv.js";
+import { describeRuntimeInputs, runtimeInputJsonSchema } from "../utils/runtime-input-schema.js";
 
 /** Extract `data` from standard NG API responses: `{ status, data, ... }` */
 export const ngExtract = (raw: unknown): unknown => {
@@ -315,6 +316,56 @@ export const runtimeInputExtract = (raw: unknown): unknown => {
   };
 };
 
+/**
+ * Extracts the runtime input template plus a JSON Schema for the flat
+ * `inputs` object accepted by pipeline run, so agents can build valid inputs
+ * without reading the template YAML themselves.
+ */
+export const pipelineInputSchemaExtract = (raw: unknown, input?: Record<string, unknown>): unknown => {
+  const r = raw as { data?: { inputSetTemplateYaml?: string; hasInputSets?: boolean } };
+  const templateYaml = r.data?.inputSetTemplateYaml ?? null;
+  const fields = describeRuntimeInputs(templateYaml);
+  const schema = runtimeInputJsonSchema(fields);
+  const required = schema.required as string[];
+  return {
+    pipelineIdentifier: (input?.pipeline_id as string | undefined) ?? null,
+    inputSetTemplateYaml: templateYaml,
+    hasInputSets: r.data?.hasInputSets ?? false,
+    fields,
+    jsonSchema: schema,
+    _hint: fields.length === 0
+      ? "This pipeline has no runtime inputs. You can execute it without providing any inputs."
+      : `Pass an inputs object matching jsonSchema to harness_execute(resource_type='pipeline', action='run', inputs={...}).${required.length > 0 ? ` Required: ${required.join(", ")}.` : ""} Fields with the same key at several paths are filled together; for structural inputs (CI codebase, template inputs) prefer input_set_ids or the full YAML.`,
+  };
+};
+
+/**
+ * Extracts a resource constraint's queue from
+ * GET /pipeline/api/resourceConstraints/executionInfo. Splits the consumers
+ * into `holders` (ACTIVE — running inside the constraint) and `queue`
+ * (BLOCKED — waiting, in start order with their position).
+ */
+export const constraintQueueExtract = (raw: unknown, input?: Record<string, unknown>): unknown => {
+  const r = raw as { data?: { name?: string; capacity?: number; resourceConstraints?: Array<Record<string, unknown>> } };
+  const consumers = (r.data?.resourceConstraints ?? []).map((c) => ({
+    pipelineIdentifier: c.pipelineIdentifier ?? null,
+    pipelineName: c.pipelineName ?? null,
+    planExecutionId: c.planExecutionId ?? null,
+    orgIdentifier: c.orgIdentifier ?? null,
+    projectIdentifier: c.projectIdentifier ?? null,
+    state: c.state ?? null,
+    startTs: c.startTs ?? null,
+  }));
+  const byStart = (a: { startTs: unknown }, b: { startTs: unknown }) => Number(a.startTs ?? 0) - Number(b.startTs ?? 0);
+  return {
+    resourceUnit: (input?.resource_unit as string | undefined) ?? null,
+    name: r.data?.name ?? null,
+    capacity: r.data?.capacity ?? null,
+    holders: consumers.filter((c) => c.state === "ACTIVE").sort(byStart),
+    queue: consumers.filter((c) => c.state === "BLOCKED").sort(byStart).map((c, i) => ({ position: i + 1, ...c })),
+  };
+};
+
 /**
  * Extracts the dynamic-execution response for
  * POST /v1/orgs/{org}/projects/{project}/pipelines/{pipeline}/execute/dynamic.
@@ -411,6 +462,60 @@ export const ccmBreakdownExtract = (raw: unknown): { items: unknown[]; total: nu
   };
 };
 
+/**
+ * Extracts a batched perspective grid (aliased `gridN` / `totalN` per group-by
+ * dimension). Rows are tagged with the dimension they belong to; `groups`
+ * keeps the per-dimension totals.
+ */
+export const ccmBatchedBreakdownExtract = (
+  raw: unknown,
+  groupBys: string[],
+): { items: unknown[]; total: number; groups: Record<string, { total: number; returned: number }> } => {
+  const data = (raw as { data?: Record<string, unknown> }).data ?? {};
+  const items: unknown[] = [];
+  const groups: Record<string, { total: number; returned: number }> = {};
+  groupBys.forEach((field, i) => {
+    const rows = (data[`grid${i}`] as { data?: unknown[] } | null | undefined)?.data ?? [];
+    const total = typeof data[`total${i}`] === "number" ? (data[`total${i}`] as number) : rows.length;
+    for (const row of rows) items.push(isRecord(row) ? { group_by: field, ...row } : row);
+    groups[field] = { total, returned: rows.length };
+  });
+  return { items, total: Object.values(groups).reduce((sum, g) => sum + g.total, 0), groups };
+};
+
+/**
+ * Extracts a cluster perspective grid, flattening each row's `clusterData`
+ * into snake_case cost fields alongside name/cost/trend.
+ */
+export const ccmClusterBreakdownExtract = (raw: unknown): { items: unknown[]; total: number } => {
+  const { items, total } = ccmBreakdownExtract(raw);
+  return {
+    total,
+    items: items.map((row) => {
+      const r = row as { name?: string; id?: string; cost?: number; costTrend?: number; clusterData?: Record<string, unknown> | null };
+      const c = r.clusterData ?? {};
+      return {
+        name: r.name,
+        id: r.id,
+        cost: r.cost,
+        cost_trend: r.costTrend,
+        cluster: c.clusterName,
+        namespace: c.namespace,
+        workload: c.workloadName,
+        workload_type: c.workloadType,
+        idle_cost: c.idleCost,
+        unallocated_cost: c.unallocatedCost,
+        utilized_cost: c.utilizedCost,
+        system_cost: c.systemCost,
+        cpu_cost: c.cpuBillingAmount,
+        memory_cost: c.memoryBillingAmount,
+        storage_cost: c.storageCost,
+        efficiency_score: c.efficiencyScore,
+      };
+    }),
+  };
+};
+
 /**
  * Extracts CCM cost time series stats from GraphQL perspectiveTimeSeriesStats response.
  * Returns the `stats` array from `data.perspectiveTimeSeriesStats.stats`.
@@ -463,6 +568,19 @@ export const dashboardListExtract = (raw: unknown): { items: unknown[]; total: n
   };
 };
 
+/** Unwrap a dashboard service response: `{ resource: {...} }` → `{...}`. */
+export const dashboardResourceExtract = (raw: unknown): unknown => {
+  if (isRecord(raw) && "resource" in raw) return raw.resource;
+  return raw;
+};
+
+/** Extract dashboard tiles: `{ resource: [...] }`. */
+export const dashboardTileListExtract = (raw: unknown): { items: unknown[]; total: number } => {
+  const resource = dashboardResourceExtract(raw);
+  const items = Array.isArray(resource) ? resource : [];
+  return { items, total: items.length };
+};
+
 /**
  * Extracts dashboard data from a ZIP ArrayBuffer containing CSVs.
  * Matches v1 `get_dashboard_data` behavior: ZIP → CSV → structured JSON tables.
@@ -797,3 +915,288 @@ export const fmeGetExtract = (raw: unknown): unknown => {
   }
   return raw;
 };
+
+// ---------------------------------------------------------------------------
+// FirstGen (CG) GraphQL extractors
+// ---------------------------------------------------------------------------
+
+/**
+ * The CG GraphQL API answers 200 with `{ data: null, errors: [...] }` for bad
+ * IDs and permission failures. Surface those as errors instead of empty data.
+ */
+function cgGqlData(raw: unknown, field: string): unknown {
+  const r = raw as { data?: Record<string, unknown> | null; errors?: Array<{ message?: string }> };
+  const value = r.data?.[field];
+  if ((value === undefined || value === null) && Array.isArray(r.errors) && r.errors.length > 0) {
+    throw new Error(`FirstGen GraphQL error: ${r.errors.map((e) => e.message ?? "unknown error").join("; ")}`);
+  }
+  return value;
+}
+
+/** Extract a CG GraphQL connection: `{ data: { <field>: { nodes, pageInfo: { total, hasMore } } } }`. */
+export const cgGqlListExtract = (field: string) => (raw: unknown): { items: unknown[]; total: number; has_more?: boolean } => {
+  const conn = cgGqlData(raw, field) as { nodes?: unknown[]; pageInfo?: { total?: number; hasMore?: boolean } } | undefined;
+  const items = conn?.nodes ?? [];
+  return { items, total: conn?.pageInfo?.total ?? items.length, has_more: conn?.pageInfo?.hasMore };
+};
+
+/** Extract a single CG GraphQL object: `{ data: { <field>: {...} } }`. */
+export const cgGqlGetExtract = (field: string) => (raw: unknown): unknown => cgGqlData(raw, field) ?? null;
+
+// ---------------------------------------------------------------------------
+// Delegate task log extractors
+// ---------------------------------------------------------------------------
+
+/**
+ * Extract delegate task logs: `{ data: { content: [...], pageToken } }`.
+ * The API pages by opaque token rather than page number.
+ */
+export const delegateTaskLogExtract = (raw: unknown): { items: unknown[]; next_page_token?: string } => {
+  const r = raw as { data?: { content?: unknown[]; pageToken?: string } };
+  return { items: r.data?.content ?? [], next_page_token: r.data?.pageToken || undefined };
+};
+
+// ---------------------------------------------------------------------------
+// Connector catalogue extractor
+// ---------------------------------------------------------------------------
+
+const CONNECTOR_CATEGORY_DOCS: Record<string, string> = {
+  CLOUD_PROVIDER: "https://developer.harness.io/docs/platform/connectors/cloud-providers/",
+  CODE_REPO: "https://developer.harness.io/docs/platform/connectors/code-repositories/",
+  ARTIFACTORY: "https://developer.harness.io/docs/platform/connectors/artifact-repositories/",
+  SECRET_MANAGER: "https://developer.harness.io/docs/platform/secrets/secrets-management/",
+  MONITORING: "https://developer.harness.io/docs/platform/connectors/monitoring-and-logging-systems/",
+  TICKETING: "https://developer.harness.io/docs/platform/connectors/ticketing-systems/",
+  CLOUD_COST: "https://developer.harness.io/docs/cloud-cost-management/get-started/",
+};
+const CONNECTOR_DOCS_FALLBACK = "https://developer.harness.io/docs/category/connectors";
+
+/**
+ * Flatten `{ data: { catalogue: [{ category, connectors: [type] }] } }` into
+ * categories with docs links, plus a type → category index for quick lookup.
+ */
+export const connectorCatalogueExtract = (raw: unknown): unknown => {
+  const data = (raw as { data?: { catalogue?: Array<{ category?: string; connectors?: string[] }> } })?.data;
+  if (!Array.isArray(data?.catalogue)) return ngExtract(raw);
+  const categories = data.catalogue.map((entry) => ({
+    category: entry.category,
+    docs_url: (entry.category && CONNECTOR_CATEGORY_DOCS[entry.category]) ?? CONNECTOR_DOCS_FALLBACK,
+    types: entry.connectors ?? [],
+  }));
+  return {
+    categories,
+    total_types: categories.reduce((n, c) => n + c.types.length, 0),
+    _hint: "For a skeleton spec of a type, call harness_schema(resource_type='connector', example_search='<Type>') and then harness_schema(example='<name>'). harness_schema(resource_type='connector', path='<Type>') returns the full field schema.",
+  };
+};
+
+/** Extract instance sync details: `{ data: { instanceDetailsDTOList: [...] } }`. */
+export const serviceInstanceExtract = (raw: unknown): { items: unknown[]; total: number } => {
+  const r = raw as { data?: { instanceDetailsDTOList?: unknown[] } };
+  const items = r.data?.instanceDetailsDTOList ?? [];
+  return { items, total: items.length };
+};
+
+/**
+ * CD license usage: `{ data: { activeServices: { count }, activeServiceInstances: { count }, serviceLicenses: { count } } }`.
+ * Reference lists are dropped — counts answer the licensing question, and
+ * per-service detail is available from artifact_promotion and service_instance.
+ */
+export const activeInstanceCountExtract = (raw: unknown): Record<string, unknown> => {
+  const data = ((raw as { data?: unknown })?.data ?? {}) as Record<string, { count?: number } | number | string | undefined>;
+  const count = (key: string): number | undefined => {
+    const value = data[key];
+    return typeof value === "object" && value !== null && typeof value.count === "number" ? value.count : undefined;
+  };
+  return {
+    active_service_instances: count("activeServiceInstances"),
+    active_services: count("activeServices"),
+    service_licenses: count("serviceLicenses"),
+    ...(typeof data.timestamp === "number" ? { as_of: new Date(data.timestamp).toISOString() } : {}),
+  };
+};
+
+/** True when a deployed build ID is `artifact`, exactly or as its tag (`repo/app:412`). */
+function isArtifactMatch(buildId: string, artifact: string): boolean {
+  return buildId === artifact || buildId.endsWith(`:${artifact}`) || buildId.endsWith(`/${artifact}`);
+}
+
+/**
+ * Per-environment running artifacts for a service, from instance sync:
+ * `{ data: { envBuildIdAndInstanceCountInfoList: [{ envId, envName, buildIdAndInstanceCountList: [{ buildId, count }] }] } }`.
+ * With `input.artifact`, also reports which environments run that build.
+ */
+export const artifactPromotionExtract = (raw: unknown, input?: Record<string, unknown>): Record<string, unknown> => {
+  const list = (raw as { data?: { envBuildIdAndInstanceCountInfoList?: unknown[] } })?.data?.envBuildIdAndInstanceCountInfoList ?? [];
+  const items = list.map((entry) => {
+    const env = (entry ?? {}) as { envId?: string; envName?: string; buildIdAndInstanceCountList?: Array<{ buildId?: string; count?: number }> };
+    const artifacts = (env.buildIdAndInstanceCountList ?? [])
+      .filter((b) => typeof b.buildId === "string")
+      .map((b) => ({ build_id: b.buildId as string, instances: b.count ?? 0 }))
+      .sort((a, b) => b.instances - a.instances);
+    return {
+      environment_id: env.envId,
+      environment_name: env.envName,
+      current_artifact: artifacts[0]?.build_id,
+      // More than one build means a rollout is in progress or only partly completed
+      ...(artifacts.length > 1 ? { mixed_versions: true } : {}),
+      artifacts,
+    };
+  });
+  const artifact = typeof input?.artifact === "string" && input.artifact ? input.artifact : undefined;
+  if (!artifact) return { items, total: items.length };
+  const runningIn = items.filter((env) => env.artifacts.some((a) => isArtifactMatch(a.build_id, artifact)));
+  return {
+    items,
+    total: items.length,
+    artifact,
+    deployed_in: runningIn.map((env) => env.environment_name ?? env.environment_id),
+    not_deployed_in: items.filter((env) => !runningIn.includes(env)).map((env) => env.environment_name ?? env.environment_id),
+  };
+};
+
+// ---------------------------------------------------------------------------
+// CCM asset governance extractors
+// ---------------------------------------------------------------------------
+
+/** Extract governance rules: `{ data: { rules: [...], totalItems } }`. */
+export const governanceRuleListExtract = (raw: unknown): { items: unknown[]; total: number } => {
+  const r = raw as { data?: { rules?: unknown[]; totalItems?: number } };
+  const items = r.data?.rules ?? [];
+  return { items, total: r.data?.totalItems ?? items.length };
+};
+
+/** Single rule from a rule list filtered by ID; throws when it is absent. */
+export const governanceRuleGetExtract = (raw: unknown): unknown => {
+  const rule = governanceRuleListExtract(raw).items[0];
+  if (!rule) throw new Error("Asset governance rule not found.");
+  return rule;
+};
+
+/** Extract rule evaluations: `{ data: { ruleExecution: [...], totalItems } }`. */
+export const governanceExecutionListExtract = (raw: unknown): { items: unknown[]; total: number } => {
+  const r = raw as { data?: { ruleExecution?: unknown[]; totalItems?: number } };
+  const items = r.data?.ruleExecution ?? [];
+  return { items, total: r.data?.totalItems ?? items.length };
+};
+
+// ---------------------------------------------------------------------------
+// Harness Code search extractors
+// ---------------------------------------------------------------------------
+
+/** Last segment of a Code repo path (`account/org/project/repo` → `repo`). */
+function repoIdFromPath(repoPath: unknown): string | undefined {
+  return typeof repoPath === "string" ? repoPath.split("/").filter(Boolean).pop() : undefined;
+}
+
+/**
+ * Normalize Code search results into one item per file with line snippets.
+ * Keyword search returns `{ file_matches: [{ repo_path, file_name, matches: [{ line_num, fragments }] }], stats }`;
+ * semantic search returns `{ results: [{ repo_path, file_path, start_line, end_line, content }] }`.
+ */
+export const codeSearchExtract = (raw: unknown): { items: unknown[]; total: number; total_matches?: number } => {
+  if (!isRecord(raw)) return { items: [], total: 0 };
+
+  if (Array.isArray(raw.file_matches)) {
+    const items = raw.file_matches.filter(isRecord).map((file) => {
+      const matches = (Array.isArray(file.matches) ? file.matches : []).filter(isRecord);
+      return {
+        repo_id: repoIdFromPath(file.repo_path),
+        repo_path: file.repo_path,
+        file_path: file.file_name,
+        ref: file.repo_branch,
+        language: file.language,
+        matches: matches.map((m) => ({
+          line: m.line_num,
+          snippet: (Array.isArray(m.fragments) ? m.fragments : [])
+            .filter(isRecord)
+            .map((f) => `${String(f.pre ?? "")}${String(f.match ?? "")}${String(f.post ?? "")}`)
+            .join(""),
+        })),
+      };
+    });
+    const stats = isRecord(raw.stats) ? raw.stats : {};
+    return {
+      items,
+      total: typeof stats.total_files === "number" ? stats.total_files : items.length,
+      ...(typeof stats.total_matches === "number" ? { total_matches: stats.total_matches } : {}),
+    };
+  }
+
+  const results = (Array.isArray(raw.results) ? raw.results : []).filter(isRecord);
+  const items = results.map((r) => ({
+    repo_id: repoIdFromPath(r.repo_path) ?? r.repo_identifier,
+    repo_path: r.repo_path,
+    file_path: r.file_path,
+    matches: [{ line: r.start_line, end_line: r.end_line, snippet: r.content ?? r.snippet }],
+    score: r.score,
+  }));
+  return { items, total: items.length };
+};
+
+/**
+ * Extract a pull request's changed files from the Code diff endpoint. Each
+ * entry's `patch` is serialized as base64 bytes; decode it to diff text.
+ */
+export const prFileDiffExtract = (raw: unknown): { items: unknown[]; total: number } => {
+  const files = (Array.isArray(raw) ? raw : []).filter(isRecord);
+  const items = files.map((f) => ({
+    path: f.path,
+    old_path: f.old_path || undefined,
+    status: f.status,
+    additions: f.additions,
+    deletions: f.deletions,
+    is_binary: f.is_binary === true ? true : undefined,
+    ...(typeof f.patch === "string" ? { patch: Buffer.from(f.patch, "base64").toString("utf-8") } : {}),
+  }));
+  return { items, total: items.length };
+};
+
+/** Extract a HAR registry's cleanup policies: `{ data: { cleanupPolicy: [...] } }`. */
+export const harCleanupPolicyExtract = (raw: unknown): { registry_id: unknown; items: unknown[]; total: number } => {
+  const data = (raw as { data?: { identifier?: unknown; cleanupPolicy?: unknown[] } }).data;
+  const items = data?.cleanupPolicy ?? [];
+  return { registry_id: data?.identifier, items, total: items.length };
+};
+
+// ---------------------------------------------------------------------------
+// Ticketing extractors
+// ---------------------------------------------------------------------------
+
+/**
+ * Extract a Jira issue from `{ data: { key, url, fields: { Status, Summary, ... } } }`.
+ * Harness returns fields keyed by display name; status and summary are lifted out.
+ */
+export const jiraIssueExtract = (raw: unknown): unknown => {
+  const issue = (raw as { data?: unknown })?.data as { key?: string; url?: string; fields?: Record<string, unknown> } | undefined;
+  if (!issue || typeof issue !== "object") return ngExtract(raw);
+  const fields = issue.fields ?? {};
+  const status = fields.Status ?? fields.status;
+  return {
+    key: issue.key,
+    url: issue.url,
+    status: status && typeof status === "object" ? (status as { name?: unknown }).name : status,
+    summary: fields.Summary ?? fields.summary,
+    fields,
+  };
+};
+
+// ---------------------------------------------------------------------------
+// Continuous Verification (CV) extractors
+// ---------------------------------------------------------------------------
+
+/** CV responses come as `{ resource: ... }`, `{ data: ... }`, or bare depending on the endpoint. */
+function cvPayload(raw: unknown): unknown {
+  const r = raw as { resource?: unknown; data?: unknown } | undefined;
+  return r?.resource ?? r?.data ?? raw;
+}
+
+/** Extract a Verify step's analysis overview. */
+export const cvExtract = (raw: unknown): unknown => cvPayload(raw);
+
+/** Extract a CV page: `{ resource: { content: [...], totalItems } }`. */
+export const cvPageExtract = (raw: unknown): { items: unknown[]; total: number } => {
+  const page = cvPayload(raw) as { content?: unknown[]; totalItems?: number } | undefined;
+  const items = Array.isArray(page?.content) ? page.content : [];
+  return { items, total: page?.totalItems ?? items.length };
+};
@@ -0,0 +1,81 @@
+/**
+ * Git Experience parameters shared by entities that can be stored in Git
+ * (pipelines, input sets, templates, services, environments, infrastructure
+ * definitions).
+ *
+ * Callers pass them flat (`branch`, `repo_name`, ...) or grouped under
+ * `git_details`; `expandGitDetails` flattens the group before dispatch so
+ * every endpoint only has to declare the query params below.
+ */
+
+import { isRecord } from "../utils/type-guards.js";
+
+/** Query params for reading a remote entity. */
+export const GIT_READ_QUERY_PARAMS: Record<string, string> = {
+  branch: "branch",
+  repo_name: "repoName",
+  connector_ref: "connectorRef",
+  store_type: "storeType",
+  load_from_fallback_branch: "loadFromFallbackBranch",
+};
+
+/** Query params for creating a remote entity, or committing an update to one. */
+export const GIT_WRITE_QUERY_PARAMS: Record<string, string> = {
+  store_type: "storeType",
+  connector_ref: "connectorRef",
+  repo_name: "repoName",
+  branch: "branch",
+  file_path: "filePath",
+  base_branch: "baseBranch",
+  commit_msg: "commitMsg",
+  is_new_branch: "isNewBranch",
+  is_harness_code_repo: "isHarnessCodeRepo",
+};
+
+/** `git_details` keys → flat input keys. Harness's own camelCase names are accepted too. */
+const GIT_DETAIL_KEYS: Record<string, string> = {
+  branch: "branch",
+  branch_name: "branch",
+  branchName: "branch",
+  repo_name: "repo_name",
+  repoName: "repo_name",
+  connector_ref: "connector_ref",
+  connectorRef: "connector_ref",
+  store_type: "store_type",
+  storeType: "store_type",
+  load_from_fallback_branch: "load_from_fallback_branch",
+  load_from_fallback: "load_from_fallback_branch",
+  loadFromFallbackBranch: "load_from_fallback_branch",
+  file_path: "file_path",
+  filePath: "file_path",
+  base_branch: "base_branch",
+  baseBranch: "base_branch",
+  commit_msg: "commit_msg",
+  commitMsg: "commit_msg",
+  is_new_branch: "is_new_branch",
+  isNewBranch: "is_new_branch",
+  is_harness_code_repo: "is_harness_code_repo",
+  isHarnessCodeRepo: "is_harness_code_repo",
+};
+
+/**
+ * Flatten `input.git_details` into top-level input keys. Explicit top-level
+ * values win; unknown keys are rejected so a typo does not silently read the
+ * default branch.
+ */
+export function expandGitDetails(input: Record<string, unknown>): Record<string, unknown> {
+  const details = input.git_details;
+  if (details === undefined || details === null) return input;
+  if (!isRecord(details)) {
+    throw new Error("git_details must be an object, e.g. { branch: 'main', repo_name: 'pipelines', connector_ref: 'github' }.");
+  }
+  const { git_details: _details, ...rest } = input;
+  for (const [key, value] of Object.entries(details)) {
+    const target = GIT_DETAIL_KEYS[key];
+    if (!target) {
+      throw new Error(`Unknown git_details field '${key}'. Supported: branch, repo_name, connector_ref, store_type, load_from_fallback_branch, file_path, base_branch, commit_msg, is_new_branch, is_harness_code_repo.`);
+    }
+    if (rest[target] === undefined && value !== undefined && value !== null && value !== "") rest[target] = value;
+  }
+  return rest;
+}
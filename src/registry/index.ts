@@ -1,13 +1,24 @@
 import { randomUUID } from "node:crypto";
 import { type Config, resolveProductBaseUrl } from "../config.js";
 import type { HarnessClient } from "../client/harness-client.js";
-import { HarnessApiError } from "../utils/errors.js";
+import type { FeatureFlagEvaluator } from "../client/feature-flags.js";
+import { HarnessApiError, getCorrelationId, tagCorrelationId } from "../utils/errors.js";
 import type { ResourceDefinition, ToolsetDefinition, ToolsetName, OperationName, EndpointSpec, FilterFieldSpec, ResourceScope } from "./types.js";
 import type { AuditManager } from "../audit/manager.js";
 import type { AuditContext, AuditEvent, AuditOutcome } from "../audit/types.js";
 import { createLogger } from "../utils/logger.js";
-import { buildDeepLink, appendStoreType } from "../utils/deep-links.js";
+import { buildDeepLink, appendStoreType, harnessUiBaseUrl } from "../utils/deep-links.js";
+import { expandGitDetails } from "./git-details.js";
+import { TOOLSET_PRESETS, TOOLSET_PRESET_NAMES, restrictToolset, type ToolsetAccess } from "./toolset-presets.js";
 import { isFormDataBody } from "../utils/type-guards.js";
+import { createDiskCache, type DiskCache } from "../utils/disk-cache.js";
+import { recordCacheLookup } from "../utils/server-metrics.js";
+import { hasPermission } from "../utils/permission-cache.js";
+import { currentTraceId } from "../utils/trace-id.js";
+import { ApiBudget, ApiBudgetExceededError, runWithApiBudget } from "../utils/api-budget.js";
+import { bodySchemaViolations, filterViolations, isStrictSchemas, strictInputError } from "../utils/strict-schemas.js";
+import { projectListExtract } from "./extractors.js";
+import type { PlatformCapabilities } from "./capabilities.js";
 
 // Import all toolsets
 import { pipelinesToolset } from "./toolsets/pipelines.js";
@@ -47,14 +58,20 @@ import { iacmToolset } from "./toolsets/iacm.js";
 import { knowledgeGraphToolset } from "./toolsets/knowledge-graph.js";
 import { semanticLayerToolset } from "./toolsets/semantic-layer.js";
 import { ansibleToolset } from "./toolsets/ansible.js";
+import { firstgenToolset } from "./toolsets/firstgen.js";
 import { incidentsToolset } from "./toolsets/incidents.js";
 import { deploysToolset } from "./toolsets/deploys.js";
+import { rawApiToolset } from "./toolsets/raw-api.js";
 
 const log = createLogger("registry");
 
 /** Keys under which different Harness APIs return list arrays. */
 const LIST_ARRAY_KEYS = ["items", "features", "content", "data", "objects"];
 const RESOURCE_SCOPES: readonly ResourceScope[] = ["account", "org", "project"];
+/** Upper bound on projects queried by one cross-project rollup list. */
+const ROLLUP_MAX_PROJECTS = 50;
+/** Per-project list calls in flight at once during a rollup (the client rate limiter still applies). */
+const ROLLUP_CONCURRENCY = 5;
 
 /** Backward-compatible aliases for renamed public toolset names. */
 const TOOLSET_ALIASES: Record<string, string> = {
@@ -72,6 +89,14 @@ function getSupportedScopes(def: ResourceDefinition): readonly ResourceScope[] {
   return [def.scope];
 }
 
+/** The rollup scope requested for a list, when the resource rolls up at that scope instead of supporting it natively. */
+function getRollupScope(def: ResourceDefinition, input: Record<string, unknown>): "org" | "account" | undefined {
+  const value = input.resource_scope;
+  if (!def.rollupScopes || !isResourceScope(value) || value === "project") return undefined;
+  if (getSupportedScopes(def).includes(value)) return undefined;
+  return def.rollupScopes.includes(value) ? value : undefined;
+}
+
 function getRequestedScope(def: ResourceDefinition, input: Record<string, unknown>): ResourceScope | undefined {
   const value = input.resource_scope;
   if (value === undefined || value === "") {
@@ -82,8 +107,9 @@ function getRequestedScope(def: ResourceDefinition, input: Record<string, unknow
   }
   const supported = getSupportedScopes(def);
   if (!supported.includes(value)) {
+    const rollup = def.rollupScopes?.length ? ` (list also rolls up across projects at ${def.rollupScopes.join("/")} scope)` : "";
     throw new Error(
-      `${def.resourceType} does not support ${value} scope. Supported scopes: ${supported.join(", ")}`,
+      `${def.resourceType} does not support ${value} scope. Supported scopes: ${supported.join(", ")}${rollup}`,
     );
   }
   return value;
@@ -123,6 +149,53 @@ function getExplicitScopeValues(scope: ResourceScope, input: Record<string, unkn
   return { orgId, projectId };
 }
 
+/** Argument names agents use to pass an account identifier, at the top level or in a body. */
+const ACCOUNT_ARGUMENT_KEYS = ["account_id", "accountIdentifier", "account_identifier"] as const;
+/** Account placeholder the templates API uses for global templates; always allowed. */
+const GLOBAL_TEMPLATES_ACCOUNT_ID = "__GLOBAL_TEMPLATES_ACCOUNT_ID__";
+
+/**
+ * Guard against account switching: the server's credentials only reach the
+ * authenticated account, so an explicit account argument for any other
+ * account can only end in a confusing 403. With HARNESS_ACCOUNT_MISMATCH=reject
+ * (default) the call fails with an explanation; with `ignore` the mismatched
+ * arguments are dropped and the authenticated account is used.
+ */
+function resolveAccountArguments(
+  input: Record<string, unknown>,
+  accountId: string,
+  mode: "reject" | "ignore",
+): Record<string, unknown> {
+  const isForeign = (value: unknown): value is string =>
+    typeof value === "string" && value !== "" && value !== accountId && value !== GLOBAL_TEMPLATES_ACCOUNT_ID;
+  const strip = (record: Record<string, unknown>, where: string): Record<string, unknown> => {
+    let result = record;
+    for (const key of ACCOUNT_ARGUMENT_KEYS) {
+      const value = record[key];
+      if (!isForeign(value)) continue;
+      if (mode === "reject") {
+        throw new Error(
+          `${where}${key} "${value}" does not match the authenticated account "${accountId}". ` +
+          `This session can only reach account "${accountId}"; a request for another account would fail with 403. ` +
+          `Omit ${key} to use the session's account, or connect with credentials for "${value}".`,
+        );
+      }
+      log.warn(`Ignoring ${where}${key} for a different account`, { requested: value, account: accountId });
+      if (result === record) result = { ...record };
+      delete result[key];
+    }
+    return result;
+  };
+
+  let resolved = strip(input, "");
+  const body = resolved.body;
+  if (body && typeof body === "object" && !Array.isArray(body) && !isFormDataBody(body)) {
+    const resolvedBody = strip(body as Record<string, unknown>, "body.");
+    if (resolvedBody !== body) resolved = { ...resolved, body: resolvedBody };
+  }
+  return resolved;
+}
+
 const ALL_TOOLSETS: ToolsetDefinition[] = [
   pipelinesToolset,
   agentsToolset,
@@ -161,8 +234,10 @@ const ALL_TOOLSETS: ToolsetDefinition[] = [
   knowledgeGraphToolset,
   semanticLayerToolset,
   ansibleToolset,
+  firstgenToolset,
   incidentsToolset,
   deploysToolset,
+  rawApiToolset,
 ];
 
 /** All available toolset names — used by docs generation to discover opt-in toolsets. */
@@ -183,6 +258,12 @@ export interface RegistryOptions {
   accountIdResolver?: () => string | undefined;
   /** When provided, every dispatch emits an AuditEvent to all registered sinks. */
   auditManager?: AuditManager;
+  /** Result of the startup capability probe — unavailable toolsets are dropped and the platform version is reported by harness_describe. */
+  capabilities?: PlatformCapabilities;
+  /** Toolsets the session's caller may not see (HARNESS_TOOLSET_PERMISSIONS); dropped like unavailable ones. */
+  hiddenToolsets?: string[];
+  /** Evaluates `featureFlag` on toolsets and resource types for this registry's account; gated definitions are hidden without it. */
+  featureFlags?: FeatureFlagEvaluator;
 }
 
 /**
@@ -193,15 +274,41 @@ export class Registry {
   private toolsets: ToolsetDefinition[] = [];
   private accountIdResolver?: () => string | undefined;
   private auditManager?: AuditManager;
+  private diskCache?: DiskCache;
+  private capabilities?: PlatformCapabilities;
+  /** In-flight and recent responses for endpoints with `responseReuseTtlMs`. */
+  private reusableResponses = new Map<string, { expiresAt: number; response: Promise<unknown> }>();
+  /** HARNESS_API_BUDGET_* accounting for this session. */
+  readonly apiBudget: ApiBudget;
+  /** Reduced access for toolsets enabled through a HARNESS_TOOLSETS preset. */
+  private toolsetAccess = new Map<string, ToolsetAccess>();
 
   constructor(private config: Config, options: RegistryOptions = {}) {
     this.accountIdResolver = options.accountIdResolver;
     this.auditManager = options.auditManager;
+    this.diskCache = createDiskCache(this.config.HARNESS_CACHE_DIR, "registry");
+    this.apiBudget = new ApiBudget({
+      perSession: this.config.HARNESS_API_BUDGET_PER_SESSION ?? 0,
+      perHour: this.config.HARNESS_API_BUDGET_PER_HOUR ?? 0,
+    });
     const allToolsets = [...ALL_TOOLSETS, ...(options.additionalToolsets ?? [])];
     const enabledNames = this.parseToolsetFilter(allToolsets);
     this.toolsets = enabledNames
       ? allToolsets.filter((t) => enabledNames.has(t.name))
       : allToolsets.filter((t) => !t.optIn);
+    if (this.toolsetAccess.size > 0) {
+      this.toolsets = this.toolsets.map((t) => restrictToolset(t, this.toolsetAccess.get(t.name) ?? "full"));
+    }
+    this.capabilities = options.capabilities;
+    if (this.capabilities?.unavailableToolsets.length) {
+      const unavailable = new Set(this.capabilities.unavailableToolsets);
+      this.toolsets = this.toolsets.filter((t) => !unavailable.has(t.name));
+    }
+    if (options.hiddenToolsets?.length) {
+      const hidden = new Set(options.hiddenToolsets);
+      this.toolsets = this.toolsets.filter((t) => !hidden.has(t.name));
+    }
+    this.toolsets = this.applyFeatureFlags(this.toolsets, options.featureFlags);
 
     for (const toolset of this.toolsets) {
       for (const resource of toolset.resources) {
@@ -214,10 +321,41 @@ export class Registry {
     });
   }
 
+  /** Drop toolsets and resource types whose `featureFlag` is off (or unknown) for this account. */
+  private applyFeatureFlags(toolsets: ToolsetDefinition[], flags: FeatureFlagEvaluator | undefined): ToolsetDefinition[] {
+    const accountId = this.getAccountId();
+    const enabled = (flag: string | undefined) => !flag || (flags?.isEnabled(flag, accountId) ?? false);
+    const gated: string[] = [];
+    const out = toolsets.flatMap((t) => {
+      if (!enabled(t.featureFlag)) {
+        gated.push(t.name);
+        return [];
+      }
+      if (!t.resources.some((r) => r.featureFlag)) return [t];
+      const resources = t.resources.filter((r) => enabled(r.featureFlag));
+      gated.push(...t.resources.filter((r) => !resources.includes(r)).map((r) => r.resourceType));
+      return [{ ...t, resources }];
+    });
+    if (gated.length > 0) log.debug("Hidden by feature flags", { accountId, gated });
+    return out;
+  }
+
   getAccountId(): string {
     return this.accountIdResolver?.() ?? this.config.HARNESS_ACCOUNT_ID;
   }
 
+  /** Toolsets (and the resource to list) checked by the startup capability probe. */
+  getProbeTargets(): Array<{ toolset: string; resourceType: string }> {
+    return this.toolsets
+      .filter((t) => t.probeResource && this.resourceMap.has(t.probeResource))
+      .map((t) => ({ toolset: t.name, resourceType: t.probeResource! }));
+  }
+
+  /** Disk cache for a non-registry consumer (e.g. live entity schemas); undefined when HARNESS_CACHE_DIR is unset. */
+  getDiskCache(namespace: string): DiskCache | undefined {
+    return createDiskCache(this.config.HARNESS_CACHE_DIR, namespace);
+  }
+
   /**
    * Parse HARNESS_TOOLSETS env var. Supports three modes:
    *
@@ -226,8 +364,13 @@ export class Registry {
    *  - Subtractive (-):  "-chaos,-ccm"                → defaults minus chaos & ccm
    *  - Mixed +/-:        "+ai-evals,-chaos"            → defaults + ai-evals - chaos
    *
+   * A preset name (`recommended`, see TOOLSET_PRESETS) or `all` (every
+   * toolset, opt-in ones included) stands for its toolsets anywhere a name is
+   * accepted; with +/- modifiers, a bare preset replaces the defaults as the
+   * base ("recommended,-ccm"). Preset toolsets may come with reduced access,
+   * recorded in `toolsetAccess`; naming a toolset directly restores full access.
+   *
    * Returns `null` when the value is empty (meaning "all defaults").
-   * Returns `"defaults"` when +/- modifiers are used (caller applies them).
    */
   private parseToolsetFilter(allToolsets: ToolsetDefinition[]): Set<string> | null {
     const raw = this.config.HARNESS_TOOLSETS;
@@ -237,65 +380,79 @@ export class Registry {
     const parsed = raw.split(",").map((s) => s.trim()).filter(Boolean);
 
     const hasModifiers = parsed.some((s) => s.startsWith("+") || s.startsWith("-"));
+    const presetAccess = new Map<string, ToolsetAccess>();
+    const fullAccess = new Set<string>();
+    const invalid: string[] = [];
+
+    /** Toolset names a token stands for; a preset also records its access levels. */
+    const expand = (rawName: string): string[] => {
+      if (rawName === "all") {
+        for (const name of validNames) fullAccess.add(name);
+        return Array.from(validNames);
+      }
+      const preset = TOOLSET_PRESETS[rawName];
+      if (preset) {
+        const names = Object.keys(preset.toolsets).filter((name) => validNames.has(name));
+        for (const name of names) presetAccess.set(name, preset.toolsets[name]!);
+        return names;
+      }
+      const name = TOOLSET_ALIASES[rawName] ?? rawName;
+      if (!validNames.has(name)) {
+        invalid.push(rawName);
+        return [];
+      }
+      fullAccess.add(name);
+      return [name];
+    };
+    const isPreset = (name: string) => name === "all" || TOOLSET_PRESETS[name] !== undefined;
 
+    let enabled: Set<string>;
     if (hasModifiers) {
-      const defaults = new Set(allToolsets.filter((t) => !t.optIn).map((t) => t.name));
-      const invalid: string[] = [];
+      const bases = parsed.filter(isPreset);
+      enabled = bases.length > 0
+        ? new Set(bases.flatMap(expand))
+        : new Set(allToolsets.filter((t) => !t.optIn).map((t) => t.name));
 
       for (const token of parsed) {
+        if (isPreset(token)) continue;
         const op = token[0];
         const rawName = (op === "+" || op === "-") ? token.slice(1) : token;
-        const name = TOOLSET_ALIASES[rawName] ?? rawName;
-        if (!validNames.has(name)) {
-          invalid.push(rawName);
-          continue;
-        }
-        if (op === "+") {
-          defaults.add(name);
-        } else if (op === "-") {
-          defaults.delete(name);
+        if (op === "-") {
+          if (isPreset(rawName)) {
+            const removed = rawName === "all" ? validNames : Object.keys(TOOLSET_PRESETS[rawName]!.toolsets);
+            for (const name of removed) enabled.delete(name);
+            continue;
+          }
+          const name = TOOLSET_ALIASES[rawName] ?? rawName;
+          if (!validNames.has(name)) invalid.push(rawName);
+          enabled.delete(name);
         } else {
-          defaults.add(name);
+          for (const name of expand(rawName)) enabled.add(name);
         }
       }
-
-      if (invalid.length > 0) {
-        const available = Array.from(validNames).sort().join(", ");
-        throw new Error(
-          `Invalid HARNESS_TOOLSETS: ${invalid.map((n) => `"${n}"`).join(", ")}. ` +
-          `Valid toolset names: ${available}`,
-        );
-      }
-
-      return defaults;
-    }
-
-    const valid: string[] = [];
-    const invalid: string[] = [];
-
-    for (const rawName of parsed) {
-      const name = TOOLSET_ALIASES[rawName] ?? rawName;
-      if (validNames.has(name)) {
-        valid.push(name);
-      } else {
-        invalid.push(rawName);
-      }
+    } else {
+      enabled = new Set(parsed.flatMap(expand));
     }
 
     if (invalid.length > 0) {
       const available = Array.from(validNames).sort().join(", ");
       throw new Error(
         `Invalid HARNESS_TOOLSETS: ${invalid.map((n) => `"${n}"`).join(", ")}. ` +
-        `Valid toolset names: ${available}`,
+        `Valid toolset names: ${available}. Presets: all, ${TOOLSET_PRESET_NAMES.join(", ")}`,
       );
     }
 
-    if (valid.length === 0) return null;
-    return new Set(valid);
+    for (const [name, access] of presetAccess) {
+      if (access !== "full" && enabled.has(name) && !fullAccess.has(name)) this.toolsetAccess.set(name, access);
+    }
+    if (enabled.size === 0) return null;
+    return enabled;
   }
 
   get orgId(): string | undefined { return this.config.HARNESS_ORG; }
   get projectId(): string | undefined { return this.config.HARNESS_PROJECT; }
+  /** How compacted list output treats fields outside the compaction whitelist (HARNESS_UNKNOWN_FIELDS). */
+  get unknownFieldsMode(): "strip" | "include" { return this.config.HARNESS_UNKNOWN_FIELDS ?? "strip"; }
 
   /** Get a resource definition by type, or throw. */
   getResource(resourceType: string): ResourceDefinition {
@@ -375,6 +532,7 @@ export class Registry {
   ): Promise<unknown> {
     const auditCtx = signalOrAudit instanceof AbortSignal ? undefined : signalOrAudit;
     const abortSignal = signalOrAudit instanceof AbortSignal ? signalOrAudit : signal;
+    input = expandGitDetails(input);
 
     if (this.config.HARNESS_READ_ONLY && !Registry.READ_OPERATIONS.has(operation)) {
       throw new Error(`Read-only mode is enabled (HARNESS_READ_ONLY=true). "${operation}" operations are not allowed.`);
@@ -399,6 +557,16 @@ export class Registry {
       }
     }
 
+    if (isStrictSchemas() && operation === "list" && def.listFilterFields) {
+      const violations = filterViolations(def.listFilterFields, input);
+      if (violations.length > 0) throw strictInputError(resourceType, "list filters", violations);
+    }
+
+    if (operation === "list") {
+      const rollupScope = getRollupScope(def, input);
+      if (rollupScope) return this.withApiBudget(() => this.dispatchRollup(client, def, rollupScope, input, abortSignal));
+    }
+
     if (spec.paramsSchema) {
       const missingParams = spec.paramsSchema.fields
         .filter(f => f.required && input[f.name] === undefined)
@@ -412,7 +580,101 @@ export class Registry {
       }
     }
 
-    return this.executeSpecWithAudit(client, def, spec, operation, resourceType, input, auditCtx, abortSignal);
+    return this.withApiBudget(() => this.executeSpecWithAudit(client, def, spec, operation, resourceType, input, auditCtx, abortSignal));
+  }
+
+  /** Count `fn`'s requests against this session's budget unless a tool call already does. */
+  private withApiBudget<T>(fn: () => Promise<T>): Promise<T> {
+    return runWithApiBudget(this.apiBudget, fn);
+  }
+
+  /**
+   * Roll a project-scoped list up to org or account scope: list the projects
+   * in scope, run the list once per project, and merge the items. Items are
+   * tagged with orgIdentifier/projectIdentifier when the API omits them, and
+   * per-project failures are reported alongside the merged items instead of
+   * failing the whole rollup.
+   */
+  private async dispatchRollup(
+    client: HarnessClient,
+    def: ResourceDefinition,
+    scope: "org" | "account",
+    input: Record<string, unknown>,
+    signal?: AbortSignal,
+  ): Promise<unknown> {
+    const orgId = scope === "org" ? resolveScopeString(input.org_id, this.config.HARNESS_ORG) : undefined;
+    if (scope === "org" && !orgId) {
+      throw new Error(`resource_scope "org" requires org_id or HARNESS_ORG.`);
+    }
+    const projectPage = projectListExtract(await client.request<unknown>({
+      method: "GET",
+      path: "/ng/api/projects",
+      params: { ...(orgId ? { orgIdentifier: orgId } : {}), pageSize: ROLLUP_MAX_PROJECTS },
+      signal,
+    }));
+    const projects = projectPage.items.flatMap((item) => {
+      const rec = item as Record<string, unknown>;
+      const org = typeof rec.orgIdentifier === "string" ? rec.orgIdentifier : orgId;
+      return typeof rec.identifier === "string" && org ? [{ org, project: rec.identifier }] : [];
+    });
+
+    const { resource_scope: _scope, ...baseInput } = input;
+    const items: unknown[] = [];
+    const errors: Array<{ org_id: string; project_id: string; error: string }> = [];
+    let total = 0;
+    for (let i = 0; i < projects.length; i += ROLLUP_CONCURRENCY) {
+      const batch = projects.slice(i, i + ROLLUP_CONCURRENCY);
+      const results = await Promise.allSettled(batch.map(({ org, project }) =>
+        this.dispatch(client, def.resourceType, "list", { ...baseInput, org_id: org, project_id: project }, signal),
+      ));
+      results.forEach((result, j) => {
+        const { org, project } = batch[j]!;
+        if (result.status === "rejected") {
+          errors.push({ org_id: org, project_id: project, error: result.reason instanceof Error ? result.reason.message : String(result.reason) });
+          return;
+        }
+        const page = result.value as Record<string, unknown> | undefined;
+        const key = page ? LIST_ARRAY_KEYS.find((k) => Array.isArray(page[k])) : undefined;
+        const pageItems = key ? (page![key] as unknown[]) : [];
+        for (const item of pageItems) {
+          items.push(typeof item === "object" && item !== null && !Array.isArray(item)
+            ? { orgIdentifier: org, projectIdentifier: project, ...(item as Record<string, unknown>) }
+            : item);
+        }
+        total += typeof page?.total === "number" ? page.total : pageItems.length;
+      });
+    }
+
+    return {
+      items,
+      total,
+      rollup: {
+        scope,
+        ...(orgId ? { org_id: orgId } : {}),
+        projects_queried: projects.length,
+        projects_total: projectPage.total,
+        truncated: projectPage.total > projects.length,
+        ...(errors.length > 0 ? { errors } : {}),
+      },
+    };
+  }
+
+  /**
+   * Return a live entry for `key` or start `fetch` and keep its promise for
+   * `ttlMs`. Failed fetches are evicted so the next call retries.
+   */
+  private reuseResponse(key: string, ttlMs: number, fetch: () => Promise<unknown>): Promise<unknown> {
+    const now = Date.now();
+    for (const [k, entry] of this.reusableResponses) {
+      if (entry.expiresAt <= now) this.reusableResponses.delete(k);
+    }
+    const cached = this.reusableResponses.get(key);
+    recordCacheLookup("response_reuse", cached !== undefined);
+    if (cached) return cached.response;
+    const response = fetch();
+    this.reusableResponses.set(key, { expiresAt: now + ttlMs, response });
+    response.catch(() => this.reusableResponses.delete(key));
+    return response;
   }
 
   /** Dispatch an execute action to the Harness API. */
@@ -426,6 +688,7 @@ export class Registry {
   ): Promise<unknown> {
     const auditCtx = signalOrAudit instanceof AbortSignal ? undefined : signalOrAudit;
     const abortSignal = signalOrAudit instanceof AbortSignal ? signalOrAudit : signal;
+    input = expandGitDetails(input);
 
     const def = this.getResource(resourceType);
     const actionSpec = def.executeActions?.[action];
@@ -438,7 +701,7 @@ export class Registry {
       throw new Error(`Read-only mode is enabled (HARNESS_READ_ONLY=true). Execute action "${action}" is not allowed.`);
     }
 
-    return this.executeSpecWithAudit(client, def, actionSpec, "execute", resourceType, input, { ...auditCtx, tool: auditCtx?.tool ?? "harness_execute", action }, abortSignal);
+    return this.withApiBudget(() => this.executeSpecWithAudit(client, def, actionSpec, "execute", resourceType, input, { ...auditCtx, tool: auditCtx?.tool ?? "harness_execute", action }, abortSignal));
   }
 
   /**
@@ -461,11 +724,11 @@ export class Registry {
     const startTime = Date.now();
     try {
       const result = await this.executeSpec(client, def, spec, input, signal);
-      this.emitAuditEvent(def, spec, operation, resourceType, input, auditCtx, "success", Date.now() - startTime);
+      this.emitAuditEvent(def, spec, operation, resourceType, input, auditCtx, "success", Date.now() - startTime, undefined, undefined, getCorrelationId(result));
       return result;
     } catch (err) {
       const httpStatus = err instanceof HarnessApiError ? err.statusCode : undefined;
-      this.emitAuditEvent(def, spec, operation, resourceType, input, auditCtx, "error", Date.now() - startTime, String(err), httpStatus);
+      this.emitAuditEvent(def, spec, operation, resourceType, input, auditCtx, "error", Date.now() - startTime, String(err), httpStatus, getCorrelationId(err));
       throw err;
     }
   }
@@ -536,6 +799,7 @@ export class Registry {
     durationMs: number,
     error?: string,
     httpStatus?: number,
+    correlationId?: string,
   ): void {
     if (!this.auditManager) return;
     const auditScope = isResourceScope(input.resource_scope) ? input.resource_scope : undefined;
@@ -545,6 +809,7 @@ export class Registry {
       ? spec.pathBuilder(input, { HARNESS_ACCOUNT_ID: this.getAccountId(), HARNESS_ORG: this.config.HARNESS_ORG, HARNESS_PROJECT: this.config.HARNESS_PROJECT })
       : spec.path;
 
+    const traceId = currentTraceId();
     const event: AuditEvent = {
       event_id: randomUUID(),
       timestamp: new Date().toISOString(),
@@ -576,11 +841,39 @@ export class Registry {
       http_path: resolvedPath,
       ...(error ? { error } : {}),
       ...(httpStatus ? { http_status: httpStatus } : {}),
+      ...(correlationId ? { correlation_id: correlationId } : {}),
+      ...(traceId ? { trace_id: traceId } : {}),
     };
 
     this.auditManager.emit(event);
   }
 
+  /** Fail fast with the missing permission instead of letting the endpoint return 403. */
+  private async checkRequiredPermission(
+    client: HarnessClient,
+    def: ResourceDefinition,
+    required: { resourceType: string; permission: string },
+    input: Record<string, unknown>,
+    scope: ResourceScope,
+    signal?: AbortSignal,
+  ): Promise<void> {
+    const orgId = shouldUseOrg(scope) ? resolveScopeString(input.org_id, this.config.HARNESS_ORG) : undefined;
+    const projectId = shouldUseProject(scope) ? resolveScopeString(input.project_id, this.config.HARNESS_PROJECT) : undefined;
+    const idField = def.identifierFields[def.identifierFields.length - 1];
+    const resourceId = idField && typeof input[idField] === "string" ? input[idField] as string : undefined;
+    const permitted = await hasPermission(client, {
+      ...required,
+      ...(resourceId ? { resourceIdentifier: resourceId } : {}),
+      ...(orgId ? { orgIdentifier: orgId } : {}),
+      ...(projectId ? { projectIdentifier: projectId } : {}),
+    }, signal);
+    if (!permitted) {
+      const target = [required.resourceType, resourceId].filter(Boolean).join(" ");
+      const where = projectId ? ` in project ${orgId}/${projectId}` : orgId ? ` in org ${orgId}` : "";
+      throw new Error(`Missing ${required.permission} on ${target}${where}. Ask a Harness admin to grant it, or use an account with access.`);
+    }
+  }
+
   private async executeSpec(
     client: HarnessClient,
     def: ResourceDefinition,
@@ -589,6 +882,7 @@ export class Registry {
     signal?: AbortSignal,
   ): Promise<unknown> {
     const resolvedAccountId = this.getAccountId();
+    input = resolveAccountArguments(input, resolvedAccountId, this.config.HARNESS_ACCOUNT_MISMATCH ?? "reject");
     const resolvedConfig: Config = { ...this.config, HARNESS_ACCOUNT_ID: resolvedAccountId };
     const requestedScope = getRequestedScope(def, input);
     const explicitScopeValues = requestedScope ? getExplicitScopeValues(requestedScope, input, this.config) : undefined;
@@ -607,6 +901,10 @@ export class Registry {
       if (shouldUseProject(requestedScope) && explicitScopeValues.projectId && !input.project_id) input = { ...input, project_id: explicitScopeValues.projectId };
     }
 
+    if (spec.requiredPermission && this.config.HARNESS_PERMISSION_PRECHECK) {
+      await this.checkRequiredPermission(client, def, spec.requiredPermission, input, pathDefaultScope, signal);
+    }
+
     // Build path with substitutions (or pathBuilder when present)
     let path: string;
     if (spec.pathBuilder) {
@@ -786,6 +1084,10 @@ export class Registry {
           `Use harness_describe(resource_type="${def.resourceType}") to see the schema.`
         );
       }
+      if (isStrictSchemas()) {
+        const violations = bodySchemaViolations(spec.bodySchema.fields, payload);
+        if (violations.length > 0) throw strictInputError(def.resourceType, "body", violations);
+      }
     }
 
     // Make request — resolve base URL and auth from product backend
@@ -808,37 +1110,77 @@ export class Registry {
       signal,
     };
 
+    // API call budget: the client refuses requests once it is spent (cache
+    // hits below never reach it). Writes then fail; reads are answered from
+    // the last response seen for the same request.
+    const isRead = (spec.operationPolicy?.risk ?? "read") === "read";
+    const budgetKey = this.apiBudget.enabled
+      ? JSON.stringify([resolvedAccountId, resolvedMethod, baseUrl ?? "", path, params])
+      : "";
+    let budgetWarning: string | undefined;
+
     // ELK→Mongo fallback: when elkFallback is enabled, try Elasticsearch first.
     // On server-side failure (5xx / timeout), retry against MongoDB.
     let raw: unknown;
     let dataSource: "elasticsearch" | "mongodb" | undefined;
-    if (spec.elkFallback) {
-      try {
-        raw = await client.request({ ...requestOpts, params: { ...params, enforce_elasticsearch: "true" } });
-        dataSource = "elasticsearch";
-      } catch (elkErr: unknown) {
-        const isApiError = elkErr instanceof HarnessApiError;
-        // Fall back to MongoDB on any server error (5xx), timeout (408),
-        // or client error (4xx) — a 404 often means the ES index doesn't
-        // exist yet for this account, and a 400 may be ES-specific.
-        // Only auth failures (401/403) are re-thrown immediately.
-        const status = isApiError ? elkErr.statusCode : 0;
-        const isRetryable = status >= 400 && status !== 401 && status !== 403;
-        if (isRetryable) {
-          log.warn(`ELK query failed for ${def.resourceType} (${status}); falling back to MongoDB`);
-          raw = await client.request({ ...requestOpts, params: { ...params, enforce_elasticsearch: "false" } });
-          dataSource = "mongodb";
+    try {
+      if (spec.elkFallback) {
+        try {
+          raw = await client.request({ ...requestOpts, params: { ...params, enforce_elasticsearch: "true" } });
+          dataSource = "elasticsearch";
+        } catch (elkErr: unknown) {
+          const isApiError = elkErr instanceof HarnessApiError;
+          // Fall back to MongoDB on any server error (5xx), timeout (408),
+          // or client error (4xx) — a 404 often means the ES index doesn't
+          // exist yet for this account, and a 400 may be ES-specific.
+          // Only auth failures (401/403) are re-thrown immediately.
+          const status = isApiError ? elkErr.statusCode : 0;
+          const isRetryable = status >= 400 && status !== 401 && status !== 403;
+          if (isRetryable) {
+            log.warn(`ELK query failed for ${def.resourceType} (${status}); falling back to MongoDB`);
+            raw = await client.request({ ...requestOpts, params: { ...params, enforce_elasticsearch: "false" } });
+            dataSource = "mongodb";
+          } else {
+            throw elkErr;
+          }
+        }
+      } else if (spec.diskCacheTtlMs && this.diskCache && resolvedMethod === "GET") {
+        const cacheKey = JSON.stringify([resolvedAccountId, baseUrl ?? "", path, params]);
+        raw = await this.diskCache.get<unknown>(cacheKey);
+        if (raw === undefined) {
+          raw = await client.request(requestOpts);
+          await this.diskCache.set(cacheKey, raw, spec.diskCacheTtlMs);
         } else {
-          throw elkErr;
+          log.debug(`Disk cache hit for ${def.resourceType}`, { path });
         }
+      } else if (spec.responseReuseTtlMs && spec.operationPolicy.risk === "read") {
+        const { signal: _signal, ...keyOpts } = requestOpts;
+        raw = await this.reuseResponse(JSON.stringify([resolvedAccountId, keyOpts]), spec.responseReuseTtlMs, () => client.request(requestOpts));
+      } else {
+        raw = await client.request(requestOpts);
       }
-    } else {
-      raw = await client.request(requestOpts);
+    } catch (err) {
+      if (!(err instanceof ApiBudgetExceededError)) throw err;
+      if (!isRead) throw new Error(`${err.message} Write operations are refused until the budget resets.`);
+      const cached = this.apiBudget.cached(budgetKey);
+      if (!cached) {
+        throw new Error(`${err.message} No earlier response for this ${def.resourceType} request is cached to return instead.`);
+      }
+      raw = cached.raw;
+      dataSource = undefined;
+      budgetWarning = `${err.message} This is the cached response from ${new Date(cached.at).toISOString()} and may be stale.`;
+      log.warn("API call budget spent; serving cached response", { resourceType: def.resourceType, path });
     }
 
+    if (this.apiBudget.enabled && isRead && !budgetWarning) this.apiBudget.remember(budgetKey, raw);
+
     // Extract response
     let result = spec.responseExtractor ? spec.responseExtractor(raw, input) : raw;
 
+    if (budgetWarning && result && typeof result === "object" && !Array.isArray(result)) {
+      result = Object.assign({}, result, { _budget_warning: budgetWarning });
+    }
+
     // Tag ELK/Mongo data source on the response when fallback is active
     if (dataSource && result && typeof result === "object" && !Array.isArray(result)) {
       result = Object.assign({}, result, { _data_source: dataSource });
@@ -852,6 +1194,10 @@ export class Registry {
       Object.defineProperty(result, "__skipCompact", { value: true, enumerable: false, configurable: true });
     }
 
+    // Carry the response's correlation ID past extraction so the audit event
+    // can record it. Non-enumerable like __skipCompact.
+    tagCorrelationId(result, getCorrelationId(raw));
+
     // Propagate storeType from the request query params into the result when
     // the API response didn't include one.  Create/update endpoints like
     // `/pipeline/api/pipelines/v2` return a slim `PipelineSaveResponse` that
@@ -965,7 +1311,7 @@ export class Registry {
         }
         try {
           let link = buildDeepLink(
-            this.config.HARNESS_BASE_URL,
+            harnessUiBaseUrl(this.config),
             resolvedAccountId,
             def.deepLinkTemplate,
             baseLinkParams,
@@ -1059,7 +1405,7 @@ export class Registry {
             if (itemProj && typeof itemProj === "string") itemLinkParams.project = itemProj;
 
             let itemLink = buildDeepLink(
-              this.config.HARNESS_BASE_URL,
+              harnessUiBaseUrl(this.config),
               resolvedAccountId,
               def.deepLinkTemplate,
               itemLinkParams,
@@ -1110,6 +1456,7 @@ export class Registry {
           description: r.description,
           scope: r.scope,
           supportedScopes: getSupportedScopes(r).length > 1 ? getSupportedScopes(r) : undefined,
+          rollupScopes: r.rollupScopes,
           operations: Object.keys(r.operations),
           executeActions: r.executeActions ? Object.keys(r.executeActions) : undefined,
           identifierFields: r.identifierFields,
@@ -1122,6 +1469,7 @@ export class Registry {
     return {
       total_resource_types: this.resourceMap.size,
       total_toolsets: this.toolsets.length,
+      ...(this.capabilities ? { platform: this.platformSummary() } : {}),
       toolsets,
     };
   }
@@ -1185,8 +1533,17 @@ export class Registry {
     return {
       total_resource_types: this.resourceMap.size,
       total_toolsets: this.toolsets.length,
+      ...(this.capabilities ? { platform: this.platformSummary() } : {}),
       resource_types,
       hint: "Call harness_describe(resource_type='<type>') for full details including diagnosticHint and executeHint.",
     };
   }
+
+  private platformSummary(): Record<string, unknown> {
+    return {
+      version: this.capabilities?.version,
+      probed_at: this.capabilities?.probedAt,
+      unavailable_toolsets: this.capabilities?.unavailableToolsets.length ? this.capabilities.unavailableToolsets : undefined,
+    };
+  }
 }
@@ -0,0 +1,66 @@
+/**
+ * Named HARNESS_TOOLSETS presets — curated toolset selections an operator can
+ * enable by name (`HARNESS_TOOLSETS=recommended` or `--toolsets recommended`)
+ * instead of listing toolsets one by one.
+ */
+
+import type { OperationName, ResourceDefinition, ToolsetDefinition } from "./types.js";
+
+/**
+ * How much of a toolset a preset exposes:
+ *  - `read`    — list/get plus execute actions whose risk is `read`
+ *  - `execute` — list/get plus every execute action (run, retry, interrupt, ...), no create/update/delete
+ *  - `full`    — everything the toolset defines
+ */
+export type ToolsetAccess = "read" | "execute" | "full";
+
+export interface ToolsetPreset {
+  description: string;
+  /** Toolsets the preset enables, with the access each one gets. */
+  toolsets: Record<string, ToolsetAccess>;
+}
+
+export const TOOLSET_PRESETS: Record<string, ToolsetPreset> = {
+  recommended: {
+    description: "Pipelines (read and run executions), execution logs, pull requests, and CCM (read) — sized to fit a typical LLM context",
+    toolsets: {
+      pipelines: "execute",
+      logs: "full",
+      "pull-requests": "full",
+      ccm: "read",
+    },
+  },
+};
+
+/** Preset names accepted by HARNESS_TOOLSETS besides `all`, which enables every toolset including opt-in ones. */
+export const TOOLSET_PRESET_NAMES: string[] = Object.keys(TOOLSET_PRESETS);
+
+const READ_OPERATIONS: ReadonlySet<OperationName> = new Set(["list", "get"]);
+
+function restrictResource(resource: ResourceDefinition, access: Exclude<ToolsetAccess, "full">): ResourceDefinition | undefined {
+  const operations = Object.fromEntries(
+    Object.entries(resource.operations).filter(([op]) => READ_OPERATIONS.has(op as OperationName)),
+  ) as ResourceDefinition["operations"];
+  const executeActions = resource.executeActions
+    ? Object.fromEntries(
+      Object.entries(resource.executeActions).filter(([, spec]) => access === "execute" || spec.operationPolicy.risk === "read"),
+    )
+    : undefined;
+  const hasActions = executeActions !== undefined && Object.keys(executeActions).length > 0;
+  if (Object.keys(operations).length === 0 && !hasActions) return undefined;
+  return { ...resource, operations, executeActions: hasActions ? executeActions : undefined };
+}
+
+/**
+ * Copy of `toolset` limited to what `access` allows. Resources left with no
+ * operations are dropped; the shared toolset definition is never mutated.
+ */
+export function restrictToolset(toolset: ToolsetDefinition, access: ToolsetAccess): ToolsetDefinition {
+  if (access === "full") return toolset;
+  return {
+    ...toolset,
+    resources: toolset.resources
+      .map((r) => restrictResource(r, access))
+      .filter((r): r is ResourceDefinition => r !== undefined),
+  };
+}
@@ -256,7 +256,7 @@ export const accessControlToolset: ToolsetDefinition = {
     {
       resourceType: "role_assignment",
       displayName: "Role Assignment",
-      description: "Role assignment binding a principal to a role. Supports list, create, and delete.",
+      description: "Role assignment binding a principal to a role. Supports list, create, and delete. Create and delete change who holds which permissions, so both require confirmation (elicitation, or confirm: true on clients without it).",
       toolset: "access_control",
       scope: "project",
       identifierFields: ["role_assignment_id"],
@@ -282,7 +282,8 @@ export const accessControlToolset: ToolsetDefinition = {
         create: {
           method: "POST",
           path: "/authz/api/roleassignments",
-          operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          // Granting a role is a privilege change — never let it proceed silently
+          operationPolicy: { risk: "high_write", retryPolicy: "do_not_retry" },
           bodyBuilder: (input) => input.body,
           responseExtractor: ngExtract,
           description: "Create a role assignment",
@@ -1,6 +1,7 @@
 import type { ToolsetDefinition, PreflightContext, ParamsSchema } from "../types.js";
 import type { PathBuilderConfig } from "../types.js";
-import { ngExtract, passthrough, gqlExtract, ccmViewsExtract, anomalyListExtract, ccmBreakdownExtract, ccmTimeseriesExtract, ccmSummaryExtract, ccmRecommendationsExtract, countExtract } from "../extractors.js";
+import { parseTimeInput, TIME_INPUT_FORMATS } from "../../utils/time-range.js";
+import { ngExtract, passthrough, gqlExtract, ccmViewsExtract, anomalyListExtract, ccmBreakdownExtract, ccmBatchedBreakdownExtract, ccmTimeseriesExtract, ccmSummaryExtract, ccmRecommendationsExtract, countExtract, ccmClusterBreakdownExtract, governanceRuleListExtract, governanceRuleGetExtract, governanceExecutionListExtract } from "../extractors.js";
 
 // ---------------------------------------------------------------------------
 // GraphQL queries — ported from the official Go MCP server
@@ -39,6 +40,82 @@ query FetchperspectiveGrid(
   )
 }`;
 
+/**
+ * Perspective grid for several group-by dimensions in one request: each
+ * dimension gets its own `$groupByN` variable and aliased `gridN` / `totalN`
+ * fields, so a multi-dimension breakdown costs one rate-limited CCM call
+ * instead of one per dimension.
+ */
+function buildBatchedGridQuery(count: number): string {
+  const groupByVars = Array.from({ length: count }, (_, i) => `  $groupBy${i}: [QLCEViewGroupByInput],`).join("\n");
+  const fields = Array.from({ length: count }, (_, i) => `  grid${i}: perspectiveGrid(
+    aggregateFunction: $aggregateFunction
+    filters: $filters
+    groupBy: $groupBy${i}
+    limit: $limit
+    offset: $offset
+    preferences: $preferences
+    isClusterHourlyData: $isClusterHourlyData
+    sortCriteria: [{sortType: COST, sortOrder: DESCENDING}]
+  ) {
+    data { name id cost costTrend __typename }
+    __typename
+  }
+  total${i}: perspectiveTotalCount(
+    filters: $filters
+    groupBy: $groupBy${i}
+    isClusterQuery: $isClusterOnly
+    isClusterHourlyData: $isClusterHourlyData
+  )`).join("\n");
+  return `
+query FetchperspectiveGridBatch(
+  $filters: [QLCEViewFilterWrapperInput],
+${groupByVars}
+  $limit: Int,
+  $offset: Int,
+  $aggregateFunction: [QLCEViewAggregationInput],
+  $isClusterOnly: Boolean!,
+  $isClusterHourlyData: Boolean = null,
+  $preferences: ViewPreferencesInput
+) {
+${fields}
+}`;
+}
+
+const CLUSTER_GRID_QUERY = `
+query FetchClusterPerspectiveGrid(
+  $filters: [QLCEViewFilterWrapperInput],
+  $groupBy: [QLCEViewGroupByInput],
+  $limit: Int,
+  $offset: Int,
+  $aggregateFunction: [QLCEViewAggregationInput],
+  $preferences: ViewPreferencesInput
+) {
+  perspectiveGrid(
+    aggregateFunction: $aggregateFunction
+    filters: $filters
+    groupBy: $groupBy
+    limit: $limit
+    offset: $offset
+    preferences: $preferences
+    isClusterHourlyData: false
+    sortCriteria: [{sortType: COST, sortOrder: DESCENDING}]
+  ) {
+    data {
+      name id cost costTrend
+      clusterData {
+        clusterName namespace workloadName workloadType
+        idleCost unallocatedCost utilizedCost systemCost
+        cpuBillingAmount memoryBillingAmount storageCost efficiencyScore
+        __typename
+      }
+      __typename
+    }
+    __typename
+  }
+  perspectiveTotalCount(filters: $filters, groupBy: $groupBy, isClusterQuery: true, isClusterHourlyData: false)
+}`;
+
 const PERSPECTIVE_TIMESERIES_QUERY = `
 query FetchPerspectiveTimeSeries(
   $filters: [QLCEViewFilterWrapperInput],
@@ -145,6 +222,15 @@ query PerspectiveRecommendations($filter: RecommendationFilterDTOInput) {
 // GraphQL helper builders — TypeScript equivalents of the Go filter helpers
 // ---------------------------------------------------------------------------
 
+/**
+ * CCM GraphQL responses are reused within a session for this long — cost data
+ * refreshes hourly at best, and analyses re-ask the same perspective queries.
+ */
+const CCM_QUERY_REUSE_MS = 2 * 60 * 1000;
+
+/** Most group-by dimensions batched into one cost_breakdown request. */
+const MAX_BATCHED_GROUP_BY = 5;
+
 const VALID_TIME_FILTERS = [
   "LAST_7", "THIS_MONTH", "LAST_30_DAYS", "THIS_QUARTER", "THIS_YEAR",
   "LAST_MONTH", "LAST_QUARTER", "LAST_YEAR", "LAST_3_MONTHS",
@@ -171,6 +257,14 @@ const OUTPUT_FIELDS: Record<string, Record<string, string>> = {
   product:             { fieldId: "product",              fieldName: "Product",        identifier: "COMMON", identifierName: "Common" },
 };
 
+/** Kubernetes dimensions for cluster perspectives (CLUSTER identifier). */
+const CLUSTER_FIELDS: Record<string, Record<string, string>> = {
+  cluster:   { fieldId: "clusterName",  fieldName: "Cluster Name", identifier: "CLUSTER", identifierName: "Cluster" },
+  namespace: { fieldId: "namespace",    fieldName: "Namespace",    identifier: "CLUSTER", identifierName: "Cluster" },
+  workload:  { fieldId: "workloadName", fieldName: "Workload",     identifier: "CLUSTER", identifierName: "Cluster" },
+  node:      { fieldId: "instanceName", fieldName: "Node",         identifier: "CLUSTER", identifierName: "Cluster" },
+};
+
 /**
  * Build the startTime AFTER/BEFORE timeFilter pair from an explicit epoch-ms
  * range — the shape the perspective GraphQL API expects. Shared by both the
@@ -273,19 +367,9 @@ function resolveTimeFilters(
   return buildTimeFilters(timeFilter);
 }
 
-/**
- * Coerce an input value to a positive epoch-ms number, or undefined. Accepts
- * numbers or numeric strings (tool args often arrive as strings).
- */
-function toEpochMs(value: unknown): number | undefined {
-  if (value == null) return undefined;
-  const n = typeof value === "number" ? value : Number(value);
-  return Number.isFinite(n) && n > 0 ? n : undefined;
-}
-
-/** Read the optional custom-window params from a tool input. */
+/** Read the optional custom-window params from a tool input (epoch ms, ISO 8601, or natural inputs like "last 7d"). */
 function customWindow(input: Record<string, unknown>): { startMs?: number; endMs?: number } {
-  return { startMs: toEpochMs(input.start_time), endMs: toEpochMs(input.end_time) };
+  return { startMs: parseTimeInput(input.start_time), endMs: parseTimeInput(input.end_time, { edge: "end" }) };
 }
 
 function buildViewFilter(viewId: string): Record<string, unknown>[] {
@@ -301,6 +385,16 @@ function buildFilters(
   return [...buildViewFilter(viewId), ...resolveTimeFilters(timeFilter, startMs, endMs)];
 }
 
+/** Group-by dimensions from `group_by` — a single field, a comma-separated list, or an array. */
+function parseGroupByList(value: unknown): string[] {
+  const raw = Array.isArray(value) ? value.map(String) : typeof value === "string" ? value.split(",") : [];
+  const fields = [...new Set(raw.map((f) => f.trim()).filter(Boolean))];
+  if (fields.length > MAX_BATCHED_GROUP_BY) {
+    throw new Error(`group_by accepts at most ${MAX_BATCHED_GROUP_BY} dimensions per request (got ${fields.length}).`);
+  }
+  return fields;
+}
+
 function buildGroupBy(field?: string): Record<string, unknown>[] {
   if (!field) {
     return [{ entityGroupBy: OUTPUT_FIELDS["product"] }];
@@ -323,6 +417,37 @@ function buildGroupBy(field?: string): Record<string, unknown>[] {
   }];
 }
 
+/** Restrict a cluster perspective query to specific clusters / namespaces. */
+function buildClusterIdFilters(input: Record<string, unknown>): Record<string, unknown>[] {
+  const filters: Record<string, unknown>[] = [];
+  for (const [key, field] of [["cluster_name", "cluster"], ["namespace", "namespace"]] as const) {
+    const raw = input[key];
+    const values = Array.isArray(raw) ? raw.map(String) : typeof raw === "string" && raw ? raw.split(",").map((v) => v.trim()) : [];
+    if (values.length > 0) {
+      filters.push({ idFilter: { field: CLUSTER_FIELDS[field], operator: "IN", values } });
+    }
+  }
+  return filters;
+}
+
+/**
+ * Preflight for cost_cluster_breakdown: default to the account's cluster
+ * perspective (from CCM metadata) when no perspective_id is given.
+ */
+async function clusterPerspectivePreflight(ctx: PreflightContext): Promise<void> {
+  if (ctx.input.perspective_id) return;
+  const meta = await ctx.registry.dispatch(ctx.client, "cost_summary", "list", {}, ctx.signal) as
+    { defaultClusterPerspectiveId?: string; clusterDataPresent?: boolean } | undefined;
+  if (!meta?.defaultClusterPerspectiveId) {
+    throw new Error(
+      meta?.clusterDataPresent === false
+        ? "No Kubernetes cluster cost data in this account. Connect a cluster with a CCM Kubernetes connector first."
+        : "No default cluster perspective found. Pass perspective_id of a cluster perspective (see cost_perspective list).",
+    );
+  }
+  ctx.input.perspective_id = meta.defaultClusterPerspectiveId;
+}
+
 function buildAggregateFunction(): Record<string, string>[] {
   return [{ operationType: "SUM", columnName: "cost" }];
 }
@@ -594,6 +719,7 @@ export const ccmToolset: ToolsetDefinition = {
   displayName: "Cloud Cost Management",
   description:
     "Cloud cost visibility, analysis, recommendations, and anomaly detection. Covers perspectives, cost breakdowns, time series, summaries, recommendations, and anomalies.",
+  probeResource: "cost_perspective",
   resources: [
     // ------------------------------------------------------------------
     // 1. cost_perspective — REST CRUD for perspective management
@@ -827,15 +953,16 @@ export const ccmToolset: ToolsetDefinition = {
       description: `Drill-down cost breakdown by any dimension within a perspective. Answers "where is my money going?" Returns cost per entity (e.g. per AWS service, per region, per product).
 
 Required: perspective_id (get from cost_perspective list).
-Optional: group_by (predefined: ${VALID_GROUP_BY_FIELDS.join(", ")}, OR any label key like "env", "team", "app"), time_filter (${VALID_TIME_FILTERS.join(", ")}), limit, offset.`,
+Optional: group_by (predefined: ${VALID_GROUP_BY_FIELDS.join(", ")}, OR any label key like "env", "team", "app"), time_filter (${VALID_TIME_FILTERS.join(", ")}), limit, offset.
+To compare several dimensions, pass group_by as a comma-separated list (e.g. "region,product") — they are fetched in one API call and each row is tagged with its group_by. CCM has tight rate limits, so prefer this over one call per dimension.`,
       toolset: "ccm",
       scope: "account",
       identifierFields: ["perspective_id"],
       listFilterFields: [
-        { name: "group_by", description: "Group results by field. Use predefined fields (region, product, etc.) OR any label key name (env, team, app, environment, etc.)" },
+        { name: "group_by", description: `Group results by field. Use predefined fields (region, product, etc.) OR any label key name (env, team, app, environment, etc.). Comma-separate up to ${MAX_BATCHED_GROUP_BY} fields to break down by several dimensions in one request.` },
         { name: "time_filter", description: "Time range filter", enum: [...VALID_TIME_FILTERS] },
-        { name: "start_time", description: "Custom window start in epoch milliseconds. When set with end_time, overrides time_filter — use for historical/custom ranges the relative enum can't express (e.g. a past quarter).", type: "number" },
-        { name: "end_time", description: "Custom window end in epoch milliseconds. Pair with start_time.", type: "number" },
+        { name: "start_time", description: `Custom window start. ${TIME_INPUT_FORMATS} When set with end_time, overrides time_filter — use for historical/custom ranges the relative enum can't express (e.g. a past quarter).` },
+        { name: "end_time", description: "Custom window end (same formats as start_time). Pair with start_time." },
         { name: "limit", description: "Result limit", type: "number" },
         { name: "offset", description: "Pagination offset", type: "number" },
       ],
@@ -844,32 +971,114 @@ Optional: group_by (predefined: ${VALID_GROUP_BY_FIELDS.join(", ")}, OR any labe
           method: "POST",
           path: "/ccm/api/graphql",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
-          bodyBuilder: (input) => ({
-            query: PERSPECTIVE_GRID_QUERY,
-            operationName: "FetchperspectiveGrid",
-            variables: {
+          responseReuseTtlMs: CCM_QUERY_REUSE_MS,
+          bodyBuilder: (input) => {
+            const groupBys = parseGroupByList(input.group_by);
+            const shared = {
               filters: buildFilters(
                 input.perspective_id as string,
                 (input.time_filter as string) ?? "LAST_30_DAYS",
                 customWindow(input).startMs,
                 customWindow(input).endMs,
               ),
-              groupBy: buildGroupBy(input.group_by as string | undefined),
               limit: (input.limit as number) ?? 25,
               offset: (input.offset as number) ?? 0,
               aggregateFunction: buildAggregateFunction(),
               isClusterOnly: false,
               isClusterHourlyData: false,
               preferences: buildPreferences(),
-            },
-          }),
-          responseExtractor: ccmBreakdownExtract,
+            };
+            if (groupBys.length <= 1) {
+              return {
+                query: PERSPECTIVE_GRID_QUERY,
+                operationName: "FetchperspectiveGrid",
+                variables: { ...shared, groupBy: buildGroupBy(groupBys[0]) },
+              };
+            }
+            return {
+              query: buildBatchedGridQuery(groupBys.length),
+              operationName: "FetchperspectiveGridBatch",
+              variables: {
+                ...shared,
+                ...Object.fromEntries(groupBys.map((field, i) => [`groupBy${i}`, buildGroupBy(field)])),
+              },
+            };
+          },
+          responseExtractor: (raw, input) => {
+            const groupBys = parseGroupByList(input?.group_by);
+            return groupBys.length > 1 ? ccmBatchedBreakdownExtract(raw, groupBys) : ccmBreakdownExtract(raw);
+          },
           description:
             "Get cost breakdown by dimension for a perspective. Group by region, awsServicecode, product, cloudProvider, etc.",
         },
       },
     },
 
+    // ------------------------------------------------------------------
+    // 2b. cost_cluster_breakdown — GraphQL cluster perspective grid
+    //    Answers: "What does each namespace / workload cost?"
+    // ------------------------------------------------------------------
+    {
+      resourceType: "cost_cluster_breakdown",
+      displayName: "Cluster Cost Breakdown",
+      description: `Kubernetes cost breakdown from a cluster perspective — per cluster, namespace, workload, or node, with idle, unallocated, utilized, CPU, memory, and storage cost and efficiency score. Answers "what does each namespace cost?"
+
+Optional: perspective_id (defaults to the account's default cluster perspective), group_by (cluster, namespace, workload, node; default namespace), cluster_name and namespace filters (comma-separated), time_filter (${VALID_TIME_FILTERS.join(", ")}), limit, offset.`,
+      toolset: "ccm",
+      scope: "account",
+      identifierFields: ["perspective_id"],
+      listFilterFields: [
+        { name: "group_by", description: "Kubernetes dimension to group by", enum: Object.keys(CLUSTER_FIELDS) },
+        { name: "cluster_name", description: "Only these clusters (comma-separated names)" },
+        { name: "namespace", description: "Only these namespaces (comma-separated)" },
+        { name: "time_filter", description: "Time range filter", enum: [...VALID_TIME_FILTERS] },
+        { name: "start_time", description: `Custom window start. ${TIME_INPUT_FORMATS} When set with end_time, overrides time_filter.` },
+        { name: "end_time", description: "Custom window end (same formats as start_time). Pair with start_time." },
+        { name: "limit", description: "Result limit", type: "number" },
+        { name: "offset", description: "Pagination offset", type: "number" },
+      ],
+      relatedResources: [
+        { resourceType: "cost_perspective", relationship: "parent", description: "Cluster perspective the breakdown is computed from" },
+        { resourceType: "cost_recommendation", relationship: "related", description: "Workload and node pool rightsizing recommendations" },
+      ],
+      operations: {
+        list: {
+          method: "POST",
+          path: "/ccm/api/graphql",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseReuseTtlMs: CCM_QUERY_REUSE_MS,
+          preflight: clusterPerspectivePreflight,
+          bodyBuilder: (input) => {
+            const { startMs, endMs } = customWindow(input);
+            const groupBy = CLUSTER_FIELDS[(input.group_by as string) ?? "namespace"] ?? CLUSTER_FIELDS.namespace;
+            return {
+              query: CLUSTER_GRID_QUERY,
+              operationName: "FetchClusterPerspectiveGrid",
+              variables: {
+                filters: [
+                  ...buildFilters(
+                    input.perspective_id as string,
+                    (input.time_filter as string) ?? "LAST_30_DAYS",
+                    startMs,
+                    endMs,
+                  ),
+                  ...buildClusterIdFilters(input),
+                ],
+                groupBy: [{ entityGroupBy: groupBy }],
+                limit: (input.limit as number) ?? 25,
+                offset: (input.offset as number) ?? 0,
+                aggregateFunction: buildAggregateFunction(),
+                preferences: { ...buildPreferences(), includeUnallocatedCost: true },
+              },
+            };
+          },
+          responseExtractor: ccmClusterBreakdownExtract,
+          description:
+            "Get Kubernetes cost per cluster, namespace, workload, or node with idle/unallocated/utilized split.",
+        },
+      },
+    },
+
     // ------------------------------------------------------------------
     // 3. cost_timeseries — GraphQL perspective time series
     //    Replaces: ccm_perspective_time_series from the official server
@@ -888,8 +1097,8 @@ Optional: time_filter (${VALID_TIME_FILTERS.join(", ")}), time_resolution (DAY,
       listFilterFields: [
         { name: "group_by", description: "Group results by field. Use predefined fields (region, product, etc.) OR any label key name (env, team, app, etc.)" },
         { name: "time_filter", description: "Time range filter", enum: [...VALID_TIME_FILTERS] },
-        { name: "start_time", description: "Custom window start in epoch milliseconds. When set with end_time, overrides time_filter — use for historical/custom ranges the relative enum can't express (e.g. a past quarter).", type: "number" },
-        { name: "end_time", description: "Custom window end in epoch milliseconds. Pair with start_time.", type: "number" },
+        { name: "start_time", description: `Custom window start. ${TIME_INPUT_FORMATS} When set with end_time, overrides time_filter — use for historical/custom ranges the relative enum can't express (e.g. a past quarter).` },
+        { name: "end_time", description: "Custom window end (same formats as start_time). Pair with start_time." },
         { name: "time_resolution", description: "Time resolution for aggregation", enum: ["DAY", "MONTH", "WEEK"] },
         { name: "limit", description: "Result limit", type: "number" },
       ],
@@ -898,6 +1107,7 @@ Optional: time_filter (${VALID_TIME_FILTERS.join(", ")}), time_resolution (DAY,
           method: "POST",
           path: "/ccm/api/graphql",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseReuseTtlMs: CCM_QUERY_REUSE_MS,
           bodyBuilder: (input) => {
             const timeResolution = (input.time_resolution as string) ?? "DAY";
             const entityGroupBy = buildGroupBy(input.group_by as string | undefined);
@@ -948,14 +1158,15 @@ Use with no perspective_id to get CCM metadata (available connectors, default pe
       identifierFields: ["perspective_id"],
       listFilterFields: [
         { name: "time_filter", description: "Time range filter" },
-        { name: "start_time", description: "Custom window start in epoch milliseconds. When set with end_time, overrides time_filter — use for historical/custom ranges the relative enum can't express (e.g. a past quarter).", type: "number" },
-        { name: "end_time", description: "Custom window end in epoch milliseconds. Pair with start_time.", type: "number" },
+        { name: "start_time", description: `Custom window start. ${TIME_INPUT_FORMATS} When set with end_time, overrides time_filter — use for historical/custom ranges the relative enum can't express (e.g. a past quarter).` },
+        { name: "end_time", description: "Custom window end (same formats as start_time). Pair with start_time." },
       ],
       operations: {
         list: {
           method: "POST",
           path: "/ccm/api/graphql",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseReuseTtlMs: CCM_QUERY_REUSE_MS,
           bodyBuilder: (input) => {
             const perspectiveId = input.perspective_id as string | undefined;
 
@@ -994,6 +1205,7 @@ Use with no perspective_id to get CCM metadata (available connectors, default pe
           method: "POST",
           path: "/ccm/api/graphql",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseReuseTtlMs: CCM_QUERY_REUSE_MS,
           bodyBuilder: (input) => ({
             query: PERSPECTIVE_BUDGET_QUERY,
             operationName: "FetchPerspectiveBudget",
@@ -1081,6 +1293,7 @@ Replaces the 5 separate resource-type tools from the official server (EC2, Azure
           method: "POST",
           path: "/ccm/api/graphql",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseReuseTtlMs: CCM_QUERY_REUSE_MS,
           bodyBuilder: (input) => ({
             query: PERSPECTIVE_RECOMMENDATIONS_QUERY,
             operationName: "PerspectiveRecommendations",
@@ -1195,8 +1408,8 @@ All the separate anomaly tools from the official server (list, list_all, list_ig
         { name: "anomaly_view", description: "View type for anomaly grouping", enum: ["RESOURCE", "PERSPECTIVE"] },
         { name: "search_text", description: "Search text to filter anomalies by name or resource" },
         { name: "time_filter", description: "Predefined time range filter", enum: [...VALID_TIME_FILTERS] },
-        { name: "start_time", description: "Custom start time in epoch milliseconds (overrides time_filter)", type: "number" },
-        { name: "end_time", description: "Custom end time in epoch milliseconds (overrides time_filter)", type: "number" },
+        { name: "start_time", description: `Custom start time (overrides time_filter). ${TIME_INPUT_FORMATS}` },
+        { name: "end_time", description: "Custom end time (overrides time_filter). Same formats as start_time." },
         { name: "order_by_field", description: "Field to order by", enum: ["ANOMALOUS_SPEND", "TIME", "ACTUAL_SPEND"] },
         { name: "order_by_direction", description: "Order direction", enum: ["ASCENDING", "DESCENDING"] },
         { name: "min_amount", description: "Minimum actual amount threshold", type: "number" },
@@ -1204,6 +1417,7 @@ All the separate anomaly tools from the official server (list, list_all, list_ig
         { name: "limit", description: "Result limit (default 10)", type: "number" },
         { name: "offset", description: "Pagination offset", type: "number" },
       ],
+      deepLinkTemplate: "/ng/account/{accountId}/ce/anomaly-detection",
       operations: {
         list: {
           method: "POST",
@@ -1232,13 +1446,14 @@ All the separate anomaly tools from the official server (list, list_all, list_ig
             }
 
             // Time filters — prefer explicit start/end, fall back to predefined
-            if (input.start_time != null || input.end_time != null) {
+            const { startMs, endMs } = customWindow(input);
+            if (startMs !== undefined || endMs !== undefined) {
               const timeFilters: Record<string, unknown>[] = [];
-              if (input.start_time != null) {
-                timeFilters.push({ operator: "AFTER", timestamp: input.start_time });
+              if (startMs !== undefined) {
+                timeFilters.push({ operator: "AFTER", timestamp: startMs });
               }
-              if (input.end_time != null) {
-                timeFilters.push({ operator: "BEFORE", timestamp: input.end_time });
+              if (endMs !== undefined) {
+                timeFilters.push({ operator: "BEFORE", timestamp: endMs });
               }
               filters.timeFilters = timeFilters;
             } else {
@@ -1557,15 +1772,9 @@ For cost time-series data, use harness_get with start_time and end_time.`,
           method: "GET",
           path: "/ccm/api/overview",
           pathBuilder: (input) => {
-            const toMillis = (v: unknown, fallbackDaysAgo: number): string => {
-              if (v && typeof v === "string") {
-                const ms = new Date(v).getTime();
-                if (!isNaN(ms)) return String(ms);
-              }
-              return String(Date.now() - fallbackDaysAgo * 86_400_000);
-            };
-            input.start_time = toMillis(input.start_time, 60);
-            input.end_time = toMillis(input.end_time, 0);
+            const now = Date.now();
+            input.start_time = String(parseTimeInput(input.start_time, { now }) ?? now - 60 * 86_400_000);
+            input.end_time = String(parseTimeInput(input.end_time, { now, edge: "end" }) ?? now);
             if (!input.group_by) input.group_by = "DAY";
             return "/ccm/api/overview";
           },
@@ -1616,6 +1825,7 @@ For cost time-series data, use harness_get with start_time and end_time.`,
             (input.value_type as string) === "business_mapping" ? "GET" : "POST",
           path: "/ccm/api/graphql",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseReuseTtlMs: CCM_QUERY_REUSE_MS,
           pathBuilder: (input) => {
             if ((input.value_type as string) === "business_mapping") {
               const sub = input.value_sub_type as string | undefined;
@@ -2041,5 +2251,125 @@ Requires CCM_UNIT_COST_METRICS feature flag.`,
         },
       },
     },
+
+    // ------------------------------------------------------------------
+    // 15. asset_governance_rule / asset_governance_evaluation —
+    //    Cloud Asset Governance (Cloud Custodian) rules and their runs
+    //    Base path: /ccm/api/governance
+    // ------------------------------------------------------------------
+    {
+      resourceType: "asset_governance_rule",
+      displayName: "Asset Governance Rule",
+      description: `Cloud Asset Governance rule — a Cloud Custodian policy (YAML) that finds or remediates cloud resources (e.g. unattached EBS volumes, idle VMs).
+
+Use harness_list to search rules, harness_get for the policy YAML. The dry_run action evaluates a rule against a cloud connector and regions without taking actions; follow it with asset_governance_evaluation to read the result.`,
+      toolset: "ccm",
+      scope: "account",
+      identifierFields: ["rule_id"],
+      listFilterFields: [
+        { name: "search", description: "Filter rules by name" },
+        { name: "cloud_provider", description: "Cloud provider", enum: ["AWS", "AZURE", "GCP"] },
+        { name: "is_oob", description: "Only Harness out-of-the-box rules (true) or only custom rules (false)", type: "boolean" },
+        { name: "limit", description: "Page size (default 20)", type: "number" },
+        { name: "offset", description: "Pagination offset", type: "number" },
+      ],
+      relatedResources: [
+        { resourceType: "asset_governance_evaluation", relationship: "child", description: "Evaluations (runs) of this rule" },
+      ],
+      operations: {
+        list: {
+          method: "POST",
+          path: "/ccm/api/governance/rule/list",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          bodyBuilder: (input) => ({
+            query: {
+              search: input.search,
+              cloudProvider: input.cloud_provider,
+              isOOTB: input.is_oob,
+              limit: (input.limit as number) ?? 20,
+              offset: (input.offset as number) ?? 0,
+              orderBy: [{ field: "RULE_NAME", order: "ASCENDING" }],
+            },
+          }),
+          responseExtractor: governanceRuleListExtract,
+          description: "List asset governance rules, optionally filtered by name, cloud provider, or out-of-the-box status",
+        },
+        get: {
+          method: "POST",
+          path: "/ccm/api/governance/rule/list",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          bodyBuilder: (input) => ({
+            query: { policyIds: [String(input.rule_id ?? "")], limit: 1, offset: 0 },
+          }),
+          responseExtractor: governanceRuleGetExtract,
+          description: "Get an asset governance rule (including its Cloud Custodian YAML) by rule_id",
+        },
+      },
+      executeActions: {
+        dry_run: {
+          method: "POST",
+          path: "/ccm/api/governance/enqueue",
+          operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          bodyBuilder: (input) => {
+            const regions = Array.isArray(input.regions)
+              ? input.regions.map(String)
+              : String(input.regions ?? "").split(",").map((r) => r.trim()).filter(Boolean);
+            if (!input.rule_id || !input.cloud_connector_id || !input.target_account || regions.length === 0) {
+              throw new Error("dry_run requires rule_id, cloud_connector_id, target_account, and regions.");
+            }
+            return {
+              ruleId: input.rule_id,
+              isDryRun: true,
+              targetRegions: regions,
+              targetAccountDetails: [{ cloudConnectorId: input.cloud_connector_id, targetInfo: input.target_account }],
+            };
+          },
+          responseExtractor: ngExtract,
+          actionDescription: "Evaluate a rule in dry-run mode — Cloud Custodian reports matching resources but takes no actions. Params: rule_id, cloud_connector_id (CCM cloud connector), target_account (AWS account ID, Azure subscription ID, or GCP project ID), regions (array or comma-separated). Returns the enqueued evaluation; read results with harness_list(resource_type='asset_governance_evaluation', rule_id=...).",
+        },
+      },
+    },
+    {
+      resourceType: "asset_governance_evaluation",
+      displayName: "Asset Governance Evaluation",
+      description: "A run of an asset governance rule against a cloud account and region — status, matched resource count, potential savings, and whether it was a dry run. List by rule_id; get by evaluation_id for details and the matched resources log.",
+      toolset: "ccm",
+      scope: "account",
+      identifierFields: ["evaluation_id"],
+      listFilterFields: [
+        { name: "rule_id", description: "Only evaluations of this rule" },
+        { name: "status", description: "Execution status", enum: ["ENQUEUED", "SUCCESS", "FAILED", "PARTIAL_SUCCESS"] },
+        { name: "limit", description: "Page size (default 20)", type: "number" },
+        { name: "offset", description: "Pagination offset", type: "number" },
+      ],
+      relatedResources: [
+        { resourceType: "asset_governance_rule", relationship: "parent", description: "Rule that was evaluated" },
+      ],
+      operations: {
+        list: {
+          method: "POST",
+          path: "/ccm/api/governance/execution/list",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          bodyBuilder: (input) => ({
+            ruleExecutionFilter: {
+              ruleIds: input.rule_id ? [String(input.rule_id)] : undefined,
+              executionStatus: input.status,
+              limit: (input.limit as number) ?? 20,
+              offset: (input.offset as number) ?? 0,
+            },
+          }),
+          responseExtractor: governanceExecutionListExtract,
+          description: "List rule evaluations, newest first, optionally for one rule or status",
+        },
+        get: {
+          method: "GET",
+          path: "/ccm/api/governance/execution/{ruleExecutionId}",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { evaluation_id: "ruleExecutionId" },
+          responseExtractor: ngExtract,
+          description: "Get an evaluation's details, including the resources it matched",
+        },
+      },
+    },
   ],
 };
@@ -178,6 +178,13 @@ export const descChaosRecommendation = `Chaos resilience recommendation based on
 
 export const descChaosRisk = `Chaos risk assessment for services and infrastructure. Supports list and get.`;
 
+export const descChaosGameDay = `Chaos GameDay — a planned exercise that groups chaos experiments with an objective so a team can run them together and record observations.
+Supports list, get, create, and execute action: schedule_run (creates a GameDay run, optionally at a future time).
+Use chaos_gameday_run to review a run's experiment results, notes, and outcome.`;
+
+export const descChaosGameDayRun = `A run of a chaos GameDay — status, scheduled/start/end time, per-experiment run results with resiliency scores, and the team's notes and summary.
+Supports list (runs of one GameDay; requires gameday_id) and get (requires gameday_id and run_id).`;
+
 export const descChaosFaultTemplate = `Versioned, structured chaos fault template stored in the database with full CRUD support.
 Use harness_list with resource_type=chaos_hub to discover available hubs and their identities before filtering templates by hub_identity.
 Supports list, get, delete, plus revision history, variables, YAML retrieval, and revision comparison via execute actions.
@@ -642,6 +649,12 @@ export const descGetRecommendation = `Get chaos recommendation details`;
 
 export const descListRisks = `List chaos risks`;
 export const descGetRisk = `Get chaos risk details`;
+export const descListGameDays = `List chaos GameDays. Filter by name with search.`;
+export const descGetGameDay = `Get a chaos GameDay with its objective, experiments, and recent runs`;
+export const descCreateGameDay = `Create a chaos GameDay. Body: name (required), objective, description, experiments (array of { experimentID } from chaos_experiment list).`;
+export const descScheduleGameDayRun = `Create a run of a GameDay. Params: gameday_id (required), name, scheduled_at (epoch ms; omit to start a run now). Team members then execute the GameDay's experiments from the run.`;
+export const descListGameDayRuns = `List runs of a GameDay`;
+export const descGetGameDayRun = `Get a GameDay run with per-experiment results and notes`;
 
 // ── Action Descriptions ──────────────────────────────────────────────
 
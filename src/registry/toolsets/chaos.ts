@@ -32,6 +32,7 @@ import {
   descDiscoveredNetworkMap,
   descChaosGuardCondition, descChaosGuardRule,
   descChaosRecommendation, descChaosRisk,
+  descChaosGameDay, descChaosGameDayRun,
   descChaosAction, descChaosProbeInRun,
   descChaosDRTest, descChaosComponentVariable,
   // Operation descriptions
@@ -57,6 +58,8 @@ import {
   descListGuardRules, descGetGuardRule, descDeleteGuardRule,
   descListRecommendations, descGetRecommendation,
   descListRisks, descGetRisk,
+  descListGameDays, descGetGameDay, descCreateGameDay, descScheduleGameDayRun,
+  descListGameDayRuns, descGetGameDayRun,
   descListDRTests,
   descDeleteProbe, descGetProbeManifest,
   descListProbesInRun,
@@ -478,6 +481,7 @@ export const chaosToolset: ToolsetDefinition = {
   name: "chaos",
   displayName: "Chaos Engineering",
   description: descToolsetChaos,
+  probeResource: "chaos_experiment",
   resources: [
     // ── Chaos Experiments ──────────────────────────────────────────────
     {
@@ -3176,6 +3180,116 @@ export const chaosToolset: ToolsetDefinition = {
       },
     },
 
+    // ── Chaos GameDays ──────────────────────────────────────────────
+    {
+      resourceType: "chaos_gameday",
+      displayName: "Chaos GameDay",
+      description: descChaosGameDay,
+      toolset: "chaos",
+      scope: "project",
+      scopeParams: CHAOS_SCOPE,
+      identifierFields: ["gameday_id"],
+      listFilterFields: [
+        { name: "search", description: "Filter GameDays by name" },
+      ],
+      relatedResources: [
+        { resourceType: "chaos_gameday_run", relationship: "child", description: "Runs of this GameDay" },
+        { resourceType: "chaos_experiment", relationship: "uses", description: "Experiments the GameDay exercises" },
+      ],
+      operations: {
+        list: {
+          method: "GET",
+          path: `${CHAOS}/rest/v2/gamedays`,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          queryParams: {
+            search: "search",
+            page: "page",
+            limit: "limit",
+          },
+          responseExtractor: chaosPageExtract,
+          description: descListGameDays,
+        },
+        get: {
+          method: "GET",
+          path: `${CHAOS}/rest/v2/gamedays/{gamedayId}`,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { gameday_id: "gamedayId" },
+          responseExtractor: passthrough,
+          description: descGetGameDay,
+        },
+        create: {
+          method: "POST",
+          path: `${CHAOS}/rest/v2/gamedays`,
+          operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          bodyBuilder: (input) => input.body,
+          bodySchema: {
+            description: "GameDay definition",
+            fields: [
+              { name: "name", type: "string", required: true, description: "GameDay name" },
+              { name: "objective", type: "string", required: false, description: "What the GameDay sets out to verify" },
+              { name: "description", type: "string", required: false, description: "Description" },
+              { name: "experiments", type: "array", required: false, description: "Experiments to include", itemType: "{ experimentID: string }" },
+            ],
+          },
+          responseExtractor: passthrough,
+          description: descCreateGameDay,
+        },
+      },
+      executeActions: {
+        schedule_run: {
+          method: "POST",
+          path: `${CHAOS}/rest/v2/gamedays/{gamedayId}/runs`,
+          operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          pathParams: { gameday_id: "gamedayId" },
+          bodyBuilder: (input) => ({
+            ...(input.name ? { name: input.name } : {}),
+            ...(input.scheduled_at ? { scheduledAt: Number(input.scheduled_at) } : {}),
+          }),
+          responseExtractor: passthrough,
+          actionDescription: descScheduleGameDayRun,
+        },
+      },
+    },
+
+    {
+      resourceType: "chaos_gameday_run",
+      displayName: "Chaos GameDay Run",
+      description: descChaosGameDayRun,
+      toolset: "chaos",
+      scope: "project",
+      scopeParams: CHAOS_SCOPE,
+      identifierFields: ["gameday_id", "run_id"],
+      listFilterFields: [
+        { name: "gameday_id", description: "GameDay whose runs to list", required: true },
+      ],
+      relatedResources: [
+        { resourceType: "chaos_gameday", relationship: "parent", description: "GameDay this run belongs to" },
+        { resourceType: "chaos_experiment_run", relationship: "child", description: "Experiment runs executed during the GameDay" },
+      ],
+      operations: {
+        list: {
+          method: "GET",
+          path: `${CHAOS}/rest/v2/gamedays/{gamedayId}/runs`,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { gameday_id: "gamedayId" },
+          queryParams: {
+            page: "page",
+            limit: "limit",
+          },
+          responseExtractor: chaosPageExtract,
+          description: descListGameDayRuns,
+        },
+        get: {
+          method: "GET",
+          path: `${CHAOS}/rest/v2/gamedays/{gamedayId}/runs/{runId}`,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { gameday_id: "gamedayId", run_id: "runId" },
+          responseExtractor: passthrough,
+          description: descGetGameDayRun,
+        },
+      },
+    },
+
     // ── Chaos DR Tests ────────────────────────────────────────────────
     {
       resourceType: "chaos_dr_test",
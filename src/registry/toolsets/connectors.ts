@@ -1,6 +1,7 @@
+import YAML from "yaml";
 import type { ToolsetDefinition, BodySchema } from "../types.js";
 import { buildBodyNormalized } from "../../utils/body-normalizer.js";
-import { ngExtract, pageExtract } from "../extractors.js";
+import { ngExtract, pageExtract, connectorCatalogueExtract, dynamicExecutionExtract, jiraIssueExtract } from "../extractors.js";
 
 const connectorCreateSchema: BodySchema = {
   description: "Connector definition",
@@ -26,6 +27,79 @@ const connectorUpdateSchema: BodySchema = {
   ],
 };
 
+/**
+ * Tickets are created through Harness's own JiraCreate / ServiceNowCreate
+ * steps, run as a one-step dynamic execution of a pipeline shell — so they go
+ * through the project's existing connector, delegate, and audit trail.
+ */
+function ticketPipelineYaml(input: Record<string, unknown>, step: Record<string, unknown>): { yaml: string } {
+  const pipelineId = input.pipeline_id;
+  if (typeof pipelineId !== "string" || !pipelineId) {
+    throw new Error("pipeline_id is required: a pipeline in this project with Allow Dynamic Execution enabled. The ticket step runs as a dynamic execution of it.");
+  }
+  return {
+    yaml: YAML.stringify({
+      pipeline: {
+        name: pipelineId,
+        identifier: pipelineId,
+        orgIdentifier: input.org_id,
+        projectIdentifier: input.project_id,
+        stages: [{ stage: { name: "Ticket", identifier: "ticket", type: "Custom", spec: { execution: { steps: [{ step }] } } } }],
+      },
+    }),
+  };
+}
+
+function ticketFields(body: Record<string, unknown>, named: Record<string, unknown>): Array<{ name: string; value: string }> {
+  const extra = body.fields && typeof body.fields === "object" ? body.fields as Record<string, unknown> : {};
+  return Object.entries({ ...named, ...extra })
+    .filter(([, value]) => value !== undefined && value !== null && value !== "")
+    .map(([name, value]) => ({ name, value: typeof value === "string" ? value : JSON.stringify(value) }));
+}
+
+function requireString(body: Record<string, unknown>, field: string, what: string): string {
+  const value = body[field];
+  if (typeof value !== "string" || !value) throw new Error(`body.${field} is required (${what}).`);
+  return value;
+}
+
+function jiraCreateBody(input: Record<string, unknown>): { yaml: string } {
+  const body = (input.body ?? {}) as Record<string, unknown>;
+  return ticketPipelineYaml(input, {
+    name: "Create Jira Issue",
+    identifier: "create_jira_issue",
+    type: "JiraCreate",
+    timeout: "5m",
+    spec: {
+      connectorRef: requireString(body, "connector_ref", "the Jira connector"),
+      projectKey: requireString(body, "project_key", "the Jira project key, e.g. OPS"),
+      issueType: requireString(body, "issue_type", "e.g. Bug, Task, Incident"),
+      fields: ticketFields(body, { Summary: requireString(body, "summary", "the issue title"), Description: body.description }),
+    },
+  });
+}
+
+function serviceNowCreateBody(input: Record<string, unknown>): { yaml: string } {
+  const body = (input.body ?? {}) as Record<string, unknown>;
+  return ticketPipelineYaml(input, {
+    name: "Create ServiceNow Ticket",
+    identifier: "create_servicenow_ticket",
+    type: "ServiceNowCreate",
+    timeout: "5m",
+    spec: {
+      connectorRef: requireString(body, "connector_ref", "the ServiceNow connector"),
+      ticketType: body.ticket_type ?? "INCIDENT",
+      createType: "Normal",
+      useServiceNowTemplate: false,
+      fields: ticketFields(body, { short_description: requireString(body, "short_description", "the ticket title"), description: body.description }),
+    },
+  });
+}
+
+const TICKET_CREATE_HINT =
+  "Returns { execution_id, status } of the ticket step's run. The ticket key is in the step outcome once it finishes — " +
+  "harness_get(resource_type='execution', resource_id=<execution_id>). Requires pipeline_id: a pipeline shell in the project with Allow Dynamic Execution enabled.";
+
 export const connectorsToolset: ToolsetDefinition = {
   name: "connectors",
   displayName: "Connectors",
@@ -122,6 +196,7 @@ export const connectorsToolset: ToolsetDefinition = {
           method: "DELETE",
           path: "/ng/api/connectors/{connectorIdentifier}",
           operationPolicy: { risk: "destructive", retryPolicy: "do_not_retry" },
+          requiredPermission: { resourceType: "CONNECTOR", permission: "core_connector_delete" },
           pathParams: { connector_id: "connectorIdentifier" },
           responseExtractor: ngExtract,
           description: "Delete a connector",
@@ -140,10 +215,97 @@ export const connectorsToolset: ToolsetDefinition = {
         },
       },
     },
+    {
+      resourceType: "jira_issue",
+      displayName: "Jira Issue",
+      description:
+        "Jira issues through a Harness Jira connector. Get returns an issue's status, summary, and link; use it to check the ticket behind a JiraApproval (approval_instance) or one created here. " +
+        "The create execute action files an issue by running Harness's JiraCreate step, so it uses the connector's credentials and delegate.",
+      toolset: "connectors",
+      scope: "project",
+      identifierFields: ["issue_key"],
+      relatedResources: [
+        { resourceType: "connector", relationship: "uses", description: "The Jira connector (connector_ref)" },
+        { resourceType: "approval_instance", relationship: "related", description: "JiraApproval instances reference the issue they wait on" },
+      ],
+      operations: {
+        get: {
+          method: "GET",
+          path: "/ng/api/jira/issue",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          queryParams: { issue_key: "issueKey", connector_ref: "connectorRef" },
+          responseExtractor: jiraIssueExtract,
+          description: "Get a Jira issue's status and fields. Requires issue_key and connector_ref.",
+          paramsSchema: { fields: [{ name: "connector_ref", required: true, description: "Jira connector identifier (prefix org. or account. for higher scopes)" }] },
+        },
+      },
+      executeActions: {
+        create: {
+          method: "POST",
+          path: "/v1/orgs/{org}/projects/{project}/pipelines/{pipeline}/execute/dynamic",
+          operationPolicy: { risk: "medium_write", retryPolicy: "do_not_retry" },
+          pathParams: { org_id: "org", project_id: "project", pipeline_id: "pipeline" },
+          headerBasedScoping: true,
+          skipScopeBodyInjection: true,
+          bodyBuilder: jiraCreateBody,
+          responseExtractor: dynamicExecutionExtract,
+          actionDescription: `Create a Jira issue through a Harness Jira connector. ${TICKET_CREATE_HINT}`,
+          bodySchema: {
+            description: "Jira issue to create",
+            fields: [
+              { name: "connector_ref", type: "string", required: true, description: "Jira connector identifier" },
+              { name: "project_key", type: "string", required: true, description: "Jira project key (e.g. OPS)" },
+              { name: "issue_type", type: "string", required: true, description: "Issue type (e.g. Bug, Task, Incident)" },
+              { name: "summary", type: "string", required: true, description: "Issue title" },
+              { name: "description", type: "string", required: false, description: "Issue description" },
+              { name: "fields", type: "object", required: false, description: "Other Jira fields by display name (e.g. {Priority: 'High', Labels: 'sev2'})" },
+            ],
+          },
+        },
+      },
+    },
+    {
+      resourceType: "servicenow_ticket",
+      displayName: "ServiceNow Ticket",
+      description:
+        "ServiceNow tickets through a Harness ServiceNow connector. The create execute action files a ticket by running Harness's ServiceNowCreate step. " +
+        "For the status of a ticket a ServiceNowApproval waits on, list approval_instance for the execution.",
+      toolset: "connectors",
+      scope: "project",
+      identifierFields: [],
+      relatedResources: [
+        { resourceType: "connector", relationship: "uses", description: "The ServiceNow connector (connector_ref)" },
+        { resourceType: "approval_instance", relationship: "related", description: "ServiceNowApproval instances report the ticket they wait on" },
+      ],
+      operations: {},
+      executeActions: {
+        create: {
+          method: "POST",
+          path: "/v1/orgs/{org}/projects/{project}/pipelines/{pipeline}/execute/dynamic",
+          operationPolicy: { risk: "medium_write", retryPolicy: "do_not_retry" },
+          pathParams: { org_id: "org", project_id: "project", pipeline_id: "pipeline" },
+          headerBasedScoping: true,
+          skipScopeBodyInjection: true,
+          bodyBuilder: serviceNowCreateBody,
+          responseExtractor: dynamicExecutionExtract,
+          actionDescription: `Create a ServiceNow ticket through a Harness ServiceNow connector. ${TICKET_CREATE_HINT}`,
+          bodySchema: {
+            description: "ServiceNow ticket to create",
+            fields: [
+              { name: "connector_ref", type: "string", required: true, description: "ServiceNow connector identifier" },
+              { name: "ticket_type", type: "string", required: false, description: "Ticket table (default INCIDENT; e.g. CHANGE_REQUEST, PROBLEM)" },
+              { name: "short_description", type: "string", required: true, description: "Ticket title" },
+              { name: "description", type: "string", required: false, description: "Ticket description" },
+              { name: "fields", type: "object", required: false, description: "Other ticket fields by column name (e.g. {urgency: '2', assignment_group: 'SRE'})" },
+            ],
+          },
+        },
+      },
+    },
     {
       resourceType: "connector_catalogue",
       displayName: "Connector Catalogue",
-      description: "Catalogue of available connector types. Supports list only.",
+      description: "Catalogue of available connector types grouped by category, with a docs link per category. Supports list only. Skeleton specs for common types are available via harness_schema(resource_type='connector', example_search='<Type>').",
       toolset: "connectors",
       scope: "account",
       identifierFields: [],
@@ -152,7 +314,9 @@ export const connectorsToolset: ToolsetDefinition = {
           method: "GET",
           path: "/ng/api/connectors/catalogue",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
-          responseExtractor: ngExtract,
+          // Connector types only change with Harness releases
+          diskCacheTtlMs: 24 * 60 * 60 * 1000,
+          responseExtractor: connectorCatalogueExtract,
           description: "List all available connector types in the catalogue",
         },
       },
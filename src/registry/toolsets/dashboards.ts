@@ -1,5 +1,10 @@
-import type { ToolsetDefinition, ParamsSchema } from "../types.js";
-import { dashboardListExtract, dashboardDataExtract } from "../extractors.js";
+import type { BodySchema, ToolsetDefinition, ParamsSchema } from "../types.js";
+import {
+  dashboardListExtract,
+  dashboardDataExtract,
+  dashboardResourceExtract,
+  dashboardTileListExtract,
+} from "../extractors.js";
 
 const DASHBOARD_DATA_GET_PARAMS: ParamsSchema = {
   fields: [
@@ -11,6 +16,53 @@ const DASHBOARD_DATA_GET_PARAMS: ParamsSchema = {
   ],
 };
 
+const TILE_VISUALIZATIONS = ["single_value", "looker_line", "looker_column", "looker_bar", "looker_area", "looker_pie", "table"];
+
+/**
+ * Build a tile from a query definition. Queries address a Harness dashboard
+ * model/explore and its fields — copy names from an existing tile
+ * (harness_list resource_type='dashboard_tile') on a built-in dashboard.
+ */
+function dashboardTileBody(input: Record<string, unknown>): Record<string, unknown> {
+  const body = (input.body && typeof input.body === "object" ? input.body : {}) as Record<string, unknown>;
+  const query = (body.query ?? {}) as Record<string, unknown>;
+  const fields = query.fields;
+  if (!query.model || !query.view || !Array.isArray(fields) || fields.length === 0) {
+    throw new Error(
+      "dashboard_tile requires query: { model, view, fields: [...] }. " +
+      "List the tiles of a built-in dashboard (harness_list resource_type='dashboard_tile') to find model, view, and field names.",
+    );
+  }
+  const type = body.type ?? "looker_column";
+  if (!TILE_VISUALIZATIONS.includes(String(type))) {
+    throw new Error(`Unsupported tile type '${String(type)}'. Use one of: ${TILE_VISUALIZATIONS.join(", ")}.`);
+  }
+  return {
+    title: body.title,
+    type,
+    note: body.note,
+    query: {
+      model: query.model,
+      view: query.view,
+      fields,
+      filters: query.filters,
+      sorts: query.sorts,
+      pivots: query.pivots,
+      limit: query.limit ?? 500,
+    },
+  };
+}
+
+const dashboardTileBodySchema: BodySchema = {
+  description: "Dashboard tile with a query definition",
+  fields: [
+    { name: "title", type: "string", required: true, description: "Tile title (e.g. 'Deployments per week')" },
+    { name: "type", type: "string", required: false, description: `Visualization: ${TILE_VISUALIZATIONS.join(", ")} (default looker_column)` },
+    { name: "query", type: "object", required: true, description: "{ model, view, fields: [...], filters?: { field: expression }, sorts?: [...], pivots?: [...], limit? } — e.g. filters { 'pipeline_execution_summary_cd.status': 'SUCCESS' }" },
+    { name: "note", type: "string", required: false, description: "Text shown under the tile title" },
+  ],
+};
+
 export const dashboardsToolset: ToolsetDefinition = {
   name: "dashboards",
   displayName: "Dashboards",
@@ -19,7 +71,7 @@ export const dashboardsToolset: ToolsetDefinition = {
     {
       resourceType: "dashboard",
       displayName: "Dashboard",
-      description: "Custom analytics dashboard. Supports list. Use dashboard_data to fetch content.",
+      description: "Custom analytics dashboard. Supports list and create. Use dashboard_data to fetch content and dashboard_tile to add charts.",
       toolset: "dashboards",
       scope: "account",
       identifierFields: ["dashboard_id"],
@@ -48,6 +100,74 @@ export const dashboardsToolset: ToolsetDefinition = {
           responseExtractor: dashboardListExtract,
           description: "List dashboards",
         },
+        create: {
+          method: "POST",
+          path: "/dashboard/v1/dashboards",
+          operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          skipScopeBodyInjection: true,
+          bodyBuilder: (input) => {
+            const body = (input.body ?? {}) as Record<string, unknown>;
+            return {
+              title: body.title,
+              description: body.description,
+              resourceIdentifier: body.folder_id ?? body.resourceIdentifier ?? "shared",
+            };
+          },
+          responseExtractor: dashboardResourceExtract,
+          description: "Create an empty custom dashboard. Add tiles with harness_create(resource_type='dashboard_tile').",
+          bodySchema: {
+            description: "New custom dashboard",
+            fields: [
+              { name: "title", type: "string", required: true, description: "Dashboard title" },
+              { name: "description", type: "string", required: false, description: "Dashboard description" },
+              { name: "resourceIdentifier", type: "string", required: false, description: "Folder ID (pass as folder_id; default 'shared')" },
+            ],
+          },
+        },
+      },
+    },
+    {
+      resourceType: "dashboard_tile",
+      displayName: "Dashboard Tile",
+      description:
+        "Tile on a custom dashboard, defined by a query against a dashboard model/view. Supports list, create, and update. " +
+        "List the tiles of a built-in dashboard to discover model, view, and field names before building a new tile.",
+      toolset: "dashboards",
+      scope: "account",
+      identifierFields: ["dashboard_id", "tile_id"],
+      deepLinkTemplate: "/ng/account/{accountId}/dashboards/folder/shared/view/{dashboardId}",
+      operations: {
+        list: {
+          method: "GET",
+          path: "/dashboard/v1/dashboards/{dashboardId}/tiles",
+          pathParams: { dashboard_id: "dashboardId" },
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseExtractor: dashboardTileListExtract,
+          description: "List a dashboard's tiles with their title, visualization type, and query (model, view, fields, filters).",
+        },
+        create: {
+          method: "POST",
+          path: "/dashboard/v1/dashboards/{dashboardId}/tiles",
+          pathParams: { dashboard_id: "dashboardId" },
+          operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          skipScopeBodyInjection: true,
+          bodyBuilder: dashboardTileBody,
+          responseExtractor: dashboardResourceExtract,
+          description:
+            "Add a tile to a dashboard from a query definition. Pass body: { title, type?, query: { model, view, fields, filters?, sorts?, pivots?, limit? } }.",
+          bodySchema: dashboardTileBodySchema,
+        },
+        update: {
+          method: "PATCH",
+          path: "/dashboard/v1/dashboards/{dashboardId}/tiles/{tileId}",
+          pathParams: { dashboard_id: "dashboardId", tile_id: "tileId" },
+          operationPolicy: { risk: "low_write", retryPolicy: "safe" },
+          skipScopeBodyInjection: true,
+          bodyBuilder: dashboardTileBody,
+          responseExtractor: dashboardResourceExtract,
+          description: "Replace a tile's title, visualization, and query. Pass the same body as create.",
+          bodySchema: dashboardTileBodySchema,
+        },
       },
     },
     {
@@ -1,5 +1,5 @@
 import type { ToolsetDefinition } from "../types.js";
-import { ngExtract, v1Unwrap } from "../extractors.js";
+import { ngExtract, v1Unwrap, delegateTaskLogExtract } from "../extractors.js";
 
 export const delegatesToolset: ToolsetDefinition = {
   name: "delegates",
@@ -51,6 +51,40 @@ export const delegatesToolset: ToolsetDefinition = {
         },
       },
     },
+    {
+      resourceType: "delegate_task_log",
+      displayName: "Delegate Task Log",
+      description:
+        "Delegate-side log lines for specific delegate tasks (the delegate's own logs, not the step console log). " +
+        "List-only; requires task_ids from an execution step's delegateInfoList. " +
+        "Use harness_diagnose(resource_type='delegate_task_logs') to find the task IDs for a failed step automatically.",
+      toolset: "delegates",
+      scope: "account",
+      scopeOptional: true,
+      identifierFields: [],
+      listFilterFields: [
+        { name: "task_ids", description: "Delegate task IDs (array)", required: true },
+        { name: "start_time", description: "Window start (epoch ms)", type: "number" },
+        { name: "end_time", description: "Window end (epoch ms)", type: "number" },
+        { name: "page_token", description: "next_page_token from a previous call" },
+      ],
+      operations: {
+        list: {
+          method: "GET",
+          path: "/ng/api/delegate-logs",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          queryParams: {
+            task_ids: "taskIds",
+            start_time: "startTime",
+            end_time: "endTime",
+            page_token: "pageToken",
+            size: "pageSize",
+          },
+          responseExtractor: delegateTaskLogExtract,
+          description: "List delegate log lines for the given task IDs",
+        },
+      },
+    },
     {
       resourceType: "delegate_token",
       displayName: "Delegate Token",
@@ -1,5 +1,6 @@
 import type { ToolsetDefinition, BodySchema } from "../types.js";
 import { buildBodyNormalized } from "../../utils/body-normalizer.js";
+import { GIT_READ_QUERY_PARAMS, GIT_WRITE_QUERY_PARAMS } from "../git-details.js";
 import { ngExtract, pageExtract } from "../extractors.js";
 
 const environmentCreateSchema: BodySchema = {
@@ -66,13 +67,15 @@ export const environmentsToolset: ToolsetDefinition = {
           path: "/ng/api/environmentsV2/{environmentIdentifier}",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
           pathParams: { environment_id: "environmentIdentifier" },
+          queryParams: GIT_READ_QUERY_PARAMS,
           responseExtractor: ngExtract,
-          description: "Get environment details",
+          description: "Get environment details. For remote (Git-stored) environments, pass branch or git_details.",
         },
         create: {
           method: "POST",
           path: "/ng/api/environmentsV2",
           operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          queryParams: GIT_WRITE_QUERY_PARAMS,
           bodyBuilder: buildBodyNormalized({ unwrapKey: "environment" }),
           responseExtractor: ngExtract,
           description: "Create a new environment",
@@ -82,6 +85,7 @@ export const environmentsToolset: ToolsetDefinition = {
           method: "PUT",
           path: "/ng/api/environmentsV2",
           operationPolicy: { risk: "low_write", retryPolicy: "safe" },
+          queryParams: { ...GIT_WRITE_QUERY_PARAMS, last_object_id: "lastObjectId", last_commit_id: "lastCommitId" },
           bodyBuilder: buildBodyNormalized({
             unwrapKey: "environment",
             injectIdentifier: { inputField: "environment_id", bodyField: "identifier" },
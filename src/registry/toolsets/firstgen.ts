@@ -0,0 +1,150 @@
+import type { ToolsetDefinition, PathBuilderConfig } from "../types.js";
+import { cgGqlListExtract, cgGqlGetExtract } from "../extractors.js";
+
+// ---------------------------------------------------------------------------
+// FirstGen (CG) GraphQL — served by the CG manager, not the NG gateway.
+// Accounts mid-migration still run workflows here; these read-only resources
+// let agents see that half of the estate.
+// ---------------------------------------------------------------------------
+
+const CG_PAGE_SIZE = 20;
+
+const CG_APPLICATIONS_QUERY = `
+query ListApplications($limit: Int!, $offset: Int) {
+  applications(limit: $limit, offset: $offset) {
+    nodes { id name description createdAt }
+    pageInfo { total hasMore }
+  }
+}`;
+
+const CG_WORKFLOWS_QUERY = `
+query ListWorkflows($filters: [WorkflowFilter], $limit: Int!, $offset: Int) {
+  workflows(filters: $filters, limit: $limit, offset: $offset) {
+    nodes { id name description createdAt }
+    pageInfo { total hasMore }
+  }
+}`;
+
+const CG_EXECUTION_QUERY = `
+query GetExecution($executionId: String!) {
+  execution(executionId: $executionId) {
+    id
+    status
+    createdAt
+    startedAt
+    endedAt
+    application { id name }
+    ... on WorkflowExecution { workflow { id name } }
+    ... on PipelineExecution { pipeline { id name } }
+  }
+}`;
+
+/** CG GraphQL takes the account as `accountId` (not `accountIdentifier`). */
+function cgGqlPath(_input: Record<string, unknown>, config: PathBuilderConfig): string {
+  return `/gateway/api/graphql?accountId=${config.HARNESS_ACCOUNT_ID ?? ""}`;
+}
+
+function pageVariables(input: Record<string, unknown>): { limit: number; offset: number } {
+  const limit = typeof input.size === "number" ? input.size : CG_PAGE_SIZE;
+  const page = typeof input.page === "number" ? input.page : 0;
+  return { limit, offset: page * limit };
+}
+
+export const firstgenToolset: ToolsetDefinition = {
+  name: "firstgen",
+  displayName: "FirstGen (CG)",
+  description:
+    "Harness FirstGen (CurrentGen) — read-only view of CG applications, workflows, and deployments " +
+    "for accounts that still run FirstGen alongside NextGen.",
+  optIn: true,
+  resources: [
+    {
+      resourceType: "cg_application",
+      displayName: "FirstGen Application",
+      description:
+        "A FirstGen (CG) application — the container for CG services, environments, workflows, and pipelines. " +
+        "List-only. Use the returned id as app_id when listing cg_workflow.",
+      toolset: "firstgen",
+      scope: "account",
+      identifierFields: ["app_id"],
+      operations: {
+        list: {
+          method: "POST",
+          path: "/gateway/api/graphql",
+          pathBuilder: cgGqlPath,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          headerBasedScoping: true,
+          skipScopeBodyInjection: true,
+          bodyBuilder: (input) => ({
+            query: CG_APPLICATIONS_QUERY,
+            variables: pageVariables(input),
+          }),
+          responseExtractor: cgGqlListExtract("applications"),
+          description: "List FirstGen applications in the account",
+        },
+      },
+    },
+    {
+      resourceType: "cg_workflow",
+      displayName: "FirstGen Workflow",
+      description:
+        "A FirstGen (CG) workflow. List-only; pass app_id to restrict to one application.",
+      toolset: "firstgen",
+      scope: "account",
+      identifierFields: ["workflow_id"],
+      listFilterFields: [
+        { name: "app_id", description: "FirstGen application ID (from cg_application list)" },
+      ],
+      relatedResources: [
+        { resourceType: "cg_application", relationship: "parent", description: "Application that owns the workflow" },
+      ],
+      operations: {
+        list: {
+          method: "POST",
+          path: "/gateway/api/graphql",
+          pathBuilder: cgGqlPath,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          headerBasedScoping: true,
+          skipScopeBodyInjection: true,
+          bodyBuilder: (input) => ({
+            query: CG_WORKFLOWS_QUERY,
+            variables: {
+              ...pageVariables(input),
+              ...(input.app_id
+                ? { filters: [{ application: { operator: "EQUALS", values: [String(input.app_id)] } }] }
+                : {}),
+            },
+          }),
+          responseExtractor: cgGqlListExtract("workflows"),
+          description: "List FirstGen workflows, optionally filtered by application",
+        },
+      },
+    },
+    {
+      resourceType: "cg_deployment",
+      displayName: "FirstGen Deployment",
+      description:
+        "A FirstGen (CG) workflow or pipeline execution. Get-only by execution_id — returns status, timing, " +
+        "application, and the workflow or pipeline that ran.",
+      toolset: "firstgen",
+      scope: "account",
+      identifierFields: ["execution_id"],
+      operations: {
+        get: {
+          method: "POST",
+          path: "/gateway/api/graphql",
+          pathBuilder: cgGqlPath,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          headerBasedScoping: true,
+          skipScopeBodyInjection: true,
+          bodyBuilder: (input) => ({
+            query: CG_EXECUTION_QUERY,
+            variables: { executionId: String(input.execution_id ?? "") },
+          }),
+          responseExtractor: cgGqlGetExtract("execution"),
+          description: "Get a FirstGen deployment (workflow or pipeline execution) by ID",
+        },
+      },
+    },
+  ],
+};
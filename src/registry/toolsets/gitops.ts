@@ -177,6 +177,7 @@ export const gitopsToolset: ToolsetDefinition = {
   displayName: "GitOps",
   description:
     "Harness GitOps — agents, applications, clusters, and repositories",
+  probeResource: "gitops_agent",
   resources: [
     {
       resourceType: "gitops_agent",
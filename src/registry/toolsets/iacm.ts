@@ -158,6 +158,7 @@ export const iacmToolset: ToolsetDefinition = {
     "iacm_module for the module registry, iacm_workspace_costs for cost breakdown, " +
     "and iacm_activity_resource_change for activity diffs.",
   optIn: false,
+  probeResource: "iacm_workspace",
   resources: [
     // ─── Workspace ─────────────────────────────────────────────────────────
     {
@@ -1,4 +1,5 @@
 import type { ToolsetDefinition } from "../types.js";
+import { GIT_READ_QUERY_PARAMS, GIT_WRITE_QUERY_PARAMS } from "../git-details.js";
 import { ngExtract, pageExtract } from "../extractors.js";
 
 export const infrastructureToolset: ToolsetDefinition = {
@@ -44,14 +45,15 @@ export const infrastructureToolset: ToolsetDefinition = {
           path: "/ng/api/infrastructures/{infraIdentifier}",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
           pathParams: { infrastructure_id: "infraIdentifier" },
-          queryParams: { environment_id: "environmentIdentifier" },
+          queryParams: { environment_id: "environmentIdentifier", ...GIT_READ_QUERY_PARAMS },
           responseExtractor: ngExtract,
-          description: "Get infrastructure definition details",
+          description: "Get infrastructure definition details. For remote (Git-stored) infrastructure definitions, pass branch or git_details.",
         },
         create: {
           method: "POST",
           path: "/ng/api/infrastructures",
           operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          queryParams: GIT_WRITE_QUERY_PARAMS,
           bodyBuilder: (input) => input.body,
           bodySchema: {
             description: "Infrastructure definition",
@@ -71,6 +73,7 @@ export const infrastructureToolset: ToolsetDefinition = {
           method: "PUT",
           path: "/ng/api/infrastructures",
           operationPolicy: { risk: "low_write", retryPolicy: "safe" },
+          queryParams: { ...GIT_WRITE_QUERY_PARAMS, last_object_id: "lastObjectId", last_commit_id: "lastCommitId" },
           bodyBuilder: (input) => input.body,
           bodySchema: {
             description: "Infrastructure definition update",
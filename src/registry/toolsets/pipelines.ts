@@ -1,6 +1,90 @@
 import type { ToolsetDefinition, BodySchema, ParamsSchema } from "../types.js";
-import { ngExtract, pageExtract, passthrough, v1ListExtract, runtimeInputExtract, executionInputsExtract, dynamicExecutionExtract } from "../extractors.js";
+import { ngExtract, pageExtract, passthrough, v1ListExtract, runtimeInputExtract, pipelineInputSchemaExtract, constraintQueueExtract, executionInputsExtract, dynamicExecutionExtract, cvExtract, cvPageExtract } from "../extractors.js";
 import YAML from "yaml";
+import { GIT_READ_QUERY_PARAMS } from "../git-details.js";
+import { parseTimeInput, TIME_INPUT_FORMATS } from "../../utils/time-range.js";
+import { parseStageIdentifiers } from "../../utils/runtime-input-resolver.js";
+
+/**
+ * The notes API takes the text as a query param, not a body. Accept
+ * `body: { notes }` (or a bare string body) and hoist it onto `input.notes`.
+ */
+function hoistExecutionNotes(input: Record<string, unknown>): undefined {
+  const body = input.body;
+  const notes = typeof body === "string"
+    ? body
+    : body && typeof body === "object" ? (body as Record<string, unknown>).notes : undefined;
+  if (typeof notes !== "string") {
+    throw new Error("body.notes (string) is required. Pass body: { notes: \"...\" } — it replaces the execution's existing notes.");
+  }
+  input.notes = notes;
+  return undefined;
+}
+
+/**
+ * Pipeline run goes to `/execute/{id}/stages` when `stage_identifiers` selects
+ * stages (allowed stage executions, as in the UI's "Run specific stages").
+ */
+function pipelineRunPath(input: Record<string, unknown>): string {
+  const pipelineId = input.pipeline_id;
+  if (typeof pipelineId !== "string" || pipelineId === "") {
+    throw new Error('Missing required field "pipeline_id" for pipeline.');
+  }
+  const base = `/pipeline/api/pipeline/execute/${encodeURIComponent(pipelineId)}`;
+  return parseStageIdentifiers(input.stage_identifiers).length > 0 ? `${base}/stages` : base;
+}
+
+/**
+ * Body for pipeline run. A full run takes the runtime input YAML as the raw
+ * body; a selective stage run wraps it in RunStageRequestDTO with the stage
+ * list and any expression values the selected stages need from skipped ones.
+ */
+function buildPipelineRunBody(input: Record<string, unknown>): unknown {
+  const inputs = input.inputs;
+  const stageIdentifiers = parseStageIdentifiers(input.stage_identifiers);
+  const expressionValues = input.expression_values;
+  if (expressionValues !== undefined && (typeof expressionValues !== "object" || expressionValues === null || Array.isArray(expressionValues))) {
+    throw new Error("expression_values must be an object mapping expressions to values, e.g. {\"<+pipeline.stages.build.output.tag>\": \"v1.2\"}.");
+  }
+  if (stageIdentifiers.length === 0) {
+    if (expressionValues !== undefined) {
+      throw new Error("expression_values only applies to selective stage runs. Pass stage_identifiers with the stages to run.");
+    }
+    // No inline runtime inputs: send empty YAML so Harness applies
+    // `inputSetIdentifiers` from the query string. HarnessClient must pass `""`
+    // as the fetch body (not omit it) — see serializeRequestBody / hasExplicitBody.
+    if (!inputs) return "";
+    // Already a YAML string (pre-resolved by execute tool handler or passed directly)
+    if (typeof inputs === "string") return inputs;
+    // Object — serialize as JSON for the API (full pipeline YAML structure)
+    return JSON.stringify(inputs);
+  }
+  // JSON is valid YAML, so this goes out under the same application/yaml content type
+  return JSON.stringify({
+    runtimeInputYaml: !inputs ? "" : typeof inputs === "string" ? inputs : YAML.stringify(inputs),
+    stageIdentifiers,
+    ...(expressionValues ? { expressionValues } : {}),
+  });
+}
+
+/**
+ * Build the PipelineExecution filter body for execution list. Trigger types and
+ * the time range are body-only filters; everything else maps to query params.
+ */
+function buildExecutionFilterBody(input: Record<string, unknown>): Record<string, unknown> {
+  const body: Record<string, unknown> = { filterType: "PipelineExecution" };
+  const triggerTypes = (Array.isArray(input.trigger_type) ? input.trigger_type : String(input.trigger_type ?? "").split(","))
+    .map((t) => String(t).trim())
+    .filter(Boolean);
+  if (triggerTypes.length > 0) body.triggerTypes = triggerTypes;
+
+  const startTime = parseTimeInput(input.start_time);
+  const endTime = parseTimeInput(input.end_time, { edge: "end" });
+  if (startTime !== undefined || endTime !== undefined) {
+    body.timeRange = { startTime: startTime ?? 0, endTime: endTime ?? Date.now() };
+  }
+  return body;
+}
 
 /**
  * Normalize a trigger body into the canonical `{ trigger: { ... } }` shape,
@@ -173,6 +257,15 @@ const inputSetUpdateSchema: BodySchema = {
   ],
 };
 
+const yamlBundleApplySchema: BodySchema = {
+  description: "Bundle parameters (pass via params). Documents are classified by top-level key, applied in dependency order (connector, environment, infrastructureDefinition, service, template, pipeline, inputSet/overlayInputSet, trigger), and created or updated. A failed document does not stop the rest.",
+  fields: [
+    { name: "documents", type: "string", required: true, description: "A multi-document YAML string (--- separated), or an array of YAML documents" },
+    { name: "mode", type: "string", required: false, description: "upsert (default — update existing entities) or create_only (skip entities that already exist)" },
+    { name: "dry_run", type: "boolean", required: false, description: "Classify, order, and check existence without writing anything" },
+  ],
+};
+
 export const pipelinesToolset: ToolsetDefinition = {
   name: "pipelines",
   displayName: "Pipelines",
@@ -181,12 +274,13 @@ export const pipelinesToolset: ToolsetDefinition = {
     {
       resourceType: "pipeline",
       displayName: "Pipeline",
-      description: "CI/CD pipeline definition. Supports list, get, create, update, delete, and execute (run).",
+      description: "CI/CD pipeline definition. Supports list, get, create, update, delete, and execute (run). List with resource_scope=org or account to roll up across projects.",
       toolset: "pipelines",
       scope: "project",
+      rollupScopes: ["org", "account"],
       identifierFields: ["pipeline_id"],
       diagnosticHint: "Use harness_diagnose with pipeline_id or execution_id to analyze failures — includes step-level error details, log snippets, delegate info, and chained pipeline traversal.",
-      executeHint: "Before executing, check required inputs: harness_get(resource_type='runtime_input_template', resource_id='PIPELINE_ID'). For simple variables, pass key-value pairs in inputs. For CI pipelines with codebase: pass {branch: 'main'}, {tag: 'v1.0'}, {pr_number: '42'}, or {commit_sha: 'abc123'} — auto-expanded to the full build structure. For complex template inputs, use input_set_ids — list available sets with harness_list(resource_type='input_set', filters={pipeline_id: '...'}).",
+      executeHint: "Before executing, check required inputs: harness_get(resource_type='runtime_input_template', resource_id='PIPELINE_ID'), or harness_get(resource_type='pipeline_input_schema', resource_id='PIPELINE_ID') for a JSON Schema of the accepted input keys. For simple variables, pass key-value pairs in inputs. For CI pipelines with codebase: pass {branch: 'main'}, {tag: 'v1.0'}, {pr_number: '42'}, or {commit_sha: 'abc123'} — auto-expanded to the full build structure. For complex template inputs, use input_set_ids — list available sets with harness_list(resource_type='input_set', filters={pipeline_id: '...'}).",
       listFilterFields: [
         { name: "search_term", description: "Filter pipelines by name or keyword" },
         { name: "module", description: "Harness module filter", enum: ["CD", "CI", "CV", "CF", "CE", "STO"] },
@@ -215,14 +309,9 @@ export const pipelinesToolset: ToolsetDefinition = {
           path: "/pipeline/api/pipelines/{pipelineIdentifier}",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
           pathParams: { pipeline_id: "pipelineIdentifier" },
-          queryParams: {
-            branch: "branch",
-            store_type: "storeType",
-            connector_ref: "connectorRef",
-            repo_name: "repoName",
-          },
+          queryParams: GIT_READ_QUERY_PARAMS,
           responseExtractor: ngExtract,
-          description: "Get pipeline details including YAML definition. For remote/git-backed pipelines, pass branch to specify which branch to read from.",
+          description: "Get pipeline details including YAML definition. For remote/git-backed pipelines, pass branch (or git_details) to specify which branch to read from; load_from_fallback_branch reads the default branch when the branch lacks the file.",
         },
         create: {
           method: "POST",
@@ -295,6 +384,7 @@ export const pipelinesToolset: ToolsetDefinition = {
           method: "DELETE",
           path: "/pipeline/api/pipelines/{pipelineIdentifier}",
           operationPolicy: { risk: "destructive", retryPolicy: "do_not_retry" },
+          requiredPermission: { resourceType: "PIPELINE", permission: "core_pipeline_delete" },
           pathParams: { pipeline_id: "pipelineIdentifier" },
           responseExtractor: ngExtract,
           description: "Delete a pipeline",
@@ -304,7 +394,9 @@ export const pipelinesToolset: ToolsetDefinition = {
         run: {
           method: "POST",
           path: "/pipeline/api/pipeline/execute/{pipelineIdentifier}",
+          pathBuilder: pipelineRunPath,
           operationPolicy: { risk: "high_write", retryPolicy: "do_not_retry" },
+          requiredPermission: { resourceType: "PIPELINE", permission: "core_pipeline_execute" },
           pathParams: { pipeline_id: "pipelineIdentifier" },
           queryParams: {
             module: "module",
@@ -316,17 +408,7 @@ export const pipelinesToolset: ToolsetDefinition = {
             repo_name: "repoName",
           },
           headers: { "Content-Type": "application/yaml" },
-          bodyBuilder: (input) => {
-            const inputs = input.inputs;
-            // No inline runtime inputs: send empty YAML so Harness applies
-            // `inputSetIdentifiers` from the query string. HarnessClient must pass `""`
-            // as the fetch body (not omit it) — see serializeRequestBody / hasExplicitBody.
-            if (!inputs) return "";
-            // Already a YAML string (pre-resolved by execute tool handler or passed directly)
-            if (typeof inputs === "string") return inputs;
-            // Object — serialize as JSON for the API (full pipeline YAML structure)
-            return JSON.stringify(inputs);
-          },
+          bodyBuilder: buildPipelineRunBody,
           responseExtractor: ngExtract,
           inputExpansions: [
             {
@@ -350,13 +432,15 @@ export const pipelinesToolset: ToolsetDefinition = {
               skipIfPresent: "build",
             },
           ],
-          actionDescription: "Execute/run a pipeline. RECOMMENDED: first check harness_get(resource_type='runtime_input_template', resource_id='PIPELINE_ID') to see required inputs. For simple variable inputs: pass key-value pairs in inputs (e.g. {branch: 'main'}) — auto-resolved. For CI pipelines with codebase: pass {branch: 'main'}, {tag: 'v1.0'}, {pr_number: '42'}, or {commit_sha: 'abc123'} — auto-expanded to the full build structure. For complex pipelines with template inputs: use input_set_ids to reference a saved input set. List available sets with harness_list(resource_type='input_set', filters={pipeline_id: '...'}). To load the pipeline YAML from a specific git branch (e.g. a feature branch): pass params={pipeline_branch: 'feature/my-fix'} — sent as ?pipelineBranchName= on the API call.",
+          actionDescription: "Execute/run a pipeline. RECOMMENDED: first check harness_get(resource_type='runtime_input_template', resource_id='PIPELINE_ID') to see required inputs. For simple variable inputs: pass key-value pairs in inputs (e.g. {branch: 'main'}) — auto-resolved. For CI pipelines with codebase: pass {branch: 'main'}, {tag: 'v1.0'}, {pr_number: '42'}, or {commit_sha: 'abc123'} — auto-expanded to the full build structure. For complex pipelines with template inputs: use input_set_ids to reference a saved input set. List available sets with harness_list(resource_type='input_set', filters={pipeline_id: '...'}). To load the pipeline YAML from a specific git branch (e.g. a feature branch): pass params={pipeline_branch: 'feature/my-fix'} — sent as ?pipelineBranchName= on the API call. To run only some stages: pass stage_identifiers (e.g. ['build', 'deploy_qa']); expression_values supplies values for expressions that reference outputs of skipped stages.",
           bodySchema: {
             description: "Runtime inputs for pipeline execution. For simple variables: pass key-value pairs in inputs like {branch: 'main', env: 'prod'}, auto-resolved against the pipeline's runtime input template. CI codebase shorthands (branch, tag, pr_number, commit_sha) are auto-expanded to full build structures. For complex pipelines with template inputs, use input_set_ids to reference saved input sets. You can combine both: input_set_ids for the base config + inputs for simple overrides. Check runtime_input_template first to see what the pipeline expects.",
             fields: [
               { name: "inputs", type: "yaml", required: false, description: "Key-value pairs (e.g. {branch: 'main', env: 'prod'}) — auto-resolved to full YAML. CI codebase shorthands (branch, tag, pr_number, commit_sha) are auto-expanded. For template inputs, use input_set_ids instead." },
               { name: "input_set_ids", type: "array", required: false, description: "Input set identifiers to apply. Recommended for complex pipelines with template inputs. List available: harness_list(resource_type='input_set', filters={pipeline_id: '...'})." },
               { name: "pipeline_branch", type: "string", required: false, description: "Git branch to load the pipeline YAML from (sent as ?pipelineBranchName= on the API). Use when the pipeline definition lives on a feature branch rather than the default branch." },
+              { name: "stage_identifiers", type: "array", required: false, description: "Run only these stages (selective stage execution). The pipeline must allow selective stage execution. Runtime inputs are resolved against the template for these stages only." },
+              { name: "expression_values", type: "object", required: false, description: "With stage_identifiers: values for expressions the selected stages read from skipped stages, e.g. {'<+pipeline.stages.build.spec.execution.steps.tag.output.outputVariables.tag>': 'v1.2'}." },
             ],
           },
         },
@@ -364,6 +448,7 @@ export const pipelinesToolset: ToolsetDefinition = {
           method: "PUT",
           path: "/pipeline/api/pipeline/execute/retry/{planExecutionId}",
           operationPolicy: { risk: "high_write", retryPolicy: "do_not_retry" },
+          requiredPermission: { resourceType: "PIPELINE", permission: "core_pipeline_execute" },
           pathParams: { execution_id: "planExecutionId" },
           queryParams: { module: "module" },
           bodyBuilder: () => ({}),
@@ -578,12 +663,18 @@ export const pipelinesToolset: ToolsetDefinition = {
     {
       resourceType: "execution",
       displayName: "Pipeline Execution",
-      description: "Pipeline execution history and details. Supports list and get.",
+      description: "Pipeline execution history and details. Supports list and get. List with resource_scope=org or account to roll up across projects (e.g. all failed executions in an org).",
       toolset: "pipelines",
       scope: "project",
+      rollupScopes: ["org", "account"],
       identifierFields: ["execution_id"],
       diagnosticHint: "Use harness_diagnose with execution_id to analyze a failed execution — includes step-level error details, log snippets, delegate info, and chained pipeline traversal.",
       relatedResources: [
+        {
+          resourceType: "execution_notes",
+          relationship: "annotated-by",
+          description: "Free-form notes on this execution, shown in the Harness UI. Use harness_update(resource_type='execution_notes', resource_id=<planExecutionId>, body={notes}) to record findings such as a root cause.",
+        },
         {
           resourceType: "execution_inputs",
           relationship: "produced-from",
@@ -593,10 +684,14 @@ export const pipelinesToolset: ToolsetDefinition = {
       listFilterFields: [
         { name: "search_term", description: "Filter executions by name or keyword" },
         { name: "pipeline_id", description: "Pipeline identifier to filter executions" },
-        { name: "status", description: "Execution status filter", enum: ["Success", "Failed", "Running", "Aborted", "Expired", "AbortedByFreeze", "NotStarted", "Paused", "Queued", "Waiting"] },
+        { name: "status", description: "Execution status filter (pass an array for several)", enum: ["Success", "Failed", "Running", "Aborted", "Expired", "AbortedByFreeze", "NotStarted", "Paused", "Queued", "Waiting", "ResourceWaiting"] },
         { name: "branch", description: "Branch to filter executions" },
         { name: "my_deployments", description: "Show only my deployments", type: "boolean" },
         { name: "module", description: "Harness module filter", enum: ["CD", "CI", "CV", "CF", "CE", "STO"] },
+        { name: "trigger_type", description: "How the execution was triggered (pass an array for several)", enum: ["MANUAL", "WEBHOOK", "WEBHOOK_CUSTOM", "SCHEDULER_CRON", "ARTIFACT", "MANIFEST"] },
+        { name: "start_time", description: `Only executions started at or after this time (e.g. 2025-07-10T08:00:00Z or "last 7d"). ${TIME_INPUT_FORMATS}` },
+        { name: "end_time", description: "Only executions started at or before this time, same formats as start_time. Default: now when start_time is set." },
+        { name: "sort", description: "Sort order (default: newest first)", enum: ["startTs,DESC", "startTs,ASC", "endTs,DESC", "endTs,ASC"] },
       ],
       deepLinkTemplate: "/ng/account/{accountId}/all/orgs/{orgIdentifier}/projects/{projectIdentifier}/pipelines/{pipelineIdentifier}/deployments/{planExecutionId}/pipeline",
       operations: {
@@ -611,12 +706,11 @@ export const pipelinesToolset: ToolsetDefinition = {
             branch: "branch",
             my_deployments: "myDeployments",
             module: "module",
+            sort: "sort",
             page: "page",
             size: "size",
           },
-          bodyBuilder: () => ({
-            filterType: "PipelineExecution",
-          }),
+          bodyBuilder: buildExecutionFilterBody,
           responseExtractor: pageExtract,
           description: "List pipeline execution history",
         },
@@ -694,6 +788,132 @@ export const pipelinesToolset: ToolsetDefinition = {
         },
       },
     },
+    {
+      resourceType: "execution_notes",
+      displayName: "Pipeline Execution Notes",
+      description:
+        "Free-form notes on a pipeline execution, shown on the execution in the Harness UI. Use to leave findings on a run for teammates (e.g. 'root cause: expired connector secret'). Supports get and update — update replaces the existing notes, so get first to append.",
+      toolset: "pipelines",
+      scope: "project",
+      identifierFields: ["execution_id"],
+      relatedResources: [
+        {
+          resourceType: "execution",
+          relationship: "annotates",
+          description: "The pipeline execution these notes belong to.",
+        },
+      ],
+      deepLinkTemplate: "/ng/account/{accountId}/all/orgs/{orgIdentifier}/projects/{projectIdentifier}/deployments/{planExecutionId}/pipeline",
+      operations: {
+        get: {
+          method: "GET",
+          path: "/pipeline/api/pipelines/execution/{planExecutionId}/notes",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { execution_id: "planExecutionId" },
+          responseExtractor: ngExtract,
+          description: "Get the notes on a pipeline execution.",
+        },
+        update: {
+          method: "PUT",
+          path: "/pipeline/api/pipelines/execution/{planExecutionId}/notes",
+          operationPolicy: { risk: "low_write", retryPolicy: "safe" },
+          pathParams: { execution_id: "planExecutionId" },
+          queryParams: { notes: "notes" },
+          bodyBuilder: hoistExecutionNotes,
+          bodySchema: {
+            description: "Execution notes (sent as the notes query parameter)",
+            fields: [
+              { name: "notes", type: "string", required: true, description: "Full notes text. Replaces any existing notes on the execution." },
+            ],
+          },
+          responseExtractor: ngExtract,
+          description: "Set the notes on a pipeline execution (replaces existing notes).",
+        },
+      },
+    },
+    {
+      resourceType: "verification",
+      displayName: "Verification Result",
+      description:
+        "Continuous Verification (CV) analysis of a Verify step: analysis type and sensitivity, test/control nodes, and healthy/warning/unhealthy metric and log cluster counts. Supports get only. " +
+        "The verify_step_execution_id is the Verify step's activityId — harness_diagnose(resource_type='verification', options={execution_id}) resolves it from an execution and summarizes why verification failed.",
+      toolset: "pipelines",
+      scope: "project",
+      identifierFields: ["verify_step_execution_id"],
+      relatedResources: [
+        { resourceType: "verification_metric", relationship: "child", description: "Per-metric analysis results" },
+        { resourceType: "verification_log", relationship: "child", description: "Log cluster analysis results" },
+        { resourceType: "execution", relationship: "parent", description: "The execution the Verify step ran in" },
+      ],
+      operations: {
+        get: {
+          method: "GET",
+          path: "/cv/api/verify-step/{verifyStepExecutionId}/overview",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { verify_step_execution_id: "verifyStepExecutionId" },
+          responseExtractor: cvExtract,
+          description: "Get the analysis overview of a Verify step",
+        },
+      },
+    },
+    {
+      resourceType: "verification_metric",
+      displayName: "Verification Metric Analysis",
+      description: "Per-metric CV analysis of a Verify step — health source, transaction, result (HEALTHY, WARNING, UNHEALTHY, NO_ANALYSIS), and per-node results against the control data. List-only; requires verify_step_execution_id.",
+      toolset: "pipelines",
+      scope: "project",
+      identifierFields: [],
+      listFilterFields: [
+        { name: "verify_step_execution_id", description: "Verify step activityId", required: true },
+        { name: "anomalous_only", description: "Only metrics that are not healthy", type: "boolean" },
+        { name: "health_source", description: "Only metrics from this health source identifier" },
+      ],
+      operations: {
+        list: {
+          method: "GET",
+          path: "/cv/api/verify-step/{verifyStepExecutionId}/analysis-result/metrics",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { verify_step_execution_id: "verifyStepExecutionId" },
+          queryParams: {
+            anomalous_only: "anomalousMetricsOnly",
+            health_source: "healthSources",
+            page: "pageIndex",
+            size: "pageSize",
+          },
+          responseExtractor: cvPageExtract,
+          description: "List metric analysis results of a Verify step",
+        },
+      },
+    },
+    {
+      resourceType: "verification_log",
+      displayName: "Verification Log Analysis",
+      description: "Log clusters from the CV analysis of a Verify step — message, cluster type (KNOWN_EVENT, UNKNOWN_EVENT, UNEXPECTED_FREQUENCY), risk, and count. List-only; requires verify_step_execution_id.",
+      toolset: "pipelines",
+      scope: "project",
+      identifierFields: [],
+      listFilterFields: [
+        { name: "verify_step_execution_id", description: "Verify step activityId", required: true },
+        { name: "cluster_type", description: "Cluster type filter (pass an array for several)", enum: ["KNOWN_EVENT", "UNKNOWN_EVENT", "UNEXPECTED_FREQUENCY"] },
+        { name: "health_source", description: "Only clusters from this health source identifier" },
+      ],
+      operations: {
+        list: {
+          method: "GET",
+          path: "/cv/api/verify-step/{verifyStepExecutionId}/analysis-result/logs",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { verify_step_execution_id: "verifyStepExecutionId" },
+          queryParams: {
+            cluster_type: "clusterTypes",
+            health_source: "healthSources",
+            page: "pageIndex",
+            size: "pageSize",
+          },
+          responseExtractor: cvPageExtract,
+          description: "List log cluster analysis results of a Verify step",
+        },
+      },
+    },
     {
       resourceType: "trigger",
       displayName: "Pipeline Trigger",
@@ -832,10 +1052,7 @@ export const pipelinesToolset: ToolsetDefinition = {
             pipeline_id: "pipelineIdentifier",
             // Git context for remote/Git-stored input sets — without a branch
             // the API silently resolves from the repo's default branch.
-            branch: "branch",
-            repo_name: "repoName",
-            connector_ref: "connectorRef",
-            store_type: "storeType",
+            ...GIT_READ_QUERY_PARAMS,
           },
           responseExtractor: ngExtract,
           description: "Get input set details. For remote/git-backed input sets, pass branch (and repo_name for multi-repo) to read from a specific branch; otherwise the default branch is used.",
@@ -936,9 +1153,37 @@ export const pipelinesToolset: ToolsetDefinition = {
             pipeline_id: "pipelineIdentifier",
             branch: "branch",
           },
-          bodyBuilder: () => ({}),
+          bodyBuilder: (input) => {
+            const stageIdentifiers = parseStageIdentifiers(input.stage_identifiers);
+            return stageIdentifiers.length > 0 ? { stageIdentifiers } : {};
+          },
           responseExtractor: runtimeInputExtract,
-          description: "Fetch the runtime input template for a pipeline. Shows all fields that require values at execution time.",
+          description: "Fetch the runtime input template for a pipeline. Shows all fields that require values at execution time. Pass stage_identifiers for the template of a selective stage run.",
+        },
+      },
+    },
+    {
+      resourceType: "pipeline_input_schema",
+      displayName: "Pipeline Input Schema",
+      description: "Runtime inputs of a pipeline as a JSON Schema for the flat `inputs` object of harness_execute(resource_type='pipeline', action='run') — one property per input key with type, default, allowed values, and regex — plus the raw runtime input template YAML.",
+      toolset: "pipelines",
+      scope: "project",
+      identifierFields: ["pipeline_id"],
+      operations: {
+        get: {
+          method: "POST",
+          path: "/pipeline/api/inputSets/template",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          queryParams: {
+            pipeline_id: "pipelineIdentifier",
+            branch: "branch",
+          },
+          bodyBuilder: (input) => {
+            const stageIdentifiers = parseStageIdentifiers(input.stage_identifiers);
+            return stageIdentifiers.length > 0 ? { stageIdentifiers } : {};
+          },
+          responseExtractor: pipelineInputSchemaExtract,
+          description: "Get the runtime input schema for a pipeline. Returns fields (key, path, type, required, default, allowed_values, regex), jsonSchema for the run inputs, and inputSetTemplateYaml. Pass stage_identifiers for the inputs of a selective stage run.",
         },
       },
     },
@@ -946,7 +1191,7 @@ export const pipelinesToolset: ToolsetDefinition = {
       resourceType: "approval_instance",
       displayName: "Approval Instance",
       description:
-        "Pipeline approval instances. List approvals for an execution (filter by status/type), or approve/reject a waiting approval. Use with harness_list to find pending approvals, then harness_execute to approve or reject.",
+        "Pipeline approval instances. List approvals for an execution (filter by status/type), or approve/reject a waiting approval. Use with harness_list to find pending approvals, then harness_execute to approve or reject. For the current status of the Jira issue a JiraApproval waits on, harness_get(resource_type='jira_issue').",
       toolset: "pipelines",
       scope: "project",
       identifierFields: ["execution_id"],
@@ -1020,5 +1265,51 @@ export const pipelinesToolset: ToolsetDefinition = {
         },
       },
     },
+    {
+      resourceType: "constraint_queue",
+      displayName: "Resource Constraint Queue",
+      description: "Executions holding or queued on a resource constraint key (a Queue step key, or the infrastructure key behind deployment concurrency). Returns capacity, holders (ACTIVE), and the queue (BLOCKED) in order, across the account. Get the key from a waiting step via harness_diagnose(resource_type='execution_waits'), or find contended keys with harness_diagnose(resource_type='resource_constraints').",
+      toolset: "pipelines",
+      scope: "account",
+      scopeParams: { account: "accountId" },
+      identifierFields: ["resource_unit"],
+      operations: {
+        get: {
+          method: "GET",
+          path: "/pipeline/api/resourceConstraints/executionInfo",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          queryParams: { resource_unit: "resourceUnit" },
+          responseExtractor: constraintQueueExtract,
+          description: "Get the holders and queue of a resource constraint. resource_id is the resource unit (constraint key).",
+        },
+      },
+    },
+    {
+      resourceType: "yaml_bundle",
+      displayName: "YAML Bundle",
+      description:
+        "Apply a bundle of Harness YAML documents (pipelines, templates, input sets, triggers, services, environments, infrastructure definitions, connectors) in one call. " +
+        "The apply execute action creates or updates each entity in dependency order and returns a per-document report. " +
+        "Use harness_diagnose(resource_type='yaml_export') to produce a bundle from an existing project.",
+      toolset: "pipelines",
+      scope: "project",
+      identifierFields: [],
+      operations: {},
+      executeActions: {
+        apply: {
+          method: "POST",
+          path: "/pipeline/api/pipelines/v2",
+          operationPolicy: { risk: "medium_write", retryPolicy: "do_not_retry" },
+          // Composite: harness_execute applies each document itself
+          // (see utils/yaml-apply.ts), so this spec is never dispatched.
+          bodyBuilder: () => {
+            throw new Error("yaml_bundle.apply runs through harness_execute, not direct dispatch.");
+          },
+          responseExtractor: ngExtract,
+          actionDescription: "Create or update every entity in a bundle of Harness YAML documents, in dependency order. Returns created/updated/skipped/failed per document; dry_run previews without writing.",
+          bodySchema: yamlBundleApplySchema,
+        },
+      },
+    },
   ],
 };
@@ -1,5 +1,5 @@
 import type { ParamsSchema, ToolsetDefinition } from "../types.js";
-import { passthrough } from "../extractors.js";
+import { passthrough, prFileDiffExtract } from "../extractors.js";
 
 const REPO_PARAMS: ParamsSchema = {
   fields: [
@@ -127,7 +127,7 @@ export const pullRequestsToolset: ToolsetDefinition = {
       resourceType: "pull_request",
       displayName: "Pull Request",
       description:
-        "Code pull request. Supports list, get, create, and update. Use execute actions for close and merge.",
+        "Code pull request. Supports list, get, create, and update. Use execute actions for close and merge, and merge_check to see what blocks a merge.",
       toolset: "pull-requests",
       scope: "account",
       scopeOptional: true,
@@ -231,6 +231,32 @@ export const pullRequestsToolset: ToolsetDefinition = {
             fields: [],
           },
         },
+        merge_check: {
+          method: "POST",
+          path: "/code/api/v1/repos/{repoIdentifier}/pullreq/{prNumber}/merge",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: {
+            repo_id: "repoIdentifier",
+            pr_number: "prNumber",
+          },
+          skipScopeBodyInjection: true,
+          bodyBuilder: (input) => ({
+            ...(input.method ? { method: input.method } : {}),
+            dry_run: true,
+            dry_run_rules: true,
+          }),
+          responseExtractor: passthrough,
+          paramsSchema: REPO_PR_PARAMS,
+          actionDescription:
+            "Dry-run a merge without changing anything. Returns mergeable, conflict_files, rule_violations (each rule with its violation messages), " +
+            "minimum_required_approvals_count, requires_code_owners_approval, requires_comment_resolution, and allowed_methods. Optional method (merge/squash/rebase/fast-forward).",
+          bodySchema: {
+            description: "No body required — the dry-run flags are always set.",
+            fields: [
+              { name: "method", type: "string", required: false, description: "Merge method to evaluate: merge, squash, rebase, or fast-forward" },
+            ],
+          },
+        },
         merge: {
           method: "POST",
           path: "/code/api/v1/repos/{repoIdentifier}/pullreq/{prNumber}/merge",
@@ -441,6 +467,39 @@ export const pullRequestsToolset: ToolsetDefinition = {
         },
       },
     },
+    {
+      resourceType: "pr_file",
+      displayName: "PR Changed File",
+      description:
+        "Files changed by a pull request with per-file status and addition/deletion counts. Supports list. Set include_patch=true to include each file's unified diff.",
+      toolset: "pull-requests",
+      scope: "account",
+      scopeOptional: true,
+      identifierFields: ["repo_id", "pr_number"],
+      listFilterFields: [
+        { name: "include_patch", description: "Include each file's unified diff text (can be large)", type: "boolean" },
+        { name: "path", description: "Only return these file paths (repeatable)" },
+      ],
+      operations: {
+        list: {
+          method: "GET",
+          path: "/code/api/v1/repos/{repoIdentifier}/pullreq/{prNumber}/diff",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: {
+            repo_id: "repoIdentifier",
+            pr_number: "prNumber",
+          },
+          queryParams: {
+            include_patch: "include_patch",
+            path: "path",
+          },
+          responseExtractor: prFileDiffExtract,
+          description:
+            "List files changed by a pull request. Pass include_patch=true for diffs — for large PRs prefer harness_diagnose(resource_type='pr_review'), which chunks diffs under a size budget.",
+          paramsSchema: REPO_PR_PARAMS,
+        },
+      },
+    },
     {
       resourceType: "pr_activity",
       displayName: "PR Activity",
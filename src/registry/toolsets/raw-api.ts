@@ -0,0 +1,116 @@
+import type { ToolsetDefinition } from "../types.js";
+
+// ---------------------------------------------------------------------------
+// Raw API escape hatch — GET-only access to endpoints no toolset wraps yet.
+// Opt-in (HARNESS_TOOLSETS=+raw-api) so it never appears unless an operator
+// asks for it; the path is restricted to the configured base URL and the
+// response is capped so a large payload cannot flood the agent's context.
+// ---------------------------------------------------------------------------
+
+const DEFAULT_MAX_RESPONSE_CHARS = 50_000;
+const MAX_RESPONSE_CHARS = 500_000;
+
+/** Query keys the client and scope injection own — callers cannot override them. */
+const RESERVED_QUERY_KEYS = new Set(["accountIdentifier", "routingId", "accountID", "orgIdentifier", "projectIdentifier"]);
+
+/** A path segment that URL parsing resolves as `.` or `..`, literally or percent-encoded. */
+const DOT_SEGMENT = /^(?:\.|%2e){1,2}$/i;
+
+/**
+ * Validate a caller-supplied API path and append its query string. Only
+ * absolute paths under the base URL are accepted — no scheme, host, dot
+ * segments (plain or percent-encoded), encoded slashes, or protocol-relative
+ * `//` prefixes.
+ */
+function rawApiPath(input: Record<string, unknown>): string {
+  const raw = typeof input.path === "string" ? input.path.trim() : "";
+  if (!raw) {
+    throw new Error("api_request requires path (e.g. '/ng/api/licenses/account'). Pass it as resource_id or params.path.");
+  }
+  if (!raw.startsWith("/") || raw.startsWith("//") || /^[a-z][a-z0-9+.-]*:/i.test(raw) || raw.includes("://")) {
+    throw new Error(`api_request path must be an absolute path under the configured base URL, got '${raw}'.`);
+  }
+  const [pathname, pathQuery] = raw.split("?", 2) as [string, string | undefined];
+  // The WHATWG URL parser (used by fetch) treats `.`, `..`, and any mix with
+  // `%2e` as dot segments, and some gateways decode `%2f`/`%5c` to slashes
+  if (/[\s\\]|%2f|%5c/i.test(pathname) || pathname.split("/").some((seg) => DOT_SEGMENT.test(seg))) {
+    throw new Error(`api_request path contains disallowed characters or dot segments: '${raw}'.`);
+  }
+  // Belt and braces: the path must survive URL normalization unchanged, so
+  // it cannot resolve outside the base URL's path
+  if (!new URL(pathname, "http://base.invalid/").pathname.startsWith(pathname.replace(/\/+$/, ""))) {
+    throw new Error(`api_request path does not stay under the configured base URL: '${raw}'.`);
+  }
+  const query = new URLSearchParams(pathQuery ?? "");
+  const extra = input.query;
+  if (extra !== undefined && (typeof extra !== "object" || extra === null || Array.isArray(extra))) {
+    throw new Error("api_request query must be an object of query parameter names to values.");
+  }
+  for (const [key, value] of Object.entries((extra ?? {}) as Record<string, unknown>)) {
+    if (value === undefined || value === null || value === "") continue;
+    for (const item of Array.isArray(value) ? value : [value]) query.append(key, String(item));
+  }
+  const reserved = [...new Set([...query.keys()].filter((k) => RESERVED_QUERY_KEYS.has(k)))];
+  if (reserved.length > 0) {
+    throw new Error(
+      `api_request does not accept ${reserved.join(", ")} in the query — account scope comes from config; ` +
+      "pass org_id/project_id or resource_scope to change org/project scope.",
+    );
+  }
+  const qs = query.toString();
+  return qs ? `${pathname}?${qs}` : pathname;
+}
+
+/** Pass small responses through untouched; replace oversized ones with a truncated preview. */
+function rawApiExtract(raw: unknown, input?: Record<string, unknown>): unknown {
+  const requested = typeof input?.max_response_chars === "number" ? input.max_response_chars : DEFAULT_MAX_RESPONSE_CHARS;
+  const limit = Math.min(Math.max(requested, 1_000), MAX_RESPONSE_CHARS);
+  const text = typeof raw === "string" ? raw : JSON.stringify(raw) ?? "";
+  if (text.length <= limit) return raw;
+  return {
+    truncated: true,
+    response_chars: text.length,
+    max_response_chars: limit,
+    preview: text.slice(0, limit),
+    _hint:
+      `Response was ${text.length} characters and was cut to ${limit}. Narrow the request with query filters or paging ` +
+      `(e.g. query: { pageSize: 10 }), or raise params.max_response_chars (up to ${MAX_RESPONSE_CHARS}).`,
+  };
+}
+
+export const rawApiToolset: ToolsetDefinition = {
+  name: "raw-api",
+  displayName: "Raw API",
+  description:
+    "Escape hatch for Harness API endpoints not yet covered by a dedicated resource type — GET only, " +
+    "restricted to the configured base URL, with scope injection and a response size cap.",
+  optIn: true,
+  resources: [
+    {
+      resourceType: "api_request",
+      displayName: "Raw API Request",
+      description:
+        "Read-only GET against any Harness API path under the configured base URL. Prefer a dedicated resource type when one exists — " +
+        "use harness_describe or harness_search first. accountIdentifier is always added; orgIdentifier/projectIdentifier come from " +
+        "org_id/project_id or config defaults (pass resource_scope='account' or 'org' to omit them). " +
+        "Pass path as resource_id (e.g. '/ng/api/licenses/account') and extra query parameters in params.query.",
+      toolset: "raw-api",
+      scope: "project",
+      supportedScopes: ["account", "org", "project"],
+      identifierFields: ["path"],
+      operations: {
+        get: {
+          method: "GET",
+          path: "/",
+          pathBuilder: rawApiPath,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseExtractor: rawApiExtract,
+          skipCompact: true,
+          description:
+            `GET an arbitrary API path. params: path (or resource_id), query (object), max_response_chars (default ${DEFAULT_MAX_RESPONSE_CHARS}). ` +
+            "Responses larger than the cap are returned as a truncated preview.",
+        },
+      },
+    },
+  ],
+};
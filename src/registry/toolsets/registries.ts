@@ -1,5 +1,5 @@
-import type { ToolsetDefinition, PathBuilderConfig } from "../types.js";
-import { passthrough, harListExtract } from "../extractors.js";
+import type { BodySchema, PreflightContext, ToolsetDefinition, PathBuilderConfig } from "../types.js";
+import { passthrough, harListExtract, harCleanupPolicyExtract, ngExtract } from "../extractors.js";
 
 /**
  * HAR API uses path-based scope refs (not query params).
@@ -21,10 +21,91 @@ function harRegistryRef(input: Record<string, unknown>, config: PathBuilderConfi
   return `${harSpaceRef(input, config)}/${registry}`;
 }
 
+const HAR_SPACE_REF = "__har_space_ref";
+const HAR_EXISTING_REGISTRY = "__har_existing_registry";
+
+/** Registry fields that round-trip through PUT; server-computed fields (url, sizes, timestamps) are dropped. */
+const HAR_WRITABLE_FIELDS = ["identifier", "packageType", "description", "labels", "allowedPattern", "blockedPattern", "config", "cleanupPolicy"];
+
+function bodyRecord(input: Record<string, unknown>): Record<string, unknown> {
+  return input.body && typeof input.body === "object" && !Array.isArray(input.body)
+    ? input.body as Record<string, unknown>
+    : {};
+}
+
+/**
+ * Build an UPSTREAM registry request. Flat params (source, url, auth_type,
+ * username, secret_identifier) map onto the nested `config`; a full `config`
+ * object in the body is passed through unchanged.
+ */
+function upstreamProxyBody(input: Record<string, unknown>): Record<string, unknown> {
+  const body = bodyRecord(input);
+  const pick = (key: string): unknown => body[key] ?? input[key];
+  const authType = pick("auth_type") ?? (pick("username") ? "UserPassword" : "Anonymous");
+  const config = (pick("config") as Record<string, unknown> | undefined) ?? {
+    type: "UPSTREAM",
+    source: pick("source") ?? "Custom",
+    url: pick("url"),
+    authType,
+    ...(authType === "UserPassword"
+      ? {
+        auth: {
+          authType,
+          userName: pick("username"),
+          secretIdentifier: pick("secret_identifier"),
+          secretSpacePath: pick("secret_space_path") ?? input[HAR_SPACE_REF],
+        },
+      }
+      : {}),
+  };
+  return {
+    identifier: pick("identifier") ?? input.registry_id,
+    packageType: pick("packageType") ?? pick("package_type"),
+    description: pick("description"),
+    labels: pick("labels"),
+    cleanupPolicy: pick("cleanupPolicy") ?? pick("cleanup_policies"),
+    config: { ...config, type: "UPSTREAM" },
+    ...(input[HAR_SPACE_REF] ? { parentRef: input[HAR_SPACE_REF] } : {}),
+  };
+}
+
+const upstreamProxyBodySchema: BodySchema = {
+  description: "Upstream proxy registry. Pass flat fields (source, url, auth_type, username, secret_identifier) or a full config object.",
+  fields: [
+    { name: "identifier", type: "string", required: true, description: "Registry identifier" },
+    { name: "packageType", type: "string", required: true, description: "Package type: DOCKER, HELM, MAVEN, NPM, PYTHON, NUGET, GENERIC, ... (package_type also accepted)" },
+    { name: "config", type: "object", required: true, description: "Upstream config — built from source (Dockerhub, AwsEcr, MavenCentral, NpmJs, PyPi, Custom, ...), url, auth_type (Anonymous or UserPassword), username, and secret_identifier" },
+    { name: "description", type: "string", required: false, description: "Registry description" },
+    { name: "cleanupPolicy", type: "array", required: false, description: "Retention rules: [{ name, expireDays, packagePrefix?: [], versionPrefix?: [] }]" },
+  ],
+};
+
+/** Load the current registry so a cleanup-policy update can send the full registry back. */
+async function loadExistingRegistry(ctx: PreflightContext): Promise<void> {
+  const raw = await ctx.registry.dispatch(ctx.client, "registry", "get", { ...ctx.input }, ctx.signal);
+  ctx.input[HAR_EXISTING_REGISTRY] = ngExtract(raw);
+}
+
+function cleanupPolicyBody(input: Record<string, unknown>): Record<string, unknown> {
+  const existing = (input[HAR_EXISTING_REGISTRY] ?? {}) as Record<string, unknown>;
+  const body = bodyRecord(input);
+  const policies = body.cleanupPolicy ?? body.policies ?? input.policies;
+  if (!Array.isArray(policies)) {
+    throw new Error("registry_cleanup_policy.update requires policies — the complete list of cleanup policies (pass [] to remove all).");
+  }
+  const next: Record<string, unknown> = {};
+  for (const field of HAR_WRITABLE_FIELDS) {
+    if (existing[field] !== undefined) next[field] = existing[field];
+  }
+  next.cleanupPolicy = policies;
+  return next;
+}
+
 export const registriesToolset: ToolsetDefinition = {
   name: "registries",
   displayName: "Artifact Registries",
-  description: "Harness Artifact Registry — registries, artifacts, and versions",
+  description: "Harness Artifact Registry — registries, upstream proxies, cleanup policies, artifacts, and versions",
+  probeResource: "registry",
   resources: [
     {
       resourceType: "registry",
@@ -75,6 +156,122 @@ export const registriesToolset: ToolsetDefinition = {
         },
       },
     },
+    {
+      resourceType: "upstream_proxy",
+      displayName: "Upstream Proxy",
+      description:
+        "Upstream proxy registry that caches packages from a remote source (Docker Hub, ECR, Maven Central, npm, PyPI, or a custom URL). Supports list, get, create, and update.",
+      toolset: "registries",
+      scope: "project",
+      identifierFields: ["registry_id"],
+      listFilterFields: [
+        { name: "search", description: "Filter upstream proxies by name or keyword" },
+        { name: "package_type", description: "Filter by package type (e.g. DOCKER, MAVEN, NPM)" },
+      ],
+      deepLinkTemplate: "/ng/account/{accountId}/all/orgs/{orgIdentifier}/projects/{projectIdentifier}/registries/{registryIdentifier}",
+      operations: {
+        list: {
+          method: "GET",
+          path: "/har/api/v1/spaces",
+          pathBuilder: (input, config) =>
+            `/har/api/v1/spaces/${harSpaceRef(input, config)}/+/registries`,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          staticQueryParams: { type: "UPSTREAM" },
+          queryParams: {
+            search: "search_term",
+            package_type: "package_type",
+            page: "page",
+            size: "size",
+          },
+          responseExtractor: harListExtract("registries"),
+          description: "List upstream proxy registries",
+        },
+        get: {
+          method: "GET",
+          path: "/har/api/v1/registry",
+          pathBuilder: (input, config) =>
+            `/har/api/v1/registry/${harRegistryRef(input, config)}/+`,
+          pathParams: { registry_id: "registryIdentifier" },
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseExtractor: ngExtract,
+          description: "Get an upstream proxy's source, URL, auth type, and cleanup policies",
+        },
+        create: {
+          method: "POST",
+          path: "/har/api/v1/registry",
+          pathBuilder: (input, config) => {
+            input[HAR_SPACE_REF] = harSpaceRef(input, config);
+            return "/har/api/v1/registry";
+          },
+          operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          queryParams: { [HAR_SPACE_REF]: "space_ref" },
+          skipScopeBodyInjection: true,
+          bodyBuilder: upstreamProxyBody,
+          responseExtractor: ngExtract,
+          description:
+            "Create an upstream proxy. Params: identifier, package_type, source (e.g. Dockerhub, AwsEcr, MavenCentral, NpmJs, PyPi, Custom), url (for Custom), " +
+            "auth_type (Anonymous or UserPassword), username and secret_identifier (a Harness secret holding the password), optional cleanup_policies.",
+          bodySchema: upstreamProxyBodySchema,
+        },
+        update: {
+          method: "PUT",
+          path: "/har/api/v1/registry",
+          pathBuilder: (input, config) => {
+            input[HAR_SPACE_REF] = harSpaceRef(input, config);
+            return `/har/api/v1/registry/${harRegistryRef(input, config)}/+`;
+          },
+          pathParams: { registry_id: "registryIdentifier" },
+          operationPolicy: { risk: "medium_write", retryPolicy: "safe" },
+          skipScopeBodyInjection: true,
+          bodyBuilder: upstreamProxyBody,
+          responseExtractor: ngExtract,
+          description: "Update an upstream proxy (full replacement). Pass the same fields as create — harness_get returns the current values.",
+          bodySchema: upstreamProxyBodySchema,
+        },
+      },
+    },
+    {
+      resourceType: "registry_cleanup_policy",
+      displayName: "Registry Cleanup Policy",
+      description:
+        "Retention rules of an artifact registry — each policy deletes versions older than expireDays, optionally limited to package or version prefixes. Supports list and update.",
+      toolset: "registries",
+      scope: "project",
+      identifierFields: ["registry_id"],
+      operations: {
+        list: {
+          method: "GET",
+          path: "/har/api/v1/registry",
+          pathBuilder: (input, config) =>
+            `/har/api/v1/registry/${harRegistryRef(input, config)}/+`,
+          pathParams: { registry_id: "registryIdentifier" },
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseExtractor: harCleanupPolicyExtract,
+          description: "List a registry's cleanup policies. Requires registry_id.",
+        },
+        update: {
+          method: "PUT",
+          path: "/har/api/v1/registry",
+          pathBuilder: (input, config) =>
+            `/har/api/v1/registry/${harRegistryRef(input, config)}/+`,
+          pathParams: { registry_id: "registryIdentifier" },
+          operationPolicy: { risk: "medium_write", retryPolicy: "safe" },
+          skipScopeBodyInjection: true,
+          preflight: loadExistingRegistry,
+          bodyBuilder: cleanupPolicyBody,
+          responseExtractor: harCleanupPolicyExtract,
+          description:
+            "Replace a registry's cleanup policies. Pass policies: the complete list [{ name, expireDays, packagePrefix?, versionPrefix? }] — " +
+            "list first and edit it, since omitted policies are removed. The rest of the registry configuration is preserved.",
+          bodySchema: {
+            description: "Complete cleanup policy list",
+            fields: [
+              { name: "cleanupPolicy", type: "array", required: true, description: "Cleanup policies (pass as policies): [{ name, expireDays, packagePrefix?: string[], versionPrefix?: string[] }]" },
+            ],
+          },
+        },
+      },
+    },
     {
       resourceType: "artifact",
       displayName: "Artifact",
@@ -1,16 +1,97 @@
-import type { ToolsetDefinition } from "../types.js";
-import { passthrough } from "../extractors.js";
+import type { PathBuilderConfig, ToolsetDefinition } from "../types.js";
+import { codeSearchExtract, passthrough } from "../extractors.js";
+
+const SEARCH_SPACE_PATH = "__search_space_path";
+const DEFAULT_SEARCH_RESULTS = 50;
+
+/**
+ * Code search addresses repos by path (`account/org/project[/repo]`), not by
+ * scope query params. Resolve the space path from the effective scope and
+ * stash it on input for the body builder.
+ */
+function codeSearchPath(input: Record<string, unknown>, config: PathBuilderConfig): string {
+  if (!input.query) {
+    throw new Error("Missing required field \"query\" for code_search.");
+  }
+  const org = (input.org_id as string | undefined) || config.HARNESS_ORG;
+  const project = (input.project_id as string | undefined) || (input.org_id ? undefined : config.HARNESS_PROJECT);
+  input[SEARCH_SPACE_PATH] = [config.HARNESS_ACCOUNT_ID, org, org ? project : undefined].filter(Boolean).join("/");
+
+  if (input.search_type === "semantic") {
+    if (!input.repo_id) {
+      throw new Error("Semantic code search runs against a single repository — pass repo_id, or use search_type='keyword' to search across repositories.");
+    }
+    return `/code/api/v1/repos/${encodeURIComponent(String(input.repo_id))}/semantic/search`;
+  }
+  return "/code/api/v1/search";
+}
+
+function codeSearchBody(input: Record<string, unknown>): Record<string, unknown> {
+  const maxResults = Number(input.max_results ?? DEFAULT_SEARCH_RESULTS);
+  if (input.search_type === "semantic") {
+    return { query: input.query, max_result_count: maxResults };
+  }
+  const spacePath = String(input[SEARCH_SPACE_PATH]);
+  return {
+    query: input.query,
+    max_result_count: maxResults,
+    enable_regex: input.regex === true || input.regex === "true",
+    ...(input.repo_id
+      ? { repo_paths: [`${spacePath}/${String(input.repo_id)}`] }
+      : { space_paths: [spacePath], recursive: true }),
+  };
+}
+
+const IMPORT_PROVIDERS = ["github", "gitlab", "bitbucket", "stash", "gitea", "gogs", "azure"];
+
+/**
+ * Build the Code import body from flat params or `body`. Provider credentials
+ * are nested under `provider` on the wire; accept them flat for convenience.
+ */
+function repoImportBody(input: Record<string, unknown>): Record<string, unknown> {
+  const body = input.body && typeof input.body === "object" && !Array.isArray(input.body)
+    ? input.body as Record<string, unknown>
+    : {};
+  const pick = (key: string): unknown => body[key] ?? input[key];
+  const providerInput = pick("provider");
+  const provider = providerInput && typeof providerInput === "object"
+    ? providerInput as Record<string, unknown>
+    : {
+      type: providerInput,
+      host: pick("host"),
+      username: pick("username"),
+      password: pick("token") ?? pick("password"),
+    };
+  const providerType = String(provider.type ?? "").toLowerCase();
+  if (!IMPORT_PROVIDERS.includes(providerType)) {
+    throw new Error(`repository.import requires provider — one of ${IMPORT_PROVIDERS.join(", ")}.`);
+  }
+  const identifier = pick("identifier") ?? input.repo_id;
+  if (!identifier || !pick("provider_repo")) {
+    throw new Error("repository.import requires identifier (the new Harness Code repo) and provider_repo (e.g. 'acme/payments').");
+  }
+  return {
+    identifier,
+    description: pick("description"),
+    provider: { ...provider, type: providerType },
+    provider_repo: pick("provider_repo"),
+    pipelines: pick("pipelines") ?? "ignore",
+    is_public: pick("is_public"),
+  };
+}
 
 export const repositoriesToolset: ToolsetDefinition = {
   name: "repositories",
   displayName: "Code Repositories",
   description: "Harness Code repositories (source control)",
+  probeResource: "repository",
   resources: [
     {
       resourceType: "repository",
       displayName: "Repository",
       description:
-        "Harness Code repository. Supports list, get, create, and update. Works at account, org, or project scope — omit org_id/project_id for account-scoped repos.",
+        "Harness Code repository. Supports list, get, create, and update. Works at account, org, or project scope — omit org_id/project_id for account-scoped repos. " +
+        "Use execute action 'import' to migrate a repo from GitHub, GitLab, Bitbucket, or another provider, then 'import_status' to follow it.",
       toolset: "repositories",
       scope: "account",
       scopeOptional: true,
@@ -84,6 +165,40 @@ export const repositoriesToolset: ToolsetDefinition = {
           },
         },
       },
+      executeActions: {
+        import: {
+          method: "POST",
+          path: "/code/api/v1/repos/import",
+          operationPolicy: { risk: "medium_write", retryPolicy: "do_not_retry" },
+          bodyBuilder: repoImportBody,
+          responseExtractor: passthrough,
+          actionDescription:
+            "Import a repository from an external Git provider into Harness Code. The import runs asynchronously — poll with action 'import_status'. " +
+            "Params: identifier (new repo name), provider (github, gitlab, bitbucket, stash, gitea, gogs, azure), provider_repo (e.g. 'acme/payments'), " +
+            "host (self-hosted providers only), username and token (needed for private repos), description, is_public, pipelines ('ignore' or 'convert').",
+          bodySchema: {
+            description: "Repository import request. Flat provider fields (host, username, token) are nested under provider automatically.",
+            fields: [
+              { name: "identifier", type: "string", required: true, description: "Identifier of the new Harness Code repository" },
+              { name: "provider", type: "object", required: true, description: "Source provider: { type, host?, username?, password? } — or pass provider as a string plus flat host/username/token" },
+              { name: "provider_repo", type: "string", required: true, description: "Repository path on the provider (e.g. 'acme/payments' or 'group/subgroup/repo')" },
+              { name: "description", type: "string", required: false, description: "Repository description" },
+              { name: "is_public", type: "boolean", required: false, description: "Whether the imported repo is public" },
+              { name: "pipelines", type: "string", required: false, description: "'ignore' (default) or 'convert' to convert provider CI pipelines to Harness pipelines" },
+            ],
+          },
+        },
+        import_status: {
+          method: "GET",
+          path: "/code/api/v1/repos/{repoIdentifier}/import-progress",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathParams: { repo_id: "repoIdentifier" },
+          responseExtractor: passthrough,
+          actionDescription:
+            "Get the progress of a repository import: state (running, success, failed, canceled), progress, and failure reason.",
+          bodySchema: { description: "No body required. Pass repo_id of the repository being imported.", fields: [] },
+        },
+      },
     },
     {
       resourceType: "branch",
@@ -258,6 +373,37 @@ export const repositoriesToolset: ToolsetDefinition = {
         },
       },
     },
+    {
+      resourceType: "code_search",
+      displayName: "Code Search",
+      description:
+        "Search code across Harness Code repositories in scope. Supports list. Keyword search (default) matches text or regex across every repo in the project (or org/account when project_id/org_id are omitted); semantic search ranks code by meaning within one repo. Returns file paths, line snippets, and repo identifiers.",
+      toolset: "repositories",
+      scope: "account",
+      scopeOptional: true,
+      identifierFields: [],
+      listFilterFields: [
+        { name: "query", description: "Search text, regex (with regex=true), or a natural-language question for semantic search", required: true },
+        { name: "repo_id", description: "Limit the search to one repository (required for semantic search)" },
+        { name: "search_type", description: "keyword (default) or semantic", enum: ["keyword", "semantic"] },
+        { name: "regex", description: "Treat query as a regular expression (keyword search only)", type: "boolean" },
+        { name: "max_results", description: "Maximum results to return (default 50)", type: "number" },
+      ],
+      operations: {
+        list: {
+          method: "POST",
+          path: "/code/api/v1/search",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          pathBuilder: codeSearchPath,
+          skipScopeBodyInjection: true,
+          bodyBuilder: codeSearchBody,
+          responseExtractor: codeSearchExtract,
+          description:
+            "Search code. Required: query. Keyword search covers all repos in the current scope unless repo_id is set; search_type='semantic' requires repo_id. " +
+            "Each item has repo_id, repo_path, file_path, and matches [{ line, snippet }] — fetch full files with harness_get(resource_type='file_content').",
+        },
+      },
+    },
     {
       resourceType: "file_content",
       displayName: "File Content",
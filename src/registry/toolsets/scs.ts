@@ -329,6 +329,7 @@ export const scsToolset: ToolsetDefinition = {
     + "To modify SBOM/SCS pipeline steps (e.g., change SBOM tool from Syft to CycloneDX, update source image), use the pipeline resource from the pipelines toolset: "
     + "harness_get(resource_type='pipeline') → edit YAML → harness_update(resource_type='pipeline'). "
     + "SCS step types: SscaOrchestration, SscaEnforcement, SscaCompliance, SscaArtifactSigning, SscaArtifactVerification.",
+  probeResource: "scs_artifact_source",
   resources: [
     // ── Artifact Sources ───────────────────────────────────────────────
     {
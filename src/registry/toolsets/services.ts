@@ -1,6 +1,8 @@
 import type { ToolsetDefinition, BodySchema } from "../types.js";
 import { buildBodyNormalized } from "../../utils/body-normalizer.js";
-import { ngExtract, pageExtract } from "../extractors.js";
+import { GIT_READ_QUERY_PARAMS, GIT_WRITE_QUERY_PARAMS } from "../git-details.js";
+import { parseTimeInput, TIME_INPUT_FORMATS } from "../../utils/time-range.js";
+import { activeInstanceCountExtract, artifactPromotionExtract, ngExtract, pageExtract, serviceInstanceExtract } from "../extractors.js";
 
 const serviceCreateSchema: BodySchema = {
   description: "Service definition",
@@ -13,6 +15,27 @@ const serviceCreateSchema: BodySchema = {
   ],
 };
 
+const serviceScaffoldSchema: BodySchema = {
+  description: "Scaffold parameters (pass via params). Creates the service, an ENV_SERVICE_OVERRIDE binding it to an existing environment, and a starter deploy pipeline, stopping at the first failure.",
+  fields: [
+    { name: "service_name", type: "string", required: true, description: "Service display name" },
+    { name: "service_id", type: "string", required: false, description: "Service identifier (derived from service_name if omitted)" },
+    { name: "runtime", type: "string", required: false, description: "kubernetes (default) or helm" },
+    { name: "repo_connector_ref", type: "string", required: false, description: "Git connector for the service's repo (required unless git_provider is Harness)" },
+    { name: "repo_name", type: "string", required: false, description: "Repository name (for account-level connectors and Harness Code)" },
+    { name: "branch", type: "string", required: false, description: "Branch to fetch manifests from (default main)" },
+    { name: "manifest_path", type: "string", required: false, description: "Manifest folder (default k8s/) or Helm chart folder (default chart/)" },
+    { name: "git_provider", type: "string", required: false, description: "Github (default), GitLab, Bitbucket, AzureRepo, Git, or Harness" },
+    { name: "environment_id", type: "string", required: true, description: "Existing environment to bind the service to" },
+    { name: "infrastructure_id", type: "string", required: false, description: "Infrastructure definition to deploy to (left as a runtime input if omitted)" },
+    { name: "pipeline_id", type: "string", required: false, description: "Pipeline identifier (default <service_id>_deploy)" },
+    { name: "pipeline_name", type: "string", required: false, description: "Pipeline name (default 'Deploy <service_name>')" },
+    { name: "template_ref", type: "string", required: false, description: "Stage template to use instead of the built-in rolling deployment stage" },
+    { name: "template_version", type: "string", required: false, description: "Stage template version label (required with template_ref)" },
+    { name: "variables", type: "object", required: false, description: "Variables set on the environment binding, as a name → value map" },
+  ],
+};
+
 const serviceUpdateSchema: BodySchema = {
   description: "Service update definition",
   fields: [
@@ -62,13 +85,15 @@ export const servicesToolset: ToolsetDefinition = {
           path: "/ng/api/servicesV2/{serviceIdentifier}",
           operationPolicy: { risk: "read", retryPolicy: "safe" },
           pathParams: { service_id: "serviceIdentifier" },
+          queryParams: GIT_READ_QUERY_PARAMS,
           responseExtractor: ngExtract,
-          description: "Get service details",
+          description: "Get service details. For remote (Git-stored) services, pass branch or git_details.",
         },
         create: {
           method: "POST",
           path: "/ng/api/servicesV2",
           operationPolicy: { risk: "low_write", retryPolicy: "do_not_retry" },
+          queryParams: GIT_WRITE_QUERY_PARAMS,
           bodyBuilder: buildBodyNormalized({ unwrapKey: "service" }),
           responseExtractor: ngExtract,
           description: "Create a new service",
@@ -78,6 +103,7 @@ export const servicesToolset: ToolsetDefinition = {
           method: "PUT",
           path: "/ng/api/servicesV2",
           operationPolicy: { risk: "low_write", retryPolicy: "safe" },
+          queryParams: { ...GIT_WRITE_QUERY_PARAMS, last_object_id: "lastObjectId", last_commit_id: "lastCommitId" },
           bodyBuilder: buildBodyNormalized({
             unwrapKey: "service",
             injectIdentifier: { inputField: "service_id", bodyField: "identifier" },
@@ -95,6 +121,117 @@ export const servicesToolset: ToolsetDefinition = {
           description: "Delete a service",
         },
       },
+      executeActions: {
+        scaffold_pipeline: {
+          method: "POST",
+          path: "/ng/api/servicesV2",
+          operationPolicy: { risk: "medium_write", retryPolicy: "do_not_retry" },
+          // Composite: harness_execute runs the individual creates itself
+          // (see utils/service-scaffold.ts), so this spec is never dispatched.
+          bodyBuilder: () => {
+            throw new Error("service.scaffold_pipeline runs through harness_execute, not direct dispatch.");
+          },
+          responseExtractor: ngExtract,
+          actionDescription: "Onboard a new service in one call: create the service from a Git repo (kubernetes or helm runtime), bind it to an existing environment, and create a starter deploy pipeline. Returns links to each created entity.",
+          bodySchema: serviceScaffoldSchema,
+        },
+      },
+    },
+    {
+      resourceType: "service_instance",
+      displayName: "Service Instance",
+      description:
+        "Running instances of a deployed service as tracked by instance sync (the perpetual task that polls the " +
+        "cluster through the service's connector). For Kubernetes these are pods, with namespace, release, containers, " +
+        "and the artifact each pod runs. List-only; requires service_id and env_id. " +
+        "Use harness_diagnose(resource_type='k8s_workload') for a per-release health summary.",
+      toolset: "services",
+      scope: "project",
+      identifierFields: [],
+      listFilterFields: [
+        { name: "service_id", description: "Service identifier", required: true },
+        { name: "env_id", description: "Environment identifier", required: true },
+        { name: "infra_id", description: "Infrastructure definition identifier" },
+        { name: "cluster_id", description: "GitOps cluster identifier (GitOps deployments only)" },
+        { name: "pipeline_execution_id", description: "Only instances deployed by this execution" },
+        { name: "build_id", description: "Only instances running this artifact tag/build" },
+      ],
+      relatedResources: [
+        { resourceType: "service", relationship: "parent", description: "Service the instances belong to" },
+        { resourceType: "environment", relationship: "related", description: "Environment the instances run in" },
+      ],
+      operations: {
+        list: {
+          method: "GET",
+          path: "/ng/api/instancedetails",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          queryParams: {
+            service_id: "serviceId",
+            env_id: "envId",
+            infra_id: "infraIdentifier",
+            cluster_id: "clusterIdentifier",
+            pipeline_execution_id: "pipelineExecutionId",
+            build_id: "buildId",
+          },
+          responseExtractor: serviceInstanceExtract,
+          description: "List running instances (pods) of a service in an environment",
+        },
+      },
+    },
+    {
+      resourceType: "artifact_promotion",
+      displayName: "Artifact Promotion Status",
+      description:
+        "Which artifact version a service is currently running in each environment, from instance sync. List-only; requires service_id. " +
+        "Pass artifact (a build ID or tag, e.g. '412') to answer 'is build 412 in prod yet' — the result adds deployed_in and not_deployed_in environment lists. " +
+        "Environments where the service has no running instances are not listed.",
+      toolset: "services",
+      scope: "project",
+      identifierFields: [],
+      listFilterFields: [
+        { name: "service_id", description: "Service identifier", required: true },
+        { name: "artifact", description: "Build ID or tag to look for across environments" },
+      ],
+      relatedResources: [
+        { resourceType: "service", relationship: "parent", description: "Service whose artifacts are reported" },
+        { resourceType: "service_instance", relationship: "related", description: "The individual instances behind each environment's counts" },
+      ],
+      operations: {
+        list: {
+          method: "GET",
+          path: "/ng/api/dashboard/getEnvBuildInstanceCountByServiceId",
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          queryParams: { service_id: "serviceId" },
+          responseExtractor: artifactPromotionExtract,
+          description: "List the artifact versions running in each environment for a service",
+        },
+      },
+    },
+    {
+      resourceType: "active_instance_count",
+      displayName: "Active Service Instance Count",
+      description:
+        "Account-wide count of active CD service instances and active services, as used for CD licensing. Supports get only (no resource_id). " +
+        "Pass as_of for the count at an earlier time. For per-environment counts of one service use artifact_promotion; for the instances themselves use service_instance.",
+      toolset: "services",
+      scope: "account",
+      identifierFields: [],
+      operations: {
+        get: {
+          method: "GET",
+          path: "/ng/api/usage/CD",
+          // The usage API requires a timestamp to report the count as of
+          pathBuilder: (input) => `/ng/api/usage/CD?timestamp=${parseTimeInput(input.as_of, { edge: "end" }) ?? Date.now()}`,
+          operationPolicy: { risk: "read", retryPolicy: "safe" },
+          responseExtractor: activeInstanceCountExtract,
+          description: "Get the number of active service instances and services in the account",
+          paramsSchema: {
+            fields: [
+              { name: "as_of", required: false, description: `Report the count as of this time (default now). ${TIME_INPUT_FORMATS}` },
+            ],
+          },
+        },
+      },
     },
   ],
 };
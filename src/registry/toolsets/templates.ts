@@ -2,6 +2,7 @@ import YAML from "yaml";
 import type { BodySchema, PathBuilderConfig, ToolsetDefinition } from "../types.js";
 import { ngExtract, pageExtract, passthrough, v1ListExtract } from "../extractors.js";
 import { SCOPE_BEHAVIOR_DOC, templateV1BasePathFromScope } from "../scope-utils.js";
+import { GIT_READ_QUERY_PARAMS } from "../git-details.js";
 
 function getTemplateYamlFromInput(input: Record<string, unknown>): string {
   const b = (input.body as Record<string, unknown>) ?? {};
@@ -303,10 +304,7 @@ export const templatesToolset: ToolsetDefinition = {
           queryParams: {
             version_label: "versionLabel",
             account_id: "accountIdentifier",
-            branch: "branch",
-            store_type: "storeType",
-            connector_ref: "connectorRef",
-            repo_name: "repoName",
+            ...GIT_READ_QUERY_PARAMS,
           },
           responseExtractor: ngExtract,
           description:
@@ -149,11 +149,13 @@ export type ToolsetName =
   | "overrides"
   | "iacm"
   | "ansible"
+  | "firstgen"
   | "ai-evals"
   | "incidents"
   | "deploys"
   | "knowledge-graph"
-  | "semantic-layer";
+  | "semantic-layer"
+  | "raw-api";
 
 export type ProductName = "harness" | "fme";
 
@@ -349,6 +351,28 @@ export interface EndpointSpec {
    * strip intentional display fields (e.g. `severity`, `requested_by`).
    */
   skipCompact?: boolean;
+  /**
+   * Persist the raw GET response on disk for this many milliseconds when
+   * HARNESS_CACHE_DIR is set. Only for slow-changing, account-wide catalog
+   * data (e.g. connector catalogue) — never for user-editable entities, which
+   * would go stale. The cache key covers account, base URL, path, and query.
+   */
+  diskCacheTtlMs?: number;
+  /**
+   * Reuse identical responses in memory for this many milliseconds, within
+   * one registry (one session). For read-only queries against rate-limited
+   * APIs (e.g. CCM GraphQL) that an analysis tends to repeat; identical
+   * concurrent requests share one call. The key covers account, base URL,
+   * path, query, and body.
+   */
+  responseReuseTtlMs?: number;
+  /**
+   * Harness RBAC permission required for this operation. When
+   * HARNESS_PERMISSION_PRECHECK is on, the registry checks it against the
+   * access control API (cached per principal) before calling the endpoint
+   * and fails with the missing permission instead of an opaque 403.
+   */
+  requiredPermission?: { resourceType: string; permission: string };
 }
 
 /**
@@ -370,6 +394,13 @@ export interface ResourceDefinition {
    * If omitted, the resource supports only its default `scope`.
    */
   supportedScopes?: readonly ResourceScope[];
+  /**
+   * Scopes at which `list` can be rolled up across projects. The API itself is
+   * project-scoped, so a list with `resource_scope` set to one of these scopes
+   * lists the org's (or account's) projects and merges one list call per
+   * project. Only used when the scope is not already in `supportedScopes`.
+   */
+  rollupScopes?: readonly Exclude<ResourceScope, "project">[];
   /**
    * When true, org/project params are only added if explicitly provided in input.
    * Use for resources that support multiple scopes (e.g., Harness Code repos/PRs
@@ -421,6 +452,8 @@ export interface ResourceDefinition {
   }>;
   /** Execution guidance for LLMs. Describes how to discover and provide runtime inputs. */
   executeHint?: string;
+  /** Feature flag gating this resource type, like `ToolsetDefinition.featureFlag`. */
+  featureFlag?: string;
   /** CRUD endpoint mappings */
   operations: Partial<Record<OperationName, EndpointSpec>>;
   /** Execute action mappings (e.g. run pipeline, toggle FF) */
@@ -455,4 +488,16 @@ export interface ToolsetDefinition {
    * default resource list.
    */
   optIn?: boolean;
+  /**
+   * Resource whose list operation the startup capability probe
+   * (HARNESS_CAPABILITY_PROBE) calls to check that this module's API exists
+   * on the target Harness install. A 404 hides the whole toolset.
+   */
+  probeResource?: string;
+  /**
+   * Harness feature flag that gates this toolset (HARNESS_FF_SDK_KEY). The
+   * toolset is only registered for accounts the flag is on for, and is hidden
+   * when the integration is not configured.
+   */
+  featureFlag?: string;
 }
@@ -7,9 +7,10 @@ import { registerPipelineYamlResource } from "./pipeline-yaml.js";
 import { registerExecutionSummaryResource } from "./execution-summary.js";
 import { registerHarnessSchemaResource } from "./harness-schema.js";
 import type { SchemaEntry } from "../data/schemas/types.js";
+import type { ArgumentCompleter } from "../utils/completions.js";
 
-export function registerAllResources(server: McpServer, registry: Registry, client: HarnessClient, config: Config, additionalSchemas?: Record<string, SchemaEntry>): void {
-  registerPipelineYamlResource(server, registry, client, config);
+export function registerAllResources(server: McpServer, registry: Registry, client: HarnessClient, config: Config, additionalSchemas?: Record<string, SchemaEntry>, complete?: ArgumentCompleter): void {
+  registerPipelineYamlResource(server, registry, client, config, complete);
   registerExecutionSummaryResource(server, registry, client, config);
   registerHarnessSchemaResource(server, additionalSchemas);
 }
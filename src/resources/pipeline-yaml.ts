@@ -5,10 +5,11 @@ import type { HarnessClient } from "../client/harness-client.js";
 import type { Config } from "../config.js";
 import { createLogger } from "../utils/logger.js";
 import { hasRequiredDiscoveryScope } from "./scope-check.js";
+import type { ArgumentCompleter } from "../utils/completions.js";
 
 const log = createLogger("resource:pipeline-yaml");
 
-export function registerPipelineYamlResource(server: McpServer, registry: Registry, client: HarnessClient, config: Config): void {
+export function registerPipelineYamlResource(server: McpServer, registry: Registry, client: HarnessClient, config: Config, complete?: ArgumentCompleter): void {
   const pipelineResourceType = (config.HARNESS_PIPELINE_VERSION ?? "0") === "0" ? "pipeline" : "pipeline_v1";
 
   const template = new ResourceTemplate("pipeline:///{pipelineId}", {
@@ -53,6 +54,9 @@ export function registerPipelineYamlResource(server: McpServer, registry: Regist
         return { resources: [] };
       }
     },
+    ...(complete
+      ? { complete: { pipelineId: (value: string, context?: { arguments?: Record<string, string> }) => complete("pipelineId", value, context) } }
+      : {}),
   });
 
   server.registerResource(
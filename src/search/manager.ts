@@ -17,12 +17,19 @@ const log = createLogger("search-manager");
 
 const TIER1_TYPES = ["pipeline", "service", "environment", "connector"] as const;
 
+/** Floor for the background entity refresh interval — protects the Harness API from tight loops. */
+export const MIN_ENTITY_REFRESH_INTERVAL_MS = 60_000;
+
 export class SearchManager {
   private provider: SearchProvider;
   private readonly mcpMode: Config["HARNESS_MCP_MODE"];
   private readonly configuredProvider: SearchProviderName;
   private readiness: SearchReadiness;
   private loggedResourcesCorpusDisabled = false;
+  private refreshTimer: ReturnType<typeof setInterval> | undefined;
+  private refreshInFlight: Promise<void> | undefined;
+  /** TTL applied to entity documents; widened while background refresh runs so items outlive the interval. */
+  private entityTtlMs: number | undefined;
 
   constructor(config: Config) {
     this.mcpMode = config.HARNESS_MCP_MODE;
@@ -251,6 +258,7 @@ export class SearchManager {
               content: buildResourceIndexContent(resourceType, item),
               corpus: "entities",
               accountId,
+              ttlMs: this.entityTtlMs,
               metadata: buildEntityMetadata(resourceType, identifier, String(item["name"] ?? ""), entityScope),
             });
           }
@@ -262,6 +270,50 @@ export class SearchManager {
     }
   }
 
+  /**
+   * Periodically re-list tier-1 entities so the entities corpus stays fresh
+   * for harness_search. Each pass evicts expired documents first, then
+   * re-indexes; documents are written with a TTL of two intervals, so entities
+   * deleted in Harness drop out of the catalog after at most two missed passes.
+   * Single-user mode only — callers must not start this for multi-user servers.
+   */
+  startEntityRefresh(registry: Registry, client: HarnessClient, intervalMs: number): void {
+    if (intervalMs <= 0 || this.refreshTimer) return;
+    const effectiveMs = Math.max(intervalMs, MIN_ENTITY_REFRESH_INTERVAL_MS);
+    this.entityTtlMs = effectiveMs * 2;
+    this.refreshTimer = setInterval(() => {
+      void this.refreshEntities(registry, client);
+    }, effectiveMs);
+    // Never keep the process alive just for catalog refreshes
+    this.refreshTimer.unref?.();
+    log.info("Background entity refresh enabled", { intervalMs: effectiveMs });
+  }
+
+  stopEntityRefresh(): void {
+    if (this.refreshTimer) clearInterval(this.refreshTimer);
+    this.refreshTimer = undefined;
+  }
+
+  /**
+   * Run one refresh pass. Overlapping calls share the in-flight pass instead of
+   * issuing duplicate list requests.
+   */
+  refreshEntities(registry: Registry, client: HarnessClient): Promise<void> {
+    if (this.refreshInFlight) return this.refreshInFlight;
+    this.refreshInFlight = (async () => {
+      try {
+        if (!this.provider.isAvailable()) return;
+        this.provider.evictExpired();
+        await this.initializeIndex(registry, client);
+      } catch (err) {
+        log.warn("Background entity refresh failed", { error: String(err) });
+      } finally {
+        this.refreshInFlight = undefined;
+      }
+    })();
+    return this.refreshInFlight;
+  }
+
   private loadProvider(config: Config): SearchProvider {
     if (this.configuredProvider === "local") {
       return new LocalSearchProvider({
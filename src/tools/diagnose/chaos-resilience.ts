@@ -0,0 +1,128 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:chaos-resilience");
+
+const DEFAULT_MAX_EXPERIMENTS = 50;
+const DEFAULT_MIN_SCORE = 80;
+
+function round(n: number): number {
+  return Math.round(n * 10) / 10;
+}
+
+function toIso(ts: unknown): string | undefined {
+  const n = typeof ts === "string" ? Number(ts) : asNumber(ts);
+  return n && Number.isFinite(n) ? new Date(n).toISOString() : undefined;
+}
+
+function listItems(raw: unknown): Record<string, unknown>[] {
+  const items = asRecord(raw)?.items;
+  return (Array.isArray(items) ? items : []).filter(isRecord);
+}
+
+function summarizeExperiment(exp: Record<string, unknown>): Record<string, unknown> {
+  const runs = (Array.isArray(exp.recentExperimentRunDetails) ? exp.recentExperimentRunDetails : []).filter(isRecord);
+  const scores = runs.map((r) => asNumber(r.resiliencyScore)).filter((s): s is number => s !== undefined);
+  const last = runs[0];
+  return {
+    experiment_id: asString(exp.experimentID) ?? asString(exp.experimentId),
+    name: asString(exp.name),
+    infra_type: asString(exp.infraType),
+    recent_runs: runs.length,
+    last_run_phase: asString(last?.phase),
+    last_run_at: toIso(last?.updatedAt),
+    last_resiliency_score: asNumber(last?.resiliencyScore),
+    average_resiliency_score: scores.length > 0 ? round(scores.reduce((a, b) => a + b, 0) / scores.length) : undefined,
+  };
+}
+
+function summarizeProbe(probe: Record<string, unknown>): Record<string, unknown> {
+  const runs = (Array.isArray(probe.recentProbeRuns) ? probe.recentProbeRuns : []).filter(isRecord);
+  const last = runs[0];
+  const status = asRecord(last?.status);
+  const executedBy = asRecord(last?.executedByExperiment);
+  return {
+    probe_id: asString(probe.probeId) ?? asString(probe.identity),
+    name: asString(probe.name),
+    type: asString(probe.type),
+    infra_type: asString(probe.infrastructureType),
+    enabled: probe.isEnabled,
+    referenced_by: asNumber(probe.referencedBy),
+    last_verdict: asString(status?.verdict),
+    last_verdict_description: asString(status?.description),
+    last_run_experiment: asString(executedBy?.experimentName),
+    last_run_at: toIso(executedBy?.updatedAt),
+  };
+}
+
+export const chaosResilienceHandler: DiagnoseHandler = {
+  entityType: "chaos_resilience",
+  description: "Review chaos resilience coverage — per-experiment resiliency scores from recent runs, experiments below a score threshold or never run, and probe health (disabled, failing, or unused probes). Pass probe_id for a single probe's status and recent verdicts.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    // Single-probe status
+    const probeId = asString(input.probe_id);
+    if (probeId) {
+      await sendProgress(extra, 0, 1, "Fetching probe...");
+      const probe = asRecord(await registry.dispatch(client, "chaos_probe", "get", { ...input, probe_id: probeId }, signal)) ?? {};
+      const runs = (Array.isArray(probe.recentProbeRuns) ? probe.recentProbeRuns : []).filter(isRecord);
+      await sendProgress(extra, 1, 1, "Probe status complete");
+      return {
+        probe: summarizeProbe(probe),
+        recent_runs: runs.map((r) => ({
+          verdict: asString(asRecord(r.status)?.verdict),
+          description: asString(asRecord(r.status)?.description),
+          experiment: asString(asRecord(r.executedByExperiment)?.experimentName),
+          experiment_id: asString(asRecord(r.executedByExperiment)?.experimentID),
+          at: toIso(asRecord(r.executedByExperiment)?.updatedAt),
+        })),
+      };
+    }
+
+    const requested = asNumber(args.max_experiments) ?? DEFAULT_MAX_EXPERIMENTS;
+    const maxExperiments = requested > 0 ? Math.min(requested, 50) : DEFAULT_MAX_EXPERIMENTS;
+    const minScore = asNumber(args.min_score) ?? DEFAULT_MIN_SCORE;
+
+    await sendProgress(extra, 0, 2, "Listing experiments...");
+    log.info("Reviewing chaos resilience", { maxExperiments, minScore });
+    const experiments = listItems(await registry.dispatch(client, "chaos_experiment", "list", {
+      ...input,
+      limit: maxExperiments,
+      page: 0,
+    }, signal)).map(summarizeExperiment);
+
+    const scored = experiments.filter((e) => typeof e.last_resiliency_score === "number");
+    const diagnostic: Record<string, unknown> = {
+      experiments_reviewed: experiments.length,
+      average_resiliency_score: scored.length > 0
+        ? round(scored.reduce((sum, e) => sum + (e.last_resiliency_score as number), 0) / scored.length)
+        : undefined,
+      min_score: minScore,
+      below_threshold: scored.filter((e) => (e.last_resiliency_score as number) < minScore),
+      never_run: experiments.filter((e) => e.recent_runs === 0).map((e) => ({ experiment_id: e.experiment_id, name: e.name })),
+      experiments,
+    };
+
+    await sendProgress(extra, 1, 2, "Checking probe health...");
+    try {
+      const probes = listItems(await registry.dispatch(client, "chaos_probe", "list", { ...input, limit: 50 }, signal))
+        .map(summarizeProbe);
+      diagnostic.probes = {
+        total: probes.length,
+        disabled: probes.filter((p) => p.enabled === false).map((p) => p.name),
+        failing: probes.filter((p) => p.last_verdict === "Failed" || p.last_verdict === "Error"),
+        unused: probes.filter((p) => p.referenced_by === 0).map((p) => p.name),
+        items: probes,
+      };
+    } catch (err) {
+      diagnostic.probes_error = err instanceof Error ? err.message : String(err);
+    }
+
+    await sendProgress(extra, 2, 2, "Resilience review complete");
+    return diagnostic;
+  },
+};
@@ -0,0 +1,396 @@
+import { createHash } from "node:crypto";
+import { parse as parseYaml } from "yaml";
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+import { harnessUiBaseUrl } from "../../utils/deep-links.js";
+
+const log = createLogger("diagnose:config-drift");
+
+/**
+ * Overrides V2 precedence, lowest first. Service-specific overrides beat
+ * environment-wide ones; infrastructure overrides beat environment overrides.
+ */
+const OVERRIDE_PRECEDENCE = [
+  "ENV_GLOBAL_OVERRIDE",
+  "ENV_SERVICE_OVERRIDE",
+  "INFRA_GLOBAL_OVERRIDE",
+  "INFRA_SERVICE_OVERRIDE",
+] as const;
+
+const PRECEDENCE_INDEX = new Map<string, number>(OVERRIDE_PRECEDENCE.map((type, idx) => [type, idx]));
+
+const OVERRIDE_PAGE_SIZE = 100;
+/** Pages of overrides read per environment before the result is reported as truncated. */
+const MAX_OVERRIDE_PAGES = 20;
+/** Distinct File Store / Git files read per diagnosis; entries past this are compared by source only. */
+const MAX_CONTENT_FETCHES = 50;
+
+/** Store types read through the SCM file-content API. */
+const GIT_STORE_TYPES = new Set(["Git", "Github", "GitLab", "Bitbucket", "AzureRepo"]);
+
+/** Where a rendered value came from — "service" or one of the override types. */
+type Source = "service" | (typeof OVERRIDE_PRECEDENCE)[number];
+
+interface RenderedEntry {
+  value: unknown;
+  source: Source;
+  /** SHA-256 of the files a manifest / config file renders, when they could be read. */
+  contentHash?: string;
+}
+
+interface RenderedConfig {
+  variables: Map<string, RenderedEntry>;
+  manifests: Map<string, RenderedEntry>;
+  configFiles: Map<string, RenderedEntry>;
+  overrideIds: string[];
+}
+
+function parseIds(value: unknown): string[] {
+  if (Array.isArray(value)) return value.map((v) => String(v).trim()).filter(Boolean);
+  const str = asString(value);
+  return str ? str.split(",").map((s) => s.trim()).filter(Boolean) : [];
+}
+
+/** Read `spec` from an override, falling back to parsing its YAML when the structured form is absent. */
+function overrideSpec(override: Record<string, unknown>): Record<string, unknown> {
+  const spec = asRecord(override.spec);
+  if (spec) return spec;
+  const yaml = asString(override.yaml);
+  if (!yaml) return {};
+  try {
+    const parsed = asRecord(parseYaml(yaml));
+    return asRecord(parsed?.overrides) ?? asRecord(asRecord(parsed?.overrideSet)?.spec) ?? {};
+  } catch {
+    return {};
+  }
+}
+
+/**
+ * Reduce a manifest / config-file store to the fields that decide which
+ * content is rendered: store type, connector, repo, ref, and file paths.
+ */
+function summarizeStore(entry: Record<string, unknown>): Record<string, unknown> {
+  const spec = asRecord(entry.spec);
+  const store = asRecord(spec?.store);
+  const storeSpec = asRecord(store?.spec);
+  const summary: Record<string, unknown> = {
+    type: entry.type,
+    store_type: store?.type,
+    connector_ref: storeSpec?.connectorRef,
+    repo_name: storeSpec?.repoName,
+    branch: storeSpec?.branch,
+    commit_id: storeSpec?.commitId,
+    git_fetch_type: storeSpec?.gitFetchType,
+    paths: storeSpec?.paths,
+    files: storeSpec?.files,
+    values_paths: spec?.valuesPaths,
+  };
+  for (const key of Object.keys(summary)) {
+    if (summary[key] === undefined || summary[key] === null) delete summary[key];
+  }
+  return summary;
+}
+
+function applyLayer(rendered: RenderedConfig, spec: Record<string, unknown>, source: Source): void {
+  for (const v of Array.isArray(spec.variables) ? spec.variables : []) {
+    if (!isRecord(v)) continue;
+    const name = asString(v.name);
+    if (name) rendered.variables.set(name, { value: v.value, source });
+  }
+  for (const m of Array.isArray(spec.manifests) ? spec.manifests : []) {
+    const manifest = asRecord(asRecord(m)?.manifest);
+    const id = asString(manifest?.identifier);
+    if (manifest && id) rendered.manifests.set(id, { value: summarizeStore(manifest), source });
+  }
+  for (const c of Array.isArray(spec.configFiles) ? spec.configFiles : []) {
+    const configFile = asRecord(asRecord(c)?.configFile);
+    const id = asString(configFile?.identifier);
+    if (configFile && id) rendered.configFiles.set(id, { value: summarizeStore(configFile), source });
+  }
+}
+
+/** Extract the service definition spec (variables/manifests/configFiles) from a service GET response. */
+function serviceDefinitionSpec(raw: unknown): Record<string, unknown> {
+  const service = asRecord(asRecord(raw)?.service) ?? asRecord(raw);
+  const yaml = asString(service?.yaml);
+  if (!yaml) return {};
+  try {
+    const parsed = asRecord(parseYaml(yaml));
+    return asRecord(asRecord(asRecord(parsed?.service)?.serviceDefinition)?.spec) ?? {};
+  } catch {
+    return {};
+  }
+}
+
+function stableStringify(value: unknown): string {
+  if (Array.isArray(value)) return `[${value.map(stableStringify).join(",")}]`;
+  if (isRecord(value)) {
+    return `{${Object.keys(value).sort().map((k) => `${JSON.stringify(k)}:${stableStringify(value[k])}`).join(",")}}`;
+  }
+  return JSON.stringify(value) ?? "undefined";
+}
+
+/** List every override in `envId`, page by page, up to MAX_OVERRIDE_PAGES. */
+async function listOverrides(
+  ctx: DiagnoseContext,
+  envId: string,
+): Promise<{ overrides: Record<string, unknown>[]; truncated: boolean }> {
+  const { client, registry, input, signal } = ctx;
+  const overrides: Record<string, unknown>[] = [];
+  for (let page = 0; page < MAX_OVERRIDE_PAGES; page++) {
+    const raw = asRecord(await registry.dispatch(client, "service_override", "list", {
+      ...input,
+      environment_id: envId,
+      page,
+      size: OVERRIDE_PAGE_SIZE,
+    }, signal));
+    const items = Array.isArray(raw?.items) ? raw.items : [];
+    overrides.push(...items.filter(isRecord));
+    const total = asNumber(raw?.total);
+    if (items.length < OVERRIDE_PAGE_SIZE || (total !== undefined && overrides.length >= total)) {
+      return { overrides, truncated: false };
+    }
+  }
+  return { overrides, truncated: true };
+}
+
+/** Read one file of a File Store or Git store, as summarized by `summarizeStore`. */
+async function readStoreFile(ctx: DiagnoseContext, store: Record<string, unknown>, path: string): Promise<string> {
+  const { client, config, input, signal } = ctx;
+  const scope = {
+    orgIdentifier: asString(input.org_id) ?? config.HARNESS_ORG,
+    projectIdentifier: asString(input.project_id) ?? config.HARNESS_PROJECT,
+  };
+  const storeType = asString(store.store_type);
+  let response: unknown;
+  if (storeType === "Harness") {
+    response = await client.request({
+      method: "GET",
+      path: `/ng/api/file-store/files/${encodeURIComponent(path)}/content`,
+      params: scope,
+      signal,
+    });
+  } else if (storeType && GIT_STORE_TYPES.has(storeType)) {
+    const byCommit = store.git_fetch_type === "Commit";
+    response = await client.request({
+      method: "GET",
+      path: "/ng/api/scm/fileContent",
+      params: {
+        ...scope,
+        connectorIdentifier: asString(store.connector_ref),
+        repoName: asString(store.repo_name),
+        branch: byCommit ? undefined : asString(store.branch),
+        commitId: byCommit ? asString(store.commit_id) : undefined,
+        filePath: path,
+      },
+      signal,
+    });
+  } else {
+    throw new Error(`content of ${storeType ?? "unknown"} stores cannot be read`);
+  }
+  if (typeof response === "string") return response;
+  const content = asString(asRecord(asRecord(response)?.data)?.content) ?? asString(asRecord(response)?.content);
+  if (content === undefined) throw new Error(`no content returned for ${path}`);
+  return content;
+}
+
+/**
+ * Hash the content of every manifest / config file rendered in any
+ * environment, so they are compared by what they deploy rather than where
+ * it is stored. Each distinct file is read once. Entries whose files cannot
+ * be read keep no hash and are compared by source; why is returned per entry.
+ */
+async function hashStoreContents(
+  ctx: DiagnoseContext,
+  envIds: string[],
+  rendered: Map<string, RenderedConfig>,
+): Promise<Record<string, string>> {
+  const reads = new Map<string, Promise<string>>();
+  const unread: Record<string, string> = {};
+  for (const env of envIds) {
+    const { manifests, configFiles } = rendered.get(env)!;
+    for (const [category, entries] of [["manifests", manifests], ["config_files", configFiles]] as const) {
+      for (const [id, entry] of entries) {
+        const store = asRecord(entry.value) ?? {};
+        const files = [store.files, store.paths].flatMap((f) => (Array.isArray(f) ? f : [])).map(String);
+        try {
+          if (files.length === 0) throw new Error("no files listed in the store");
+          const contents = await Promise.all(files.map((path) => {
+            const key = stableStringify([store.store_type, store.connector_ref, store.repo_name, store.git_fetch_type, store.branch, store.commit_id, path]);
+            let read = reads.get(key);
+            if (!read) {
+              if (reads.size >= MAX_CONTENT_FETCHES) throw new Error(`more than ${MAX_CONTENT_FETCHES} files to read`);
+              read = readStoreFile(ctx, store, path);
+              read.catch(() => {});
+              reads.set(key, read);
+            }
+            return read;
+          }));
+          const hash = createHash("sha256");
+          for (const content of contents) hash.update(createHash("sha256").update(content).digest("hex"));
+          entry.contentHash = hash.digest("hex");
+        } catch (err) {
+          unread[`${category}.${id}@${env}`] = err instanceof Error ? err.message : String(err);
+        }
+      }
+    }
+  }
+  return unread;
+}
+
+/**
+ * Compare one category (variables, manifests, config files) across environments.
+ * An entry drifts when its rendered value differs between environments or is
+ * missing from some of them. Manifests and config files are compared by
+ * content hash when every environment's content was read, else by source.
+ */
+function compareCategory(
+  envIds: string[],
+  rendered: Map<string, RenderedConfig>,
+  pick: (r: RenderedConfig) => Map<string, RenderedEntry>,
+  hasContent = false,
+): { drifted: Record<string, unknown>[]; consistent: string[] } {
+  const keys = new Set<string>();
+  for (const env of envIds) for (const key of pick(rendered.get(env)!).keys()) keys.add(key);
+
+  const drifted: Record<string, unknown>[] = [];
+  const consistent: string[] = [];
+  for (const key of [...keys].sort()) {
+    const values: Record<string, unknown> = {};
+    const sources: Record<string, Source> = {};
+    const missingIn: string[] = [];
+    const entries: RenderedEntry[] = [];
+    for (const env of envIds) {
+      const entry = pick(rendered.get(env)!).get(key);
+      if (!entry) {
+        missingIn.push(env);
+        continue;
+      }
+      entries.push(entry);
+      values[env] = entry.contentHash ? { ...(entry.value as Record<string, unknown>), content_sha256: entry.contentHash } : entry.value;
+      sources[env] = entry.source;
+    }
+    const byContent = hasContent && entries.every((e) => e.contentHash !== undefined);
+    const fingerprints = new Set(entries.map((e) => (byContent ? e.contentHash : stableStringify(e.value))));
+    if (fingerprints.size <= 1 && missingIn.length === 0) {
+      consistent.push(key);
+    } else {
+      drifted.push({
+        name: key,
+        values,
+        sources,
+        missing_in: missingIn.length > 0 ? missingIn : undefined,
+        ...(hasContent ? { compared_by: byContent ? "content" : "source" } : {}),
+      });
+    }
+  }
+  return { drifted, consistent };
+}
+
+export const configDriftHandler: DiagnoseHandler = {
+  entityType: "config_drift",
+  description: "Detect configuration drift for a service across environments — renders variables, manifests, and config files from the service definition plus Overrides V2 layers and reports values and File Store / Git content that diverge.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, config, input, args, extra, signal } = ctx;
+
+    const serviceId = asString(args.service_id) ?? asString(args.resource_id);
+    if (!serviceId) {
+      throw new Error("service_id (or resource_id) is required for config_drift.");
+    }
+    const envIds = parseIds(args.environment_ids);
+    if (envIds.length < 2) {
+      throw new Error("environment_ids must list at least two environments to compare (array or comma-separated string).");
+    }
+    const infraId = asString(args.infra_id);
+
+    const totalSteps = envIds.length + 2;
+    await sendProgress(extra, 0, totalSteps, `Fetching service '${serviceId}'...`);
+    log.info("Detecting config drift", { serviceId, envIds, infraId });
+
+    const serviceRaw = await registry.dispatch(client, "service", "get", { ...input, service_id: serviceId }, signal);
+    const baseSpec = serviceDefinitionSpec(serviceRaw);
+
+    const rendered = new Map<string, RenderedConfig>();
+    const overrideErrors: Record<string, string> = {};
+    const truncatedEnvs: string[] = [];
+    for (const [idx, envId] of envIds.entries()) {
+      await sendProgress(extra, idx + 1, totalSteps, `Rendering overrides for environment '${envId}'...`);
+      const env: RenderedConfig = { variables: new Map(), manifests: new Map(), configFiles: new Map(), overrideIds: [] };
+      applyLayer(env, baseSpec, "service");
+
+      let overrides: Record<string, unknown>[] = [];
+      try {
+        const listed = await listOverrides(ctx, envId);
+        overrides = listed.overrides;
+        if (listed.truncated) truncatedEnvs.push(envId);
+      } catch (err) {
+        overrideErrors[envId] = err instanceof Error ? err.message : String(err);
+      }
+
+      const applicable = overrides.filter((o) => {
+        const type = asString(o.type);
+        const serviceRef = asString(o.serviceRef);
+        const infra = asString(o.infraIdentifier);
+        if (type?.endsWith("_SERVICE_OVERRIDE") && serviceRef !== serviceId) return false;
+        if (type?.startsWith("INFRA_") && (!infraId || infra !== infraId)) return false;
+        return type !== undefined && PRECEDENCE_INDEX.has(type);
+      });
+      applicable.sort((a, b) => PRECEDENCE_INDEX.get(a.type as string)! - PRECEDENCE_INDEX.get(b.type as string)!);
+      for (const override of applicable) {
+        applyLayer(env, overrideSpec(override), override.type as Source);
+        const id = asString(override.identifier);
+        if (id) env.overrideIds.push(id);
+      }
+      rendered.set(envId, env);
+    }
+
+    await sendProgress(extra, envIds.length + 1, totalSteps, "Reading manifest and config file content...");
+    const unreadContent = await hashStoreContents(ctx, envIds, rendered);
+
+    const variables = compareCategory(envIds, rendered, (r) => r.variables);
+    const manifests = compareCategory(envIds, rendered, (r) => r.manifests, true);
+    const configFiles = compareCategory(envIds, rendered, (r) => r.configFiles, true);
+    const driftCount = variables.drifted.length + manifests.drifted.length + configFiles.drifted.length;
+
+    const diagnostic: Record<string, unknown> = {
+      service_id: serviceId,
+      environments: envIds,
+      infra_id: infraId,
+      drift_detected: driftCount > 0,
+      drift_count: driftCount,
+      applied_overrides: Object.fromEntries(envIds.map((e) => [e, rendered.get(e)!.overrideIds])),
+      variables: { drifted: variables.drifted, consistent_count: variables.consistent.length },
+      manifests: { drifted: manifests.drifted, consistent_count: manifests.consistent.length },
+      config_files: { drifted: configFiles.drifted, consistent_count: configFiles.consistent.length },
+    };
+    const notes: string[] = [];
+    if (Object.keys(overrideErrors).length > 0) {
+      diagnostic.override_errors = overrideErrors;
+      notes.push("Overrides could not be fetched for some environments — their rendered config reflects the service definition only.");
+    }
+    if (truncatedEnvs.length > 0) {
+      diagnostic.truncated_overrides = truncatedEnvs;
+      notes.push(`Only the first ${MAX_OVERRIDE_PAGES * OVERRIDE_PAGE_SIZE} overrides were read for ${truncatedEnvs.join(", ")} — overrides past that are not applied, so drift there may be incomplete or overstated.`);
+    }
+    if (Object.keys(unreadContent).length > 0) {
+      diagnostic.unread_content = unreadContent;
+      notes.push("Content could not be read for some manifests/config files — those are compared by source (store type, repo, branch, paths, or File Store files) instead.");
+    }
+    if (notes.length > 0) diagnostic.note = notes.join(" ");
+    if (manifests.drifted.length > 0 || configFiles.drifted.length > 0) {
+      diagnostic.hint = "Drifted manifests/config files with compared_by='content' render different files; with compared_by='source' their content could not be read and their sources differ. Inspect content with harness_get(resource_type='file_content') for Harness Code or harness_get(resource_type='file_store') for File Store entries.";
+    }
+    const base = harnessUiBaseUrl(config);
+    const org = asString(input.org_id) ?? config.HARNESS_ORG;
+    const project = asString(input.project_id) ?? config.HARNESS_PROJECT;
+    if (org && project) {
+      diagnostic.openInHarness = `${base}/ng/account/${registry.getAccountId()}/all/cd/orgs/${org}/projects/${project}/serviceOverrides`;
+    }
+
+    await sendProgress(extra, totalSteps, totalSteps, "Config drift detection complete");
+    return diagnostic;
+  },
+};
@@ -2,12 +2,137 @@ import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
 import { createLogger } from "../../utils/logger.js";
 import { sendProgress } from "../../utils/progress.js";
 import { isRecord, asRecord, asString, asNumber } from "../../utils/type-guards.js";
+import { HarnessApiError } from "../../utils/errors.js";
+import type { HarnessClient } from "../../client/harness-client.js";
+import type { Registry } from "../../registry/index.js";
+import { harnessUiBaseUrl } from "../../utils/deep-links.js";
 
 const log = createLogger("diagnose:connector");
 
+const TOTAL_STEPS = 4;
+
+interface SecretRef {
+  /** Spec path of the reference, e.g. `authentication.spec.spec.passwordRef` */
+  field: string;
+  /** Reference as written in the connector (`name`, `org.name`, `account.name`) */
+  ref: string;
+  scope: "account" | "org" | "project";
+  identifier: string;
+}
+
+export interface ProbableCause {
+  priority: "high" | "medium" | "low";
+  cause: string;
+  evidence: string;
+  fix: string;
+}
+
+const PRIORITY_ORDER: Record<ProbableCause["priority"], number> = { high: 0, medium: 1, low: 2 };
+
+/** Non-secret `*Ref` fields that appear in connector specs. */
+const NON_SECRET_REF_FIELDS = new Set(["connectorRef", "secretManagerRef", "delegateRef"]);
+
+/**
+ * Collect secret references from a connector spec: every string field ending in
+ * `Ref` (passwordRef, tokenRef, secretKeyRef, sshKeyRef, ...), at any depth.
+ * Runtime expressions (`<+...>`) are skipped — they resolve at execution time.
+ */
+export function collectSecretRefs(spec: unknown, prefix = ""): SecretRef[] {
+  if (!isRecord(spec)) return [];
+  const refs: SecretRef[] = [];
+  for (const [key, value] of Object.entries(spec)) {
+    const field = prefix ? `${prefix}.${key}` : key;
+    if (typeof value === "string" && key.endsWith("Ref") && !NON_SECRET_REF_FIELDS.has(key)) {
+      if (!value || value.startsWith("<+")) continue;
+      const [head, ...rest] = value.split(".");
+      const scoped = (head === "account" || head === "org") && rest.length > 0;
+      refs.push({
+        field,
+        ref: value,
+        scope: scoped ? (head as "account" | "org") : "project",
+        identifier: scoped ? rest.join(".") : value,
+      });
+    } else if (isRecord(value)) {
+      refs.push(...collectSecretRefs(value, field));
+    }
+  }
+  return refs;
+}
+
+async function checkSecretRef(
+  client: HarnessClient,
+  registry: Registry,
+  input: Record<string, unknown>,
+  ref: SecretRef,
+  signal: AbortSignal,
+): Promise<Record<string, unknown>> {
+  const result = { field: ref.field, ref: ref.ref, scope: ref.scope };
+  try {
+    await registry.dispatch(client, "secret", "get", {
+      org_id: input.org_id,
+      project_id: input.project_id,
+      secret_id: ref.identifier,
+      resource_scope: ref.scope,
+    }, signal);
+    return { ...result, exists: true };
+  } catch (err) {
+    if (err instanceof HarnessApiError && err.statusCode === 404) return { ...result, exists: false };
+    log.debug("Secret lookup failed", { ref: ref.ref, error: String(err) });
+    return { ...result, exists: "unknown", error: String(err) };
+  }
+}
+
+interface DelegateSummary {
+  name?: string;
+  tags?: string[];
+  connected?: boolean;
+}
+
+/** A selector matches a delegate by name or by one of its tags. */
+function resolveSelectors(selectors: string[], delegates: DelegateSummary[]): Record<string, unknown>[] {
+  return selectors.map((selector) => {
+    const matches = delegates.filter((d) => d.name === selector || (d.tags ?? []).includes(selector));
+    return {
+      selector,
+      matching_delegates: matches.map((d) => d.name),
+      connected_delegates: matches.filter((d) => d.connected).map((d) => d.name),
+    };
+  });
+}
+
+/** Classify test-connection error text into likely causes. */
+function causesFromTestErrors(text: string): ProbableCause[] {
+  const causes: ProbableCause[] = [];
+  if (/401|403|unauthori[sz]ed|forbidden|invalid credentials|authentication failed|bad credentials|access denied/i.test(text)) {
+    causes.push({
+      priority: "high",
+      cause: "Credentials rejected by the target system",
+      evidence: text,
+      fix: "Rotate or correct the referenced secret value, and confirm the account/token has the required permissions on the target.",
+    });
+  }
+  if (/certificate|x509|ssl|tls|pkix/i.test(text)) {
+    causes.push({
+      priority: "medium",
+      cause: "TLS certificate not trusted by the delegate",
+      evidence: text,
+      fix: "Install the target's CA certificate on the delegate, or fix the certificate chain on the target.",
+    });
+  }
+  if (/timed? ?out|timeout|connection refused|unknown ?host|no route|unreachable|could not resolve|ENOTFOUND|ECONNREFUSED/i.test(text)) {
+    causes.push({
+      priority: "medium",
+      cause: "Target not reachable from the delegate",
+      evidence: text,
+      fix: "Check the connector URL, DNS, proxy settings, and firewall rules between the delegate and the target.",
+    });
+  }
+  return causes;
+}
+
 export const connectorHandler: DiagnoseHandler = {
   entityType: "connector",
-  description: "Diagnose a connector — fetches details and runs a connectivity test, returning type, auth method, status, and any connection errors.",
+  description: "Diagnose a connector — fetches details, runs a connectivity test, checks that referenced secrets exist and delegate selectors resolve to connected delegates, and returns a prioritized list of probable causes.",
 
   async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
     const { client, registry, config, input, extra, signal } = ctx;
@@ -21,7 +146,7 @@ export const connectorHandler: DiagnoseHandler = {
     const diagnostic: Record<string, unknown> = {};
 
     // 1. Fetch connector details
-    await sendProgress(extra, 0, 2, "Fetching connector details...");
+    await sendProgress(extra, 0, TOTAL_STEPS, "Fetching connector details...");
     log.info("Fetching connector", { connectorId });
 
     const raw = await registry.dispatch(client, "connector", "get", input, signal);
@@ -76,7 +201,7 @@ export const connectorHandler: DiagnoseHandler = {
     }
 
     // 2. Run connectivity test
-    await sendProgress(extra, 1, 2, "Testing connectivity...");
+    await sendProgress(extra, 1, TOTAL_STEPS, "Testing connectivity...");
     log.info("Testing connector connectivity", { connectorId });
 
     try {
@@ -109,15 +234,89 @@ export const connectorHandler: DiagnoseHandler = {
       };
     }
 
+    const causes: ProbableCause[] = [];
+    const test = asRecord(diagnostic.test_result) ?? {};
+    if (test.status !== "SUCCESS") {
+      const errorText = [
+        asString(test.error_summary),
+        asString(test.error),
+        ...(Array.isArray(test.errors) ? test.errors.filter(isRecord).map((e) => asString(e.message)) : []),
+      ].filter(Boolean).join("; ");
+      if (errorText) causes.push(...causesFromTestErrors(errorText));
+    }
+
+    // 3. Check that secrets the connector references exist
+    await sendProgress(extra, 2, TOTAL_STEPS, "Checking referenced secrets...");
+    const secretRefs = collectSecretRefs(spec);
+    if (secretRefs.length > 0) {
+      const secrets = await Promise.all(secretRefs.map((ref) => checkSecretRef(client, registry, input, ref, signal)));
+      diagnostic.secrets = secrets;
+      for (const secret of secrets.filter((s) => s.exists === false)) {
+        causes.push({
+          priority: "high",
+          cause: `Referenced secret "${String(secret.ref)}" does not exist`,
+          evidence: `${String(secret.field)} → ${String(secret.ref)} (${String(secret.scope)} scope) returned 404`,
+          fix: "Create the secret at that scope, or update the connector to reference an existing secret (prefix account./org. for higher scopes).",
+        });
+      }
+    }
+
+    // 4. Check that delegate selectors resolve to connected delegates
+    await sendProgress(extra, 3, TOTAL_STEPS, "Resolving delegate selectors...");
+    const rawSelectors = spec?.delegateSelectors;
+    const selectors = Array.isArray(rawSelectors)
+      ? rawSelectors.filter((s): s is string => typeof s === "string" && s.length > 0)
+      : [];
+    if (selectors.length > 0) {
+      try {
+        const raw = await registry.dispatch(client, "delegate", "list", { ...input, all: "true" }, signal);
+        const delegates = (Array.isArray(raw) ? raw : []).filter(isRecord) as DelegateSummary[];
+        const resolved = resolveSelectors(selectors, delegates);
+        diagnostic.delegate_selectors = resolved;
+        for (const entry of resolved) {
+          const matching = entry.matching_delegates as unknown[];
+          const connected = entry.connected_delegates as unknown[];
+          if (matching.length === 0) {
+            causes.push({
+              priority: "high",
+              cause: `Delegate selector "${String(entry.selector)}" matches no delegate`,
+              evidence: `No delegate has the name or tag "${String(entry.selector)}"`,
+              fix: "Fix the selector on the connector, or add the tag to a running delegate.",
+            });
+          } else if (connected.length === 0) {
+            causes.push({
+              priority: "high",
+              cause: `No delegate matching "${String(entry.selector)}" is connected`,
+              evidence: `Matching delegates are all disconnected: ${matching.join(", ")}`,
+              fix: `Restart the delegate(s) or check their network path to Harness. Run harness_diagnose(resource_type="delegate") for heartbeat details.`,
+            });
+          }
+        }
+      } catch (err) {
+        log.warn("Delegate selector resolution failed", { connectorId, error: String(err) });
+        diagnostic.delegate_selectors = { error: String(err) };
+      }
+    }
+
+    if (test.status !== "SUCCESS" && causes.length === 0) {
+      causes.push({
+        priority: "low",
+        cause: "Connectivity test failed without a recognized cause",
+        evidence: asString(test.error_summary) ?? asString(test.error) ?? String(test.status ?? "unknown"),
+        fix: "Review the test errors above and the delegate logs for the test task.",
+      });
+    }
+    diagnostic.probable_causes = causes.sort((a, b) => PRIORITY_ORDER[a.priority] - PRIORITY_ORDER[b.priority]);
+
     // Deep link
     const orgId = asString(input.org_id) ?? config.HARNESS_ORG;
     const projectId = asString(input.project_id) ?? config.HARNESS_PROJECT;
     if (orgId && projectId) {
-      const base = config.HARNESS_BASE_URL.replace(/\/$/, "");
+      const base = harnessUiBaseUrl(config);
       diagnostic.openInHarness = `${base}/ng/account/${registry.getAccountId()}/all/orgs/${orgId}/projects/${projectId}/setup/connectors/${connectorId}`;
     }
 
-    await sendProgress(extra, 2, 2, "Connector diagnosis complete");
+    await sendProgress(extra, TOTAL_STEPS, TOTAL_STEPS, "Connector diagnosis complete");
     return diagnostic;
   },
 };
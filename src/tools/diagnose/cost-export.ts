@@ -0,0 +1,123 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { writeOutputFile, toCsv } from "../../utils/output-dir.js";
+import { asNumber, asString, isRecord } from "../../utils/type-guards.js";
+import { beginOperation, type Operation } from "../../utils/checkpoints.js";
+
+const log = createLogger("diagnose:cost-export");
+
+const PAGE_SIZE = 500;
+const DEFAULT_MAX_ROWS = 10_000;
+const BASE_COLUMNS = ["name", "id", "cost", "costTrend"];
+
+/** Rows fetched so far, saved after each page so an interrupted export can resume. */
+interface ExportCheckpoint {
+  rows: Record<string, unknown>[];
+  total: number;
+}
+
+/**
+ * Page through cost_breakdown (or cost_cluster_breakdown) until the reported
+ * total or max_rows is reached, continuing from the operation's checkpoint.
+ */
+async function fetchAllRows(
+  ctx: DiagnoseContext,
+  resourceType: string,
+  query: Record<string, unknown>,
+  maxRows: number,
+  operation: Operation<ExportCheckpoint>,
+): Promise<{ rows: Record<string, unknown>[]; total: number }> {
+  const rows: Record<string, unknown>[] = [...(operation.resumed?.rows ?? [])];
+  let total = operation.resumed?.total ?? 0;
+  for (let offset = rows.length; offset < maxRows; offset += PAGE_SIZE) {
+    const limit = Math.min(PAGE_SIZE, maxRows - offset);
+    const page = await ctx.registry.dispatch(ctx.client, resourceType, "list", { ...query, limit, offset }, ctx.signal) as
+      { items?: unknown[]; total?: number } | undefined;
+    const items = (page?.items ?? []).filter(isRecord);
+    total = asNumber(page?.total) ?? total;
+    rows.push(...items);
+    operation.save({ rows: [...rows], total });
+    await sendProgress(ctx.extra, rows.length, Math.min(total || rows.length, maxRows), `Fetched ${rows.length} rows (resume_token: ${operation.id})...`);
+    if (items.length < limit || (total > 0 && rows.length >= total)) break;
+  }
+  return { rows, total };
+}
+
+/** Stable column order: known fields first, then any extras in first-seen order. */
+function columnsFor(rows: Record<string, unknown>[], preferred: string[]): string[] {
+  const columns = preferred.filter((c) => rows.some((r) => r[c] !== undefined));
+  for (const row of rows) {
+    for (const key of Object.keys(row)) {
+      if (!columns.includes(key) && !key.startsWith("__")) columns.push(key);
+    }
+  }
+  return columns;
+}
+
+export const costExportHandler: DiagnoseHandler = {
+  entityType: "cost_export",
+  description: "Export a CCM perspective breakdown over a time range as a CSV file in HARNESS_OUTPUT_DIR and return a resource_link to it — for finance users who need the raw rows rather than a summary.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { config, input, args } = ctx;
+
+    const outputDir = config.HARNESS_OUTPUT_DIR;
+    if (!outputDir) {
+      throw new Error("cost_export writes files and requires HARNESS_OUTPUT_DIR to be set on the server.");
+    }
+    const format = asString(args.format) ?? "csv";
+    if (format !== "csv") {
+      throw new Error(`Unsupported export format '${format}'. Only 'csv' is available; Parquet needs a columnar writer this server does not bundle.`);
+    }
+
+    const cluster = args.cluster === true;
+    const perspectiveId = asString(input.perspective_id) ?? asString(args.resource_id);
+    if (!perspectiveId && !cluster) {
+      throw new Error("perspective_id is required for cost_export (or set cluster: true to use the default cluster perspective).");
+    }
+    const requestedMax = asNumber(args.max_rows) ?? DEFAULT_MAX_ROWS;
+    const maxRows = requestedMax > 0 ? Math.min(requestedMax, 100_000) : DEFAULT_MAX_ROWS;
+    const resourceType = cluster ? "cost_cluster_breakdown" : "cost_breakdown";
+    const query: Record<string, unknown> = {
+      perspective_id: perspectiveId,
+      group_by: asString(input.group_by),
+      time_filter: asString(input.time_filter),
+      start_time: input.start_time,
+      end_time: input.end_time,
+      ...(cluster ? { cluster_name: input.cluster_name, namespace: input.namespace } : {}),
+    };
+
+    const operation = beginOperation<ExportCheckpoint>("cost_export", ctx.client.account, { resourceType, query, maxRows }, asString(args.resume_token));
+    log.info("Exporting cost report", { perspectiveId, resourceType, maxRows, resumedRows: operation.resumed?.rows.length ?? 0 });
+    const { rows, total } = await fetchAllRows(ctx, resourceType, query, maxRows, operation);
+    const columns = columnsFor(rows, cluster ? ["name", "id", "cost", "cost_trend"] : BASE_COLUMNS);
+
+    const stamp = new Date().toISOString().replace(/[:.]/g, "-");
+    const fileName = `cost-${perspectiveId ?? "cluster"}-${asString(input.group_by) ?? "default"}-${stamp}.csv`;
+    const file = await writeOutputFile(outputDir, fileName, toCsv(columns, rows));
+    const totalCost = rows.reduce((sum, r) => sum + (asNumber(r.cost) ?? 0), 0);
+    operation.complete();
+
+    return {
+      perspective_id: perspectiveId,
+      group_by: asString(input.group_by),
+      time_filter: asString(input.time_filter) ?? (input.start_time ? "custom" : "LAST_30_DAYS"),
+      format,
+      ...(operation.resumed ? { resumed: true } : {}),
+      rows: rows.length,
+      ...(total > rows.length ? { truncated: true, total_available: total } : {}),
+      columns,
+      total_cost: Math.round(totalCost * 100) / 100,
+      file_path: file.path,
+      bytes: file.bytes,
+      resource_links: [{
+        type: "resource_link",
+        uri: file.uri,
+        name: file.name,
+        mimeType: "text/csv",
+        description: `${rows.length} cost rows`,
+      }],
+    };
+  },
+};
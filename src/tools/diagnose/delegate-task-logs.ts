@@ -0,0 +1,184 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:delegate-task-logs");
+
+const FAILED_STATUSES = new Set(["Failed", "Errored", "Aborted", "Expired"]);
+const DEFAULT_MAX_LOG_LINES = 200;
+/** Padding around the step's run window — delegate task logs often start before the step reports running. */
+const WINDOW_PADDING_MS = 5 * 60_000;
+
+interface DelegateTaskRef {
+  delegate_id?: string;
+  delegate_name?: string;
+  task_id?: string;
+  task_name?: string;
+}
+
+/**
+ * Pick the step to investigate: the node matching step_id (node execution
+ * UUID from a Harness URL, or step identifier), else the first failed step.
+ */
+function selectStepNode(
+  nodeMap: Record<string, unknown>,
+  stepId: string | undefined,
+): Record<string, unknown> | undefined {
+  const nodes = Object.entries(nodeMap)
+    .filter((entry): entry is [string, Record<string, unknown>] => isRecord(entry[1]));
+  if (stepId) {
+    return nodes.find(([uuid, node]) => uuid === stepId || node.uuid === stepId || node.identifier === stepId)?.[1];
+  }
+  const failedSteps = nodes
+    .map(([, node]) => node)
+    .filter((node) => FAILED_STATUSES.has(asString(node.status) ?? "") && (asString(node.baseFqn) ?? "").includes(".steps."));
+  // Prefer the step that actually ran on a delegate
+  return failedSteps.find((node) => Array.isArray(node.delegateInfoList) && node.delegateInfoList.length > 0)
+    ?? failedSteps[0];
+}
+
+function collectTaskRefs(node: Record<string, unknown>): DelegateTaskRef[] {
+  const refs: DelegateTaskRef[] = [];
+  const seenTasks = new Set<string>();
+  for (const info of Array.isArray(node.delegateInfoList) ? node.delegateInfoList : []) {
+    if (!isRecord(info)) continue;
+    const taskId = asString(info.taskId);
+    if (taskId) seenTasks.add(taskId);
+    refs.push({
+      delegate_id: asString(info.id),
+      delegate_name: asString(info.name),
+      task_id: taskId,
+      task_name: asString(info.taskName),
+    });
+  }
+  // Tasks queued but never acknowledged by a delegate only appear in executableResponses
+  for (const response of Array.isArray(node.executableResponses) ? node.executableResponses : []) {
+    const task = asRecord(asRecord(response)?.task);
+    const taskId = asString(task?.taskId);
+    if (taskId && !seenTasks.has(taskId)) {
+      seenTasks.add(taskId);
+      refs.push({ task_id: taskId, task_name: asString(task?.taskName) });
+    }
+  }
+  return refs;
+}
+
+function formatLogLine(entry: unknown): string {
+  const e = asRecord(entry);
+  if (!e) return String(entry);
+  const time = asString(e.ISOTime) ?? asString(e.timestamp) ?? "";
+  const severity = asString(e.severity) ?? "";
+  const message = asString(e.message) ?? JSON.stringify(e);
+  return [time, severity, message].filter(Boolean).join(" ");
+}
+
+export const delegateTaskLogsHandler: DiagnoseHandler = {
+  entityType: "delegate_task_logs",
+  description: "Correlate a failed execution step with the delegate that ran it — resolves delegate task IDs from the execution graph, fetches the delegate-side task logs for the step's time window, and reports the delegate's current health.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const executionId = asString(input.execution_id) ?? asString(args.resource_id);
+    if (!executionId) {
+      throw new Error("execution_id is required for delegate_task_logs. Pass it in options or via a Harness execution URL.");
+    }
+    const stepId = asString(input.step_id);
+    const requestedLines = Number(args.max_log_lines ?? DEFAULT_MAX_LOG_LINES);
+    const maxLines = Number.isFinite(requestedLines) && requestedLines > 0 ? requestedLines : DEFAULT_MAX_LOG_LINES;
+
+    await sendProgress(extra, 0, 3, "Fetching execution graph...");
+    log.info("Correlating delegate task logs", { executionId, stepId });
+    const execution = asRecord(await registry.dispatch(client, "execution", "get", {
+      ...input,
+      execution_id: executionId,
+      render_full_graph: true,
+    }, signal));
+    const nodeMap = asRecord(asRecord(execution?.executionGraph)?.nodeMap) ?? {};
+
+    const node = selectStepNode(nodeMap, stepId);
+    if (!node) {
+      throw new Error(stepId
+        ? `Step '${stepId}' was not found in execution '${executionId}'.`
+        : `Execution '${executionId}' has no failed step. Pass step_id to inspect a specific step.`);
+    }
+
+    const startTs = typeof node.startTs === "number" ? node.startTs : undefined;
+    const endTs = typeof node.endTs === "number" ? node.endTs : undefined;
+    const failureInfo = asRecord(node.failureInfo);
+    const diagnostic: Record<string, unknown> = {
+      execution_id: executionId,
+      step: {
+        identifier: asString(node.identifier),
+        name: asString(node.name),
+        status: asString(node.status),
+        step_type: asString(node.stepType),
+        failure_message: asString(failureInfo?.message),
+        started_at: startTs ? new Date(startTs).toISOString() : undefined,
+        ended_at: endTs ? new Date(endTs).toISOString() : undefined,
+      },
+    };
+
+    const refs = collectTaskRefs(node);
+    const taskIds = refs.map((r) => r.task_id).filter((id): id is string => !!id);
+    if (taskIds.length === 0) {
+      diagnostic.delegates = [];
+      diagnostic.note = "No delegate task was recorded for this step. It likely ran on Harness Cloud infrastructure or inside a CI build pod — use harness_diagnose(resource_type='pipeline', options={include_logs:true}) for the step console log.";
+      await sendProgress(extra, 3, 3, "Delegate task correlation complete");
+      return diagnostic;
+    }
+
+    // Current delegate health for each distinct delegate that picked up a task
+    await sendProgress(extra, 1, 3, "Checking delegate health...");
+    const delegateNames = [...new Set(refs.map((r) => r.delegate_name).filter((n): n is string => !!n))];
+    const health = new Map<string, Record<string, unknown>>();
+    if (delegateNames.length > 0) {
+      try {
+        const raw = await registry.dispatch(client, "delegate", "list", { all: true }, signal);
+        const delegates = Array.isArray(raw) ? raw : (Array.isArray(asRecord(raw)?.items) ? asRecord(raw)!.items as unknown[] : []);
+        for (const d of delegates) {
+          const rec = asRecord(d);
+          const name = asString(rec?.name);
+          if (!rec || !name || !delegateNames.includes(name)) continue;
+          const lastHeartBeat = typeof rec.lastHeartBeat === "number" ? rec.lastHeartBeat : undefined;
+          health.set(name, {
+            connected: rec.connected,
+            last_heartbeat: lastHeartBeat ? new Date(lastHeartBeat).toISOString() : undefined,
+            replicas: Array.isArray(rec.delegateReplicas) ? rec.delegateReplicas.length : undefined,
+          });
+        }
+      } catch (err) {
+        log.warn("Delegate health lookup failed", { error: String(err) });
+      }
+    }
+    diagnostic.delegates = refs.map((r) => ({
+      ...r,
+      health: r.delegate_name ? health.get(r.delegate_name) : undefined,
+    }));
+
+    await sendProgress(extra, 2, 3, `Fetching delegate logs for ${taskIds.length} task(s)...`);
+    try {
+      const logs = asRecord(await registry.dispatch(client, "delegate_task_log", "list", {
+        task_ids: taskIds,
+        start_time: startTs ? startTs - WINDOW_PADDING_MS : undefined,
+        end_time: endTs ? endTs + WINDOW_PADDING_MS : undefined,
+        size: maxLines,
+      }, signal));
+      const items = Array.isArray(logs?.items) ? logs.items : [];
+      diagnostic.delegate_logs = {
+        line_count: items.length,
+        lines: items.map(formatLogLine),
+        next_page_token: logs?.next_page_token,
+      };
+      if (items.length === 0) {
+        diagnostic.note = "No delegate-side log lines were found for these tasks in the step's time window. Delegate logs are retained for a limited time and are only available for Harness-hosted log aggregation (not self-managed installs).";
+      }
+    } catch (err) {
+      diagnostic.delegate_logs_error = err instanceof Error ? err.message : String(err);
+    }
+
+    await sendProgress(extra, 3, 3, "Delegate task correlation complete");
+    return diagnostic;
+  },
+};
@@ -2,6 +2,7 @@ import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
 import { createLogger } from "../../utils/logger.js";
 import { sendProgress } from "../../utils/progress.js";
 import { asString, isRecord } from "../../utils/type-guards.js";
+import { harnessUiBaseUrl } from "../../utils/deep-links.js";
 
 const log = createLogger("diagnose:delegate");
 
@@ -110,7 +111,7 @@ export const delegateHandler: DiagnoseHandler = {
       return {
         total_delegates: 0,
         note: "No delegates returned by the API for this account.",
-        openInHarness: `${config.HARNESS_BASE_URL.replace(/\/$/, "")}/ng/account/${registry.getAccountId()}/settings/resources/delegates`,
+        openInHarness: `${harnessUiBaseUrl(config)}/ng/account/${registry.getAccountId()}/settings/resources/delegates`,
       };
     }
 
@@ -157,7 +158,7 @@ export const delegateHandler: DiagnoseHandler = {
       }));
     }
 
-    const base = config.HARNESS_BASE_URL.replace(/\/$/, "");
+    const base = harnessUiBaseUrl(config);
     diagnostic.openInHarness = `${base}/ng/account/${registry.getAccountId()}/settings/resources/delegates`;
 
     await sendProgress(extra, 1, 1, "Delegate diagnosis complete");
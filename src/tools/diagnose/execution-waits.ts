@@ -0,0 +1,238 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:execution-waits");
+
+/** Node statuses that mean "blocked on something", not "doing work". */
+const WAITING_STATUSES = new Set([
+  "ResourceWaiting",
+  "ApprovalWaiting",
+  "InterventionWaiting",
+  "InputWaiting",
+  "AsyncWaiting",
+  "TaskWaiting",
+  "TimedWaiting",
+  "WaitStepRunning",
+  "UploadWaiting",
+  "Paused",
+  "Queued",
+  "QueuedLicenseLimitReached",
+  "QueuedExecutionConcurrencyReached",
+]);
+
+const APPROVAL_STEP_TYPES = new Set(["HarnessApproval", "JiraApproval", "ServiceNowApproval", "CustomApproval"]);
+const CONSTRAINT_STEP_TYPES = new Set(["ResourceConstraint", "Queue"]);
+const DONE_STATUSES = new Set(["Success", "Failed", "Errored", "Aborted", "Expired", "Skipped", "IgnoreFailed", "ApprovalRejected"]);
+
+type Node = Record<string, unknown>;
+type WaitKind = "resource_constraint" | "barrier" | "approval" | "execution_input" | "manual_intervention" | "wait_step" | "delegate_task" | "paused" | "queued";
+
+function isWaiting(node: Node): boolean {
+  const status = asString(node.status) ?? "";
+  // A Barrier step shows Running while it waits for the other participants
+  return WAITING_STATUSES.has(status) || (node.stepType === "Barrier" && status === "Running");
+}
+
+function classify(node: Node): WaitKind {
+  const stepType = asString(node.stepType) ?? "";
+  const status = asString(node.status) ?? "";
+  if (CONSTRAINT_STEP_TYPES.has(stepType) || status === "ResourceWaiting") return "resource_constraint";
+  if (stepType === "Barrier") return "barrier";
+  if (APPROVAL_STEP_TYPES.has(stepType) || status === "ApprovalWaiting") return "approval";
+  if (status === "InputWaiting") return "execution_input";
+  if (status === "InterventionWaiting") return "manual_intervention";
+  if (stepType === "Wait" || status === "WaitStepRunning" || status === "TimedWaiting") return "wait_step";
+  if (status === "AsyncWaiting" || status === "TaskWaiting") return "delegate_task";
+  if (status === "Paused") return "paused";
+  return "queued";
+}
+
+function stepSpec(node: Node): Record<string, unknown> {
+  const params = asRecord(node.stepParameters);
+  return asRecord(params?.spec) ?? params ?? {};
+}
+
+/** Resource unit a constraint or Queue step waits on. */
+export function constraintKey(node: Node): string | undefined {
+  const spec = stepSpec(node);
+  return asString(spec.resourceUnit) ?? asString(spec.key);
+}
+
+function describeStep(node: Node, now: number): Record<string, unknown> {
+  const startTs = asNumber(node.startTs);
+  return {
+    identifier: asString(node.identifier),
+    name: asString(node.name),
+    step_type: asString(node.stepType),
+    status: asString(node.status),
+    node_execution_id: asString(node.uuid),
+    ...(startTs ? { waiting_since: new Date(startTs).toISOString(), waiting_seconds: Math.max(0, Math.round((now - startTs) / 1000)) } : {}),
+  };
+}
+
+function errorMessage(err: unknown): string {
+  return err instanceof Error ? err.message : String(err);
+}
+
+/** Approvers and the approvals recorded so far for a waiting approval instance. */
+function describeApproval(instance: Record<string, unknown>): Record<string, unknown> {
+  const details = asRecord(instance.details) ?? {};
+  const approvers = asRecord(details.approvers);
+  const activities = (Array.isArray(details.approvalActivities) ? details.approvalActivities : []).filter(isRecord);
+  const deadline = asNumber(instance.deadline);
+  return {
+    approval_id: asString(instance.id),
+    approval_type: asString(instance.type),
+    ...(approvers ? {
+      approver_user_groups: approvers.userGroups,
+      minimum_count: asNumber(approvers.minimumCount),
+    } : {}),
+    ...(activities.length > 0 ? {
+      approvals_so_far: activities.map((a) => ({ user: asString(asRecord(a.user)?.name) ?? asString(asRecord(a.user)?.email), action: asString(a.action) })),
+    } : {}),
+    ...(asString(asRecord(details.issue)?.key) ? { ticket: asString(asRecord(details.issue)?.key) } : {}),
+    ...(deadline ? { deadline: new Date(deadline).toISOString() } : {}),
+  };
+}
+
+/** One line per wait, in the order an operator would act on them. */
+function summarize(waits: Record<string, unknown>[]): string[] {
+  return waits.map((w) => {
+    const step = asRecord(w.step);
+    const label = asString(step?.name) ?? asString(step?.identifier) ?? asString(w.stage) ?? "execution";
+    switch (w.kind) {
+      case "resource_constraint": {
+        const holders = Array.isArray(w.holders) ? w.holders.length : 0;
+        const position = asNumber(w.queue_position);
+        return `${label} is queued on resource constraint '${w.resource_unit ?? "unknown"}'${holders > 0 ? ` held by ${holders} execution(s)` : ""}${position ? `, position ${position} in the queue` : ""}.`;
+      }
+      case "barrier": {
+        const pending = Array.isArray(w.waiting_on) ? w.waiting_on.length : 0;
+        return `${label} waits at barrier '${w.barrier_ref ?? "unknown"}'${pending > 0 ? ` for ${pending} other step(s) to arrive` : ""}.`;
+      }
+      case "approval":
+        return `${label} waits for ${asString(w.approval_type) ?? "an"} approval.`;
+      case "execution_input":
+        return `${label} waits for runtime input to be provided in the Harness UI.`;
+      case "manual_intervention":
+        return `${label} failed and waits for a manual intervention (retry, ignore, abort, or rollback).`;
+      case "wait_step":
+        return `${label} is a Wait step that must be marked as success or failed, or time out.`;
+      case "delegate_task":
+        return `${label} waits for a delegate to pick up or finish its task — check delegate availability and selectors.`;
+      case "paused":
+        return `${label} is paused; resume it with harness_execute(resource_type='execution', action='interrupt', interrupt_type='Resume').`;
+      default:
+        return `${label} is queued (${asString(step?.status) ?? asString(w.status) ?? "Queued"}) — usually a pipeline concurrency or license limit.`;
+    }
+  });
+}
+
+export const executionWaitsHandler: DiagnoseHandler = {
+  entityType: "execution_waits",
+  description: "Explain what a running execution is waiting on — resource constraints and Queue steps (with the executions holding them and the queue position), barriers (with the steps not yet at the barrier), approvals (with approvers), runtime inputs, manual interventions, Wait steps, delegate tasks, and queued stages.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const executionId = asString(input.execution_id) ?? asString(args.resource_id);
+    if (!executionId) {
+      throw new Error("execution_id is required for execution_waits. Pass it in options, as resource_id, or via a Harness execution URL.");
+    }
+
+    await sendProgress(extra, 0, 3, "Fetching execution graph...");
+    const execution = asRecord(await registry.dispatch(client, "execution", "get", {
+      ...input,
+      execution_id: executionId,
+      render_full_graph: true,
+    }, signal)) ?? {};
+    const summary = asRecord(execution.pipelineExecutionSummary) ?? {};
+    const nodes = Object.values(asRecord(asRecord(execution.executionGraph)?.nodeMap) ?? {}).filter(isRecord);
+    const stages = Object.values(asRecord(summary.layoutNodeMap) ?? {}).filter(isRecord);
+    const now = Date.now();
+    const status = asString(summary.status);
+
+    const diagnostic: Record<string, unknown> = {
+      execution_id: executionId,
+      pipeline_id: asString(summary.pipelineIdentifier),
+      status,
+    };
+
+    const waits: Record<string, unknown>[] = [];
+    const waitingSteps = nodes.filter((n) => n.stepType && isWaiting(n));
+    for (const node of waitingSteps) {
+      waits.push({ kind: classify(node), step: describeStep(node, now) });
+    }
+    // Stages that have not started because they are queued show up only in the layout
+    for (const stage of stages) {
+      const stageStatus = asString(stage.status) ?? "";
+      if (!stageStatus.startsWith("Queued") && stageStatus !== "ResourceWaiting") continue;
+      if (waitingSteps.some((n) => asString(n.uuid) && n.uuid === stage.nodeExecutionId)) continue;
+      waits.push({ kind: stageStatus === "ResourceWaiting" ? "resource_constraint" : "queued", stage: asString(stage.name) ?? asString(stage.nodeIdentifier), status: stageStatus });
+    }
+    if (waits.length === 0 && status && status.startsWith("Queued")) {
+      waits.push({ kind: "queued", status });
+    }
+
+    await sendProgress(extra, 1, 3, `Resolving ${waits.length} wait(s)...`);
+    const queues = new Map<string, Promise<unknown>>();
+    const errors: string[] = [];
+
+    await Promise.all(waits.map(async (wait, i) => {
+      const node = waitingSteps[i];
+      if (!node) return;
+      try {
+        if (wait.kind === "resource_constraint") {
+          const key = constraintKey(node);
+          wait.resource_unit = key ?? null;
+          if (!key) return;
+          if (!queues.has(key)) queues.set(key, registry.dispatch(client, "constraint_queue", "get", { resource_unit: key }, signal));
+          const queue = asRecord(await queues.get(key)) ?? {};
+          const queued = (Array.isArray(queue.queue) ? queue.queue : []).filter(isRecord);
+          wait.capacity = queue.capacity;
+          wait.holders = queue.holders;
+          wait.queue_length = queued.length;
+          const self = queued.find((q) => q.planExecutionId === executionId);
+          if (self) wait.queue_position = self.position;
+        } else if (wait.kind === "barrier") {
+          const ref = asString(stepSpec(node).barrierRef);
+          wait.barrier_ref = ref ?? null;
+          if (!ref) return;
+          const participants = nodes.filter((n) => n !== node && n.stepType === "Barrier" && asString(stepSpec(n).barrierRef) === ref);
+          wait.participants = participants.map((n) => describeStep(n, now));
+          // Participants that are not yet at the barrier are what this step waits on
+          wait.waiting_on = participants
+            .filter((n) => !isWaiting(n) && asString(n.status) !== "Running" && !DONE_STATUSES.has(asString(n.status) ?? ""))
+            .map((n) => asString(n.identifier));
+        } else if (wait.kind === "approval") {
+          const result = asRecord(await registry.dispatch(client, "approval_instance", "list", {
+            ...input,
+            execution_id: executionId,
+            node_execution_id: asString(node.uuid),
+            approval_status: "WAITING",
+          }, signal));
+          const instance = (Array.isArray(result?.items) ? result!.items as unknown[] : []).find(isRecord);
+          if (instance) Object.assign(wait, describeApproval(instance));
+        }
+      } catch (err) {
+        errors.push(`${wait.kind} ${asString(node.identifier) ?? ""}: ${errorMessage(err)}`.trim());
+      }
+    }));
+
+    await sendProgress(extra, 2, 3, "Summarizing...");
+    log.info("Execution waits resolved", { executionId, waits: waits.length });
+    diagnostic.waiting = waits.length > 0;
+    diagnostic.waits = waits;
+    diagnostic.summary = waits.length > 0
+      ? summarize(waits)
+      : [status && ["Running", "AsyncWaiting"].includes(status)
+        ? "The execution is running and not blocked on a barrier, constraint, approval, or queue."
+        : `The execution is ${status ?? "in an unknown state"} and not waiting on anything.`];
+    if (errors.length > 0) diagnostic.errors = errors;
+
+    await sendProgress(extra, 3, 3, "Execution wait analysis complete");
+    return diagnostic;
+  },
+};
@@ -2,6 +2,7 @@ import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
 import { createLogger } from "../../utils/logger.js";
 import { sendProgress } from "../../utils/progress.js";
 import { isRecord, asRecord, asString } from "../../utils/type-guards.js";
+import { harnessUiBaseUrl } from "../../utils/deep-links.js";
 
 const log = createLogger("diagnose:gitops-application");
 
@@ -256,7 +257,7 @@ export const gitopsApplicationHandler: DiagnoseHandler = {
     const orgId = asString(input.org_id) ?? config.HARNESS_ORG;
     const projectId = asString(input.project_id) ?? config.HARNESS_PROJECT;
     if (orgId && projectId) {
-      const base = config.HARNESS_BASE_URL.replace(/\/$/, "");
+      const base = harnessUiBaseUrl(config);
       diagnostic.openInHarness = `${base}/ng/account/${registry.getAccountId()}/all/orgs/${orgId}/projects/${projectId}/gitops/applications/${encodeURIComponent(appName)}`;
     }
 
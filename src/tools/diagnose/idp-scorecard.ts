@@ -0,0 +1,182 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:idp-scorecard");
+
+const DEFAULT_MAX_CHECKS = 20;
+
+function records(value: unknown): Record<string, unknown>[] {
+  return (Array.isArray(value) ? value : []).filter(isRecord);
+}
+
+/** `namespace/Kind/name` reference for an entity row from scorecard or check stats. */
+function entityRef(row: Record<string, unknown>): string | undefined {
+  const name = asString(row.name);
+  if (!name) return undefined;
+  const kind = asString(row.kind) ?? "Component";
+  return `${asString(row.namespace) ?? "default"}/${kind}/${name}`;
+}
+
+function isFail(status: string | undefined): boolean {
+  return status?.toUpperCase() === "FAIL";
+}
+
+function summarizeEntityCheck(check: Record<string, unknown>): Record<string, unknown> {
+  return {
+    check_id: asString(check.identifier) ?? asString(check.check_identifier),
+    name: asString(check.check_name) ?? asString(check.name),
+    status: asString(check.status),
+    reason: asString(check.reason) ?? asString(check.failure_reason),
+    weight: asNumber(check.weightage),
+  };
+}
+
+/** Per-scorecard check results for one entity, with the reason for each failing check. */
+async function entityStatus(ctx: DiagnoseContext, entityIdentifier: string): Promise<Record<string, unknown>> {
+  const { client, registry, input, extra, signal } = ctx;
+  await sendProgress(extra, 0, 1, "Fetching entity scores...");
+  const result = asRecord(await registry.dispatch(client, "idp_score", "list", {
+    ...input,
+    entity_identifier: entityIdentifier,
+  }, signal));
+
+  const scorecardFilter = asString(input.scorecard_id);
+  const scorecards = records(result?.items)
+    .filter((s) => !scorecardFilter || asString(s.scorecard_identifier) === scorecardFilter)
+    .map((s) => {
+      const checks = records(s.checks).map(summarizeEntityCheck);
+      return {
+        scorecard_id: asString(s.scorecard_identifier),
+        scorecard: asString(s.scorecard_name),
+        score: asNumber(s.score),
+        passed: checks.filter((c) => !isFail(c.status as string | undefined)).length,
+        failed: checks.filter((c) => isFail(c.status as string | undefined)).length,
+        failing_checks: checks.filter((c) => isFail(c.status as string | undefined)),
+        passing_checks: checks.filter((c) => !isFail(c.status as string | undefined)).map((c) => c.name),
+      };
+    });
+
+  await sendProgress(extra, 1, 1, "Entity check status complete");
+  return {
+    entity_identifier: entityIdentifier,
+    overall_score: asNumber(result?.overall_score),
+    scorecards,
+    ...(scorecards.length === 0 ? { note: "No scorecards apply to this entity (or the scorecard_id filter matched none)." } : {}),
+  };
+}
+
+/** Which entities fail each check of a scorecard. */
+async function scorecardChecks(ctx: DiagnoseContext, scorecardId: string, maxChecks: number): Promise<Record<string, unknown>> {
+  const { client, registry, input, extra, signal } = ctx;
+  await sendProgress(extra, 0, maxChecks + 2, "Fetching scorecard...");
+  const detail = asRecord(await registry.dispatch(client, "scorecard", "get", { ...input, scorecard_id: scorecardId }, signal));
+  const scorecard = asRecord(detail?.scorecard) ?? detail;
+  const checks = records(detail?.checks);
+  const selected = checks.slice(0, maxChecks);
+  const total = selected.length + 2;
+
+  await sendProgress(extra, 1, total, "Fetching scorecard stats...");
+  let entities: Record<string, unknown>[] = [];
+  let statsError: string | undefined;
+  try {
+    const stats = asRecord(await registry.dispatch(client, "scorecard_stats", "get", { ...input, scorecard_id: scorecardId }, signal));
+    entities = records(stats?.stats).map((row) => ({
+      entity: entityRef(row),
+      owner: asString(row.owner),
+      system: asString(row.system),
+      score: asNumber(row.score),
+    }));
+  } catch (err) {
+    statsError = err instanceof Error ? err.message : String(err);
+  }
+
+  const checkResults: Record<string, unknown>[] = [];
+  for (const [i, check] of selected.entries()) {
+    const checkId = asString(check.identifier);
+    if (!checkId) continue;
+    const custom = check.custom === true;
+    await sendProgress(extra, i + 2, total, `Reading check ${checkId}...`);
+    const summary: Record<string, unknown> = {
+      check_id: checkId,
+      name: asString(check.name),
+      custom,
+      weight: asNumber(check.weightage),
+    };
+    try {
+      const stats = asRecord(await registry.dispatch(client, "scorecard_check_stats", "get", {
+        ...input,
+        check_id: checkId,
+        is_custom: custom,
+      }, signal));
+      const rows = records(stats?.stats);
+      const failing = rows.filter((r) => isFail(asString(r.status)));
+      summary.passed = rows.length - failing.length;
+      summary.failed = failing.length;
+      summary.failing_entities = failing.map((r) => ({ entity: entityRef(r), owner: asString(r.owner) }));
+    } catch (err) {
+      log.warn("Failed to read check stats", { checkId, error: String(err) });
+      summary.error = err instanceof Error ? err.message : String(err);
+    }
+    checkResults.push(summary);
+  }
+
+  // Checks that fail the most entities first
+  checkResults.sort((a, b) => (asNumber(b.failed) ?? 0) - (asNumber(a.failed) ?? 0));
+
+  await sendProgress(extra, total, total, "Scorecard check drill-down complete");
+  return {
+    scorecard_id: scorecardId,
+    name: asString(scorecard?.name),
+    published: scorecard?.published,
+    entities_evaluated: statsError ? undefined : entities.length,
+    ...(statsError ? { stats_error: statsError } : {}),
+    lowest_scoring: [...entities]
+      .filter((e) => typeof e.score === "number")
+      .sort((a, b) => (a.score as number) - (b.score as number))
+      .slice(0, 10),
+    checks: checkResults,
+    ...(checks.length > selected.length ? { checks_truncated: true, total_checks: checks.length } : {}),
+    _hint: "Pass entity_identifier ('namespace/Kind/name') to see why a specific entity fails each check.",
+  };
+}
+
+export const idpScorecardHandler: DiagnoseHandler = {
+  entityType: "idp_scorecard",
+  description: "Drill into IDP scorecard checks — with no scorecard_id, list scorecards; with scorecard_id, report which catalog entities fail each check; with entity_identifier, report every check's pass/fail status and failure reason for that entity.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const entityIdentifier = asString(input.entity_identifier);
+    if (entityIdentifier) {
+      log.info("Fetching entity check status", { entityIdentifier });
+      return entityStatus(ctx, entityIdentifier);
+    }
+
+    const scorecardId = asString(input.scorecard_id) ?? asString(args.resource_id);
+    if (scorecardId) {
+      const requested = asNumber(args.max_checks) ?? DEFAULT_MAX_CHECKS;
+      const maxChecks = requested > 0 ? Math.min(requested, 50) : DEFAULT_MAX_CHECKS;
+      log.info("Drilling into scorecard checks", { scorecardId, maxChecks });
+      return scorecardChecks(ctx, scorecardId, maxChecks);
+    }
+
+    await sendProgress(extra, 0, 1, "Listing scorecards...");
+    const list = asRecord(await registry.dispatch(client, "scorecard", "list", { ...input, limit: 100 }, signal));
+    const scorecards = records(list?.items).map((s) => ({
+      scorecard_id: asString(s.identifier),
+      name: asString(s.name),
+      description: asString(s.description),
+      published: s.published,
+      checks: Array.isArray(s.checks) ? s.checks.length : undefined,
+    }));
+    await sendProgress(extra, 1, 1, "Scorecard listing complete");
+    return {
+      scorecards,
+      total: scorecards.length,
+      _hint: "Pass scorecard_id to see which entities fail each check, or entity_identifier for one entity's check results.",
+    };
+  },
+};
@@ -0,0 +1,121 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:k8s-workload");
+
+interface WorkloadGroup {
+  infrastructure?: string;
+  namespace?: string;
+  release?: string;
+  connector_ref?: string;
+  pods: Array<Record<string, unknown>>;
+  artifacts: Set<string>;
+  lastDeployedAt?: number;
+  lastExecution?: string;
+}
+
+function podSummary(instance: Record<string, unknown>, info: Record<string, unknown> | undefined): Record<string, unknown> {
+  const containers = Array.isArray(info?.containerList) ? info.containerList.filter(isRecord) : [];
+  return {
+    pod: asString(instance.podName) ?? asString(info?.podName),
+    pod_ip: asString(info?.podIP),
+    artifact: asString(instance.artifactName),
+    images: containers.map((c) => asString(c.image)).filter((i): i is string => !!i),
+  };
+}
+
+/**
+ * Group instance sync records by infrastructure / namespace / release so a
+ * service deployed to several clusters or namespaces is reported per workload.
+ */
+function groupInstances(instances: Record<string, unknown>[]): WorkloadGroup[] {
+  const groups = new Map<string, WorkloadGroup>();
+  for (const instance of instances) {
+    const info = asRecord(instance.instanceInfoDTO);
+    const infraDetails = asRecord(instance.infrastructureDetails);
+    const infrastructure = asString(instance.infrastructureName) ?? asString(instance.infraIdentifier);
+    const namespace = asString(infraDetails?.namespace) ?? asString(info?.namespace);
+    const release = asString(infraDetails?.releaseName) ?? asString(info?.releaseName);
+    const key = `${infrastructure ?? ""}/${namespace ?? ""}/${release ?? ""}`;
+    let group = groups.get(key);
+    if (!group) {
+      group = { infrastructure, namespace, release, connector_ref: asString(instance.connectorRef), pods: [], artifacts: new Set() };
+      groups.set(key, group);
+    }
+    group.pods.push(podSummary(instance, info));
+    const artifact = asString(instance.artifactName);
+    if (artifact) group.artifacts.add(artifact);
+    const deployedAt = asNumber(instance.deployedAt);
+    if (deployedAt && deployedAt > (group.lastDeployedAt ?? 0)) {
+      group.lastDeployedAt = deployedAt;
+      group.lastExecution = asString(instance.pipelineExecutionName);
+    }
+  }
+  return [...groups.values()];
+}
+
+function workloadStatus(group: WorkloadGroup): { status: string; reason?: string } {
+  if (group.artifacts.size > 1) {
+    return {
+      status: "degraded",
+      reason: `Pods are running ${group.artifacts.size} different artifacts — a rollout is in progress or a previous one only partially completed.`,
+    };
+  }
+  return { status: "healthy" };
+}
+
+export const k8sWorkloadHandler: DiagnoseHandler = {
+  entityType: "k8s_workload",
+  description: "Report the running pods of a deployed Kubernetes service from Harness instance sync data — pod count, namespace, release, and artifact per workload, flagging workloads with mixed artifact versions or no running instances.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const serviceId = asString(input.service_id) ?? asString(args.resource_id);
+    const envId = asString(input.env_id) ?? asString(input.environment_id);
+    if (!serviceId || !envId) {
+      throw new Error("service_id and env_id are required for k8s_workload.");
+    }
+
+    await sendProgress(extra, 0, 1, "Fetching instance sync data...");
+    log.info("Fetching service instances", { serviceId, envId });
+    const result = asRecord(await registry.dispatch(client, "service_instance", "list", {
+      ...input,
+      service_id: serviceId,
+      env_id: envId,
+      infra_id: asString(input.infra_id),
+    }, signal));
+    const instances = (Array.isArray(result?.items) ? result.items : []).filter(isRecord);
+
+    const diagnostic: Record<string, unknown> = { service_id: serviceId, env_id: envId };
+    if (instances.length === 0) {
+      diagnostic.status = "no_instances";
+      diagnostic.workloads = [];
+      diagnostic.note = "Instance sync reports no running instances. Either nothing is deployed to this environment, the last deployment scaled to zero, or instance sync cannot reach the cluster — check the infrastructure's connector with harness_diagnose(resource_type='connector').";
+      await sendProgress(extra, 1, 1, "Workload status complete");
+      return diagnostic;
+    }
+
+    const workloads = groupInstances(instances).map((group) => ({
+      infrastructure: group.infrastructure,
+      namespace: group.namespace,
+      release: group.release,
+      connector_ref: group.connector_ref,
+      ...workloadStatus(group),
+      pod_count: group.pods.length,
+      artifacts: [...group.artifacts],
+      last_deployed_at: group.lastDeployedAt ? new Date(group.lastDeployedAt).toISOString() : undefined,
+      last_execution: group.lastExecution,
+      pods: group.pods,
+    }));
+    diagnostic.status = workloads.some((w) => w.status !== "healthy") ? "degraded" : "healthy";
+    diagnostic.total_pods = instances.length;
+    diagnostic.workloads = workloads;
+    diagnostic._note = "Instance sync lists pods Harness last observed running; it is refreshed periodically (about every 10 minutes), so very recent pod restarts or crash loops may not be reflected yet.";
+
+    await sendProgress(extra, 1, 1, "Workload status complete");
+    return diagnostic;
+  },
+};
@@ -0,0 +1,145 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asRecord, asString } from "../../utils/type-guards.js";
+import { unifiedDiff } from "../../utils/yaml-diff.js";
+
+const log = createLogger("diagnose:pipeline-diff");
+
+/** Diff text beyond this many characters is truncated to protect the agent's context window. */
+const MAX_DIFF_CHARS = 100_000;
+const DEFAULT_CONTEXT_LINES = 3;
+
+interface Revision {
+  kind: "branch" | "commit";
+  ref: string;
+}
+
+interface FetchedRevision {
+  yaml: string;
+  commitId?: string;
+  filePath?: string;
+}
+
+function resolveRevision(args: Record<string, unknown>, side: "base" | "head"): Revision | undefined {
+  const commit = asString(args[`${side}_commit`]);
+  if (commit) return { kind: "commit", ref: commit };
+  const branch = asString(args[`${side}_branch`]);
+  if (branch) return { kind: "branch", ref: branch };
+  return undefined;
+}
+
+function revisionLabel(pipelineId: string, rev: Revision): string {
+  return `${pipelineId}@${rev.kind === "commit" ? rev.ref.slice(0, 12) : rev.ref}`;
+}
+
+/** Decode a Harness Code file_content payload (base64 by default) to text. */
+function decodeFileContent(raw: unknown): string | undefined {
+  const content = asRecord(asRecord(raw)?.content);
+  const data = asString(content?.data);
+  if (data === undefined) return undefined;
+  const encoding = asString(content?.encoding) ?? "base64";
+  return encoding === "base64" ? Buffer.from(data, "base64").toString("utf-8") : data;
+}
+
+export const pipelineDiffHandler: DiagnoseHandler = {
+  entityType: "pipeline_diff",
+  description: "Compare two revisions of a remote (Git-backed) pipeline — by branch or commit — and return a unified YAML diff with addition/deletion counts.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const pipelineId = asString(args.pipeline_id) ?? asString(args.resource_id);
+    if (!pipelineId) {
+      throw new Error("pipeline_id (or resource_id) is required for pipeline_diff.");
+    }
+    const base = resolveRevision(args, "base");
+    const head = resolveRevision(args, "head");
+    if (!base || !head) {
+      throw new Error("Both revisions are required: pass base_branch or base_commit, and head_branch or head_commit.");
+    }
+    const requestedContext = Number(args.context_lines ?? DEFAULT_CONTEXT_LINES);
+    const context = Number.isFinite(requestedContext) ? requestedContext : DEFAULT_CONTEXT_LINES;
+
+    // Commit revisions are read from the repository directly, which needs the
+    // pipeline's git location. Look it up once from the default branch.
+    let gitLocation: { repoName?: string; filePath?: string; connectorRef?: string } | undefined;
+    const needsGitLocation = base.kind === "commit" || head.kind === "commit";
+    if (needsGitLocation) {
+      const raw = asRecord(await registry.dispatch(client, "pipeline", "get", { ...input, pipeline_id: pipelineId }, signal));
+      const gitDetails = asRecord(raw?.gitDetails);
+      gitLocation = {
+        repoName: asString(gitDetails?.repoName),
+        filePath: asString(gitDetails?.filePath),
+        connectorRef: asString(raw?.connectorRef) ?? asString(gitDetails?.connectorRef),
+      };
+      if (!gitLocation.repoName || !gitLocation.filePath) {
+        throw new Error(`Pipeline '${pipelineId}' is not stored in Git (no gitDetails.repoName/filePath). Commit-based diffs require a remote pipeline.`);
+      }
+      if (gitLocation.connectorRef) {
+        throw new Error(
+          `Pipeline '${pipelineId}' is stored in an external Git provider (connector '${gitLocation.connectorRef}'). ` +
+          "Commit-based diffs are only supported for Harness Code repositories — use base_branch/head_branch instead.",
+        );
+      }
+    }
+
+    const fetchRevision = async (rev: Revision): Promise<FetchedRevision> => {
+      if (rev.kind === "branch") {
+        const raw = asRecord(await registry.dispatch(client, "pipeline", "get", { ...input, pipeline_id: pipelineId, branch: rev.ref }, signal));
+        const yaml = asString(raw?.yamlPipeline);
+        if (yaml === undefined) {
+          throw new Error(`Pipeline '${pipelineId}' on branch '${rev.ref}' returned no YAML.`);
+        }
+        const gitDetails = asRecord(raw?.gitDetails);
+        return { yaml, commitId: asString(gitDetails?.commitId), filePath: asString(gitDetails?.filePath) };
+      }
+      const raw = await registry.dispatch(client, "file_content", "get", {
+        ...input,
+        repo_id: gitLocation!.repoName,
+        path: gitLocation!.filePath,
+        git_ref: rev.ref,
+      }, signal);
+      const yaml = decodeFileContent(raw);
+      if (yaml === undefined) {
+        throw new Error(`File '${gitLocation!.filePath}' at commit '${rev.ref}' returned no content.`);
+      }
+      return { yaml, commitId: rev.ref, filePath: gitLocation!.filePath };
+    };
+
+    await sendProgress(extra, 0, 2, `Fetching ${revisionLabel(pipelineId, base)}...`);
+    log.info("Fetching pipeline revisions", { pipelineId, base: base.ref, head: head.ref });
+    const baseRev = await fetchRevision(base);
+    await sendProgress(extra, 1, 2, `Fetching ${revisionLabel(pipelineId, head)}...`);
+    const headRev = await fetchRevision(head);
+
+    const result = unifiedDiff(baseRev.yaml, headRev.yaml, {
+      fromLabel: revisionLabel(pipelineId, base),
+      toLabel: revisionLabel(pipelineId, head),
+      context,
+    });
+
+    const diagnostic: Record<string, unknown> = {
+      pipeline_id: pipelineId,
+      base: { [base.kind]: base.ref, commit_id: baseRev.commitId, file_path: baseRev.filePath },
+      head: { [head.kind]: head.ref, commit_id: headRev.commitId, file_path: headRev.filePath },
+      identical: result.hunks === 0,
+      additions: result.additions,
+      deletions: result.deletions,
+      hunks: result.hunks,
+    };
+    if (result.summary) {
+      diagnostic.summary = result.summary;
+      diagnostic.note = "Fetch each revision with harness_get to compare specific sections.";
+    } else if (result.diff.length > MAX_DIFF_CHARS) {
+      diagnostic.diff = result.diff.slice(0, MAX_DIFF_CHARS);
+      diagnostic.diff_truncated = true;
+      diagnostic.note = `Diff truncated to ${MAX_DIFF_CHARS} characters. Reduce context_lines or fetch each revision with harness_get to compare specific sections.`;
+    } else if (result.hunks > 0) {
+      diagnostic.diff = result.diff;
+    }
+
+    await sendProgress(extra, 2, 2, "Pipeline diff complete");
+    return diagnostic;
+  },
+};
@@ -0,0 +1,227 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { parseTimeInput } from "../../utils/time-range.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:pipeline-health");
+
+const DAY_MS = 86_400_000;
+const DEFAULT_WINDOW_DAYS = 30;
+const PAGE_SIZE = 100;
+const DEFAULT_MAX_EXECUTIONS = 500;
+const MAX_EXECUTIONS = 2000;
+const DEFAULT_MIN_RUNS = 3;
+const DEFAULT_TOP = 10;
+
+/** Statuses that end a run. Running/queued executions are left out of the rates. */
+const TERMINAL_STATUSES = new Set(["Success", "Failed", "Aborted", "Expired", "AbortedByFreeze", "IgnoreFailed", "Errored", "ApprovalRejected"]);
+const FAILED_STATUSES = new Set(["Failed", "Expired", "Errored", "ApprovalRejected"]);
+
+function round(n: number): number {
+  return Math.round(n * 10) / 10;
+}
+
+interface PipelineStats {
+  pipeline_id: string;
+  name?: string;
+  runs: number;
+  succeeded: number;
+  failed: number;
+  aborted: number;
+  durations: number[];
+  failedStages: Map<string, number>;
+  lastFailure?: { execution_id?: string; at?: string; message?: string };
+}
+
+/** Names of the stages that failed in one execution, from its layout graph. */
+export function failedStageNames(execution: Record<string, unknown>): string[] {
+  const nodes = asRecord(execution.layoutNodeMap) ?? {};
+  return Object.values(nodes)
+    .filter(isRecord)
+    .filter((n) => FAILED_STATUSES.has(String(n.status)) && n.nodeType !== "parallel")
+    .map((n) => asString(n.name) ?? asString(n.nodeIdentifier) ?? "unknown");
+}
+
+/** Fold execution summaries into per-pipeline counts, durations, and failed stages. */
+export function aggregateExecutions(executions: Record<string, unknown>[]): Map<string, PipelineStats> {
+  const byPipeline = new Map<string, PipelineStats>();
+  for (const exec of executions) {
+    const status = asString(exec.status);
+    if (!status || !TERMINAL_STATUSES.has(status)) continue;
+    const pipelineId = asString(exec.pipelineIdentifier) ?? "unknown";
+    let stats = byPipeline.get(pipelineId);
+    if (!stats) {
+      stats = { pipeline_id: pipelineId, name: asString(exec.name), runs: 0, succeeded: 0, failed: 0, aborted: 0, durations: [], failedStages: new Map() };
+      byPipeline.set(pipelineId, stats);
+    }
+    stats.runs++;
+    if (status === "Success" || status === "IgnoreFailed") stats.succeeded++;
+    else if (FAILED_STATUSES.has(status)) {
+      stats.failed++;
+      for (const stage of failedStageNames(exec)) stats.failedStages.set(stage, (stats.failedStages.get(stage) ?? 0) + 1);
+      // Executions are listed newest first — keep the first failure seen
+      if (!stats.lastFailure) {
+        const startTs = asNumber(exec.startTs);
+        stats.lastFailure = {
+          execution_id: asString(exec.planExecutionId),
+          at: startTs ? new Date(startTs).toISOString() : undefined,
+          message: asString(asRecord(exec.failureInfo)?.message),
+        };
+      }
+    } else stats.aborted++;
+    const start = asNumber(exec.startTs);
+    const end = asNumber(exec.endTs);
+    if (start && end && end >= start) stats.durations.push(end - start);
+  }
+  return byPipeline;
+}
+
+function summarize(stats: PipelineStats): Record<string, unknown> {
+  const completed = stats.succeeded + stats.failed;
+  const mean = stats.durations.length > 0 ? stats.durations.reduce((a, b) => a + b, 0) / stats.durations.length : undefined;
+  return {
+    pipeline_id: stats.pipeline_id,
+    ...(stats.name ? { name: stats.name } : {}),
+    runs: stats.runs,
+    succeeded: stats.succeeded,
+    failed: stats.failed,
+    aborted: stats.aborted,
+    // Aborts are usually deliberate, so they count toward runs but not the rate
+    success_rate_percent: completed > 0 ? round((stats.succeeded / completed) * 100) : undefined,
+    mean_duration_seconds: mean !== undefined ? round(mean / 1000) : undefined,
+    failures_by_stage: [...stats.failedStages.entries()]
+      .sort((a, b) => b[1] - a[1])
+      .map(([stage, failures]) => ({ stage, failures })),
+    ...(stats.lastFailure ? { last_failure: stats.lastFailure } : {}),
+  };
+}
+
+/**
+ * Pipeline health from the pipeline dashboard API — server-side totals over
+ * the whole window, plus the change against the previous window of equal length.
+ */
+async function fetchDashboardHealth(
+  ctx: DiagnoseContext,
+  pipelineId: string,
+  window: { startTime: number; endTime: number },
+  module: string,
+): Promise<Record<string, unknown>> {
+  const { client, config, input, signal } = ctx;
+  const response = await client.request<Record<string, unknown>>({
+    method: "GET",
+    path: "/pipeline/api/dashboard/pipelineHealth",
+    params: {
+      orgIdentifier: asString(input.org_id) ?? config.HARNESS_ORG,
+      projectIdentifier: asString(input.project_id) ?? config.HARNESS_PROJECT,
+      pipelineIdentifier: pipelineId,
+      moduleInfo: module,
+      startTime: window.startTime,
+      endTime: window.endTime,
+    },
+    signal,
+  });
+  const executions = asRecord(asRecord(asRecord(response)?.data)?.executions) ?? {};
+  const total = asRecord(executions.total);
+  const success = asRecord(executions.success);
+  const mean = asRecord(executions.meanInfo);
+  const median = asRecord(executions.medianInfo);
+  const seconds = (ms: number | undefined) => (ms !== undefined ? round(ms / 1000) : undefined);
+  return {
+    runs: asNumber(total?.count),
+    runs_change_percent: asNumber(total?.rate),
+    success_rate_percent: asNumber(success?.percent),
+    success_rate_change_percent: asNumber(success?.rate),
+    mean_duration_seconds: seconds(asNumber(mean?.duration)),
+    mean_duration_change_percent: asNumber(mean?.rate),
+    median_duration_seconds: seconds(asNumber(median?.duration)),
+  };
+}
+
+export const pipelineHealthHandler: DiagnoseHandler = {
+  entityType: "pipeline_health",
+  description: "Pipeline health over a time window — success rate, mean duration, and failures by stage. With pipeline_id, reports that pipeline using the pipeline dashboard API (with change vs. the previous window). Without it, ranks the project's pipelines by failure rate to answer 'which pipelines are flakiest'.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const endTime = parseTimeInput(args.end_time, { edge: "end" }) ?? Date.now();
+    const startTime = parseTimeInput(args.start_time) ?? endTime - DEFAULT_WINDOW_DAYS * DAY_MS;
+    if (startTime >= endTime) throw new Error("start_time must be before end_time.");
+    const pipelineId = asString(input.pipeline_id) ?? asString(input.resource_id);
+    const module = asString(args.module);
+    const requested = asNumber(args.max_executions) ?? DEFAULT_MAX_EXECUTIONS;
+    const maxExecutions = requested > 0 ? Math.min(requested, MAX_EXECUTIONS) : DEFAULT_MAX_EXECUTIONS;
+    const minRuns = asNumber(args.min_runs) ?? DEFAULT_MIN_RUNS;
+    const top = asNumber(args.top) ?? DEFAULT_TOP;
+
+    const totalSteps = pipelineId ? 2 : 1;
+    await sendProgress(extra, 0, totalSteps, "Listing executions...");
+    log.info("Computing pipeline health", { pipelineId, startTime, endTime, maxExecutions });
+
+    const executions: Record<string, unknown>[] = [];
+    let total = 0;
+    for (let page = 0; executions.length < maxExecutions; page++) {
+      const result = asRecord(await registry.dispatch(client, "execution", "list", {
+        ...input,
+        ...(pipelineId ? { pipeline_id: pipelineId } : {}),
+        ...(module ? { module } : {}),
+        start_time: startTime,
+        end_time: endTime,
+        sort: "startTs,DESC",
+        page,
+        size: PAGE_SIZE,
+      }, signal)) ?? {};
+      const items = (Array.isArray(result.items) ? result.items : []).filter(isRecord);
+      total = asNumber(result.total) ?? total;
+      executions.push(...items);
+      if (items.length < PAGE_SIZE || executions.length >= total) break;
+    }
+    const sampled = executions.slice(0, maxExecutions);
+    const truncated = total > sampled.length;
+
+    const window = { start: new Date(startTime).toISOString(), end: new Date(endTime).toISOString() };
+    const pipelines = [...aggregateExecutions(sampled).values()].map(summarize);
+    const sampleNote = truncated
+      ? { truncated: true, note: `Computed from the newest ${sampled.length} of ${total} executions in the window. Raise max_executions (max ${MAX_EXECUTIONS}) or narrow the window.` }
+      : {};
+
+    if (pipelineId) {
+      const computed = pipelines.find((p) => p.pipeline_id === pipelineId)
+        ?? { pipeline_id: pipelineId, runs: 0, failures_by_stage: [] };
+      const diagnostic: Record<string, unknown> = { window, pipeline: computed, executions_sampled: sampled.length, ...sampleNote };
+
+      await sendProgress(extra, 1, totalSteps, "Fetching dashboard health...");
+      const firstModule = sampled.map((e) => (Array.isArray(e.modules) ? asString(e.modules[0]) : undefined)).find(Boolean);
+      try {
+        diagnostic.dashboard = await fetchDashboardHealth(ctx, pipelineId, { startTime, endTime }, (module ?? firstModule ?? "CD").toUpperCase());
+      } catch (err) {
+        diagnostic.dashboard_error = err instanceof Error ? err.message : String(err);
+      }
+      await sendProgress(extra, totalSteps, totalSteps, "Pipeline health complete");
+      return diagnostic;
+    }
+
+    const ranked = pipelines
+      .filter((p) => (p.runs as number) >= minRuns && p.success_rate_percent !== undefined)
+      .sort((a, b) => (a.success_rate_percent as number) - (b.success_rate_percent as number) || (b.failed as number) - (a.failed as number));
+    const counted = pipelines.reduce(
+      (acc, p) => ({ succeeded: acc.succeeded + (p.succeeded as number), failed: acc.failed + (p.failed as number) }),
+      { succeeded: 0, failed: 0 },
+    );
+
+    await sendProgress(extra, 1, 1, "Pipeline health complete");
+    return {
+      window,
+      executions_sampled: sampled.length,
+      ...sampleNote,
+      overall_success_rate_percent: counted.succeeded + counted.failed > 0
+        ? round((counted.succeeded / (counted.succeeded + counted.failed)) * 100)
+        : undefined,
+      pipelines_seen: pipelines.length,
+      min_runs: minRuns,
+      flakiest: ranked.filter((p) => (p.failed as number) > 0).slice(0, top),
+      healthiest: ranked.filter((p) => p.failed === 0).slice(0, top).map((p) => p.pipeline_id),
+    };
+  },
+};
@@ -5,7 +5,9 @@ import { createLogger } from "../../utils/logger.js";
 import { sendProgress } from "../../utils/progress.js";
 import { isRecord, asRecord, asString, asNumber } from "../../utils/type-guards.js";
 import { resolveLogContent, resolveLogDownloadUrl } from "../../utils/log-resolver.js";
+import { fullLogPointer, summarizeLog } from "../../utils/log-summary.js";
 import { TERMINAL_STATUSES } from "../../utils/poll-execution.js";
+import { harnessUiBaseUrl } from "../../utils/deep-links.js";
 
 const log = createLogger("diagnose:pipeline");
 const NON_TERMINAL_EXECUTION_ERROR_PREFIX = "Cannot diagnose execution with status";
@@ -422,7 +424,7 @@ function buildExecutionSummary(
   const pipelineIdentifier = asString(pes.pipelineIdentifier);
   const execId = asString(pes.planExecutionId);
   if (pipelineIdentifier && execId && orgId && projectId) {
-    const base = config.HARNESS_BASE_URL.replace(/\/$/, "");
+    const base = harnessUiBaseUrl(config);
     summary.openInHarness = `${base}/ng/account/${accountId}/all/orgs/${orgId}/projects/${projectId}/pipelines/${pipelineIdentifier}/deployments/${execId}/pipeline`;
   } else if (execution.openInHarness) {
     summary.openInHarness = execution.openInHarness;
@@ -458,6 +460,7 @@ async function resolveDiagnoseLog(
     signal?: AbortSignal;
     returnDownloadUrl?: boolean;
     logSnippetLines: number;
+    summarizeLogs?: boolean;
   },
 ): Promise<unknown> {
   if (options.returnDownloadUrl) {
@@ -465,6 +468,7 @@ async function resolveDiagnoseLog(
     return { download_url: downloadUrl };
   }
   const logText = await resolveLogContent(client, prefix, { signal: options.signal });
+  if (options.summarizeLogs) return { ...summarizeLog(logText), full_log: fullLogPointer(prefix) };
   return truncateLog(logText, options.logSnippetLines);
 }
 
@@ -485,6 +489,7 @@ export const pipelineHandler: DiagnoseHandler = {
     const includeLogs = args.include_logs ?? !isSummary;
     const returnDownloadUrl = args.return_download_url === true;
     const logSnippetLines = asNumber(args.log_snippet_lines) ?? 120;
+    const summarizeLogs = args.summarize_logs === true;
     const maxFailedSteps = asNumber(args.max_failed_steps) ?? 5;
     const maxAllStepLogs = asNumber(args.max_all_step_logs) ?? 25;
 
@@ -653,6 +658,7 @@ export const pipelineHandler: DiagnoseHandler = {
                 signal,
                 returnDownloadUrl,
                 logSnippetLines,
+                summarizeLogs,
               });
               return { key, value: logValue };
             } catch (err) {
@@ -691,6 +697,7 @@ export const pipelineHandler: DiagnoseHandler = {
             signal,
             returnDownloadUrl,
             logSnippetLines,
+            summarizeLogs,
           });
           diagnostic.requested_step_log = {
             step_id: requestedStepId,
@@ -763,6 +770,7 @@ export const pipelineHandler: DiagnoseHandler = {
             signal,
             returnDownloadUrl,
             logSnippetLines,
+            summarizeLogs,
           });
           diagnostic.requested_step_log = {
             step_id: bestNodeId,
@@ -821,6 +829,7 @@ export const pipelineHandler: DiagnoseHandler = {
                 signal,
                 returnDownloadUrl,
                 logSnippetLines,
+                summarizeLogs,
               });
               return { nodeId, logValue };
             } catch (err) {
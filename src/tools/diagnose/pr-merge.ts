@@ -0,0 +1,205 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:pr-merge");
+
+const PASSING_CHECK_STATES = new Set(["success", "failure_ignored"]);
+const PENDING_CHECK_STATES = new Set(["pending", "running"]);
+
+function records(value: unknown): Record<string, unknown>[] {
+  return (Array.isArray(value) ? value : []).filter(isRecord);
+}
+
+function stringList(value: unknown): string[] {
+  return (Array.isArray(value) ? value : []).filter((v): v is string => typeof v === "string");
+}
+
+/** Match a branch name against a protection-rule glob (`*` within a segment, `**` across segments). */
+export function branchGlobMatch(pattern: string, branch: string): boolean {
+  const re = pattern
+    .split("**")
+    .map((part) => part.split("*").map((s) => s.replace(/[.+?^${}()|[\]\\]/g, "\\$&")).join("[^/]*"))
+    .join(".*");
+  return new RegExp(`^${re}$`).test(branch);
+}
+
+/** Whether a branch rule's pattern covers the target branch. */
+export function ruleAppliesTo(rule: Record<string, unknown>, branch: string, defaultBranch: string | undefined): boolean {
+  const pattern = asRecord(rule.pattern);
+  if (!pattern) return true;
+  const include = stringList(pattern.include);
+  const exclude = stringList(pattern.exclude);
+  if (exclude.some((p) => branchGlobMatch(p, branch))) return false;
+  const matchesDefault = pattern.default === true && defaultBranch !== undefined && branch === defaultBranch;
+  if (pattern.default !== true && include.length === 0) return true;
+  return matchesDefault || include.some((p) => branchGlobMatch(p, branch));
+}
+
+function summarizeRule(rule: Record<string, unknown>): Record<string, unknown> {
+  const pullreq = asRecord(asRecord(rule.definition)?.pullreq);
+  const approvals = asRecord(pullreq?.approvals);
+  const comments = asRecord(pullreq?.comments);
+  const merge = asRecord(pullreq?.merge);
+  return {
+    identifier: asString(rule.identifier),
+    state: asString(rule.state),
+    inherited_from: asString(rule.space_path) ?? asString(asRecord(rule.scope)?.space_path),
+    pattern: asRecord(rule.pattern),
+    requirements: {
+      minimum_approvals: asNumber(approvals?.require_minimum_count),
+      code_owner_approval: approvals?.require_code_owners === true ? true : undefined,
+      latest_commit_approval: approvals?.require_latest_commit === true ? true : undefined,
+      no_change_requests: approvals?.require_no_change_request === true ? true : undefined,
+      comments_resolved: comments?.require_resolve_all === true ? true : undefined,
+      required_checks: stringList(asRecord(pullreq?.status_checks)?.require_identifiers),
+      merge_strategies: stringList(merge?.strategies_allowed),
+    },
+  };
+}
+
+function summarizeCheck(entry: Record<string, unknown>): Record<string, unknown> {
+  const check = asRecord(entry.check) ?? entry;
+  return {
+    identifier: asString(check.identifier) ?? asString(check.uid),
+    status: asString(check.status),
+    required: entry.required === true,
+    bypassable: entry.bypassable === true ? true : undefined,
+    summary: asString(check.summary),
+    link: asString(check.link),
+  };
+}
+
+export const prMergeHandler: DiagnoseHandler = {
+  entityType: "pr_merge",
+  description: "Explain what blocks a Harness Code pull request from merging — dry-runs the merge to collect rule violations and conflicts, reports required status checks that are failing, pending, or not yet reported, and lists the branch protection rules that apply to the target branch with their requirements.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const repoId = asString(input.repo_id);
+    const prNumber = asString(input.pr_number) ?? asNumber(input.pr_number)?.toString() ?? asString(args.resource_id);
+    if (!repoId || !prNumber) {
+      throw new Error("repo_id and pr_number are required for pr_merge. Provide them in options or via a Harness Code pull request URL.");
+    }
+    const scoped = { ...input, repo_id: repoId, pr_number: prNumber };
+
+    await sendProgress(extra, 0, 4, "Fetching pull request...");
+    log.info("Checking merge readiness", { repoId, prNumber });
+    const pr = asRecord(await registry.dispatch(client, "pull_request", "get", scoped, signal)) ?? {};
+    const targetBranch = asString(pr.target_branch);
+    const blockers: string[] = [];
+    if (asString(pr.state) && asString(pr.state) !== "open") {
+      blockers.push(`Pull request is ${asString(pr.state)}, not open.`);
+    }
+    if (pr.is_draft === true) blockers.push("Pull request is a draft — mark it ready for review first.");
+
+    const diagnostic: Record<string, unknown> = {
+      pull_request: {
+        number: asNumber(pr.number),
+        title: asString(pr.title),
+        state: asString(pr.state),
+        is_draft: pr.is_draft,
+        source_branch: asString(pr.source_branch),
+        target_branch: targetBranch,
+        merge_check_status: asString(pr.merge_check_status),
+      },
+    };
+
+    // Dry-run merge: the server's own verdict on rule violations and conflicts
+    await sendProgress(extra, 1, 4, "Dry-running merge...");
+    try {
+      const dryRun = asRecord(await registry.dispatchExecute(client, "pull_request", "merge_check", scoped, signal)) ?? {};
+      const conflicts = stringList(dryRun.conflict_files);
+      if (conflicts.length > 0) blockers.push(`Merge conflicts in ${conflicts.length} file(s): ${conflicts.slice(0, 10).join(", ")}.`);
+      const violations = records(dryRun.rule_violations).map((v) => ({
+        rule: asString(asRecord(v.rule)?.identifier),
+        rule_state: asString(asRecord(v.rule)?.state),
+        bypassable: v.bypassable === true,
+        bypassed: v.bypassed === true,
+        violations: records(v.violations).map((x) => ({ code: asString(x.code), message: asString(x.message) })),
+      }));
+      for (const v of violations) {
+        if (v.bypassed || v.rule_state === "monitor") continue;
+        for (const x of v.violations) {
+          blockers.push(`Rule '${v.rule ?? "unknown"}': ${x.message ?? x.code ?? "violation"}${v.bypassable ? " (bypassable)" : ""}`);
+        }
+      }
+      diagnostic.dry_run = {
+        mergeable: dryRun.mergeable,
+        allowed_methods: stringList(dryRun.allowed_methods),
+        minimum_required_approvals: asNumber(dryRun.minimum_required_approvals_count),
+        requires_code_owners_approval: dryRun.requires_code_owners_approval,
+        requires_comment_resolution: dryRun.requires_comment_resolution,
+        requires_no_change_requests: dryRun.requires_no_change_requests,
+        conflict_files: conflicts,
+        rule_violations: violations,
+      };
+    } catch (err) {
+      diagnostic.dry_run_error = err instanceof Error ? err.message : String(err);
+    }
+
+    // Branch protection rules that apply to the target branch
+    await sendProgress(extra, 2, 4, "Reading branch protection rules...");
+    let requiredByRules: string[] = [];
+    try {
+      let defaultBranch: string | undefined;
+      try {
+        defaultBranch = asString(asRecord(await registry.dispatch(client, "repository", "get", scoped, signal))?.default_branch);
+      } catch {
+        // Rules with a `default` pattern are reported as applying when the default branch is unknown
+      }
+      const ruleList = await registry.dispatch(client, "repo_rule", "list", { ...scoped, type: "branch", inherited: true, limit: 100 }, signal);
+      const rules = records(Array.isArray(ruleList) ? ruleList : asRecord(ruleList)?.items)
+        .filter((r) => asString(r.state) !== "disabled")
+        .filter((r) => !targetBranch || ruleAppliesTo(r, targetBranch, defaultBranch ?? targetBranch))
+        .map(summarizeRule);
+      requiredByRules = [...new Set(rules
+        .filter((r) => r.state === "active")
+        .flatMap((r) => (asRecord(r.requirements)?.required_checks as string[] | undefined) ?? []))];
+      diagnostic.rules = rules;
+    } catch (err) {
+      diagnostic.rules_error = err instanceof Error ? err.message : String(err);
+    }
+
+    // Status checks on the PR's head commit
+    await sendProgress(extra, 3, 4, "Reading status checks...");
+    try {
+      const raw = await registry.dispatch(client, "pr_check", "list", scoped, signal);
+      const checks = records(Array.isArray(raw) ? raw : asRecord(raw)?.checks).map(summarizeCheck);
+      for (const check of checks) {
+        if (requiredByRules.includes(check.identifier as string)) check.required = true;
+      }
+      const required = checks.filter((c) => c.required === true);
+      const failing = required.filter((c) => !PASSING_CHECK_STATES.has(c.status as string) && !PENDING_CHECK_STATES.has(c.status as string));
+      const pending = required.filter((c) => PENDING_CHECK_STATES.has(c.status as string));
+      const missing = requiredByRules.filter((id) => !checks.some((c) => c.identifier === id));
+      for (const c of failing) blockers.push(`Required check '${c.identifier}' is ${c.status}${c.summary ? `: ${c.summary}` : ""}.`);
+      for (const c of pending) blockers.push(`Required check '${c.identifier}' is still ${c.status}.`);
+      for (const id of missing) blockers.push(`Required check '${id}' has not reported on the latest commit.`);
+      diagnostic.checks = {
+        commit_sha: asString(asRecord(raw)?.commit_sha),
+        required_failing: failing.map((c) => c.identifier),
+        required_pending: pending.map((c) => c.identifier),
+        required_missing: missing,
+        optional_failing: checks
+          .filter((c) => c.required !== true && !PASSING_CHECK_STATES.has(c.status as string) && !PENDING_CHECK_STATES.has(c.status as string))
+          .map((c) => c.identifier),
+        items: checks,
+      };
+    } catch (err) {
+      diagnostic.checks_error = err instanceof Error ? err.message : String(err);
+    }
+
+    // Dedupe: a required-check failure often also appears as a rule violation
+    const uniqueBlockers = [...new Set(blockers)];
+    diagnostic.blockers = uniqueBlockers;
+    diagnostic.can_merge = uniqueBlockers.length === 0
+      && !diagnostic.dry_run_error
+      && asRecord(diagnostic.dry_run)?.mergeable !== false;
+
+    await sendProgress(extra, 4, 4, "Merge readiness check complete");
+    return diagnostic;
+  },
+};
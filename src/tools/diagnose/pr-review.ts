@@ -0,0 +1,208 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:pr-review");
+
+const DEFAULT_CHUNK_CHARS = 30_000;
+const MIN_CHUNK_CHARS = 2_000;
+const MAX_DESCRIPTION_CHARS = 4_000;
+
+/** Lock files and build output — listed with stats but their diffs are skipped unless include_generated is set. */
+const GENERATED_PATTERNS = [
+  /(^|\/)(package-lock\.json|pnpm-lock\.yaml|yarn\.lock|go\.sum|Cargo\.lock|poetry\.lock|Gemfile\.lock|composer\.lock)$/,
+  /\.min\.(js|css)$/,
+  /(^|\/)(dist|build|vendor|node_modules)\//,
+  /\.snap$/,
+];
+
+export interface DiffPiece {
+  path: string;
+  text: string;
+}
+
+export interface DiffChunk {
+  index: number;
+  files: string[];
+  chars: number;
+  pieces: DiffPiece[];
+}
+
+/** Split one text block into slices of at most `budget` characters on line boundaries. */
+function sliceLines(text: string, budget: number): string[] {
+  const slices: string[] = [];
+  let current = "";
+  for (const line of text.split("\n")) {
+    const next = current ? `${current}\n${line}` : line;
+    if (next.length > budget && current) {
+      slices.push(current);
+      current = line.length > budget ? line.slice(0, budget) : line;
+    } else {
+      current = next.length > budget ? next.slice(0, budget) : next;
+    }
+  }
+  if (current) slices.push(current);
+  return slices;
+}
+
+/**
+ * Break a file's patch into pieces that each fit the budget. Splits happen at
+ * hunk (`@@`) boundaries and every piece repeats the file header so it reads
+ * as a standalone diff; a single oversized hunk is split on line boundaries.
+ */
+function splitPatch(path: string, patch: string, budget: number): DiffPiece[] {
+  if (patch.length <= budget) return [{ path, text: patch }];
+  const lines = patch.split("\n");
+  const firstHunk = lines.findIndex((l) => l.startsWith("@@"));
+  const header = firstHunk > 0 ? lines.slice(0, firstHunk).join("\n") : "";
+  const body = firstHunk >= 0 ? lines.slice(firstHunk) : lines;
+
+  const hunks: string[] = [];
+  for (const line of body) {
+    if (line.startsWith("@@") || hunks.length === 0) hunks.push(line);
+    else hunks[hunks.length - 1] += `\n${line}`;
+  }
+
+  const room = Math.max(budget - header.length - 1, MIN_CHUNK_CHARS / 2);
+  const pieces: DiffPiece[] = [];
+  let current = "";
+  const flush = () => {
+    if (current) pieces.push({ path, text: header ? `${header}\n${current}` : current });
+    current = "";
+  };
+  for (const hunk of hunks) {
+    if (hunk.length > room) {
+      flush();
+      for (const slice of sliceLines(hunk, room)) pieces.push({ path, text: header ? `${header}\n${slice}` : slice });
+      continue;
+    }
+    if (current && current.length + hunk.length + 1 > room) flush();
+    current = current ? `${current}\n${hunk}` : hunk;
+  }
+  flush();
+  return pieces;
+}
+
+/** Greedily pack per-file diff pieces into chunks of at most `budget` characters, keeping file order. */
+export function chunkDiffs(files: Array<{ path: string; patch: string }>, budget: number): DiffChunk[] {
+  const chunks: DiffChunk[] = [];
+  let current: DiffChunk | undefined;
+  for (const file of files) {
+    for (const piece of splitPatch(file.path, file.patch, budget)) {
+      if (!current || (current.chars > 0 && current.chars + piece.text.length + 1 > budget)) {
+        current = { index: chunks.length, files: [], chars: 0, pieces: [] };
+        chunks.push(current);
+      }
+      current.pieces.push(piece);
+      current.chars += piece.text.length + (current.pieces.length > 1 ? 1 : 0);
+      if (!current.files.includes(piece.path)) current.files.push(piece.path);
+    }
+  }
+  return chunks;
+}
+
+function withDiffHeader(file: Record<string, unknown>, patch: string): string {
+  if (patch.startsWith("diff --git")) return patch;
+  const path = asString(file.path) ?? "";
+  return `diff --git a/${asString(file.old_path) ?? path} b/${path}\n${patch}`;
+}
+
+function summarizePullRequest(pr: Record<string, unknown>): Record<string, unknown> {
+  const description = asString(pr.description);
+  return {
+    number: asNumber(pr.number),
+    title: asString(pr.title),
+    description: description && description.length > MAX_DESCRIPTION_CHARS
+      ? `${description.slice(0, MAX_DESCRIPTION_CHARS)}\n…(truncated)`
+      : description,
+    state: asString(pr.state),
+    is_draft: pr.is_draft,
+    author: asString(asRecord(pr.author)?.display_name) ?? asString(asRecord(pr.author)?.email),
+    source_branch: asString(pr.source_branch),
+    target_branch: asString(pr.target_branch),
+    source_sha: asString(pr.source_sha),
+    merge_base_sha: asString(pr.merge_base_sha),
+    merge_check_status: asString(pr.merge_check_status),
+    stats: asRecord(pr.stats),
+  };
+}
+
+export const prReviewHandler: DiagnoseHandler = {
+  entityType: "pr_review",
+  description: "Gather a Harness Code pull request for review — metadata, the changed-file list with stats, and unified diffs split into chunks under a character budget. Returns one chunk per call (chunk, default 0) with an index of every chunk so large PRs can be reviewed piece by piece.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const repoId = asString(input.repo_id);
+    const prNumber = asString(input.pr_number) ?? asNumber(input.pr_number)?.toString() ?? asString(args.resource_id);
+    if (!repoId || !prNumber) {
+      throw new Error("repo_id and pr_number are required for pr_review. Provide them in options or via a Harness Code pull request URL.");
+    }
+    const requestedBudget = asNumber(args.max_chunk_chars) ?? DEFAULT_CHUNK_CHARS;
+    const budget = Math.min(Math.max(requestedBudget, MIN_CHUNK_CHARS), 200_000);
+    const includeGenerated = args.include_generated === true;
+
+    await sendProgress(extra, 0, 2, "Fetching pull request...");
+    log.info("Preparing pull request review", { repoId, prNumber, budget });
+    const scoped = { ...input, repo_id: repoId, pr_number: prNumber };
+    const pr = asRecord(await registry.dispatch(client, "pull_request", "get", scoped, signal)) ?? {};
+
+    await sendProgress(extra, 1, 2, "Fetching diffs...");
+    const list = asRecord(await registry.dispatch(client, "pr_file", "list", { ...scoped, include_patch: true }, signal));
+    const rawFiles = (Array.isArray(list?.items) ? list.items : []).filter(isRecord);
+
+    const files: Record<string, unknown>[] = [];
+    const reviewable: Array<{ path: string; patch: string }> = [];
+    for (const file of rawFiles) {
+      const path = asString(file.path) ?? asString(file.old_path) ?? "";
+      const entry: Record<string, unknown> = {
+        path,
+        ...(file.old_path && file.old_path !== path ? { old_path: file.old_path } : {}),
+        status: asString(file.status),
+        additions: asNumber(file.additions),
+        deletions: asNumber(file.deletions),
+      };
+      const patch = asString(file.patch);
+      if (file.is_binary === true) entry.skipped = "binary";
+      else if (!includeGenerated && GENERATED_PATTERNS.some((re) => re.test(path))) entry.skipped = "generated";
+      else if (!patch) entry.skipped = "no_diff";
+      else reviewable.push({ path, patch: withDiffHeader(file, patch) });
+      files.push(entry);
+    }
+
+    const chunks = chunkDiffs(reviewable, budget);
+    for (const entry of files) {
+      const inChunks = chunks.filter((c) => c.files.includes(entry.path as string)).map((c) => c.index);
+      if (inChunks.length > 0) entry.chunks = inChunks;
+    }
+
+    const requestedChunk = asNumber(args.chunk) ?? 0;
+    const current = chunks[requestedChunk];
+    if (chunks.length > 0 && !current) {
+      throw new Error(`chunk ${requestedChunk} is out of range — this pull request has ${chunks.length} chunk(s) (0-${chunks.length - 1}).`);
+    }
+
+    await sendProgress(extra, 2, 2, "Pull request review data ready");
+    return {
+      repo_id: repoId,
+      pull_request: summarizePullRequest(pr),
+      totals: {
+        files: files.length,
+        additions: files.reduce((n, f) => n + (asNumber(f.additions) ?? 0), 0),
+        deletions: files.reduce((n, f) => n + (asNumber(f.deletions) ?? 0), 0),
+        skipped: files.filter((f) => f.skipped).length,
+      },
+      files,
+      chunk_count: chunks.length,
+      chunks: chunks.map((c) => ({ index: c.index, files: c.files, chars: c.chars })),
+      ...(current
+        ? { chunk: { index: current.index, files: current.files, diff: current.pieces.map((p) => p.text).join("\n") } }
+        : {}),
+      ...(chunks.length > requestedChunk + 1
+        ? { _hint: `The diff spans ${chunks.length} chunks — call again with options.chunk=${requestedChunk + 1} for the next one.` }
+        : {}),
+    };
+  },
+};
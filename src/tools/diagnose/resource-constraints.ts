@@ -0,0 +1,134 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+import { constraintKey } from "./execution-waits.js";
+
+const log = createLogger("diagnose:resource-constraints");
+
+const DEFAULT_MAX_EXECUTIONS = 50;
+const MAX_EXECUTIONS = 200;
+const BATCH_SIZE = 5;
+/** Executions that can hold or wait on a constraint — finished ones have released it. */
+const ACTIVE_STATUSES = ["Running", "Queued", "Waiting", "ResourceWaiting", "Paused"];
+const CONSTRAINT_STEP_TYPES = new Set(["ResourceConstraint", "Queue"]);
+const SCOPES = ["project", "org", "account"] as const;
+
+interface ConstraintStep {
+  execution_id: string;
+  pipeline_id?: string;
+  org_id?: string;
+  project_id?: string;
+  step: string | undefined;
+  step_type: string | undefined;
+  status: string | undefined;
+}
+
+function errorMessage(err: unknown): string {
+  return err instanceof Error ? err.message : String(err);
+}
+
+/** Constraint and Queue steps of one execution graph, keyed by resource unit. */
+export function constraintSteps(execution: Record<string, unknown>): Array<{ key: string; node: Record<string, unknown> }> {
+  const nodes = Object.values(asRecord(asRecord(execution.executionGraph)?.nodeMap) ?? {}).filter(isRecord);
+  return nodes.flatMap((node) => {
+    if (!CONSTRAINT_STEP_TYPES.has(asString(node.stepType) ?? "")) return [];
+    const key = constraintKey(node);
+    return key ? [{ key, node }] : [];
+  });
+}
+
+export const resourceConstraintsHandler: DiagnoseHandler = {
+  entityType: "resource_constraints",
+  description: "List the resource constraints (deployment concurrency on an infrastructure, Queue step keys) that active executions hold or queue on, with capacity, the holding executions, and the queue for each key. With resource_unit, returns that one constraint's queue.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const resourceUnit = asString(input.resource_unit) ?? asString(args.resource_id);
+    if (resourceUnit) {
+      const queue = asRecord(await registry.dispatch(client, "constraint_queue", "get", { resource_unit: resourceUnit }, signal)) ?? {};
+      return { constraints: [queue] };
+    }
+
+    const scope = asString(input.scope) ?? "project";
+    if (!(SCOPES as readonly string[]).includes(scope)) {
+      throw new Error(`Invalid scope '${scope}'. Use one of: ${SCOPES.join(", ")}.`);
+    }
+    const requested = asNumber(args.max_executions) ?? DEFAULT_MAX_EXECUTIONS;
+    const maxExecutions = requested > 0 ? Math.min(requested, MAX_EXECUTIONS) : DEFAULT_MAX_EXECUTIONS;
+    const pipelineId = asString(input.pipeline_id);
+
+    await sendProgress(extra, 0, 3, "Listing active executions...");
+    const listed = asRecord(await registry.dispatch(client, "execution", "list", {
+      ...input,
+      ...(scope !== "project" ? { resource_scope: scope } : {}),
+      ...(pipelineId ? { pipeline_id: pipelineId } : {}),
+      status: ACTIVE_STATUSES,
+      page: 0,
+      size: maxExecutions,
+    }, signal)) ?? {};
+    const executions = (Array.isArray(listed.items) ? listed.items : []).filter(isRecord).slice(0, maxExecutions);
+    log.info("Scanning executions for resource constraints", { scope, executions: executions.length });
+
+    await sendProgress(extra, 1, 3, `Reading ${executions.length} execution graph(s)...`);
+    const stepsByKey = new Map<string, ConstraintStep[]>();
+    const errors: Array<{ entity: string; error: string }> = [];
+    for (let i = 0; i < executions.length; i += BATCH_SIZE) {
+      await Promise.all(executions.slice(i, i + BATCH_SIZE).map(async (exec) => {
+        const executionId = asString(exec.planExecutionId);
+        if (!executionId) return;
+        const orgId = asString(exec.orgIdentifier) ?? asString(input.org_id);
+        const projectId = asString(exec.projectIdentifier) ?? asString(input.project_id);
+        try {
+          const execution = asRecord(await registry.dispatch(client, "execution", "get", {
+            ...(orgId ? { org_id: orgId } : {}),
+            ...(projectId ? { project_id: projectId } : {}),
+            execution_id: executionId,
+            render_full_graph: true,
+          }, signal)) ?? {};
+          for (const { key, node } of constraintSteps(execution)) {
+            const steps = stepsByKey.get(key) ?? [];
+            steps.push({
+              execution_id: executionId,
+              pipeline_id: asString(exec.pipelineIdentifier),
+              org_id: orgId,
+              project_id: projectId,
+              step: asString(node.identifier),
+              step_type: asString(node.stepType),
+              status: asString(node.status),
+            });
+            stepsByKey.set(key, steps);
+          }
+        } catch (err) {
+          errors.push({ entity: `execution/${executionId}`, error: errorMessage(err) });
+        }
+      }));
+    }
+
+    await sendProgress(extra, 2, 3, `Fetching ${stepsByKey.size} constraint queue(s)...`);
+    const constraints = await Promise.all([...stepsByKey.entries()].map(async ([key, steps]) => {
+      try {
+        const queue = asRecord(await registry.dispatch(client, "constraint_queue", "get", { resource_unit: key }, signal)) ?? {};
+        return { ...queue, resourceUnit: key, steps };
+      } catch (err) {
+        errors.push({ entity: `constraint/${key}`, error: errorMessage(err) });
+        return { resourceUnit: key, steps };
+      }
+    }));
+    const queueLength = (c: Record<string, unknown>) => (Array.isArray(c.queue) ? c.queue.length : 0);
+    constraints.sort((a, b) => queueLength(b) - queueLength(a));
+
+    await sendProgress(extra, 3, 3, "Resource constraint scan complete");
+    return {
+      scope,
+      executions_scanned: executions.length,
+      ...(asNumber(listed.total) !== undefined && asNumber(listed.total)! > executions.length
+        ? { note: `Scanned ${executions.length} of ${listed.total} active executions. Raise max_executions (max ${MAX_EXECUTIONS}) or filter by pipeline_id.` }
+        : {}),
+      constraints,
+      contended: constraints.filter((c) => queueLength(c) > 0).length,
+      ...(errors.length > 0 ? { errors } : {}),
+    };
+  },
+};
@@ -0,0 +1,224 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { resolveLogContent } from "../../utils/log-resolver.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:terraform-plan");
+
+/** Provisioner step types whose runs we report. Plan steps also get a parsed plan summary. */
+const PROVISIONER_STEP_TYPES = new Set([
+  "TerraformPlan",
+  "TerraformApply",
+  "TerraformDestroy",
+  "TerraformRollback",
+  "TerraformCloudRun",
+  "TerraformCloudRollback",
+  "TerragruntPlan",
+  "TerragruntApply",
+  "TerragruntDestroy",
+  "TerragruntRollback",
+]);
+const PLAN_STEP_TYPES = new Set(["TerraformPlan", "TerragruntPlan", "TerraformCloudRun"]);
+
+const DEFAULT_MAX_EXECUTIONS = 5;
+const DEFAULT_MAX_CHANGED_RESOURCES = 50;
+
+const PLAN_TOTALS_RE = /Plan:\s*(\d+) to add,\s*(\d+) to change,\s*(\d+) to destroy/;
+const NO_CHANGES_RE = /No changes\.|Your infrastructure matches the configuration/;
+const RESOURCE_CHANGE_RE = /#\s+(\S+)\s+(will be created|will be destroyed|will be updated in-place|must be replaced|will be read during apply)/;
+
+const CHANGE_ACTIONS: Record<string, string> = {
+  "will be created": "create",
+  "will be destroyed": "destroy",
+  "will be updated in-place": "update",
+  "must be replaced": "replace",
+  "will be read during apply": "read",
+};
+
+export interface PlanSummary {
+  has_changes: boolean;
+  add?: number;
+  change?: number;
+  destroy?: number;
+  resource_changes?: Array<{ address: string; action: string }>;
+  resource_changes_truncated?: boolean;
+}
+
+/**
+ * Parse the human-readable `terraform plan` output from a step log into
+ * totals and per-resource actions. Returns undefined if the log holds no plan.
+ */
+export function parsePlanSummary(logText: string, maxResources = DEFAULT_MAX_CHANGED_RESOURCES): PlanSummary | undefined {
+  const totals = PLAN_TOTALS_RE.exec(logText);
+  if (!totals) {
+    return NO_CHANGES_RE.test(logText) ? { has_changes: false, add: 0, change: 0, destroy: 0 } : undefined;
+  }
+  const changes: Array<{ address: string; action: string }> = [];
+  const seen = new Set<string>();
+  for (const line of logText.split("\n")) {
+    const m = RESOURCE_CHANGE_RE.exec(line);
+    if (!m || seen.has(m[1]!)) continue;
+    seen.add(m[1]!);
+    changes.push({ address: m[1]!, action: CHANGE_ACTIONS[m[2]!]! });
+  }
+  const add = Number(totals[1]);
+  const change = Number(totals[2]);
+  const destroy = Number(totals[3]);
+  return {
+    has_changes: add + change + destroy > 0,
+    add,
+    change,
+    destroy,
+    resource_changes: changes.slice(0, maxResources),
+    ...(changes.length > maxResources ? { resource_changes_truncated: true } : {}),
+  };
+}
+
+/** Stage identifier from a step FQN like `pipeline.stages.infra.spec.execution.steps.plan`. */
+function stageFromFqn(fqn: string | undefined): string | undefined {
+  return fqn ? /\.stages\.([^.]+)\./.exec(fqn)?.[1] : undefined;
+}
+
+function summarizeProvisionerStep(node: Record<string, unknown>): Record<string, unknown> {
+  const params = asRecord(node.stepParameters);
+  const spec = asRecord(params?.spec);
+  const configuration = asRecord(spec?.configuration);
+  const output = asRecord(asRecord(node.outcomes)?.output);
+  const detailedExitCode = asNumber(output?.detailedExitCode);
+  const startTs = asNumber(node.startTs);
+  const endTs = asNumber(node.endTs);
+  return {
+    node_execution_id: asString(node.uuid),
+    identifier: asString(node.identifier),
+    name: asString(node.name),
+    step_type: asString(node.stepType),
+    stage: stageFromFqn(asString(node.baseFqn)),
+    status: asString(node.status),
+    provisioner_identifier: asString(spec?.provisionerIdentifier),
+    command: asString(configuration?.command),
+    workspace: asString(configuration?.workspace) ?? asString(spec?.workspace),
+    // `terraform plan -detailed-exitcode`: 0 = no changes, 2 = changes present
+    detailed_exit_code: detailedExitCode,
+    ...(detailedExitCode !== undefined ? { has_changes: detailedExitCode === 2 } : {}),
+    failure_message: asString(asRecord(node.failureInfo)?.message),
+    started_at: startTs ? new Date(startTs).toISOString() : undefined,
+    ended_at: endTs ? new Date(endTs).toISOString() : undefined,
+  };
+}
+
+function provisionerNodes(execution: Record<string, unknown> | undefined): Record<string, unknown>[] {
+  const nodeMap = asRecord(asRecord(execution?.executionGraph)?.nodeMap) ?? {};
+  return Object.values(nodeMap)
+    .filter(isRecord)
+    .filter((node) => PROVISIONER_STEP_TYPES.has(asString(node.stepType) ?? ""))
+    .sort((a, b) => (asNumber(a.startTs) ?? 0) - (asNumber(b.startTs) ?? 0));
+}
+
+export const terraformPlanHandler: DiagnoseHandler = {
+  entityType: "terraform_plan",
+  description: "Inspect Terraform/Terragrunt provisioner steps in pipeline executions — lists plan/apply/destroy runs per infrastructure stage and, for a single execution, parses each plan step's log into add/change/destroy totals and the resources it intends to change.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    const executionId = asString(input.execution_id);
+    const pipelineId = asString(input.pipeline_id) ?? asString(args.resource_id);
+    if (!executionId && !pipelineId) {
+      throw new Error("execution_id or pipeline_id is required for terraform_plan. Provide either in options or via a Harness URL.");
+    }
+
+    // Pipeline mode: list provisioner runs across recent executions (no log parsing)
+    if (!executionId) {
+      const requested = asNumber(args.max_executions) ?? DEFAULT_MAX_EXECUTIONS;
+      const maxExecutions = requested > 0 ? Math.min(requested, 20) : DEFAULT_MAX_EXECUTIONS;
+      await sendProgress(extra, 0, maxExecutions + 1, "Listing recent executions...");
+      const list = asRecord(await registry.dispatch(client, "execution", "list", {
+        ...input,
+        pipeline_id: pipelineId,
+        size: maxExecutions,
+        page: 0,
+      }, signal));
+      const items = (Array.isArray(list?.items) ? list.items : []).filter(isRecord);
+
+      const executions: Record<string, unknown>[] = [];
+      for (const [i, item] of items.entries()) {
+        const id = asString(item.planExecutionId);
+        if (!id) continue;
+        await sendProgress(extra, i + 1, maxExecutions + 1, `Reading execution ${id}...`);
+        try {
+          const execution = asRecord(await registry.dispatch(client, "execution", "get", {
+            ...input,
+            execution_id: id,
+            render_full_graph: true,
+          }, signal));
+          const steps = provisionerNodes(execution).map(summarizeProvisionerStep);
+          if (steps.length === 0) continue;
+          executions.push({
+            execution_id: id,
+            status: asString(item.status),
+            started_at: asNumber(item.startTs) ? new Date(asNumber(item.startTs)!).toISOString() : undefined,
+            provisioner_steps: steps,
+          });
+        } catch (err) {
+          log.warn("Failed to read execution graph", { executionId: id, error: String(err) });
+          executions.push({ execution_id: id, error: err instanceof Error ? err.message : String(err) });
+        }
+      }
+
+      await sendProgress(extra, maxExecutions + 1, maxExecutions + 1, "Terraform run listing complete");
+      return {
+        pipeline_id: pipelineId,
+        executions_scanned: items.length,
+        executions,
+        ...(executions.length === 0
+          ? { note: "No Terraform or Terragrunt steps ran in the scanned executions." }
+          : { _hint: "Pass execution_id to parse the plan output of a specific run." }),
+      };
+    }
+
+    // Execution mode: every provisioner step, with a plan summary for plan steps
+    const includePlanLog = args.include_plan_log !== false;
+    const maxResources = asNumber(args.max_changed_resources) ?? DEFAULT_MAX_CHANGED_RESOURCES;
+    await sendProgress(extra, 0, 2, "Fetching execution graph...");
+    log.info("Inspecting Terraform steps", { executionId });
+    const execution = asRecord(await registry.dispatch(client, "execution", "get", {
+      ...input,
+      execution_id: executionId,
+      render_full_graph: true,
+    }, signal));
+    const nodes = provisionerNodes(execution);
+    if (nodes.length === 0) {
+      await sendProgress(extra, 2, 2, "Terraform inspection complete");
+      return {
+        execution_id: executionId,
+        provisioner_steps: [],
+        note: "This execution has no Terraform or Terragrunt steps.",
+      };
+    }
+
+    await sendProgress(extra, 1, 2, "Parsing plan output...");
+    const steps: Record<string, unknown>[] = [];
+    for (const node of nodes) {
+      const step = summarizeProvisionerStep(node);
+      const prefix = asString(node.logBaseKey);
+      if (includePlanLog && PLAN_STEP_TYPES.has(asString(node.stepType) ?? "") && prefix) {
+        try {
+          const plan = parsePlanSummary(await resolveLogContent(client, prefix, { signal }), maxResources);
+          if (plan) step.plan = plan;
+          else step.plan_note = "No plan output found in the step log.";
+        } catch (err) {
+          step.plan_error = err instanceof Error ? err.message : String(err);
+        }
+      }
+      steps.push(step);
+    }
+
+    await sendProgress(extra, 2, 2, "Terraform inspection complete");
+    return {
+      execution_id: executionId,
+      pipeline_id: asString(asRecord(execution?.pipelineExecutionSummary)?.pipelineIdentifier),
+      provisioner_steps: steps,
+    };
+  },
+};
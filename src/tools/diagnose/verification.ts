@@ -0,0 +1,165 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+
+const log = createLogger("diagnose:verification");
+
+const DEFAULT_MAX_ITEMS = 20;
+const FAILED_STATUSES = new Set(["Failed", "Errored", "Expired", "ApprovalRejected"]);
+
+/**
+ * Verify steps in an execution graph, failed ones first. With step_id, only
+ * the matching node (node execution UUID or step identifier).
+ */
+function findVerifySteps(nodeMap: Record<string, unknown>, stepId: string | undefined): Record<string, unknown>[] {
+  const nodes = Object.entries(nodeMap)
+    .filter((entry): entry is [string, Record<string, unknown>] => isRecord(entry[1]));
+  if (stepId) {
+    const match = nodes.find(([uuid, node]) => uuid === stepId || node.uuid === stepId || node.identifier === stepId)?.[1];
+    return match ? [match] : [];
+  }
+  const verify = nodes.map(([, node]) => node).filter((node) => node.stepType === "Verify");
+  return [
+    ...verify.filter((n) => FAILED_STATUSES.has(asString(n.status) ?? "")),
+    ...verify.filter((n) => !FAILED_STATUSES.has(asString(n.status) ?? "")),
+  ];
+}
+
+/** The CV activity behind a Verify step — reported in progressData, or in the step outcome on older executions. */
+export function verifyStepExecutionId(node: Record<string, unknown>): string | undefined {
+  const outcomes = asRecord(node.outcomes);
+  const output = asRecord(outcomes?.output) ?? Object.values(outcomes ?? {}).map(asRecord).find((o) => o?.activityId);
+  return asString(asRecord(node.progressData)?.activityId) ?? asString(output?.activityId);
+}
+
+function summarizeMetric(metric: Record<string, unknown>): Record<string, unknown> {
+  const nodes = (Array.isArray(metric.testDataNodes) ? metric.testDataNodes : []).filter(isRecord);
+  return {
+    metric: asString(metric.metricName) ?? asString(metric.metricIdentifier),
+    transaction: asString(metric.transactionGroup),
+    health_source: asString(asRecord(metric.healthSource)?.name) ?? asString(asRecord(metric.healthSource)?.identifier),
+    result: asString(metric.analysisResult),
+    anomalous_nodes: nodes
+      .filter((n) => n.analysisResult !== "HEALTHY" && n.analysisResult !== "NO_ANALYSIS")
+      .map((n) => ({
+        node: asString(n.nodeIdentifier),
+        result: asString(n.analysisResult),
+        reason: asString(n.analysisReason),
+        control_node: asString(n.controlNodeIdentifier),
+      })),
+  };
+}
+
+function summarizeLogCluster(cluster: Record<string, unknown>): Record<string, unknown> {
+  return {
+    type: asString(cluster.clusterType),
+    risk: asString(cluster.risk),
+    count: asNumber(cluster.count),
+    message: asString(cluster.message)?.slice(0, 500),
+  };
+}
+
+/** Plain-language reasons verification went unhealthy, most severe first. */
+function explain(overview: Record<string, unknown>, metrics: Record<string, unknown>[], logs: Record<string, unknown>[]): string[] {
+  const reasons: string[] = [];
+  const unhealthy = metrics.filter((m) => m.result === "UNHEALTHY");
+  if (unhealthy.length > 0) {
+    reasons.push(`${unhealthy.length} metric(s) deviated from the baseline: ${unhealthy.slice(0, 5).map((m) => `${m.metric}${m.transaction ? ` (${m.transaction})` : ""}`).join(", ")}.`);
+  }
+  const unknown = logs.filter((l) => l.type === "UNKNOWN_EVENT");
+  if (unknown.length > 0) reasons.push(`${unknown.length} new log cluster(s) appeared that the baseline does not have.`);
+  const frequency = logs.filter((l) => l.type === "UNEXPECTED_FREQUENCY");
+  if (frequency.length > 0) reasons.push(`${frequency.length} known log cluster(s) occurred at an unexpected frequency.`);
+  const errors = asNumber(asRecord(overview.errorClusters)?.unknownClustersCount) ?? 0;
+  if (errors > 0) reasons.push(`${errors} unknown error cluster(s) were detected.`);
+  if (reasons.length === 0 && asRecord(overview.metricsAnalysis)?.noAnalysis) {
+    reasons.push("Some metrics had no analysis — the health source returned no data for them. Check the health source query and the monitored service's data collection.");
+  }
+  return reasons;
+}
+
+export const verificationHandler: DiagnoseHandler = {
+  entityType: "verification",
+  description: "Explain a Continuous Verification result — resolves the Verify step of an execution (or takes verify_step_execution_id), and reports the analysis overview, anomalous metrics with the nodes that deviated, and new or unexpected-frequency log clusters, with a plain-language summary of why verification failed.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { client, registry, input, args, extra, signal } = ctx;
+
+    let activityId = asString(input.verify_step_execution_id);
+    const executionId = asString(input.execution_id) ?? asString(args.resource_id);
+    const requested = asNumber(args.max_items) ?? DEFAULT_MAX_ITEMS;
+    const maxItems = requested > 0 ? Math.min(requested, 100) : DEFAULT_MAX_ITEMS;
+    const diagnostic: Record<string, unknown> = {};
+
+    if (!activityId) {
+      if (!executionId) {
+        throw new Error("execution_id or verify_step_execution_id is required for verification. Pass it in options or via a Harness execution URL.");
+      }
+      await sendProgress(extra, 0, 3, "Finding the Verify step...");
+      const execution = asRecord(await registry.dispatch(client, "execution", "get", {
+        ...input,
+        execution_id: executionId,
+        render_full_graph: true,
+      }, signal));
+      const nodeMap = asRecord(asRecord(execution?.executionGraph)?.nodeMap) ?? {};
+      const stepId = asString(input.step_id);
+      const steps = findVerifySteps(nodeMap, stepId);
+      const step = steps[0];
+      if (!step) {
+        throw new Error(stepId
+          ? `Step '${stepId}' was not found in execution '${executionId}'.`
+          : `Execution '${executionId}' has no Verify step.`);
+      }
+      activityId = verifyStepExecutionId(step);
+      diagnostic.execution_id = executionId;
+      diagnostic.step = {
+        identifier: asString(step.identifier),
+        name: asString(step.name),
+        status: asString(step.status),
+        failure_message: asString(asRecord(step.failureInfo)?.message),
+      };
+      if (steps.length > 1) diagnostic.other_verify_steps = steps.slice(1).map((s) => ({ identifier: asString(s.identifier), status: asString(s.status) }));
+      if (!activityId) {
+        diagnostic.note = "The Verify step has no CV activity — it was skipped or failed before analysis started. Check the step's failure message.";
+        return diagnostic;
+      }
+    }
+    diagnostic.verify_step_execution_id = activityId;
+    log.info("Fetching verification analysis", { activityId, executionId });
+
+    await sendProgress(extra, 1, 3, "Fetching verification overview...");
+    const overview = asRecord(await registry.dispatch(client, "verification", "get", { ...input, verify_step_execution_id: activityId }, signal)) ?? {};
+    const spec = asRecord(overview.spec);
+    diagnostic.overview = {
+      analysis_type: asString(overview.appliedDeploymentAnalysisType) ?? asString(spec?.analysisType),
+      sensitivity: asString(spec?.sensitivity),
+      duration_minutes: asNumber(spec?.durationInMinutes),
+      started_at: asNumber(overview.verificationStartTimestamp) ? new Date(asNumber(overview.verificationStartTimestamp)!).toISOString() : undefined,
+      progress_percentage: asNumber(overview.verificationProgressPercentage),
+      metrics: overview.metricsAnalysis,
+      log_clusters: overview.logClusters,
+      error_clusters: overview.errorClusters,
+      test_nodes: (Array.isArray(asRecord(overview.testNodes)?.nodes) ? asRecord(overview.testNodes)!.nodes as unknown[] : []).length,
+      control_nodes: (Array.isArray(asRecord(overview.controlNodes)?.nodes) ? asRecord(overview.controlNodes)!.nodes as unknown[] : []).length,
+    };
+
+    await sendProgress(extra, 2, 3, "Fetching anomalous metrics and log clusters...");
+    const [metricsResult, logsResult] = await Promise.allSettled([
+      registry.dispatch(client, "verification_metric", "list", { ...input, verify_step_execution_id: activityId, anomalous_only: true, page: 0, size: maxItems }, signal),
+      registry.dispatch(client, "verification_log", "list", { ...input, verify_step_execution_id: activityId, cluster_type: ["UNKNOWN_EVENT", "UNEXPECTED_FREQUENCY"], page: 0, size: maxItems }, signal),
+    ]);
+    const items = (r: PromiseSettledResult<unknown>) =>
+      r.status === "fulfilled" ? (Array.isArray(asRecord(r.value)?.items) ? (asRecord(r.value)!.items as unknown[]).filter(isRecord) : []) : [];
+    const metrics = items(metricsResult).map(summarizeMetric);
+    const logs = items(logsResult).map(summarizeLogCluster);
+    diagnostic.anomalous_metrics = metrics;
+    diagnostic.anomalous_log_clusters = logs;
+    if (metricsResult.status === "rejected") diagnostic.metrics_error = String(metricsResult.reason instanceof Error ? metricsResult.reason.message : metricsResult.reason);
+    if (logsResult.status === "rejected") diagnostic.logs_error = String(logsResult.reason instanceof Error ? logsResult.reason.message : logsResult.reason);
+    diagnostic.why = explain(overview, metrics, logs);
+
+    await sendProgress(extra, 3, 3, "Verification analysis complete");
+    return diagnostic;
+  },
+};
@@ -0,0 +1,257 @@
+import type { DiagnoseHandler, DiagnoseContext } from "./types.js";
+import { createLogger } from "../../utils/logger.js";
+import { sendProgress } from "../../utils/progress.js";
+import { sanitizeFileName, writeOutputFile } from "../../utils/output-dir.js";
+import { buildZip, type ZipEntry } from "../../utils/zip.js";
+import { asNumber, asRecord, asString, isRecord } from "../../utils/type-guards.js";
+import { beginOperation } from "../../utils/checkpoints.js";
+
+const log = createLogger("diagnose:yaml-export");
+
+export const YAML_EXPORT_TYPES = ["pipeline", "input_set", "template"] as const;
+type ExportType = (typeof YAML_EXPORT_TYPES)[number];
+
+const PAGE_SIZE = 100;
+const BATCH_SIZE = 5;
+const DEFAULT_MAX_ENTITIES = 500;
+const MAX_ENTITIES = 5000;
+
+interface ExportError {
+  entity: string;
+  error: string;
+}
+
+/** Progress saved after each batch, so an interrupted export can resume. */
+interface ExportCheckpoint {
+  entries: ZipEntry[];
+  errors: ExportError[];
+  counts: Record<string, number>;
+  /** Entities already exported or failed (`pipeline/<id>`, `input_set/<pipeline>`, `template/<id>@<version>`). */
+  done: string[];
+  truncated: boolean;
+}
+
+function toList(value: unknown): string[] | undefined {
+  if (Array.isArray(value)) return value.map(String).filter(Boolean);
+  if (typeof value === "string" && value.trim()) return value.split(",").map((s) => s.trim()).filter(Boolean);
+  return undefined;
+}
+
+/** Page through a list resource until it runs out or `limit` items are collected. */
+async function listAll(
+  ctx: DiagnoseContext,
+  resourceType: string,
+  query: Record<string, unknown>,
+  limit: number,
+): Promise<{ items: Record<string, unknown>[]; truncated: boolean }> {
+  const items: Record<string, unknown>[] = [];
+  for (let page = 0; items.length < limit; page++) {
+    const result = asRecord(await ctx.registry.dispatch(ctx.client, resourceType, "list", { ...query, page, size: PAGE_SIZE }, ctx.signal)) ?? {};
+    const pageItems = (Array.isArray(result.items) ? result.items : []).filter(isRecord);
+    items.push(...pageItems);
+    const total = asNumber(result.total);
+    if (pageItems.length < PAGE_SIZE || (total !== undefined && items.length >= total)) {
+      return { items: items.slice(0, limit), truncated: items.length > limit };
+    }
+  }
+  return { items: items.slice(0, limit), truncated: true };
+}
+
+/**
+ * Run `fn` over `items` a few at a time, so a large project does not fan out
+ * hundreds of requests at once. `afterBatch` runs once each batch settles.
+ */
+async function inBatches<T>(items: T[], fn: (item: T) => Promise<void>, afterBatch?: () => Promise<void>): Promise<void> {
+  for (let i = 0; i < items.length; i += BATCH_SIZE) {
+    await Promise.all(items.slice(i, i + BATCH_SIZE).map(fn));
+    await afterBatch?.();
+  }
+}
+
+function errorMessage(err: unknown): string {
+  return err instanceof Error ? err.message : String(err);
+}
+
+/** A cancelled call must stop the export, not be recorded as a per-entity error (and skipped on resume). */
+function rethrowIfAborted(ctx: DiagnoseContext, err: unknown): void {
+  if (ctx.signal.aborted) throw err;
+}
+
+export const yamlExportHandler: DiagnoseHandler = {
+  entityType: "yaml_export",
+  description: "Export the YAML of pipelines, input sets, and templates in a project into a zip file in HARNESS_OUTPUT_DIR and return a resource_link to it — for audits, backups, and migration reviews.",
+
+  async diagnose(ctx: DiagnoseContext): Promise<Record<string, unknown>> {
+    const { config, input, args, extra } = ctx;
+
+    const outputDir = config.HARNESS_OUTPUT_DIR;
+    if (!outputDir) {
+      throw new Error("yaml_export writes files and requires HARNESS_OUTPUT_DIR to be set on the server.");
+    }
+    const requestedTypes = toList(args.entity_types) ?? [...YAML_EXPORT_TYPES];
+    const unknown = requestedTypes.filter((t) => !(YAML_EXPORT_TYPES as readonly string[]).includes(t));
+    if (unknown.length > 0) {
+      throw new Error(`Unsupported entity_types: ${unknown.join(", ")}. Supported: ${YAML_EXPORT_TYPES.join(", ")}.`);
+    }
+    const types = new Set(requestedTypes as ExportType[]);
+    const pipelineFilter = toList(args.pipeline_ids);
+    const requestedMax = asNumber(args.max_entities) ?? DEFAULT_MAX_ENTITIES;
+    const maxEntities = requestedMax > 0 ? Math.min(requestedMax, MAX_ENTITIES) : DEFAULT_MAX_ENTITIES;
+    const orgId = asString(input.org_id) ?? config.HARNESS_ORG;
+    const projectId = asString(input.project_id) ?? config.HARNESS_PROJECT;
+    if (!orgId || !projectId) throw new Error("yaml_export reads project-level entities; provide org_id and project_id.");
+    const scope = { org_id: orgId, project_id: projectId };
+
+    const operation = beginOperation<ExportCheckpoint>(
+      "yaml_export",
+      ctx.client.account,
+      { types: [...types].sort(), pipelineFilter, maxEntities, orgId, projectId },
+      asString(args.resume_token),
+    );
+    const saved = operation.resumed;
+    // Copies, so an interruption mid-batch cannot leak partial work into the saved checkpoint
+    const entries: ZipEntry[] = [...(saved?.entries ?? [])];
+    const errors: ExportError[] = [...(saved?.errors ?? [])];
+    const counts: Record<string, number> = { ...saved?.counts };
+    const done = new Set(saved?.done);
+    let truncated = saved?.truncated ?? false;
+    const add = (type: ExportType, path: string, yaml: string | undefined, entity: string) => {
+      if (!yaml) {
+        errors.push({ entity, error: "No YAML in the API response." });
+        return;
+      }
+      if (entries.length >= maxEntities) {
+        truncated = true;
+        return;
+      }
+      entries.push({ name: path, content: yaml });
+      counts[type] = (counts[type] ?? 0) + 1;
+    };
+    const totalSteps = types.size + 1;
+    let step = 0;
+    // Between batches no request is in flight, so the snapshot is consistent
+    const checkpoint = async () => {
+      operation.save({ entries: [...entries], errors: [...errors], counts: { ...counts }, done: [...done], truncated });
+      await sendProgress(extra, step, totalSteps, `Exported ${entries.length} entities (resume_token: ${operation.id})`);
+    };
+    log.info("Exporting entity YAML", { types: [...types], orgId, projectId, maxEntities, resumed: done.size > 0 });
+
+    if (types.has("pipeline") || types.has("input_set")) {
+      await sendProgress(extra, step, totalSteps, "Listing pipelines...");
+      const listed = pipelineFilter
+        ? { items: pipelineFilter.map((identifier) => ({ identifier })), truncated: false }
+        : await listAll(ctx, "pipeline", scope, maxEntities);
+      truncated ||= listed.truncated;
+      const pipelineIds = listed.items.map((p) => asString(p.identifier)).filter((id): id is string => !!id);
+
+      if (types.has("pipeline")) {
+        await sendProgress(extra, step++, totalSteps, `Exporting ${pipelineIds.length} pipelines...`);
+        await inBatches(pipelineIds.filter((id) => !done.has(`pipeline/${id}`)), async (pipelineId) => {
+          try {
+            const pipeline = asRecord(await ctx.registry.dispatch(ctx.client, "pipeline", "get", { ...scope, pipeline_id: pipelineId }, ctx.signal));
+            add("pipeline", `pipelines/${sanitizeFileName(pipelineId)}.yaml`, asString(pipeline?.yamlPipeline), `pipeline/${pipelineId}`);
+          } catch (err) {
+            rethrowIfAborted(ctx, err);
+            errors.push({ entity: `pipeline/${pipelineId}`, error: errorMessage(err) });
+          }
+          done.add(`pipeline/${pipelineId}`);
+        }, checkpoint);
+      }
+
+      if (types.has("input_set")) {
+        await sendProgress(extra, step++, totalSteps, "Exporting input sets...");
+        await inBatches(pipelineIds.filter((id) => !done.has(`input_set/${id}`)), async (pipelineId) => {
+          try {
+            const sets = await listAll(ctx, "input_set", { ...scope, pipeline_id: pipelineId }, maxEntities);
+            for (const set of sets.items) {
+              const setId = asString(set.identifier);
+              if (!setId) continue;
+              const entity = `input_set/${pipelineId}/${setId}`;
+              try {
+                const full = asRecord(await ctx.registry.dispatch(ctx.client, "input_set", "get", { ...scope, pipeline_id: pipelineId, input_set_id: setId }, ctx.signal));
+                add("input_set", `input_sets/${sanitizeFileName(pipelineId)}/${sanitizeFileName(setId)}.yaml`, asString(full?.inputSetYaml), entity);
+              } catch (err) {
+                rethrowIfAborted(ctx, err);
+                errors.push({ entity, error: errorMessage(err) });
+              }
+            }
+          } catch (err) {
+            rethrowIfAborted(ctx, err);
+            errors.push({ entity: `input_set/${pipelineId}`, error: errorMessage(err) });
+          }
+          done.add(`input_set/${pipelineId}`);
+        }, checkpoint);
+      }
+    }
+
+    if (types.has("template")) {
+      await sendProgress(extra, step++, totalSteps, "Exporting templates...");
+      try {
+        // "All" lists every version, not only the stable one
+        const listed = await listAll(ctx, "template", { ...scope, template_list_type: "All" }, maxEntities);
+        truncated ||= listed.truncated;
+        await inBatches(listed.items, async (template) => {
+          const templateId = asString(template.identifier);
+          if (!templateId) return;
+          const version = asString(template.versionLabel) ?? "default";
+          const entity = `template/${templateId}@${version}`;
+          if (done.has(entity)) return;
+          done.add(entity);
+          try {
+            // List items usually carry the YAML; fall back to a get for those that don't
+            const yaml = asString(template.yaml) ?? asString(asRecord(await ctx.registry.dispatch(ctx.client, "template", "get", {
+              ...scope,
+              template_id: templateId,
+              ...(template.versionLabel ? { version_label: template.versionLabel } : {}),
+            }, ctx.signal))?.yaml);
+            add("template", `templates/${sanitizeFileName(templateId)}/${sanitizeFileName(version)}.yaml`, yaml, entity);
+          } catch (err) {
+            rethrowIfAborted(ctx, err);
+            errors.push({ entity, error: errorMessage(err) });
+          }
+        }, checkpoint);
+      } catch (err) {
+        rethrowIfAborted(ctx, err);
+        errors.push({ entity: "template", error: errorMessage(err) });
+      }
+    }
+
+    await sendProgress(extra, step, totalSteps, "Writing zip...");
+    const exportedAt = new Date();
+    const manifest = {
+      exported_at: exportedAt.toISOString(),
+      account_id: ctx.registry.getAccountId(),
+      org_id: orgId,
+      project_id: projectId,
+      counts,
+      ...(truncated ? { truncated: true } : {}),
+      errors,
+    };
+    entries.sort((a, b) => a.name.localeCompare(b.name));
+    const zip = buildZip([...entries, { name: "manifest.json", content: JSON.stringify(manifest, null, 2) + "\n" }], exportedAt);
+    const stamp = exportedAt.toISOString().replace(/[:.]/g, "-");
+    const file = await writeOutputFile(outputDir, `harness-yaml-${orgId}-${projectId}-${stamp}.zip`, zip);
+    operation.complete();
+    await sendProgress(extra, totalSteps, totalSteps, "YAML export complete");
+
+    return {
+      org_id: orgId,
+      project_id: projectId,
+      entity_types: [...types],
+      ...(saved ? { resumed: true } : {}),
+      counts,
+      files: entries.length,
+      ...(truncated ? { truncated: true, note: `Stopped at max_entities (${maxEntities}). Raise it (max ${MAX_ENTITIES}) or narrow entity_types / pipeline_ids.` } : {}),
+      ...(errors.length > 0 ? { errors } : {}),
+      file_path: file.path,
+      bytes: file.bytes,
+      resource_links: [{
+        type: "resource_link",
+        uri: file.uri,
+        name: file.name,
+        mimeType: "application/zip",
+        description: `${entries.length} YAML files`,
+      }],
+    };
+  },
+};
@@ -1,5 +1,6 @@
 import type { HarnessClient } from "../../client/harness-client.js";
 import { createLogger } from "../../utils/logger.js";
+import type { DiskCache } from "../../utils/disk-cache.js";
 import {
   normalizeEntitySchema,
   type JsonObject,
@@ -51,6 +52,9 @@ export const LIVE_ENTITY_SCHEMAS: Record<string, LiveEntitySchemaDefinition> = {
 
 export const LIVE_ENTITY_RESOURCE_TYPES = Object.keys(LIVE_ENTITY_SCHEMAS);
 
+/** Live schemas only change with Harness releases; a day keeps restarts cheap without pinning stale shapes. */
+const LIVE_SCHEMA_DISK_TTL_MS = 24 * 60 * 60 * 1000;
+
 const RESPONSE_SCHEMA_KEYS = [
   "schema",
   "yamlSchema",
@@ -316,7 +320,7 @@ function logBundledServe(
   });
 }
 
-export function createLiveSchemaFetcher(client: HarnessClient): LiveSchemaFetcher {
+export function createLiveSchemaFetcher(client: HarnessClient, diskCache?: DiskCache): LiveSchemaFetcher {
   const cache = new Map<string, EntitySchemaCacheEntry>();
   preloadBundledEntitySchemas(cache, client.account);
 
@@ -367,6 +371,13 @@ export function createLiveSchemaFetcher(client: HarnessClient): LiveSchemaFetche
         return entry;
       }
 
+      const persisted = await diskCache?.get<JsonObject>(cacheKey);
+      if (persisted) {
+        const entry: EntitySchemaCacheEntry = { schema: persisted, source: "ng-yaml-schema" };
+        cache.set(cacheKey, entry);
+        return entry;
+      }
+
       const queryParams = buildYamlSchemaParams(definition, accountId, params);
 
       try {
@@ -393,6 +404,7 @@ export function createLiveSchemaFetcher(client: HarnessClient): LiveSchemaFetche
 
         const entry: EntitySchemaCacheEntry = { schema: normalized, source: "ng-yaml-schema" };
         cache.set(cacheKey, entry);
+        await diskCache?.set(cacheKey, normalized, LIVE_SCHEMA_DISK_TTL_MS);
         return entry;
       } catch (err) {
         throw err;
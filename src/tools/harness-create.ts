@@ -4,16 +4,20 @@ import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import type { Config } from "../config.js";
 import { jsonResult, errorResult } from "../utils/response-formatter.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
 import { isUserError, isUserFixableApiError, toMcpError } from "../utils/errors.js";
 import { confirmViaElicitation, describeElicitationFailure, describeBlockedAudit } from "../utils/elicitation.js";
 import { applyUrlDefaults } from "../utils/url-parser.js";
 import { coerceRecord } from "../utils/type-guards.js";
 import { formatBodyPreview } from "../utils/body-preview.js";
-import { resourceScopeSchema, resourceTypeSchema } from "./input-schemas.js";
+import { changePlansFor, type ChangePlanRequest } from "../utils/change-plans.js";
+import { resourceScopeSchema, resourceTypeSchema, orgIdSchema, projectIdSchema, gitDetailsSchema, planOnlySchema, planHashSchema } from "./input-schemas.js";
 import { createOutputSchema } from "./output-schemas.js";
 
 export function registerCreateTool(server: McpServer, registry: Registry, client: HarnessClient, config: Config): void {
   const creatableTypes = registry.getTypesForOperation("create");
+  const plans = changePlansFor(server);
 
   server.registerTool(
     "harness_create",
@@ -27,10 +31,13 @@ export function registerCreateTool(server: McpServer, registry: Registry, client
         ]).describe("The resource definition body. For pipelines: pass a YAML string directly, or an object with yamlPipeline (YAML string) or pipeline (JSON object). For other resources: pass a JSON object"),
         url: z.string().optional().describe("A Harness UI URL — org, project, and supported resource_scope are extracted automatically"),
         resource_scope: resourceScopeSchema,
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
+        git_details: gitDetailsSchema,
         confirm: z.boolean().optional().describe("Set to true to confirm the operation. Only required when the operation risk is medium_write or above (most write resources) AND the client cannot surface a confirmation prompt — e.g. managed MCP that does not advertise elicitation, or an elicitation that fails at runtime. Has no effect for low-risk creates. Does NOT override an explicit decline from a client that completed an elicitation prompt — a user's decline is authoritative."),
         params: z.record(z.string(), z.unknown()).optional().describe("Additional parameters. For external Git pipelines: store_type='REMOTE', connector_ref, repo_name, branch, file_path, commit_msg. For Harness Code pipelines: store_type='REMOTE', is_harness_code_repo=true, repo_name, branch, file_path."),
+        plan_only: planOnlySchema,
+        plan_hash: planHashSchema,
       },
       outputSchema: createOutputSchema,
       annotations: {
@@ -41,9 +48,9 @@ export function registerCreateTool(server: McpServer, registry: Registry, client
         openWorldHint: true,
       },
     },
-    async (args) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_create", args, extra, async (extra) => {
       try {
-        const { params, body, confirm: _confirm, ...rest } = args;
+        const { params, body, confirm: _confirm, plan_only: planOnly, plan_hash: planHash, ...rest } = args;
         const coercedBody = typeof body === "string" ? (coerceRecord(body) ?? body) : body;
         const input = applyUrlDefaults({ ...rest, body: coercedBody } as Record<string, unknown>, args.url, { includeResourceScope: true });
         const coercedParams = coerceRecord(params);
@@ -73,6 +80,18 @@ export function registerCreateTool(server: McpServer, registry: Registry, client
           );
           return errorResult(reason);
         }
+        // Plan/act contract: plan_only returns the resolved change and its
+        // hash without writing; plan_hash applies a previously issued plan.
+        // An approved plan is not a confirmation: the call still goes through
+        // elicitation (or needs confirm). A hash is consumed even if the user
+        // then declines the prompt — a rejected plan has to be planned again.
+        const changePlan: ChangePlanRequest = { tool: "harness_create", operation: "create", resourceType: args.resource_type, risk, input };
+        if (planOnly) return jsonResult(plans.issue(changePlan, client.account));
+        if (planHash) {
+          const planError = plans.consume(changePlan, client.account, planHash);
+          if (planError) return errorResult(planError);
+        }
+
         const bodyPreview = formatBodyPreview(args.body);
         const elicit = await confirmViaElicitation({
           server,
@@ -93,13 +112,13 @@ export function registerCreateTool(server: McpServer, registry: Registry, client
           return errorResult(describeElicitationFailure(elicit));
         }
 
-        const result = await registry.dispatch(client, args.resource_type, "create", input, { tool: "harness_create", confirmation: elicit.method });
+        const result = await registry.dispatch(client, args.resource_type, "create", input, { tool: "harness_create", confirmation: elicit.method }, extra.signal);
         return jsonResult(result);
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
-        if (isUserFixableApiError(err)) return errorResult(err.message);
+        if (isUserFixableApiError(err)) return errorResult(err.message, err.correlationId);
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
@@ -4,29 +4,40 @@ import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import type { Config } from "../config.js";
 import { jsonResult, errorResult } from "../utils/response-formatter.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
 import { isUserError, isUserFixableApiError, toMcpError } from "../utils/errors.js";
 import { confirmViaElicitation, describeElicitationFailure, describeBlockedAudit } from "../utils/elicitation.js";
 import { applyUrlDefaults } from "../utils/url-parser.js";
 import { coerceRecord, asString } from "../utils/type-guards.js";
-import { resourceScopeSchema, resourceTypeSchema } from "./input-schemas.js";
+import { changePlansFor, type ChangePlanRequest } from "../utils/change-plans.js";
+import { buildDeleteImpact, consumeDeleteToken, deleteTokenKey, issueDeleteToken } from "../utils/delete-confirmation.js";
+import { resourceScopeSchema, resourceTypeSchema, orgIdSchema, projectIdSchema, gitDetailsSchema, planOnlySchema, planHashSchema } from "./input-schemas.js";
 import { deleteOutputSchema } from "./output-schemas.js";
 
 export function registerDeleteTool(server: McpServer, registry: Registry, client: HarnessClient, config: Config): void {
   const deletableTypes = registry.getTypesForOperation("delete");
+  const plans = changePlansFor(server);
 
   server.registerTool(
     "harness_delete",
     {
-      description: "Delete a Harness resource. You can pass a Harness URL to auto-extract identifiers. This is destructive and cannot be undone.",
+      description: config.HARNESS_TWO_PHASE_DELETE
+        ? "Delete a Harness resource. You can pass a Harness URL to auto-extract identifiers. This is destructive and cannot be undone. Two-phase: the first call returns an impact summary (references, last activity) and a confirmation_token — show the impact to the user, then call again with the same arguments plus confirmation_token to delete."
+        : "Delete a Harness resource. You can pass a Harness URL to auto-extract identifiers. This is destructive and cannot be undone.",
       inputSchema: {
         resource_type: resourceTypeSchema(deletableTypes).describe("The type of resource to delete"),
         resource_id: z.string().optional().describe("The identifier of the resource to delete. Optional when url contains the resource ID."),
         url: z.string().optional().describe("A Harness UI URL — org, project, resource type, ID, and supported resource_scope are extracted automatically"),
         resource_scope: resourceScopeSchema,
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
+        git_details: gitDetailsSchema,
         confirm: z.boolean().optional().describe("Set to true to confirm the destructive operation. Required when the client cannot surface a confirmation prompt — e.g. managed MCP that does not advertise elicitation, or an elicitation that fails at runtime. Does NOT override an explicit decline from a client that completed an elicitation prompt — a user's decline is authoritative."),
         params: z.record(z.string(), z.unknown()).optional().describe("Additional identifiers for nested resources (e.g. pipeline_id for triggers/input sets, environment_id for infrastructure)."),
+        confirmation_token: z.string().optional().describe("Token returned by a previous harness_delete call for the same resource when two-phase delete is enabled (HARNESS_TWO_PHASE_DELETE). Single-use; expires after 5 minutes."),
+        plan_only: planOnlySchema,
+        plan_hash: planHashSchema,
       },
       outputSchema: deleteOutputSchema,
       annotations: {
@@ -37,7 +48,7 @@ export function registerDeleteTool(server: McpServer, registry: Registry, client
         openWorldHint: true,
       },
     },
-    async (args) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_delete", args, extra, async (extra) => {
       try {
         // Validate resource_type and operation before asking user to confirm
         const def = registry.getResource(args.resource_type);
@@ -45,7 +56,7 @@ export function registerDeleteTool(server: McpServer, registry: Registry, client
           return errorResult(`Resource "${args.resource_type}" does not support "delete". Supported: ${Object.keys(def.operations).join(", ")}`);
         }
 
-        const { params, confirm: _confirm, ...rest } = args;
+        const { params, confirm: _confirm, confirmation_token: confirmationToken, plan_only: planOnly, plan_hash: planHash, ...rest } = args;
         const input = applyUrlDefaults(rest as Record<string, unknown>, args.url, { includeResourceScope: true });
         const coercedParams = coerceRecord(params);
         if (coercedParams) Object.assign(input, coercedParams);
@@ -86,13 +97,70 @@ export function registerDeleteTool(server: McpServer, registry: Registry, client
           );
           return errorResult(reason);
         }
+
+        // Plan/act contract — see harness_create.ts. With two-phase delete the
+        // plan also carries the impact summary and a confirmation_token, and
+        // applying it still requires that token: a plan never skips the
+        // impact review.
+        const changePlan: ChangePlanRequest = { tool: "harness_delete", operation: "delete", resourceType: args.resource_type, resourceId: resolvedResourceId, risk: def.operations.delete!.operationPolicy.risk, input };
+        const tokenKey = config.HARNESS_TWO_PHASE_DELETE
+          ? deleteTokenKey(client.account, args.resource_type, resolvedResourceId, input, config)
+          : undefined;
+        if (planOnly) {
+          const impact = tokenKey
+            ? await buildDeleteImpact(registry, client, config, args.resource_type, resolvedResourceId, input, extra.signal)
+            : undefined;
+          const payload: Record<string, unknown> = {
+            deleted: false,
+            resource_type: args.resource_type,
+            resource_id: resolvedResourceId,
+            ...plans.issue(changePlan, client.account),
+          };
+          if (tokenKey) {
+            payload.impact = impact;
+            payload.confirmation_token = issueDeleteToken(tokenKey, server).token;
+            payload.next_step = "Review the plan and its impact with the user, then call harness_delete again with the same arguments plus plan_hash and confirmation_token to delete. The confirmation_token expires after 5 minutes; request a new plan if it lapses.";
+          }
+          return jsonResult(payload);
+        }
+        if (planHash) {
+          if (tokenKey && !confirmationToken) {
+            return errorResult("Two-phase delete is enabled: pass the confirmation_token from the plan_only response together with plan_hash.");
+          }
+          const planError = plans.consume(changePlan, client.account, planHash);
+          if (planError) return errorResult(planError);
+        }
+
+        // Two-phase delete: without a token, return the impact summary and a
+        // token instead of deleting. A valid token is the caller's explicit
+        // confirmation of that summary.
+        if (tokenKey) {
+          if (!confirmationToken) {
+            const impact = await buildDeleteImpact(registry, client, config, args.resource_type, resolvedResourceId, input, extra.signal);
+            const { token, expiresAt } = issueDeleteToken(tokenKey, server);
+            return jsonResult({
+              deleted: false,
+              resource_type: args.resource_type,
+              resource_id: resolvedResourceId,
+              impact,
+              confirmation_token: token,
+              expires_at: new Date(expiresAt).toISOString(),
+              next_step: "Review the impact with the user, then call harness_delete again with the same arguments plus confirmation_token to delete.",
+            });
+          }
+          const tokenError = consumeDeleteToken(confirmationToken, tokenKey, server);
+          if (tokenError) {
+            return errorResult(`${tokenError} Call harness_delete without confirmation_token to get a fresh impact summary and token.`);
+          }
+        }
+
         const elicit = await confirmViaElicitation({
           server,
           toolName: "harness_delete",
           message: `Delete ${args.resource_type} "${resolvedResourceId}"?\n\nThis is destructive and cannot be undone.`,
           risk: "destructive",
           autoApproveRisk: config.HARNESS_AUTO_APPROVE_RISK,
-          callerConfirmed: args.confirm === true,
+          callerConfirmed: args.confirm === true || (config.HARNESS_TWO_PHASE_DELETE && confirmationToken !== undefined),
         });
         if (!elicit.proceed) {
           registry.auditBlockedAttempt(
@@ -105,7 +173,7 @@ export function registerDeleteTool(server: McpServer, registry: Registry, client
           return errorResult(describeElicitationFailure(elicit));
         }
 
-        const result = await registry.dispatch(client, args.resource_type, "delete", input, { tool: "harness_delete", confirmation: elicit.method, resource_id: resolvedResourceId });
+        const result = await registry.dispatch(client, args.resource_type, "delete", input, { tool: "harness_delete", confirmation: elicit.method, resource_id: resolvedResourceId }, extra.signal);
 
         const payload: Record<string, unknown> = {
           deleted: true,
@@ -120,9 +188,9 @@ export function registerDeleteTool(server: McpServer, registry: Registry, client
         return jsonResult(payload);
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
-        if (isUserFixableApiError(err)) return errorResult(err.message);
+        if (isUserFixableApiError(err)) return errorResult(err.message, err.correlationId);
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
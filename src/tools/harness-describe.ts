@@ -4,20 +4,23 @@ import type { Registry } from "../registry/index.js";
 import type { InputExpansionRule } from "../registry/types.js";
 import { jsonResult } from "../utils/response-formatter.js";
 import { getExamplesForResource } from "../data/examples/index.js";
+import { serverStatus } from "../utils/server-metrics.js";
+import type { Config } from "../config.js";
 import { describeOutputSchema } from "./output-schemas.js";
 
-export function registerDescribeTool(server: McpServer, registry: Registry): void {
+export function registerDescribeTool(server: McpServer, registry: Registry, config?: Config): void {
   const allTypes = registry.getAllResourceTypes() as [string, ...string[]];
   const allToolsets = registry.getAllToolsets().map(t => t.name) as [string, ...string[]];
 
   server.registerTool(
     "harness_describe",
     {
-      description: "Describe available Harness resource types, their supported operations, and fields. No API call — returns local metadata only. Use this to discover what resource_types you can use with other harness_ tools.",
+      description: "Describe available Harness resource types, their supported operations, and fields. No API call — returns local metadata only. Use this to discover what resource_types you can use with other harness_ tools. Pass server_status=true for this MCP server's own health (uptime, enabled toolsets, auth mode, cache hit rates, API error counts, memory).",
       inputSchema: {
         resource_type: z.enum(allTypes).optional().describe("Get details for a specific resource type"),
         toolset: z.enum(allToolsets).optional().describe("Filter to a specific toolset"),
         search_term: z.string().optional().describe("Search for resource types by keyword (matches type name, display name, toolset, description)"),
+        server_status: z.boolean().optional().describe("Return the MCP server's own status instead: uptime, enabled toolsets, auth mode, cache hit rates, Harness API call/error counts, and memory usage"),
       },
       outputSchema: describeOutputSchema,
       annotations: {
@@ -29,6 +32,10 @@ export function registerDescribeTool(server: McpServer, registry: Registry): voi
       },
     },
     async (args) => {
+      if (args.server_status) {
+        return jsonResult(serverStatus(registry, config));
+      }
+
       if (args.resource_type) {
         try {
           const def = registry.getResource(args.resource_type);
@@ -3,7 +3,11 @@ import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
 import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import type { Config } from "../config.js";
-import { jsonResult, errorResult } from "../utils/response-formatter.js";
+import { jsonResultWithLinks, errorResult } from "../utils/response-formatter.js";
+import { chunkedJsonResult } from "../utils/result-chunks.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
+import type { ResourceLinkItem } from "../utils/response-formatter.js";
 import { isUserError, isUserFixableApiError, toMcpError } from "../utils/errors.js";
 import { applyUrlDefaults } from "../utils/url-parser.js";
 import { asString } from "../utils/type-guards.js";
@@ -12,7 +16,23 @@ import { pipelineHandler } from "./diagnose/pipeline.js";
 import { connectorHandler } from "./diagnose/connector.js";
 import { delegateHandler } from "./diagnose/delegate.js";
 import { gitopsApplicationHandler } from "./diagnose/gitops-application.js";
+import { pipelineDiffHandler } from "./diagnose/pipeline-diff.js";
+import { configDriftHandler } from "./diagnose/config-drift.js";
+import { delegateTaskLogsHandler } from "./diagnose/delegate-task-logs.js";
+import { terraformPlanHandler } from "./diagnose/terraform-plan.js";
+import { k8sWorkloadHandler } from "./diagnose/k8s-workload.js";
+import { costExportHandler } from "./diagnose/cost-export.js";
+import { chaosResilienceHandler } from "./diagnose/chaos-resilience.js";
+import { idpScorecardHandler } from "./diagnose/idp-scorecard.js";
+import { prReviewHandler } from "./diagnose/pr-review.js";
+import { prMergeHandler } from "./diagnose/pr-merge.js";
+import { pipelineHealthHandler } from "./diagnose/pipeline-health.js";
+import { verificationHandler } from "./diagnose/verification.js";
+import { yamlExportHandler } from "./diagnose/yaml-export.js";
+import { executionWaitsHandler } from "./diagnose/execution-waits.js";
+import { resourceConstraintsHandler } from "./diagnose/resource-constraints.js";
 import { diagnoseOutputSchema } from "./output-schemas.js";
+import { orgIdSchema, projectIdSchema } from "./input-schemas.js";
 
 const ALIASES: Record<string, string> = { execution: "pipeline", gitops_app: "gitops_application" };
 
@@ -21,6 +41,21 @@ const handlers: Record<string, DiagnoseHandler> = {
   connector: connectorHandler,
   delegate: delegateHandler,
   gitops_application: gitopsApplicationHandler,
+  pipeline_diff: pipelineDiffHandler,
+  config_drift: configDriftHandler,
+  delegate_task_logs: delegateTaskLogsHandler,
+  terraform_plan: terraformPlanHandler,
+  k8s_workload: k8sWorkloadHandler,
+  cost_export: costExportHandler,
+  chaos_resilience: chaosResilienceHandler,
+  idp_scorecard: idpScorecardHandler,
+  pr_review: prReviewHandler,
+  pr_merge: prMergeHandler,
+  pipeline_health: pipelineHealthHandler,
+  verification: verificationHandler,
+  yaml_export: yamlExportHandler,
+  execution_waits: executionWaitsHandler,
+  resource_constraints: resourceConstraintsHandler,
 };
 
 const SUPPORTED_TYPES = Object.keys(handlers).join(", ");
@@ -32,14 +67,14 @@ export function registerDiagnoseTool(server: McpServer, registry: Registry, clie
   server.registerTool(
     "harness_diagnose",
     {
-      description: `Diagnose a Harness resource — analyze failures, test connectivity, check health, or troubleshoot GitOps sync issues. Defaults to pipeline execution diagnosis. Accepts a Harness URL to auto-detect the resource type.`,
+      description: `Diagnose a Harness resource — analyze failures, test connectivity, check health, or troubleshoot GitOps sync issues, diff two revisions of a remote pipeline (pipeline_diff), detect service config drift across environments (config_drift), fetch the delegate-side logs for a failed step's delegate tasks (delegate_task_logs), summarize Terraform/Terragrunt provisioner runs and plan output (terraform_plan), report the running pods of a deployed Kubernetes service (k8s_workload), export a CCM perspective breakdown to a CSV file (cost_export), review chaos resiliency scores and probe health (chaos_resilience), drill into which catalog entities fail which IDP scorecard checks and why (idp_scorecard), gather a pull request's metadata and chunked diffs for code review (pr_review), explain which required checks and protection rules block a pull request merge (pr_merge), report pipeline success rate, mean duration, and failures by stage over a time window — or rank a project's flakiest pipelines (pipeline_health), explain why a Continuous Verification step failed a deployment (verification), export a project's pipeline, input set, and template YAML to a zip file (yaml_export), or explain why a running execution is stuck — the barriers, resource constraints, approvals, and queues it waits on and who holds them (execution_waits), or list which executions hold or queue on resource constraints across a project, org, or account (resource_constraints). Defaults to pipeline execution diagnosis. Accepts a Harness URL to auto-detect the resource type.`,
       inputSchema: {
         resource_type: z.enum(DIAGNOSE_TYPES).optional().describe("Resource type to diagnose. Auto-detected from url if provided. Defaults to pipeline."),
         resource_id: z.string().optional().describe("Primary identifier of the resource (connector ID, delegate name). Auto-detected from url if provided."),
         url: z.string().optional().describe("A Harness URL — resource type, org, project, and ID are extracted automatically"),
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
-        options: z.record(z.string(), z.unknown()).optional().describe("Resource-specific diagnostic options. Pipeline: execution_id, pipeline_id, summary, include_yaml, include_logs, include_all_step_logs (boolean, fetch logs for ALL steps not just failed/deepest — logs are fetched in batches of 3 for memory safety, capped at max_all_step_logs, use for pipeline summarization), max_all_step_logs (number, default 25, max steps to include in all_step_logs — prevents OOM on large matrix/loop pipelines), return_download_url (boolean, return signed logs.zip URLs instead of inline log text), log_snippet_lines, max_failed_steps. Pipeline diagnosis requires a completed execution. When a Harness URL contains ?step=<nodeExecutionId>, setting include_logs:true fetches that specific step's log regardless of pass/fail status and returns it as requested_step_log alongside any failed_step_logs. GitOps: agent_id. Call harness_describe for details."),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
+        options: z.record(z.string(), z.unknown()).optional().describe("Resource-specific diagnostic options. Pipeline: execution_id, pipeline_id, summary, include_yaml, include_logs, include_all_step_logs (boolean, fetch logs for ALL steps not just failed/deepest — logs are fetched in batches of 3 for memory safety, capped at max_all_step_logs, use for pipeline summarization), max_all_step_logs (number, default 25, max steps to include in all_step_logs — prevents OOM on large matrix/loop pipelines), return_download_url (boolean, return signed logs.zip URLs instead of inline log text), log_snippet_lines, summarize_logs (boolean, return each log as a condensed summary — ANSI/timestamps stripped, repeats collapsed, error and stack-trace blocks extracted — with a full_log pointer, instead of the last log_snippet_lines lines), max_failed_steps. Pipeline diagnosis requires a completed execution. When a Harness URL contains ?step=<nodeExecutionId>, setting include_logs:true fetches that specific step's log regardless of pass/fail status and returns it as requested_step_log alongside any failed_step_logs. GitOps: agent_id. Pipeline diff: pipeline_id, base_branch or base_commit, head_branch or head_commit, context_lines (default 3) — commit refs require a Harness Code repo. Config drift: service_id, environment_ids (2+), infra_id (include INFRA_* overrides). Delegate task logs: execution_id, step_id (defaults to the first failed step), max_log_lines (default 200). Terraform plan: execution_id (parse plan output of each plan step) or pipeline_id (list provisioner runs across recent executions), max_executions (default 5, max 20), include_plan_log (default true), max_changed_resources (default 50). K8s workload: service_id, env_id, infra_id. Cost export: perspective_id, group_by, time_filter or start_time/end_time, cluster (boolean, export the cluster breakdown; perspective_id optional), max_rows (default 10000), resume_token (from a progress message of an interrupted run with the same arguments) — writes CSV to HARNESS_OUTPUT_DIR and returns a resource_link. Chaos resilience: max_experiments (default 50), min_score (default 80), tags/infra_id/environment_id filters, or probe_id for a single probe's status. IDP scorecard: no id (list scorecards), scorecard_id (failing entities per check, max_checks default 20), or entity_identifier (namespace/Kind/name — pass/fail and reason per check; scorecard_id narrows it). PR review: repo_id, pr_number, chunk (default 0), max_chunk_chars (default 30000), include_generated (include lock file and build output diffs). PR merge: repo_id, pr_number. Pipeline health: pipeline_id (omit to rank all pipelines in the project), start_time/end_time (default last 30 days), module (CD, CI), max_executions (default 500, max 2000), min_runs (default 3, fewer runs are left out of the ranking), top (default 10). Verification: execution_id (uses the failed Verify step, else the first), step_id, or verify_step_execution_id; max_items (default 20). YAML export: entity_types (pipeline, input_set, template; default all), pipeline_ids (limit pipelines and their input sets), max_entities (default 500, max 5000), resume_token (continue an interrupted export) — writes a zip to HARNESS_OUTPUT_DIR and returns a resource_link. Execution waits: execution_id (a running or queued execution). Resource constraints: scope (project, org, or account; default project), pipeline_id, max_executions (active executions scanned, default 50, max 200), or resource_unit for one constraint's queue. Call harness_describe for details."),
       },
       outputSchema: diagnoseOutputSchema,
       annotations: {
@@ -49,7 +84,7 @@ export function registerDiagnoseTool(server: McpServer, registry: Registry, clie
         openWorldHint: true,
       },
     },
-    async (args, extra) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_diagnose", args, extra, async (extra) => {
       try {
         const { options, ...rest } = args;
         const input = applyUrlDefaults(rest as Record<string, unknown>, args.url);
@@ -76,12 +111,14 @@ export function registerDiagnoseTool(server: McpServer, registry: Registry, clie
         const ctx: DiagnoseContext = { client, registry, config, input, args: mergedArgs, extra, signal: extra.signal };
         const result = await handler.diagnose(ctx);
 
-        return jsonResult(result);
+        // Handlers that write files (cost_export) list them as resource_links
+        const links = Array.isArray(result.resource_links) ? result.resource_links as ResourceLinkItem[] : [];
+        return links.length > 0 ? jsonResultWithLinks(result, links) : chunkedJsonResult(result, client.account);
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
-        if (isUserFixableApiError(err)) return errorResult(err.message);
+        if (isUserFixableApiError(err)) return errorResult(err.message, err.correlationId);
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
@@ -5,17 +5,22 @@ import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import type { Config } from "../config.js";
 import { jsonResult, errorResult } from "../utils/response-formatter.js";
-import { isUserError, isUserFixableApiError, toMcpError, HarnessApiError } from "../utils/errors.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
+import { isUserError, isUserFixableApiError, toMcpError, getCorrelationId, HarnessApiError } from "../utils/errors.js";
 import { confirmViaElicitation, describeElicitationFailure, describeBlockedAudit } from "../utils/elicitation.js";
 import { createLogger } from "../utils/logger.js";
 import { applyUrlDefaults } from "../utils/url-parser.js";
 import { asRecord, asString, coerceRecord } from "../utils/type-guards.js";
-import { isFlatKeyValueInputs, isResolvableInputs, flattenInputs, resolveRuntimeInputs, resolveRuntimeInputsWithBaseYaml, type ResolutionResult } from "../utils/runtime-input-resolver.js";
+import { changePlansFor, type ChangePlanRequest } from "../utils/change-plans.js";
+import { isFlatKeyValueInputs, isResolvableInputs, flattenInputs, resolveRuntimeInputs, resolveRuntimeInputsWithBaseYaml, parseStageIdentifiers, type ResolutionResult } from "../utils/runtime-input-resolver.js";
 import { applyInputExpansions } from "../utils/input-expander.js";
 import { materializeInputSetsToRuntimeYaml, mergeRuntimePipelineFragments } from "../utils/materialize-input-sets.js";
-import { resourceScopeSchema, resourceTypeSchema } from "./input-schemas.js";
+import { resourceScopeSchema, resourceTypeSchema, orgIdSchema, projectIdSchema, gitDetailsSchema, planOnlySchema, planHashSchema } from "./input-schemas.js";
 import { pollExecutionToTerminal, FAILURE_STATUSES, AbortError } from "../utils/poll-execution.js";
 import { sendProgress } from "../utils/progress.js";
+import { scaffoldServicePipeline } from "../utils/service-scaffold.js";
+import { applyYamlBundle } from "../utils/yaml-apply.js";
 import { executeOutputSchema } from "./output-schemas.js";
 
 const log = createLogger("execute");
@@ -125,6 +130,7 @@ function extractExecutionId(result: unknown, resourceType: string): string | und
 
 export function registerExecuteTool(server: McpServer, registry: Registry, client: HarnessClient, config: Config): void {
   const executableTypes = registry.getTypesWithExecuteActions();
+  const plans = changePlansFor(server);
 
   server.registerTool(
     "harness_execute",
@@ -141,11 +147,14 @@ export function registerExecuteTool(server: McpServer, registry: Registry, clien
         url: z.string().optional().describe("Harness UI URL — auto-extracts org, project, type, and ID"),
         action: z.string().describe("Action to execute (e.g. run, retry, interrupt, toggle, test_connection, sync)"),
         resource_id: z.string().optional().describe("Primary resource identifier"),
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
+        git_details: gitDetailsSchema,
         resource_scope: resourceScopeSchema,
         inputs: z.union([z.string(), z.record(z.string(), z.unknown())]).optional().describe("Pipeline runtime inputs: key-value pairs like {branch: 'main'} (auto-resolved), or full YAML string. Check runtime_input_template first via harness_get."),
         input_set_ids: z.array(z.string()).optional().describe("Input set IDs for complex pipelines. List available: harness_list(resource_type='input_set', filters={pipeline_id: '...'})."),
+        stage_identifiers: z.array(z.string()).optional().describe("For pipeline run: run only these stages (selective stage execution). Runtime inputs are resolved against the selected stages only."),
+        expression_values: z.record(z.string(), z.string()).optional().describe("For pipeline run with stage_identifiers: values for expressions the selected stages read from skipped stages, keyed by expression (e.g. {'<+pipeline.stages.build.variables.tag>': 'v1.2'})."),
         body: z.record(z.string(), z.unknown()).optional().describe("Additional body payload for the action"),
         params: z.record(z.string(), z.unknown()).optional().describe("Action-specific parameters. Call harness_describe for available fields per resource_type."),
         confirm: z.boolean().optional().describe("Set to true to confirm the operation. Only required when the action's risk is medium_write or above (e.g. pipeline.run is high_write; hql_query.run/validate are read and need no confirmation) AND the client cannot surface a confirmation prompt — e.g. managed MCP that does not advertise elicitation, or an elicitation that fails at runtime. Has no effect for low-risk actions. Does NOT override an explicit decline from a client that completed an elicitation prompt — a user's decline is authoritative."),
@@ -159,6 +168,8 @@ export function registerExecuteTool(server: McpServer, registry: Registry, clien
             max_results: z.number().optional().describe("Maximum rows to return"),
           })
         ).max(20).optional().describe("Batch HQL queries — use with resource_type='hql_query' and action='validate' or 'run'. Fans out in parallel, returns per-query results."),
+        plan_only: planOnlySchema,
+        plan_hash: planHashSchema,
       },
       outputSchema: executeOutputSchema,
       annotations: {
@@ -169,9 +180,9 @@ export function registerExecuteTool(server: McpServer, registry: Registry, clien
         openWorldHint: true,
       },
     },
-    async (args, extra) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_execute", args, extra, async (extra) => {
       try {
-        const { params, wait, wait_timeout_seconds, wait_poll_interval_seconds, confirm: _confirm, queries: batchQueries, ...rest } = args;
+        const { params, wait, wait_timeout_seconds, wait_poll_interval_seconds, confirm: _confirm, plan_only: planOnly, plan_hash: planHash, queries: batchQueries, ...rest } = args;
         const input = applyUrlDefaults(rest as Record<string, unknown>, args.url, { includeResourceScope: true });
         const coercedParams = coerceRecord(params);
         if (coercedParams) Object.assign(input, coercedParams);
@@ -217,6 +228,14 @@ export function registerExecuteTool(server: McpServer, registry: Registry, clien
           return errorResult(reason);
         }
 
+        // Plan/act contract — see harness_create.ts.
+        const changePlan: ChangePlanRequest = { tool: "harness_execute", operation: args.action, resourceType, resourceId, risk, input: batchQueries ? { ...input, queries: batchQueries } : input };
+        if (planOnly) return jsonResult(plans.issue(changePlan, client.account));
+        if (planHash) {
+          const planError = plans.consume(changePlan, client.account, planHash);
+          if (planError) return errorResult(planError);
+        }
+
         const elicit = await confirmViaElicitation({
           server,
           toolName: "harness_execute",
@@ -236,6 +255,30 @@ export function registerExecuteTool(server: McpServer, registry: Registry, clien
           return errorResult(describeElicitationFailure(elicit));
         }
 
+        // Composites — service scaffold (service, override, and pipeline
+        // creates) and YAML bundle apply — dispatch and audit each entity
+        // through the registry themselves
+        const composite = resourceType === "service" && args.action === "scaffold_pipeline"
+          ? scaffoldServicePipeline
+          : resourceType === "yaml_bundle" && args.action === "apply"
+            ? applyYamlBundle
+            : undefined;
+        if (composite) {
+          try {
+            const result = await composite(
+              registry,
+              client,
+              input,
+              { tool: "harness_execute", confirmation: elicit.method, action: args.action },
+              extra.signal,
+            );
+            return jsonResult(result);
+          } catch (err) {
+            if (isUserError(err) || isUserFixableApiError(err)) return errorResult((err as Error).message, getCorrelationId(err));
+            throw toMcpError(err);
+          }
+        }
+
         // Batch HQL path — fan out queries in parallel through the registry
         if (batchQueries && batchQueries.length > 0) {
           if (resourceType !== "hql_query") {
@@ -266,7 +309,7 @@ export function registerExecuteTool(server: McpServer, registry: Registry, clien
             const succeeded = results.filter((r) => r.success).length;
             return jsonResult({ results, summary: { total: results.length, succeeded, failed: results.length - succeeded } });
           } catch (err) {
-            if (isUserError(err) || isUserFixableApiError(err)) return errorResult((err as Error).message);
+            if (isUserError(err) || isUserFixableApiError(err)) return errorResult((err as Error).message, getCorrelationId(err));
             throw toMcpError(err);
           }
         }
@@ -401,6 +444,7 @@ export function registerExecuteTool(server: McpServer, registry: Registry, clien
               orgId: asString(input.org_id) || registry.orgId,
               projectId: asString(input.project_id) || registry.projectId,
               branch: asString(input.pipeline_branch) ?? asString(input.branch),
+              stageIdentifiers: parseStageIdentifiers(input.stage_identifiers),
             };
             resolved = materializedInputSetYaml
               ? await resolveRuntimeInputsWithBaseYaml(client, inputsToResolve, resolveOptions, materializedInputSetYaml)
@@ -593,10 +637,10 @@ export function registerExecuteTool(server: McpServer, registry: Registry, clien
         return jsonResult({ ...(asRecord(result) ?? {}), ...envelope });
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
-        if (isUserFixableApiError(err)) return errorResult(err.message);
+        if (isUserFixableApiError(err)) return errorResult(err.message, err.correlationId);
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
 
@@ -3,15 +3,20 @@ import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
 import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import { jsonResult, errorResult } from "../utils/response-formatter.js";
+import { chunkedJsonResult, continueResult } from "../utils/result-chunks.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
 import { isUserError, isUserFixableApiError, toMcpError, enrichErrorWithHint, HarnessApiError } from "../utils/errors.js";
 import { applyUrlDefaults } from "../utils/url-parser.js";
-import { asString, coerceRecord } from "../utils/type-guards.js";
-import { resolveLogContent, resolveLogDownloadUrl } from "../utils/log-resolver.js";
+import { asNumber, asString, coerceRecord } from "../utils/type-guards.js";
+import { resolveLogContent, resolveLogDownloadUrl, resolveLogFiles } from "../utils/log-resolver.js";
+import { compileLogPattern, searchLogFiles } from "../utils/log-search.js";
+import { fullLogPointer, summarizeLog } from "../utils/log-summary.js";
 import { buildLogPrefixFromExecution } from "../utils/log-prefix.js";
 import type { SearchManager } from "../search/index.js";
 import { buildResourceIndexContent } from "../search/embedding-content.js";
 import { buildEntityDocumentId, buildEntityMetadata, resolveEntityScope } from "../search/entity-index.js";
-import { resourceTypeSchema } from "./input-schemas.js";
+import { resourceTypeSchema, orgIdSchema, projectIdSchema, gitDetailsSchema } from "./input-schemas.js";
 import { getOutputSchema } from "./output-schemas.js";
 
 function isTrue(value: unknown): boolean {
@@ -30,10 +35,16 @@ export function registerGetTool(server: McpServer, registry: Registry, client: H
         resource_id: z.string().optional().describe("Primary resource identifier. Auto-detected from url."),
         url: z.string().optional().describe("Harness UI URL — auto-extracts org, project, type, and ID"),
         resource_scope: z.enum(["account", "org", "project"]).optional().describe("Scope to query. Use account for account-level resources and to omit org/project defaults; org injects only org; project injects org+project. Auto-detected from url."),
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
+        git_details: gitDetailsSchema,
         params: z.record(z.string(), z.unknown()).optional().describe("Additional identifiers for nested resources. Call harness_describe for fields per resource_type."),
         return_download_url: z.union([z.boolean(), z.enum(["true", "false"])]).optional().describe("For execution_log only: return a directly fetchable log download URL instead of buffering log content."),
+        log_search: z.string().optional().describe("For execution_log only: regex (case-insensitive) to grep the logs for server-side. Returns matching lines with context, grouped by step log key, instead of the full log."),
+        log_summary: z.union([z.boolean(), z.enum(["true", "false"])]).optional().describe("For execution_log only: return a condensed view — ANSI codes and timestamps stripped, repeated lines collapsed, error and stack-trace blocks extracted — plus a pointer to the full log."),
+        log_context_lines: z.number().min(0).max(20).optional().describe("For log_search: lines of context around each match. Default 2."),
+        log_max_matches: z.number().min(1).max(500).optional().describe("For log_search: maximum matching lines returned. Default 50; total_matches still counts all."),
+        continue_result: z.string().optional().describe("Cursor (next_cursor) from a chunked result of harness_get or harness_diagnose. Returns the next chunk; all other arguments are ignored."),
       },
       outputSchema: getOutputSchema,
       annotations: {
@@ -43,8 +54,13 @@ export function registerGetTool(server: McpServer, registry: Registry, client: H
         openWorldHint: true,
       },
     },
-    async (args) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_get", args, extra, async (extra) => {
       try {
+        if (args.continue_result) {
+          const chunk = continueResult(args.continue_result, client.account);
+          return typeof chunk === "string" ? errorResult(chunk) : chunk;
+        }
+
         const { params, ...rest } = args;
         const input = applyUrlDefaults(rest as Record<string, unknown>, args.url, { includeResourceScope: true });
         const coercedParams = coerceRecord(params);
@@ -89,6 +105,13 @@ export function registerGetTool(server: McpServer, registry: Registry, client: H
 
         // execution_log: preserve legacy content by default; opt into URL-only mode with return_download_url=true.
         if (resourceType === "execution_log") {
+          const logSearch = asString(input.log_search);
+          let logPattern: RegExp | undefined;
+          try {
+            logPattern = logSearch ? compileLogPattern(logSearch) : undefined;
+          } catch (err) {
+            return errorResult(err instanceof Error ? err.message : String(err));
+          }
           try {
             let prefix = asString(input.prefix);
             if (!prefix) {
@@ -103,15 +126,26 @@ export function registerGetTool(server: McpServer, registry: Registry, client: H
               const downloadUrl = await resolveLogDownloadUrl(client, prefix);
               return jsonResult({ download_url: downloadUrl });
             }
+            if (logPattern) {
+              const files = await resolveLogFiles(client, prefix, { signal: extra.signal });
+              return chunkedJsonResult(searchLogFiles(files, {
+                pattern: logPattern,
+                contextLines: asNumber(input.log_context_lines) ?? 2,
+                maxMatches: asNumber(input.log_max_matches) ?? 50,
+              }), client.account);
+            }
             const logText = await resolveLogContent(client, prefix);
-            return jsonResult({ log_content: logText });
+            if (isTrue(input.log_summary)) {
+              return jsonResult({ ...summarizeLog(logText), full_log: fullLogPointer(prefix) });
+            }
+            return chunkedJsonResult({ log_content: logText }, client.account);
           } catch (err) {
             const msg = err instanceof Error ? err.message : String(err);
             return errorResult(`Failed to resolve execution logs: ${msg}. Try harness_diagnose with include_logs=true for better failure analysis.`);
           }
         }
 
-        const result = await registry.dispatch(client, resourceType, "get", input);
+        const result = await registry.dispatch(client, resourceType, "get", input, extra.signal);
 
         // Fire-and-forget: index item for semantic search (skipped in multi-user + local)
         if (searchManager && result && typeof result === "object") {
@@ -130,7 +164,7 @@ export function registerGetTool(server: McpServer, registry: Registry, client: H
           }
         }
 
-        return jsonResult(result);
+        return chunkedJsonResult(result, client.account);
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
         if (isUserFixableApiError(err)) {
@@ -139,10 +173,10 @@ export function registerGetTool(server: McpServer, registry: Registry, client: H
           if (err instanceof HarnessApiError && err.statusCode === 404 && rt) {
             try { hint = registry.getResource(rt).diagnosticHint; } catch { /* unknown type */ }
           }
-          return errorResult(enrichErrorWithHint(err.message, hint));
+          return errorResult(enrichErrorWithHint(err.message, hint), err.correlationId);
         }
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
@@ -3,14 +3,17 @@ import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
 import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import { jsonResult, errorResult, normalizeHarnessListPayload } from "../utils/response-formatter.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
 import { isUserError, isUserFixableApiError, toMcpError, enrichErrorWithHint, HarnessApiError } from "../utils/errors.js";
 import { compactItems } from "../utils/compact.js";
+import { ListDeltaTracker, listQueryKey } from "../utils/list-delta.js";
 import { applyUrlDefaults } from "../utils/url-parser.js";
 import { asString, isRecord, coerceRecord } from "../utils/type-guards.js";
 import type { SearchManager } from "../search/index.js";
 import { buildResourceIndexContent } from "../search/embedding-content.js";
 import { buildEntityDocumentId, buildEntityMetadata, resolveEntityScope } from "../search/entity-index.js";
-import { resourceTypeSchema } from "./input-schemas.js";
+import { resourceTypeSchema, orgIdSchema, projectIdSchema, gitDetailsSchema } from "./input-schemas.js";
 import { listOutputSchema } from "./output-schemas.js";
 
 export function registerListTool(server: McpServer, registry: Registry, client: HarnessClient, searchManager?: SearchManager): void {
@@ -21,6 +24,8 @@ export function registerListTool(server: McpServer, registry: Registry, client:
     : "Resource-specific filters as key-value pairs. Call harness_describe for available filters per resource_type.";
 
   const listableTypes = registry.getTypesForOperation("list");
+  // One tracker per server instance, i.e. per MCP session
+  const deltas = new ListDeltaTracker();
 
   server.registerTool(
     "harness_list",
@@ -30,14 +35,16 @@ export function registerListTool(server: McpServer, registry: Registry, client:
         resource_type: resourceTypeSchema(listableTypes).optional().describe("Resource type to list. Auto-detected from url."),
         url: z.string().optional().describe("Harness UI URL — auto-extracts org, project, and type"),
         resource_scope: z.enum(["account", "org", "project"]).optional().describe("Scope to query. Use account for account-level resources and to omit org/project defaults; org injects only org; project injects org+project. Auto-detected from url."),
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
+        git_details: gitDetailsSchema,
         page: z.number().default(0).optional().describe("Page number, 0-indexed"),
         size: z.number().min(1).max(100).default(20).optional().describe("Page size (1–100)"),
         search_term: z.string().optional().describe("Filter results by name or keyword"),
         compact: z.boolean().default(true).optional().describe("Strip verbose metadata from list items, keeping only essential fields (default true)"),
         params: z.record(z.string(), z.unknown()).optional().describe("Additional identifiers for nested resources (e.g. repo_id for pull requests). Call harness_describe for fields per resource_type."),
         filters: z.record(z.string(), z.unknown()).optional().describe(filtersDesc),
+        since_cursor: z.string().optional().describe("Delta mode for polling. Pass \"\" to start: the full page comes back with delta.cursor. Pass that cursor on the next identical call to get only items added or changed since, plus delta.removed identities. An unknown or expired cursor returns the full page with delta.reset=true."),
       },
       outputSchema: listOutputSchema,
       annotations: {
@@ -47,7 +54,7 @@ export function registerListTool(server: McpServer, registry: Registry, client:
         openWorldHint: true,
       },
     },
-    async (args) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_list", args, extra, async (extra) => {
       try {
         const { params, filters, ...rest } = args;
         const input = applyUrlDefaults(rest as Record<string, unknown>, args.url, { includeResourceScope: true });
@@ -64,7 +71,7 @@ export function registerListTool(server: McpServer, registry: Registry, client:
         if (resourceType === "template" && input.template_list_type === undefined) {
           input.template_list_type = "All";
         }
-        const rawResult = await registry.dispatch(client, resourceType, "list", input);
+        const rawResult = await registry.dispatch(client, resourceType, "list", input, extra.signal);
         const page = typeof args.page === "number" ? args.page : 0;
         const result = normalizeHarnessListPayload(rawResult, { page });
 
@@ -76,7 +83,7 @@ export function registerListTool(server: McpServer, registry: Registry, client:
           const items = result.items;
           if (Array.isArray(items)) {
             const compactFn = registry.getResource(resourceType).compactItem;
-            result.items = compactItems(items, compactFn);
+            result.items = compactItems(items, compactFn, { unknownFields: registry.unknownFieldsMode });
           }
         }
 
@@ -99,6 +106,13 @@ export function registerListTool(server: McpServer, registry: Registry, client:
           ).catch(() => { /* never surface indexing errors to caller */ });
         }
 
+        // Delta mode: diff the (compacted) page against the snapshot behind the cursor
+        if (typeof args.since_cursor === "string" && isRecord(result) && Array.isArray(result.items)) {
+          const { items, delta } = deltas.diff(listQueryKey(input), result.items, args.since_cursor);
+          result.items = items;
+          result.delta = delta;
+        }
+
         return jsonResult(result);
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
@@ -108,10 +122,10 @@ export function registerListTool(server: McpServer, registry: Registry, client:
           if (err instanceof HarnessApiError && err.statusCode === 404 && rt) {
             try { hint = registry.getResource(rt).diagnosticHint; } catch { /* unknown type */ }
           }
-          return errorResult(enrichErrorWithHint(err.message, hint));
+          return errorResult(enrichErrorWithHint(err.message, hint), err.correlationId);
         }
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
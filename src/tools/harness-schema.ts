@@ -3,6 +3,8 @@ import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
 import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import { jsonResult, errorResult } from "../utils/response-formatter.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
 import { SCHEMAS } from "../data/schemas/index.js";
 import type { SchemaEntry } from "../data/schemas/types.js";
 import { getExample, searchExamples, getExamplesForResource } from "../data/examples/index.js";
@@ -215,7 +217,7 @@ function listAvailableSchemaNames(
 
 export function registerSchemaTool(
   server: McpServer,
-  _registry: Registry | undefined,
+  registry: Registry | undefined,
   client: HarnessClient | undefined,
   additionalSchemas?: Record<string, SchemaEntry>,
 ): void {
@@ -234,7 +236,7 @@ export function registerSchemaTool(
       }
     : { ...SCHEMAS };
 
-  const liveFetcher = client ? createLiveSchemaFetcher(client) : undefined;
+  const liveFetcher = client ? createLiveSchemaFetcher(client, registry?.getDiskCache("entity-schemas")) : undefined;
   const availableSchemas = listAvailableSchemaNames(Object.keys(allSchemas), liveFetcher);
   const hasLiveEntities = liveFetcher !== undefined;
 
@@ -293,7 +295,7 @@ export function registerSchemaTool(
         openWorldHint: hasLiveEntities,
       },
     },
-    async (args) => {
+    async (args, extra) => runWithApiBudget(registry?.apiBudget, () => withToolTimeout("harness_schema", args, extra, async () => {
       try {
         if (args.example) {
           const ex = getExample(args.example);
@@ -365,7 +367,12 @@ export function registerSchemaTool(
           const { schema, source } = fetched;
 
           if (!args.path) {
-            return jsonResult(getEntitySchemaSummary(schema, args.resource_type, source));
+            const summary = getEntitySchemaSummary(schema, args.resource_type, source);
+            const examples = getExamplesForResource(args.resource_type);
+            if (examples.length > 0) {
+              (summary as Record<string, unknown>).examples_available = examples.map((e) => e.name);
+            }
+            return jsonResult(summary);
           }
 
           const node = navigateEntitySchemaPath(schema, args.resource_type, args.path);
@@ -423,6 +430,6 @@ export function registerSchemaTool(
       } catch (err) {
         return errorResult(err instanceof Error ? err.message : String(err));
       }
-    },
+    })),
   );
 }
@@ -3,6 +3,8 @@ import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
 import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import { jsonResult, errorResult } from "../utils/response-formatter.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
 import { isUserError, isUserFixableApiError, toMcpError } from "../utils/errors.js";
 import { compactItems } from "../utils/compact.js";
 import { createLogger } from "../utils/logger.js";
@@ -13,6 +15,7 @@ import { searchOutputSchema } from "./output-schemas.js";
 import type { SearchManager } from "../search/index.js";
 import type { SearchResult } from "../search/types.js";
 import { entityResultMatchesEffectiveScope } from "../search/entity-index.js";
+import { orgIdSchema, projectIdSchema } from "./input-schemas.js";
 
 const log = createLogger("search");
 const RESOURCE_SCOPES: readonly ResourceScope[] = ["account", "org", "project"];
@@ -110,8 +113,8 @@ export function registerSearchTool(server: McpServer, registry: Registry, client
         resource_types: z.array(z.enum(listableTypes)).optional().describe("Types to search (defaults to all listable)"),
         url: z.string().optional().describe("Harness UI URL — auto-extracts org and project"),
         resource_scope: z.enum(["account", "org", "project"]).optional().describe("Scope to search. Use account for account-level resources and to omit org/project defaults; org injects only org; project injects org+project. Auto-detected from url."),
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
         max_per_type: z.number().default(5).optional().describe("Max results per type"),
         compact: z.boolean().default(true).optional().describe("Strip verbose metadata (default true)"),
       },
@@ -123,7 +126,7 @@ export function registerSearchTool(server: McpServer, registry: Registry, client
         openWorldHint: true,
       },
     },
-    async (args, extra) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_search", args, extra, async (extra) => {
       try {
         const signal = extra.signal;
         const mergedArgs = applyUrlDefaults(args as Record<string, unknown>, args.url, { includeResourceScope: true });
@@ -285,9 +288,9 @@ export function registerSearchTool(server: McpServer, registry: Registry, client
         });
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
-        if (isUserFixableApiError(err)) return errorResult(err.message);
+        if (isUserFixableApiError(err)) return errorResult(err.message, err.correlationId);
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
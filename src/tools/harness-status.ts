@@ -4,13 +4,16 @@ import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import type { Config } from "../config.js";
 import { jsonResult, errorResult } from "../utils/response-formatter.js";
-import { buildDeepLink } from "../utils/deep-links.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
+import { buildDeepLink, harnessUiBaseUrl } from "../utils/deep-links.js";
 import { isUserError, isUserFixableApiError, toMcpError } from "../utils/errors.js";
 import { createLogger } from "../utils/logger.js";
 import { sendProgress } from "../utils/progress.js";
 import { applyUrlDefaults } from "../utils/url-parser.js";
 import { asString } from "../utils/type-guards.js";
 import { statusOutputSchema } from "./output-schemas.js";
+import { orgIdSchema, projectIdSchema } from "./input-schemas.js";
 
 const log = createLogger("status");
 
@@ -74,8 +77,8 @@ export function registerStatusTool(
     {
       description: "Get a live project health overview: recent failed executions, currently running executions, and recent deployment activity. You can pass a Harness URL to auto-extract org and project. Ideal first question: 'what's happening in my project right now?'",
       inputSchema: {
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
         url: z.string().optional().describe("A Harness UI URL — org and project are extracted automatically"),
         limit: z.number().default(5).optional().describe("Max items per section (default 5, max 20)"),
       },
@@ -87,7 +90,7 @@ export function registerStatusTool(
         openWorldHint: true,
       },
     },
-    async (args, extra) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_status", args, extra, async (extra) => {
       try {
         const signal = extra.signal;
         const merged = applyUrlDefaults(args as Record<string, unknown>, args.url);
@@ -153,13 +156,13 @@ export function registerStatusTool(
 
         const accountId = registry.getAccountId();
         const failedItems = (failed?.items ?? []).map((e) =>
-          summarizeExecution(e, config.HARNESS_BASE_URL, accountId, orgId, projectId),
+          summarizeExecution(e, harnessUiBaseUrl(config), accountId, orgId, projectId),
         );
         const runningItems = (running?.items ?? []).map((e) =>
-          summarizeExecution(e, config.HARNESS_BASE_URL, accountId, orgId, projectId),
+          summarizeExecution(e, harnessUiBaseUrl(config), accountId, orgId, projectId),
         );
         const recentItems = (recent?.items ?? []).map((e) =>
-          summarizeExecution(e, config.HARNESS_BASE_URL, accountId, orgId, projectId),
+          summarizeExecution(e, harnessUiBaseUrl(config), accountId, orgId, projectId),
         );
 
         // Compute health
@@ -180,7 +183,7 @@ export function registerStatusTool(
         let deploymentsLink: string | undefined;
         try {
           deploymentsLink = buildDeepLink(
-            config.HARNESS_BASE_URL,
+            harnessUiBaseUrl(config),
             accountId,
             "/ng/account/{accountId}/all/orgs/{orgIdentifier}/projects/{projectIdentifier}/deployments",
             { orgIdentifier: orgId, projectIdentifier: projectId },
@@ -222,9 +225,9 @@ export function registerStatusTool(
         return jsonResult(status);
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
-        if (isUserFixableApiError(err)) return errorResult(err.message);
+        if (isUserFixableApiError(err)) return errorResult(err.message, err.correlationId);
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
@@ -4,16 +4,20 @@ import type { Registry } from "../registry/index.js";
 import type { HarnessClient } from "../client/harness-client.js";
 import type { Config } from "../config.js";
 import { jsonResult, errorResult } from "../utils/response-formatter.js";
+import { withToolTimeout } from "../utils/tool-timeouts.js";
+import { runWithApiBudget } from "../utils/api-budget.js";
 import { isUserError, isUserFixableApiError, toMcpError } from "../utils/errors.js";
 import { confirmViaElicitation, describeElicitationFailure, describeBlockedAudit } from "../utils/elicitation.js";
 import { applyUrlDefaults } from "../utils/url-parser.js";
 import { asString, isRecord, coerceRecord } from "../utils/type-guards.js";
 import { formatBodyPreview } from "../utils/body-preview.js";
-import { resourceScopeSchema, resourceTypeSchema } from "./input-schemas.js";
+import { changePlansFor, type ChangePlanRequest } from "../utils/change-plans.js";
+import { resourceScopeSchema, resourceTypeSchema, orgIdSchema, projectIdSchema, gitDetailsSchema, planOnlySchema, planHashSchema } from "./input-schemas.js";
 import { updateOutputSchema } from "./output-schemas.js";
 
 export function registerUpdateTool(server: McpServer, registry: Registry, client: HarnessClient, config: Config): void {
   const updatableTypes = registry.getTypesForOperation("update");
+  const plans = changePlansFor(server);
 
   server.registerTool(
     "harness_update",
@@ -28,10 +32,13 @@ export function registerUpdateTool(server: McpServer, registry: Registry, client
           z.record(z.string(), z.unknown()),
           z.string(),
         ]).describe("The updated resource definition body. For pipelines: pass a YAML string directly, or an object with yamlPipeline (YAML string) or pipeline (JSON object)"),
-        org_id: z.string().optional().describe("Organization identifier (overrides default)"),
-        project_id: z.string().optional().describe("Project identifier (overrides default)"),
+        org_id: orgIdSchema(registry.orgId),
+        project_id: projectIdSchema(registry.projectId),
+        git_details: gitDetailsSchema,
         confirm: z.boolean().optional().describe("Set to true to confirm the operation. Only required when the operation risk is medium_write or above AND the client cannot surface a confirmation prompt — e.g. managed MCP that does not advertise elicitation, or an elicitation that fails at runtime. Has no effect for low-risk updates. Does NOT override an explicit decline from a client that completed an elicitation prompt — a user's decline is authoritative."),
         params: z.record(z.string(), z.unknown()).optional().describe("Additional identifiers (e.g. pipeline_id for triggers/input sets, version_label for templates)."),
+        plan_only: planOnlySchema,
+        plan_hash: planHashSchema,
       },
       outputSchema: updateOutputSchema,
       annotations: {
@@ -42,7 +49,7 @@ export function registerUpdateTool(server: McpServer, registry: Registry, client
         openWorldHint: true,
       },
     },
-    async (args) => {
+    async (args, extra) => runWithApiBudget(registry.apiBudget, () => withToolTimeout("harness_update", args, extra, async (extra) => {
       try {
         // Validate resource_type and operation before asking user to confirm
         const def = registry.getResource(args.resource_type);
@@ -50,7 +57,7 @@ export function registerUpdateTool(server: McpServer, registry: Registry, client
           return errorResult(`Resource "${args.resource_type}" does not support "update". Supported: ${Object.keys(def.operations).join(", ")}`);
         }
 
-        const { params, body, confirm: _confirm, ...rest } = args;
+        const { params, body, confirm: _confirm, plan_only: planOnly, plan_hash: planHash, ...rest } = args;
         const coercedBody = typeof body === "string" ? (coerceRecord(body) ?? body) : body;
         const input = applyUrlDefaults({ ...rest, body: coercedBody } as Record<string, unknown>, args.url, { includeResourceScope: true });
         const coercedParams = coerceRecord(params);
@@ -91,6 +98,14 @@ export function registerUpdateTool(server: McpServer, registry: Registry, client
           );
           return errorResult(reason);
         }
+        // Plan/act contract — see harness_create.ts.
+        const changePlan: ChangePlanRequest = { tool: "harness_update", operation: "update", resourceType: args.resource_type, resourceId: resolvedResourceId, risk, input };
+        if (planOnly) return jsonResult(plans.issue(changePlan, client.account));
+        if (planHash) {
+          const planError = plans.consume(changePlan, client.account, planHash);
+          if (planError) return errorResult(planError);
+        }
+
         const bodyPreview = formatBodyPreview(args.body);
         const elicit = await confirmViaElicitation({
           server,
@@ -118,13 +133,13 @@ export function registerUpdateTool(server: McpServer, registry: Registry, client
           input.version_label = "v1";
         }
 
-        const result = await registry.dispatch(client, args.resource_type, "update", input, { tool: "harness_update", confirmation: elicit.method, resource_id: resolvedResourceId });
+        const result = await registry.dispatch(client, args.resource_type, "update", input, { tool: "harness_update", confirmation: elicit.method, resource_id: resolvedResourceId }, extra.signal);
         return jsonResult(result);
       } catch (err) {
         if (isUserError(err)) return errorResult(err.message);
-        if (isUserFixableApiError(err)) return errorResult(err.message);
+        if (isUserFixableApiError(err)) return errorResult(err.message, err.correlationId);
         throw toMcpError(err);
       }
-    },
+    })),
   );
 }
@@ -28,7 +28,7 @@ export function registerAllTools(server: McpServer, registry: Registry, client:
   registerExecuteTool(server, registry, client, config);
   registerDiagnoseTool(server, registry, client, config);
   registerSearchTool(server, registry, client, searchManager);
-  registerDescribeTool(server, registry);
+  registerDescribeTool(server, registry, config);
   registerStatusTool(server, registry, client, config);
   registerSchemaTool(server, registry, client, additionalSchemas);
 }
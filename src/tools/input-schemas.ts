@@ -8,6 +8,34 @@ export const resourceScopeSchema = z
     "Scope for the operation. account: omit org/project (e.g. /v1/templates). org: org only. project: org+project. Auto-detected from url when present.",
   );
 
+/** Git Experience context for remote (Git-stored) pipelines, templates, input sets, services, environments, and infrastructure. */
+export const gitDetailsSchema = z
+  .object({
+    branch: z.string().optional().describe("Branch to read from or commit to"),
+    repo_name: z.string().optional().describe("Repository name"),
+    connector_ref: z.string().optional().describe("Git connector (not needed for Harness Code repos)"),
+    store_type: z.enum(["INLINE", "REMOTE"]).optional().describe("REMOTE to store a new entity in Git"),
+    load_from_fallback_branch: z.boolean().optional().describe("Read the default branch when the file is not on branch"),
+    file_path: z.string().optional().describe("File path in the repo, for creates"),
+    commit_msg: z.string().optional().describe("Commit message, for creates and updates"),
+    base_branch: z.string().optional().describe("Branch to cut a new branch from"),
+    is_new_branch: z.boolean().optional().describe("Commit to a new branch"),
+    is_harness_code_repo: z.boolean().optional().describe("The repo is a Harness Code repo"),
+  })
+  .optional()
+  .describe("Git details for remote (Git-stored) entities. Equivalent to passing the same fields flat in params; auto-filled from branch/repoName/connectorRef in a Harness URL.");
+
+/** Plan/act contract for write tools — see src/utils/change-plans.ts. */
+export const planOnlySchema = z
+  .boolean()
+  .optional()
+  .describe("Set to true to return a change plan (resolved target, scope, risk, and payload) with a plan_hash instead of making the change. Nothing is written and no confirmation is asked.");
+
+export const planHashSchema = z
+  .string()
+  .optional()
+  .describe("plan_hash from a previous plan_only call. Repeat the planned arguments exactly; the change runs only if they still produce that plan. Does not replace confirmation. Valid only in the session that requested the plan; single-use; expires after 1 hour.");
+
 export function resourceTypeSchema(resourceTypes: string[]) {
   if (resourceTypes.length === 0) {
     return z.string().refine(() => false, { error: "No enabled resource types support this operation" });
@@ -15,3 +43,24 @@ export function resourceTypeSchema(resourceTypes: string[]) {
 
   return z.enum(resourceTypes as [string, ...string[]]);
 }
+
+/**
+ * `org_id` input. Names the configured default so agents don't ask the user
+ * for a value the server already has.
+ */
+export function orgIdSchema(defaultOrg: string | undefined) {
+  return z.string().optional().describe(
+    defaultOrg
+      ? `Organization identifier. Defaults to '${defaultOrg}' if omitted.`
+      : "Organization identifier. No default is configured — required for org- and project-scoped resources.",
+  );
+}
+
+/** `project_id` input, naming the configured default like `orgIdSchema`. */
+export function projectIdSchema(defaultProject: string | undefined) {
+  return z.string().optional().describe(
+    defaultProject
+      ? `Project identifier. Defaults to '${defaultProject}' if omitted.`
+      : "Project identifier. No default is configured — required for project-scoped resources.",
+  );
+}
@@ -21,7 +21,7 @@ export const updateOutputSchema = z.object({}).catchall(z.unknown()).describe("U
 
 // --- harness_delete ---
 export const deleteOutputSchema = z.object({
-  deleted: z.boolean().describe("Whether the resource was successfully deleted"),
+  deleted: z.boolean().describe("Whether the resource was successfully deleted (false for a two-phase delete preview)"),
   resource_type: z.string().describe("The type of resource that was deleted"),
   resource_id: z.string().describe("The ID of the deleted resource"),
   version_label: z.string().describe("Deleted template version label, when applicable").optional(),
@@ -30,6 +30,17 @@ export const deleteOutputSchema = z.object({
     .catchall(z.unknown())
     .describe("Optional API response payload (e.g. template-service delete body)")
     .optional(),
+  impact: z
+    .object({})
+    .catchall(z.unknown())
+    .describe("Two-phase delete preview: references, last activity, and lookups that were unavailable")
+    .optional(),
+  confirmation_token: z.string().describe("Two-phase delete preview: pass back to harness_delete to delete").optional(),
+  expires_at: z.string().describe("When confirmation_token or plan_hash expires (ISO 8601)").optional(),
+  plan_only: z.boolean().describe("True when this is a change plan; nothing was deleted").optional(),
+  plan_hash: z.string().describe("Change plan: pass back to harness_delete to apply the plan").optional(),
+  plan: z.object({}).catchall(z.unknown()).describe("Change plan: the resolved delete that plan_hash approves").optional(),
+  next_step: z.string().optional(),
 });
 
 // --- harness_execute ---
@@ -73,3 +84,18 @@ export const statusOutputSchema = z.object({
 
 // --- harness_schema ---
 export const schemaOutputSchema = z.object({}).catchall(z.unknown()).describe("Schema data — shape varies by query mode");
+
+/** Declared output schema per tool — checked against every result in strict schema mode. */
+export const TOOL_OUTPUT_SCHEMAS: Record<string, z.ZodType> = {
+  harness_list: listOutputSchema,
+  harness_get: getOutputSchema,
+  harness_create: createOutputSchema,
+  harness_update: updateOutputSchema,
+  harness_delete: deleteOutputSchema,
+  harness_execute: executeOutputSchema,
+  harness_diagnose: diagnoseOutputSchema,
+  harness_search: searchOutputSchema,
+  harness_describe: describeOutputSchema,
+  harness_status: statusOutputSchema,
+  harness_schema: schemaOutputSchema,
+};
@@ -0,0 +1,130 @@
+/**
+ * Harness API call budgets (HARNESS_API_BUDGET_PER_SESSION,
+ * HARNESS_API_BUDGET_PER_HOUR).
+ *
+ * A runaway agent loop can burn through a shared account's API rate limits
+ * in minutes. Each Registry (one per MCP session) owns an `ApiBudget` with a
+ * per-session limit and a per-hour limit shared by every session on the same
+ * account. The session's tool calls run inside it (`runWithApiBudget`), and
+ * `HarnessClient` counts every outbound request against it — so cache hits
+ * cost nothing, and direct client calls (diagnose handlers, log downloads)
+ * are counted like registry operations. Once either limit is spent, the
+ * client throws `ApiBudgetExceededError`; the registry then refuses writes
+ * and answers reads from the last response seen for the same request, with
+ * a warning, or refuses them when there is none.
+ */
+
+import { AsyncLocalStorage } from "node:async_hooks";
+
+/** Read responses remembered per session for serving once the budget is spent. */
+const MAX_CACHED_RESPONSES = 200;
+
+const HOUR_MS = 3_600_000;
+
+/** Calls per account in the current hour window, shared across sessions. */
+const hourlyCalls = new Map<string, { count: number; resetAt: number }>();
+/** When expired hour windows were last swept from `hourlyCalls`. */
+let lastPrunedAt = 0;
+
+/** Drop hour windows that have ended, at most once a minute, so idle accounts do not accumulate. */
+function pruneHourlyCalls(now: number): void {
+  if (now - lastPrunedAt < 60_000) return;
+  lastPrunedAt = now;
+  for (const [account, window] of hourlyCalls) {
+    if (now >= window.resetAt) hourlyCalls.delete(account);
+  }
+}
+
+/** Thrown by `HarnessClient` when the current budget is spent; no request was sent. */
+export class ApiBudgetExceededError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "ApiBudgetExceededError";
+  }
+}
+
+export interface ApiBudgetLimits {
+  /** Calls allowed per session; 0 = unlimited. */
+  perSession: number;
+  /** Calls allowed per account per hour; 0 = unlimited. */
+  perHour: number;
+}
+
+export class ApiBudget {
+  private sessionCalls = 0;
+  private responses = new Map<string, { raw: unknown; at: number }>();
+
+  constructor(private limits: ApiBudgetLimits) {}
+
+  /** True when any limit is configured. */
+  get enabled(): boolean {
+    return this.limits.perSession > 0 || this.limits.perHour > 0;
+  }
+
+  /**
+   * Count one call for `account`. Returns undefined when it is within budget,
+   * or why it is not — in which case nothing is counted.
+   */
+  consume(account: string, now: number = Date.now()): string | undefined {
+    const { perSession, perHour } = this.limits;
+    if (perSession > 0 && this.sessionCalls >= perSession) {
+      return `The API call budget for this session is spent (${perSession} calls, HARNESS_API_BUDGET_PER_SESSION). Start a new session to reset it.`;
+    }
+    if (perHour > 0) {
+      pruneHourlyCalls(now);
+      let window = hourlyCalls.get(account);
+      if (!window || now >= window.resetAt) {
+        window = { count: 0, resetAt: now + HOUR_MS };
+        hourlyCalls.set(account, window);
+      }
+      if (window.count >= perHour) {
+        const minutes = Math.ceil((window.resetAt - now) / 60_000);
+        return `The hourly API call budget for account ${account} is spent (${perHour} calls, HARNESS_API_BUDGET_PER_HOUR). It resets in ${minutes} min.`;
+      }
+      window.count++;
+    }
+    this.sessionCalls++;
+    return undefined;
+  }
+
+  /** Remember a read response for `key`. */
+  remember(key: string, raw: unknown, now: number = Date.now()): void {
+    this.responses.delete(key);
+    this.responses.set(key, { raw, at: now });
+    if (this.responses.size > MAX_CACHED_RESPONSES) {
+      this.responses.delete(this.responses.keys().next().value as string);
+    }
+  }
+
+  /** The last response remembered for `key`, if any. */
+  cached(key: string): { raw: unknown; at: number } | undefined {
+    return this.responses.get(key);
+  }
+}
+
+const budgetContext = new AsyncLocalStorage<ApiBudget>();
+
+/** Budget of the session whose tool call is running, if any. */
+export function currentApiBudget(): ApiBudget | undefined {
+  return budgetContext.getStore();
+}
+
+/**
+ * Run `fn` with `budget` counting its outbound API requests. Tool handlers
+ * wrap themselves in their session's budget at registration. A call already
+ * inside a budget keeps it, and without a budget `fn` runs uncounted.
+ */
+export function runWithApiBudget<T>(budget: ApiBudget | undefined, fn: () => T): T {
+  return budget && !currentApiBudget() ? budgetContext.run(budget, fn) : fn();
+}
+
+/** Hour windows currently tracked. Tests and diagnostics. */
+export function trackedHourlyBudgets(): number {
+  return hourlyCalls.size;
+}
+
+/** Forget hourly counts for every account. Tests only. */
+export function resetApiBudgets(): void {
+  hourlyCalls.clear();
+  lastPrunedAt = 0;
+}
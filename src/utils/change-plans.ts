@@ -0,0 +1,164 @@
+/**
+ * Plan/act contract for write tools.
+ *
+ * Calling harness_create, harness_update, harness_delete, or harness_execute
+ * with `plan_only: true` resolves the request exactly as a real call would —
+ * identifiers, scope, merged params, risk — and returns it as a change plan
+ * with a `plan_hash` instead of writing anything. The plan is the approval
+ * artifact: a reviewer (or a governance pipeline) inspects it, and the agent
+ * then repeats the call with the same arguments plus `plan_hash` to apply it.
+ *
+ * Issued plans live in memory on the MCP session that requested them, so
+ * another session — even on the same account — cannot redeem them. The hash
+ * covers the canonical plan plus a random nonce: it cannot be recomputed from
+ * the arguments, and it only matches a call that would perform the same
+ * change. Plans are single-use and expire after an hour.
+ */
+
+import { createHash, randomBytes } from "node:crypto";
+import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import type { RiskLevel } from "../registry/types.js";
+
+/** Plans can be applied for one hour after they are issued. */
+export const PLAN_TTL_MS = 60 * 60 * 1000;
+
+/** At most this many unapplied plans are held per session; the oldest are evicted first. */
+const MAX_PLANS = 200;
+
+/** Arguments that control the contract itself and are not part of the change. */
+const CONTRACT_FIELDS = new Set(["plan_only", "plan_hash", "confirm", "confirmation_token"]);
+
+export interface ChangePlanRequest {
+  tool: string;
+  /** "create", "update", "delete", or the execute action name. */
+  operation: string;
+  resourceType: string;
+  resourceId?: string;
+  risk: RiskLevel;
+  /** The resolved input map the tool would dispatch. */
+  input: Record<string, unknown>;
+}
+
+export interface ChangePlan {
+  version: 1;
+  tool: string;
+  operation: string;
+  resource_type: string;
+  resource_id?: string;
+  account_id: string;
+  org_id?: string;
+  project_id?: string;
+  risk: RiskLevel;
+  input: Record<string, unknown>;
+}
+
+interface IssuedPlan {
+  owner: string;
+  /** Digest of the canonical plan, without the nonce. */
+  digest: string;
+  expiresAt: number;
+}
+
+/** JSON with object keys sorted at every level, so equal plans serialize identically. */
+function canonicalJson(value: unknown): string {
+  if (Array.isArray(value)) return `[${value.map(canonicalJson).join(",")}]`;
+  if (value !== null && typeof value === "object") {
+    const entries = Object.keys(value as Record<string, unknown>)
+      .filter((key) => (value as Record<string, unknown>)[key] !== undefined)
+      .sort()
+      .map((key) => `${JSON.stringify(key)}:${canonicalJson((value as Record<string, unknown>)[key])}`);
+    return `{${entries.join(",")}}`;
+  }
+  return JSON.stringify(value) ?? "null";
+}
+
+/** Build the plan for a request. `owner` is the Harness account the change runs against. */
+export function buildChangePlan(request: ChangePlanRequest, owner: string): ChangePlan {
+  const input: Record<string, unknown> = {};
+  for (const [key, value] of Object.entries(request.input)) {
+    if (!CONTRACT_FIELDS.has(key) && value !== undefined) input[key] = value;
+  }
+  const plan: ChangePlan = {
+    version: 1,
+    tool: request.tool,
+    operation: request.operation,
+    resource_type: request.resourceType,
+    account_id: owner,
+    risk: request.risk,
+    input,
+  };
+  if (request.resourceId) plan.resource_id = request.resourceId;
+  if (typeof input.org_id === "string") plan.org_id = input.org_id;
+  if (typeof input.project_id === "string") plan.project_id = input.project_id;
+  return plan;
+}
+
+/** Digest of the canonical plan; equal for calls that would make the same change. */
+export function digestChangePlan(plan: ChangePlan): string {
+  return createHash("sha256").update(canonicalJson(plan)).digest("hex");
+}
+
+/** Issued plans for one MCP session. */
+export class ChangePlanStore {
+  private issued = new Map<string, IssuedPlan>();
+
+  private prune(now: number): void {
+    for (const [hash, entry] of this.issued) {
+      if (entry.expiresAt <= now) this.issued.delete(hash);
+    }
+  }
+
+  /** Issue a plan for `owner` and return the tool result payload. */
+  issue(request: ChangePlanRequest, owner: string): Record<string, unknown> {
+    const now = Date.now();
+    this.prune(now);
+    const plan = buildChangePlan(request, owner);
+    const digest = digestChangePlan(plan);
+    const hash = createHash("sha256").update(digest).update(randomBytes(16)).digest("hex");
+    for (const oldest of this.issued.keys()) {
+      if (this.issued.size < MAX_PLANS) break;
+      this.issued.delete(oldest);
+    }
+    const expiresAt = now + PLAN_TTL_MS;
+    this.issued.set(hash, { owner, digest, expiresAt });
+    return {
+      plan_only: true,
+      plan_hash: hash,
+      plan,
+      expires_at: new Date(expiresAt).toISOString(),
+      next_step: `Have the plan reviewed, then call ${request.tool} again with the same arguments plus plan_hash (instead of plan_only) to apply it. Any change to the arguments invalidates the hash.`,
+    };
+  }
+
+  /**
+   * Check `planHash` against the plan this call would produce and consume it.
+   * Returns an error message when the hash was never issued to `owner` in
+   * this session, has expired or been used, or does not match the current
+   * arguments.
+   */
+  consume(request: ChangePlanRequest, owner: string, planHash: string): string | undefined {
+    this.prune(Date.now());
+    const hash = planHash.trim().toLowerCase();
+    const entry = this.issued.get(hash);
+    if (!entry || entry.owner !== owner) {
+      return `plan_hash is unknown, already applied, or expired (plans are valid for ${PLAN_TTL_MS / 60_000} minutes in the session that requested them). Call ${request.tool} with plan_only=true to get a fresh plan.`;
+    }
+    if (digestChangePlan(buildChangePlan(request, owner)) !== entry.digest) {
+      return `plan_hash does not match these arguments — the change differs from the approved plan. Repeat the planned arguments exactly, or call ${request.tool} with plan_only=true to plan the new change.`;
+    }
+    this.issued.delete(hash);
+    return undefined;
+  }
+}
+
+const stores = new WeakMap<McpServer, ChangePlanStore>();
+
+/** The plan store for a session's server — shared by all write tools registered on it. */
+export function changePlansFor(server: McpServer): ChangePlanStore {
+  let store = stores.get(server);
+  if (!store) {
+    store = new ChangePlanStore();
+    stores.set(server, store);
+  }
+  return store;
+}
@@ -0,0 +1,109 @@
+/**
+ * Checkpoints for long operations (bulk exports, account-wide scans).
+ *
+ * An operation saves its progress after each unit of work. If the call is
+ * interrupted — client disconnect, tool deadline, transport hiccup — the
+ * caller re-runs it with the same arguments plus `resume_token`, and the
+ * operation continues from the last checkpoint instead of starting over.
+ *
+ * The token is the operation ID. It is reported in progress notifications,
+ * so it survives in a TOOL_TIMEOUT error's last progress update, and in the
+ * final result. Checkpoints live in memory, belong to the caller's account,
+ * and are bound to the arguments they were created with.
+ */
+
+import { createHash, randomBytes } from "node:crypto";
+
+/** Checkpoints expire 30 minutes after their last save. */
+export const CHECKPOINT_TTL_MS = 30 * 60 * 1000;
+
+/**
+ * At most this many checkpoints are held per account; its least recently
+ * saved are evicted first, so one tenant cannot push out another's.
+ */
+const MAX_CHECKPOINTS_PER_OWNER = 50;
+
+interface StoredCheckpoint {
+  kind: string;
+  owner: string;
+  fingerprint: string;
+  state: unknown;
+  expiresAt: number;
+}
+
+const checkpoints = new Map<string, StoredCheckpoint>();
+
+/** Drop all checkpoints (useful for testing). */
+export function resetCheckpoints(): void {
+  checkpoints.clear();
+}
+
+function fingerprint(params: Record<string, unknown>): string {
+  const sorted = Object.keys(params).sort().map((key) => [key, params[key]]);
+  return createHash("sha256").update(JSON.stringify(sorted)).digest("hex");
+}
+
+function prune(now: number): void {
+  for (const [id, entry] of checkpoints) {
+    if (entry.expiresAt <= now) checkpoints.delete(id);
+  }
+}
+
+export interface Operation<S> {
+  /** Pass back as `resume_token` to continue after an interruption. */
+  readonly id: string;
+  /** State from the last checkpoint when resuming, otherwise undefined. */
+  readonly resumed: S | undefined;
+  /** Save progress. Cheap enough to call after every batch. */
+  save(state: S): void;
+  /** Drop the checkpoint once the operation has produced its result. */
+  complete(): void;
+}
+
+/**
+ * Start an operation, or resume one when `resumeToken` is given. `params` are
+ * the arguments that shape the work; resuming with different ones is refused,
+ * since the saved progress would not apply. Throws a user-facing error for an
+ * unknown, expired, or mismatched token.
+ */
+export function beginOperation<S>(
+  kind: string,
+  owner: string,
+  params: Record<string, unknown>,
+  resumeToken?: string,
+): Operation<S> {
+  prune(Date.now());
+  const print = fingerprint(params);
+  let id: string;
+  let resumed: S | undefined;
+  if (resumeToken) {
+    const entry = checkpoints.get(resumeToken.trim());
+    if (!entry || entry.owner !== owner || entry.kind !== kind) {
+      throw new Error(`resume_token is unknown or has expired (checkpoints are kept ${CHECKPOINT_TTL_MS / 60_000} minutes). Re-run without resume_token to start over.`);
+    }
+    if (entry.fingerprint !== print) {
+      throw new Error("resume_token belongs to a run with different arguments. Repeat the original arguments, or omit resume_token to start over.");
+    }
+    id = resumeToken.trim();
+    resumed = entry.state as S;
+  } else {
+    id = randomBytes(12).toString("hex");
+  }
+
+  return {
+    id,
+    resumed,
+    save(state: S): void {
+      checkpoints.delete(id);
+      // Re-inserting keeps Map order = least recently saved first
+      const owned = [...checkpoints].filter(([, entry]) => entry.owner === owner).map(([key]) => key);
+      for (const oldest of owned.slice(0, Math.max(0, owned.length - MAX_CHECKPOINTS_PER_OWNER + 1))) {
+        checkpoints.delete(oldest);
+      }
+      checkpoints.set(id, { kind, owner, fingerprint: print, state, expiresAt: Date.now() + CHECKPOINT_TTL_MS });
+    },
+    complete(): void {
+      checkpoints.delete(id);
+    },
+  };
+}
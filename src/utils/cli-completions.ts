@@ -0,0 +1,159 @@
+/**
+ * Shell completion scripts and the man page for `harness-mcp-v2 completions`.
+ * Generated from the same command and option tables as `--help`, so new flags
+ * show up everywhere at once.
+ *
+ *   harness-mcp-v2 completions bash > /etc/bash_completion.d/harness-mcp-v2
+ *   harness-mcp-v2 completions zsh  > "${fpath[1]}/_harness-mcp-v2"
+ *   harness-mcp-v2 completions fish > ~/.config/fish/completions/harness-mcp-v2.fish
+ *   harness-mcp-v2 completions man  > /usr/local/share/man/man1/harness-mcp-v2.1
+ */
+
+import { ALL_TOOLSET_NAMES } from "../registry/index.js";
+import { TOOLSET_PRESET_NAMES } from "../registry/toolset-presets.js";
+import { CLI_COMMANDS, CLI_OPTIONS, CLI_TRANSPORTS, type CompletionShell } from "./cli.js";
+
+const BIN = "harness-mcp-v2";
+
+/** The MCP tools, completed after `call` and `tools schema`. */
+export const TOOL_NAMES = [
+  "harness_list",
+  "harness_get",
+  "harness_create",
+  "harness_update",
+  "harness_delete",
+  "harness_execute",
+  "harness_diagnose",
+  "harness_search",
+  "harness_describe",
+  "harness_status",
+  "harness_schema",
+] as const;
+
+const TOP_LEVEL = [...CLI_TRANSPORTS, "config", "tools", "call", "fixtures", "self-test", "completions"];
+const SUBCOMMANDS: Record<string, string[]> = {
+  config: ["env"],
+  tools: ["list", "schema"],
+  fixtures: ["record"],
+  completions: ["bash", "zsh", "fish", "man"],
+};
+const FLAGS = CLI_OPTIONS.map((o) => o.flag);
+
+function bashCompletion(): string {
+  const valueFlags = CLI_OPTIONS.filter((o) => o.value).map((o) => o.flag).join("|");
+  return `# bash completion for ${BIN}
+_${BIN.replace(/-/g, "_")}() {
+  local cur prev first second
+  cur="\${COMP_WORDS[COMP_CWORD]}"
+  prev="\${COMP_WORDS[COMP_CWORD-1]}"
+  first="\${COMP_WORDS[1]}"
+  second="\${COMP_WORDS[2]}"
+
+  case "$prev" in
+    --env-file|--socket-path|--mock-fixtures) COMPREPLY=( $(compgen -f -- "$cur") ); return ;;
+    ${valueFlags}) COMPREPLY=(); return ;;
+  esac
+
+  if [[ "$cur" == -* ]]; then
+    COMPREPLY=( $(compgen -W "${FLAGS.join(" ")}" -- "$cur") )
+    return
+  fi
+
+  if [[ $COMP_CWORD -eq 1 ]]; then
+    COMPREPLY=( $(compgen -W "${TOP_LEVEL.join(" ")}" -- "$cur") )
+    return
+  fi
+
+  case "$first" in
+${Object.entries(SUBCOMMANDS).map(([cmd, subs]) => `    ${cmd}) [[ $COMP_CWORD -eq 2 ]] && COMPREPLY=( $(compgen -W "${subs.join(" ")}" -- "$cur") ) ;;`).join("\n")}
+    call) [[ $COMP_CWORD -eq 2 ]] && COMPREPLY=( $(compgen -W "${TOOL_NAMES.join(" ")}" -- "$cur") ) ;;
+  esac
+  if [[ "$first" == "tools" && "$second" == "schema" && $COMP_CWORD -eq 3 ]]; then
+    COMPREPLY=( $(compgen -W "${TOOL_NAMES.join(" ")}" -- "$cur") )
+  fi
+}
+complete -F _${BIN.replace(/-/g, "_")} ${BIN}
+`;
+}
+
+const zshEscape = (text: string): string => text.replace(/'/g, "'\\''").replace(/[[\]:]/g, "\\$&");
+
+function zshCompletion(): string {
+  const options = CLI_OPTIONS.map((o) => {
+    const action = o.value === "path" ? ":path:_files" : o.value ? `:${o.value}:` : "";
+    return `    '${o.flag}[${zshEscape(o.description)}]${action}'`;
+  });
+  return `#compdef ${BIN}
+# zsh completion for ${BIN}
+_${BIN.replace(/-/g, "_")}() {
+  local -a toplevel tools
+  toplevel=(${TOP_LEVEL.join(" ")})
+  tools=(${TOOL_NAMES.join(" ")})
+  _arguments -C \\
+${options.join(" \\\n")} \\
+    '1:command:->command' \\
+    '2:subcommand:->subcommand' \\
+    '3:argument:->argument'
+  case $state in
+    command) compadd -a toplevel ;;
+    subcommand)
+      case $words[2] in
+${Object.entries(SUBCOMMANDS).map(([cmd, subs]) => `        ${cmd}) compadd ${subs.join(" ")} ;;`).join("\n")}
+        call) compadd -a tools ;;
+      esac ;;
+    argument)
+      [[ $words[2] == tools && $words[3] == schema ]] && compadd -a tools ;;
+  esac
+}
+_${BIN.replace(/-/g, "_")} "$@"
+`;
+}
+
+function fishCompletion(): string {
+  const lines = [`# fish completion for ${BIN}`, `complete -c ${BIN} -f`];
+  lines.push(`complete -c ${BIN} -n "__fish_use_subcommand" -a "${TOP_LEVEL.join(" ")}"`);
+  for (const [cmd, subs] of Object.entries(SUBCOMMANDS)) {
+    lines.push(`complete -c ${BIN} -n "__fish_seen_subcommand_from ${cmd}; and not __fish_seen_subcommand_from ${subs.join(" ")}" -a "${subs.join(" ")}"`);
+  }
+  lines.push(`complete -c ${BIN} -n "__fish_seen_subcommand_from call schema; and not __fish_seen_subcommand_from ${TOOL_NAMES.join(" ")}" -a "${TOOL_NAMES.join(" ")}"`);
+  for (const o of CLI_OPTIONS) {
+    const value = o.value === "path" ? " -r -F" : o.value ? " -r" : "";
+    lines.push(`complete -c ${BIN} -l ${o.flag.slice(2)}${value} -d "${o.description.replace(/"/g, '\\"')}"`);
+  }
+  return `${lines.join("\n")}\n`;
+}
+
+const roff = (text: string): string => text.replace(/\\/g, "\\e").replace(/-/g, "\\-").replace(/^\./gm, "\\&.");
+
+function manPage(version: string): string {
+  return `.TH ${BIN.toUpperCase()} 1 "" "${BIN} ${version}" "User Commands"
+.SH NAME
+${BIN} \\- MCP server for the Harness.io platform
+.SH SYNOPSIS
+.B ${BIN}
+[\\fI${CLI_TRANSPORTS.join("|")}\\fR] [\\fIoptions\\fR]
+.br
+.B ${BIN}
+\\fIcommand\\fR [\\fIarguments\\fR] [\\fIoptions\\fR]
+.SH DESCRIPTION
+Serves Harness resources (pipelines, services, connectors, and more) to MCP clients over stdio, HTTP, or a Unix socket, and to internal services over gRPC. The transport defaults to stdio. Configuration is read from HARNESS_* environment variables and an optional .env file; run \\fB${BIN} config env\\fR to list them.
+.SH COMMANDS
+${CLI_COMMANDS.map((c) => `.TP\n.B ${roff(c.usage)}\n${roff(c.description)}`).join("\n")}
+.SH OPTIONS
+${CLI_OPTIONS.map((o) => `.TP\n.B ${roff(o.flag)}${o.value ? ` \\fI${o.value}\\fR` : ""}\n${roff(o.description)}`).join("\n")}
+.SH TOOLSETS
+Select with HARNESS_TOOLSETS or \\fB--toolsets\\fR (comma-separated): ${ALL_TOOLSET_NAMES.join(", ")}. Presets: all, ${TOOLSET_PRESET_NAMES.join(", ")}.
+.SH EXIT STATUS
+0 on success; 1 on a configuration error, a fatal server error, or a tool error reported by \\fBcall\\fR.
+`;
+}
+
+/** Completion script for `shell`, or the man page for "man". */
+export function generateCompletions(shell: CompletionShell, version: string): string {
+  switch (shell) {
+    case "bash": return bashCompletion();
+    case "zsh": return zshCompletion();
+    case "fish": return fishCompletion();
+    case "man": return manPage(version);
+  }
+}
@@ -6,33 +6,95 @@ import { dirname, resolve } from "node:path";
  * CLI argument parsing for transport selection and port configuration.
  */
 
-export type Transport = "stdio" | "http";
+export type Transport = "stdio" | "http" | "socket" | "grpc";
+
+/** Offline subcommands that run instead of starting a server. */
+export type CliCommand =
+  | { name: "config-env" }
+  | { name: "tools-list" }
+  | { name: "tools-schema"; tool: string }
+  | { name: "call"; tool: string; args: Record<string, unknown> }
+  | { name: "fixtures-record"; dir: string }
+  | { name: "self-test" }
+  | { name: "completions"; shell: CompletionShell };
+
+export type CompletionShell = "bash" | "zsh" | "fish" | "man";
+const COMPLETION_SHELLS = new Set<string>(["bash", "zsh", "fish", "man"]);
 
 export interface CliArgs {
   transport: Transport;
+  /** Subcommand to run instead of serving (e.g. `config env`). */
+  command?: CliCommand;
   port: number;
   envFile?: string;
+  /** --smp: target a Harness Self-Managed Platform install (HARNESS_DEPLOYMENT=smp). */
+  smp: boolean;
+  /** --socket-path: Unix domain socket to listen on (socket transport). */
+  socketPath?: string;
+  /** --strict-schemas: validate registry inputs and tool outputs against declared schemas (HARNESS_STRICT_SCHEMAS=true). */
+  strictSchemas: boolean;
+  /** --toolsets: toolset list or preset name, overriding HARNESS_TOOLSETS. */
+  toolsets?: string;
+  /** --mock: serve canned fixtures instead of calling Harness (HARNESS_MOCK=true). */
+  mock: boolean;
+  /** --mock-fixtures: fixture directory for mock mode (HARNESS_MOCK_FIXTURES_DIR); implies --mock. */
+  mockFixtures?: string;
 }
 
-const VALID_TRANSPORTS = new Set<string>(["stdio", "http"]);
 const DEFAULT_PORT = 3000;
 const MIN_PORT = 1;
 const MAX_PORT = 65535;
 
-const HELP_TEXT = `
-harness-mcp-server — MCP server for Harness.io CI/CD platform
+/** Subcommands, shared by the help text, shell completions, and the man page. */
+export const CLI_COMMANDS: ReadonlyArray<{ usage: string; description: string }> = [
+  { usage: "config env", description: "List every supported environment variable with its current value (secrets masked) and the effective service routes" },
+  { usage: "tools list", description: "List the tools and, per toolset, the resource operations exposed to agents (read/write), without starting a server" },
+  { usage: "tools schema <tool>", description: "Print a tool's input/output JSON Schema and annotations" },
+  { usage: "call <tool>", description: "Run one tool with --args (a JSON object) and print the result as JSON; exits 1 when the tool reports an error" },
+  { usage: "fixtures record <dir>", description: "Run the read tools against the configured account and save the API responses, sanitized, as mock-mode/WireMock fixtures in <dir>" },
+  { usage: "self-test", description: "Start the server in-process, check MCP protocol conformance and each toolset's connectivity to Harness, and print a report; exits 1 on failure" },
+  { usage: "completions <shell>", description: "Print a completion script for bash, zsh, or fish, or the man page with \"man\"" },
+];
 
-Usage:
-  harness-mcp-server [stdio|http] [options]
+/** Options, shared by the help text, shell completions, and the man page. */
+export const CLI_OPTIONS: ReadonlyArray<{ flag: string; value?: string; description: string }> = [
+  { flag: "--port", value: "number", description: "Port for the HTTP or gRPC transport (default: 3000, or PORT env var)" },
+  { flag: "--socket-path", value: "path", description: "Listen on a Unix domain socket (implies the socket transport)" },
+  { flag: "--env-file", value: "path", description: "Path to .env file (default: .env in current directory)" },
+  { flag: "--smp", description: "Use the Self-Managed Platform gateway layout (HARNESS_DEPLOYMENT=smp)" },
+  { flag: "--toolsets", value: "list", description: "Toolsets to enable, or a preset such as \"recommended\" or \"all\" (HARNESS_TOOLSETS)" },
+  { flag: "--mock", description: "Serve canned fixture responses instead of calling Harness; no credentials needed (HARNESS_MOCK=true)" },
+  { flag: "--mock-fixtures", value: "path", description: "Directory of WireMock mapping files for mock mode; implies --mock (HARNESS_MOCK_FIXTURES_DIR)" },
+  { flag: "--strict-schemas", description: "Validate inputs and outputs against declared schemas (HARNESS_STRICT_SCHEMAS=true)" },
+  { flag: "--args", value: "json", description: "Tool arguments for call, as a JSON object" },
+  { flag: "--help", description: "Show this help message and exit" },
+  { flag: "--version", description: "Print version and exit" },
+];
 
-Options:
-  --port <number>       Port for HTTP transport (default: 3000, or PORT env var)
-  --env-file <path>     Path to .env file (default: .env in current directory)
-  --help                Show this help message and exit
-  --version             Print version and exit
+export const CLI_TRANSPORTS = ["stdio", "http", "socket", "grpc"] as const;
+const VALID_TRANSPORTS = new Set<string>(CLI_TRANSPORTS);
 
-Transport defaults to "stdio" if not specified.
-`.trim();
+const HELP_TEXT = [
+  "harness-mcp-server — MCP server for Harness.io CI/CD platform",
+  "",
+  "Usage:",
+  "  harness-mcp-server [stdio|http|socket|grpc] [options]",
+  "  harness-mcp-server [stdio|http|socket|grpc] --mock [--mock-fixtures <dir>]",
+  "  harness-mcp-server config env [--env-file <path>]",
+  "  harness-mcp-server tools list|schema <tool> [--env-file <path>]",
+  "  harness-mcp-server call <tool> [--args '<json>'] [--env-file <path>]",
+  "  harness-mcp-server fixtures record <dir> [--toolsets <list>] [--env-file <path>]",
+  "  harness-mcp-server self-test [--toolsets <list>] [--env-file <path>]",
+  "  harness-mcp-server completions bash|zsh|fish|man",
+  "",
+  "Commands:",
+  ...CLI_COMMANDS.map((c) => `  ${c.usage.padEnd(22)}${c.description}`),
+  "",
+  "Options:",
+  ...CLI_OPTIONS.map((o) => `  ${`${o.flag}${o.value ? ` <${o.value}>` : ""}`.padEnd(22)}${o.description}`),
+  "",
+  'Transport defaults to "stdio" if not specified.',
+].join("\n");
 
 export function getVersion(): string {
   try {
@@ -49,7 +111,7 @@ export function getVersion(): string {
  * Parse CLI arguments for transport mode and port.
  *
  * Usage:
- *   node build/index.js [stdio|http] [--port <number>]
+ *   node build/index.js [stdio|http|grpc] [--port <number>]
  *
  * - Transport defaults to "stdio" if not specified.
  * - Port defaults to --port flag, then PORT env var, then 3000.
@@ -66,30 +128,120 @@ export function parseArgs(argv: string[] = process.argv.slice(2)): CliArgs {
     process.exit(0);
   }
 
+  const command = parseCommand(argv);
+  const toolsets = parseToolsets(argv);
+  const mockFixtures = parseMockFixtures(argv);
+  const mock = argv.includes("--mock") || mockFixtures !== undefined;
+  if (command) {
+    return { transport: "stdio", port: resolvePort(argv), envFile: parseEnvFile(argv), smp: argv.includes("--smp"), strictSchemas: false, ...(toolsets ? { toolsets } : {}), mock, ...(mockFixtures ? { mockFixtures } : {}), command };
+  }
+
   const transport = parseTransport(argv);
   const port = resolvePort(argv);
   const envFile = parseEnvFile(argv);
-  return { transport, port, envFile };
+  const smp = argv.includes("--smp");
+  const strictSchemas = argv.includes("--strict-schemas");
+  const socketPath = parseSocketPath(argv);
+  if (transport === "socket" && !socketPath) {
+    throw new Error("The socket transport requires --socket-path <path>.");
+  }
+  if (socketPath && (transport === "http" || transport === "grpc")) {
+    throw new Error(`--socket-path cannot be combined with the ${transport} transport.`);
+  }
+  return { transport: socketPath ? "socket" : transport, port, envFile, smp, strictSchemas, ...(socketPath ? { socketPath } : {}), ...(toolsets ? { toolsets } : {}), mock, ...(mockFixtures ? { mockFixtures } : {}) };
 }
 
-function parseTransport(argv: string[]): Transport {
-  // First positional arg that isn't a flag or flag value
+const FLAGS_WITH_VALUES = new Set(["--port", "--env-file", "--socket-path", "--toolsets", "--mock-fixtures", "--args"]);
+
+function positionalArgs(argv: string[]): string[] {
+  const positionals: string[] = [];
   for (let i = 0; i < argv.length; i++) {
     const arg = argv[i]!;
-    if (arg === "--port" || arg === "--env-file") {
+    if (FLAGS_WITH_VALUES.has(arg)) {
       i++; // skip the value after the flag
       continue;
     }
-    if (arg.startsWith("-")) continue;
+    if (!arg.startsWith("-")) positionals.push(arg);
+  }
+  return positionals;
+}
 
-    if (!VALID_TRANSPORTS.has(arg)) {
-      throw new Error(
-        `Unknown transport: "${arg}". Supported: stdio, http`,
-      );
+function parseCommand(argv: string[]): CliCommand | undefined {
+  const [first, second, third] = positionalArgs(argv);
+  if (first === "config") {
+    if (second === "env") return { name: "config-env" };
+    throw new Error(`Unknown config subcommand: "${second ?? ""}". Supported: config env`);
+  }
+  if (first === "tools") {
+    if (second === "list") return { name: "tools-list" };
+    if (second === "schema") {
+      if (!third) throw new Error("tools schema requires a tool name, e.g. tools schema harness_list");
+      return { name: "tools-schema", tool: third };
     }
-    return arg as Transport;
+    throw new Error(`Unknown tools subcommand: "${second ?? ""}". Supported: tools list, tools schema <tool>`);
+  }
+  if (first === "completions") {
+    if (!second || !COMPLETION_SHELLS.has(second)) {
+      throw new Error(`completions requires one of: bash, zsh, fish, man (got "${second ?? ""}")`);
+    }
+    return { name: "completions", shell: second as CompletionShell };
+  }
+  if (first === "self-test") return { name: "self-test" };
+  if (first === "fixtures") {
+    if (second !== "record") throw new Error(`Unknown fixtures subcommand: "${second ?? ""}". Supported: fixtures record <dir>`);
+    if (!third) throw new Error("fixtures record requires an output directory, e.g. fixtures record ./fixtures");
+    return { name: "fixtures-record", dir: third };
+  }
+  if (first === "call") {
+    if (!second) throw new Error("call requires a tool name, e.g. call harness_list --args '{\"resource_type\":\"pipeline\"}'");
+    return { name: "call", tool: second, args: parseToolArgs(argv) };
+  }
+  return undefined;
+}
+
+function parseToolArgs(argv: string[]): Record<string, unknown> {
+  let raw: string | undefined;
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i]!;
+    if (arg.startsWith("--args=")) raw = arg.slice("--args=".length);
+    else if (arg === "--args" && i + 1 < argv.length) raw = argv[i + 1]!;
+  }
+  if (raw === undefined || raw.trim() === "") return {};
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(raw);
+  } catch (err) {
+    throw new Error(`--args is not valid JSON: ${err instanceof Error ? err.message : String(err)}`);
+  }
+  if (typeof parsed !== "object" || parsed === null || Array.isArray(parsed)) {
+    throw new Error("--args must be a JSON object of tool arguments.");
+  }
+  return parsed as Record<string, unknown>;
+}
+
+function parseTransport(argv: string[]): Transport {
+  // First positional arg that isn't a flag or flag value
+  const arg = positionalArgs(argv)[0];
+  if (arg === undefined) return "stdio";
+  if (!VALID_TRANSPORTS.has(arg)) {
+    throw new Error(
+      `Unknown transport: "${arg}". Supported: stdio, http, socket, grpc`,
+    );
+  }
+  return arg as Transport;
+}
+
+/** Transports that take per-session or per-call credentials, and so can run multi-user mode. */
+export const MULTI_USER_TRANSPORTS: ReadonlySet<Transport> = new Set(["http", "grpc"]);
+
+/** Throw when `transport` cannot serve `mode`. */
+export function assertTransportSupportsMode(transport: Transport, mode: string): void {
+  if (mode === "multi-user" && !MULTI_USER_TRANSPORTS.has(transport)) {
+    throw new Error(
+      "Multi-user mode is only supported with the HTTP or gRPC transport. " +
+      `Use --transport http or grpc, or set HARNESS_MCP_MODE=single-user for ${transport}.`,
+    );
   }
-  return "stdio";
 }
 
 export function resolvePort(argv: string[] = process.argv.slice(2)): number {
@@ -132,3 +284,42 @@ function parseEnvFile(argv: string[]): string | undefined {
 
   return undefined;
 }
+
+function parseSocketPath(argv: string[]): string | undefined {
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i]!;
+    if (arg.startsWith("--socket-path=")) {
+      return arg.slice("--socket-path=".length) || undefined;
+    }
+    if (arg === "--socket-path" && i + 1 < argv.length) {
+      return argv[i + 1]!;
+    }
+  }
+  return undefined;
+}
+
+function parseToolsets(argv: string[]): string | undefined {
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i]!;
+    if (arg.startsWith("--toolsets=")) {
+      return arg.slice("--toolsets=".length).trim() || undefined;
+    }
+    if (arg === "--toolsets" && i + 1 < argv.length) {
+      return argv[i + 1]!.trim() || undefined;
+    }
+  }
+  return undefined;
+}
+
+function parseMockFixtures(argv: string[]): string | undefined {
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i]!;
+    if (arg.startsWith("--mock-fixtures=")) {
+      return arg.slice("--mock-fixtures=".length) || undefined;
+    }
+    if (arg === "--mock-fixtures" && i + 1 < argv.length) {
+      return argv[i + 1]!;
+    }
+  }
+  return undefined;
+}
@@ -0,0 +1,59 @@
+/**
+ * Per-account API client pool for multi-user (gateway) HTTP deployments.
+ *
+ * Sessions that present the same credential for the same account share one
+ * client — and with it the rate limiter, keep-alive connections, and cached
+ * current-user lookup — instead of each session building its own. Pool keys
+ * include the account, base URL, and a hash of the API key, so sessions from
+ * different accounts (or different users in one account) never share a client.
+ */
+
+import { createHash } from "node:crypto";
+import type { Config } from "../config.js";
+
+interface PoolEntry<T> {
+  client: T;
+  accountId: string;
+  refs: number;
+}
+
+/** Stable pool key — never contains the raw API key. */
+export function clientPoolKey(config: Pick<Config, "HARNESS_ACCOUNT_ID" | "HARNESS_BASE_URL" | "HARNESS_API_KEY">): string {
+  const credential = createHash("sha256").update(config.HARNESS_API_KEY ?? "").digest("hex").slice(0, 16);
+  return `${config.HARNESS_ACCOUNT_ID ?? ""}|${config.HARNESS_BASE_URL}|${credential}`;
+}
+
+export class ClientPool<T> {
+  private readonly entries = new Map<string, PoolEntry<T>>();
+
+  constructor(private readonly factory: (config: Config) => T) {}
+
+  /** Get (or create) the client for this session's account and credential. Pair with `release`. */
+  acquire(config: Config): T {
+    const key = clientPoolKey(config);
+    let entry = this.entries.get(key);
+    if (!entry) {
+      entry = { client: this.factory(config), accountId: config.HARNESS_ACCOUNT_ID ?? "", refs: 0 };
+      this.entries.set(key, entry);
+    }
+    entry.refs++;
+    return entry.client;
+  }
+
+  /** Drop a session's reference; the client is discarded when no session uses it. */
+  release(config: Config): void {
+    const key = clientPoolKey(config);
+    const entry = this.entries.get(key);
+    if (!entry) return;
+    entry.refs--;
+    if (entry.refs <= 0) this.entries.delete(key);
+  }
+
+  /** Pooled clients and distinct accounts, for health reporting. */
+  stats(): { clients: number; accounts: number } {
+    return {
+      clients: this.entries.size,
+      accounts: new Set([...this.entries.values()].map((e) => e.accountId)).size,
+    };
+  }
+}
@@ -55,6 +55,15 @@ function isWhitelistedKey(key: string): boolean {
   );
 }
 
+export interface CompactOptions {
+  /**
+   * "strip" (default) drops fields compaction does not surface. "include"
+   * keeps them under `_extra` so no API data is lost while the essentials
+   * stay at the top level.
+   */
+  unknownFields?: "strip" | "include";
+}
+
 /**
  * Strip verbose fields from an array of list items.
  * Keeps identity, status, type, ownership, timestamp, and deep link fields.
@@ -68,10 +77,16 @@ function isWhitelistedKey(key: string): boolean {
 export function compactItems(
   items: unknown[],
   compactFn?: (item: Record<string, unknown>) => Record<string, unknown>,
+  options: CompactOptions = {},
 ): unknown[] {
+  const includeUnknown = options.unknownFields === "include";
   return items.map((item) => {
     if (typeof item !== "object" || item === null || Array.isArray(item)) return item;
-    if (compactFn) return mergeOpenInHarness(compactFn(item as Record<string, unknown>));
+    if (compactFn) {
+      const full = item as Record<string, unknown>;
+      const slim = compactFn(full);
+      return mergeOpenInHarness(includeUnknown ? attachExtra(full, slim) : slim);
+    }
     const full = item as Record<string, unknown>;
     const slim: Record<string, unknown> = {};
     for (const key of Object.keys(full)) {
@@ -80,10 +95,19 @@ export function compactItems(
       }
     }
 
-    return mergeOpenInHarness(slim);
+    return mergeOpenInHarness(includeUnknown ? attachExtra(full, slim) : slim);
   });
 }
 
+/** Collect fields of `full` that compaction left out into `_extra` (omitted when there are none). */
+function attachExtra(full: Record<string, unknown>, slim: Record<string, unknown>): Record<string, unknown> {
+  const extra: Record<string, unknown> = {};
+  for (const [key, value] of Object.entries(full)) {
+    if (!(key in slim) && key !== "openInHarness") extra[key] = value;
+  }
+  return Object.keys(extra).length > 0 ? { ...slim, _extra: extra } : slim;
+}
+
 /** Merge deep link into name as markdown hyperlink, then drop the separate field. */
 function mergeOpenInHarness(slim: Record<string, unknown>): Record<string, unknown> {
   if (typeof slim.openInHarness === "string" && typeof slim.name === "string") {
@@ -0,0 +1,136 @@
+/**
+ * Argument autocompletion (`completion/complete`) backed by the live catalog.
+ *
+ * Prompt arguments and resource-template variables that name Harness
+ * entities — org, project, pipeline, repository — complete from a list call
+ * scoped by the other arguments already filled in (or the configured
+ * defaults). Lists are cached briefly so completing as the user types does
+ * not issue one API call per keystroke.
+ */
+
+import type * as z from "zod/v4";
+import { completable } from "@modelcontextprotocol/sdk/server/completable.js";
+import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import type { Registry } from "../registry/index.js";
+import type { HarnessClient } from "../client/harness-client.js";
+import type { Config } from "../config.js";
+import { asRecord, asString, isRecord } from "./type-guards.js";
+import { createLogger } from "./logger.js";
+
+const log = createLogger("completions");
+
+/** MCP caps completion responses at 100 values. */
+const MAX_COMPLETIONS = 100;
+const CACHE_TTL_MS = 60_000;
+
+/** Argument names (prompt camelCase and tool snake_case) → the resource type that lists their values. */
+const COMPLETABLE_ARGUMENTS: Record<string, string> = {
+  orgId: "organization",
+  org_id: "organization",
+  projectId: "project",
+  project_id: "project",
+  pipelineId: "pipeline",
+  pipeline_id: "pipeline",
+  repoId: "repository",
+  repo_id: "repository",
+};
+
+export interface CompletionContext {
+  arguments?: Record<string, string>;
+}
+
+export type ArgumentCompleter = (argument: string, value: string, context?: CompletionContext) => Promise<string[]>;
+
+export function isCompletableArgument(argument: string): boolean {
+  return argument in COMPLETABLE_ARGUMENTS;
+}
+
+/** Prefix matches first, then substring matches, case-insensitively. */
+export function rankCompletions(ids: string[], value: string): string[] {
+  const needle = value.toLowerCase();
+  const prefix = ids.filter((id) => id.toLowerCase().startsWith(needle));
+  const contains = ids.filter((id) => !id.toLowerCase().startsWith(needle) && id.toLowerCase().includes(needle));
+  return [...prefix, ...contains].slice(0, MAX_COMPLETIONS);
+}
+
+function identifiersFrom(result: unknown): string[] {
+  const items = Array.isArray(result) ? result : asRecord(result)?.items;
+  return (Array.isArray(items) ? items : [])
+    .filter(isRecord)
+    .map((item) => asString(item.identifier) ?? asString(asRecord(item.project)?.identifier) ?? asString(asRecord(item.organization)?.identifier))
+    .filter((id): id is string => id !== undefined);
+}
+
+/**
+ * Build a completer bound to one server's registry and credentials. Scope
+ * comes from sibling arguments (`orgId`/`org_id`, `projectId`/`project_id`)
+ * and falls back to HARNESS_ORG / HARNESS_PROJECT.
+ */
+export function createArgumentCompleter(registry: Registry, client: HarnessClient, config: Config): ArgumentCompleter {
+  const cache = new Map<string, { expires: number; ids: string[] }>();
+
+  return async (argument, value, context) => {
+    const resourceType = COMPLETABLE_ARGUMENTS[argument];
+    if (!resourceType) return [];
+    try {
+      registry.getResource(resourceType);
+    } catch {
+      // Toolset disabled — nothing to complete from
+      return [];
+    }
+
+    const args = context?.arguments ?? {};
+    const orgId = args.orgId ?? args.org_id ?? config.HARNESS_ORG;
+    const projectId = args.projectId ?? args.project_id ?? config.HARNESS_PROJECT;
+    const key = `${resourceType}|${resourceType === "organization" ? "" : orgId ?? ""}|${resourceType === "organization" || resourceType === "project" ? "" : projectId ?? ""}`;
+
+    let entry = cache.get(key);
+    if (!entry || entry.expires < Date.now()) {
+      try {
+        const result = await registry.dispatch(client, resourceType, "list", {
+          ...(orgId ? { org_id: orgId } : {}),
+          ...(projectId ? { project_id: projectId } : {}),
+          size: MAX_COMPLETIONS,
+          page: 0,
+        }, { tool: "completion" });
+        entry = { expires: Date.now() + CACHE_TTL_MS, ids: identifiersFrom(result) };
+        cache.set(key, entry);
+      } catch (err) {
+        log.debug("Completion lookup failed", { argument, resourceType, error: String(err) });
+        return [];
+      }
+    }
+    return rankCompletions(entry.ids, value);
+  };
+}
+
+type PromptConfig = Parameters<McpServer["registerPrompt"]>[1];
+
+/**
+ * Attach catalog completions to the entity-naming arguments of a prompt's
+ * argsSchema. Schemas are cloned first — prompt configs are module-level and
+ * shared by every HTTP session's server.
+ */
+export function withPromptCompletions(promptConfig: PromptConfig, complete: ArgumentCompleter): PromptConfig {
+  const argsSchema = promptConfig.argsSchema;
+  if (!argsSchema) return promptConfig;
+  const completed = Object.fromEntries(
+    Object.entries(argsSchema).map(([name, schema]) =>
+      isCompletableArgument(name)
+        ? [name, completable((schema as z.ZodType).clone(), (value, context) => complete(name, String(value ?? ""), context))]
+        : [name, schema],
+    ),
+  );
+  return { ...promptConfig, argsSchema: completed as typeof argsSchema };
+}
+
+/**
+ * A view of `server` whose `registerPrompt` attaches catalog completions, so
+ * prompt modules keep calling `server.registerPrompt` unchanged.
+ */
+export function withCompletingPrompts(server: McpServer, complete: ArgumentCompleter): McpServer {
+  const view = Object.create(server) as McpServer;
+  view.registerPrompt = ((name: string, promptConfig: PromptConfig, callback: never) =>
+    server.registerPrompt(name, withPromptCompletions(promptConfig, complete), callback)) as McpServer["registerPrompt"];
+  return view;
+}
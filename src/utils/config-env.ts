@@ -0,0 +1,80 @@
+/**
+ * `harness-mcp-server config env` — list every supported environment
+ * variable with its current value (secrets masked) and where the value comes
+ * from, followed by the effective base URL and per-service routes. Meant for
+ * debugging misconfiguration without starting a server.
+ */
+
+import { ConfigSchema, configEnvFields, type Config } from "../config.js";
+import { applyRoute, clusterFromBaseUrl, resolveRouteMap } from "../client/routing.js";
+
+/** Variables whose values are credentials and must never be printed. */
+const SECRET_NAME = /(API_KEY|SDK_KEY|TOKEN|SECRET|PASSWORD|WEBHOOK_URL|NATS_URL)$/;
+
+/** Display value for a variable: secrets show only that they are set and their length. */
+export function maskEnvValue(name: string, value: string): string {
+  if (SECRET_NAME.test(name)) return value ? `<set, ${value.length} chars>` : "<empty>";
+  return value;
+}
+
+function formatDefault(value: unknown): string {
+  return typeof value === "string" ? value : JSON.stringify(value);
+}
+
+/** Render the `config env` report for `env`. */
+export function renderConfigEnv(env: NodeJS.ProcessEnv = process.env): string {
+  const fields = configEnvFields();
+  const width = Math.max(...fields.map((f) => f.name.length));
+  const lines = ["Environment variables (value  [source]):"];
+  for (const { name, defaultValue } of fields) {
+    const raw = env[name];
+    let shown: string;
+    if (raw !== undefined && raw !== "") {
+      shown = `${maskEnvValue(name, raw)}  [env]`;
+    } else if (defaultValue !== undefined) {
+      shown = `${formatDefault(defaultValue)}  [default]`;
+    } else {
+      shown = "-  [unset]";
+    }
+    lines.push(`  ${name.padEnd(width)}  ${shown}`);
+  }
+
+  lines.push("");
+  let config: Config;
+  try {
+    const result = ConfigSchema.safeParse(env);
+    if (!result.success) {
+      const issues = result.error.issues.map((i) => `  ${i.path.join(".")}: ${i.message}`).join("\n");
+      lines.push(`Configuration is invalid:\n${issues}`);
+      return lines.join("\n");
+    }
+    config = result.data;
+  } catch (err) {
+    lines.push(`Configuration is invalid: ${err instanceof Error ? err.message : String(err)}`);
+    return lines.join("\n");
+  }
+
+  const cluster = config.HARNESS_CLUSTER ?? clusterFromBaseUrl(config.HARNESS_BASE_URL);
+  lines.push(`Base URL:   ${config.HARNESS_BASE_URL}`);
+  lines.push(`Deployment: ${config.HARNESS_DEPLOYMENT}`);
+  lines.push(`Cluster:    ${cluster ?? "-"}`);
+  const routes = resolveRouteMap(config.HARNESS_DEPLOYMENT, config.HARNESS_ROUTE_MAP);
+  const prefixes = Object.keys(routes).sort();
+  if (prefixes.length === 0) {
+    lines.push("Service routes: none (every service is called at the base URL)");
+    return lines.join("\n");
+  }
+  lines.push("Service routes:");
+  const prefixWidth = Math.max(...prefixes.map((p) => p.length));
+  for (const prefix of prefixes) {
+    let target: string;
+    try {
+      const routed = applyRoute(config.HARNESS_BASE_URL, prefix, routes, { account: config.HARNESS_ACCOUNT_ID || undefined, cluster });
+      target = `${routed.baseUrl}${routed.path}`;
+    } catch (err) {
+      target = `error: ${err instanceof Error ? err.message : String(err)}`;
+    }
+    lines.push(`  ${prefix.padEnd(prefixWidth)}  -> ${target}`);
+  }
+  return lines.join("\n");
+}
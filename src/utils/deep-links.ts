@@ -1,3 +1,14 @@
+import type { Config } from "../config.js";
+
+/**
+ * Origin that UI links are built on: HARNESS_UI_URL when set (vanity domains,
+ * or an API proxy in front of the UI), else HARNESS_BASE_URL. A trailing
+ * `/gateway` is an API ingress, not part of UI paths, so it is dropped.
+ */
+export function harnessUiBaseUrl(config: Pick<Config, "HARNESS_BASE_URL" | "HARNESS_UI_URL">): string {
+  return (config.HARNESS_UI_URL ?? config.HARNESS_BASE_URL).replace(/\/+$/, "").replace(/\/gateway$/, "");
+}
+
 /**
  * Build Harness UI deep-link URLs.
  */
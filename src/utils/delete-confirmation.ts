@@ -0,0 +1,223 @@
+/**
+ * Two-phase delete confirmation (HARNESS_TWO_PHASE_DELETE).
+ *
+ * The first `harness_delete` call returns an impact summary — what still
+ * references the resource and when it was last used or changed — together
+ * with a single-use confirmation token. The delete only runs when the same
+ * resource is deleted again with that token, so an agent has to surface the
+ * impact before anything is removed. A token is bound to the account and the
+ * MCP session it was issued to.
+ */
+
+import { randomBytes } from "node:crypto";
+import type { Config } from "../config.js";
+import type { HarnessClient } from "../client/harness-client.js";
+import type { Registry } from "../registry/index.js";
+import { asRecord, asString } from "./type-guards.js";
+import { createLogger } from "./logger.js";
+
+const log = createLogger("delete-confirmation");
+
+/** Tokens expire after 5 minutes. */
+export const DELETE_TOKEN_TTL_MS = 5 * 60 * 1000;
+
+const MAX_REFERENCES = 20;
+
+/** Entity type names used by the entity setup usage API, for resource types that can be referenced. */
+const REFERRED_ENTITY_TYPES: Readonly<Record<string, string>> = {
+  pipeline: "Pipelines",
+  connector: "Connectors",
+  secret: "Secrets",
+  template: "Template",
+  service: "Service",
+  environment: "Environment",
+};
+
+/** Fields on a get response that say when a resource was last changed or used. */
+const ACTIVITY_FIELDS = [
+  "lastUpdatedAt",
+  "lastModifiedAt",
+  "updatedAt",
+  "createdAt",
+  "lastConnectedAt",
+  "lastTestedAt",
+  "lastExecutionTs",
+  "lastExecutionStatus",
+];
+
+/** `session` is the issuing session's server object, compared by identity. */
+const tokens = new Map<string, { key: string; session: object; expiresAt: number }>();
+
+/** Identity of a delete request — a token only confirms the exact resource, on the account, it was issued for. */
+export function deleteTokenKey(
+  account: string,
+  resourceType: string,
+  resourceId: string,
+  input: Record<string, unknown>,
+  config: Config,
+): string {
+  const org = asString(input.org_id) ?? config.HARNESS_ORG ?? "";
+  const project = asString(input.project_id) ?? config.HARNESS_PROJECT ?? "";
+  const scope = asString(input.resource_scope) ?? "";
+  const version = asString(input.version_label) ?? "";
+  return [account, resourceType, org, project, scope, resourceId, version].join("|");
+}
+
+export function issueDeleteToken(key: string, session: object, now = Date.now()): { token: string; expiresAt: number } {
+  for (const [token, entry] of tokens) {
+    if (entry.expiresAt <= now) tokens.delete(token);
+  }
+  const token = `del_${randomBytes(16).toString("hex")}`;
+  const expiresAt = now + DELETE_TOKEN_TTL_MS;
+  tokens.set(token, { key, session, expiresAt });
+  return { token, expiresAt };
+}
+
+/**
+ * Validate and consume a token. Returns an error message when the token is
+ * unknown, expired, or was issued to another session or for a different
+ * resource; a token is removed on first valid use.
+ */
+export function consumeDeleteToken(token: string, key: string, session: object, now = Date.now()): string | undefined {
+  const entry = tokens.get(token);
+  if (!entry || entry.session !== session) return "Unknown or already used confirmation_token.";
+  if (entry.expiresAt <= now) {
+    tokens.delete(token);
+    return "confirmation_token has expired.";
+  }
+  if (entry.key !== key) return "confirmation_token was issued for a different resource.";
+  tokens.delete(token);
+  return undefined;
+}
+
+/** Reset all tokens (useful for testing). */
+export function resetDeleteTokens(): void {
+  tokens.clear();
+}
+
+function pickActivity(record: Record<string, unknown>): Record<string, unknown> {
+  const activity: Record<string, unknown> = {};
+  const sources = [record, asRecord(record.status), asRecord(record.executionSummaryInfo), asRecord(record.entityValidityDetails)];
+  for (const source of sources) {
+    if (!source) continue;
+    for (const field of ACTIVITY_FIELDS) {
+      if (source[field] !== undefined && activity[field] === undefined) activity[field] = source[field];
+    }
+  }
+  return activity;
+}
+
+async function lastExecution(
+  registry: Registry,
+  client: HarnessClient,
+  input: Record<string, unknown>,
+  pipelineId: string,
+  signal?: AbortSignal,
+): Promise<Record<string, unknown> | undefined> {
+  const result = asRecord(await registry.dispatch(client, "execution", "list", {
+    org_id: input.org_id,
+    project_id: input.project_id,
+    pipeline_id: pipelineId,
+    size: 1,
+  }, signal));
+  const items = Array.isArray(result?.items) ? result.items : [];
+  const latest = asRecord(items[0]);
+  if (!latest) return undefined;
+  return {
+    execution_id: latest.planExecutionId,
+    status: latest.status,
+    started_at: latest.startTs,
+  };
+}
+
+async function findReferences(
+  client: HarnessClient,
+  referredEntityType: string,
+  fqn: string,
+  signal?: AbortSignal,
+): Promise<{ total: number; referenced_by: Array<Record<string, unknown>> }> {
+  const raw = asRecord(await client.request<unknown>({
+    method: "GET",
+    path: "/ng/api/entitySetupUsage",
+    params: { referredEntityFQN: fqn, referredEntityType, pageSize: MAX_REFERENCES },
+    signal,
+  }));
+  const page = asRecord(raw?.data) ?? raw ?? {};
+  const content = Array.isArray(page.content) ? page.content : [];
+  const referencedBy = content.flatMap((entry) => {
+    const referred = asRecord(asRecord(entry)?.referredByEntity);
+    if (!referred) return [];
+    const ref = asRecord(referred.entityRef);
+    return [{
+      type: referred.type,
+      name: referred.name,
+      identifier: ref?.identifier,
+      ...(ref?.orgIdentifier ? { org_id: ref.orgIdentifier } : {}),
+      ...(ref?.projectIdentifier ? { project_id: ref.projectIdentifier } : {}),
+    }];
+  });
+  const total = typeof page.totalItems === "number" ? page.totalItems : referencedBy.length;
+  return { total, referenced_by: referencedBy };
+}
+
+/**
+ * Best-effort impact summary for deleting a resource: its current state,
+ * last activity, entities that reference it, and (for pipelines) the last
+ * execution. Lookups that fail are listed under `unavailable` rather than
+ * failing the preview.
+ */
+export async function buildDeleteImpact(
+  registry: Registry,
+  client: HarnessClient,
+  config: Config,
+  resourceType: string,
+  resourceId: string,
+  input: Record<string, unknown>,
+  signal?: AbortSignal,
+): Promise<Record<string, unknown>> {
+  const def = registry.getResource(resourceType);
+  const impact: Record<string, unknown> = {};
+  const unavailable: string[] = [];
+
+  if (def.operations.get) {
+    try {
+      const current = await registry.dispatch(client, resourceType, "get", input, signal);
+      const record = asRecord(current);
+      if (record) {
+        const inner = asRecord(record.pipeline) ?? asRecord(record.connector) ?? asRecord(record.inputSet) ?? record;
+        const name = asString(inner.name) ?? asString(record.name);
+        if (name) impact.name = name;
+        const activity = { ...pickActivity(inner), ...pickActivity(record) };
+        if (Object.keys(activity).length > 0) impact.last_activity = activity;
+      }
+    } catch (err) {
+      log.debug("Delete impact: get failed", { resourceType, error: String(err) });
+      unavailable.push(`current state: ${err instanceof Error ? err.message : String(err)}`);
+    }
+  }
+
+  if (resourceType === "pipeline") {
+    try {
+      const execution = await lastExecution(registry, client, input, resourceId, signal);
+      impact.last_execution = execution ?? null;
+    } catch (err) {
+      unavailable.push(`last execution: ${err instanceof Error ? err.message : String(err)}`);
+    }
+  }
+
+  const referredEntityType = REFERRED_ENTITY_TYPES[resourceType];
+  if (referredEntityType) {
+    const scope = asString(input.resource_scope);
+    const org = scope === "account" ? undefined : asString(input.org_id) ?? config.HARNESS_ORG;
+    const project = scope === "account" || scope === "org" ? undefined : asString(input.project_id) ?? config.HARNESS_PROJECT;
+    const fqn = [registry.getAccountId(), org, project, resourceId].filter(Boolean).join("/");
+    try {
+      impact.references = await findReferences(client, referredEntityType, fqn, signal);
+    } catch (err) {
+      unavailable.push(`references: ${err instanceof Error ? err.message : String(err)}`);
+    }
+  }
+
+  if (unavailable.length > 0) impact.unavailable = unavailable;
+  return impact;
+}
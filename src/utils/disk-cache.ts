@@ -0,0 +1,89 @@
+/**
+ * Optional on-disk JSON cache for slow-changing, schema-relevant API data
+ * (connector catalogue, live entity YAML schemas). Lets long-lived
+ * deployments skip refetching on every restart.
+ *
+ * One file per key under `<dir>/<namespace>/`, named by the SHA-256 of the
+ * key so account/scope identifiers never appear in file names. Writes go to a
+ * temp file and are renamed into place, so concurrent readers never observe
+ * partial JSON. Every method is best-effort: I/O errors are logged and treated
+ * as a cache miss — the cache must never fail a tool call.
+ */
+import { createHash, randomUUID } from "node:crypto";
+import { mkdir, readFile, rename, rm, writeFile } from "node:fs/promises";
+import { join } from "node:path";
+import { createLogger } from "./logger.js";
+import { recordCacheLookup } from "./server-metrics.js";
+
+const log = createLogger("disk-cache");
+
+/** On-disk format version — bump to invalidate all entries after a format change. */
+const FORMAT_VERSION = 1;
+
+interface CacheFile {
+  v: number;
+  key: string;
+  expiresAt: number;
+  value: unknown;
+}
+
+export class DiskCache {
+  private readonly dir: string;
+  private readonly metricsName: string;
+
+  constructor(rootDir: string, namespace: string) {
+    this.dir = join(rootDir, namespace);
+    this.metricsName = `disk:${namespace}`;
+  }
+
+  private fileFor(key: string): string {
+    return join(this.dir, `${createHash("sha256").update(key).digest("hex")}.json`);
+  }
+
+  /** Return the cached value, or undefined on miss, expiry, or read error. */
+  async get<T>(key: string): Promise<T | undefined> {
+    const file = this.fileFor(key);
+    let parsed: CacheFile;
+    try {
+      parsed = JSON.parse(await readFile(file, "utf-8")) as CacheFile;
+    } catch (err) {
+      if ((err as NodeJS.ErrnoException).code !== "ENOENT") {
+        log.debug("Disk cache read failed", { error: String(err) });
+      }
+      recordCacheLookup(this.metricsName, false);
+      return undefined;
+    }
+    // Guard against hash collisions and stale formats as well as expiry
+    if (parsed.v !== FORMAT_VERSION || parsed.key !== key || parsed.expiresAt <= Date.now()) {
+      await rm(file, { force: true }).catch(() => {});
+      recordCacheLookup(this.metricsName, false);
+      return undefined;
+    }
+    recordCacheLookup(this.metricsName, true);
+    return parsed.value as T;
+  }
+
+  async set(key: string, value: unknown, ttlMs: number): Promise<void> {
+    if (ttlMs <= 0 || value === undefined) return;
+    const file = this.fileFor(key);
+    const tmp = `${file}.${randomUUID()}.tmp`;
+    const entry: CacheFile = { v: FORMAT_VERSION, key, expiresAt: Date.now() + ttlMs, value };
+    try {
+      await mkdir(this.dir, { recursive: true });
+      await writeFile(tmp, JSON.stringify(entry), { encoding: "utf-8", mode: 0o600 });
+      await rename(tmp, file);
+    } catch (err) {
+      log.warn("Disk cache write failed", { error: String(err) });
+      await rm(tmp, { force: true }).catch(() => {});
+    }
+  }
+
+  async delete(key: string): Promise<void> {
+    await rm(this.fileFor(key), { force: true }).catch(() => {});
+  }
+}
+
+/** Build a cache for `namespace` when a cache dir is configured; undefined disables caching. */
+export function createDiskCache(rootDir: string | undefined, namespace: string): DiskCache | undefined {
+  return rootDir ? new DiskCache(rootDir, namespace) : undefined;
+}
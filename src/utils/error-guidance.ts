@@ -0,0 +1,82 @@
+/**
+ * Actionable hints for common Harness API error signatures.
+ *
+ * Some failures look the same to an agent but have one well-known cause: a
+ * 403 from the log service, a 404 for a Git-backed pipeline fetched without
+ * a branch, a module the account is not licensed for. HarnessClient matches
+ * every API error against this table and appends the first matching hint to
+ * the error text, so the agent gets the fix instead of guessing.
+ */
+
+export interface ErrorSignature {
+  status: number;
+  /** Request path as passed to HarnessClient (before routing). */
+  path: string;
+  message: string;
+  /** Harness error code from the response body, e.g. INVALID_REQUEST. */
+  code?: string;
+  params?: Record<string, unknown>;
+}
+
+interface GuidanceRule {
+  /** Short identifier, used in tests and logs. */
+  id: string;
+  matches: (error: ErrorSignature) => boolean;
+  hint: string;
+}
+
+const hasParam = (error: ErrorSignature, name: string): boolean => {
+  const value = error.params?.[name];
+  return value !== undefined && value !== "";
+};
+
+/** Pipeline, input set, and template endpoints that read Git-backed (REMOTE) entities. */
+const GIT_BACKED_PATH = /^\/(pipeline\/api\/(pipelines|inputSets)|template\/api\/templates)\//;
+
+const GUIDANCE_RULES: GuidanceRule[] = [
+  {
+    id: "license_missing",
+    matches: (e) => /licen[sc]e/i.test(e.message) && (e.status === 400 || e.status === 403 || /LICENSE/.test(e.code ?? "")),
+    hint:
+      "The account has no active license for this module, so its APIs are rejected regardless of permissions. " +
+      "Check Account Settings > Subscriptions in the Harness UI or ask an account admin; " +
+      "exclude the module's toolset with HARNESS_TOOLSETS to hide its resources.",
+  },
+  {
+    id: "log_service_forbidden",
+    matches: (e) => e.status === 403 && e.path.includes("/log-service/"),
+    hint:
+      "The log service rejected the token. Logs need view permission on the pipeline's project and a log prefix from the same account. " +
+      "Fetch logs with harness_diagnose(include_logs=true) or harness_get(resource_type=\"execution_log\", execution_id=...) " +
+      "so the prefix is built from the execution, and check HARNESS_BASE_URL points at the cluster that ran it.",
+  },
+  {
+    id: "remote_entity_without_branch",
+    matches: (e) =>
+      (e.status === 404 || (e.status === 400 && /not found|does not exist/i.test(e.message))) &&
+      GIT_BACKED_PATH.test(e.path) &&
+      !hasParam(e, "branch"),
+    hint:
+      "If this entity is stored in Git (REMOTE), Harness reads it from the repository's default branch when no branch is given. " +
+      "Pass branch (and connector_ref/repo_name for a non-default repository), or use the entity's Harness URL, which carries them.",
+  },
+  {
+    id: "rbac_forbidden",
+    matches: (e) => e.status === 403 && /permission|not authorized|unauthorized|access denied/i.test(e.message),
+    hint:
+      "The API key's principal lacks a permission for this operation. " +
+      "List its roles with harness_list(resource_type=\"role_assignment\") and ask an admin to grant the missing permission.",
+  },
+];
+
+/** The hint for the first rule that matches `error`, if any. */
+export function guidanceFor(error: ErrorSignature): { id: string; hint: string } | undefined {
+  const rule = GUIDANCE_RULES.find((r) => r.matches(error));
+  return rule ? { id: rule.id, hint: rule.hint } : undefined;
+}
+
+/** `error.message` with the matching hint appended. */
+export function withGuidance(error: ErrorSignature): string {
+  const guidance = guidanceFor(error);
+  return guidance ? `${error.message}\n\nGuidance: ${guidance.hint}` : error.message;
+}
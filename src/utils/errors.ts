@@ -34,6 +34,45 @@ export class HarnessApiError extends Error {
   }
 }
 
+/** Response headers that carry the gateway's request correlation ID, in lookup order. */
+const CORRELATION_ID_HEADERS = [
+  "x-harness-correlation-id",
+  "x-correlation-id",
+  "correlation-id",
+  "x-request-id",
+  "request-id",
+];
+
+/** Read the correlation ID from Harness response headers, if any. */
+export function correlationIdFromHeaders(headers: Headers | undefined): string | undefined {
+  for (const name of CORRELATION_ID_HEADERS) {
+    const value = headers?.get?.(name)?.trim();
+    if (value) return value;
+  }
+  return undefined;
+}
+
+/**
+ * Attach a correlation ID to an API result as a non-enumerable `__correlationId`
+ * marker — visible to the registry and audit layer, never serialized into tool output.
+ */
+export function tagCorrelationId<T>(value: T, correlationId: string | undefined): T {
+  if (correlationId && value !== null && typeof value === "object" && Object.isExtensible(value)) {
+    Object.defineProperty(value, "__correlationId", { value: correlationId, enumerable: false, configurable: true });
+  }
+  return value;
+}
+
+/** Correlation ID of a HarnessApiError or a tagged API result. */
+export function getCorrelationId(value: unknown): string | undefined {
+  if (value instanceof HarnessApiError) return value.correlationId;
+  if (value !== null && typeof value === "object") {
+    const id = (value as { __correlationId?: unknown }).__correlationId;
+    return typeof id === "string" ? id : undefined;
+  }
+  return undefined;
+}
+
 /**
  * Returns true for user-fixable errors (registry validation, missing fields,
  * unknown resource types) — i.e. plain Errors that are NOT HarnessApiErrors.
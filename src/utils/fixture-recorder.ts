@@ -0,0 +1,227 @@
+/**
+ * `fixtures record <dir>`: drive the read tools against a live account and
+ * save every Harness API exchange they make as a WireMock stub mapping, for
+ * mock mode (HARNESS_MOCK_FIXTURES_DIR) and WireMock-based tests.
+ *
+ * Recorded data is sanitized before it is written: the account ID becomes
+ * `mock` (the account mock mode runs as), email addresses become
+ * `userN@example.com`, values under secret-looking keys and secret-shaped
+ * strings (plus HARNESS_REDACT_PATTERNS) are redacted.
+ */
+
+import { mkdir, writeFile } from "node:fs/promises";
+import { join, resolve } from "node:path";
+import type { Client } from "@modelcontextprotocol/sdk/client/index.js";
+import type { CallToolResult } from "@modelcontextprotocol/sdk/types.js";
+import type { ExchangeRecorder } from "../client/harness-client.js";
+import type { MockMapping } from "../client/mock-api.js";
+import type { Registry } from "../registry/index.js";
+import { isSensitiveKey, redactSecretValues } from "./redact.js";
+
+/** Account ID written into fixtures; matches the placeholder account mock mode uses. */
+export const FIXTURE_ACCOUNT_ID = "mock";
+
+/**
+ * Query params left out of recorded request matchers: account scoping, so
+ * fixtures match any account, and paging, so an agent asking for a different
+ * page size still gets the recorded page.
+ */
+const UNMATCHED_QUERY_PARAMS = new Set(["accountIdentifier", "routingId", "accountID", "page", "size", "limit", "offset", "pageIndex", "pageSize"]);
+
+const EMAIL_PATTERN = /[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}/g;
+
+const REDACTED = "[REDACTED]";
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+/** Rewrites account IDs, emails, and secrets; one instance per recording so email numbering is stable. */
+export class FixtureSanitizer {
+  private readonly accountPattern: RegExp | undefined;
+  private readonly emails = new Map<string, string>();
+
+  constructor(accountId: string) {
+    this.accountPattern = accountId ? new RegExp(escapeRegExp(accountId), "g") : undefined;
+  }
+
+  text(value: string): string {
+    let out = redactSecretValues(value);
+    if (this.accountPattern) out = out.replace(this.accountPattern, FIXTURE_ACCOUNT_ID);
+    return out.replace(EMAIL_PATTERN, (email) => {
+      const key = email.toLowerCase();
+      if (key.endsWith("@example.com")) return email;
+      let alias = this.emails.get(key);
+      if (!alias) {
+        alias = `user${this.emails.size + 1}@example.com`;
+        this.emails.set(key, alias);
+      }
+      return alias;
+    });
+  }
+
+  value(value: unknown): unknown {
+    if (typeof value === "string") return this.text(value);
+    if (Array.isArray(value)) return value.map((v) => this.value(v));
+    if (value === null || typeof value !== "object") return value;
+    const out: Record<string, unknown> = {};
+    for (const [key, v] of Object.entries(value as Record<string, unknown>)) {
+      out[key] = isSensitiveKey(key) && v !== null && typeof v !== "object" ? REDACTED : this.value(v);
+    }
+    return out;
+  }
+}
+
+/** Collects client exchanges and turns them into sanitized, de-duplicated mappings. */
+export class FixtureRecorder {
+  private readonly pending: Array<Promise<MockMapping | undefined>> = [];
+  private readonly sanitizer: FixtureSanitizer;
+  private readonly basePath: string;
+
+  constructor(accountId: string, baseUrl: string) {
+    this.sanitizer = new FixtureSanitizer(accountId);
+    this.basePath = new URL(baseUrl).pathname.replace(/\/$/, "");
+  }
+
+  /** Pass to `HarnessClient.setExchangeRecorder`. */
+  readonly record: ExchangeRecorder = ({ method, url, response }) => {
+    // Throttling and server errors are transient — retries will record the real answer
+    if (response.status === 429 || response.status >= 500) return;
+    this.pending.push(this.toMapping(method, url, response).catch(() => undefined));
+  };
+
+  private async toMapping(method: string, url: string, response: Response): Promise<MockMapping | undefined> {
+    const target = new URL(url);
+    const path = this.basePath && target.pathname.startsWith(`${this.basePath}/`)
+      ? target.pathname.slice(this.basePath.length)
+      : target.pathname;
+    const queryParameters: Record<string, { equalTo: string }> = {};
+    for (const [key, value] of target.searchParams) {
+      if (!UNMATCHED_QUERY_PARAMS.has(key)) queryParameters[key] = { equalTo: this.sanitizer.text(value) };
+    }
+
+    const contentType = response.headers.get("content-type") ?? "";
+    const bytes = Buffer.from(await response.arrayBuffer());
+    const out: MockMapping["response"] = { status: response.status };
+    if (contentType.includes("json")) {
+      try {
+        out.jsonBody = this.sanitizer.value(JSON.parse(bytes.toString("utf-8")));
+      } catch {
+        out.body = this.sanitizer.text(bytes.toString("utf-8"));
+      }
+    } else if (contentType.startsWith("text/") || contentType.includes("yaml") || contentType === "") {
+      out.body = this.sanitizer.text(bytes.toString("utf-8"));
+    } else {
+      // Archives and other binary payloads cannot be scrubbed reliably; keep them as-is
+      out.base64Body = bytes.toString("base64");
+    }
+    if (contentType) out.headers = { "Content-Type": contentType };
+
+    return {
+      request: {
+        method,
+        urlPath: this.sanitizer.text(path),
+        ...(Object.keys(queryParameters).length > 0 ? { queryParameters } : {}),
+      },
+      response: out,
+    };
+  }
+
+  /** Every exchange recorded so far, one mapping per distinct request (the first response wins). */
+  async mappings(): Promise<MockMapping[]> {
+    const seen = new Set<string>();
+    const out: MockMapping[] = [];
+    for (const mapping of await Promise.all(this.pending)) {
+      if (!mapping) continue;
+      const key = JSON.stringify(mapping.request);
+      if (seen.has(key)) continue;
+      seen.add(key);
+      out.push(mapping);
+    }
+    return out;
+  }
+}
+
+/** Write one WireMock mapping file per mapping into `dir`; returns the file paths. */
+export async function writeFixtureFiles(dir: string, mappings: MockMapping[]): Promise<string[]> {
+  const root = resolve(dir);
+  await mkdir(root, { recursive: true });
+  const used = new Set<string>();
+  const paths: string[] = [];
+  for (const mapping of mappings) {
+    const slug = `${mapping.request.method ?? "any"}-${mapping.request.urlPath ?? "root"}`.toLowerCase().replace(/[^a-z0-9]+/g, "-").replace(/^-|-$/g, "");
+    let name = `${slug}.json`;
+    for (let n = 2; used.has(name); n++) name = `${slug}-${n}.json`;
+    used.add(name);
+    const path = join(root, name);
+    await writeFile(path, `${JSON.stringify(mapping, null, 2)}\n`);
+    paths.push(path);
+  }
+  return paths;
+}
+
+export interface RecordingSummary {
+  calls: number;
+  /** `tool resource_type: error`, for calls that failed (missing parent IDs, unlicensed modules, ...). */
+  failures: string[];
+}
+
+function resultPayload(result: CallToolResult): unknown {
+  if (result.structuredContent !== undefined) return result.structuredContent;
+  const text = result.content.find((c) => c.type === "text");
+  try {
+    return text && text.type === "text" ? JSON.parse(text.text) : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+function errorText(result: CallToolResult): string {
+  const payload = resultPayload(result) as { error?: unknown } | undefined;
+  return typeof payload?.error === "string" ? payload.error : "tool error";
+}
+
+/** Identifier of the first list item, to fetch with harness_get. */
+function firstIdentifier(payload: unknown): string | undefined {
+  const items = (payload as { items?: unknown } | undefined)?.items;
+  if (!Array.isArray(items)) return undefined;
+  for (const item of items) {
+    const record = item as Record<string, unknown> | null;
+    const id = record?.identifier ?? record?.id;
+    if (typeof id === "string" && id) return id;
+  }
+  return undefined;
+}
+
+/**
+ * Call harness_list for every enabled resource type that supports list, then
+ * harness_get on the first item of each non-empty list. Failed calls are
+ * reported and skipped; whatever the successful calls fetched is recorded.
+ */
+export async function runReadTools(
+  client: Client,
+  registry: Registry,
+  onProgress: (message: string) => void = () => {},
+): Promise<RecordingSummary> {
+  const summary: RecordingSummary = { calls: 0, failures: [] };
+  const call = async (tool: string, args: Record<string, unknown>): Promise<unknown> => {
+    summary.calls++;
+    const result = await client.callTool({ name: tool, arguments: args }) as CallToolResult;
+    if (result.isError) {
+      summary.failures.push(`${tool} ${String(args.resource_type)}: ${errorText(result)}`);
+      return undefined;
+    }
+    return resultPayload(result);
+  };
+
+  for (const resourceType of registry.getTypesForOperation("list")) {
+    onProgress(`harness_list ${resourceType}`);
+    const listed = await call("harness_list", { resource_type: resourceType });
+    const id = firstIdentifier(listed);
+    if (id && registry.supportsOperation(resourceType, "get")) {
+      onProgress(`harness_get ${resourceType} ${id}`);
+      await call("harness_get", { resource_type: resourceType, resource_id: id });
+    }
+  }
+  return summary;
+}
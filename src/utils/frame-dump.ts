@@ -0,0 +1,59 @@
+/**
+ * JSON-RPC frame dump for troubleshooting clients (HARNESS_DEBUG_FRAMES).
+ *
+ * Wraps a connected transport so every inbound and outbound message is
+ * logged — through the normal logger, so frames land on stderr (or LOG_FILE)
+ * with credentials redacted and never touch the stdio JSON-RPC stream.
+ */
+
+import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import { createLogger } from "./logger.js";
+
+const log = createLogger("jsonrpc");
+
+/** Frames larger than this are logged as a truncated string preview. */
+const MAX_FRAME_CHARS = 20_000;
+
+type FrameDirection = "in" | "out";
+
+function frameSummary(message: unknown): Record<string, unknown> {
+  const m = (typeof message === "object" && message !== null ? message : {}) as Record<string, unknown>;
+  return {
+    ...(m.id !== undefined ? { id: m.id } : {}),
+    ...(typeof m.method === "string" ? { method: m.method } : {}),
+    ...("error" in m ? { is_error: true } : {}),
+  };
+}
+
+/** Log one frame; oversized frames are cut to a preview so a log download cannot flood the log. */
+export function logFrame(direction: FrameDirection, message: unknown, sessionId?: string): void {
+  const text = JSON.stringify(message) ?? "";
+  log.info(`jsonrpc ${direction}`, {
+    direction,
+    ...(sessionId ? { sessionId } : {}),
+    ...frameSummary(message),
+    bytes: text.length,
+    ...(text.length > MAX_FRAME_CHARS
+      ? { frame_preview: text.slice(0, MAX_FRAME_CHARS), truncated: true }
+      : { frame: message }),
+  });
+}
+
+/**
+ * Dump every frame on a transport. Call after `server.connect(transport)` —
+ * the server installs `onmessage` during connect and this wraps it.
+ */
+export function attachFrameDump(transport: Transport): void {
+  const send = transport.send.bind(transport);
+  transport.send = async (message, options) => {
+    logFrame("out", message, transport.sessionId);
+    return send(message, options);
+  };
+  const onmessage = transport.onmessage;
+  if (onmessage) {
+    transport.onmessage = (message, extra) => {
+      logFrame("in", message, transport.sessionId);
+      onmessage.call(transport, message, extra);
+    };
+  }
+}
@@ -0,0 +1,318 @@
+/**
+ * gRPC transport for internal service-to-service callers: the tool-dispatch
+ * surface (list tools, call a tool) as unary RPCs, described by
+ * proto/harness/mcp/v1/tool_dispatch.proto, plus the standard
+ * grpc.health.v1.Health/Check.
+ *
+ * Served directly on node:http2. The messages only carry strings and a bool,
+ * so a small protobuf codec here replaces a gRPC runtime and generated code.
+ * Compressed messages and streaming calls are not supported.
+ */
+import { createSecureServer, createServer, type Http2Server, type IncomingHttpHeaders, type SecureServerOptions, type ServerHttp2Stream } from "node:http2";
+import type { CallToolResult, Tool } from "@modelcontextprotocol/sdk/types.js";
+import { createLogger } from "./logger.js";
+
+const log = createLogger("grpc");
+
+/** gRPC status codes used by this server. */
+export const GrpcStatus = {
+  OK: 0,
+  CANCELLED: 1,
+  UNKNOWN: 2,
+  INVALID_ARGUMENT: 3,
+  DEADLINE_EXCEEDED: 4,
+  NOT_FOUND: 5,
+  RESOURCE_EXHAUSTED: 8,
+  UNIMPLEMENTED: 12,
+  INTERNAL: 13,
+  UNAUTHENTICATED: 16,
+} as const;
+export type GrpcStatusCode = (typeof GrpcStatus)[keyof typeof GrpcStatus];
+
+export class GrpcError extends Error {
+  constructor(readonly code: GrpcStatusCode, message: string) {
+    super(message);
+    this.name = "GrpcError";
+  }
+}
+
+export const TOOL_DISPATCH_SERVICE = "harness.mcp.v1.ToolDispatch";
+const HEALTH_SERVICE = "grpc.health.v1.Health";
+
+// ---------------------------------------------------------------------------
+// Protobuf wire format (varint, length-delimited)
+// ---------------------------------------------------------------------------
+
+function encodeVarint(value: number): Buffer {
+  const bytes: number[] = [];
+  let v = value;
+  while (v > 0x7f) {
+    bytes.push((v % 0x80) | 0x80);
+    v = Math.floor(v / 0x80);
+  }
+  bytes.push(v);
+  return Buffer.from(bytes);
+}
+
+/** Builds one message; fields are written in call order. Empty strings and false are omitted, as proto3 does. */
+export class ProtoWriter {
+  private readonly parts: Buffer[] = [];
+
+  string(field: number, value: string | undefined): this {
+    if (value) this.bytes(field, Buffer.from(value, "utf-8"));
+    return this;
+  }
+
+  bool(field: number, value: boolean): this {
+    return this.varint(field, value ? 1 : 0);
+  }
+
+  varint(field: number, value: number): this {
+    if (value) this.parts.push(encodeVarint(field * 8), encodeVarint(value));
+    return this;
+  }
+
+  message(field: number, value: ProtoWriter): this {
+    return this.bytes(field, value.finish());
+  }
+
+  private bytes(field: number, value: Buffer): this {
+    this.parts.push(encodeVarint(field * 8 + 2), encodeVarint(value.length), value);
+    return this;
+  }
+
+  finish(): Buffer {
+    return Buffer.concat(this.parts);
+  }
+}
+
+/** Field number → values in wire order (varints as numbers, length-delimited fields as buffers). */
+export type ProtoFields = Map<number, Array<number | Buffer>>;
+
+export function decodeProto(buf: Buffer): ProtoFields {
+  const fields: ProtoFields = new Map();
+  let pos = 0;
+  const varint = (): number => {
+    let result = 0;
+    for (let scale = 1; ; scale *= 0x80) {
+      if (pos >= buf.length) throw new GrpcError(GrpcStatus.INVALID_ARGUMENT, "Truncated protobuf message");
+      const byte = buf[pos++]!;
+      result += (byte & 0x7f) * scale;
+      if (byte < 0x80) return result;
+    }
+  };
+  while (pos < buf.length) {
+    const key = varint();
+    const field = Math.floor(key / 8);
+    const wireType = key % 8;
+    let value: number | Buffer;
+    if (wireType === 0) {
+      value = varint();
+    } else if (wireType === 2) {
+      const length = varint();
+      if (pos + length > buf.length) throw new GrpcError(GrpcStatus.INVALID_ARGUMENT, "Truncated protobuf message");
+      value = buf.subarray(pos, pos + length);
+      pos += length;
+    } else if (wireType === 1 || wireType === 5) {
+      // Fixed-width fields appear in no message here; skip them
+      pos += wireType === 1 ? 8 : 4;
+      continue;
+    } else {
+      throw new GrpcError(GrpcStatus.INVALID_ARGUMENT, `Unsupported protobuf wire type ${wireType}`);
+    }
+    const list = fields.get(field) ?? [];
+    list.push(value);
+    fields.set(field, list);
+  }
+  return fields;
+}
+
+/** Last value of a string field (proto3: last one wins), or "". */
+export function protoString(fields: ProtoFields, field: number): string {
+  const value = fields.get(field)?.at(-1);
+  return Buffer.isBuffer(value) ? value.toString("utf-8") : "";
+}
+
+// ---------------------------------------------------------------------------
+// Messages (see tool_dispatch.proto)
+// ---------------------------------------------------------------------------
+
+function encodeTool(tool: Tool): ProtoWriter {
+  return new ProtoWriter()
+    .string(1, tool.name)
+    .string(2, tool.description)
+    .string(3, JSON.stringify(tool.inputSchema))
+    .string(4, tool.outputSchema ? JSON.stringify(tool.outputSchema) : undefined)
+    .string(5, tool.annotations ? JSON.stringify(tool.annotations) : undefined);
+}
+
+export function encodeListToolsResponse(tools: Tool[]): Buffer {
+  const writer = new ProtoWriter();
+  for (const tool of tools) writer.message(1, encodeTool(tool));
+  return writer.finish();
+}
+
+export function decodeCallToolRequest(buf: Buffer): { name: string; args: Record<string, unknown> } {
+  const fields = decodeProto(buf);
+  const name = protoString(fields, 1);
+  if (!name) throw new GrpcError(GrpcStatus.INVALID_ARGUMENT, "CallToolRequest.name is required");
+  const raw = protoString(fields, 2);
+  let args: unknown = {};
+  if (raw) {
+    try {
+      args = JSON.parse(raw);
+    } catch (err) {
+      throw new GrpcError(GrpcStatus.INVALID_ARGUMENT, `CallToolRequest.arguments_json is not valid JSON: ${err instanceof Error ? err.message : String(err)}`);
+    }
+  }
+  if (typeof args !== "object" || args === null || Array.isArray(args)) {
+    throw new GrpcError(GrpcStatus.INVALID_ARGUMENT, "CallToolRequest.arguments_json must be a JSON object");
+  }
+  return { name, args: args as Record<string, unknown> };
+}
+
+export function encodeCallToolResponse(result: CallToolResult): Buffer {
+  return new ProtoWriter().bool(1, Boolean(result.isError)).string(2, JSON.stringify(result)).finish();
+}
+
+// ---------------------------------------------------------------------------
+// gRPC over HTTP/2
+// ---------------------------------------------------------------------------
+
+/** Length-prefixed message framing: 1 byte compressed flag, 4 bytes big-endian length. */
+export function frameMessage(payload: Buffer): Buffer {
+  const header = Buffer.alloc(5);
+  header.writeUInt32BE(payload.length, 1);
+  return Buffer.concat([header, payload]);
+}
+
+/** The single message of a unary request body. */
+export function unframeMessage(body: Buffer): Buffer {
+  if (body.length === 0) return body;
+  if (body.length < 5) throw new GrpcError(GrpcStatus.INVALID_ARGUMENT, "Truncated gRPC message frame");
+  if (body[0] !== 0) throw new GrpcError(GrpcStatus.UNIMPLEMENTED, "Compressed gRPC messages are not supported; send with identity encoding");
+  const length = body.readUInt32BE(1);
+  if (body.length !== 5 + length) throw new GrpcError(GrpcStatus.INVALID_ARGUMENT, "Unary calls take exactly one request message");
+  return body.subarray(5);
+}
+
+const TIMEOUT_UNITS_MS: Record<string, number> = { H: 3_600_000, M: 60_000, S: 1000, m: 1, u: 0.001, n: 0.000001 };
+
+/** `grpc-timeout` header ("30S", "500m", ...) in milliseconds, or undefined. */
+export function parseGrpcTimeout(value: string | undefined): number | undefined {
+  const match = value ? /^(\d{1,8})([HMSmun])$/.exec(value) : null;
+  return match ? Math.max(1, Math.ceil(Number(match[1]) * TIMEOUT_UNITS_MS[match[2]!]!)) : undefined;
+}
+
+export interface GrpcToolHandlers {
+  /**
+   * Checked before every call. Throw a GrpcError (e.g. UNAUTHENTICATED) to
+   * reject it; the returned value is passed to the handlers.
+   */
+  authorize(metadata: IncomingHttpHeaders): unknown;
+  listTools(metadata: IncomingHttpHeaders, auth: unknown): Promise<Tool[]>;
+  callTool(name: string, args: Record<string, unknown>, metadata: IncomingHttpHeaders, auth: unknown, signal: AbortSignal): Promise<CallToolResult>;
+}
+
+export interface GrpcServerOptions {
+  /** TLS (and, with `ca` + `requestCert`, mutual TLS); plaintext HTTP/2 (h2c) when omitted. */
+  tls?: Pick<SecureServerOptions, "cert" | "key" | "ca" | "requestCert" | "rejectUnauthorized">;
+  /** Largest accepted request message. */
+  maxMessageBytes: number;
+}
+
+function sendTrailersOnly(stream: ServerHttp2Stream, code: GrpcStatusCode, message: string): void {
+  if (stream.destroyed || stream.headersSent) {
+    stream.close();
+    return;
+  }
+  stream.respond({
+    ":status": 200,
+    "content-type": "application/grpc+proto",
+    "grpc-status": String(code),
+    "grpc-message": encodeURIComponent(message),
+  }, { endStream: true });
+}
+
+function sendMessage(stream: ServerHttp2Stream, payload: Buffer): void {
+  if (stream.destroyed) return;
+  stream.respond({ ":status": 200, "content-type": "application/grpc+proto" }, { waitForTrailers: true });
+  stream.once("wantTrailers", () => stream.sendTrailers({ "grpc-status": String(GrpcStatus.OK) }));
+  stream.end(frameMessage(payload));
+}
+
+function readBody(stream: ServerHttp2Stream, maxBytes: number): Promise<Buffer> {
+  return new Promise((resolve, reject) => {
+    const chunks: Buffer[] = [];
+    let size = 0;
+    stream.on("data", (chunk: Buffer) => {
+      size += chunk.length;
+      if (size > maxBytes + 5) {
+        reject(new GrpcError(GrpcStatus.RESOURCE_EXHAUSTED, `Request message exceeds ${maxBytes} bytes`));
+        stream.removeAllListeners("data");
+        return;
+      }
+      chunks.push(chunk);
+    });
+    stream.once("end", () => resolve(Buffer.concat(chunks)));
+    stream.once("error", reject);
+  });
+}
+
+async function handleCall(stream: ServerHttp2Stream, headers: IncomingHttpHeaders, handlers: GrpcToolHandlers, maxMessageBytes: number): Promise<void> {
+  const path = String(headers[":path"] ?? "");
+  const contentType = String(headers["content-type"] ?? "");
+  if (headers[":method"] !== "POST" || !contentType.startsWith("application/grpc")) {
+    stream.respond({ ":status": 415 }, { endStream: true });
+    return;
+  }
+  if (contentType.startsWith("application/grpc+") && contentType !== "application/grpc+proto") {
+    throw new GrpcError(GrpcStatus.UNIMPLEMENTED, `Unsupported gRPC content type "${contentType}"; use application/grpc+proto`);
+  }
+
+  const request = unframeMessage(await readBody(stream, maxMessageBytes));
+  if (path === `/${HEALTH_SERVICE}/Check`) {
+    // grpc.health.v1.HealthCheckResponse { status: SERVING }
+    sendMessage(stream, new ProtoWriter().varint(1, 1).finish());
+    return;
+  }
+
+  const controller = new AbortController();
+  stream.once("close", () => controller.abort());
+  const timeoutMs = parseGrpcTimeout(headers["grpc-timeout"] as string | undefined);
+  const signal = timeoutMs ? AbortSignal.any([controller.signal, AbortSignal.timeout(timeoutMs)]) : controller.signal;
+
+  const auth = handlers.authorize(headers);
+  if (path === `/${TOOL_DISPATCH_SERVICE}/ListTools`) {
+    sendMessage(stream, encodeListToolsResponse(await handlers.listTools(headers, auth)));
+    return;
+  }
+  if (path === `/${TOOL_DISPATCH_SERVICE}/CallTool`) {
+    const { name, args } = decodeCallToolRequest(request);
+    const result = await handlers.callTool(name, args, headers, auth, signal);
+    if (signal.aborted && !controller.signal.aborted) throw new GrpcError(GrpcStatus.DEADLINE_EXCEEDED, "Deadline exceeded");
+    sendMessage(stream, encodeCallToolResponse(result));
+    return;
+  }
+  throw new GrpcError(GrpcStatus.UNIMPLEMENTED, `Unknown method ${path}`);
+}
+
+/** An HTTP/2 server answering the ToolDispatch and health RPCs; call `listen` on it. */
+export function createGrpcServer(handlers: GrpcToolHandlers, options: GrpcServerOptions): Http2Server {
+  const server = options.tls ? createSecureServer({ ...options.tls, allowHTTP1: false }) : createServer();
+  server.on("stream", (stream, headers) => {
+    const started = Date.now();
+    const method = String(headers[":path"] ?? "");
+    handleCall(stream, headers, handlers, options.maxMessageBytes)
+      .then(() => log.debug("gRPC call", { method, durationMs: Date.now() - started }))
+      .catch((err: unknown) => {
+        const code = err instanceof GrpcError ? err.code : GrpcStatus.INTERNAL;
+        const message = err instanceof Error ? err.message : String(err);
+        if (code === GrpcStatus.INTERNAL) log.error("gRPC call failed", { method, error: message });
+        else log.debug("gRPC call rejected", { method, code, error: message });
+        sendTrailersOnly(stream, code, message);
+      });
+  });
+  server.on("sessionError", (err) => log.warn("gRPC session error", { error: err.message }));
+  return server;
+}
@@ -0,0 +1,187 @@
+/**
+ * Push updates from Harness event streams (HARNESS_EVENTS_NATS_URL) and
+ * webhook notifications (HARNESS_WEBHOOK_SECRET, HTTP transport).
+ *
+ * A NATS subscriber or the webhook receiver feeds Harness events — pipeline executions finishing,
+ * approvals waiting for a decision — into an in-process hub. Each connected
+ * MCP session that belongs to the event's account gets:
+ *
+ * - a `notifications/message` (logger `harness-events`, level `notice`)
+ *   carrying the normalized event, so agents can react instead of polling;
+ * - `notifications/resources/updated` for `executions:///recent` when an
+ *   execution finishes and the client subscribed to that resource.
+ *
+ * Events naming an execution also wake `harness_execute` calls waiting on it.
+ *
+ * Payloads are JSON. Both flat events and Harness notification webhook bodies
+ * (`{ eventType, eventData }`) are understood; unrecognized event types are
+ * forwarded as `other`.
+ */
+import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { SubscribeRequestSchema, UnsubscribeRequestSchema } from "@modelcontextprotocol/sdk/types.js";
+import type { Config } from "../config.js";
+import { createLogger } from "./logger.js";
+import { NatsSubscriber, type NatsMessage } from "./nats-client.js";
+import { wakeExecutionWaiters } from "./poll-execution.js";
+import { asRecord, asString } from "./type-guards.js";
+
+const log = createLogger("harness-events");
+
+export type HarnessEventType = "execution_finished" | "approval_required" | "other";
+
+export interface HarnessEvent {
+  type: HarnessEventType;
+  /** Event type as published, e.g. PIPELINE_END. */
+  sourceType: string;
+  subject: string;
+  accountId?: string;
+  orgId?: string;
+  projectId?: string;
+  pipelineId?: string;
+  executionId?: string;
+  status?: string;
+  /** Harness UI link, when the event carries one. */
+  url?: string;
+}
+
+/** The resource whose contents change when an execution finishes. */
+export const RECENT_EXECUTIONS_URI = "executions:///recent";
+
+const EXECUTION_FINISHED = new Set(["PIPELINE_END", "PIPELINE_SUCCESS", "PIPELINE_FAILED", "PIPELINE_ABORTED", "PIPELINE_COMPLETED", "EXECUTION_FINISHED", "EXECUTION_COMPLETED"]);
+
+function classify(sourceType: string): HarnessEventType {
+  const key = sourceType.toUpperCase().replace(/[^A-Z]+/g, "_").replace(/^_|_$/g, "");
+  if (EXECUTION_FINISHED.has(key)) return "execution_finished";
+  // APPROVAL, APPROVAL_REQUIRED, HARNESS_APPROVAL_WAITING, ...
+  if (key.includes("APPROVAL")) return "approval_required";
+  return "other";
+}
+
+/** Normalize a published event; undefined when the payload is not a JSON object. */
+export function parseHarnessEvent(message: NatsMessage): HarnessEvent | undefined {
+  let raw: unknown;
+  try {
+    raw = JSON.parse(message.data.toString("utf-8"));
+  } catch {
+    return undefined;
+  }
+  return normalizeHarnessEvent(raw, message.subject);
+}
+
+/**
+ * Normalize an event body, flat or a notification webhook body. `subject`
+ * says where it came from (NATS subject, webhook path) and supplies the event
+ * type when the body has none.
+ */
+export function normalizeHarnessEvent(raw: unknown, subject: string): HarnessEvent | undefined {
+  const outer = asRecord(raw);
+  if (!outer) return undefined;
+  // Notification webhooks nest the details under eventData
+  const data = asRecord(outer.eventData) ?? outer;
+  const pick = (...keys: string[]) => {
+    for (const key of keys) {
+      const value = asString(data[key]) ?? asString(outer[key]);
+      if (value) return value;
+    }
+    return undefined;
+  };
+  // Without a type field, the subject's last token names the event (harness.events.<account>.pipeline_end)
+  const sourceType = pick("eventType", "type", "event") ?? subject.split(".").at(-1) ?? "";
+  const event: HarnessEvent = { type: classify(sourceType), sourceType, subject };
+  const fields: Array<[Exclude<keyof HarnessEvent, "type" | "sourceType" | "subject">, string[]]> = [
+    ["accountId", ["accountIdentifier", "accountId"]],
+    ["orgId", ["orgIdentifier", "orgId"]],
+    ["projectId", ["projectIdentifier", "projectId"]],
+    ["pipelineId", ["pipelineIdentifier", "pipelineId"]],
+    ["executionId", ["planExecutionId", "executionId"]],
+    ["status", ["status", "nodeStatus", "pipelineStatus"]],
+    ["url", ["pipelineExecutionUrl", "executionUrl", "url"]],
+  ];
+  for (const [field, keys] of fields) {
+    const value = pick(...keys);
+    if (value) event[field] = value;
+  }
+  return event;
+}
+
+type EventListener = (event: HarnessEvent) => void;
+const listeners = new Set<EventListener>();
+
+/** Deliver an event to every attached session, and wake `wait: true` executions it concerns. */
+export function publishHarnessEvent(event: HarnessEvent): void {
+  if (event.executionId) wakeExecutionWaiters(event.executionId);
+  for (const listener of listeners) listener(event);
+}
+
+/**
+ * Start the NATS consumer when HARNESS_EVENTS_NATS_URL is set. Returns the
+ * subscriber so shutdown can stop it, or undefined when events are off.
+ */
+export function startHarnessEventConsumer(config: Config): NatsSubscriber | undefined {
+  if (!config.HARNESS_EVENTS_NATS_URL) return undefined;
+  const subscriber = new NatsSubscriber({
+    url: config.HARNESS_EVENTS_NATS_URL,
+    subjects: config.HARNESS_EVENTS_SUBJECTS.split(",").map((s) => s.trim()).filter(Boolean),
+    onMessage: (message) => {
+      const event = parseHarnessEvent(message);
+      if (!event) {
+        log.debug("Ignoring non-JSON event", { subject: message.subject });
+        return;
+      }
+      log.debug("Harness event", { type: event.type, sourceType: event.sourceType, executionId: event.executionId });
+      publishHarnessEvent(event);
+    },
+  });
+  subscriber.start();
+  return subscriber;
+}
+
+/**
+ * Forward events for this server's account to its client. Call after the
+ * resources are registered and before connecting; the listener is removed
+ * when the server closes. In multi-user mode, events without an account ID
+ * are not forwarded, since they cannot be attributed to a session.
+ */
+export function attachHarnessEvents(server: McpServer, config: Config): void {
+  const subscriptions = new Set<string>();
+  server.server.registerCapabilities({ resources: { subscribe: true } });
+  server.server.setRequestHandler(SubscribeRequestSchema, async (request) => {
+    subscriptions.add(request.params.uri);
+    return {};
+  });
+  server.server.setRequestHandler(UnsubscribeRequestSchema, async (request) => {
+    subscriptions.delete(request.params.uri);
+    return {};
+  });
+
+  const accountId = config.HARNESS_ACCOUNT_ID;
+  const listener: EventListener = (event) => {
+    if (event.accountId ? event.accountId !== accountId : config.HARNESS_MCP_MODE === "multi-user") return;
+    if (!server.isConnected()) return;
+    server.server
+      .notification({ method: "notifications/message", params: { level: "notice", logger: "harness-events", data: event } })
+      .catch(() => { /* client may be gone — delivery is best-effort */ });
+    if (event.type === "execution_finished" && subscriptions.has(RECENT_EXECUTIONS_URI)) {
+      server.server.sendResourceUpdated({ uri: RECENT_EXECUTIONS_URI }).catch(() => {});
+    }
+  };
+  listeners.add(listener);
+
+  // Detach on transport close, and on close() too: a server that was never
+  // connected has no transport to report it.
+  const onclose = server.server.onclose;
+  server.server.onclose = () => {
+    listeners.delete(listener);
+    onclose?.();
+  };
+  const close = server.close.bind(server);
+  server.close = async () => {
+    listeners.delete(listener);
+    await close();
+  };
+}
+
+/** Number of sessions currently receiving events. */
+export function attachedEventListenerCount(): number {
+  return listeners.size;
+}
@@ -3,10 +3,11 @@ import type { IncomingHttpHeaders } from "node:http";
 import type { RequestHandler } from "express";
 import type { Config } from "../config.js";
 import { createLogger } from "./logger.js";
+import { verifyJwt, type JwtValidationOptions } from "./http-jwt.js";
 
 const log = createLogger("http-auth");
 
-type HttpAuthConfig = Pick<Config, "HARNESS_MCP_AUTH_TOKEN" | "HARNESS_MCP_ALLOW_UNAUTHENTICATED_HTTP" | "HARNESS_MCP_MODE" | "HARNESS_API_KEY">;
+type HttpAuthConfig = Pick<Config, "HARNESS_MCP_AUTH_TOKEN" | "HARNESS_MCP_ALLOW_UNAUTHENTICATED_HTTP" | "HARNESS_MCP_MODE" | "HARNESS_API_KEY" | "HARNESS_MCP_TLS_CA" | "HARNESS_MCP_JWT_SECRET" | "HARNESS_MCP_JWT_PUBLIC_KEY">;
 
 export function isLoopbackBindHost(host: string): boolean {
   return host === "127.0.0.1" || host === "::1" || host === "localhost";
@@ -18,7 +19,7 @@ function getHeader(headers: IncomingHttpHeaders, name: string): string | undefin
   return typeof value === "string" ? value : undefined;
 }
 
-function timingSafeStringEqual(left: string, right: string): boolean {
+export function timingSafeStringEqual(left: string, right: string): boolean {
   const leftBuffer = Buffer.from(left);
   const rightBuffer = Buffer.from(right);
   if (leftBuffer.length !== rightBuffer.length) {
@@ -42,18 +43,80 @@ export function isAuthorizedHttpRequest(
   return timingSafeStringEqual(authorization, `Bearer ${token}`);
 }
 
-export function createHttpAuthMiddleware(token: string | undefined): RequestHandler {
+/**
+ * Check request headers against the static bearer token and/or JWT
+ * validation. With JWT auth configured, a request passes when it carries the
+ * static token (if set) or a valid JWT; the verified claims are returned.
+ * JWT failures name the exact reason (expired, wrong audience, ...).
+ */
+export function authenticateHttpHeaders(
+  headers: IncomingHttpHeaders,
+  token: string | undefined,
+  jwtOptions?: JwtValidationOptions,
+): { ok: true; jwtClaims?: unknown } | { ok: false; message: string } {
+  if (!jwtOptions) {
+    return isAuthorizedHttpRequest(headers, token) ? { ok: true } : { ok: false, message: "Unauthorized" };
+  }
+  if (token && isAuthorizedHttpRequest(headers, token)) return { ok: true };
+  const bearer = /^Bearer\s+(\S+)$/i.exec(getHeader(headers, "authorization") ?? "")?.[1];
+  if (!bearer) return { ok: false, message: "Unauthorized: missing bearer token" };
+  const result = verifyJwt(bearer, jwtOptions);
+  if (!result.ok) {
+    log.debug("JWT rejected", { reason: result.reason });
+    return { ok: false, message: `Unauthorized: ${result.reason}` };
+  }
+  return { ok: true, jwtClaims: result.claims };
+}
+
+/** Gate HTTP routes with `authenticateHttpHeaders`; verified JWT claims are kept on `res.locals.jwtClaims`. */
+export function createHttpAuthMiddleware(token: string | undefined, jwtOptions?: JwtValidationOptions): RequestHandler {
   return (req, res, next) => {
-    if (req.path === "/health" || req.method === "OPTIONS" || isAuthorizedHttpRequest(req.headers, token)) {
+    if (req.path === "/health" || req.method === "OPTIONS") {
       next();
       return;
     }
+    const result = authenticateHttpHeaders(req.headers, token, jwtOptions);
+    if (!result.ok) {
+      res.status(401).json({
+        jsonrpc: "2.0",
+        error: { code: -32001, message: result.message },
+        id: null,
+      });
+      return;
+    }
+    if (result.jwtClaims !== undefined) res.locals.jwtClaims = result.jwtClaims;
+    next();
+  };
+}
 
-    res.status(401).json({
-      jsonrpc: "2.0",
-      error: { code: -32001, message: "Unauthorized" },
-      id: null,
-    });
+type AdminRouteConfig = Pick<Config, "HARNESS_MCP_MODE" | "HARNESS_MCP_AUTH_TOKEN" | "HARNESS_MCP_ADMIN_TOKEN" | "HARNESS_MCP_JWT_SECRET" | "HARNESS_MCP_JWT_PUBLIC_KEY">;
+
+/**
+ * Whether admin routes (`/admin/debug`) are served. They toggle process-wide
+ * logging and expose every session's in-flight requests, so they need a real
+ * credential: HARNESS_MCP_ADMIN_TOKEN, or in single-user mode the MCP bearer
+ * token or JWT validation. Multi-user mode always needs the admin token — a
+ * tenant's MCP credential must not see other tenants' traffic.
+ */
+export function adminRoutesEnabled(config: AdminRouteConfig): boolean {
+  if (config.HARNESS_MCP_ADMIN_TOKEN) return true;
+  if (config.HARNESS_MCP_MODE === "multi-user") return false;
+  return !!config.HARNESS_MCP_AUTH_TOKEN || !!config.HARNESS_MCP_JWT_SECRET || !!config.HARNESS_MCP_JWT_PUBLIC_KEY;
+}
+
+/** Require `X-Harness-Admin-Token` on admin routes when an admin token is configured. */
+export function createAdminAuthMiddleware(adminToken: string | undefined): RequestHandler {
+  return (req, res, next) => {
+    if (!adminToken) {
+      next();
+      return;
+    }
+    const presented = getHeader(req.headers, "x-harness-admin-token");
+    if (!presented || !timingSafeStringEqual(presented, adminToken)) {
+      res.status(401).json({ error: "Unauthorized: missing or invalid X-Harness-Admin-Token" });
+      return;
+    }
+    next();
   };
 }
 
@@ -73,10 +136,15 @@ export function validateHttpAuthForBindHost(host: string, config: HttpAuthConfig
   }
 
   // Check 2: DNS-rebinding defense — non-loopback binds must be explicitly secured.
-  if (!isLoopbackBindHost(host) && !config.HARNESS_MCP_AUTH_TOKEN && !config.HARNESS_MCP_ALLOW_UNAUTHENTICATED_HTTP) {
+  // Client certificate verification (mTLS) and JWT validation authenticate every request, so they count.
+  const hasTransportAuth = !!config.HARNESS_MCP_AUTH_TOKEN
+    || !!config.HARNESS_MCP_TLS_CA
+    || !!config.HARNESS_MCP_JWT_SECRET
+    || !!config.HARNESS_MCP_JWT_PUBLIC_KEY;
+  if (!isLoopbackBindHost(host) && !hasTransportAuth && !config.HARNESS_MCP_ALLOW_UNAUTHENTICATED_HTTP) {
     throw new Error(
       "HARNESS_MCP_AUTH_TOKEN is required when HTTP transport binds to a non-loopback host. " +
-      "Set HARNESS_MCP_AUTH_TOKEN or explicitly set HARNESS_MCP_ALLOW_UNAUTHENTICATED_HTTP=true.",
+      "Set HARNESS_MCP_AUTH_TOKEN, configure JWT validation (HARNESS_MCP_JWT_SECRET or HARNESS_MCP_JWT_PUBLIC_KEY), require client certificates with HARNESS_MCP_TLS_CA, or explicitly set HARNESS_MCP_ALLOW_UNAUTHENTICATED_HTTP=true.",
     );
   }
 }
@@ -0,0 +1,76 @@
+import type { RequestHandler } from "express";
+import { isLoopbackBindHost } from "./http-auth.js";
+
+const ALLOW_METHODS = "GET, POST, DELETE, OPTIONS";
+const ALLOW_HEADERS = "Authorization, Content-Type, mcp-session-id, mcp-protocol-version, last-event-id, x-harness-api-key, x-harness-account-id, x-harness-org, x-harness-project, x-harness-pipeline-version, x-harness-auto-approve-risk";
+const EXPOSE_HEADERS = "mcp-session-id";
+
+/**
+ * Normalize an origin entry to `scheme://host[:port]`. Returns undefined for
+ * values that are not http(s) origins (paths, wildcards, bare hostnames).
+ */
+export function normalizeHttpOrigin(raw: string): string | undefined {
+  const trimmed = raw.trim();
+  if (!/^https?:\/\//i.test(trimmed)) return undefined;
+  try {
+    const url = new URL(trimmed);
+    if (url.pathname !== "/" || url.search || url.hash || url.username) return undefined;
+    return url.origin;
+  } catch {
+    return undefined;
+  }
+}
+
+/**
+ * Origins browsers may call the HTTP transport from: the server's own origin
+ * (all loopback spellings when bound to loopback) plus HARNESS_MCP_ALLOWED_ORIGINS.
+ */
+export function resolveAllowedOrigins(host: string, port: number, configured?: string, scheme: "http" | "https" = "http"): string[] {
+  const selfHosts = isLoopbackBindHost(host) ? ["127.0.0.1", "localhost", "[::1]"] : [host.includes(":") ? `[${host}]` : host];
+  const origins = selfHosts.map((h) => `${scheme}://${h}:${port}`);
+  for (const value of configured?.split(",") ?? []) {
+    const origin = normalizeHttpOrigin(value);
+    if (origin && !origins.includes(origin)) origins.push(origin);
+  }
+  return origins;
+}
+
+/**
+ * CORS and Origin validation for the HTTP transport.
+ *
+ * Requests without an `Origin` header (CLI and desktop MCP clients) pass
+ * through. Browser requests from an allowed origin get CORS headers and a 204
+ * preflight response; any other origin is rejected with 403, which blocks
+ * DNS-rebinding and cross-site requests from pages the user happens to open.
+ */
+export function createCorsMiddleware(allowedOrigins: readonly string[]): RequestHandler {
+  const allowed = new Set(allowedOrigins);
+  return (req, res, next) => {
+    const origin = req.headers.origin;
+    if (origin === undefined) {
+      next();
+      return;
+    }
+
+    if (!allowed.has(origin)) {
+      res.status(403).json({
+        jsonrpc: "2.0",
+        error: { code: -32000, message: `Origin not allowed: ${origin}` },
+        id: null,
+      });
+      return;
+    }
+
+    res.setHeader("Access-Control-Allow-Origin", origin);
+    res.setHeader("Vary", "Origin");
+    res.setHeader("Access-Control-Allow-Methods", ALLOW_METHODS);
+    res.setHeader("Access-Control-Allow-Headers", ALLOW_HEADERS);
+    res.setHeader("Access-Control-Expose-Headers", EXPOSE_HEADERS);
+    if (req.method === "OPTIONS") {
+      res.setHeader("Access-Control-Max-Age", "600");
+      res.status(204).end();
+      return;
+    }
+    next();
+  };
+}
@@ -5,16 +5,25 @@ export interface HttpHealthResponse {
   body: {
     status: "ok";
     sessions: number;
+    /** Multi-user mode only: distinct accounts with live sessions. */
+    accounts?: number;
+    /** Multi-user mode only: pooled API clients (one per account + credential). */
+    api_clients?: number;
     search: SearchReadiness;
   };
 }
 
-export function buildHttpHealthResponse(search: SearchReadiness, sessions: number): HttpHealthResponse {
+export function buildHttpHealthResponse(
+  search: SearchReadiness,
+  sessions: number,
+  pool?: { clients: number; accounts: number },
+): HttpHealthResponse {
   return {
     statusCode: 200,
     body: {
       status: "ok",
       sessions,
+      ...(pool ? { accounts: pool.accounts, api_clients: pool.clients } : {}),
       search,
     },
   };
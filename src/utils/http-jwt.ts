@@ -0,0 +1,148 @@
+/**
+ * JWT bearer validation for the HTTP transport (HARNESS_MCP_JWT_*).
+ *
+ * Service-to-service deployments authenticate callers with JWTs minted by an
+ * internal identity provider instead of a shared static token. Tokens are
+ * checked for signature, allowed algorithm, expiry (required), not-before,
+ * issuer, and audience, with a configurable clock-skew leeway. Every failure
+ * maps to a specific message so a caller can tell an expired token from a
+ * token minted for another service.
+ */
+
+import { readFileSync } from "node:fs";
+import jwt from "jsonwebtoken";
+import type { Config } from "../config.js";
+
+const HMAC_ALGORITHMS = ["HS256", "HS384", "HS512"] as const;
+const ASYMMETRIC_ALGORITHMS = ["RS256", "RS384", "RS512", "PS256", "PS384", "PS512", "ES256", "ES384", "ES512"] as const;
+
+type HttpJwtConfig = Pick<
+  Config,
+  | "HARNESS_MCP_JWT_SECRET"
+  | "HARNESS_MCP_JWT_PUBLIC_KEY"
+  | "HARNESS_MCP_JWT_ISSUER"
+  | "HARNESS_MCP_JWT_AUDIENCE"
+  | "HARNESS_MCP_JWT_LEEWAY_SECONDS"
+  | "HARNESS_MCP_JWT_ALGORITHMS"
+>;
+
+export interface JwtValidationOptions {
+  key: string | Buffer;
+  algorithms: jwt.Algorithm[];
+  issuer?: string;
+  audience?: string[];
+  leewaySeconds: number;
+}
+
+export type JwtVerification =
+  | { ok: true; claims: jwt.JwtPayload }
+  | { ok: false; reason: string };
+
+function splitList(raw: string | undefined): string[] {
+  return (raw ?? "").split(",").map((s) => s.trim()).filter(Boolean);
+}
+
+/**
+ * Allowed algorithms for the configured key. HMAC algorithms are only allowed
+ * with a shared secret and asymmetric ones only with a public key, so a token
+ * cannot downgrade verification by choosing the other family.
+ */
+export function resolveJwtAlgorithms(config: HttpJwtConfig): jwt.Algorithm[] {
+  const symmetric = Boolean(config.HARNESS_MCP_JWT_SECRET);
+  const family: readonly string[] = symmetric ? HMAC_ALGORITHMS : ASYMMETRIC_ALGORITHMS;
+  const requested = splitList(config.HARNESS_MCP_JWT_ALGORITHMS);
+  if (requested.length === 0) return [symmetric ? "HS256" : "RS256"];
+  const invalid = requested.filter((alg) => !family.includes(alg));
+  if (invalid.length > 0) {
+    throw new Error(
+      `Invalid HARNESS_MCP_JWT_ALGORITHMS entries for ${symmetric ? "HARNESS_MCP_JWT_SECRET" : "HARNESS_MCP_JWT_PUBLIC_KEY"}: ${invalid.join(", ")}. ` +
+      `Allowed: ${family.join(", ")}.`,
+    );
+  }
+  return requested as jwt.Algorithm[];
+}
+
+/** Throws when the JWT settings are inconsistent. Called from loadConfig. */
+export function validateHttpJwtConfig(config: HttpJwtConfig): void {
+  const enabled = Boolean(config.HARNESS_MCP_JWT_SECRET || config.HARNESS_MCP_JWT_PUBLIC_KEY);
+  if (config.HARNESS_MCP_JWT_SECRET && config.HARNESS_MCP_JWT_PUBLIC_KEY) {
+    throw new Error("Set either HARNESS_MCP_JWT_SECRET or HARNESS_MCP_JWT_PUBLIC_KEY, not both.");
+  }
+  if (!enabled) {
+    if (config.HARNESS_MCP_JWT_ISSUER || config.HARNESS_MCP_JWT_AUDIENCE || config.HARNESS_MCP_JWT_ALGORITHMS) {
+      throw new Error("HARNESS_MCP_JWT_ISSUER, HARNESS_MCP_JWT_AUDIENCE, and HARNESS_MCP_JWT_ALGORITHMS require HARNESS_MCP_JWT_SECRET or HARNESS_MCP_JWT_PUBLIC_KEY.");
+    }
+    return;
+  }
+  resolveJwtAlgorithms(config);
+}
+
+/** Validation options for the HTTP transport, or undefined when JWT auth is off. */
+export function loadJwtValidationOptions(config: HttpJwtConfig): JwtValidationOptions | undefined {
+  let key: string | Buffer;
+  if (config.HARNESS_MCP_JWT_SECRET) {
+    key = config.HARNESS_MCP_JWT_SECRET;
+  } else if (config.HARNESS_MCP_JWT_PUBLIC_KEY) {
+    try {
+      key = readFileSync(config.HARNESS_MCP_JWT_PUBLIC_KEY);
+    } catch (err) {
+      throw new Error(`Cannot read HARNESS_MCP_JWT_PUBLIC_KEY file "${config.HARNESS_MCP_JWT_PUBLIC_KEY}": ${err instanceof Error ? err.message : String(err)}`);
+    }
+  } else {
+    return undefined;
+  }
+  const audience = splitList(config.HARNESS_MCP_JWT_AUDIENCE);
+  return {
+    key,
+    algorithms: resolveJwtAlgorithms(config),
+    ...(config.HARNESS_MCP_JWT_ISSUER ? { issuer: config.HARNESS_MCP_JWT_ISSUER } : {}),
+    ...(audience.length > 0 ? { audience } : {}),
+    leewaySeconds: config.HARNESS_MCP_JWT_LEEWAY_SECONDS ?? 30,
+  };
+}
+
+const iso = (seconds: number): string => new Date(seconds * 1000).toISOString();
+
+/** Verify a bearer JWT. `nowSeconds` overrides the clock (for tests). */
+export function verifyJwt(token: string, options: JwtValidationOptions, nowSeconds = Math.floor(Date.now() / 1000)): JwtVerification {
+  const decoded = jwt.decode(token, { complete: true });
+  if (!decoded || typeof decoded.payload === "string") {
+    return { ok: false, reason: "JWT is malformed" };
+  }
+  const { header, payload } = decoded;
+  if (!options.algorithms.includes(header.alg as jwt.Algorithm)) {
+    return { ok: false, reason: `JWT algorithm ${header.alg} is not allowed (allowed: ${options.algorithms.join(", ")})` };
+  }
+  if (typeof payload.exp !== "number") {
+    return { ok: false, reason: "JWT has no exp claim" };
+  }
+
+  try {
+    const claims = jwt.verify(token, options.key, {
+      algorithms: options.algorithms,
+      clockTolerance: options.leewaySeconds,
+      clockTimestamp: nowSeconds,
+      ...(options.issuer ? { issuer: options.issuer } : {}),
+      ...(options.audience ? { audience: options.audience as [string, ...string[]] } : {}),
+    });
+    return { ok: true, claims: claims as jwt.JwtPayload };
+  } catch (err) {
+    if (err instanceof jwt.TokenExpiredError) {
+      return { ok: false, reason: `JWT expired at ${iso(payload.exp)} (leeway ${options.leewaySeconds}s)` };
+    }
+    if (err instanceof jwt.NotBeforeError) {
+      return { ok: false, reason: `JWT is not valid before ${iso(payload.nbf ?? nowSeconds)} (leeway ${options.leewaySeconds}s)` };
+    }
+    const message = err instanceof Error ? err.message : String(err);
+    if (message.startsWith("jwt issuer invalid")) {
+      return { ok: false, reason: `JWT issuer ${JSON.stringify(payload.iss ?? null)} does not match expected "${options.issuer}"` };
+    }
+    if (message.startsWith("jwt audience invalid")) {
+      return { ok: false, reason: `JWT audience ${JSON.stringify(payload.aud ?? null)} does not include any of: ${options.audience?.join(", ")}` };
+    }
+    if (message === "invalid signature") {
+      return { ok: false, reason: "JWT signature is invalid" };
+    }
+    return { ok: false, reason: `JWT is invalid: ${message}` };
+  }
+}
@@ -1,3 +1,7 @@
+import type { ServerResponse } from "node:http";
+import type { Server } from "@modelcontextprotocol/sdk/server/index.js";
+import { EmptyResultSchema } from "@modelcontextprotocol/sdk/types.js";
+
 export interface HttpSessionActivity {
   lastActivity: number;
   activeRequests: number;
@@ -16,3 +20,44 @@ export function endSessionRequest(session: HttpSessionActivity, now = Date.now()
 export function isSessionExpired(session: HttpSessionActivity, ttlMs: number, now = Date.now()): boolean {
   return session.activeRequests === 0 && now - session.lastActivity > ttlMs;
 }
+
+/**
+ * SSE comment line — ignored by SSE parsers, but resets load balancer and
+ * proxy idle timers on streams that are otherwise silent during long polls.
+ */
+export const SSE_KEEPALIVE_COMMENT = ": keepalive\n\n";
+
+/**
+ * Write a keepalive comment to `res` every `intervalMs` while it is an open
+ * SSE stream (a GET stream, or a POST response the transport upgraded to SSE).
+ * JSON responses are left untouched. Stops when the response closes; returns
+ * a stop function. 0 disables.
+ */
+export function startSseKeepalive(res: ServerResponse, intervalMs: number): () => void {
+  if (intervalMs <= 0) return () => {};
+  const timer = setInterval(() => {
+    if (res.writableEnded || res.destroyed) return stop();
+    if (!res.headersSent || !String(res.getHeader("content-type") ?? "").includes("text/event-stream")) return;
+    res.write(SSE_KEEPALIVE_COMMENT);
+  }, intervalMs);
+  timer.unref();
+  const stop = (): void => clearInterval(timer);
+  res.once("close", stop);
+  return stop;
+}
+
+/** Consecutive failed pings before a session with an open stream is treated as dead. */
+export const MAX_FAILED_PINGS = 2;
+
+/**
+ * Send an MCP `ping` to the client. Resolves false when the client does not
+ * answer within `timeoutMs` (half-open connection, suspended laptop, ...).
+ */
+export async function pingClient(server: Server, timeoutMs: number): Promise<boolean> {
+  try {
+    await server.request({ method: "ping" }, EmptyResultSchema, { timeout: timeoutMs });
+    return true;
+  } catch {
+    return false;
+  }
+}
@@ -0,0 +1,40 @@
+import { readFileSync } from "node:fs";
+import type { ServerOptions } from "node:https";
+import type { Config } from "../config.js";
+
+type HttpTlsConfig = Pick<Config, "HARNESS_MCP_TLS_CERT" | "HARNESS_MCP_TLS_KEY" | "HARNESS_MCP_TLS_CA">;
+
+/** Throws when the TLS settings are incomplete. Called from loadConfig so misconfiguration fails at startup. */
+export function validateHttpTlsConfig(config: HttpTlsConfig): void {
+  const { HARNESS_MCP_TLS_CERT: cert, HARNESS_MCP_TLS_KEY: key, HARNESS_MCP_TLS_CA: ca } = config;
+  if (Boolean(cert) !== Boolean(key)) {
+    throw new Error("HARNESS_MCP_TLS_CERT and HARNESS_MCP_TLS_KEY must be set together to enable TLS on the HTTP transport.");
+  }
+  if (ca && !cert) {
+    throw new Error("HARNESS_MCP_TLS_CA (client certificate verification) requires HARNESS_MCP_TLS_CERT and HARNESS_MCP_TLS_KEY.");
+  }
+}
+
+function readPem(envName: string, path: string): Buffer {
+  try {
+    return readFileSync(path);
+  } catch (err) {
+    throw new Error(`Cannot read ${envName} file "${path}": ${err instanceof Error ? err.message : String(err)}`);
+  }
+}
+
+/**
+ * HTTPS server options for the HTTP transport, or undefined when TLS is off.
+ * With HARNESS_MCP_TLS_CA set, clients must present a certificate signed by
+ * that CA (mutual TLS); connections without one fail the handshake.
+ */
+export function loadHttpTlsOptions(config: HttpTlsConfig): ServerOptions | undefined {
+  if (!config.HARNESS_MCP_TLS_CERT || !config.HARNESS_MCP_TLS_KEY) return undefined;
+  return {
+    cert: readPem("HARNESS_MCP_TLS_CERT", config.HARNESS_MCP_TLS_CERT),
+    key: readPem("HARNESS_MCP_TLS_KEY", config.HARNESS_MCP_TLS_KEY),
+    ...(config.HARNESS_MCP_TLS_CA
+      ? { ca: readPem("HARNESS_MCP_TLS_CA", config.HARNESS_MCP_TLS_CA), requestCert: true, rejectUnauthorized: true }
+      : {}),
+  };
+}
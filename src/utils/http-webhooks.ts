@@ -0,0 +1,40 @@
+/**
+ * Receiver for Harness notification webhooks (HTTP transport, HARNESS_WEBHOOK_SECRET).
+ *
+ * Point a pipeline notification rule's webhook at
+ * `POST /webhooks/harness?token=<secret>` (or send the secret in the
+ * `X-Harness-Webhook-Token` header). Each event is fed to the same hub as
+ * NATS events: `harness_execute` waits on the execution re-check immediately,
+ * and sessions for the account get a notification.
+ *
+ * The MCP bearer token is not required here — Harness cannot send one — so the
+ * route is registered ahead of the auth middleware and checks its own secret.
+ */
+import type { RequestHandler } from "express";
+import { normalizeHarnessEvent, publishHarnessEvent } from "./harness-events.js";
+import { timingSafeStringEqual } from "./http-auth.js";
+import { createLogger } from "./logger.js";
+
+const log = createLogger("http-webhooks");
+
+export const HARNESS_WEBHOOK_PATH = "/webhooks/harness";
+
+export function createHarnessWebhookHandler(secret: string): RequestHandler {
+  return (req, res) => {
+    const header = req.headers["x-harness-webhook-token"];
+    const query = req.query.token;
+    const presented = (Array.isArray(header) ? header[0] : header) ?? (typeof query === "string" ? query : "");
+    if (!timingSafeStringEqual(presented, secret)) {
+      res.status(401).json({ error: "Invalid webhook token" });
+      return;
+    }
+    const event = normalizeHarnessEvent(req.body, HARNESS_WEBHOOK_PATH);
+    if (!event) {
+      res.status(400).json({ error: "Expected a JSON object body" });
+      return;
+    }
+    log.debug("Harness webhook", { type: event.type, sourceType: event.sourceType, executionId: event.executionId });
+    publishHarnessEvent(event);
+    res.status(202).json({ received: true, type: event.type, executionId: event.executionId ?? null });
+  };
+}
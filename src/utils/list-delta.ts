@@ -0,0 +1,124 @@
+/**
+ * Delta mode for harness_list (`since_cursor`).
+ *
+ * Agents that poll a list (running executions, open PRs, active anomalies)
+ * otherwise get the full page back on every call. With delta mode each
+ * response carries a cursor; passing it back returns only the items added or
+ * changed since that response, plus the identities of items that dropped off
+ * the page.
+ *
+ * Snapshots live in memory, one tracker per MCP session, and are bounded —
+ * an unknown or evicted cursor falls back to a full snapshot with
+ * `reset: true`, so callers never silently miss changes.
+ */
+
+import { randomUUID } from "node:crypto";
+import { asString, isRecord } from "./type-guards.js";
+
+/** Cursors remembered per session; the oldest is evicted first. */
+const MAX_SNAPSHOTS = 50;
+
+/** Input keys that do not change which items a list call returns. */
+const NON_QUERY_KEYS = new Set(["since_cursor", "compact", "url"]);
+
+/** Fields tried in order for an item's identity. */
+const IDENTITY_FIELDS = ["planExecutionId", "identifier", "id", "uuid", "number", "name"];
+
+interface Snapshot {
+  query: string;
+  /** Item identity → fingerprint of its content. */
+  items: Map<string, string>;
+}
+
+export interface ListDelta {
+  /** Items added or changed since the cursor (every item when `reset`). */
+  items: unknown[];
+  delta: {
+    cursor: string;
+    since_cursor?: string;
+    /** True when the cursor was empty, unknown, or for a different query — `items` is the full page. */
+    reset: boolean;
+    added: number;
+    changed: number;
+    unchanged: number;
+    /** Identities of items on the previous page that are no longer there. */
+    removed: string[];
+  };
+}
+
+function stableStringify(value: unknown): string {
+  if (Array.isArray(value)) return `[${value.map(stableStringify).join(",")}]`;
+  if (isRecord(value)) {
+    return `{${Object.keys(value).sort().map((k) => `${JSON.stringify(k)}:${stableStringify(value[k])}`).join(",")}}`;
+  }
+  return JSON.stringify(value) ?? "null";
+}
+
+/** Identity of a list item — scoped by org/project so same-named entities in different projects stay distinct. */
+export function itemIdentity(item: unknown): string {
+  if (!isRecord(item)) return stableStringify(item);
+  const field = IDENTITY_FIELDS.find((f) => asString(item[f]) !== undefined || typeof item[f] === "number");
+  if (!field) return stableStringify(item);
+  const scope = [asString(item.orgIdentifier), asString(item.projectIdentifier)].filter(Boolean).join("/");
+  return scope ? `${scope}/${String(item[field])}` : String(item[field]);
+}
+
+/** Key for the query a list call answers, so a cursor is only honoured for the same query. */
+export function listQueryKey(input: Record<string, unknown>): string {
+  const query: Record<string, unknown> = {};
+  for (const [key, value] of Object.entries(input)) {
+    if (!NON_QUERY_KEYS.has(key) && value !== undefined) query[key] = value;
+  }
+  return stableStringify(query);
+}
+
+export class ListDeltaTracker {
+  private snapshots = new Map<string, Snapshot>();
+
+  /**
+   * Diff `items` against the snapshot behind `sinceCursor` and record a new
+   * snapshot. An empty `sinceCursor` starts delta mode.
+   */
+  diff(query: string, items: unknown[], sinceCursor: string): ListDelta {
+    const previous = sinceCursor ? this.snapshots.get(sinceCursor) : undefined;
+    const usable = previous?.query === query ? previous : undefined;
+
+    const current = new Map<string, string>();
+    const changedItems: unknown[] = [];
+    let added = 0;
+    let changed = 0;
+    for (const item of items) {
+      const id = itemIdentity(item);
+      const fingerprint = stableStringify(item);
+      current.set(id, fingerprint);
+      const before = usable?.items.get(id);
+      if (before === undefined) {
+        added++;
+        changedItems.push(item);
+      } else if (before !== fingerprint) {
+        changed++;
+        changedItems.push(item);
+      }
+    }
+    const removed = usable ? [...usable.items.keys()].filter((id) => !current.has(id)) : [];
+
+    const cursor = randomUUID();
+    this.snapshots.set(cursor, { query, items: current });
+    if (this.snapshots.size > MAX_SNAPSHOTS) {
+      this.snapshots.delete(this.snapshots.keys().next().value as string);
+    }
+
+    return {
+      items: changedItems,
+      delta: {
+        cursor,
+        ...(sinceCursor ? { since_cursor: sinceCursor } : {}),
+        reset: !usable,
+        added,
+        changed,
+        unchanged: items.length - changedItems.length,
+        removed,
+      },
+    };
+  }
+}
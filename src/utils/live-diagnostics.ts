@@ -0,0 +1,101 @@
+/**
+ * Live diagnostics for a running server: toggle debug logging and dump the
+ * tool calls and Harness API requests currently in flight, without restarting
+ * the client session.
+ *
+ *   kill -USR1 <pid>                              # stdio, socket, or http
+ *   curl -X POST http://127.0.0.1:3000/admin/debug # http only
+ *
+ * Tool calls are tracked by `withToolTimeout`, API requests by HarnessClient.
+ */
+
+import { createLogger, getLogLevel, setLogLevel, type LogLevel } from "./logger.js";
+
+const log = createLogger("live-diagnostics");
+
+export type InFlightKind = "tool" | "api";
+
+interface InFlightEntry {
+  kind: InFlightKind;
+  label: string;
+  startedAt: number;
+  detail?: Record<string, unknown>;
+}
+
+export interface InFlightSnapshot {
+  log_level: LogLevel;
+  in_flight: Array<{ kind: InFlightKind; label: string; started_at: string; elapsed_ms: number } & Record<string, unknown>>;
+}
+
+const inFlight = new Map<number, InFlightEntry>();
+let nextId = 0;
+
+/** Level to restore when debug logging is toggled off; set while debug is toggled on. */
+let levelBeforeDebug: LogLevel | undefined;
+
+/**
+ * Record a tool call or API request as in flight. Returns the function that
+ * removes it again; call it from a `finally`.
+ */
+export function beginInFlight(kind: InFlightKind, label: string, detail?: Record<string, unknown>): () => void {
+  const id = nextId++;
+  inFlight.set(id, { kind, label, startedAt: Date.now(), detail });
+  return () => { inFlight.delete(id); };
+}
+
+/** Everything in flight, oldest first, with the current log level. */
+export function inFlightSnapshot(now: number = Date.now()): InFlightSnapshot {
+  const entries = [...inFlight.values()].sort((a, b) => a.startedAt - b.startedAt);
+  return {
+    log_level: getLogLevel(),
+    in_flight: entries.map((e) => ({
+      kind: e.kind,
+      label: e.label,
+      started_at: new Date(e.startedAt).toISOString(),
+      elapsed_ms: now - e.startedAt,
+      ...e.detail,
+    })),
+  };
+}
+
+/**
+ * Switch to debug logging, or back to the level that was active before.
+ * Returns the new level.
+ */
+export function toggleDebugLogging(): LogLevel {
+  if (levelBeforeDebug !== undefined) {
+    setLogLevel(levelBeforeDebug);
+    levelBeforeDebug = undefined;
+  } else if (getLogLevel() !== "debug") {
+    levelBeforeDebug = getLogLevel();
+    setLogLevel("debug");
+  }
+  return getLogLevel();
+}
+
+/** Toggle debug logging and log the in-flight dump at warn, so it is visible at any level. */
+export function toggleAndDump(trigger: string): InFlightSnapshot {
+  const level = toggleDebugLogging();
+  const snapshot = inFlightSnapshot();
+  log.warn(`Live diagnostics (${trigger}): log level is now ${level}`, {
+    log_level: level,
+    in_flight_count: snapshot.in_flight.length,
+    in_flight: snapshot.in_flight,
+  });
+  return snapshot;
+}
+
+let signalInstalled = false;
+
+/** Handle SIGUSR1 with `toggleAndDump`. No-op on Windows, which has no SIGUSR1. Idempotent. */
+export function installDiagnosticsSignal(): void {
+  if (signalInstalled || process.platform === "win32") return;
+  signalInstalled = true;
+  process.on("SIGUSR1", () => { toggleAndDump("SIGUSR1"); });
+}
+
+/** Clear tracked requests and the toggle state. Tests only. */
+export function resetLiveDiagnostics(): void {
+  inFlight.clear();
+  levelBeforeDebug = undefined;
+}
@@ -2,6 +2,7 @@ import { gunzipSync, inflateRawSync } from "node:zlib";
 import type { HarnessClient } from "../client/harness-client.js";
 import { HarnessApiError } from "./errors.js";
 import { createLogger } from "./logger.js";
+import { bufferResponseBody, canSpillToDisk } from "./spill-buffer.js";
 
 const log = createLogger("log-resolver");
 
@@ -321,16 +322,23 @@ function extractZipEntriesFromLocalHeaders(buf: Buffer): ZipEntry[] {
   return entries;
 }
 
+/** File extension for a downloaded log blob, from its leading bytes. */
+function archiveExtension(magic: Buffer): string {
+  if (magic[0] === 0x50 && magic[1] === 0x4b) return ".zip";
+  if (magic[0] === 0x1f && magic[1] === 0x8b) return ".gz";
+  return ".log";
+}
+
 /**
- * Decompress a downloaded blob — handles gzip, zip, or plain text.
+ * Decompress a downloaded blob into its files — handles gzip, zip, or plain
+ * text. Gzip and plain text yield one unnamed file.
  */
-function decompressBlob(buf: Buffer): string {
-  if (buf.length === 0) return "";
+function decompressBlobFiles(buf: Buffer): ZipEntry[] {
+  if (buf.length === 0) return [];
 
   // Gzip
   if (buf[0] === GZIP_MAGIC_0 && buf[1] === GZIP_MAGIC_1) {
-    const decompressed = gunzipSync(buf, { maxOutputLength: DEFAULT_MAX_LOG_BYTES * 10 });
-    return decompressed.toString("utf-8");
+    return [{ fileName: "", data: gunzipSync(buf, { maxOutputLength: DEFAULT_MAX_LOG_BYTES * 10 }) }];
   }
 
   // ZIP
@@ -338,11 +346,18 @@ function decompressBlob(buf: Buffer): string {
     const entries = extractZipEntries(buf);
     // Sort by filename (typically contains timestamps)
     entries.sort((a, b) => a.fileName.localeCompare(b.fileName));
-    return entries.map((e) => e.data.toString("utf-8")).join("\n");
+    return entries;
   }
 
   // Plain text
-  return buf.toString("utf-8");
+  return [{ fileName: "", data: buf }];
+}
+
+/**
+ * Decompress a downloaded blob — handles gzip, zip, or plain text.
+ */
+function decompressBlob(buf: Buffer): string {
+  return decompressBlobFiles(buf).map((e) => e.data.toString("utf-8")).join("\n");
 }
 
 // ─── Blob download ──────────────────────────────────────────────────────────
@@ -561,17 +576,12 @@ export async function resolveLogDownloadUrl(
   return rewriteDownloadUrlIfNeeded(client, blobLink);
 }
 
-/**
- * Resolve execution log content from the Harness log-service.
- *
- * Full pipeline: initiate blob download → poll until ready → download zip →
- * extract → parse JSON log entries → return clean text.
- */
-export async function resolveLogContent(
+/** Initiate the blob download, poll until ready, and download the raw zip/gzip. */
+async function downloadLogBlob(
   client: HarnessClient,
   prefix: string,
   options?: LogResolveOptions,
-): Promise<string> {
+): Promise<Buffer> {
   const maxBytes = options?.maxLogSizeBytes ?? DEFAULT_MAX_LOG_BYTES;
   const signal = options?.signal;
 
@@ -589,21 +599,55 @@ export async function resolveLogContent(
   }
 
   const contentLength = Number(response.headers.get("content-length") ?? 0);
-  if (contentLength > maxBytes) {
+  if (contentLength > maxBytes && !canSpillToDisk()) {
     throw new Error(`Log file too large (${Math.round(contentLength / 1024 / 1024)}MB). Maximum: ${Math.round(maxBytes / 1024 / 1024)}MB.`);
   }
 
-  const arrayBuf = await response.arrayBuffer();
-  if (arrayBuf.byteLength > maxBytes) {
-    throw new Error(`Log file too large (${Math.round(arrayBuf.byteLength / 1024 / 1024)}MB). Maximum: ${Math.round(maxBytes / 1024 / 1024)}MB.`);
+  // Streamed so an oversized blob is either cut off early or, with HARNESS_OUTPUT_DIR set, spilled to disk
+  const name = `execution-log-${new Date().toISOString().replace(/[:.]/g, "-")}`;
+  const body = await bufferResponseBody(response, name, { maxInMemoryBytes: maxBytes });
+  if (body.size > maxBytes) {
+    // A spilled archive is kept for the user unless it hit the disk cap —
+    // a truncated archive cannot be opened
+    const file = body.spilled && !body.truncated
+      ? await body.persist(`${name}${archiveExtension(body.magic)}`).catch(() => undefined)
+      : undefined;
+    const truncated = body.truncated;
+    await body.dispose();
+    throw new Error(
+      `Log file too large (${truncated ? "over " : ""}${Math.round(body.size / 1024 / 1024)}MB). Maximum: ${Math.round(maxBytes / 1024 / 1024)}MB.` +
+      (file ? ` The raw log archive was saved to ${file.path}.` : "") +
+      (truncated ? " It also exceeds HARNESS_SPILL_MAX_MB, so it was not saved." : ""),
+    );
+  }
+  // Within maxBytes the body never spilled (bufferResponseBody keeps it in memory)
+  let buf: Buffer;
+  try {
+    buf = body.toBuffer();
+  } finally {
+    await body.dispose();
   }
-  const buf = Buffer.from(arrayBuf);
 
   log.debug("Downloaded blob", {
     bytes: buf.length,
     contentType: response.headers.get("content-type"),
     magic: buf.length >= 4 ? buf.subarray(0, 4).toString("hex") : "empty",
   });
+  return buf;
+}
+
+/**
+ * Resolve execution log content from the Harness log-service.
+ *
+ * Full pipeline: initiate blob download → poll until ready → download zip →
+ * extract → parse JSON log entries → return clean text.
+ */
+export async function resolveLogContent(
+  client: HarnessClient,
+  prefix: string,
+  options?: LogResolveOptions,
+): Promise<string> {
+  const buf = await downloadLogBlob(client, prefix, options);
 
   // Step 4 & 5: Extract and parse
   const rawText = decompressBlob(buf);
@@ -615,3 +659,23 @@ export async function resolveLogContent(
 
   return parsed;
 }
+
+export interface LogFile {
+  /** Log key of the file within the prefix (one per step), or "" for a single-file blob. */
+  key: string;
+  text: string;
+}
+
+/**
+ * Like `resolveLogContent`, but keeps the blob's files apart. A prefix that
+ * covers a stage or a whole execution downloads one file per step, so callers
+ * can say which step a line came from.
+ */
+export async function resolveLogFiles(
+  client: HarnessClient,
+  prefix: string,
+  options?: LogResolveOptions,
+): Promise<LogFile[]> {
+  const buf = await downloadLogBlob(client, prefix, options);
+  return decompressBlobFiles(buf).map((e) => ({ key: e.fileName, text: parseLogLines(e.data.toString("utf-8")) }));
+}
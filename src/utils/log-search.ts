@@ -0,0 +1,96 @@
+/**
+ * Server-side grep over execution logs.
+ *
+ * A failed execution's logs are often megabytes; the model usually needs only
+ * the lines around an error. `searchLogFiles` matches a regex line by line and
+ * returns grep-style hunks (`12: matched`, `11- context`) grouped per log file,
+ * so only the relevant slice is sent back.
+ */
+import type { LogFile } from "./log-resolver.js";
+
+const MAX_LINE_CHARS = 500;
+
+export interface LogSearchOptions {
+  /** From `compileLogPattern`. */
+  pattern: RegExp;
+  /** Lines of context before and after each match. */
+  contextLines: number;
+  /** Stop after this many matching lines. */
+  maxMatches: number;
+}
+
+export interface LogSearchHunk {
+  /** Log key of the step the lines came from ("" for a single-file log). */
+  log_key: string;
+  /** 1-based line numbers; matches are `N: text`, context is `N- text`. */
+  lines: string[];
+}
+
+export interface LogSearchResult {
+  pattern: string;
+  files_searched: number;
+  lines_searched: number;
+  total_matches: number;
+  truncated: boolean;
+  hunks: LogSearchHunk[];
+}
+
+/** Compile a search pattern; throws a user-facing error when it is not a valid regex. */
+export function compileLogPattern(pattern: string, caseSensitive = false): RegExp {
+  try {
+    return new RegExp(pattern, caseSensitive ? "" : "i");
+  } catch (err) {
+    throw new Error(`Invalid log_search pattern: ${err instanceof Error ? err.message : String(err)}`);
+  }
+}
+
+function clip(line: string): string {
+  return line.length > MAX_LINE_CHARS ? `${line.slice(0, MAX_LINE_CHARS)}…` : line;
+}
+
+/**
+ * Search log files for `pattern`. Counting continues past `maxMatches` so
+ * `total_matches` is exact; only the returned hunks are capped.
+ */
+export function searchLogFiles(files: LogFile[], options: LogSearchOptions): LogSearchResult {
+  const re = options.pattern;
+  const context = Math.max(0, options.contextLines);
+  const hunks: LogSearchHunk[] = [];
+  let linesSearched = 0;
+  let totalMatches = 0;
+
+  for (const file of files) {
+    const lines = file.text.split("\n");
+    linesSearched += lines.length;
+    const matched = new Set<number>();
+    for (let i = 0; i < lines.length; i++) {
+      if (!re.test(lines[i]!)) continue;
+      totalMatches++;
+      if (totalMatches <= options.maxMatches) matched.add(i);
+    }
+    let hunk: LogSearchHunk | undefined;
+    // Index one past the last line already emitted for this file
+    let emittedUpTo = 0;
+    for (const i of matched) {
+      const from = Math.max(emittedUpTo, i - context);
+      if (!hunk || from > emittedUpTo) {
+        hunk = { log_key: file.key, lines: [] };
+        hunks.push(hunk);
+      }
+      const to = Math.min(lines.length, i + context + 1);
+      for (let j = from; j < to; j++) {
+        hunk.lines.push(`${j + 1}${matched.has(j) ? ":" : "-"} ${clip(lines[j]!)}`);
+      }
+      emittedUpTo = to;
+    }
+  }
+
+  return {
+    pattern: re.source,
+    files_searched: files.length,
+    lines_searched: linesSearched,
+    total_matches: totalMatches,
+    truncated: totalMatches > options.maxMatches,
+    hunks,
+  };
+}
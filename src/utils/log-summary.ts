@@ -0,0 +1,169 @@
+/**
+ * Condense execution logs for failure analysis.
+ *
+ * Raw step logs are dominated by noise the model pays tokens for but never
+ * needs: ANSI colour codes, per-line timestamps, and the same progress or
+ * retry line printed hundreds of times. `summarizeLog`:
+ *
+ * 1. strips ANSI/control sequences and leading timestamps;
+ * 2. collapses runs of identical lines (`line [x42]`) and drops exact repeats
+ *    of lines seen earlier;
+ * 3. extracts error blocks — lines that look like errors plus the stack
+ *    trace lines that follow them;
+ * 4. fits the result in a character budget: error blocks first, then as much
+ *    of the head and tail of the condensed log as fits.
+ *
+ * Callers attach a pointer to the full log (`full_log`) so the model can
+ * fetch or grep the complete text when the summary is not enough.
+ */
+import { sanitizeText } from "./sanitize-text.js";
+
+const DEFAULT_MAX_CHARS = 8_000;
+const MAX_BLOCK_LINES = 40;
+const MAX_LINE_CHARS = 400;
+
+// [2026-03-09T17:01:23Z] info:  |  2026-03-09T17:01:23.123Z  |  2026-03-09 17:01:23,123  |  17:01:23
+const TIMESTAMP_RE = /^\s*(?:\[\s*\d{4}-\d{2}-\d{2}[T ][\d:.,]+(?:Z|[+-]\d{2}:?\d{2})?\s*\](?:\s+[a-z]+:)?|\d{4}-\d{2}-\d{2}[T ][\d:.,]+(?:Z|[+-]\d{2}:?\d{2})?|\[?\d{2}:\d{2}:\d{2}(?:[.,]\d+)?\]?)\s*/i;
+const ERROR_RE = /\b(?:error|exception|fatal|failed|failure|panic|traceback|denied|refused|timed? ?out|exit (?:code|status) [1-9])\b/i;
+// Java/JS `at ...`, Python `File "..."`, `Caused by:`, `... 12 more`, Go `goroutine`/`file.go:12`
+const STACK_RE = /^\s+at\s|^\s+File\s"|^Caused by:|^\s*\.\.\.\s\d+\smore|^goroutine\s\d+|^\s+\S+\.go:\d+|^\s{2,}\S/;
+
+export interface LogSummaryOptions {
+  /** Character budget for `error_blocks` plus `condensed`. Default 8000. */
+  maxChars?: number;
+}
+
+export interface LogErrorBlock {
+  /** 1-based line number in the cleaned, un-deduplicated log. */
+  line: number;
+  text: string;
+}
+
+export interface LogSummary {
+  error_blocks: LogErrorBlock[];
+  /** Condensed log; omitted middle sections are marked `… N lines omitted …`. */
+  condensed: string;
+  stats: {
+    total_lines: number;
+    condensed_lines: number;
+    duplicate_lines: number;
+    original_chars: number;
+    summary_chars: number;
+  };
+}
+
+/** Strip ANSI sequences, control characters, and a leading timestamp from one line. */
+export function cleanLogLine(line: string): string {
+  return sanitizeText(line).replace(TIMESTAMP_RE, "").trimEnd();
+}
+
+function clip(line: string): string {
+  return line.length > MAX_LINE_CHARS ? `${line.slice(0, MAX_LINE_CHARS)}…` : line;
+}
+
+function extractErrorBlocks(lines: string[]): LogErrorBlock[] {
+  const blocks: LogErrorBlock[] = [];
+  const seen = new Set<string>();
+  for (let i = 0; i < lines.length; i++) {
+    if (!ERROR_RE.test(lines[i]!)) continue;
+    const block = [clip(lines[i]!)];
+    let j = i + 1;
+    while (j < lines.length && STACK_RE.test(lines[j]!) && block.length < MAX_BLOCK_LINES) {
+      block.push(clip(lines[j]!));
+      j++;
+    }
+    const text = block.join("\n");
+    // The same error repeated (retries, matrix legs) is reported once
+    if (!seen.has(text)) {
+      seen.add(text);
+      blocks.push({ line: i + 1, text });
+    }
+    i = j - 1;
+  }
+  return blocks;
+}
+
+function condense(lines: string[]): { lines: string[]; duplicates: number } {
+  const out: string[] = [];
+  const seen = new Set<string>();
+  let duplicates = 0;
+  for (let i = 0; i < lines.length; ) {
+    const line = lines[i]!;
+    let run = 1;
+    while (i + run < lines.length && lines[i + run] === line) run++;
+    i += run;
+    if (!line.trim()) continue;
+    if (seen.has(line)) {
+      duplicates += run;
+      continue;
+    }
+    seen.add(line);
+    duplicates += run - 1;
+    out.push(run > 1 ? `${clip(line)} [x${run}]` : clip(line));
+  }
+  return { lines: out, duplicates };
+}
+
+/** Keep the head and tail of `lines` within `budget` characters, marking the omitted middle. */
+function fitHeadTail(lines: string[], budget: number): string {
+  const full = lines.join("\n");
+  if (full.length <= budget) return full;
+  const head: string[] = [];
+  const tail: string[] = [];
+  // Leave room for the omitted-lines marker; the tail usually holds the
+  // failure, so it gets two thirds of the rest
+  const available = budget - 32;
+  let headBudget = Math.floor(available / 3);
+  let tailBudget = available - headBudget;
+  let lo = 0;
+  let hi = lines.length - 1;
+  while (hi >= lo && lines[hi]!.length + 1 <= tailBudget) {
+    tailBudget -= lines[hi]!.length + 1;
+    tail.unshift(lines[hi--]!);
+  }
+  headBudget += tailBudget;
+  while (lo <= hi && lines[lo]!.length + 1 <= headBudget) {
+    headBudget -= lines[lo]!.length + 1;
+    head.push(lines[lo++]!);
+  }
+  const omitted = hi - lo + 1;
+  return [...head, ...(omitted > 0 ? [`… ${omitted} lines omitted …`] : []), ...tail].join("\n");
+}
+
+export function summarizeLog(text: string, options: LogSummaryOptions = {}): LogSummary {
+  const maxChars = options.maxChars ?? DEFAULT_MAX_CHARS;
+  const cleaned = text.split("\n").map(cleanLogLine);
+  const errorBlocks = extractErrorBlocks(cleaned);
+  const { lines, duplicates } = condense(cleaned);
+
+  // Error blocks may use up to half the budget; the rest goes to the condensed log
+  const keptBlocks: LogErrorBlock[] = [];
+  let blockChars = 0;
+  for (const block of errorBlocks) {
+    if (blockChars + block.text.length > maxChars / 2) break;
+    keptBlocks.push(block);
+    blockChars += block.text.length;
+  }
+  const condensed = fitHeadTail(lines, maxChars - blockChars);
+
+  return {
+    error_blocks: keptBlocks,
+    condensed,
+    stats: {
+      total_lines: cleaned.length,
+      condensed_lines: lines.length,
+      duplicate_lines: duplicates,
+      original_chars: text.length,
+      summary_chars: blockChars + condensed.length,
+    },
+  };
+}
+
+/** How to fetch (or grep) the full log behind a summary. */
+export function fullLogPointer(prefix: string): Record<string, unknown> {
+  return {
+    tool: "harness_get",
+    arguments: { resource_type: "execution_log", params: { prefix } },
+    hint: "Add log_search=<regex> to grep the full log instead of downloading all of it.",
+  };
+}
@@ -3,7 +3,13 @@
  * CRITICAL: Never write to stdout — it's reserved for JSON-RPC in stdio transport.
  */
 
-type LogLevel = "debug" | "info" | "warn" | "error";
+import { AsyncLocalStorage } from "node:async_hooks";
+import { appendFileSync } from "node:fs";
+import { format } from "node:util";
+import { redactSecretValues, redactSensitiveFields } from "./redact.js";
+import { currentTraceId } from "./trace-id.js";
+
+export type LogLevel = "debug" | "info" | "warn" | "error";
 
 const LOG_LEVELS: Record<LogLevel, number> = {
   debug: 0,
@@ -18,6 +24,97 @@ export function setLogLevel(level: LogLevel): void {
   globalLevel = level;
 }
 
+export function getLogLevel(): LogLevel {
+  return globalLevel;
+}
+
+/** When set, log lines are appended here instead of written to stderr. */
+let logFile: string | undefined;
+
+/**
+ * Route log output to a file (LOG_FILE) instead of stderr. Call once at startup.
+ */
+export function configureLogOutput(opts: { file?: string }): void {
+  logFile = opts.file;
+}
+
+function writeLine(line: string): void {
+  if (logFile) {
+    try {
+      appendFileSync(logFile, line + "\n");
+      return;
+    } catch {
+      // Unwritable log file — fall through to stderr rather than lose the line
+    }
+  }
+  console.error(line);
+}
+
+export interface LogRecord {
+  level: LogLevel;
+  module: string;
+  msg: string;
+  data: Record<string, unknown>;
+}
+
+/**
+ * Which connection (and request) a log line belongs to. Set around each
+ * inbound MCP message so lines logged while handling it can be forwarded to
+ * that client only.
+ */
+export interface LogContext {
+  owner: symbol;
+  requestId?: string | number;
+}
+
+type LogForwarder = (record: LogRecord, context: LogContext) => void;
+
+const logContext = new AsyncLocalStorage<LogContext>();
+const forwarders = new Map<symbol, LogForwarder>();
+
+/** Run `fn` with log lines attributed to `context.owner` (see `addLogForwarder`). */
+export function runWithLogContext<T>(context: LogContext, fn: () => T): T {
+  return logContext.run(context, fn);
+}
+
+/**
+ * Receive (already redacted) log records emitted while handling `owner`'s
+ * requests, independent of LOG_LEVEL. Returns an unsubscribe function.
+ */
+export function addLogForwarder(owner: symbol, forwarder: LogForwarder): () => void {
+  forwarders.set(owner, forwarder);
+  return () => { forwarders.delete(owner); };
+}
+
+function forward(record: LogRecord): void {
+  const context = logContext.getStore();
+  if (!context) return;
+  try {
+    forwarders.get(context.owner)?.(record, context);
+  } catch {
+    // Forwarding is best-effort — never let it break the caller
+  }
+}
+
+let stdoutGuarded = false;
+
+/**
+ * Stdio transport safety: stdout carries JSON-RPC frames, so a stray
+ * `console.log` from this code or a dependency corrupts the stream and the
+ * client drops the connection. Reroute every console method that writes to
+ * stdout through the logger (stderr or LOG_FILE) instead. Idempotent.
+ */
+export function guardStdout(): void {
+  if (stdoutGuarded) return;
+  stdoutGuarded = true;
+  const guardLog = createLogger("stdout-guard");
+  for (const method of ["log", "info", "debug", "dir", "table"] as const) {
+    console[method] = (...args: unknown[]) => {
+      guardLog.warn(`console.${method} redirected from stdout`, { output: format(...args) });
+    };
+  }
+}
+
 export interface Logger {
   debug: (msg: string, data?: Record<string, unknown>) => void;
   info: (msg: string, data?: Record<string, unknown>) => void;
@@ -75,17 +172,27 @@ export function logAudit(entry: AuditEntry): void {
 
 export function createLogger(module: string): Logger {
   function log(level: LogLevel, message: string, data?: Record<string, unknown>): void {
-    if (LOG_LEVELS[level] < LOG_LEVELS[globalLevel]) return;
+    const toStderr = LOG_LEVELS[level] >= LOG_LEVELS[globalLevel];
+    const context = logContext.getStore();
+    const toClient = context !== undefined && forwarders.has(context.owner);
+    if (!toStderr && !toClient) return;
+
+    const msg = redactSecretValues(message);
+    const fields = data ? (redactSensitiveFields(data) as Record<string, unknown>) : {};
+    const traceId = currentTraceId();
+    if (traceId && fields.trace_id === undefined) fields.trace_id = traceId;
+    if (toClient) forward({ level, module, msg, data: fields });
+    if (!toStderr) return;
 
     const entry = {
       ts: new Date().toISOString(),
       level,
       module,
-      msg: message,
-      ...data,
+      msg,
+      ...fields,
     };
 
-    console.error(JSON.stringify(entry));
+    writeLine(JSON.stringify(entry));
   }
 
   return {
@@ -0,0 +1,95 @@
+/**
+ * MCP logging capability — forward server diagnostics to the client as
+ * `notifications/message`.
+ *
+ * Lines logged while handling a connection's requests (retries, rate
+ * limiting, auth warnings, ...) are sent to that connection only, filtered by
+ * the level the client chose with `logging/setLevel`. Until the client picks
+ * a level, only warnings and errors are sent so chat UIs are not flooded with
+ * debug output. Background work (startup, index refresh) is never forwarded.
+ */
+
+import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import type { LoggingLevel } from "@modelcontextprotocol/sdk/types.js";
+import { addLogForwarder, runWithLogContext, type LogLevel } from "./logger.js";
+import { isRecord } from "./type-guards.js";
+
+/** RFC 5424 severities, as used by MCP logging. */
+const MCP_LEVEL_SEVERITY: Record<LoggingLevel, number> = {
+  debug: 0,
+  info: 1,
+  notice: 2,
+  warning: 3,
+  error: 4,
+  critical: 5,
+  alert: 6,
+  emergency: 7,
+};
+
+const LOGGER_TO_MCP_LEVEL: Record<LogLevel, LoggingLevel> = {
+  debug: "debug",
+  info: "info",
+  warn: "warning",
+  error: "error",
+};
+
+/** Applied until the client sends `logging/setLevel`. */
+export const DEFAULT_CLIENT_LOG_LEVEL: LoggingLevel = "warning";
+
+/** Modules whose lines are never forwarded — frame dumps would echo every notification they cause. */
+const NEVER_FORWARDED = new Set(["jsonrpc"]);
+
+export function shouldForwardLog(level: LogLevel, clientLevel: LoggingLevel): boolean {
+  return MCP_LEVEL_SEVERITY[LOGGER_TO_MCP_LEVEL[level]] >= MCP_LEVEL_SEVERITY[clientLevel];
+}
+
+/**
+ * Forward log lines produced while handling this transport's messages to its
+ * client. Call after `server.connect(transport)`; the forwarder is removed
+ * when the transport closes.
+ */
+export function attachMcpLogging(server: McpServer, transport: Transport): void {
+  const owner = Symbol("mcp-logging");
+  let clientLevel: LoggingLevel = DEFAULT_CLIENT_LOG_LEVEL;
+  let sending = false;
+
+  const detach = addLogForwarder(owner, (record, context) => {
+    if (sending || NEVER_FORWARDED.has(record.module) || !shouldForwardLog(record.level, clientLevel)) return;
+    sending = true;
+    try {
+      // Tie the message to the request being handled so HTTP clients get it on that request's stream
+      server.server
+        .notification({
+          method: "notifications/message",
+          params: {
+            level: LOGGER_TO_MCP_LEVEL[record.level],
+            logger: record.module,
+            data: { msg: record.msg, ...record.data },
+          },
+        }, context.requestId !== undefined ? { relatedRequestId: context.requestId } : undefined)
+        .catch(() => { /* client may be gone — logging is best-effort */ });
+    } finally {
+      sending = false;
+    }
+  });
+
+  const onmessage = transport.onmessage;
+  if (onmessage) {
+    transport.onmessage = (message, extra) => {
+      // The SDK answers logging/setLevel itself; mirror the choice for our filter
+      if ("method" in message && message.method === "logging/setLevel" && isRecord(message.params)) {
+        const level = message.params.level;
+        if (typeof level === "string" && level in MCP_LEVEL_SEVERITY) clientLevel = level as LoggingLevel;
+      }
+      const requestId = "id" in message && "method" in message ? message.id : undefined;
+      runWithLogContext({ owner, requestId }, () => onmessage.call(transport, message, extra));
+    };
+  }
+
+  const onclose = transport.onclose;
+  transport.onclose = () => {
+    detach();
+    onclose?.call(transport);
+  };
+}
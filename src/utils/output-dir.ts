@@ -0,0 +1,43 @@
+/**
+ * Writes tool-generated files (e.g. cost exports) into HARNESS_OUTPUT_DIR so
+ * clients can open them via a `resource_link` instead of receiving large
+ * payloads inline. File names are sanitized and always resolve inside the
+ * output directory.
+ */
+import { mkdir, writeFile } from "node:fs/promises";
+import { basename, join, resolve } from "node:path";
+import { pathToFileURL } from "node:url";
+
+export interface OutputFile {
+  path: string;
+  uri: string;
+  name: string;
+  bytes: number;
+}
+
+/** Reduce a proposed name to a safe single path segment. */
+export function sanitizeFileName(name: string): string {
+  const cleaned = basename(name).replace(/[^A-Za-z0-9._-]+/g, "_").replace(/^\.+/, "");
+  return cleaned || "output";
+}
+
+export async function writeOutputFile(dir: string, name: string, content: string | Uint8Array): Promise<OutputFile> {
+  const root = resolve(dir);
+  await mkdir(root, { recursive: true });
+  const fileName = sanitizeFileName(name);
+  const path = join(root, fileName);
+  await writeFile(path, content, typeof content === "string" ? "utf-8" : undefined);
+  return { path, uri: pathToFileURL(path).href, name: fileName, bytes: typeof content === "string" ? Buffer.byteLength(content, "utf-8") : content.byteLength };
+}
+
+/** RFC 4180 CSV: quote fields containing commas, quotes, or newlines. */
+export function toCsv(columns: string[], rows: Array<Record<string, unknown>>): string {
+  const cell = (v: unknown): string => {
+    if (v === null || v === undefined) return "";
+    const s = typeof v === "object" ? JSON.stringify(v) : String(v);
+    return /[",\r\n]/.test(s) ? `"${s.replace(/"/g, '""')}"` : s;
+  };
+  const lines = [columns.map(cell).join(",")];
+  for (const row of rows) lines.push(columns.map((c) => cell(row[c])).join(","));
+  return lines.join("\n") + "\n";
+}
@@ -0,0 +1,113 @@
+/**
+ * Harness RBAC pre-checks with a per-principal decision cache
+ * (HARNESS_PERMISSION_PRECHECK).
+ *
+ * Write operations that declare `requiredPermission` are checked against the
+ * access control API before they run, so an agent gets "missing
+ * core_pipeline_execute on pipeline X" instead of an opaque 403 halfway
+ * through a multi-step change. Decisions are cached per principal (account
+ * plus API key and JWT subject) for a short TTL so repeated
+ * checks do not hit the ACL service, and every cached decision for a
+ * principal is dropped as soon as any Harness call made for it returns 403 —
+ * a 403 means the cached view of its permissions is stale.
+ */
+
+import type { HarnessClient } from "../client/harness-client.js";
+import { currentPrincipal } from "./principal.js";
+import { asRecord } from "./type-guards.js";
+import { recordCacheLookup } from "./server-metrics.js";
+
+/** Cached decisions expire after one minute. */
+export const PERMISSION_CACHE_TTL_MS = 60_000;
+
+const MAX_ENTRIES = 10_000;
+
+export interface PermissionCheck {
+  /** ACL resource type, e.g. PIPELINE, CONNECTOR, SECRET. */
+  resourceType: string;
+  /** Permission identifier, e.g. core_pipeline_execute. */
+  permission: string;
+  resourceIdentifier?: string;
+  orgIdentifier?: string;
+  projectIdentifier?: string;
+}
+
+const decisions = new Map<string, { permitted: boolean; expiresAt: number }>();
+
+/**
+ * Cache partition for the caller: the account, the client's credential (so
+ * multi-user sessions with different API keys never share decisions), and
+ * the JWT subject, when there is one.
+ */
+export function permissionPrincipalKey(accountId: string, credentialId = ""): string {
+  return `${accountId}|${credentialId}|${currentPrincipal()?.id ?? ""}`;
+}
+
+function checkKey(principalKey: string, check: PermissionCheck): string {
+  return [
+    principalKey,
+    check.orgIdentifier ?? "",
+    check.projectIdentifier ?? "",
+    check.resourceType,
+    check.resourceIdentifier ?? "",
+    check.permission,
+  ].join("|");
+}
+
+/** Drop every cached decision for a principal. Called by HarnessClient on 403. */
+export function invalidatePermissions(principalKey: string): void {
+  const prefix = `${principalKey}|`;
+  for (const key of decisions.keys()) {
+    if (key.startsWith(prefix)) decisions.delete(key);
+  }
+}
+
+/** Drop all cached decisions (useful for testing). */
+export function resetPermissionCache(): void {
+  decisions.clear();
+}
+
+/**
+ * Whether the caller holds `check.permission`. Served from the cache when a
+ * fresh decision exists; otherwise asks the ACL service and caches the answer.
+ */
+export async function hasPermission(
+  client: HarnessClient,
+  check: PermissionCheck,
+  signal?: AbortSignal,
+  now = Date.now(),
+): Promise<boolean> {
+  const accountId = client.account;
+  const key = checkKey(permissionPrincipalKey(accountId, client.credentialId), check);
+  const cached = decisions.get(key);
+  if (cached && cached.expiresAt > now) {
+    recordCacheLookup("permissions", true);
+    return cached.permitted;
+  }
+  recordCacheLookup("permissions", false);
+
+  const raw = await client.request<unknown>({
+    method: "POST",
+    path: "/authz/api/acl",
+    body: {
+      permissions: [{
+        resourceScope: {
+          accountIdentifier: accountId,
+          ...(check.orgIdentifier ? { orgIdentifier: check.orgIdentifier } : {}),
+          ...(check.projectIdentifier ? { projectIdentifier: check.projectIdentifier } : {}),
+        },
+        resourceType: check.resourceType,
+        ...(check.resourceIdentifier ? { resourceIdentifier: check.resourceIdentifier } : {}),
+        permission: check.permission,
+      }],
+    },
+    retryPolicy: "safe",
+    signal,
+  });
+  const acl = asRecord(asRecord(raw)?.data)?.accessControlList;
+  const permitted = Array.isArray(acl) && asRecord(acl[0])?.permitted === true;
+
+  if (decisions.size >= MAX_ENTRIES) decisions.delete(decisions.keys().next().value!);
+  decisions.set(key, { permitted, expiresAt: now + PERMISSION_CACHE_TTL_MS });
+  return permitted;
+}
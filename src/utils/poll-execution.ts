@@ -8,6 +8,10 @@
  *
  * Pure logic — receives the Registry + HarnessClient via DI so future callers
  * (e.g. a standalone `wait` execute action on `execution`) can reuse it.
+ *
+ * Pushed events (the HTTP webhook receiver, HARNESS_EVENTS_NATS_URL) call
+ * `wakeExecutionWaiters` so a wait re-checks as soon as Harness reports a
+ * change instead of at the next backoff tick.
  */
 
 import type { HarnessClient } from "../client/harness-client.js";
@@ -85,25 +89,59 @@ class AbortError extends Error {
   }
 }
 
+/** Wake-ups for in-flight waits, keyed by execution ID (see `wakeExecutionWaiters`). */
+const waiters = new Map<string, Set<() => void>>();
+
+/**
+ * Cut the current poll sleep short for every wait on `executionId`, so the
+ * next status check happens now. Called when a Harness webhook or event says
+ * the execution changed. Only the follow-up API call decides the outcome, so
+ * a spoofed or stale event costs one extra poll. Returns the number of waits
+ * woken.
+ */
+export function wakeExecutionWaiters(executionId: string): number {
+  const wakers = waiters.get(executionId);
+  if (!wakers) return 0;
+  const woken = [...wakers];
+  for (const wake of woken) wake();
+  return woken.length;
+}
+
 /**
- * Sleep for `ms`, rejecting early if the signal aborts.
- * Cleans up listeners and timers in both branches to avoid leaks across long polls.
+ * Sleep for `ms`, rejecting early if the signal aborts and resolving early
+ * when `wakeExecutionWaiters(wakeKey)` is called.
+ * Cleans up listeners and timers in every branch to avoid leaks across long polls.
  */
-function abortableSleep(ms: number, signal?: AbortSignal): Promise<void> {
+function abortableSleep(ms: number, signal?: AbortSignal, wakeKey?: string): Promise<void> {
   return new Promise<void>((resolve, reject) => {
     if (signal?.aborted) {
       reject(new AbortError());
       return;
     }
-    const timer = setTimeout(() => {
+    const cleanup = (): void => {
+      clearTimeout(timer);
       if (signal) signal.removeEventListener("abort", onAbort);
+      if (wakeKey) {
+        const wakers = waiters.get(wakeKey);
+        wakers?.delete(wake);
+        if (wakers?.size === 0) waiters.delete(wakeKey);
+      }
+    };
+    const wake = (): void => {
+      cleanup();
       resolve();
-    }, ms);
+    };
+    const timer = setTimeout(wake, ms);
     const onAbort = (): void => {
-      clearTimeout(timer);
+      cleanup();
       reject(new AbortError());
     };
     if (signal) signal.addEventListener("abort", onAbort, { once: true });
+    if (wakeKey) {
+      const wakers = waiters.get(wakeKey) ?? new Set();
+      wakers.add(wake);
+      waiters.set(wakeKey, wakers);
+    }
   });
 }
 
@@ -166,7 +204,7 @@ export async function pollExecutionToTerminal(
 
   // Initial sleep — gives the execution a moment to be persisted by Harness
   // before we ask for its status.
-  await abortableSleep(opts.initialIntervalMs, opts.signal);
+  await abortableSleep(opts.initialIntervalMs, opts.signal, opts.executionId);
 
   while (true) {
     if (opts.signal?.aborted) throw new AbortError();
@@ -220,7 +258,7 @@ export async function pollExecutionToTerminal(
     if (remaining <= 0) {
       return buildResult(opts.executionId, lastSnapshot, false, true, Date.now() - startedAt, pollCount);
     }
-    await abortableSleep(Math.min(interval, remaining), opts.signal);
+    await abortableSleep(Math.min(interval, remaining), opts.signal, opts.executionId);
   }
 }
 
@@ -0,0 +1,83 @@
+/**
+ * Caller identity for HTTP sessions authenticated with a JWT (HARNESS_MCP_JWT_*).
+ *
+ * The principal is taken from the validated token on the `initialize`
+ * request and bound to the session. While the session's messages are being
+ * handled it is available through AsyncLocalStorage, and HarnessClient adds
+ * `X-Principal-*` headers to every Harness API call so downstream services
+ * see the original caller rather than only the server's API key.
+ */
+
+import { AsyncLocalStorage } from "node:async_hooks";
+import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import { asString, isRecord } from "./type-guards.js";
+
+export interface Principal {
+  /** Token subject (`sub`). */
+  id: string;
+  /** Principal kind, e.g. USER or SERVICE, from a `principal_type` or `type` claim. */
+  type?: string;
+  issuer?: string;
+  email?: string;
+  name?: string;
+}
+
+const principalContext = new AsyncLocalStorage<Principal>();
+
+/** Principal for verified JWT claims, or undefined when there is no subject. */
+export function principalFromClaims(claims: unknown): Principal | undefined {
+  if (!isRecord(claims)) return undefined;
+  const id = asString(claims.sub);
+  if (!id) return undefined;
+  const type = asString(claims.principal_type) ?? asString(claims.type);
+  const issuer = asString(claims.iss);
+  const email = asString(claims.email);
+  const name = asString(claims.name) ?? asString(claims.preferred_username);
+  return {
+    id,
+    ...(type ? { type } : {}),
+    ...(issuer ? { issuer } : {}),
+    ...(email ? { email } : {}),
+    ...(name ? { name } : {}),
+  };
+}
+
+/** The principal whose session is being handled, if any. */
+export function currentPrincipal(): Principal | undefined {
+  return principalContext.getStore();
+}
+
+/** Run `fn` on behalf of `principal`. */
+export function runWithPrincipal<T>(principal: Principal, fn: () => T): T {
+  return principalContext.run(principal, fn);
+}
+
+/**
+ * Handle every inbound message on `transport` on behalf of `principal`. Call
+ * after `server.connect(transport)` — connect installs `onmessage`.
+ */
+export function attachPrincipal(transport: Transport, principal: Principal): void {
+  const onmessage = transport.onmessage;
+  if (!onmessage) return;
+  transport.onmessage = (message, extra) => {
+    runWithPrincipal(principal, () => onmessage.call(transport, message, extra));
+  };
+}
+
+/** Header-safe value: no control characters, non-ASCII percent-encoded. */
+function headerValue(value: string): string {
+  // eslint-disable-next-line no-control-regex
+  const printable = value.replace(/[\u0000-\u001f\u007f]/g, "");
+  return /^[\x20-\x7e]*$/.test(printable) ? printable : encodeURIComponent(printable);
+}
+
+/** Identity headers for downstream calls made on behalf of `principal`. */
+export function principalHeaders(principal: Principal | undefined): Record<string, string> {
+  if (!principal) return {};
+  const headers: Record<string, string> = { "X-Principal-Id": headerValue(principal.id) };
+  if (principal.type) headers["X-Principal-Type"] = headerValue(principal.type);
+  if (principal.issuer) headers["X-Principal-Issuer"] = headerValue(principal.issuer);
+  if (principal.email) headers["X-Principal-Email"] = headerValue(principal.email);
+  if (principal.name) headers["X-Principal-Name"] = headerValue(principal.name);
+  return headers;
+}
@@ -0,0 +1,113 @@
+/**
+ * MCP protocol version negotiation and per-version result shaping.
+ *
+ * The SDK answers `initialize` with the client's requested revision when it
+ * supports it (otherwise its latest). Tools are registered once with the
+ * newest features — titles, annotations, output schemas, structured content,
+ * resource links — so this adapter records the negotiated revision per
+ * transport and strips what an older client's schema does not define:
+ *
+ * | Revision   | Tool annotations | title / outputSchema / structuredContent / resource_link |
+ * |------------|------------------|----------------------------------------------------------|
+ * | 2024-11-05 | stripped         | stripped                                                 |
+ * | 2025-03-26 | kept             | stripped                                                 |
+ * | 2025-06-18+| kept             | kept                                                     |
+ */
+
+import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import type { JSONRPCMessage } from "@modelcontextprotocol/sdk/types.js";
+import { LATEST_PROTOCOL_VERSION, SUPPORTED_PROTOCOL_VERSIONS } from "@modelcontextprotocol/sdk/types.js";
+import { isRecord } from "./type-guards.js";
+import { createLogger } from "./logger.js";
+
+const log = createLogger("protocol");
+
+export interface ProtocolFeatures {
+  version: string;
+  /** Tool `annotations` (readOnlyHint, destructiveHint, ...) — 2025-03-26+. */
+  toolAnnotations: boolean;
+  /** Tool `title`/`outputSchema`, result `structuredContent`, and `resource_link` content — 2025-06-18+. */
+  structuredOutput: boolean;
+}
+
+/** Mirror the SDK's choice: echo a supported requested revision, otherwise answer with the latest. */
+export function negotiateProtocolVersion(requested: unknown): string {
+  return typeof requested === "string" && SUPPORTED_PROTOCOL_VERSIONS.includes(requested)
+    ? requested
+    : LATEST_PROTOCOL_VERSION;
+}
+
+/** Revisions are ISO dates, so string comparison orders them. */
+export function protocolFeatures(version: string): ProtocolFeatures {
+  return {
+    version,
+    toolAnnotations: version >= "2025-03-26",
+    structuredOutput: version >= "2025-06-18",
+  };
+}
+
+function adaptTool(tool: Record<string, unknown>, features: ProtocolFeatures): Record<string, unknown> {
+  const { title, outputSchema, annotations, ...rest } = tool;
+  return {
+    ...rest,
+    ...(features.structuredOutput ? { title, outputSchema } : {}),
+    ...(features.toolAnnotations ? { annotations } : {}),
+  };
+}
+
+/** Older clients cannot render resource_link items — keep the URI visible as text. */
+function downgradeContent(item: unknown): unknown {
+  if (!isRecord(item) || item.type !== "resource_link") return item;
+  const label = typeof item.description === "string" ? ` — ${item.description}` : "";
+  return { type: "text", text: `Resource ${String(item.name)}: ${String(item.uri)}${label}` };
+}
+
+/**
+ * Shape an outbound message for the negotiated revision. Only `tools/list`
+ * and `tools/call` results change; everything else passes through untouched.
+ */
+export function adaptMessageForProtocol(message: JSONRPCMessage, features: ProtocolFeatures): JSONRPCMessage {
+  if (features.structuredOutput && features.toolAnnotations) return message;
+  if (!("result" in message) || !isRecord(message.result)) return message;
+  const result = message.result;
+
+  if (Array.isArray(result.tools)) {
+    return { ...message, result: { ...result, tools: result.tools.map((t) => (isRecord(t) ? adaptTool(t, features) : t)) } };
+  }
+  if (Array.isArray(result.content) && !features.structuredOutput) {
+    const { structuredContent: _structured, ...rest } = result;
+    return { ...message, result: { ...rest, content: result.content.map(downgradeContent) } };
+  }
+  return message;
+}
+
+/**
+ * Track the negotiated revision on a connected transport and adapt outbound
+ * results to it. Call after `server.connect(transport)`. Returns a getter for
+ * the negotiated revision (undefined until `initialize` arrives).
+ */
+export function attachProtocolAdapter(transport: Transport): () => string | undefined {
+  let features: ProtocolFeatures | undefined;
+
+  const onmessage = transport.onmessage;
+  if (onmessage) {
+    transport.onmessage = (message, extra) => {
+      if ("method" in message && message.method === "initialize" && "id" in message) {
+        const params = isRecord(message.params) ? message.params : {};
+        const requested = params.protocolVersion;
+        features = protocolFeatures(negotiateProtocolVersion(requested));
+        log.info("Negotiated MCP protocol version", {
+          requested,
+          negotiated: features.version,
+          client: isRecord(params.clientInfo) ? params.clientInfo.name : undefined,
+        });
+      }
+      onmessage.call(transport, message, extra);
+    };
+  }
+
+  const send = transport.send.bind(transport);
+  transport.send = async (message, options) => send(features ? adaptMessageForProtocol(message, features) : message, options);
+
+  return () => features?.version;
+}
@@ -5,14 +5,118 @@ const SENSITIVE_KEY_PATTERN = new RegExp(`^(${SENSITIVE_KEYS})$`, "i");
 
 const REDACTED = "[REDACTED]";
 
+/** Whether values under `key` are treated as secrets (api_key, password, token, ...). */
+export function isSensitiveKey(key: string): boolean {
+  return SENSITIVE_KEY_PATTERN.test(key);
+}
+
+interface ValuePattern {
+  pattern: RegExp;
+  replacement: string;
+}
+
+/**
+ * Secret-shaped values redacted wherever they appear, regardless of the key
+ * they sit under — API keys echoed in error text, bearer headers in log
+ * lines, credentials embedded in connector URLs.
+ */
+const SECRET_VALUE_PATTERNS: ValuePattern[] = [
+  // Harness PAT/SAT: pat.<account>.<token id>.<secret>
+  { pattern: /\b(?:pat|sat)\.[A-Za-z0-9_-]{4,}\.[A-Za-z0-9_-]{4,}\.[A-Za-z0-9_-]{4,}/g, replacement: REDACTED },
+  { pattern: /\b(Bearer|Basic)\s+[A-Za-z0-9._~+/-]{8,}=*/g, replacement: `$1 ${REDACTED}` },
+  // JWTs
+  { pattern: /\beyJ[A-Za-z0-9_-]{5,}\.eyJ[A-Za-z0-9_-]{5,}\.[A-Za-z0-9_-]{5,}/g, replacement: REDACTED },
+  // AWS access key IDs
+  { pattern: /\b(?:AKIA|ASIA)[0-9A-Z]{16}\b/g, replacement: REDACTED },
+  // GitHub, GitLab, and Slack tokens
+  { pattern: /\b(?:gh[pousr]_[A-Za-z0-9]{30,}|github_pat_[A-Za-z0-9_]{40,}|glpat-[A-Za-z0-9_-]{20,}|xox[abprs]-[A-Za-z0-9-]{10,})/g, replacement: REDACTED },
+  { pattern: /-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----/g, replacement: REDACTED },
+  // user:password@ in URLs — keep the scheme and user for context
+  { pattern: /(\b[a-z][a-z0-9+.-]*:\/\/[^\s:/@]+:)[^\s@/]+@/gi, replacement: `$1${REDACTED}@` },
+];
+
+/** Operator-supplied patterns from HARNESS_REDACT_PATTERNS. */
+let customPatterns: ValuePattern[] = [];
+
+/**
+ * Parse HARNESS_REDACT_PATTERNS — a JSON array of regular expression sources.
+ * Throws on invalid JSON, invalid regexes, or patterns that match the empty string.
+ */
+export function parseRedactPatterns(json: string): RegExp[] {
+  let raw: unknown;
+  try {
+    raw = JSON.parse(json);
+  } catch (err) {
+    throw new Error(`HARNESS_REDACT_PATTERNS is not valid JSON: ${err instanceof Error ? err.message : String(err)}`);
+  }
+  if (!Array.isArray(raw) || raw.some((p) => typeof p !== "string")) {
+    throw new Error("HARNESS_REDACT_PATTERNS must be a JSON array of regular expression strings.");
+  }
+  return (raw as string[]).map((source) => {
+    let pattern: RegExp;
+    try {
+      pattern = new RegExp(source, "g");
+    } catch (err) {
+      throw new Error(`HARNESS_REDACT_PATTERNS entry ${JSON.stringify(source)} is not a valid regular expression: ${err instanceof Error ? err.message : String(err)}`);
+    }
+    if (pattern.test("")) {
+      throw new Error(`HARNESS_REDACT_PATTERNS entry ${JSON.stringify(source)} matches the empty string.`);
+    }
+    pattern.lastIndex = 0;
+    return pattern;
+  });
+}
+
+/**
+ * Configure the redaction module. Call once at startup.
+ */
+export function configureRedaction(opts: { patterns?: RegExp[] }): void {
+  customPatterns = (opts.patterns ?? []).map((pattern) => ({ pattern, replacement: REDACTED }));
+}
+
+/** Replace secret-shaped substrings (built-in and HARNESS_REDACT_PATTERNS) in free text. */
+export function redactSecretValues(text: string): string {
+  let out = text;
+  for (const { pattern, replacement } of [...SECRET_VALUE_PATTERNS, ...customPatterns]) {
+    pattern.lastIndex = 0;
+    out = out.replace(pattern, replacement);
+  }
+  return out;
+}
+
+/** Recursively redact secret-shaped string values, leaving keys and structure intact. */
+function redactValuesDeep(value: unknown, depth: number): unknown {
+  if (typeof value === "string") return redactSecretValues(value);
+  if (value === null || typeof value !== "object" || depth > 20) return value;
+  if (Array.isArray(value)) return value.map((item) => redactValuesDeep(item, depth + 1));
+  const result: Record<string, unknown> = {};
+  for (const [key, v] of Object.entries(value as Record<string, unknown>)) {
+    result[key] = redactValuesDeep(v, depth + 1);
+  }
+  return result;
+}
+
+/**
+ * Redact secret-shaped values from a tool result before it reaches the client.
+ * Value-based only — keys are never redacted here, so ordinary fields such as
+ * a webhook's name survive — and returns the input unchanged (same reference)
+ * when nothing matches.
+ */
+export function redactToolOutput<T>(data: T, serialized: string): { data: T; text: string } {
+  const text = redactSecretValues(serialized);
+  if (text === serialized) return { data, text };
+  return { data: redactValuesDeep(data, 0) as T, text };
+}
+
 /**
- * Recursively redact values whose keys match sensitive patterns.
+ * Recursively redact values whose keys match sensitive patterns, and
+ * secret-shaped string values under any key.
  * Returns a new object — the original is never mutated.
  */
 export function redactSensitiveFields(obj: unknown, depth = 0): unknown {
   if (depth > 10) return REDACTED;
 
-  if (typeof obj === "string") return obj;
+  if (typeof obj === "string") return redactSecretValues(obj);
   if (obj === null || obj === undefined) return obj;
   if (typeof obj !== "object") return obj;
 
@@ -26,6 +130,8 @@ export function redactSensitiveFields(obj: unknown, depth = 0): unknown {
       result[key] = REDACTED;
     } else if (typeof value === "object" && value !== null) {
       result[key] = redactSensitiveFields(value, depth + 1);
+    } else if (typeof value === "string") {
+      result[key] = redactSecretValues(value);
     } else {
       result[key] = value;
     }
@@ -59,9 +165,9 @@ export function redactJsonString(jsonStr: string, maxLen = 1000): string {
     const out = JSON.stringify(redacted);
     return out.length > maxLen ? out.slice(0, maxLen) + "..." : out;
   } catch {
-    const scrubbed = jsonStr
+    const scrubbed = redactSecretValues(jsonStr
       .replace(YAML_BLOCK_SECRET_PATTERN, `$1  ${REDACTED}\n`)
-      .replace(INLINE_SECRET_PATTERN, `$1: ${REDACTED}`);
+      .replace(INLINE_SECRET_PATTERN, `$1: ${REDACTED}`));
     return scrubbed.length > maxLen ? scrubbed.slice(0, maxLen) + "..." : scrubbed;
   }
 }
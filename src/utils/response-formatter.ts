@@ -5,7 +5,15 @@
  * Errors keep minimal formatting for readability in tool-call error surfaces.
  */
 
-export type ContentItem = { type: "text"; text: string };
+import { redactToolOutput } from "./redact.js";
+import { sanitizeDeep, sanitizeText } from "./sanitize-text.js";
+import { localizeTimes } from "./time-format.js";
+
+export type ContentItem =
+  | { type: "text"; text: string }
+  | { type: "resource_link"; uri: string; name: string; mimeType?: string; description?: string };
+
+export type ResourceLinkItem = Extract<ContentItem, { type: "resource_link" }>;
 
 export interface ToolResult {
   /** Required: MCP SDK's CallToolResult extends Result which has an index signature. */
@@ -57,9 +65,14 @@ export function normalizeHarnessListPayload(
   return result;
 }
 
-export function jsonResult(data: unknown): ToolResult {
+export function jsonResult(raw: unknown): ToolResult {
+  // Render epoch-ms timestamps and durations readably when HARNESS_LOCALIZE_TIMES is on,
+  // and drop ANSI codes and control characters picked up from logs and shell output
+  const payload = sanitizeDeep(localizeTimes(raw));
+  // Scrub secret-shaped values (tokens, keys, URL credentials) before they reach the client
+  const { data, text } = redactToolOutput(payload, JSON.stringify(payload));
   return {
-    content: [{ type: "text", text: JSON.stringify(data) }],
+    content: [{ type: "text", text }],
     // MCP structuredContent must be an object; arrays and primitives are intentionally excluded.
     ...(data !== null && typeof data === "object" && !Array.isArray(data)
       ? { structuredContent: data as Record<string, unknown> }
@@ -67,9 +80,16 @@ export function jsonResult(data: unknown): ToolResult {
   };
 }
 
-export function errorResult(message: string): ToolResult {
+/** jsonResult plus resource_link content items for files the tool produced. */
+export function jsonResultWithLinks(data: unknown, links: ResourceLinkItem[]): ToolResult {
+  const result = jsonResult(data);
+  result.content.push(...links);
+  return result;
+}
+
+export function errorResult(message: string, correlationId?: string): ToolResult {
   return {
-    content: [{ type: "text", text: JSON.stringify({ error: message }) }],
+    content: [{ type: "text", text: JSON.stringify({ error: sanitizeText(message), ...(correlationId ? { correlation_id: correlationId } : {}) }) }],
     isError: true,
   };
 }
@@ -0,0 +1,134 @@
+/**
+ * Chunked delivery of very large tool results (HARNESS_RESULT_CHUNK_CHARS).
+ *
+ * Some payloads — SBOM components, execution graphs of large matrix
+ * pipelines, full execution logs — exceed what MCP clients and transports
+ * accept in one message. When a result's JSON text is longer than the chunk
+ * size, the tool returns only the first chunk and a cursor; the remaining
+ * chunks are held in a bounded in-memory buffer and fetched with
+ * `harness_get(continue_result=<cursor>)`. Concatenating every chunk's `chunk`
+ * text in order yields the original JSON.
+ */
+
+import { randomBytes } from "node:crypto";
+import { jsonResult, type ToolResult } from "./response-formatter.js";
+
+/** Buffered results expire after 10 minutes. */
+export const RESULT_BUFFER_TTL_MS = 10 * 60 * 1000;
+
+/** Total characters held across all buffered results; the oldest are evicted first. */
+const MAX_BUFFERED_CHARS = 50_000_000;
+
+interface BufferedResult {
+  text: string;
+  owner: string;
+  chunkChars: number;
+  expiresAt: number;
+}
+
+let _chunkChars = 0;
+const buffers = new Map<string, BufferedResult>();
+let bufferedChars = 0;
+
+/** Configure the chunk size in characters (0 disables chunking). Call once at startup. */
+export function configureResultChunking(opts: { chunkChars?: number }): void {
+  const chars = opts.chunkChars ?? 0;
+  _chunkChars = Number.isFinite(chars) ? Math.max(0, Math.floor(chars)) : 0;
+}
+
+/** Drop all buffered results (useful for testing). */
+export function resetResultChunks(): void {
+  buffers.clear();
+  bufferedChars = 0;
+}
+
+function evict(id: string): void {
+  const entry = buffers.get(id);
+  if (!entry) return;
+  bufferedChars -= entry.text.length;
+  buffers.delete(id);
+}
+
+function store(text: string, owner: string, chunkChars: number, now: number): string {
+  for (const [id, entry] of buffers) {
+    if (entry.expiresAt <= now) evict(id);
+  }
+  // Map iteration order is insertion order, so the first entries are the oldest
+  for (const id of buffers.keys()) {
+    if (bufferedChars + text.length <= MAX_BUFFERED_CHARS) break;
+    evict(id);
+  }
+  const id = randomBytes(12).toString("hex");
+  buffers.set(id, { text, owner, chunkChars, expiresAt: now + RESULT_BUFFER_TTL_MS });
+  bufferedChars += text.length;
+  return id;
+}
+
+function chunkPayload(id: string, entry: BufferedResult, index: number): Record<string, unknown> {
+  const totalChunks = Math.ceil(entry.text.length / entry.chunkChars);
+  const hasMore = index + 1 < totalChunks;
+  return {
+    chunked: true,
+    chunk_index: index,
+    total_chunks: totalChunks,
+    total_chars: entry.text.length,
+    chunk: entry.text.slice(index * entry.chunkChars, (index + 1) * entry.chunkChars),
+    ...(hasMore
+      ? {
+          next_cursor: `${id}:${index + 1}`,
+          expires_at: new Date(entry.expiresAt).toISOString(),
+          next_step: "Call harness_get with continue_result=<next_cursor> for the next chunk. Concatenate the chunk strings in order to rebuild the JSON result.",
+        }
+      : {}),
+  };
+}
+
+/**
+ * jsonResult for tools whose output can be very large. Results that fit in one
+ * chunk (or when chunking is off) are returned unchanged; larger ones return
+ * the first chunk and buffer the rest for `owner` (the caller's account).
+ */
+export function chunkedJsonResult(raw: unknown, owner: string, now = Date.now()): ToolResult {
+  const result = jsonResult(raw);
+  const first = result.content[0];
+  if (_chunkChars <= 0 || first?.type !== "text" || first.text.length <= _chunkChars) return result;
+
+  // Chunk the redacted text so buffered chunks never hold unredacted values
+  const id = store(first.text, owner, _chunkChars, now);
+  return chunkResult(id, 0, now);
+}
+
+function chunkResult(id: string, index: number, now: number): ToolResult {
+  const entry = buffers.get(id)!;
+  // Each read extends the buffer's lifetime; the last chunk releases it
+  entry.expiresAt = now + RESULT_BUFFER_TTL_MS;
+  const payload = chunkPayload(id, entry, index);
+  if (payload.next_cursor === undefined) evict(id);
+  return {
+    content: [{ type: "text", text: JSON.stringify(payload) }],
+    structuredContent: payload,
+  };
+}
+
+/**
+ * Fetch the chunk a cursor points at. Returns an error message when the cursor
+ * is malformed, expired, already fully read, or belongs to another account.
+ */
+export function continueResult(cursor: string, owner: string, now = Date.now()): ToolResult | string {
+  const match = /^([0-9a-f]+):(\d+)$/.exec(cursor.trim());
+  if (!match) return `Invalid continue_result cursor "${cursor}". Pass the next_cursor value from a chunked result.`;
+  const [, id, indexText] = match;
+  const entry = buffers.get(id!);
+  if (!entry || entry.owner !== owner) {
+    return "continue_result cursor is unknown or has expired. Re-run the original call to get a fresh cursor.";
+  }
+  if (entry.expiresAt <= now) {
+    evict(id!);
+    return "continue_result cursor has expired. Re-run the original call to get a fresh cursor.";
+  }
+  const index = Number(indexText);
+  if (index >= Math.ceil(entry.text.length / entry.chunkChars)) {
+    return `continue_result chunk ${index} is out of range.`;
+  }
+  return chunkResult(id!, index, now);
+}
@@ -12,6 +12,8 @@ import YAML from "yaml";
 import type { HarnessClient } from "../client/harness-client.js";
 import { createLogger } from "./logger.js";
 import { isRecord, asRecord, asString } from "./type-guards.js";
+import { recordCacheLookup } from "./server-metrics.js";
+import { permissionPrincipalKey } from "./permission-cache.js";
 
 const log = createLogger("runtime-inputs");
 
@@ -27,8 +29,13 @@ interface CachedTemplate {
 
 const templateCache = new Map<string, CachedTemplate>();
 
-function templateCacheKey(opts: ResolveOptions): string {
-  return `${opts.pipelineId}|${opts.orgId ?? ""}|${opts.projectId ?? ""}|${opts.branch ?? ""}`;
+/**
+ * Keyed by the caller (account, credential, and principal) — in multi-user
+ * mode a template fetched with one user's access must not be served to
+ * another user, even on the same account.
+ */
+function templateCacheKey(client: HarnessClient, opts: ResolveOptions): string {
+  return `${permissionPrincipalKey(client.account, client.credentialId)}|${opts.pipelineId}|${opts.orgId ?? ""}|${opts.projectId ?? ""}|${opts.branch ?? ""}|${opts.stageIdentifiers?.join(",") ?? ""}`;
 }
 
 /** Evict expired entries. Called on cache writes to prevent unbounded growth. */
@@ -49,6 +56,8 @@ export interface ResolveOptions {
   orgId?: string;
   projectId?: string;
   branch?: string;
+  /** Selective stage execution: fetch the template for these stages only. */
+  stageIdentifiers?: string[];
 }
 
 export interface ResolutionResult {
@@ -75,12 +84,14 @@ export async function fetchRuntimeInputTemplate(
   client: HarnessClient,
   options: ResolveOptions,
 ): Promise<string | null> {
-  const cacheKey = templateCacheKey(options);
+  const cacheKey = templateCacheKey(client, options);
   const cached = templateCache.get(cacheKey);
   if (cached && Date.now() < cached.expiresAt) {
     log.debug("Runtime input template cache hit", { pipelineId: options.pipelineId });
+    recordCacheLookup("runtime_input_template", true);
     return cached.yaml;
   }
+  recordCacheLookup("runtime_input_template", false);
 
   const params: Record<string, string> = {
     pipelineIdentifier: options.pipelineId,
@@ -93,7 +104,7 @@ export async function fetchRuntimeInputTemplate(
     method: "POST",
     path: "/pipeline/api/inputSets/template",
     params,
-    body: {},
+    body: options.stageIdentifiers?.length ? { stageIdentifiers: options.stageIdentifiers } : {},
   });
 
   const data = asRecord(asRecord(raw)?.data);
@@ -110,6 +121,15 @@ export async function fetchRuntimeInputTemplate(
   return result;
 }
 
+/**
+ * Normalize `stage_identifiers` (array or comma-separated string) for
+ * selective stage execution. Returns an empty array when none are given.
+ */
+export function parseStageIdentifiers(value: unknown): string[] {
+  const raw = Array.isArray(value) ? value : typeof value === "string" ? value.split(",") : [];
+  return raw.map((s) => String(s).trim()).filter(Boolean);
+}
+
 /**
  * Check if a value looks like a flat key-value map of runtime inputs
  * (as opposed to already being a full pipeline YAML structure or string).
@@ -0,0 +1,110 @@
+/**
+ * JSON-schema view of a pipeline's runtime input template.
+ *
+ * The template endpoint returns YAML with `<+input>` placeholders. Agents
+ * building `harness_execute(action="run", inputs={...})` need the flat keys
+ * the runtime input resolver matches on, so each placeholder is described by
+ * that key — the variable name for `variables` entries, the leaf field name
+ * otherwise — along with the modifiers Harness allows on the placeholder
+ * (`.default()`, `.allowedValues()`, `.regex()`, `.executionInput()`).
+ */
+import YAML from "yaml";
+
+const INPUT_PLACEHOLDER = /^<\+input>/;
+
+export interface RuntimeInputField {
+  /** Key to pass in `inputs` — matched by the runtime input resolver. */
+  key: string;
+  /** Dotted path of the placeholder in the template YAML. */
+  path: string;
+  type: "string" | "number";
+  required: boolean;
+  default?: string;
+  allowed_values?: string[];
+  regex?: string;
+  /** Value is requested when the stage runs, not when the execution starts. */
+  execution_input?: boolean;
+}
+
+/** Argument of a `.name(...)` modifier, or undefined when absent. */
+function modifierArg(value: string, name: string): string | undefined {
+  const start = value.indexOf(`.${name}(`);
+  if (start < 0) return undefined;
+  // Arguments can contain parentheses (regexes), so close on the last `)` before the next modifier
+  const rest = value.slice(start + name.length + 2);
+  const next = rest.search(/\)\.(default|allowedValues|regex|executionInput|selectOneFrom)\(/);
+  const end = next >= 0 ? next : rest.lastIndexOf(")");
+  return end >= 0 ? rest.slice(0, end) : undefined;
+}
+
+function fieldFor(placeholder: string, path: string[], siblings: Record<string, unknown> | undefined): RuntimeInputField {
+  const leaf = path[path.length - 1] ?? "";
+  const variableName = (leaf === "value" || leaf === "default") && typeof siblings?.name === "string"
+    ? siblings.name
+    : undefined;
+  const defaultValue = modifierArg(placeholder, "default");
+  const allowed = modifierArg(placeholder, "allowedValues") ?? modifierArg(placeholder, "selectOneFrom");
+  const regex = modifierArg(placeholder, "regex");
+  return {
+    key: variableName ?? leaf,
+    path: path.join("."),
+    type: variableName && siblings?.type === "Number" ? "number" : "string",
+    required: defaultValue === undefined,
+    ...(defaultValue !== undefined ? { default: defaultValue } : {}),
+    ...(allowed !== undefined ? { allowed_values: allowed.split(",").map((v) => v.trim()).filter(Boolean) } : {}),
+    ...(regex !== undefined ? { regex } : {}),
+    ...(placeholder.includes(".executionInput()") ? { execution_input: true } : {}),
+  };
+}
+
+/** Every `<+input>` placeholder in the template, in document order. */
+export function describeRuntimeInputs(templateYaml: string | null | undefined): RuntimeInputField[] {
+  if (!templateYaml || templateYaml.trim() === "") return [];
+  const fields: RuntimeInputField[] = [];
+  const walk = (node: unknown, path: string[], siblings?: Record<string, unknown>): void => {
+    if (Array.isArray(node)) {
+      node.forEach((item, i) => walk(item, [...path, String(i)]));
+    } else if (node !== null && typeof node === "object") {
+      const map = node as Record<string, unknown>;
+      for (const [key, value] of Object.entries(map)) walk(value, [...path, key], map);
+    } else if (typeof node === "string" && INPUT_PLACEHOLDER.test(node)) {
+      fields.push(fieldFor(node, path, siblings));
+    }
+  };
+  walk(YAML.parse(templateYaml), []);
+  return fields;
+}
+
+/**
+ * JSON Schema (draft 2020-12) for the flat `inputs` object of a pipeline run.
+ * Keys that appear at several paths share one property; the resolver fills
+ * every placeholder with that key, and `x-harness-paths` lists them.
+ */
+export function runtimeInputJsonSchema(fields: RuntimeInputField[]): Record<string, unknown> {
+  const properties: Record<string, Record<string, unknown>> = {};
+  const required: string[] = [];
+  for (const field of fields) {
+    const existing = properties[field.key];
+    if (existing) {
+      (existing["x-harness-paths"] as string[]).push(field.path);
+      if (field.required && !required.includes(field.key)) required.push(field.key);
+      continue;
+    }
+    properties[field.key] = {
+      type: field.type,
+      ...(field.default !== undefined ? { default: field.type === "number" && !Number.isNaN(Number(field.default)) ? Number(field.default) : field.default } : {}),
+      ...(field.allowed_values ? { enum: field.allowed_values } : {}),
+      ...(field.regex !== undefined ? { pattern: field.regex } : {}),
+      ...(field.execution_input ? { "x-harness-execution-input": true } : {}),
+      "x-harness-paths": [field.path],
+    };
+    if (field.required) required.push(field.key);
+  }
+  return {
+    $schema: "https://json-schema.org/draft/2020-12/schema",
+    type: "object",
+    properties,
+    required,
+    additionalProperties: true,
+  };
+}
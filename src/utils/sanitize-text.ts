@@ -0,0 +1,66 @@
+/**
+ * Terminal noise removal for tool output.
+ *
+ * Step logs, shell outputs, and some API error messages carry ANSI colour
+ * codes, carriage-return progress redraws, other control characters, and
+ * replacement characters or lone surrogates left by invalid UTF-8. JSON
+ * encodes them as `\u001b[31m`-style escapes that confuse some MCP clients
+ * and waste tokens. `jsonResult` and `errorResult` run every string through
+ * `sanitizeText` before serializing.
+ */
+
+// CSI (colours, cursor movement), OSC (titles, hyperlinks), and two-byte ESC sequences
+export const ANSI_RE = /\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]/g;
+// C0 controls except tab and newline, plus DEL
+const CONTROL_RE = /[\x00-\x08\x0b-\x1f\x7f]/g;
+// U+FFFD from undecodable bytes, and UTF-16 surrogates without their pair
+const INVALID_RE = /\ufffd|[\ud800-\udbff](?![\udc00-\udfff])|(?<![\ud800-\udbff])[\udc00-\udfff]/g;
+const NEEDS_SANITIZING = /[\x00-\x08\x0b-\x1f\x7f\ufffd\ud800-\udfff]/;
+
+/** Keep only what a terminal would show for a line redrawn with `\r`. */
+function lastRedraw(line: string): string {
+  if (!line.includes("\r")) return line;
+  return line.split("\r").filter(Boolean).at(-1) ?? "";
+}
+
+export function sanitizeText(text: string): string {
+  if (!NEEDS_SANITIZING.test(text)) return text;
+  return text
+    .replace(/\r\n/g, "\n")
+    .split("\n")
+    .map(lastRedraw)
+    .join("\n")
+    .replace(ANSI_RE, "")
+    .replace(CONTROL_RE, "")
+    .replace(INVALID_RE, "");
+}
+
+/**
+ * Sanitize every string in a JSON-shaped value, keys included. Unchanged
+ * subtrees are returned as-is, so clean results are not copied.
+ */
+export function sanitizeDeep(value: unknown, depth = 0): unknown {
+  if (typeof value === "string") return sanitizeText(value);
+  if (value === null || typeof value !== "object" || depth > 64) return value;
+  if (Array.isArray(value)) {
+    let changed = false;
+    const next = value.map((item) => {
+      const clean = sanitizeDeep(item, depth + 1);
+      if (clean !== item) changed = true;
+      return clean;
+    });
+    return changed ? next : value;
+  }
+  // Only plain objects — class instances (Date, Buffer) serialize themselves
+  const proto = Object.getPrototypeOf(value) as unknown;
+  if (proto !== Object.prototype && proto !== null) return value;
+  let changed = false;
+  const next: Record<string, unknown> = {};
+  for (const [key, item] of Object.entries(value as Record<string, unknown>)) {
+    const cleanKey = sanitizeText(key);
+    const clean = sanitizeDeep(item, depth + 1);
+    if (clean !== item || cleanKey !== key) changed = true;
+    next[cleanKey] = clean;
+  }
+  return changed ? next : value;
+}
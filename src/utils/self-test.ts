@@ -0,0 +1,188 @@
+/**
+ * `self-test`: start the server in-process, drive it over an in-memory MCP
+ * transport the way a client such as the MCP Inspector would (initialize,
+ * ping, tools/resources/prompts listing, sample tool calls), then list one
+ * item per enabled toolset against the configured account. The report shows
+ * what to fix before wiring the server into an IDE or desktop client.
+ */
+
+import { Client } from "@modelcontextprotocol/sdk/client/index.js";
+import { InMemoryTransport } from "@modelcontextprotocol/sdk/inMemory.js";
+import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import { SUPPORTED_PROTOCOL_VERSIONS, type CallToolResult, type JSONRPCMessage } from "@modelcontextprotocol/sdk/types.js";
+import type { HarnessClient } from "../client/harness-client.js";
+import type { Registry } from "../registry/index.js";
+import { getVersion } from "./cli.js";
+import { HarnessApiError } from "./errors.js";
+import { isRecord } from "./type-guards.js";
+
+export type CheckStatus = "ok" | "warn" | "fail";
+
+export interface SelfTestCheck {
+  name: string;
+  status: CheckStatus;
+  detail: string;
+}
+
+/** Tool names clients accept (MCP spec: 1-64 characters from this set). */
+const TOOL_NAME_PATTERN = /^[A-Za-z0-9_.-]{1,64}$/;
+
+function errorMessage(err: unknown): string {
+  return err instanceof Error ? err.message : String(err);
+}
+
+/**
+ * Protocol conformance checks. `connect` attaches the server to the transport
+ * with the same layers the real transports use, so the checks see exactly
+ * what a client would receive.
+ */
+export async function runProtocolChecks(connect: (transport: Transport) => Promise<void>): Promise<SelfTestCheck[]> {
+  const checks: SelfTestCheck[] = [];
+  const run = async (name: string, fn: () => Promise<string | Omit<SelfTestCheck, "name">>): Promise<void> => {
+    try {
+      const result = await fn();
+      checks.push(typeof result === "string" ? { name, status: "ok", detail: result } : { name, ...result });
+    } catch (err) {
+      checks.push({ name, status: "fail", detail: errorMessage(err) });
+    }
+  };
+
+  const [clientTransport, serverTransport] = InMemoryTransport.createLinkedPair();
+  // Capture the initialize result as it goes over the wire — the SDK client does not expose the negotiated revision
+  let initializeResult: Record<string, unknown> | undefined;
+  const send = serverTransport.send.bind(serverTransport);
+  serverTransport.send = (message: JSONRPCMessage, options) => {
+    if (!initializeResult && "result" in message && isRecord(message.result) && "protocolVersion" in message.result) {
+      initializeResult = message.result;
+    }
+    return send(message, options);
+  };
+  await connect(serverTransport);
+
+  const client = new Client({ name: "harness-mcp-self-test", version: getVersion() });
+  try {
+    await client.connect(clientTransport);
+  } catch (err) {
+    return [{ name: "initialize", status: "fail", detail: errorMessage(err) }];
+  }
+
+  try {
+    const capabilities = client.getServerCapabilities() ?? {};
+    await run("initialize", async () => {
+      const version = String(initializeResult?.protocolVersion ?? "");
+      const server = client.getServerVersion();
+      if (!SUPPORTED_PROTOCOL_VERSIONS.includes(version)) {
+        return { status: "fail", detail: `unsupported protocol version "${version}"` };
+      }
+      if (!capabilities.tools) return { status: "fail", detail: "server does not advertise the tools capability" };
+      return `protocol ${version}, ${server?.name ?? "?"} ${server?.version ?? "?"}, capabilities: ${Object.keys(capabilities).sort().join(", ")}`;
+    });
+
+    await run("ping", async () => {
+      await client.ping();
+      return "answered";
+    });
+
+    let toolNames: string[] = [];
+    await run("tools/list", async () => {
+      const { tools } = await client.listTools();
+      toolNames = tools.map((t) => t.name);
+      const problems: string[] = [];
+      if (tools.length === 0) problems.push("no tools");
+      if (new Set(toolNames).size !== toolNames.length) problems.push("duplicate tool names");
+      for (const tool of tools) {
+        if (!TOOL_NAME_PATTERN.test(tool.name)) problems.push(`${tool.name}: invalid name`);
+        if (!tool.description) problems.push(`${tool.name}: no description`);
+        if (tool.inputSchema?.type !== "object") problems.push(`${tool.name}: inputSchema is not an object schema`);
+        if (tool.outputSchema && tool.outputSchema.type !== "object") problems.push(`${tool.name}: outputSchema is not an object schema`);
+      }
+      return problems.length > 0 ? { status: "fail", detail: problems.join("; ") } : `${tools.length} tools, schemas valid`;
+    });
+
+    if (capabilities.resources) {
+      await run("resources/list", async () => {
+        const [{ resources }, { resourceTemplates }] = await Promise.all([client.listResources(), client.listResourceTemplates()]);
+        return `${resources.length} resources, ${resourceTemplates.length} templates`;
+      });
+    }
+    if (capabilities.prompts) {
+      await run("prompts/list", async () => `${(await client.listPrompts()).prompts.length} prompts`);
+    }
+
+    if (toolNames.includes("harness_describe")) {
+      await run("tools/call harness_describe", async () => {
+        const result = await client.callTool({ name: "harness_describe", arguments: {} }) as CallToolResult;
+        if (result.isError) return { status: "fail", detail: "returned an error result" };
+        if (result.content.length === 0) return { status: "fail", detail: "returned no content" };
+        return result.structuredContent !== undefined ? "text and structured content" : "text content";
+      });
+    }
+
+    await run("tools/call unknown tool", async () => {
+      try {
+        const result = await client.callTool({ name: "self_test_no_such_tool", arguments: {} }) as CallToolResult;
+        return result.isError ? "rejected with an error result" : { status: "fail", detail: "an unknown tool call succeeded" };
+      } catch {
+        return "rejected with a JSON-RPC error";
+      }
+    });
+  } finally {
+    await client.close();
+  }
+  return checks;
+}
+
+/**
+ * List one item of each enabled toolset's probe resource. 404 (module not
+ * deployed) and 403 (no permission) are warnings; anything else that fails
+ * is a failure.
+ */
+export async function runConnectivityChecks(registry: Registry, client: HarnessClient, timeoutMs: number): Promise<SelfTestCheck[]> {
+  return Promise.all(registry.getProbeTargets().map(async ({ toolset, resourceType }): Promise<SelfTestCheck> => {
+    const started = Date.now();
+    try {
+      await registry.dispatch(client, resourceType, "list", { page: 0, size: 1, limit: 1 }, AbortSignal.timeout(timeoutMs));
+      return { name: toolset, status: "ok", detail: `${resourceType} list, ${Date.now() - started} ms` };
+    } catch (err) {
+      if (err instanceof HarnessApiError) {
+        if (err.statusCode === 404) return { name: toolset, status: "warn", detail: `${resourceType} API not found (404) — module not deployed on this install?` };
+        if (err.statusCode === 403) return { name: toolset, status: "warn", detail: `reachable, but the API key lacks permission to list ${resourceType} (403)` };
+        if (err.statusCode === 401) return { name: toolset, status: "fail", detail: "authentication failed (401) — check HARNESS_API_KEY" };
+        return { name: toolset, status: "fail", detail: `${resourceType} list failed (${err.statusCode}): ${err.message}` };
+      }
+      if (err instanceof Error && (err.name === "TimeoutError" || err.name === "AbortError")) {
+        return { name: toolset, status: "fail", detail: `${resourceType} list timed out after ${timeoutMs} ms` };
+      }
+      // Input problems (e.g. a project-scoped probe without HARNESS_ORG/HARNESS_PROJECT) say nothing about connectivity
+      return { name: toolset, status: "warn", detail: errorMessage(err) };
+    }
+  }));
+}
+
+export interface SelfTestReport {
+  protocol: SelfTestCheck[];
+  /** Undefined when connectivity was not checked; `skipReason` says why. */
+  connectivity?: SelfTestCheck[];
+  target: string;
+  skipReason?: string;
+}
+
+/** True when no check failed (warnings pass). */
+export function selfTestPassed(report: SelfTestReport): boolean {
+  return ![...report.protocol, ...(report.connectivity ?? [])].some((c) => c.status === "fail");
+}
+
+export function renderSelfTestReport(report: SelfTestReport): string {
+  const all = [...report.protocol, ...(report.connectivity ?? [])];
+  const width = Math.max(...all.map((c) => c.name.length), 10);
+  const line = (c: SelfTestCheck) => `  ${`[${c.status}]`.padEnd(7)}${c.name.padEnd(width)}  ${c.detail}`;
+  const lines = ["Protocol conformance:", ...report.protocol.map(line), "", `Connectivity (${report.target}):`];
+  if (report.connectivity) {
+    lines.push(...(report.connectivity.length > 0 ? report.connectivity.map(line) : ["  (no enabled toolset declares a probe resource)"]));
+  } else {
+    lines.push(`  skipped: ${report.skipReason ?? "not requested"}`);
+  }
+  const count = (status: CheckStatus) => all.filter((c) => c.status === status).length;
+  lines.push("", `${selfTestPassed(report) ? "PASS" : "FAIL"}: ${count("ok")} ok, ${count("warn")} warnings, ${count("fail")} failures`);
+  return lines.join("\n");
+}
@@ -0,0 +1,125 @@
+/**
+ * Process-wide self-metrics for the MCP server.
+ *
+ * Counters are recorded at the few choke points that matter for operators —
+ * every Harness API call (outcome and status code) and every cache lookup —
+ * and reported by `harness_describe(server_status=true)` so the server can be
+ * diagnosed from a client without access to its logs. Counts are process-wide
+ * (shared by all sessions) and never include request data.
+ */
+
+import type { Config } from "../config.js";
+import type { Registry } from "../registry/index.js";
+
+const startedAt = Date.now();
+
+let apiCalls = 0;
+let apiErrors = 0;
+let apiErrorsByStatus = new Map<number, number>();
+let cacheStats = new Map<string, { hits: number; misses: number }>();
+let transport: string | undefined;
+let connectionEvents = new Map<ConnectionEvent, number>();
+
+/** Outbound connection pool events (HARNESS_HTTP2). */
+export type ConnectionEvent = "http2_session_opened" | "http2_session_closed" | "http2_request" | "http2_reused" | "http1_request";
+
+/** Record the outcome of one Harness API call. `errorStatus` is the HTTP status (0 for network errors) on failure. */
+export function recordApiCall(errorStatus?: number): void {
+  apiCalls++;
+  if (errorStatus === undefined) return;
+  apiErrors++;
+  apiErrorsByStatus.set(errorStatus, (apiErrorsByStatus.get(errorStatus) ?? 0) + 1);
+}
+
+/** Record a lookup against a named cache. */
+export function recordCacheLookup(cache: string, hit: boolean): void {
+  const stats = cacheStats.get(cache) ?? { hits: 0, misses: 0 };
+  if (hit) stats.hits++;
+  else stats.misses++;
+  cacheStats.set(cache, stats);
+}
+
+/** Record an outbound connection pool event. */
+export function recordConnectionEvent(event: ConnectionEvent): void {
+  connectionEvents.set(event, (connectionEvents.get(event) ?? 0) + 1);
+}
+
+export function recordServerTransport(name: string): void {
+  transport = name;
+}
+
+/** Reset all counters (useful for testing). */
+export function resetServerMetrics(): void {
+  apiCalls = 0;
+  apiErrors = 0;
+  apiErrorsByStatus = new Map();
+  cacheStats = new Map();
+  connectionEvents = new Map();
+}
+
+function credentialKind(apiKey: string | undefined): string {
+  if (!apiKey) return "none";
+  if (apiKey.startsWith("pat.")) return "personal_access_token";
+  if (apiKey.startsWith("sat.")) return "service_account_token";
+  return "other";
+}
+
+function connectionStats(): Record<string, number> {
+  const count = (event: ConnectionEvent) => connectionEvents.get(event) ?? 0;
+  return {
+    http2_sessions_opened: count("http2_session_opened"),
+    http2_sessions_open: count("http2_session_opened") - count("http2_session_closed"),
+    http2_requests: count("http2_request"),
+    http2_session_reuses: count("http2_reused"),
+    http1_requests: count("http1_request"),
+  };
+}
+
+const toMb = (bytes: number): number => Math.round((bytes / 1024 / 1024) * 10) / 10;
+
+/** Snapshot of server status. Never includes credential values. */
+export function serverStatus(registry: Registry, config?: Config): Record<string, unknown> {
+  const uptimeSeconds = Math.round((Date.now() - startedAt) / 1000);
+  const memory = process.memoryUsage();
+  const toolsets = registry.getAllToolsets();
+
+  return {
+    uptime_seconds: uptimeSeconds,
+    started_at: new Date(startedAt).toISOString(),
+    node_version: process.version,
+    ...(transport ? { transport } : {}),
+    ...(config
+      ? {
+          auth: {
+            mode: config.HARNESS_MCP_MODE,
+            credential: config.HARNESS_MCP_MODE === "multi-user" ? "per_session" : credentialKind(config.HARNESS_API_KEY),
+            http_bearer_token: Boolean(config.HARNESS_MCP_AUTH_TOKEN),
+          },
+          read_only: config.HARNESS_READ_ONLY,
+        }
+      : {}),
+    toolsets: {
+      enabled: toolsets.map((t) => t.name),
+      resource_types: registry.getAllResourceTypes().length,
+    },
+    api: {
+      calls: apiCalls,
+      errors: apiErrors,
+      error_rate: apiCalls > 0 ? Math.round((apiErrors / apiCalls) * 1000) / 1000 : 0,
+      errors_by_status: Object.fromEntries([...apiErrorsByStatus.entries()].sort(([a], [b]) => a - b)),
+    },
+    caches: Object.fromEntries(
+      [...cacheStats.entries()].map(([name, { hits, misses }]) => [
+        name,
+        { hits, misses, hit_rate: hits + misses > 0 ? Math.round((hits / (hits + misses)) * 1000) / 1000 : 0 },
+      ]),
+    ),
+    ...(connectionEvents.size > 0 ? { connections: connectionStats() } : {}),
+    memory_mb: {
+      rss: toMb(memory.rss),
+      heap_used: toMb(memory.heapUsed),
+      heap_total: toMb(memory.heapTotal),
+      external: toMb(memory.external),
+    },
+  };
+}
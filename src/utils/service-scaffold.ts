@@ -0,0 +1,271 @@
+/**
+ * `harness_execute(resource_type="service", action="scaffold_pipeline")` —
+ * onboard a new service in one call.
+ *
+ * Given a Git repo and a runtime, this creates:
+ *   1. the service, with its manifests read from the repo;
+ *   2. an ENV_SERVICE_OVERRIDE binding the service to an existing environment;
+ *   3. a starter deploy pipeline for the pair — a built-in rolling deployment
+ *      stage, or a stage template when `template_ref` is given.
+ *
+ * Every step goes through registry dispatch, so each entity is validated and
+ * audited like a separate harness_create. Steps run in order and stop at the
+ * first failure; the error names what was already created.
+ */
+
+import YAML from "yaml";
+import type { HarnessClient } from "../client/harness-client.js";
+import type { AuditContext } from "../audit/types.js";
+import type { Registry } from "../registry/index.js";
+import { asRecord, asString } from "./type-guards.js";
+
+interface RuntimeSpec {
+  deploymentType: string;
+  manifestType: string;
+  defaultManifestPath: string;
+  deployStep: { type: string; name: string };
+  rollbackStep: { type: string; name: string };
+}
+
+/** Supported runtimes and the Harness service/step types each maps to. */
+export const SCAFFOLD_RUNTIMES: Record<string, RuntimeSpec> = {
+  kubernetes: {
+    deploymentType: "Kubernetes",
+    manifestType: "K8sManifest",
+    defaultManifestPath: "k8s/",
+    deployStep: { type: "K8sRollingDeploy", name: "Rolling Deployment" },
+    rollbackStep: { type: "K8sRollingRollback", name: "Rolling Rollback" },
+  },
+  helm: {
+    deploymentType: "NativeHelm",
+    manifestType: "HelmChart",
+    defaultManifestPath: "chart/",
+    deployStep: { type: "HelmDeploy", name: "Helm Deployment" },
+    rollbackStep: { type: "HelmRollback", name: "Helm Rollback" },
+  },
+};
+
+export const SCAFFOLD_GIT_PROVIDERS = ["Github", "GitLab", "Bitbucket", "AzureRepo", "Git", "Harness"] as const;
+
+export interface ScaffoldOptions {
+  serviceId: string;
+  serviceName: string;
+  runtime: string;
+  repoConnectorRef?: string;
+  repoName?: string;
+  branch: string;
+  manifestPath: string;
+  gitProvider: string;
+  environmentId: string;
+  infrastructureId?: string;
+  pipelineId: string;
+  pipelineName: string;
+  templateRef?: string;
+  templateVersion?: string;
+  variables: Record<string, string>;
+  orgId: string;
+  projectId: string;
+}
+
+/** Harness identifier from a display name: letters, digits, and underscores, not starting with a digit. */
+export function toIdentifier(name: string): string {
+  const id = name.trim().replace(/[^A-Za-z0-9_]+/g, "_").replace(/^_+|_+$/g, "");
+  return /^[0-9]/.test(id) ? `_${id}` : id;
+}
+
+/** Validate scaffold input (harness_execute params) and fill defaults. */
+export function parseScaffoldOptions(input: Record<string, unknown>, defaults: { orgId?: string; projectId?: string }): ScaffoldOptions {
+  const serviceName = asString(input.service_name);
+  if (!serviceName) throw new Error("scaffold_pipeline requires service_name.");
+  const runtime = asString(input.runtime) ?? "kubernetes";
+  const spec = SCAFFOLD_RUNTIMES[runtime];
+  if (!spec) {
+    throw new Error(`Unsupported runtime "${runtime}". Supported: ${Object.keys(SCAFFOLD_RUNTIMES).join(", ")}.`);
+  }
+  const gitProvider = asString(input.git_provider) ?? "Github";
+  if (!(SCAFFOLD_GIT_PROVIDERS as readonly string[]).includes(gitProvider)) {
+    throw new Error(`Unsupported git_provider "${gitProvider}". Supported: ${SCAFFOLD_GIT_PROVIDERS.join(", ")}.`);
+  }
+  const repoConnectorRef = asString(input.repo_connector_ref);
+  if (!repoConnectorRef && gitProvider !== "Harness") {
+    throw new Error("scaffold_pipeline requires repo_connector_ref (the Git connector for the service's repo), unless git_provider is Harness.");
+  }
+  const repoName = asString(input.repo_name);
+  if (gitProvider === "Harness" && !repoName) {
+    throw new Error("scaffold_pipeline with git_provider=Harness requires repo_name.");
+  }
+  const environmentId = asString(input.environment_id);
+  if (!environmentId) throw new Error("scaffold_pipeline requires environment_id (an existing environment to bind the service to).");
+  const orgId = asString(input.org_id) ?? defaults.orgId;
+  const projectId = asString(input.project_id) ?? defaults.projectId;
+  if (!orgId || !projectId) throw new Error("scaffold_pipeline creates project-level entities; provide org_id and project_id.");
+  const templateRef = asString(input.template_ref);
+  const templateVersion = asString(input.template_version);
+  if (templateRef && !templateVersion) throw new Error("template_ref requires template_version (the stage template's version label).");
+
+  const variables: Record<string, string> = {};
+  for (const [name, value] of Object.entries(asRecord(input.variables) ?? {})) {
+    if (value !== undefined && value !== null) variables[name] = String(value);
+  }
+
+  const serviceId = asString(input.service_id) ?? toIdentifier(serviceName);
+  return {
+    serviceId,
+    serviceName,
+    runtime,
+    repoConnectorRef,
+    repoName,
+    branch: asString(input.branch) ?? "main",
+    manifestPath: asString(input.manifest_path) ?? spec.defaultManifestPath,
+    gitProvider,
+    environmentId,
+    infrastructureId: asString(input.infrastructure_id),
+    pipelineId: asString(input.pipeline_id) ?? `${serviceId}_deploy`,
+    pipelineName: asString(input.pipeline_name) ?? `Deploy ${serviceName}`,
+    templateRef,
+    templateVersion,
+    variables,
+    orgId,
+    projectId,
+  };
+}
+
+/** Service YAML: one manifest read from the repo at `manifestPath`. */
+export function buildScaffoldServiceYaml(opts: ScaffoldOptions): string {
+  const spec = SCAFFOLD_RUNTIMES[opts.runtime]!;
+  const store = {
+    type: opts.gitProvider,
+    spec: {
+      ...(opts.repoConnectorRef ? { connectorRef: opts.repoConnectorRef } : {}),
+      ...(opts.repoName ? { repoName: opts.repoName } : {}),
+      gitFetchType: "Branch",
+      branch: opts.branch,
+      ...(spec.manifestType === "HelmChart" ? { folderPath: opts.manifestPath } : { paths: [opts.manifestPath] }),
+    },
+  };
+  const manifestSpec = spec.manifestType === "HelmChart"
+    ? { store, helmVersion: "V3", skipResourceVersioning: false }
+    : { store, skipResourceVersioning: false };
+  return YAML.stringify({
+    service: {
+      name: opts.serviceName,
+      identifier: opts.serviceId,
+      serviceDefinition: {
+        type: spec.deploymentType,
+        spec: {
+          manifests: [{ manifest: { identifier: "manifests", type: spec.manifestType, spec: manifestSpec } }],
+        },
+      },
+    },
+  });
+}
+
+/** Pipeline YAML: one Deployment stage for the service and environment. */
+export function buildScaffoldPipelineYaml(opts: ScaffoldOptions): string {
+  const spec = SCAFFOLD_RUNTIMES[opts.runtime]!;
+  const service = { serviceRef: opts.serviceId };
+  const environment = {
+    environmentRef: opts.environmentId,
+    deployToAll: false,
+    infrastructureDefinitions: opts.infrastructureId ? [{ identifier: opts.infrastructureId }] : "<+input>",
+  };
+  const stage = opts.templateRef
+    ? {
+        name: "Deploy",
+        identifier: "deploy",
+        template: {
+          templateRef: opts.templateRef,
+          versionLabel: opts.templateVersion,
+          templateInputs: { type: "Deployment", spec: { service, environment } },
+        },
+      }
+    : {
+        name: "Deploy",
+        identifier: "deploy",
+        type: "Deployment",
+        spec: {
+          deploymentType: spec.deploymentType,
+          service,
+          environment,
+          execution: {
+            steps: [{ step: { name: spec.deployStep.name, identifier: "deploy", type: spec.deployStep.type, timeout: "10m", spec: { skipDryRun: false } } }],
+            rollbackSteps: [{ step: { name: spec.rollbackStep.name, identifier: "rollback", type: spec.rollbackStep.type, timeout: "10m", spec: {} } }],
+          },
+        },
+        failureStrategies: [{ onFailure: { errors: ["AllErrors"], action: { type: "StageRollback" } } }],
+      };
+  return YAML.stringify({
+    pipeline: {
+      name: opts.pipelineName,
+      identifier: opts.pipelineId,
+      orgIdentifier: opts.orgId,
+      projectIdentifier: opts.projectId,
+      stages: [{ stage }],
+    },
+  });
+}
+
+function created(step: string, result: unknown, identifier: string): Record<string, unknown> {
+  const link = asString(asRecord(result)?.openInHarness);
+  return { step, identifier, ...(link ? { openInHarness: link } : {}) };
+}
+
+/**
+ * Run the scaffold. `auditCtx` is attached to every dispatch so the audit
+ * trail shows each created entity under harness_execute/scaffold_pipeline.
+ */
+export async function scaffoldServicePipeline(
+  registry: Registry,
+  client: HarnessClient,
+  input: Record<string, unknown>,
+  auditCtx: AuditContext,
+  signal?: AbortSignal,
+): Promise<Record<string, unknown>> {
+  const opts = parseScaffoldOptions(input, { orgId: registry.orgId, projectId: registry.projectId });
+  const scope = { org_id: opts.orgId, project_id: opts.projectId };
+
+  // Fail before creating anything when the environment to bind to is missing
+  await registry.dispatch(client, "environment", "get", { ...scope, environment_id: opts.environmentId }, { ...auditCtx, resource_id: opts.environmentId }, signal);
+
+  const done: Array<Record<string, unknown>> = [];
+  const step = async (name: string, identifier: string, run: () => Promise<unknown>): Promise<void> => {
+    try {
+      done.push(created(name, await run(), identifier));
+    } catch (err) {
+      const createdSoFar = done.length > 0
+        ? ` Already created: ${done.map((d) => `${d.step} "${d.identifier}"`).join(", ")} — delete them with harness_delete before retrying, or finish the remaining steps with harness_create.`
+        : "";
+      throw new Error(`scaffold_pipeline failed creating the ${name}: ${err instanceof Error ? err.message : String(err)}.${createdSoFar}`);
+    }
+  };
+
+  await step("service", opts.serviceId, () => registry.dispatch(client, "service", "create", {
+    ...scope,
+    body: { identifier: opts.serviceId, name: opts.serviceName, yaml: buildScaffoldServiceYaml(opts) },
+  }, { ...auditCtx, resource_id: opts.serviceId }, signal));
+
+  const overrideId = `${opts.environmentId}_${opts.serviceId}`;
+  await step("environment binding", overrideId, () => registry.dispatch(client, "service_override", "create", {
+    ...scope,
+    body: {
+      identifier: overrideId,
+      environmentRef: opts.environmentId,
+      serviceRef: opts.serviceId,
+      type: "ENV_SERVICE_OVERRIDE",
+      spec: { variables: Object.entries(opts.variables).map(([name, value]) => ({ name, type: "String", value })) },
+    },
+  }, { ...auditCtx, resource_id: overrideId }, signal));
+
+  await step("pipeline", opts.pipelineId, () => registry.dispatch(client, "pipeline", "create", {
+    ...scope,
+    body: { yamlPipeline: buildScaffoldPipelineYaml(opts) },
+  }, { ...auditCtx, resource_id: opts.pipelineId }, signal));
+
+  return {
+    scaffolded: true,
+    runtime: opts.runtime,
+    created: done,
+    next_step: `Run it with harness_execute(resource_type="pipeline", action="run", resource_id="${opts.pipelineId}")` +
+      (opts.infrastructureId ? "." : ", passing the infrastructure definition as a runtime input."),
+  };
+}
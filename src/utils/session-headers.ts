@@ -10,6 +10,8 @@ export const PIPELINE_VERSION_HEADER = "x-harness-pipeline-version";
 export const AUTO_APPROVE_RISK_HEADER = "x-harness-auto-approve-risk";
 export const API_KEY_HEADER = "x-harness-api-key";
 export const ACCOUNT_ID_HEADER = "x-harness-account-id";
+/** Gateway-style alias for ACCOUNT_ID_HEADER. */
+export const ACCOUNT_HEADER = "harness-account";
 export const ORG_HEADER = "x-harness-org";
 export const PROJECT_HEADER = "x-harness-project";
 
@@ -91,7 +93,9 @@ export function mergeConfigWithSessionHeaders(
   // Identity headers are only accepted in multi-user mode.
   // In single-user mode, the operator's config is authoritative.
   const sessionApiKey = isMultiUser ? getHeader(headers, API_KEY_HEADER) : undefined;
-  const rawSessionAccountId = isMultiUser ? getHeader(headers, ACCOUNT_ID_HEADER) : undefined;
+  const rawSessionAccountId = isMultiUser
+    ? getHeader(headers, ACCOUNT_ID_HEADER) ?? getHeader(headers, ACCOUNT_HEADER)
+    : undefined;
   const tokenAccountId = sessionApiKey ? extractAccountIdFromToken(sessionApiKey) : undefined;
   const sessionAccountId = rawSessionAccountId ?? tokenAccountId;
   const sessionOrg = getHeader(headers, ORG_HEADER);
@@ -0,0 +1,150 @@
+/**
+ * Memory-bounded buffer for downloaded payloads. Bytes stay in memory up to a
+ * threshold (HARNESS_SPILL_THRESHOLD_MB); past it, everything written so far
+ * moves to a temp file in HARNESS_OUTPUT_DIR and later chunks are appended
+ * there, so a large download never sits whole in the V8 heap. Spilled
+ * contents are only ever streamed or moved, never read back whole, and the
+ * file stops growing at HARNESS_SPILL_MAX_MB. Without an output dir the
+ * buffer stays in memory and callers enforce their own limits.
+ */
+import { randomUUID } from "node:crypto";
+import { createReadStream } from "node:fs";
+import { appendFile, mkdir, rename, rm, writeFile } from "node:fs/promises";
+import { join, resolve } from "node:path";
+import { Readable } from "node:stream";
+import { pathToFileURL } from "node:url";
+import { sanitizeFileName, type OutputFile } from "./output-dir.js";
+
+const DEFAULT_THRESHOLD_BYTES = 1024 * 1024;
+const DEFAULT_MAX_DISK_BYTES = 256 * 1024 * 1024;
+
+let settings: { dir?: string; thresholdBytes: number; maxDiskBytes: number } = {
+  thresholdBytes: DEFAULT_THRESHOLD_BYTES,
+  maxDiskBytes: DEFAULT_MAX_DISK_BYTES,
+};
+
+/** Set the spill directory, in-memory threshold, and disk cap (called once at startup). */
+export function configureSpillBuffers(options: { dir?: string; thresholdBytes?: number; maxDiskBytes?: number }): void {
+  settings = {
+    dir: options.dir,
+    thresholdBytes: options.thresholdBytes && options.thresholdBytes > 0 ? options.thresholdBytes : DEFAULT_THRESHOLD_BYTES,
+    maxDiskBytes: options.maxDiskBytes && options.maxDiskBytes > 0 ? options.maxDiskBytes : DEFAULT_MAX_DISK_BYTES,
+  };
+}
+
+/** Whether downloads can spill to disk (HARNESS_OUTPUT_DIR is set). */
+export function canSpillToDisk(): boolean {
+  return settings.dir !== undefined;
+}
+
+export class SpillBuffer {
+  private chunks: Buffer[] = [];
+  private bytes = 0;
+  private spillPath: string | undefined;
+  private head = Buffer.alloc(0);
+  /** Set when reading stopped at the disk cap; the contents are incomplete. */
+  truncated = false;
+
+  constructor(
+    private readonly name: string,
+    private readonly dir: string | undefined = settings.dir,
+    private readonly thresholdBytes: number = settings.thresholdBytes,
+  ) {}
+
+  /** Total bytes written. */
+  get size(): number { return this.bytes; }
+  /** True once the contents live in a temp file rather than memory. */
+  get spilled(): boolean { return this.spillPath !== undefined; }
+  /** Whether this buffer can move to disk (an output dir is configured). */
+  get canSpill(): boolean { return this.dir !== undefined; }
+  /** First bytes written, for sniffing the payload format without reading the file back. */
+  get magic(): Buffer { return this.head; }
+
+  async write(chunk: Uint8Array): Promise<void> {
+    const buf = Buffer.from(chunk.buffer, chunk.byteOffset, chunk.byteLength);
+    if (this.head.length < 4) this.head = Buffer.concat([this.head, buf.subarray(0, 4 - this.head.length)]);
+    this.bytes += buf.length;
+    if (this.spillPath) {
+      await appendFile(this.spillPath, buf);
+      return;
+    }
+    this.chunks.push(buf);
+    if (this.dir && this.bytes > this.thresholdBytes) await this.spill();
+  }
+
+  private async spill(): Promise<void> {
+    const root = resolve(this.dir!);
+    await mkdir(root, { recursive: true });
+    const path = join(root, `.${sanitizeFileName(this.name)}.${randomUUID()}.part`);
+    await writeFile(path, Buffer.concat(this.chunks), { mode: 0o600 });
+    this.spillPath = path;
+    this.chunks = [];
+  }
+
+  /** The contents, when they are still in memory. Spilled contents must be read with `stream()`. */
+  toBuffer(): Buffer {
+    if (this.spillPath) throw new Error("Buffer has spilled to disk; read it with stream()");
+    return Buffer.concat(this.chunks);
+  }
+
+  /** Stream the contents from memory or from the spill file. */
+  stream(): Readable {
+    return this.spillPath ? createReadStream(this.spillPath) : Readable.from(this.chunks);
+  }
+
+  /** Keep the contents in the output dir as `name` (default: the buffer's name) and return the file. */
+  async persist(name: string = this.name): Promise<OutputFile> {
+    if (!this.dir) throw new Error("Cannot keep a download on disk: HARNESS_OUTPUT_DIR is not set.");
+    if (!this.spillPath) await this.spill();
+    const fileName = sanitizeFileName(name);
+    const path = join(resolve(this.dir), fileName);
+    await rename(this.spillPath!, path);
+    this.spillPath = undefined;
+    return { path, uri: pathToFileURL(path).href, name: fileName, bytes: this.bytes };
+  }
+
+  /** Drop the contents and remove any temp file. */
+  async dispose(): Promise<void> {
+    this.chunks = [];
+    if (this.spillPath) {
+      await rm(this.spillPath, { force: true }).catch(() => {});
+      this.spillPath = undefined;
+    }
+  }
+}
+
+/**
+ * Read a response body into a SpillBuffer. Bodies up to `maxInMemoryBytes`
+ * stay in memory — the caller reads those whole anyway, so spilling them
+ * would only add disk I/O. Past it, the body spills to disk when it can;
+ * otherwise reading stops so an oversized body is never held whole. A
+ * spilled body stops at the disk cap (`truncated`). Check `size` against the
+ * limit afterwards.
+ */
+export async function bufferResponseBody(
+  response: Response,
+  name: string,
+  options: { maxInMemoryBytes?: number } = {},
+): Promise<SpillBuffer> {
+  const buffer = new SpillBuffer(name, settings.dir, Math.max(settings.thresholdBytes, options.maxInMemoryBytes ?? 0));
+  if (!response.body) return buffer;
+  const reader = response.body.getReader();
+  try {
+    for (;;) {
+      const { done, value } = await reader.read();
+      if (done) break;
+      await buffer.write(value);
+      const overMemory = !buffer.canSpill && options.maxInMemoryBytes !== undefined && buffer.size > options.maxInMemoryBytes;
+      const overDisk = buffer.spilled && buffer.size > settings.maxDiskBytes;
+      if (overMemory || overDisk) {
+        if (overDisk) buffer.truncated = true;
+        await reader.cancel().catch(() => {});
+        break;
+      }
+    }
+  } catch (err) {
+    await buffer.dispose();
+    throw err;
+  }
+  return buffer;
+}
@@ -0,0 +1,220 @@
+/**
+ * Stdio transport that accepts both JSON-RPC framings MCP clients use.
+ *
+ * The SDK's stdio transport only reads newline-delimited JSON, but some
+ * clients reuse LSP plumbing and send `Content-Length:` framed messages.
+ * Framing is detected per message — a message starting with `{` or `[` is a
+ * JSON line, anything else is a header block — and blank lines between
+ * messages are skipped. Replies use the framing of the most recent inbound
+ * message, so each client reads responses the way it writes requests.
+ *
+ * Buffered input is bounded: a message larger than the frame limit (64 MB by
+ * default, HARNESS_STDIO_MAX_FRAME_MB) is rejected with a protocol error and
+ * its bytes are discarded as they arrive, and a header block that never ends
+ * is dropped once it passes 16 KB.
+ */
+
+import type { Readable, Writable } from "node:stream";
+import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import { JSONRPCMessageSchema, type JSONRPCMessage } from "@modelcontextprotocol/sdk/types.js";
+
+export type StdioFraming = "ndjson" | "content-length";
+
+const HEADER_SEPARATOR = /\r?\n\r?\n/;
+
+export const DEFAULT_MAX_FRAME_BYTES = 64 * 1024 * 1024;
+
+/** Longest header block accepted while waiting for its blank-line terminator. */
+const MAX_HEADER_BYTES = 16 * 1024;
+
+function isWhitespace(byte: number): boolean {
+  return byte === 0x20 || byte === 0x09 || byte === 0x0a || byte === 0x0d;
+}
+
+function parseMessage(text: string): JSONRPCMessage {
+  return JSONRPCMessageSchema.parse(JSON.parse(text));
+}
+
+/** Incremental reader for NDJSON and Content-Length framed JSON-RPC. */
+export class FrameReader {
+  private buffer: Buffer = Buffer.alloc(0);
+  /** Body bytes of a rejected Content-Length frame still to be dropped. */
+  private discardBytes = 0;
+  /** Dropping the rest of a rejected JSON line, up to its newline. */
+  private discardLine = false;
+
+  constructor(private readonly maxFrameBytes = DEFAULT_MAX_FRAME_BYTES) {}
+
+  append(chunk: Buffer): void {
+    this.buffer = this.buffer.length === 0 ? chunk : Buffer.concat([this.buffer, chunk]);
+  }
+
+  clear(): void {
+    this.buffer = Buffer.alloc(0);
+    this.discardBytes = 0;
+    this.discardLine = false;
+  }
+
+  /** Drop what is left of a rejected frame. False while more of it is still to come. */
+  private skipRejected(): boolean {
+    if (this.discardBytes > 0) {
+      const n = Math.min(this.discardBytes, this.buffer.length);
+      this.buffer = this.buffer.subarray(n);
+      this.discardBytes -= n;
+      if (this.discardBytes > 0) return false;
+    }
+    if (this.discardLine) {
+      const newline = this.buffer.indexOf(0x0a);
+      if (newline === -1) {
+        this.buffer = Buffer.alloc(0);
+        return false;
+      }
+      this.buffer = this.buffer.subarray(newline + 1);
+      this.discardLine = false;
+    }
+    return true;
+  }
+
+  private tooLarge(size: number): Error {
+    return new Error(`Stdio message of ${size} bytes exceeds the ${this.maxFrameBytes}-byte frame limit (HARNESS_STDIO_MAX_FRAME_MB)`);
+  }
+
+  /**
+   * Next complete message, or undefined when more input is needed. Malformed
+   * frames throw after being consumed, so reading can continue past them.
+   */
+  read(): { message: JSONRPCMessage; framing: StdioFraming } | undefined {
+    if (!this.skipRejected()) return undefined;
+    let start = 0;
+    while (start < this.buffer.length && isWhitespace(this.buffer[start]!)) start++;
+    this.buffer = this.buffer.subarray(start);
+    if (this.buffer.length === 0) return undefined;
+
+    const first = this.buffer[0];
+    if (first === 0x7b /* { */ || first === 0x5b /* [ */) {
+      const newline = this.buffer.indexOf(0x0a);
+      if (newline === -1) {
+        if (this.buffer.length <= this.maxFrameBytes) return undefined;
+        const size = this.buffer.length;
+        this.buffer = Buffer.alloc(0);
+        this.discardLine = true;
+        throw this.tooLarge(size);
+      }
+      if (newline > this.maxFrameBytes) {
+        this.buffer = this.buffer.subarray(newline + 1);
+        throw this.tooLarge(newline);
+      }
+      const line = this.buffer.toString("utf8", 0, newline).replace(/\r$/, "");
+      this.buffer = this.buffer.subarray(newline + 1);
+      return { message: parseMessage(line), framing: "ndjson" };
+    }
+
+    // Header block: decode as latin1 so string offsets equal byte offsets
+    const head = this.buffer.toString("latin1", 0, Math.min(this.buffer.length, MAX_HEADER_BYTES));
+    const firstLineEnd = head.indexOf("\n");
+    if (firstLineEnd !== -1 && !/^[A-Za-z][A-Za-z0-9-]*\s*:/.test(head)) {
+      // Neither JSON nor a header: drop the line rather than waiting for a header block that never ends
+      this.buffer = this.buffer.subarray(firstLineEnd + 1);
+      throw new Error("Stdio input line is neither JSON nor a Content-Length header");
+    }
+    const separator = HEADER_SEPARATOR.exec(head);
+    if (!separator) {
+      if (this.buffer.length < MAX_HEADER_BYTES) return undefined;
+      this.buffer = Buffer.alloc(0);
+      throw new Error(`Stdio frame header block exceeds ${MAX_HEADER_BYTES} bytes without a terminating blank line`);
+    }
+    const bodyStart = separator.index + separator[0].length;
+    const lengthHeader = /^content-length\s*:\s*(\d+)\s*$/im.exec(head.slice(0, separator.index));
+    if (!lengthHeader) {
+      this.buffer = this.buffer.subarray(bodyStart);
+      throw new Error("Stdio frame header block has no Content-Length header");
+    }
+    const length = Number(lengthHeader[1]);
+    if (length > this.maxFrameBytes) {
+      this.buffer = this.buffer.subarray(bodyStart);
+      this.discardBytes = length;
+      this.skipRejected();
+      throw this.tooLarge(length);
+    }
+    const bodyEnd = bodyStart + length;
+    if (this.buffer.length < bodyEnd) return undefined;
+    const body = this.buffer.toString("utf8", bodyStart, bodyEnd);
+    this.buffer = this.buffer.subarray(bodyEnd);
+    return { message: parseMessage(body), framing: "content-length" };
+  }
+}
+
+/** Serialize a message in the given framing. */
+export function frameMessage(message: JSONRPCMessage, framing: StdioFraming): string {
+  const json = JSON.stringify(message);
+  if (framing === "ndjson") return `${json}\n`;
+  return `Content-Length: ${Buffer.byteLength(json, "utf8")}\r\n\r\n${json}`;
+}
+
+/**
+ * Drop-in replacement for the SDK's StdioServerTransport: reads both
+ * framings from `stdin` and writes to `stdout`. Also used per connection by
+ * the Unix socket transport.
+ */
+export class StdioTransport implements Transport {
+  private readonly reader: FrameReader;
+  private started = false;
+  private framing: StdioFraming = "ndjson";
+
+  onclose?: () => void;
+  onerror?: (error: Error) => void;
+  onmessage?: (message: JSONRPCMessage) => void;
+
+  constructor(
+    private readonly stdin: Readable = process.stdin,
+    private readonly stdout: Writable = process.stdout,
+    maxFrameBytes = DEFAULT_MAX_FRAME_BYTES,
+  ) {
+    this.reader = new FrameReader(maxFrameBytes);
+  }
+
+  private readonly onData = (chunk: Buffer | string): void => {
+    this.reader.append(typeof chunk === "string" ? Buffer.from(chunk, "utf8") : chunk);
+    for (;;) {
+      let frame;
+      try {
+        frame = this.reader.read();
+      } catch (err) {
+        this.onerror?.(err instanceof Error ? err : new Error(String(err)));
+        continue;
+      }
+      if (!frame) break;
+      this.framing = frame.framing;
+      this.onmessage?.(frame.message);
+    }
+  };
+
+  private readonly onStreamError = (error: Error): void => {
+    this.onerror?.(error);
+  };
+
+  async start(): Promise<void> {
+    if (this.started) {
+      throw new Error("StdioTransport already started! If using Server class, note that connect() calls start() automatically.");
+    }
+    this.started = true;
+    this.stdin.on("data", this.onData);
+    this.stdin.on("error", this.onStreamError);
+  }
+
+  async close(): Promise<void> {
+    this.stdin.off("data", this.onData);
+    this.stdin.off("error", this.onStreamError);
+    // Only pause stdin when nothing else is reading it
+    if (this.stdin.listenerCount("data") === 0) this.stdin.pause();
+    this.reader.clear();
+    this.onclose?.();
+  }
+
+  send(message: JSONRPCMessage): Promise<void> {
+    return new Promise((resolve) => {
+      if (this.stdout.write(frameMessage(message, this.framing))) resolve();
+      else this.stdout.once("drain", resolve);
+    });
+  }
+}
@@ -0,0 +1,151 @@
+/**
+ * Strict schema validation (`--strict-schemas` / HARNESS_STRICT_SCHEMAS).
+ *
+ * The SDK always validates tool arguments against each tool's Zod input
+ * schema, but the registry's per-resource declarations are advisory: dispatch
+ * only enforces required body fields, params, and filters. Strict mode also
+ * checks the declared body field types and filter types/enums before the API
+ * call, and checks each tool's structured output against its declared output
+ * schema — so drift between a declaration and what a handler actually sends
+ * or returns fails loudly with the offending paths, instead of surfacing as
+ * an opaque API 400 or a client-side output validation error.
+ */
+
+import type * as z from "zod/v4";
+import type { BodyFieldSpec, FilterFieldSpec } from "../registry/types.js";
+import type { ToolResult } from "./response-formatter.js";
+import { isRecord } from "./type-guards.js";
+import { createLogger } from "./logger.js";
+
+const log = createLogger("strict-schemas");
+
+let _enabled = false;
+let _outputSchemas: Readonly<Record<string, z.ZodType>> = {};
+
+/**
+ * Enable strict validation. `outputSchemas` maps tool name → declared output
+ * schema. Call once at startup.
+ */
+export function configureStrictSchemas(opts: { enabled: boolean; outputSchemas?: Record<string, z.ZodType> }): void {
+  _enabled = opts.enabled;
+  _outputSchemas = opts.outputSchemas ?? {};
+}
+
+export function isStrictSchemas(): boolean {
+  return _enabled;
+}
+
+function describeValue(value: unknown): string {
+  if (value === null) return "null";
+  if (Array.isArray(value)) return "array";
+  return typeof value;
+}
+
+function matchesFieldType(value: unknown, type: BodyFieldSpec["type"]): boolean {
+  switch (type) {
+    case "string": return typeof value === "string";
+    case "number": return typeof value === "number" && Number.isFinite(value);
+    case "boolean": return typeof value === "boolean";
+    case "array": return Array.isArray(value);
+    case "object": return isRecord(value);
+    // YAML fields accept the raw string or an already-parsed object
+    case "yaml": return typeof value === "string" || isRecord(value);
+  }
+}
+
+/**
+ * Type mismatches for the declared fields present in `payload`, recursing into
+ * nested object fields (where missing required nested fields are reported too).
+ * Undeclared fields are not reported — bodySchema lists the fields agents need,
+ * not every field the API accepts.
+ */
+export function bodySchemaViolations(fields: BodyFieldSpec[], payload: Record<string, unknown>, prefix = ""): string[] {
+  const violations: string[] = [];
+  for (const field of fields) {
+    const path = `${prefix}${field.name}`;
+    const value = payload[field.name];
+    if (value === undefined) {
+      // Top-level required fields are already enforced by the registry
+      if (prefix && field.required) violations.push(`${path}: required`);
+      continue;
+    }
+    if (!matchesFieldType(value, field.type)) {
+      violations.push(`${path}: expected ${field.type}, got ${describeValue(value)}`);
+      continue;
+    }
+    if (field.type === "object" && field.fields && isRecord(value)) {
+      violations.push(...bodySchemaViolations(field.fields, value, `${path}.`));
+    }
+  }
+  return violations;
+}
+
+function matchesFilterType(value: unknown, type: FilterFieldSpec["type"] = "string"): boolean {
+  // Filters become query parameters, so numeric and boolean strings are equivalent
+  switch (type) {
+    case "string": return typeof value === "string";
+    case "number": return typeof value === "number" || (typeof value === "string" && value.trim() !== "" && !Number.isNaN(Number(value)));
+    case "boolean": return typeof value === "boolean" || value === "true" || value === "false";
+  }
+}
+
+/** Type and enum mismatches for the declared list filters present in `input`. */
+export function filterViolations(fields: FilterFieldSpec[], input: Record<string, unknown>): string[] {
+  const violations: string[] = [];
+  for (const field of fields) {
+    const value = input[field.name];
+    if (value === undefined || value === "") continue;
+    for (const item of Array.isArray(value) ? value : [value]) {
+      if (!matchesFilterType(item, field.type)) {
+        violations.push(`${field.name}: expected ${field.type ?? "string"}, got ${describeValue(item)}`);
+      } else if (field.enum && !field.enum.includes(String(item))) {
+        violations.push(`${field.name}: "${String(item)}" is not one of ${field.enum.join(", ")}`);
+      }
+    }
+  }
+  return violations;
+}
+
+/** Error message for input that does not match a resource's declared schema. */
+export function strictInputError(resourceType: string, what: string, violations: string[]): Error {
+  return new Error(
+    `Strict schema validation failed for ${resourceType} ${what}: ${violations.join("; ")}. ` +
+    `Use harness_describe(resource_type="${resourceType}") to see the schema.`,
+  );
+}
+
+function isToolResult(value: unknown): value is ToolResult {
+  return isRecord(value) && Array.isArray(value.content);
+}
+
+/**
+ * Check a successful tool result against the tool's declared output schema.
+ * Mismatches are logged and returned as a structured error naming each
+ * offending path; results for tools without a declared schema pass through.
+ */
+export function checkToolOutput<T>(tool: string, result: T | ToolResult): T | ToolResult {
+  const schema = _outputSchemas[tool];
+  if (!schema || !isToolResult(result) || result.isError) return result;
+
+  let issues: string[];
+  if (result.structuredContent === undefined) {
+    issues = ["(root): no structured content — the handler returned a non-object payload"];
+  } else {
+    const parsed = schema.safeParse(result.structuredContent);
+    if (parsed.success) return result;
+    issues = parsed.error.issues.map((issue) => `${issue.path.length > 0 ? issue.path.join(".") : "(root)"}: ${issue.message}`);
+  }
+
+  log.error("Tool output does not match its declared output schema", { tool, issues });
+  const payload = {
+    error: `${tool} returned output that does not match its declared output schema.`,
+    code: "OUTPUT_SCHEMA_MISMATCH",
+    tool,
+    issues,
+    hint: "This is a server-side schema drift. Report it, or restart without --strict-schemas to receive the unvalidated output.",
+  };
+  return {
+    content: [{ type: "text", text: JSON.stringify(payload) }],
+    isError: true,
+  };
+}
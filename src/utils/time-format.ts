@@ -0,0 +1,102 @@
+/**
+ * Readable timestamps and durations in tool output (HARNESS_LOCALIZE_TIMES).
+ *
+ * Harness APIs return times as epoch milliseconds (`startTs: 1720598400000`)
+ * and durations as raw millisecond counts, which models routinely misread
+ * when summarizing. When enabled, every JSON tool result is post-processed:
+ * epoch-ms values in timestamp-named fields become ISO 8601 strings in the
+ * configured timezone (HARNESS_TIMEZONE, default UTC), and millisecond
+ * duration fields become strings like "1h 2m 5s".
+ */
+
+/** Timestamp-like keys: createdAt, startTs, lastModifiedTime, endDate, timestamp. */
+const TIMESTAMP_KEY = /(?:At|Ts|Time|Date|Timestamp|^timestamp|^created|^updated|^lastModified)$/;
+
+/** Millisecond duration keys: duration, durationMs, totalDuration, elapsedTime. */
+const DURATION_KEY = /(?:^duration|Duration)(?:Ms|Millis)?$|(?:^elapsed|Elapsed)(?:Time|Ms)?$/;
+
+/** Epoch-ms values from 2001 to 2099 — smaller numbers are not millisecond timestamps. */
+const MIN_EPOCH_MS = 978_307_200_000;
+const MAX_EPOCH_MS = 4_102_444_800_000;
+
+let _enabled = false;
+let _timeZone = "UTC";
+
+/** Throws when `timeZone` is not an IANA zone name the runtime knows. */
+export function assertTimeZone(timeZone: string): void {
+  try {
+    new Intl.DateTimeFormat("en-US", { timeZone });
+  } catch {
+    throw new Error(`Invalid HARNESS_TIMEZONE "${timeZone}". Use an IANA zone name such as "UTC", "America/New_York", or "Asia/Kolkata".`);
+  }
+}
+
+/** Configure output localization. Call once at startup. */
+export function configureTimeFormatting(opts: { enabled: boolean; timeZone?: string }): void {
+  if (opts.timeZone) assertTimeZone(opts.timeZone);
+  _enabled = opts.enabled;
+  _timeZone = opts.timeZone ?? "UTC";
+}
+
+const pad = (n: number, width = 2): string => String(n).padStart(width, "0");
+
+/** ISO 8601 with the zone's UTC offset, e.g. `2025-07-10T13:30:00.000+05:30` (`Z` for UTC). */
+export function formatTimestamp(ms: number, timeZone = _timeZone): string {
+  if (timeZone === "UTC") return new Date(ms).toISOString();
+  const parts = Object.fromEntries(
+    new Intl.DateTimeFormat("en-US", {
+      timeZone,
+      hourCycle: "h23",
+      year: "numeric",
+      month: "2-digit",
+      day: "2-digit",
+      hour: "2-digit",
+      minute: "2-digit",
+      second: "2-digit",
+    })
+      .formatToParts(new Date(ms))
+      .map((p) => [p.type, p.value]),
+  );
+  const millis = ((ms % 1000) + 1000) % 1000;
+  const wallClock = Date.UTC(+parts.year!, +parts.month! - 1, +parts.day!, +parts.hour!, +parts.minute!, +parts.second!, millis);
+  const offsetMinutes = Math.round((wallClock - ms) / 60_000);
+  const sign = offsetMinutes < 0 ? "-" : "+";
+  const abs = Math.abs(offsetMinutes);
+  return `${parts.year}-${parts.month}-${parts.day}T${parts.hour}:${parts.minute}:${parts.second}.${pad(millis, 3)}${sign}${pad(Math.floor(abs / 60))}:${pad(abs % 60)}`;
+}
+
+/** Human-readable duration: "850ms", "42s", "3m 5s", "2h 0m 12s", "1d 4h 30m". */
+export function formatDuration(ms: number): string {
+  if (ms < 1000) return `${Math.round(ms)}ms`;
+  const totalSeconds = Math.round(ms / 1000);
+  const days = Math.floor(totalSeconds / 86_400);
+  const hours = Math.floor((totalSeconds % 86_400) / 3600);
+  const minutes = Math.floor((totalSeconds % 3600) / 60);
+  const seconds = totalSeconds % 60;
+  if (days > 0) return `${days}d ${hours}h ${minutes}m`;
+  if (hours > 0) return `${hours}h ${minutes}m ${seconds}s`;
+  if (minutes > 0) return `${minutes}m ${seconds}s`;
+  return `${seconds}s`;
+}
+
+function localizeValue(key: string | undefined, value: unknown): unknown {
+  if (typeof value === "number" && key !== undefined) {
+    if (TIMESTAMP_KEY.test(key) && value >= MIN_EPOCH_MS && value <= MAX_EPOCH_MS) return formatTimestamp(value);
+    if (DURATION_KEY.test(key) && value >= 0) return formatDuration(value);
+    return value;
+  }
+  if (Array.isArray(value)) return value.map((item) => localizeValue(key, item));
+  if (value !== null && typeof value === "object") {
+    return Object.fromEntries(Object.entries(value).map(([k, v]) => [k, localizeValue(k, v)]));
+  }
+  return value;
+}
+
+/**
+ * Localize timestamps and durations in a tool payload. Returns the payload
+ * unchanged when localization is off; never mutates the input.
+ */
+export function localizeTimes<T>(payload: T): T {
+  if (!_enabled) return payload;
+  return localizeValue(undefined, payload) as T;
+}
@@ -0,0 +1,87 @@
+/**
+ * Natural time inputs → epoch milliseconds.
+ *
+ * Harness APIs take epoch ms (or ISO strings) for time windows, but agents
+ * relay what users say: "last 7d", "yesterday", "2 hours ago". Tools that
+ * accept `start_time` / `end_time` parse them here so every toolset accepts
+ * the same forms:
+ *
+ *  - epoch ms as a number or numeric string (`1720598400000`)
+ *  - ISO 8601 / RFC 3339 (`2025-07-10`, `2025-07-10T08:00:00Z`)
+ *  - `now`, `today`, `yesterday` (UTC day boundaries)
+ *  - relative durations: `7d`, `last 7d`, `last 24 hours`, `2 weeks ago`
+ *
+ * A relative duration always means "that long before now". Day keywords
+ * resolve to the start of the day, or to its end when parsed as the end of a
+ * window (`{ edge: "end" }`), so `start_time=yesterday, end_time=yesterday`
+ * covers the whole day.
+ */
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+const UNIT_MS: Record<string, number> = {
+  s: 1000,
+  m: 60 * 1000,
+  h: 60 * 60 * 1000,
+  d: DAY_MS,
+  w: 7 * DAY_MS,
+};
+
+/** Unit spellings accepted in relative durations, mapped to UNIT_MS keys. */
+const UNIT_ALIASES: Record<string, string> = {
+  s: "s", sec: "s", secs: "s", second: "s", seconds: "s",
+  m: "m", min: "m", mins: "m", minute: "m", minutes: "m",
+  h: "h", hr: "h", hrs: "h", hour: "h", hours: "h",
+  d: "d", day: "d", days: "d",
+  w: "w", wk: "w", wks: "w", week: "w", weeks: "w",
+};
+
+const RELATIVE_PATTERN = /^(?:last|past)?\s*(\d+)\s*([a-z]+)(?:\s+ago)?$/;
+
+export interface TimeParseOptions {
+  /** Reference time for relative inputs. Default: Date.now(). */
+  now?: number;
+  /** Which end of a window the value is: day keywords resolve to the start (default) or end of the day. */
+  edge?: "start" | "end";
+}
+
+function startOfUtcDay(ms: number): number {
+  return Math.floor(ms / DAY_MS) * DAY_MS;
+}
+
+/**
+ * Parse a natural time input to epoch ms. Returns undefined for empty or
+ * unrecognized input so callers can fall back to their default window.
+ */
+export function parseTimeInput(value: unknown, opts: TimeParseOptions = {}): number | undefined {
+  if (value === undefined || value === null || value === "") return undefined;
+  const now = opts.now ?? Date.now();
+
+  if (typeof value === "number") return Number.isFinite(value) && value > 0 ? value : undefined;
+  if (typeof value !== "string") return undefined;
+
+  const text = value.trim().toLowerCase();
+  if (/^\d+$/.test(text)) {
+    const n = Number(text);
+    return n > 0 ? n : undefined;
+  }
+
+  if (text === "now") return now;
+  if (text === "today" || text === "yesterday") {
+    const dayStart = startOfUtcDay(now) - (text === "yesterday" ? DAY_MS : 0);
+    return opts.edge === "end" ? dayStart + DAY_MS - 1 : dayStart;
+  }
+
+  const relative = RELATIVE_PATTERN.exec(text);
+  if (relative) {
+    const unit = UNIT_ALIASES[relative[2]!];
+    if (!unit) return undefined;
+    return now - Number(relative[1]) * UNIT_MS[unit]!;
+  }
+
+  const parsed = new Date(value.trim()).getTime();
+  return Number.isNaN(parsed) ? undefined : parsed;
+}
+
+/** Description suffix for time filter fields, so every toolset documents the same accepted forms. */
+export const TIME_INPUT_FORMATS = "Accepts epoch ms, ISO 8601, now/today/yesterday, or relative durations like \"last 7d\" or \"24h ago\".";
@@ -0,0 +1,124 @@
+/**
+ * CLI access to the MCP surface: the `tools list` and `tools schema`
+ * subcommands, and one-shot `call`. The server is built with the current
+ * environment (toolset filtering, read-only mode, pipeline version) and driven
+ * over an in-memory transport, so the output is exactly what an agent would
+ * see. Listing never contacts Harness.
+ */
+
+import { Client } from "@modelcontextprotocol/sdk/client/index.js";
+import { InMemoryTransport } from "@modelcontextprotocol/sdk/inMemory.js";
+import type { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import type { CallToolResult, Tool } from "@modelcontextprotocol/sdk/types.js";
+import { ConfigSchema, type Config } from "../config.js";
+import type { Registry } from "../registry/index.js";
+
+/** Stand-in credential so single-user config validates when no API key is set. Never sent anywhere. */
+const CATALOG_PLACEHOLDER_API_KEY = "pat.catalog.offline.placeholder";
+
+/** Config for building the catalog: the real environment, with a placeholder API key when none is set. */
+export function loadCatalogConfig(env: NodeJS.ProcessEnv = process.env): Config {
+  const multiUser = env.HARNESS_MCP_MODE === "multi-user";
+  const result = ConfigSchema.safeParse(
+    multiUser || env.HARNESS_API_KEY ? env : { ...env, HARNESS_API_KEY: CATALOG_PLACEHOLDER_API_KEY },
+  );
+  if (!result.success) {
+    const issues = result.error.issues.map((i) => `  ${i.path.join(".")}: ${i.message}`).join("\n");
+    throw new Error(`Invalid configuration:\n${issues}`);
+  }
+  return result.data;
+}
+
+/** Connect an MCP client to `server` over an in-memory transport for the duration of `fn`. */
+export async function withInMemoryClient<T>(server: McpServer, fn: (client: Client) => Promise<T>): Promise<T> {
+  const [clientTransport, serverTransport] = InMemoryTransport.createLinkedPair();
+  const client = new Client({ name: "harness-mcp-cli", version: "0.0.0" });
+  await server.connect(serverTransport);
+  try {
+    await client.connect(clientTransport);
+    return await fn(client);
+  } finally {
+    await client.close();
+  }
+}
+
+/** The tools `server` advertises, as returned by `tools/list`. */
+export function listServerTools(server: McpServer): Promise<Tool[]> {
+  return withInMemoryClient(server, async (client) => (await client.listTools()).tools);
+}
+
+/** Run one tool call against `server`. Unknown tools are rejected with the list of available ones. */
+export function callServerTool(server: McpServer, name: string, args: Record<string, unknown>): Promise<CallToolResult> {
+  return withInMemoryClient(server, async (client) => {
+    const { tools } = await client.listTools();
+    if (!tools.some((t) => t.name === name)) {
+      throw new Error(`Unknown tool "${name}". Available: ${tools.map((t) => t.name).join(", ")}`);
+    }
+    return await client.callTool({ name, arguments: args }) as CallToolResult;
+  });
+}
+
+/**
+ * `call` output: the structured result when the tool returns one, otherwise
+ * its text content (parsed when it is JSON), as pretty-printed JSON.
+ */
+export function renderCallResult(result: CallToolResult): string {
+  if (result.structuredContent !== undefined && !result.isError) {
+    return JSON.stringify(result.structuredContent, null, 2);
+  }
+  const texts = result.content.flatMap((c) => (c.type === "text" ? [c.text] : []));
+  const values = texts.map((text) => {
+    try {
+      return JSON.parse(text) as unknown;
+    } catch {
+      return text;
+    }
+  });
+  const body = values.length === 1 ? values[0] : values;
+  return JSON.stringify(result.isError && typeof body === "string" ? { error: body } : body, null, 2);
+}
+
+function toolAccess(tool: Tool): string {
+  if (tool.annotations?.readOnlyHint) return "read";
+  return tool.annotations?.destructiveHint ? "write, destructive" : "write";
+}
+
+/** `tools list` output: MCP tools with access flags, then resource operations per toolset. */
+export function renderToolList(tools: Tool[], registry: Registry): string {
+  const width = Math.max(...tools.map((t) => t.name.length));
+  const lines = [`Tools (${tools.length}):`];
+  for (const tool of tools) {
+    lines.push(`  ${tool.name.padEnd(width)}  [${toolAccess(tool)}]  ${tool.annotations?.title ?? ""}`.trimEnd());
+  }
+
+  const toolsets = registry.getAllToolsets();
+  lines.push("", `Resource types by toolset (${toolsets.length} enabled):`);
+  for (const toolset of toolsets) {
+    lines.push(`  ${toolset.name} — ${toolset.displayName}`);
+    for (const def of toolset.resources) {
+      const reads: string[] = [];
+      const writes: string[] = [];
+      for (const [op, spec] of Object.entries(def.operations)) {
+        (spec.operationPolicy.risk === "read" ? reads : writes).push(op);
+      }
+      for (const [action, spec] of Object.entries(def.executeActions ?? {})) {
+        (spec.operationPolicy.risk === "read" ? reads : writes).push(`execute:${action}`);
+      }
+      const parts = [
+        ...(reads.length ? [`read: ${reads.join(", ")}`] : []),
+        ...(writes.length ? [`write: ${writes.join(", ")}`] : []),
+      ];
+      lines.push(`    ${def.resourceType}  ${parts.join("; ")}`);
+    }
+  }
+  return lines.join("\n");
+}
+
+/** `tools schema <name>` output: the tool's description, schemas, and annotations as JSON. */
+export function renderToolSchema(tools: Tool[], name: string): string {
+  const tool = tools.find((t) => t.name === name);
+  if (!tool) {
+    throw new Error(`Unknown tool "${name}". Available: ${tools.map((t) => t.name).join(", ")}`);
+  }
+  return JSON.stringify(tool, null, 2);
+}
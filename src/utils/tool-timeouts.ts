@@ -0,0 +1,213 @@
+/**
+ * Per-call deadlines for tool handlers.
+ *
+ * Every network-bound tool handler runs inside `withToolTimeout`, which races
+ * the handler against a deadline resolved from HARNESS_TOOL_TIMEOUTS. When the
+ * deadline fires the handler's signal is aborted (cancelling in-flight API
+ * calls that honour it) and the client gets a structured timeout error with
+ * the last progress notification, instead of a stdio request that never
+ * returns.
+ */
+
+import type { RequestHandlerExtra } from "@modelcontextprotocol/sdk/shared/protocol.js";
+import type { ServerRequest, ServerNotification } from "@modelcontextprotocol/sdk/types.js";
+import type { ToolResult } from "./response-formatter.js";
+import { checkToolOutput, isStrictSchemas } from "./strict-schemas.js";
+import { createLogger } from "./logger.js";
+import { beginInFlight } from "./live-diagnostics.js";
+import { newTraceId, runWithTraceId, withTraceMeta } from "./trace-id.js";
+
+const log = createLogger("tool-timeouts");
+
+type Extra = RequestHandlerExtra<ServerRequest, ServerNotification>;
+
+/**
+ * Timeouts in milliseconds keyed by `default`, a tool name (`harness_diagnose`),
+ * or `tool:resource_type` (`harness_get:execution_log`). 0 disables the deadline.
+ */
+export type ToolTimeouts = Record<string, number>;
+
+/** Built-in deadlines — log downloads and multi-call diagnoses get longer than plain CRUD. */
+export const DEFAULT_TOOL_TIMEOUTS: Readonly<ToolTimeouts> = {
+  default: 120_000,
+  "harness_get:execution_log": 300_000,
+  harness_diagnose: 300_000,
+};
+
+/** Headroom past `wait_timeout_seconds` so harness_execute's own wait timeout reports first. */
+const EXECUTE_WAIT_GRACE_MS = 60_000;
+
+/** Parse and validate HARNESS_TOOL_TIMEOUTS (JSON object of key → milliseconds). */
+export function parseToolTimeouts(json: string): ToolTimeouts {
+  let raw: unknown;
+  try {
+    raw = JSON.parse(json);
+  } catch (err) {
+    throw new Error(`HARNESS_TOOL_TIMEOUTS is not valid JSON: ${err instanceof Error ? err.message : String(err)}`);
+  }
+  if (typeof raw !== "object" || raw === null || Array.isArray(raw)) {
+    throw new Error("HARNESS_TOOL_TIMEOUTS must be a JSON object of tool name (or tool:resource_type, or default) to milliseconds.");
+  }
+  const timeouts: ToolTimeouts = {};
+  for (const [key, value] of Object.entries(raw as Record<string, unknown>)) {
+    if (typeof value !== "number" || !Number.isInteger(value) || value < 0) {
+      throw new Error(`HARNESS_TOOL_TIMEOUTS["${key}"] must be a non-negative integer number of milliseconds (0 disables the timeout).`);
+    }
+    timeouts[key] = value;
+  }
+  return timeouts;
+}
+
+/** Module-level timeouts: built-in defaults merged with HARNESS_TOOL_TIMEOUTS. */
+let _timeouts: ToolTimeouts = { ...DEFAULT_TOOL_TIMEOUTS };
+
+/**
+ * Configure tool deadlines. Call once at startup.
+ */
+export function configureToolTimeouts(overrides: ToolTimeouts = {}): void {
+  _timeouts = { ...DEFAULT_TOOL_TIMEOUTS, ...overrides };
+}
+
+/**
+ * Resolve the deadline for one call: `tool:resource_type`, then `tool`, then
+ * `default`. harness_execute calls that wait for completion are extended to
+ * cover the requested wait.
+ */
+export function resolveToolTimeout(tool: string, args: Record<string, unknown>): number {
+  const resourceType = typeof args.resource_type === "string" ? args.resource_type : undefined;
+  let timeout = (resourceType !== undefined ? _timeouts[`${tool}:${resourceType}`] : undefined)
+    ?? _timeouts[tool]
+    ?? _timeouts.default
+    ?? 0;
+  if (timeout > 0 && tool === "harness_execute" && args.wait === true) {
+    const waitSeconds = typeof args.wait_timeout_seconds === "number" ? args.wait_timeout_seconds : 600;
+    timeout = Math.max(timeout, waitSeconds * 1000 + EXECUTE_WAIT_GRACE_MS);
+  }
+  return timeout;
+}
+
+interface ProgressSnapshot {
+  progress: number;
+  total?: number;
+  message?: string;
+}
+
+/** Structured error returned when a tool call exceeds its deadline. */
+export function toolTimeoutResult(
+  tool: string,
+  resourceType: string | undefined,
+  timeoutMs: number,
+  elapsedMs: number,
+  lastProgress: ProgressSnapshot | undefined,
+  progressUpdates: number,
+): ToolResult {
+  const key = resourceType ? `${tool}:${resourceType}` : tool;
+  const payload = {
+    error: `${tool}${resourceType ? ` (${resourceType})` : ""} timed out after ${Math.round(timeoutMs / 1000)}s.`,
+    code: "TOOL_TIMEOUT",
+    tool,
+    ...(resourceType ? { resource_type: resourceType } : {}),
+    timeout_ms: timeoutMs,
+    elapsed_ms: elapsedMs,
+    partial_progress: lastProgress
+      ? { ...lastProgress, updates: progressUpdates }
+      : { updates: 0, message: "No progress was reported before the deadline." },
+    hint:
+      "Narrow the request (smaller page size, fewer steps or executions, a shorter time range) and retry, " +
+      `or raise the deadline with HARNESS_TOOL_TIMEOUTS={"${key}": <ms>}.`,
+  };
+  return {
+    content: [{ type: "text", text: JSON.stringify(payload) }],
+    isError: true,
+  };
+}
+
+/**
+ * Run a tool handler under its configured deadline. The handler receives an
+ * `extra` whose signal aborts on timeout (or when the client cancels) and whose
+ * progress notifications are recorded for the timeout error. In strict schema
+ * mode the result is also checked against the tool's declared output schema.
+ * The call is listed in the live diagnostics dump while it runs, and gets a
+ * trace ID that tags its log lines and API requests and is returned in the
+ * result's `_meta.trace_id`.
+ */
+export async function withToolTimeout<T>(
+  tool: string,
+  args: Record<string, unknown>,
+  extra: Extra,
+  handler: (extra: Extra) => Promise<T>,
+): Promise<T | ToolResult> {
+  const resourceType = typeof args.resource_type === "string" ? args.resource_type : undefined;
+  const traceId = newTraceId();
+  const end = beginInFlight("tool", tool, {
+    ...(resourceType ? { resource_type: resourceType } : {}),
+    request_id: extra.requestId,
+    trace_id: traceId,
+  });
+  try {
+    return await runWithTraceId(traceId, async () => {
+      const raw = await runWithDeadline(tool, args, extra, handler);
+      const result = isStrictSchemas() ? checkToolOutput(tool, raw) : raw;
+      if ((result as { isError?: unknown } | null)?.isError === true) {
+        log.info("Tool call returned an error", { tool, resourceType });
+      }
+      return withTraceMeta(result, traceId);
+    });
+  } finally {
+    end();
+  }
+}
+
+async function runWithDeadline<T>(
+  tool: string,
+  args: Record<string, unknown>,
+  extra: Extra,
+  handler: (extra: Extra) => Promise<T>,
+): Promise<T | ToolResult> {
+  const timeoutMs = resolveToolTimeout(tool, args);
+  if (timeoutMs <= 0) return handler(extra);
+
+  const controller = new AbortController();
+  const parentSignal = extra.signal;
+  const onParentAbort = () => controller.abort(parentSignal.reason);
+  if (parentSignal.aborted) controller.abort(parentSignal.reason);
+  else parentSignal.addEventListener("abort", onParentAbort, { once: true });
+
+  let lastProgress: ProgressSnapshot | undefined;
+  let progressUpdates = 0;
+  const guarded: Extra = {
+    ...extra,
+    signal: controller.signal,
+    sendNotification: async (notification) => {
+      if (notification.method === "notifications/progress") {
+        const { progress, total, message } = notification.params as ProgressSnapshot;
+        lastProgress = { progress, total, message };
+        progressUpdates++;
+      }
+      return extra.sendNotification(notification);
+    },
+  };
+
+  const startedAt = Date.now();
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  const deadline = new Promise<ToolResult>((resolve) => {
+    timer = setTimeout(() => {
+      const elapsedMs = Date.now() - startedAt;
+      const resourceType = typeof args.resource_type === "string" ? args.resource_type : undefined;
+      log.warn("Tool call timed out", { tool, resourceType, timeoutMs, elapsedMs, progressUpdates });
+      controller.abort(new DOMException(`${tool} timed out after ${timeoutMs}ms`, "TimeoutError"));
+      resolve(toolTimeoutResult(tool, resourceType, timeoutMs, elapsedMs, lastProgress, progressUpdates));
+    }, timeoutMs);
+    timer.unref?.();
+  });
+
+  const run = handler(guarded);
+  // The handler may still reject after the deadline wins — keep that off the unhandled-rejection path
+  run.catch(() => { /* reported via the timeout result */ });
+  try {
+    return await Promise.race<T | ToolResult>([run, deadline]);
+  } finally {
+    clearTimeout(timer);
+    parentSignal.removeEventListener("abort", onParentAbort);
+  }
+}
@@ -0,0 +1,75 @@
+/**
+ * Per-principal toolset visibility for multi-user HTTP mode
+ * (HARNESS_TOOLSET_PERMISSIONS).
+ *
+ * Maps a toolset to the Harness permission a caller needs to see it, e.g.
+ *
+ *   {"ccm": {"resourceType": "CCM_PERSPECTIVE", "permission": "ccm_perspective_view"}}
+ *
+ * When a session is initialized the permissions are checked at account scope
+ * with the session's own credentials, and toolsets the caller lacks are left
+ * out of that session's registry — so their resource types never appear in
+ * the session's `tools/list` schemas, `harness_describe`, or dispatch.
+ */
+
+import type { HarnessClient } from "../client/harness-client.js";
+import { createLogger } from "./logger.js";
+import { hasPermission, type PermissionCheck } from "./permission-cache.js";
+
+const log = createLogger("toolset-permissions");
+
+export type ToolsetPermissions = Record<string, Pick<PermissionCheck, "resourceType" | "permission">>;
+
+/**
+ * Parse and validate HARNESS_TOOLSET_PERMISSIONS. Toolset names are checked
+ * against `knownToolsets` when given.
+ */
+export function parseToolsetPermissions(json: string, knownToolsets?: readonly string[]): ToolsetPermissions {
+  let raw: unknown;
+  try {
+    raw = JSON.parse(json);
+  } catch (err) {
+    throw new Error(`HARNESS_TOOLSET_PERMISSIONS is not valid JSON: ${err instanceof Error ? err.message : String(err)}`);
+  }
+  if (typeof raw !== "object" || raw === null || Array.isArray(raw)) {
+    throw new Error('HARNESS_TOOLSET_PERMISSIONS must be a JSON object of toolset name to {"resourceType": ..., "permission": ...}.');
+  }
+  const permissions: ToolsetPermissions = {};
+  for (const [toolset, value] of Object.entries(raw as Record<string, unknown>)) {
+    if (knownToolsets && !knownToolsets.includes(toolset)) {
+      throw new Error(`HARNESS_TOOLSET_PERMISSIONS: unknown toolset "${toolset}". Valid toolsets: ${knownToolsets.join(", ")}`);
+    }
+    const entry = value as Record<string, unknown> | null;
+    if (
+      typeof entry !== "object" || entry === null ||
+      typeof entry.resourceType !== "string" || !entry.resourceType ||
+      typeof entry.permission !== "string" || !entry.permission
+    ) {
+      throw new Error(`HARNESS_TOOLSET_PERMISSIONS["${toolset}"] must be an object with non-empty "resourceType" and "permission" strings.`);
+    }
+    permissions[toolset] = { resourceType: entry.resourceType, permission: entry.permission };
+  }
+  return permissions;
+}
+
+/**
+ * Toolsets the caller behind `client` may not see. A failed check hides the
+ * toolset — visibility fails closed.
+ */
+export async function hiddenToolsetsFor(
+  client: HarnessClient,
+  permissions: ToolsetPermissions,
+  signal?: AbortSignal,
+): Promise<string[]> {
+  const decisions = await Promise.all(
+    Object.entries(permissions).map(async ([toolset, check]) => {
+      try {
+        return { toolset, visible: await hasPermission(client, check, signal) };
+      } catch (err) {
+        log.warn("Toolset permission check failed; hiding toolset", { toolset, error: String(err) });
+        return { toolset, visible: false };
+      }
+    }),
+  );
+  return decisions.filter((d) => !d.visible).map((d) => d.toolset);
+}
@@ -0,0 +1,42 @@
+/**
+ * Per-tool-call trace IDs.
+ *
+ * `withToolTimeout` gives every tool call a fresh trace ID and runs the
+ * handler inside it. While it runs, the ID is stamped on every log line and
+ * audit event and sent to Harness as `X-Harness-MCP-Trace-Id`; finally it is
+ * returned to the client in the tool result's `_meta.trace_id`, so a failed
+ * agent step can be matched to exactly the server log lines it produced.
+ */
+
+import { AsyncLocalStorage } from "node:async_hooks";
+import { randomUUID } from "node:crypto";
+
+/**
+ * Header carrying the trace ID on downstream Harness API requests. Not
+ * `X-Request-Id`: Harness echoes that one back as its own correlation ID.
+ */
+export const TRACE_ID_HEADER = "X-Harness-MCP-Trace-Id";
+
+const traceContext = new AsyncLocalStorage<string>();
+
+export function newTraceId(): string {
+  return randomUUID();
+}
+
+/** Trace ID of the tool call being handled, if any. */
+export function currentTraceId(): string | undefined {
+  return traceContext.getStore();
+}
+
+/** Run `fn` with `traceId` as the current trace ID. */
+export function runWithTraceId<T>(traceId: string, fn: () => T): T {
+  return traceContext.run(traceId, fn);
+}
+
+/** `result` with `_meta.trace_id` set, keeping any existing `_meta`. Non-object results are returned as-is. */
+export function withTraceMeta<T>(result: T, traceId: string): T {
+  if (result === null || typeof result !== "object" || Array.isArray(result)) return result;
+  const existing = (result as { _meta?: unknown })._meta;
+  const meta = existing !== null && typeof existing === "object" ? existing : {};
+  return { ...result, _meta: { ...meta, trace_id: traceId } };
+}
@@ -0,0 +1,71 @@
+/**
+ * Unix domain socket listener for the `socket` transport.
+ *
+ * Each connection speaks JSON-RPC with the same framing as stdio
+ * (newline-delimited or Content-Length, see stdio-transport.ts) — so local IDE integrations get a persistent server without TCP, and
+ * access is controlled by file permissions: the socket is created owner-only
+ * (0600).
+ */
+
+import { createConnection, createServer, type Server, type Socket } from "node:net";
+import { lstatSync, unlinkSync } from "node:fs";
+
+const SOCKET_MODE_UMASK = 0o177;
+
+/** Resolve true when something is accepting connections on `path`. */
+function isSocketLive(path: string): Promise<boolean> {
+  return new Promise((resolve) => {
+    const probe = createConnection(path);
+    probe.once("connect", () => { probe.destroy(); resolve(true); });
+    probe.once("error", () => resolve(false));
+  });
+}
+
+/**
+ * Clear a stale socket file left by a crashed server. Refuses to touch a live
+ * socket (another server is running) or anything that is not a socket.
+ */
+export async function prepareSocketPath(path: string): Promise<void> {
+  let stat;
+  try {
+    stat = lstatSync(path);
+  } catch {
+    return; // nothing there
+  }
+  if (!stat.isSocket()) {
+    throw new Error(`--socket-path ${path} exists and is not a socket — refusing to replace it.`);
+  }
+  if (await isSocketLive(path)) {
+    throw new Error(`--socket-path ${path} is already in use by another server.`);
+  }
+  unlinkSync(path);
+}
+
+/** Listen on `path` with owner-only permissions. */
+export async function listenOnUnixSocket(path: string, onConnection: (socket: Socket) => void): Promise<Server> {
+  await prepareSocketPath(path);
+  const server = createServer(onConnection);
+  // Create the socket file 0600 from the start — no window where others can connect
+  const previousUmask = process.umask(SOCKET_MODE_UMASK);
+  try {
+    await new Promise<void>((resolve, reject) => {
+      server.once("error", reject);
+      server.listen(path, () => {
+        server.off("error", reject);
+        resolve();
+      });
+    });
+  } finally {
+    process.umask(previousUmask);
+  }
+  return server;
+}
+
+/** Remove the socket file on shutdown; missing files are fine. */
+export function removeSocketPath(path: string): void {
+  try {
+    unlinkSync(path);
+  } catch {
+    // Already gone
+  }
+}
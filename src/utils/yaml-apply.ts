@@ -0,0 +1,253 @@
+/**
+ * `harness_execute(resource_type="yaml_bundle", action="apply")` — apply a
+ * bundle of Harness YAML documents in one call.
+ *
+ * Each document is classified by its top-level key (pipeline, template,
+ * connector, ...), the bundle is sorted into dependency order — connectors
+ * before the environments and services that use them, templates before the
+ * pipelines that reference them, pipelines before their input sets and
+ * triggers — and every entity is created, or updated when it already exists.
+ *
+ * Every write goes through registry dispatch, so each entity is validated
+ * and audited like a separate harness_create/harness_update. Unlike the
+ * service scaffold, a failure does not stop the run: the report lists the
+ * outcome of every document so a partial apply can be fixed and re-applied.
+ */
+
+import YAML from "yaml";
+import type { HarnessClient } from "../client/harness-client.js";
+import type { AuditContext } from "../audit/types.js";
+import type { Registry } from "../registry/index.js";
+import { HarnessApiError } from "./errors.js";
+import { asRecord, asString } from "./type-guards.js";
+
+interface KindSpec {
+  /** Registry resource type the document is applied through. */
+  resourceType: string;
+  /** Identifier and scope inputs for get/update, read from the document body. */
+  ids: (doc: Record<string, unknown>) => Record<string, unknown>;
+  /** Create/update body for the registry operation. */
+  body: (doc: Record<string, unknown>, yaml: string) => unknown;
+}
+
+function requireId(doc: Record<string, unknown>, field: string, kind: string): string {
+  const value = asString(doc[field]);
+  if (!value) throw new Error(`${kind} document has no ${field}.`);
+  return value;
+}
+
+/** NG entities (service, environment, infrastructure) take identifier, name, and the full YAML. */
+function ngEntityBody(doc: Record<string, unknown>, yaml: string, extra: string[] = []): Record<string, unknown> {
+  const body: Record<string, unknown> = { identifier: doc.identifier, name: doc.name ?? doc.identifier, yaml };
+  for (const field of ["description", "tags", "type", ...extra]) {
+    if (doc[field] !== undefined) body[field] = doc[field];
+  }
+  return body;
+}
+
+/**
+ * Supported top-level keys, in apply order. Templates can nest and
+ * environments can reference connectors, so order follows the usual
+ * reference direction; a bundle that breaks it surfaces as a per-entity error.
+ */
+export const YAML_BUNDLE_KINDS: Record<string, KindSpec> = {
+  connector: {
+    resourceType: "connector",
+    ids: (doc) => ({ connector_id: requireId(doc, "identifier", "connector") }),
+    body: (doc) => ({ connector: doc }),
+  },
+  environment: {
+    resourceType: "environment",
+    ids: (doc) => ({ environment_id: requireId(doc, "identifier", "environment") }),
+    body: (doc, yaml) => ngEntityBody(doc, yaml),
+  },
+  infrastructureDefinition: {
+    resourceType: "infrastructure",
+    ids: (doc) => ({
+      infrastructure_id: requireId(doc, "identifier", "infrastructureDefinition"),
+      environment_id: requireId(doc, "environmentRef", "infrastructureDefinition"),
+    }),
+    body: (doc, yaml) => ngEntityBody(doc, yaml, ["environmentRef", "deploymentType"]),
+  },
+  service: {
+    resourceType: "service",
+    ids: (doc) => ({ service_id: requireId(doc, "identifier", "service") }),
+    body: (doc, yaml) => ngEntityBody(doc, yaml),
+  },
+  template: {
+    resourceType: "template",
+    ids: (doc) => ({
+      template_id: requireId(doc, "identifier", "template"),
+      version_label: requireId(doc, "versionLabel", "template"),
+    }),
+    body: (_doc, yaml) => yaml,
+  },
+  pipeline: {
+    resourceType: "pipeline",
+    ids: (doc) => ({ pipeline_id: requireId(doc, "identifier", "pipeline") }),
+    body: (_doc, yaml) => yaml,
+  },
+  inputSet: {
+    resourceType: "input_set",
+    ids: (doc) => {
+      const pipelineId = asString(asRecord(doc.pipeline)?.identifier);
+      if (!pipelineId) throw new Error("inputSet document has no pipeline.identifier.");
+      return { input_set_id: requireId(doc, "identifier", "inputSet"), pipeline_id: pipelineId };
+    },
+    body: (_doc, yaml) => yaml,
+  },
+  overlayInputSet: {
+    resourceType: "input_set",
+    ids: (doc) => ({
+      input_set_id: requireId(doc, "identifier", "overlayInputSet"),
+      pipeline_id: requireId(doc, "pipelineIdentifier", "overlayInputSet"),
+    }),
+    body: (_doc, yaml) => yaml,
+  },
+  trigger: {
+    resourceType: "trigger",
+    ids: (doc) => ({
+      trigger_id: requireId(doc, "identifier", "trigger"),
+      pipeline_id: requireId(doc, "pipelineIdentifier", "trigger"),
+    }),
+    body: (doc) => ({ trigger: doc }),
+  },
+};
+
+const KIND_ORDER = Object.keys(YAML_BUNDLE_KINDS);
+
+export interface BundleDocument {
+  index: number;
+  kind: string;
+  identifier?: string;
+  doc: Record<string, unknown>;
+  yaml: string;
+  error?: string;
+}
+
+/**
+ * Split the bundle into documents. `documents` may be one multi-document
+ * YAML string (`---` separated) or an array of YAML strings / objects.
+ */
+export function parseYamlBundle(documents: unknown): BundleDocument[] {
+  const texts: string[] = [];
+  if (typeof documents === "string") {
+    for (const d of YAML.parseAllDocuments(documents)) {
+      if (d.errors.length > 0) throw new Error(`Invalid YAML in bundle: ${d.errors[0]!.message}`);
+      if (d.contents !== null) texts.push(d.toString());
+    }
+  } else if (Array.isArray(documents)) {
+    for (const d of documents) texts.push(typeof d === "string" ? d : YAML.stringify(d));
+  } else {
+    throw new Error("yaml_bundle apply requires documents: a multi-document YAML string or an array of YAML documents.");
+  }
+  if (texts.length === 0) throw new Error("The YAML bundle has no documents.");
+
+  return texts.map((yaml, index) => {
+    let parsed: unknown;
+    try {
+      parsed = YAML.parse(yaml);
+    } catch (err) {
+      return { index, kind: "unknown", doc: {}, yaml, error: `Invalid YAML: ${err instanceof Error ? err.message : String(err)}` };
+    }
+    const root = asRecord(parsed) ?? {};
+    const kind = Object.keys(root).find((k) => k in YAML_BUNDLE_KINDS);
+    if (!kind) {
+      return { index, kind: Object.keys(root)[0] ?? "unknown", doc: {}, yaml, error: `Unsupported document type. Supported top-level keys: ${KIND_ORDER.join(", ")}.` };
+    }
+    const doc = asRecord(root[kind]) ?? {};
+    return { index, kind, identifier: asString(doc.identifier), doc, yaml };
+  });
+}
+
+/** Documents in apply order; stable within a kind so bundle order is kept. */
+export function orderBundle(docs: BundleDocument[]): BundleDocument[] {
+  const rank = (d: BundleDocument) => (d.kind in YAML_BUNDLE_KINDS ? KIND_ORDER.indexOf(d.kind) : KIND_ORDER.length);
+  return [...docs].sort((a, b) => rank(a) - rank(b) || a.index - b.index);
+}
+
+function isNotFound(err: unknown): boolean {
+  if (err instanceof HarnessApiError && err.statusCode === 404) return true;
+  const message = err instanceof Error ? err.message : String(err);
+  return /not found|does not exist|ENTITY_NOT_FOUND/i.test(message);
+}
+
+/**
+ * Apply the bundle. With `dry_run`, documents are classified and ordered and
+ * existence is checked, but nothing is written.
+ */
+export async function applyYamlBundle(
+  registry: Registry,
+  client: HarnessClient,
+  input: Record<string, unknown>,
+  auditCtx: AuditContext,
+  signal?: AbortSignal,
+): Promise<Record<string, unknown>> {
+  const docs = orderBundle(parseYamlBundle(input.documents ?? input.body));
+  const dryRun = input.dry_run === true;
+  const createOnly = input.mode === "create_only";
+  const results: Array<Record<string, unknown>> = [];
+
+  for (const entry of docs) {
+    const spec = YAML_BUNDLE_KINDS[entry.kind];
+    const base = { document: entry.index, kind: entry.kind, resource_type: spec?.resourceType, identifier: entry.identifier };
+    if (entry.error || !spec) {
+      results.push({ ...base, status: "invalid", error: entry.error });
+      continue;
+    }
+    try {
+      // The document's own orgIdentifier/projectIdentifier win; one with
+      // neither follows the call's scope (resource_scope, org_id, project_id)
+      const docOrg = asString(entry.doc.orgIdentifier);
+      const docProject = asString(entry.doc.projectIdentifier);
+      const scope = docOrg
+        ? { org_id: docOrg, ...(docProject ? { project_id: docProject } : {}), resource_scope: docProject ? "project" : "org" }
+        : {
+            ...(asString(input.org_id) ? { org_id: input.org_id } : {}),
+            ...(asString(input.project_id) ? { project_id: input.project_id } : {}),
+            ...(asString(input.resource_scope) ? { resource_scope: input.resource_scope } : {}),
+          };
+      const ids = { ...scope, ...spec.ids(entry.doc) };
+
+      let exists = true;
+      try {
+        await registry.dispatch(client, spec.resourceType, "get", ids, signal);
+      } catch (err) {
+        if (!isNotFound(err)) throw err;
+        exists = false;
+      }
+
+      if (exists && createOnly) {
+        results.push({ ...base, status: "skipped", reason: "Already exists (mode=create_only)." });
+        continue;
+      }
+      const op = exists ? "update" : "create";
+      if (dryRun) {
+        results.push({ ...base, status: `would_${op}` });
+        continue;
+      }
+      const result = await registry.dispatch(
+        client,
+        spec.resourceType,
+        op,
+        { ...ids, body: spec.body(entry.doc, entry.yaml) },
+        { ...auditCtx, resource_id: entry.identifier },
+        signal,
+      );
+      const link = asString(asRecord(result)?.openInHarness);
+      results.push({ ...base, status: exists ? "updated" : "created", ...(link ? { openInHarness: link } : {}) });
+    } catch (err) {
+      results.push({ ...base, status: "failed", error: err instanceof Error ? err.message : String(err) });
+    }
+  }
+
+  const summary: Record<string, number> = {};
+  for (const r of results) summary[r.status as string] = (summary[r.status as string] ?? 0) + 1;
+  return {
+    applied: !dryRun,
+    ...(dryRun ? { dry_run: true } : {}),
+    documents: docs.length,
+    summary,
+    results,
+  };
+}
@@ -0,0 +1,160 @@
+/**
+ * Line-based unified diff for YAML documents.
+ *
+ * Used by the pipeline diff handler to compare two revisions of a remote
+ * pipeline. Deliberately dependency-free: an LCS table over lines is more than
+ * fast enough for pipeline-sized files, and output follows `diff -u` so agents
+ * and humans can read it without extra tooling.
+ */
+
+export interface UnifiedDiffOptions {
+  /** Label for the "---" header line. Default: "a". */
+  fromLabel?: string;
+  /** Label for the "+++" header line. Default: "b". */
+  toLabel?: string;
+  /** Unchanged lines to show around each change. Default: 3. */
+  context?: number;
+}
+
+export interface UnifiedDiffResult {
+  /** Unified diff text; empty string when the inputs are identical. */
+  diff: string;
+  additions: number;
+  deletions: number;
+  hunks: number;
+  /**
+   * Set when the changed region was too large to diff line by line. `diff`
+   * is then empty, and the counts cover the whole changed region.
+   */
+  summary?: string;
+}
+
+type Op = { kind: " " | "-" | "+"; line: string; aIndex: number; bIndex: number };
+
+/**
+ * Cap on LCS table cells (4 bytes each, so ~16 MB). Past it the changed
+ * region is reported as a summary instead of a line diff.
+ */
+const MAX_LCS_CELLS = 4_000_000;
+
+interface ChangedRegion {
+  start: number;
+  deleted: number;
+  added: number;
+}
+
+function splitLines(text: string): string[] {
+  const normalized = text.replace(/\r\n/g, "\n");
+  const lines = normalized.split("\n");
+  // A trailing newline should not count as an extra empty line
+  if (lines.length > 0 && lines[lines.length - 1] === "") lines.pop();
+  return lines;
+}
+
+/** Line ops for `a` → `b`, or the changed region when it is too large to diff. */
+function computeOps(a: string[], b: string[]): Op[] | ChangedRegion {
+  // Trim common prefix/suffix first — revisions usually differ in a small region
+  let start = 0;
+  while (start < a.length && start < b.length && a[start] === b[start]) start++;
+  let endA = a.length;
+  let endB = b.length;
+  while (endA > start && endB > start && a[endA - 1] === b[endB - 1]) {
+    endA--;
+    endB--;
+  }
+
+  const midA = a.slice(start, endA);
+  const midB = b.slice(start, endB);
+  const n = midA.length;
+  const m = midB.length;
+  if ((n + 1) * (m + 1) > MAX_LCS_CELLS) return { start, deleted: n, added: m };
+
+  const ops: Op[] = [];
+  for (let i = 0; i < start; i++) ops.push({ kind: " ", line: a[i]!, aIndex: i, bIndex: i });
+  const width = m + 1;
+  const table = new Uint32Array((n + 1) * width);
+  for (let i = n - 1; i >= 0; i--) {
+    for (let j = m - 1; j >= 0; j--) {
+      table[i * width + j] = midA[i] === midB[j]
+        ? table[(i + 1) * width + j + 1]! + 1
+        : Math.max(table[(i + 1) * width + j]!, table[i * width + j + 1]!);
+    }
+  }
+  let i = 0;
+  let j = 0;
+  while (i < n || j < m) {
+    if (i < n && j < m && midA[i] === midB[j]) {
+      ops.push({ kind: " ", line: midA[i]!, aIndex: start + i, bIndex: start + j });
+      i++;
+      j++;
+    } else if (j < m && (i >= n || table[i * width + j + 1]! >= table[(i + 1) * width + j]!)) {
+      ops.push({ kind: "+", line: midB[j]!, aIndex: start + i, bIndex: start + j });
+      j++;
+    } else {
+      ops.push({ kind: "-", line: midA[i]!, aIndex: start + i, bIndex: start + j });
+      i++;
+    }
+  }
+
+  const offsetB = b.length - a.length;
+  for (let i = endA; i < a.length; i++) ops.push({ kind: " ", line: a[i]!, aIndex: i, bIndex: i + offsetB });
+  return ops;
+}
+
+function formatRange(start: number, count: number): string {
+  // diff -u convention: an empty range is reported at the line before it
+  if (count === 0) return `${start},0`;
+  if (count === 1) return `${start + 1}`;
+  return `${start + 1},${count}`;
+}
+
+/**
+ * Produce a unified diff between two texts. Identical inputs yield an empty
+ * diff with zero hunks.
+ */
+export function unifiedDiff(from: string, to: string, options: UnifiedDiffOptions = {}): UnifiedDiffResult {
+  const context = Math.max(0, options.context ?? 3);
+  const ops = computeOps(splitLines(from), splitLines(to));
+  if (!Array.isArray(ops)) {
+    return {
+      diff: "",
+      additions: ops.added,
+      deletions: ops.deleted,
+      hunks: 1,
+      summary: `Changed from line ${ops.start + 1}: ${ops.deleted} lines replaced by ${ops.added}. The changed region is too large to diff line by line.`,
+    };
+  }
+
+  const changeIdx: number[] = [];
+  ops.forEach((op, idx) => {
+    if (op.kind !== " ") changeIdx.push(idx);
+  });
+  if (changeIdx.length === 0) return { diff: "", additions: 0, deletions: 0, hunks: 0 };
+
+  // Group changes whose context windows overlap into a single hunk
+  const ranges: Array<[number, number]> = [];
+  for (const idx of changeIdx) {
+    const lo = Math.max(0, idx - context);
+    const hi = Math.min(ops.length - 1, idx + context);
+    const last = ranges[ranges.length - 1];
+    if (last && lo <= last[1] + 1) last[1] = Math.max(last[1], hi);
+    else ranges.push([lo, hi]);
+  }
+
+  const out: string[] = [`--- ${options.fromLabel ?? "a"}`, `+++ ${options.toLabel ?? "b"}`];
+  let additions = 0;
+  let deletions = 0;
+  for (const [lo, hi] of ranges) {
+    const slice = ops.slice(lo, hi + 1);
+    const aCount = slice.filter((op) => op.kind !== "+").length;
+    const bCount = slice.filter((op) => op.kind !== "-").length;
+    out.push(`@@ -${formatRange(slice[0]!.aIndex, aCount)} +${formatRange(slice[0]!.bIndex, bCount)} @@`);
+    for (const op of slice) {
+      if (op.kind === "+") additions++;
+      if (op.kind === "-") deletions++;
+      out.push(`${op.kind}${op.line}`);
+    }
+  }
+
+  return { diff: out.join("\n") + "\n", additions, deletions, hunks: ranges.length };
+}
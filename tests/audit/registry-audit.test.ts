@@ -2,6 +2,7 @@ import { describe, it, expect, vi } from "vitest";
 import { Registry } from "../../src/registry/index.js";
 import { AuditManager } from "../../src/audit/manager.js";
 import type { AuditEvent, AuditSink } from "../../src/audit/types.js";
+import { HarnessApiError, tagCorrelationId } from "../../src/utils/errors.js";
 
 function makeConfig(overrides = {}) {
   return {
@@ -288,4 +289,25 @@ describe("Registry audit emission", () => {
     expect(sink.events).toHaveLength(1);
     expect(sink.events[0]!.tool).toBe("harness_list");
   });
+
+  it("records the response correlation ID on success and failure", async () => {
+    const sink = collectingSink();
+    const auditManager = new AuditManager();
+    auditManager.addSink(sink);
+    const registry = new Registry(makeConfig() as any, { auditManager });
+
+    const request = vi.fn()
+      .mockResolvedValueOnce(tagCorrelationId({ status: "SUCCESS", data: { content: [] } }, "corr-ok"))
+      .mockRejectedValueOnce(new HarnessApiError("Forbidden", 403, "NOT_AUTHORIZED", "corr-403"));
+    const client = { request, account: "acct1" };
+
+    await registry.dispatch(client as any, "pipeline", "list", {}, { tool: "harness_list" });
+    await expect(registry.dispatch(client as any, "pipeline", "list", {}, { tool: "harness_list" })).rejects.toThrow("Forbidden");
+
+    expect(sink.events.map((e) => [e.outcome, e.correlation_id])).toEqual([
+      ["success", "corr-ok"],
+      ["error", "corr-403"],
+    ]);
+    expect(sink.events[1]!.http_status).toBe(403);
+  });
 });
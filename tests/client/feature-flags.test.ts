@@ -0,0 +1,111 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+import { FeatureFlagGate, type FeatureFlagEvaluator } from "../../src/client/feature-flags.js";
+import { Registry } from "../../src/registry/index.js";
+import type { ToolsetDefinition } from "../../src/registry/types.js";
+import type { Config } from "../../src/config.js";
+
+const BASE = "https://config.ff.harness.io/api/1.0";
+
+function token(claims: Record<string, unknown>): string {
+  return `header.${Buffer.from(JSON.stringify(claims)).toString("base64url")}.sig`;
+}
+
+const BOOLEAN_VARIATIONS = [{ identifier: "true", value: "true" }, { identifier: "false", value: "false" }];
+
+function flag(feature: string, overrides: Record<string, unknown> = {}) {
+  return { feature, kind: "boolean", state: "on", variations: BOOLEAN_VARIATIONS, offVariation: "false", defaultServe: { variation: "false" }, ...overrides };
+}
+
+function mockFfApi(flags: unknown[], segments: unknown[] = []) {
+  return vi.spyOn(globalThis, "fetch").mockImplementation(async (input) => {
+    const url = String(input);
+    if (url === `${BASE}/client/auth`) return Response.json({ authToken: token({ environment: "env-1", clusterIdentifier: "2" }) });
+    if (url === `${BASE}/client/env/env-1/feature-configs?cluster=2`) return Response.json(flags);
+    if (url === `${BASE}/client/env/env-1/target-segments?cluster=2`) return Response.json(segments);
+    return new Response("not found", { status: 404 });
+  });
+}
+
+describe("FeatureFlagGate", () => {
+  afterEach(() => {
+    vi.restoreAllMocks();
+  });
+
+  it("evaluates state, target mappings, rules, and segments for the account", async () => {
+    mockFfApi([
+      flag("MCP_OFF", { state: "off", defaultServe: { variation: "true" } }),
+      flag("MCP_EVERYONE", { defaultServe: { variation: "true" } }),
+      flag("MCP_TARGETED", { variationToTargetMap: [{ variation: "true", targets: [{ identifier: "acct-a" }] }] }),
+      flag("MCP_BETA", { rules: [{ priority: 1, clauses: [{ attribute: "", op: "segmentMatch", values: ["beta"] }], serve: { variation: "true" } }] }),
+      flag("MCP_PREFIX", { rules: [{ priority: 1, clauses: [{ attribute: "identifier", op: "starts_with", values: ["int-"] }], serve: { variation: "true" } }] }),
+    ], [{ identifier: "beta", included: [{ identifier: "acct-b" }], excluded: [{ identifier: "acct-a" }], rules: [] }]);
+
+    const gate = new FeatureFlagGate("sdk-key", BASE, 60_000);
+    expect(gate.ready).toBe(false);
+    await gate.refresh();
+    expect(gate.ready).toBe(true);
+
+    expect(gate.isEnabled("MCP_OFF", "acct-a")).toBe(false);
+    expect(gate.isEnabled("MCP_EVERYONE", "acct-a")).toBe(true);
+    expect(gate.isEnabled("MCP_TARGETED", "acct-a")).toBe(true);
+    expect(gate.isEnabled("MCP_TARGETED", "acct-b")).toBe(false);
+    expect(gate.isEnabled("MCP_BETA", "acct-b")).toBe(true);
+    expect(gate.isEnabled("MCP_BETA", "acct-a")).toBe(false);
+    expect(gate.isEnabled("MCP_PREFIX", "int-123")).toBe(true);
+    expect(gate.isEnabled("MCP_UNKNOWN", "acct-a")).toBe(false);
+  });
+
+  it("keeps the last known flags when a poll fails", async () => {
+    const fetchSpy = mockFfApi([flag("MCP_EVERYONE", { defaultServe: { variation: "true" } })]);
+    const gate = new FeatureFlagGate("sdk-key", BASE, 60_000);
+    await gate.refresh();
+
+    fetchSpy.mockResolvedValue(new Response("unavailable", { status: 503 }));
+    await gate.refresh();
+    expect(gate.isEnabled("MCP_EVERYONE", "acct-a")).toBe(true);
+  });
+});
+
+describe("Registry feature flag gating", () => {
+  const config = {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "acct-a",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_TOOLSETS: "pipelines,preview",
+    LOG_LEVEL: "error",
+  } as Config;
+  const resource = (resourceType: string, featureFlag?: string) => ({
+    resourceType,
+    displayName: resourceType,
+    description: resourceType,
+    toolset: "preview",
+    scope: "project" as const,
+    identifierFields: ["id"],
+    operations: { list: { method: "GET" as const, path: `/preview/${resourceType}` } },
+    featureFlag,
+  });
+  const preview: ToolsetDefinition = {
+    name: "preview",
+    displayName: "Preview",
+    description: "Flag-gated preview resources",
+    resources: [resource("preview_widget"), resource("preview_gadget", "MCP_GADGET")],
+  };
+
+  it("hides gated resource types and toolsets whose flag is off for the account", () => {
+    const flags: FeatureFlagEvaluator = { isEnabled: (name, accountId) => name === "MCP_GADGET" && accountId === "acct-a" };
+    const on = new Registry(config, { additionalToolsets: [preview], featureFlags: flags });
+    expect(on.getAllResourceTypes()).toEqual(expect.arrayContaining(["preview_widget", "preview_gadget"]));
+
+    const off = new Registry({ ...config, HARNESS_ACCOUNT_ID: "acct-b" }, { additionalToolsets: [preview], featureFlags: flags });
+    expect(off.getAllResourceTypes()).toContain("preview_widget");
+    expect(off.getAllResourceTypes()).not.toContain("preview_gadget");
+
+    const gatedToolset = new Registry(config, { additionalToolsets: [{ ...preview, featureFlag: "MCP_PREVIEW" }], featureFlags: flags });
+    expect(gatedToolset.getAllResourceTypes()).not.toContain("preview_widget");
+  });
+
+  it("hides gated definitions when the integration is not configured", () => {
+    const registry = new Registry(config, { additionalToolsets: [preview] });
+    expect(registry.getAllResourceTypes()).not.toContain("preview_gadget");
+  });
+});
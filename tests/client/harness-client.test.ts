@@ -1,7 +1,10 @@
 import { describe, it, expect, vi, beforeEach, afterEach } from "vitest";
 import { HarnessClient } from "../../src/client/harness-client.js";
-import { HarnessApiError } from "../../src/utils/errors.js";
+import { HarnessApiError, getCorrelationId } from "../../src/utils/errors.js";
 import type { Config } from "../../src/config.js";
+import { runWithPrincipal } from "../../src/utils/principal.js";
+import { runWithTraceId } from "../../src/utils/trace-id.js";
+import { ApiBudget, ApiBudgetExceededError, runWithApiBudget } from "../../src/utils/api-budget.js";
 
 function makeConfig(overrides: Partial<Config> = {}): Config {
   return {
@@ -355,6 +358,41 @@ describe("HarnessClient", () => {
     });
   });
 
+  describe("request — route map", () => {
+    it("rewrites gateway prefixes with the SMP preset", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({ data: "ok" }), { status: 200 }));
+      const client = new HarnessClient(makeConfig({ HARNESS_BASE_URL: "https://harness.example.com", HARNESS_DEPLOYMENT: "smp" }));
+
+      await client.request({ method: "GET", path: "/ng/api/projects" });
+
+      const url = new URL(fetchSpy.mock.calls[0][0] as string);
+      expect(url.origin).toBe("https://harness.example.com");
+      expect(url.pathname).toBe("/gateway/ng/api/projects");
+      expect(url.searchParams.get("accountIdentifier")).toBe("test-account");
+    });
+
+    it("applies HARNESS_ROUTE_MAP port overrides", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({ data: "ok" }), { status: 200 }));
+      const client = new HarnessClient(makeConfig({ HARNESS_ROUTE_MAP: '{"/code/api":{"port":3443}}' }));
+
+      await client.request({ method: "GET", path: "/code/api/v1/repos" });
+
+      const url = new URL(fetchSpy.mock.calls[0][0] as string);
+      expect(url.host).toBe("app.harness.io:3443");
+      expect(url.pathname).toBe("/code/api/v1/repos");
+    });
+
+    it("does not route requests with a per-request base URL", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({ data: "ok" }), { status: 200 }));
+      const client = new HarnessClient(makeConfig({ HARNESS_ROUTE_MAP: '{"/internal/api":"/gateway/internal/api"}' }));
+
+      await client.request({ method: "GET", path: "/internal/api/items", baseUrl: "https://api.split.io" });
+
+      const url = new URL(fetchSpy.mock.calls[0][0] as string);
+      expect(url.pathname).toBe("/internal/api/items");
+    });
+  });
+
   describe("request — headers", () => {
     it("sets x-api-key and Harness-Account headers", async () => {
       fetchSpy.mockResolvedValue(new Response(JSON.stringify({}), { status: 200 }));
@@ -413,6 +451,68 @@ describe("HarnessClient", () => {
       expect(headers["Harness-Account"]).toBeUndefined();
     });
 
+    it("adds X-Principal headers for requests made on behalf of a JWT principal", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({}), { status: 200 }));
+      const client = new HarnessClient(makeConfig());
+
+      await runWithPrincipal({ id: "user-42", type: "USER", email: "dev@example.com" }, () => client.request({ path: "/test" }));
+      await client.request({ path: "/test" });
+
+      const withPrincipal = fetchSpy.mock.calls[0][1]?.headers as Record<string, string>;
+      expect(withPrincipal["X-Principal-Id"]).toBe("user-42");
+      expect(withPrincipal["X-Principal-Type"]).toBe("USER");
+      expect(withPrincipal["X-Principal-Email"]).toBe("dev@example.com");
+      const withoutPrincipal = fetchSpy.mock.calls[1][1]?.headers as Record<string, string>;
+      expect(withoutPrincipal["X-Principal-Id"]).toBeUndefined();
+    });
+
+    it("does not send X-Principal headers to the FME API", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({}), { status: 200 }));
+      const client = new HarnessClient(makeConfig());
+
+      await runWithPrincipal({ id: "user-42", type: "USER", email: "dev@example.com" }, () =>
+        client.request({ path: "/internal/api/v2/workspaces", product: "fme", baseUrl: "https://api.split.io", headers: { Authorization: "Bearer fme-admin-key" } }),
+      );
+
+      const headers = fetchSpy.mock.calls[0][1]?.headers as Record<string, string>;
+      expect(Object.keys(headers).filter((h) => h.toLowerCase().startsWith("x-principal-"))).toEqual([]);
+    });
+
+    it("sends the current tool call's trace ID", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({}), { status: 200 }));
+      const client = new HarnessClient(makeConfig());
+
+      await runWithTraceId("trace-1", () => client.request({ path: "/test" }));
+      await client.request({ path: "/test" });
+
+      expect((fetchSpy.mock.calls[0][1]?.headers as Record<string, string>)["X-Harness-MCP-Trace-Id"]).toBe("trace-1");
+      expect((fetchSpy.mock.calls[1][1]?.headers as Record<string, string>)["X-Harness-MCP-Trace-Id"]).toBeUndefined();
+    });
+
+    it("does not send the trace ID to the FME API", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({}), { status: 200 }));
+      const client = new HarnessClient(makeConfig());
+
+      await runWithTraceId("trace-1", () =>
+        client.request({ path: "/internal/api/v2/workspaces", product: "fme", baseUrl: "https://api.split.io", headers: { Authorization: "Bearer fme-admin-key" } }),
+      );
+
+      expect((fetchSpy.mock.calls[0][1]?.headers as Record<string, string>)["X-Harness-MCP-Trace-Id"]).toBeUndefined();
+    });
+
+    it("counts requests against the current API budget and refuses them once it is spent", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({}), { status: 200 }));
+      const client = new HarnessClient(makeConfig());
+      const budget = new ApiBudget({ perSession: 1, perHour: 0 });
+
+      await runWithApiBudget(budget, () => client.request({ path: "/test" }));
+      await expect(runWithApiBudget(budget, () => client.request({ path: "/test" }))).rejects.toBeInstanceOf(ApiBudgetExceededError);
+      await expect(runWithApiBudget(budget, () => client.requestStream({ path: "/test" }))).rejects.toBeInstanceOf(ApiBudgetExceededError);
+      await client.request({ path: "/test" });
+
+      expect(fetchSpy).toHaveBeenCalledTimes(2);
+    });
+
     it("uses resolved account ID for Harness-Account header", async () => {
       fetchSpy.mockResolvedValue(new Response(JSON.stringify({}), { status: 200 }));
       const client = new HarnessClient(makeConfig());
@@ -503,6 +603,18 @@ describe("HarnessClient", () => {
       const result = await client.request<{ data: { id: string } }>({ path: "/test" });
       expect(result.data.id).toBe("p1");
     });
+
+    it("tags the result with the response correlation ID without serializing it", async () => {
+      fetchSpy.mockResolvedValue(new Response(JSON.stringify({ data: { id: "p1" } }), {
+        status: 200,
+        headers: { "X-Harness-Correlation-Id": "corr-ok" },
+      }));
+      const client = new HarnessClient(makeConfig());
+
+      const result = await client.request({ path: "/test" });
+      expect(getCorrelationId(result)).toBe("corr-ok");
+      expect(JSON.stringify(result)).toBe('{"data":{"id":"p1"}}');
+    });
   });
 
   describe("request — error handling", () => {
@@ -526,6 +638,16 @@ describe("HarnessClient", () => {
       }
     });
 
+    it("falls back to the correlation ID response header when the body has none", async () => {
+      fetchSpy.mockResolvedValue(new Response(
+        JSON.stringify({ message: "Forbidden", code: "NOT_AUTHORIZED" }),
+        { status: 403, headers: { "x-request-id": "req-42" } },
+      ));
+      const client = new HarnessClient(makeConfig({ HARNESS_MAX_RETRIES: 0 }));
+
+      await expect(client.request({ path: "/test" })).rejects.toMatchObject({ statusCode: 403, correlationId: "req-42" });
+    });
+
     it.each([
       {
         path: "/chaos/manager/api/rest/v2/experiment",
@@ -586,6 +708,17 @@ describe("HarnessClient", () => {
       await expect(client.request({ path: "/test" })).rejects.toThrow(/HTTP 502: Bad Gateway/);
     });
 
+    it("appends guidance for known error signatures", async () => {
+      fetchSpy.mockResolvedValue(new Response(
+        JSON.stringify({ status: "ERROR", code: "ENTITY_NOT_FOUND", message: "Pipeline [deploy] not found" }),
+        { status: 404 },
+      ));
+      const client = new HarnessClient(makeConfig({ HARNESS_MAX_RETRIES: 0 }));
+
+      await expect(client.request({ path: "/pipeline/api/pipelines/deploy" }))
+        .rejects.toThrow(/Pipeline \[deploy\] not found\n\nGuidance: .*pass branch/i);
+    });
+
     it("returns actionable message for HTML 403 (proxy/WAF block)", async () => {
       const html = '<!doctype html><meta charset="utf-8"><meta name=viewport content="width=device-width, initial-scale=1"><title>403</title>403 Forbidden';
       fetchSpy.mockResolvedValue(new Response(html, { status: 403 }));
@@ -0,0 +1,100 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createServer, type Http2Server } from "node:http2";
+import type { AddressInfo } from "node:net";
+import { Http2Pool, closeHttp2Pools } from "../../src/client/http2-pool.js";
+import { resetServerMetrics, serverStatus } from "../../src/utils/server-metrics.js";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+
+describe("Http2Pool", () => {
+  let server: Http2Server;
+  let origin: string;
+  let pool: Http2Pool;
+  let sessions = 0;
+  let closedSessions = 0;
+
+  beforeEach(async () => {
+    resetServerMetrics();
+    sessions = 0;
+    closedSessions = 0;
+    // h2c server — the pool speaks cleartext HTTP/2 to it with `cleartext: true`
+    server = createServer();
+    server.on("session", (session) => {
+      sessions++;
+      session.on("close", () => { closedSessions++; });
+    });
+    server.on("stream", (stream, headers) => {
+      if (headers[":path"] === "/empty") {
+        stream.respond({ ":status": 204 });
+        stream.end();
+        return;
+      }
+      let body = "";
+      stream.setEncoding("utf-8");
+      stream.on("data", (chunk: string) => { body += chunk; });
+      stream.on("end", () => {
+        stream.respond({ ":status": 200, "content-type": "application/json", "x-request-id": "abc" });
+        stream.end(JSON.stringify({ method: headers[":method"], path: headers[":path"], auth: headers["x-api-key"], body }));
+      });
+    });
+    await new Promise<void>((resolve) => server.listen(0, "127.0.0.1", resolve));
+    origin = `http://127.0.0.1:${(server.address() as AddressInfo).port}`;
+    pool = new Http2Pool({ cleartext: true });
+  });
+
+  afterEach(async () => {
+    pool.close();
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+    vi.restoreAllMocks();
+    resetServerMetrics();
+  });
+
+  it("sends requests over one reused session and returns a fetch Response", async () => {
+    const first = await pool.fetch(`${origin}/ng/api/projects?page=0`, { method: "GET", headers: { "x-api-key": "pat.x", Connection: "keep-alive" } });
+    expect(first.status).toBe(200);
+    expect(first.headers.get("x-request-id")).toBe("abc");
+    expect(await first.json()).toEqual({ method: "GET", path: "/ng/api/projects?page=0", auth: "pat.x", body: "" });
+
+    const second = await pool.fetch(`${origin}/pipeline/api/run`, { method: "POST", headers: {}, body: "{\"a\":1}" });
+    expect(await second.json()).toMatchObject({ method: "POST", body: "{\"a\":1}" });
+    expect(sessions).toBe(1);
+
+    const empty = await pool.fetch(`${origin}/empty`, { method: "DELETE", headers: {} });
+    expect(empty.status).toBe(204);
+
+    const config = { HARNESS_API_KEY: "pat.a.b.c", HARNESS_ACCOUNT_ID: "a", HARNESS_BASE_URL: "https://app.harness.io", HARNESS_TOOLSETS: "logs" } as Config;
+    expect(serverStatus(new Registry(config), config).connections).toEqual({
+      http2_sessions_opened: 1,
+      http2_sessions_open: 1,
+      http2_requests: 3,
+      http2_session_reuses: 2,
+      http1_requests: 0,
+    });
+  });
+
+  it("closes open sessions on shutdown", async () => {
+    await (await pool.fetch(`${origin}/a`, { method: "GET", headers: {} })).text();
+    const other = new Http2Pool({ cleartext: true });
+    await (await other.fetch(`${origin}/b`, { method: "GET", headers: {} })).text();
+    expect(sessions).toBe(2);
+
+    closeHttp2Pools();
+    await expect.poll(() => closedSessions).toBe(2);
+  });
+
+  it("falls back to fetch for http:// origins unless cleartext is allowed", async () => {
+    const fetchSpy = vi.spyOn(globalThis, "fetch").mockResolvedValue(new Response("{}", { status: 200 }));
+    const strict = new Http2Pool();
+    await strict.fetch(`${origin}/x`, { method: "GET", headers: {} });
+    expect(fetchSpy).toHaveBeenCalledWith(`${origin}/x`, expect.objectContaining({ method: "GET" }));
+    expect(sessions).toBe(0);
+  });
+
+  it("falls back to fetch when the origin does not speak HTTP/2", async () => {
+    const fetchSpy = vi.spyOn(globalThis, "fetch").mockResolvedValue(new Response("{}", { status: 200 }));
+    // Nothing listens on port 1 — the connect fails and the origin is marked HTTP/1.1-only
+    await pool.fetch("http://127.0.0.1:1/x", { method: "GET", headers: {} });
+    await pool.fetch("http://127.0.0.1:1/y", { method: "GET", headers: {} });
+    expect(fetchSpy).toHaveBeenCalledTimes(2);
+  });
+});
@@ -0,0 +1,128 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { MockHarnessApi, loadMockFixtureDir, type MockMapping } from "../../src/client/mock-api.js";
+import { HarnessClient } from "../../src/client/harness-client.js";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+
+const BASE = "https://app.harness.io";
+
+function mapping(request: MockMapping["request"], jsonBody: unknown, extra: Partial<MockMapping> = {}): MockMapping {
+  return { request, response: { status: 200, jsonBody }, ...extra };
+}
+
+describe("MockHarnessApi", () => {
+  it("matches on method, path, and query parameters, ignoring account scoping", async () => {
+    const api = new MockHarnessApi([
+      mapping({ method: "GET", urlPath: "/ng/api/servicesV2", queryParameters: { projectIdentifier: { equalTo: "web" } } }, { which: "web" }),
+      mapping({ method: "GET", urlPath: "/ng/api/servicesV2" }, { which: "any" }),
+    ]);
+
+    const scoped = await api.fetch(`${BASE}/ng/api/servicesV2?accountIdentifier=abc&routingId=abc&projectIdentifier=web`, { method: "GET" });
+    expect(await scoped.json()).toEqual({ which: "web" });
+    const other = await api.fetch(`${BASE}/ng/api/servicesV2?projectIdentifier=api`, { method: "GET" });
+    expect(await other.json()).toEqual({ which: "any" });
+    expect((await api.fetch(`${BASE}/ng/api/servicesV2`, { method: "POST" })).status).toBe(404);
+  });
+
+  it("supports url, urlPattern, and urlPathPattern", async () => {
+    const api = new MockHarnessApi([
+      mapping({ url: "/ng/api/projects?orgIdentifier=default" }, { kind: "url" }),
+      mapping({ urlPattern: "/ng/api/projects\\?orgIdentifier=.*" }, { kind: "urlPattern" }),
+      mapping({ urlPathPattern: "/ng/api/projects/[^/]+" }, { kind: "urlPathPattern" }),
+    ]);
+
+    expect(await (await api.fetch(`${BASE}/ng/api/projects?accountIdentifier=a&orgIdentifier=default`, { method: "GET" })).json()).toEqual({ kind: "url" });
+    expect(await (await api.fetch(`${BASE}/ng/api/projects?orgIdentifier=ops`, { method: "GET" })).json()).toEqual({ kind: "urlPattern" });
+    expect(await (await api.fetch(`${BASE}/ng/api/projects/web`, { method: "GET" })).json()).toEqual({ kind: "urlPathPattern" });
+  });
+
+  it("prefers lower priority values, then earlier mappings", async () => {
+    const api = new MockHarnessApi([
+      mapping({ urlPath: "/x" }, { n: 1 }),
+      mapping({ urlPath: "/x" }, { n: 2 }, { priority: 1 }),
+      mapping({ urlPath: "/x" }, { n: 3 }, { priority: 1 }),
+    ]);
+    expect(await (await api.fetch(`${BASE}/x`, { method: "GET" })).json()).toEqual({ n: 2 });
+  });
+
+  it("strips the base URL path prefix before matching", async () => {
+    const api = new MockHarnessApi([mapping({ urlPath: "/ng/api/organizations" }, { ok: true })], "/gateway");
+    const res = await api.fetch("https://harness.example.com/gateway/ng/api/organizations", { method: "GET" });
+    expect(res.status).toBe(200);
+  });
+
+  it("answers unmatched requests with a Harness-style 404 naming the request", async () => {
+    const res = await new MockHarnessApi([]).fetch(`${BASE}/ng/api/secrets/db`, { method: "GET" });
+    expect(res.status).toBe(404);
+    expect(await res.json()).toMatchObject({ status: "ERROR", message: expect.stringContaining("GET /ng/api/secrets/db") });
+  });
+
+  it("serves text bodies and custom headers", async () => {
+    const api = new MockHarnessApi([{ request: { urlPath: "/log" }, response: { body: "line 1\n", headers: { "x-demo": "1" } } }]);
+    const res = await api.fetch(`${BASE}/log`, { method: "GET" });
+    expect(await res.text()).toBe("line 1\n");
+    expect(res.headers.get("content-type")).toBe("text/plain");
+    expect(res.headers.get("x-demo")).toBe("1");
+  });
+});
+
+describe("loadMockFixtureDir", () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = mkdtempSync(join(tmpdir(), "mock-fixtures-"));
+  });
+
+  afterEach(() => {
+    rmSync(dir, { recursive: true, force: true });
+  });
+
+  it("reads single mappings and WireMock mapping documents", () => {
+    writeFileSync(join(dir, "a.json"), JSON.stringify(mapping({ urlPath: "/a" }, {})));
+    writeFileSync(join(dir, "b.json"), JSON.stringify({ mappings: [mapping({ urlPath: "/b" }, {}), mapping({ urlPath: "/c" }, {})] }));
+    writeFileSync(join(dir, "notes.txt"), "ignored");
+    expect(loadMockFixtureDir(dir).map((m) => m.request.urlPath)).toEqual(["/a", "/b", "/c"]);
+  });
+
+  it("names the file when a fixture is malformed", () => {
+    writeFileSync(join(dir, "bad.json"), JSON.stringify({ request: { urlPath: "/a" } }));
+    expect(() => loadMockFixtureDir(dir)).toThrow(/bad\.json/);
+  });
+});
+
+describe("mock mode through the registry", () => {
+  const config = {
+    HARNESS_API_KEY: "pat.mock.offline.placeholder",
+    HARNESS_ACCOUNT_ID: "mock",
+    HARNESS_BASE_URL: BASE,
+    HARNESS_API_TIMEOUT_MS: 5000,
+    HARNESS_MAX_RETRIES: 0,
+    HARNESS_RATE_LIMIT_RPS: 100,
+    HARNESS_MOCK: true,
+    HARNESS_READ_ONLY: true,
+    LOG_LEVEL: "error",
+  } as Config;
+
+  it("serves the embedded demo account without touching the network", async () => {
+    const fetchSpy = vi.spyOn(globalThis, "fetch");
+    try {
+      const client = new HarnessClient(config);
+      const registry = new Registry(config);
+
+      const list = await registry.dispatch(client, "pipeline", "list", { org_id: "default", project_id: "demo" }) as { items: Array<{ identifier: string }> };
+      expect(list.items.map((p) => p.identifier)).toEqual(["build_and_test", "deploy_prod"]);
+
+      const pipeline = await registry.dispatch(client, "pipeline", "get", { org_id: "default", project_id: "demo", pipeline_id: "deploy_prod" });
+      expect(JSON.stringify(pipeline)).toContain("K8sRollingDeploy");
+
+      await expect(registry.dispatch(client, "pipeline", "get", { org_id: "default", project_id: "demo", pipeline_id: "missing" }))
+        .rejects.toThrow(/no fixture matches GET \/pipeline\/api\/pipelines\/missing/);
+      expect(fetchSpy).not.toHaveBeenCalled();
+    } finally {
+      fetchSpy.mockRestore();
+    }
+  });
+});
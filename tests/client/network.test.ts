@@ -0,0 +1,90 @@
+import { describe, expect, it, vi } from "vitest";
+import type { LookupAddress } from "node:dns";
+import { DnsCache } from "../../src/client/network.js";
+
+function enotfound(hostname: string): NodeJS.ErrnoException {
+  const err: NodeJS.ErrnoException = new Error(`getaddrinfo ENOTFOUND ${hostname}`);
+  err.code = "ENOTFOUND";
+  return err;
+}
+
+describe("DnsCache", () => {
+  const addresses: LookupAddress[] = [
+    { address: "10.0.0.1", family: 4 },
+    { address: "fd00::1", family: 6 },
+  ];
+
+  it("serves repeat lookups from cache until the TTL expires", async () => {
+    vi.useFakeTimers();
+    try {
+      const resolver = vi.fn().mockResolvedValue(addresses);
+      const cache = new DnsCache(1_000, 500, resolver);
+
+      expect(await cache.resolve("app.harness.io")).toEqual(addresses);
+      expect(await cache.resolve("app.harness.io")).toEqual(addresses);
+      expect(resolver).toHaveBeenCalledTimes(1);
+
+      vi.advanceTimersByTime(1_001);
+      await cache.resolve("app.harness.io");
+      expect(resolver).toHaveBeenCalledTimes(2);
+    } finally {
+      vi.useRealTimers();
+    }
+  });
+
+  it("shares one lookup between concurrent misses", async () => {
+    let release!: (value: LookupAddress[]) => void;
+    const resolver = vi.fn(() => new Promise<LookupAddress[]>((r) => { release = r; }));
+    const cache = new DnsCache(1_000, 500, resolver);
+
+    const both = Promise.all([cache.resolve("app.harness.io"), cache.resolve("app.harness.io")]);
+    release(addresses);
+    expect(await both).toEqual([addresses, addresses]);
+    expect(resolver).toHaveBeenCalledTimes(1);
+  });
+
+  it("caches name-not-found failures for the negative TTL only", async () => {
+    vi.useFakeTimers();
+    try {
+      const resolver = vi.fn().mockRejectedValue(enotfound("nope.invalid"));
+      const cache = new DnsCache(10_000, 500, resolver);
+
+      await expect(cache.resolve("nope.invalid")).rejects.toThrow("ENOTFOUND");
+      await expect(cache.resolve("nope.invalid")).rejects.toThrow("ENOTFOUND");
+      expect(resolver).toHaveBeenCalledTimes(1);
+
+      vi.advanceTimersByTime(501);
+      await expect(cache.resolve("nope.invalid")).rejects.toThrow("ENOTFOUND");
+      expect(resolver).toHaveBeenCalledTimes(2);
+    } finally {
+      vi.useRealTimers();
+    }
+  });
+
+  it("does not cache other resolver errors", async () => {
+    const err: NodeJS.ErrnoException = new Error("socket hang up");
+    err.code = "ECONNRESET";
+    const resolver = vi.fn().mockRejectedValue(err);
+    const cache = new DnsCache(10_000, 500, resolver);
+
+    await expect(cache.resolve("app.harness.io")).rejects.toThrow("socket hang up");
+    await expect(cache.resolve("app.harness.io")).rejects.toThrow("socket hang up");
+    expect(resolver).toHaveBeenCalledTimes(2);
+  });
+
+  it("implements the net lookup callback contract", async () => {
+    const resolver = vi.fn().mockResolvedValue(addresses);
+    const cache = new DnsCache(1_000, 500, resolver);
+
+    const single = await new Promise<[string | LookupAddress[], number | undefined]>((resolve, reject) => {
+      cache.lookup("app.harness.io", { family: 4 }, (err, address, family) => (err ? reject(err) : resolve([address, family])));
+    });
+    expect(single).toEqual(["10.0.0.1", 4]);
+    expect(resolver).toHaveBeenCalledWith("app.harness.io", 4);
+
+    const all = await new Promise<string | LookupAddress[]>((resolve, reject) => {
+      cache.lookup("app.harness.io", { all: true }, (err, address) => (err ? reject(err) : resolve(address)));
+    });
+    expect(all).toEqual(addresses);
+  });
+});
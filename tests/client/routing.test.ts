@@ -0,0 +1,113 @@
+import { describe, it, expect } from "vitest";
+import { applyRoute, clusterFromBaseUrl, normalizeBaseUrl, parseRouteMap, resolveRouteMap, SMP_ROUTE_PRESET } from "../../src/client/routing.js";
+
+describe("parseRouteMap", () => {
+  it("accepts prefix strings and prefix/port objects, normalizing slashes", () => {
+    expect(parseRouteMap('{"ng/api/":"gateway/ng/api","/log-service":{"port":8079}}')).toEqual({
+      "/ng/api": { prefix: "/gateway/ng/api" },
+      "/log-service": { prefix: undefined, port: 8079 },
+    });
+  });
+
+  it("rejects invalid JSON, non-objects, and bad ports", () => {
+    expect(() => parseRouteMap("{")).toThrow(/not valid JSON/);
+    expect(() => parseRouteMap("[]")).toThrow(/JSON object/);
+    expect(() => parseRouteMap('{"/ng/api":{"port":70000}}')).toThrow(/port/);
+    expect(() => parseRouteMap('{"/ng/api":42}')).toThrow(/prefix string or an object/);
+  });
+});
+
+describe("resolveRouteMap", () => {
+  it("is empty for SaaS without overrides", () => {
+    expect(resolveRouteMap("saas")).toEqual({});
+  });
+
+  it("merges user overrides over the SMP preset", () => {
+    const routes = resolveRouteMap("smp", '{"/ng/api":"/custom/ng/api"}');
+    expect(routes["/ng/api"]).toEqual({ prefix: "/custom/ng/api" });
+    expect(routes["/pipeline/api"]).toEqual(SMP_ROUTE_PRESET["/pipeline/api"]);
+  });
+});
+
+describe("applyRoute", () => {
+  const routes = parseRouteMap('{"/ng/api":"/gateway/ng/api","/ng/api/v2":{"port":9090},"/code":{"port":3000}}');
+
+  it("uses the longest matching prefix", () => {
+    expect(applyRoute("https://h.example.com", "/ng/api/v2/x", routes)).toEqual({
+      baseUrl: "https://h.example.com:9090",
+      path: "/ng/api/v2/x",
+    });
+    expect(applyRoute("https://h.example.com", "/ng/api/projects", routes)).toEqual({
+      baseUrl: "https://h.example.com",
+      path: "/gateway/ng/api/projects",
+    });
+  });
+
+  it("matches whole path segments only", () => {
+    expect(applyRoute("https://h.example.com", "/codex/api", routes).path).toBe("/codex/api");
+  });
+
+  it("preserves a base URL path when overriding the port", () => {
+    expect(applyRoute("https://h.example.com/harness", "/code/api/v1", routes)).toEqual({
+      baseUrl: "https://h.example.com:3000/harness",
+      path: "/code/api/v1",
+    });
+  });
+
+  it("leaves unmatched paths untouched", () => {
+    expect(applyRoute("https://h.example.com", "/pipeline/api/x", routes)).toEqual({
+      baseUrl: "https://h.example.com",
+      path: "/pipeline/api/x",
+    });
+  });
+});
+
+describe("URL templates", () => {
+  const routes = parseRouteMap('{"/log-service":{"url":"https://logs.{cluster}.example.com/{account}"},"/sto/api":{"url":"https://sto.example.com","port":8443}}');
+
+  it("substitutes account and cluster into the base URL", () => {
+    expect(applyRoute("https://app3.harness.io", "/log-service/blob", routes, { account: "acc1", cluster: "app3" })).toEqual({
+      baseUrl: "https://logs.app3.example.com/acc1",
+      path: "/log-service/blob",
+    });
+    expect(applyRoute("https://app3.harness.io", "/sto/api/v2/scans", routes).baseUrl).toBe("https://sto.example.com:8443");
+  });
+
+  it("fails when a placeholder has no value", () => {
+    expect(() => applyRoute("https://10.0.0.1", "/log-service/blob", routes, { account: "acc1" })).toThrow(/\{cluster\}/);
+  });
+
+  it("rejects unknown placeholders and non-http templates", () => {
+    expect(() => parseRouteMap('{"/ng/api":{"url":"https://{region}.example.com"}}')).toThrow(/unknown placeholder.*\{region\}/);
+    expect(() => parseRouteMap('{"/ng/api":{"url":"ftp://example.com"}}')).toThrow(/http\(s\) URL template/);
+  });
+});
+
+describe("gateway prefix detection", () => {
+  it("does not double /gateway when the base URL already ends with it", () => {
+    expect(applyRoute("https://smp.example.com/gateway", "/ng/api/projects", SMP_ROUTE_PRESET)).toEqual({
+      baseUrl: "https://smp.example.com/gateway",
+      path: "/ng/api/projects",
+    });
+  });
+});
+
+describe("normalizeBaseUrl", () => {
+  it("drops Harness UI paths and fragments", () => {
+    expect(normalizeBaseUrl("https://app3.harness.io/ng/account/abc123/home")).toBe("https://app3.harness.io");
+    expect(normalizeBaseUrl("https://vanity.harness.io/ng/#/account/abc123")).toBe("https://vanity.harness.io");
+    expect(normalizeBaseUrl("https://app.harness.io/")).toBe("https://app.harness.io");
+  });
+
+  it("keeps gateway and proxy mount paths", () => {
+    expect(normalizeBaseUrl("https://smp.example.com/gateway/")).toBe("https://smp.example.com/gateway");
+    expect(normalizeBaseUrl("https://proxy.example.com/harness")).toBe("https://proxy.example.com/harness");
+  });
+});
+
+describe("clusterFromBaseUrl", () => {
+  it("uses the first host label", () => {
+    expect(clusterFromBaseUrl("https://app3.harness.io")).toBe("app3");
+    expect(clusterFromBaseUrl("https://10.0.0.1:8443")).toBeUndefined();
+  });
+});
@@ -100,6 +100,7 @@ const SEARCH_DIR = join(SRC, "search");
 /** Files allowed to call the global fetch() API (documented exceptions). */
 const ALLOWED_GLOBAL_FETCH_FILES = new Set([
   "src/client/harness-client.ts",
+  "src/client/feature-flags.ts",
   "src/utils/log-resolver.ts",
   "src/audit/sinks/webhook.ts",
   "src/search/remote-provider.ts",
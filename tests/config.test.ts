@@ -372,6 +372,86 @@ describe("ConfigSchema", () => {
       }),
     ).toThrow('Invalid HARNESS_MCP_ALLOWED_HOSTS entries: "http://"');
   });
+
+  it("defaults HARNESS_ENTITY_REFRESH_INTERVAL_MS to 0 (disabled)", () => {
+    const result = ConfigSchema.safeParse(validConfig);
+    expect(result.success).toBe(true);
+    if (result.success) {
+      expect(result.data.HARNESS_ENTITY_REFRESH_INTERVAL_MS).toBe(0);
+    }
+  });
+
+  it("coerces HARNESS_ENTITY_REFRESH_INTERVAL_MS and rejects negatives", () => {
+    const ok = ConfigSchema.safeParse({ ...validConfig, HARNESS_ENTITY_REFRESH_INTERVAL_MS: "300000" });
+    expect(ok.success).toBe(true);
+    if (ok.success) {
+      expect(ok.data.HARNESS_ENTITY_REFRESH_INTERVAL_MS).toBe(300000);
+    }
+    expect(ConfigSchema.safeParse({ ...validConfig, HARNESS_ENTITY_REFRESH_INTERVAL_MS: "-1" }).success).toBe(false);
+  });
+
+  it("leaves HARNESS_CACHE_DIR unset by default and accepts a directory", () => {
+    const unset = ConfigSchema.safeParse({ ...validConfig, HARNESS_CACHE_DIR: "" });
+    expect(unset.success).toBe(true);
+    if (unset.success) {
+      expect(unset.data.HARNESS_CACHE_DIR).toBeUndefined();
+    }
+    const set = ConfigSchema.safeParse({ ...validConfig, HARNESS_CACHE_DIR: "/var/cache/harness-mcp" });
+    expect(set.success).toBe(true);
+    if (set.success) {
+      expect(set.data.HARNESS_CACHE_DIR).toBe("/var/cache/harness-mcp");
+    }
+  });
+
+  it("defaults HARNESS_UNKNOWN_FIELDS to strip and accepts include", () => {
+    const defaults = ConfigSchema.safeParse(validConfig);
+    expect(defaults.success).toBe(true);
+    if (defaults.success) {
+      expect(defaults.data.HARNESS_UNKNOWN_FIELDS).toBe("strip");
+    }
+    const include = ConfigSchema.safeParse({ ...validConfig, HARNESS_UNKNOWN_FIELDS: "include" });
+    expect(include.success).toBe(true);
+    if (include.success) {
+      expect(include.data.HARNESS_UNKNOWN_FIELDS).toBe("include");
+    }
+    expect(ConfigSchema.safeParse({ ...validConfig, HARNESS_UNKNOWN_FIELDS: "drop" }).success).toBe(false);
+  });
+
+  it("defaults HARNESS_CAPABILITY_PROBE to false with a 10s timeout", () => {
+    const result = ConfigSchema.safeParse(validConfig);
+    expect(result.success).toBe(true);
+    if (result.success) {
+      expect(result.data.HARNESS_CAPABILITY_PROBE).toBe(false);
+      expect(result.data.HARNESS_CAPABILITY_PROBE_TIMEOUT_MS).toBe(10_000);
+    }
+  });
+
+  it("rejects HARNESS_CAPABILITY_PROBE_TIMEOUT_MS below one second", () => {
+    expect(ConfigSchema.safeParse({ ...validConfig, HARNESS_CAPABILITY_PROBE_TIMEOUT_MS: "500" }).success).toBe(false);
+  });
+
+  it("defaults HARNESS_DEPLOYMENT to saas", () => {
+    const result = ConfigSchema.safeParse(validConfig);
+    expect(result.success).toBe(true);
+    if (result.success) {
+      expect(result.data.HARNESS_DEPLOYMENT).toBe("saas");
+    }
+  });
+
+  it("requires a non-SaaS HARNESS_BASE_URL when HARNESS_DEPLOYMENT=smp", () => {
+    expect(() => ConfigSchema.parse({ ...validConfig, HARNESS_DEPLOYMENT: "smp" })).toThrow(/Self-Managed Platform/);
+    const ok = ConfigSchema.safeParse({ ...validConfig, HARNESS_DEPLOYMENT: "smp", HARNESS_BASE_URL: "https://harness.example.com" });
+    expect(ok.success).toBe(true);
+  });
+
+  it("rejects a malformed HARNESS_ROUTE_MAP at load time", () => {
+    expect(() => ConfigSchema.parse({ ...validConfig, HARNESS_ROUTE_MAP: "not-json" })).toThrow(/HARNESS_ROUTE_MAP/);
+  });
+
+  it("accepts an optional HARNESS_OUTPUT_DIR", () => {
+    expect(ConfigSchema.parse(validConfig).HARNESS_OUTPUT_DIR).toBeUndefined();
+    expect(ConfigSchema.parse({ ...validConfig, HARNESS_OUTPUT_DIR: "/tmp/out" }).HARNESS_OUTPUT_DIR).toBe("/tmp/out");
+  });
 });
 
 describe("ConfigSchema — boolean env var coercion", () => {
@@ -592,6 +672,14 @@ describe("loadConfig — account ID extraction", () => {
     });
   });
 
+  it("mock mode needs no API key and is always read-only", () => {
+    withEnv({ HARNESS_MOCK: "true" }, () => {
+      const config = loadConfig();
+      expect(config.HARNESS_ACCOUNT_ID).toBe("mock");
+      expect(config.HARNESS_READ_ONLY).toBe(true);
+    });
+  });
+
   it("defaults HARNESS_HF_CACHE_DIR to /tmp/hf-cache", () => {
     withEnv({ HARNESS_API_KEY: "pat.acct123.tok.sec" }, () => {
       const config = loadConfig();
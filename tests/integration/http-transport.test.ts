@@ -16,6 +16,7 @@ import { createHttpAuthMiddleware } from "../../src/utils/http-auth.js";
 import { isSessionExpired } from "../../src/utils/http-sessions.js";
 import { mergeConfigWithSessionHeaders, MissingSessionCredentialsError } from "../../src/utils/session-headers.js";
 import { createHarnessHttpExpressApp } from "../../src/utils/http-app.js";
+import { createCorsMiddleware, resolveAllowedOrigins } from "../../src/utils/http-cors.js";
 
 // We can't easily test the full HTTP server without starting it,
 // so we test the session management patterns and transport lifecycle
@@ -306,23 +307,23 @@ describe("HTTP transport session management", () => {
   });
 
   describe("CORS headers", () => {
-    it("sets correct CORS headers for localhost", () => {
-      const host = "127.0.0.1";
-      const port = 3000;
-      const headers: Record<string, string> = {};
-
-      // Simulate CORS middleware
-      headers["Access-Control-Allow-Origin"] = `http://${host}:${port}`;
-      headers["Access-Control-Allow-Methods"] = "GET, POST, DELETE, OPTIONS";
-      headers["Access-Control-Allow-Headers"] = "Authorization, Content-Type, mcp-session-id, x-harness-api-key, x-harness-account-id, x-harness-org, x-harness-project, x-harness-pipeline-version, x-harness-auto-approve-risk";
-      headers["Access-Control-Expose-Headers"] = "mcp-session-id";
-
-      expect(headers["Access-Control-Allow-Origin"]).toBe("http://127.0.0.1:3000");
-      expect(headers["Access-Control-Allow-Methods"]).toContain("POST");
-      expect(headers["Access-Control-Allow-Methods"]).toContain("DELETE");
-      expect(headers["Access-Control-Allow-Headers"]).toContain("Authorization");
-      expect(headers["Access-Control-Allow-Headers"]).toContain("mcp-session-id");
-      expect(headers["Access-Control-Expose-Headers"]).toContain("mcp-session-id");
+    it("allows the server's own origin for localhost", async () => {
+      const app = createHarnessHttpExpressApp(resolveHttpHostValidationOptions("127.0.0.1", {}));
+      app.use(createCorsMiddleware(resolveAllowedOrigins("127.0.0.1", 3000)));
+      app.get("/probe", (_req, res) => {
+        res.json({ ok: true });
+      });
+
+      await withListeningApp(app, async (baseUrl) => {
+        const res = await fetch(new URL("/probe", baseUrl), { headers: { Origin: "http://127.0.0.1:3000" } });
+        expect(res.status).toBe(200);
+        expect(res.headers.get("access-control-allow-origin")).toBe("http://127.0.0.1:3000");
+        expect(res.headers.get("access-control-allow-methods")).toContain("POST");
+        expect(res.headers.get("access-control-allow-methods")).toContain("DELETE");
+        expect(res.headers.get("access-control-allow-headers")).toContain("Authorization");
+        expect(res.headers.get("access-control-allow-headers")).toContain("mcp-session-id");
+        expect(res.headers.get("access-control-expose-headers")).toContain("mcp-session-id");
+      });
     });
   });
 
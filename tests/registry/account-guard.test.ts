@@ -0,0 +1,65 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "web",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    HARNESS_TOOLSETS: "connectors,templates",
+    LOG_LEVEL: "info",
+    ...overrides,
+  } as Config;
+}
+
+function makeClient() {
+  const request = vi.fn().mockResolvedValue({ status: "SUCCESS", data: {} });
+  return { client: { request, account: "test-account" } as unknown as HarnessClient, request };
+}
+
+describe("account-switching guard", () => {
+  it("rejects an account_id for a different account by default", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient();
+    await expect(
+      registry.dispatch(client, "connector", "get", { connector_id: "github", account_id: "other-account" }),
+    ).rejects.toThrow(/account_id "other-account" does not match the authenticated account "test-account"/);
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it("checks account identifiers inside the body", async () => {
+    const registry = new Registry(makeConfig());
+    const { client } = makeClient();
+    await expect(
+      registry.dispatch(client, "connector", "create", { body: { connector: { identifier: "x" }, accountIdentifier: "other-account" } }),
+    ).rejects.toThrow(/body\.accountIdentifier "other-account"/);
+  });
+
+  it("allows the authenticated account and the global templates placeholder", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient();
+    await registry.dispatch(client, "connector", "get", { connector_id: "github", account_id: "test-account" });
+    await registry.dispatch(client, "template", "get", { template_id: "t1", account_id: "__GLOBAL_TEMPLATES_ACCOUNT_ID__" });
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+
+  it("drops mismatched account arguments with HARNESS_ACCOUNT_MISMATCH=ignore", async () => {
+    const registry = new Registry(makeConfig({ HARNESS_ACCOUNT_MISMATCH: "ignore" }));
+    const { client, request } = makeClient();
+    await registry.dispatch(client, "connector", "get", { connector_id: "github", account_id: "other-account" });
+    expect(request).toHaveBeenCalledOnce();
+    expect(JSON.stringify(request.mock.calls[0][0])).not.toContain("other-account");
+  });
+});
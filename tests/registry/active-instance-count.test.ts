@@ -0,0 +1,47 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+const config = {
+  HARNESS_API_KEY: "pat.test",
+  HARNESS_ACCOUNT_ID: "test-account",
+  HARNESS_BASE_URL: "https://app.harness.io",
+  HARNESS_TOOLSETS: "services",
+  LOG_LEVEL: "info",
+} as Config;
+
+function makeClient() {
+  const request = vi.fn().mockResolvedValue({
+    status: "SUCCESS",
+    data: {
+      module: "CD",
+      timestamp: Date.parse("2026-05-01T00:00:00Z"),
+      activeServices: { count: 42, displayName: "Last 30 Days", references: [{ identifier: "checkout" }] },
+      activeServiceInstances: { count: 318, displayName: "95th Percentile" },
+      serviceLicenses: { count: 50 },
+    },
+  });
+  return { client: { request, account: "test-account" } as unknown as HarnessClient, request };
+}
+
+describe("active_instance_count", () => {
+  it("returns license usage counts without reference lists", async () => {
+    const { client, request } = makeClient();
+    const result = await new Registry(config).dispatch(client, "active_instance_count", "get", {});
+    expect(result).toMatchObject({
+      active_service_instances: 318,
+      active_services: 42,
+      service_licenses: 50,
+      as_of: "2026-05-01T00:00:00.000Z",
+    });
+    expect(JSON.stringify(result)).not.toContain("references");
+    expect(request.mock.calls[0]![0].path).toMatch(/^\/ng\/api\/usage\/CD\?timestamp=\d+$/);
+  });
+
+  it("passes as_of through as the usage timestamp", async () => {
+    const { client, request } = makeClient();
+    await new Registry(config).dispatch(client, "active_instance_count", "get", { as_of: "2026-04-01T00:00:00Z" });
+    expect(request.mock.calls[0]![0].path).toBe(`/ng/api/usage/CD?timestamp=${Date.parse("2026-04-01T00:00:00Z")}`);
+  });
+});
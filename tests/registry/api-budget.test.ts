@@ -0,0 +1,109 @@
+import { describe, it, expect, vi, afterEach } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import { ApiBudget, ApiBudgetExceededError, currentApiBudget, resetApiBudgets, trackedHourlyBudgets } from "../../src/utils/api-budget.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "web",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    HARNESS_TOOLSETS: "connectors",
+    LOG_LEVEL: "info",
+    ...overrides,
+  } as Config;
+}
+
+/** `request` records what actually went out; like `HarnessClient`, the client charges the budget first. */
+function makeClient(response: unknown = { status: "SUCCESS", data: { connector: { identifier: "github" } } }) {
+  const request = vi.fn().mockResolvedValue(response);
+  const client = {
+    account: "test-account",
+    request: async (options: unknown) => {
+      const refusal = currentApiBudget()?.consume("test-account");
+      if (refusal) throw new ApiBudgetExceededError(refusal);
+      return request(options);
+    },
+  } as unknown as HarnessClient;
+  return { client, request };
+}
+
+afterEach(() => {
+  resetApiBudgets();
+});
+
+describe("API call budget", () => {
+  it("serves the cached response for a repeated read once the session budget is spent", async () => {
+    const registry = new Registry(makeConfig({ HARNESS_API_BUDGET_PER_SESSION: 1 }));
+    const { client, request } = makeClient();
+    await registry.dispatch(client, "connector", "get", { connector_id: "github" });
+    const second = await registry.dispatch(client, "connector", "get", { connector_id: "github" }) as Record<string, unknown>;
+    expect(request).toHaveBeenCalledOnce();
+    expect(second._budget_warning).toMatch(/budget for this session is spent .*cached response/);
+  });
+
+  it("refuses reads with nothing cached and all writes once spent", async () => {
+    const registry = new Registry(makeConfig({ HARNESS_API_BUDGET_PER_SESSION: 1 }));
+    const { client, request } = makeClient();
+    await registry.dispatch(client, "connector", "get", { connector_id: "github" });
+    await expect(registry.dispatch(client, "connector", "get", { connector_id: "gitlab" }))
+      .rejects.toThrow(/No earlier response for this connector request is cached/);
+    await expect(registry.dispatch(client, "connector", "delete", { connector_id: "github" }))
+      .rejects.toThrow(/Write operations are refused/);
+    expect(request).toHaveBeenCalledOnce();
+  });
+
+  it("shares the hourly budget across sessions on the same account", async () => {
+    const config = makeConfig({ HARNESS_API_BUDGET_PER_HOUR: 2 });
+    const { client, request } = makeClient();
+    await new Registry(config).dispatch(client, "connector", "get", { connector_id: "a" });
+    await new Registry(config).dispatch(client, "connector", "get", { connector_id: "b" });
+    await expect(new Registry(config).dispatch(client, "connector", "get", { connector_id: "c" }))
+      .rejects.toThrow(/hourly API call budget for account test-account is spent \(2 calls/);
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+
+  it("does not charge the budget for responses served without an API call", async () => {
+    const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "ccm", HARNESS_API_BUDGET_PER_SESSION: 2 }));
+    const { client, request } = makeClient({ data: { perspectiveGrid: { data: [] }, perspectiveTotalCount: 0 } });
+    const list = (perspective: string) =>
+      registry.dispatch(client, "cost_breakdown", "list", { perspective_id: perspective, group_by: "region" }) as Promise<Record<string, unknown>>;
+
+    await list("p1");
+    await list("p1"); // reused response — no request goes out
+    const other = await list("p2");
+
+    expect(request).toHaveBeenCalledTimes(2);
+    expect(other._budget_warning).toBeUndefined();
+    await expect(list("p3")).rejects.toThrow(/budget for this session is spent/);
+  });
+
+  it("drops expired hourly windows for accounts that stop calling", () => {
+    const budget = new ApiBudget({ perSession: 0, perHour: 5 });
+    const start = Date.now();
+    budget.consume("acct-a", start);
+    budget.consume("acct-b", start);
+    expect(trackedHourlyBudgets()).toBe(2);
+
+    budget.consume("acct-c", start + 60 * 60_000 + 1);
+    expect(trackedHourlyBudgets()).toBe(1);
+  });
+
+  it("does not limit anything by default", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient();
+    for (let i = 0; i < 5; i++) await registry.dispatch(client, "connector", "get", { connector_id: "github" });
+    expect(request).toHaveBeenCalledTimes(5);
+  });
+});
@@ -0,0 +1,103 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import { probePlatformCapabilities } from "../../src/registry/capabilities.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+import { HarnessApiError } from "../../src/utils/errors.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  };
+}
+
+function makeClient(requestFn: (...args: unknown[]) => unknown): HarnessClient {
+  return { request: vi.fn(requestFn), account: "test-account" } as unknown as HarnessClient;
+}
+
+describe("probePlatformCapabilities", () => {
+  it("marks toolsets whose probe resource returns 404 as unavailable", async () => {
+    const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "pipelines,chaos,gitops" }));
+    const client = makeClient(async (opts) => {
+      const path = (opts as { path: string }).path;
+      if (path.includes("/chaos/")) throw new HarnessApiError("Not Found", 404);
+      if (path === "/ng/api/version") return { resource: { versionInfo: { version: "1.2.3" } } };
+      return { data: { content: [] } };
+    });
+
+    const caps = await probePlatformCapabilities(registry, client, 5000);
+
+    expect(caps.unavailableToolsets).toEqual(["chaos"]);
+    expect(caps.version).toBe("1.2.3");
+    expect(caps.probedAt).toBeTruthy();
+  });
+
+  it("keeps toolsets on non-404 failures (auth, server errors)", async () => {
+    const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "chaos,gitops" }));
+    const client = makeClient(async (opts) => {
+      const path = (opts as { path: string }).path;
+      if (path.includes("/chaos/")) throw new HarnessApiError("Forbidden", 403);
+      throw new HarnessApiError("Internal error", 500);
+    });
+
+    const caps = await probePlatformCapabilities(registry, client, 5000);
+
+    expect(caps.unavailableToolsets).toEqual([]);
+    expect(caps.version).toBeUndefined();
+  });
+
+  it("only probes enabled toolsets that declare probeResource", async () => {
+    const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "pipelines,services" }));
+    const request = vi.fn().mockResolvedValue({});
+    const client = { request, account: "test-account" } as unknown as HarnessClient;
+
+    await probePlatformCapabilities(registry, client, 5000);
+
+    // Only the version lookup — neither toolset declares a probe resource
+    expect(request).toHaveBeenCalledTimes(1);
+  });
+});
+
+describe("Registry — capabilities option", () => {
+  it("drops unavailable toolsets and their resource types", () => {
+    const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "pipelines,chaos" }), {
+      capabilities: { probedAt: "2026-01-01T00:00:00.000Z", unavailableToolsets: ["chaos"] },
+    });
+
+    expect(registry.getAllResourceTypes()).not.toContain("chaos_experiment");
+    expect(registry.getAllResourceTypes()).toContain("pipeline");
+  });
+
+  it("reports platform version and hidden toolsets in describeSummary", () => {
+    const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "pipelines,chaos" }), {
+      capabilities: { version: "1.2.3", probedAt: "2026-01-01T00:00:00.000Z", unavailableToolsets: ["chaos"] },
+    });
+
+    const summary = registry.describeSummary() as { total_toolsets: number; platform: Record<string, unknown> };
+    expect(summary.total_toolsets).toBe(1);
+    expect(summary.platform).toEqual({
+      version: "1.2.3",
+      probed_at: "2026-01-01T00:00:00.000Z",
+      unavailable_toolsets: ["chaos"],
+    });
+  });
+
+  it("omits the platform block when no probe ran", () => {
+    const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "pipelines" }));
+    expect(registry.describeSummary()).not.toHaveProperty("platform");
+  });
+});
@@ -0,0 +1,92 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  };
+}
+
+function makeClient(request: ReturnType<typeof vi.fn>): HarnessClient {
+  return { request, account: "test-account" } as unknown as HarnessClient;
+}
+
+type Call = { method: string; path: string; body: Record<string, unknown> };
+
+describe("CCM asset governance", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "ccm" }));
+
+  it("lists rules with search and provider filters", async () => {
+    const request = vi.fn().mockResolvedValue({ data: { rules: [{ uuid: "r1", name: "unattached-ebs" }], totalItems: 7 } });
+
+    const result = await registry.dispatch(makeClient(request), "asset_governance_rule", "list", { search: "ebs", cloud_provider: "AWS" });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.path).toBe("/ccm/api/governance/rule/list");
+    expect(call.body.query).toMatchObject({ search: "ebs", cloudProvider: "AWS", limit: 20, offset: 0 });
+    expect(result).toEqual({ items: [{ uuid: "r1", name: "unattached-ebs" }], total: 7 });
+  });
+
+  it("gets a rule by ID and reports a missing rule", async () => {
+    const found = vi.fn().mockResolvedValue({ data: { rules: [{ uuid: "r1", rulesYaml: "policies: []" }], totalItems: 1 } });
+    await expect(registry.dispatch(makeClient(found), "asset_governance_rule", "get", { rule_id: "r1" }))
+      .resolves.toEqual({ uuid: "r1", rulesYaml: "policies: []" });
+    expect((found.mock.calls[0]![0] as Call).body.query).toMatchObject({ policyIds: ["r1"] });
+
+    const missing = vi.fn().mockResolvedValue({ data: { rules: [], totalItems: 0 } });
+    await expect(registry.dispatch(makeClient(missing), "asset_governance_rule", "get", { rule_id: "nope" }))
+      .rejects.toThrow("Asset governance rule not found");
+  });
+
+  it("enqueues a dry run against a connector and regions", async () => {
+    const request = vi.fn().mockResolvedValue({ data: "exec-1" });
+
+    await registry.dispatchExecute(makeClient(request), "asset_governance_rule", "dry_run", {
+      rule_id: "r1",
+      cloud_connector_id: "aws_ccm",
+      target_account: "123456789012",
+      regions: "us-east-1, us-west-2",
+    });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.path).toBe("/ccm/api/governance/enqueue");
+    expect(call.body).toMatchObject({
+      ruleId: "r1",
+      isDryRun: true,
+      targetRegions: ["us-east-1", "us-west-2"],
+      targetAccountDetails: [{ cloudConnectorId: "aws_ccm", targetInfo: "123456789012" }],
+    });
+  });
+
+  it("rejects a dry run without a target", async () => {
+    const request = vi.fn();
+    await expect(registry.dispatchExecute(makeClient(request), "asset_governance_rule", "dry_run", { rule_id: "r1" }))
+      .rejects.toThrow("dry_run requires");
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it("lists evaluations for a rule", async () => {
+    const request = vi.fn().mockResolvedValue({ data: { ruleExecution: [{ uuid: "e1", executionStatus: "SUCCESS" }], totalItems: 1 } });
+
+    const result = await registry.dispatch(makeClient(request), "asset_governance_evaluation", "list", { rule_id: "r1" });
+
+    expect((request.mock.calls[0]![0] as Call).body.ruleExecutionFilter).toMatchObject({ ruleIds: ["r1"], limit: 20 });
+    expect(result).toEqual({ items: [{ uuid: "e1", executionStatus: "SUCCESS" }], total: 1 });
+  });
+});
@@ -0,0 +1,106 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    HARNESS_TOOLSETS: "ccm",
+    LOG_LEVEL: "info",
+    ...overrides,
+  } as Config;
+}
+
+function makeClient(requestFn: (...args: unknown[]) => unknown): HarnessClient {
+  return { request: requestFn, account: "test-account" } as unknown as HarnessClient;
+}
+
+interface GqlCall {
+  body: { query: string; operationName: string; variables: Record<string, unknown> };
+}
+
+describe("cost_breakdown group_by batching", () => {
+  it("fetches several dimensions in one GraphQL request and tags rows", async () => {
+    const registry = new Registry(makeConfig());
+    const request = vi.fn().mockResolvedValue({
+      data: {
+        grid0: { data: [{ name: "us-east-1", id: "us-east-1", cost: 900 }] },
+        total0: 4,
+        grid1: { data: [{ name: "EC2", id: "ec2", cost: 700 }, { name: "S3", id: "s3", cost: 200 }] },
+        total1: 9,
+      },
+    });
+
+    const result = await registry.dispatch(makeClient(request), "cost_breakdown", "list", {
+      perspective_id: "p1",
+      group_by: "region, product",
+    }) as { items: Array<Record<string, unknown>>; total: number; groups: Record<string, unknown> };
+
+    expect(request).toHaveBeenCalledOnce();
+    const { query, operationName, variables } = (request.mock.calls[0]![0] as GqlCall).body;
+    expect(operationName).toBe("FetchperspectiveGridBatch");
+    expect(query).toContain("grid1: perspectiveGrid(");
+    expect(variables.groupBy0).toEqual([{ entityGroupBy: expect.objectContaining({ fieldId: "region" }) }]);
+    expect(variables.groupBy1).toEqual([{ entityGroupBy: expect.objectContaining({ fieldId: "product" }) }]);
+    expect(result.items.map((i) => [i.group_by, i.name])).toEqual([["region", "us-east-1"], ["product", "EC2"], ["product", "S3"]]);
+    expect(result.groups).toEqual({ region: { total: 4, returned: 1 }, product: { total: 9, returned: 2 } });
+  });
+
+  it("keeps the single-dimension query shape", async () => {
+    const registry = new Registry(makeConfig());
+    const request = vi.fn().mockResolvedValue({ data: { perspectiveGrid: { data: [] }, perspectiveTotalCount: 0 } });
+
+    await registry.dispatch(makeClient(request), "cost_breakdown", "list", { perspective_id: "p1", group_by: "region" });
+
+    expect((request.mock.calls[0]![0] as GqlCall).body.operationName).toBe("FetchperspectiveGrid");
+  });
+
+  it("rejects more dimensions than one request can batch", async () => {
+    const registry = new Registry(makeConfig());
+    const request = vi.fn();
+
+    await expect(registry.dispatch(makeClient(request), "cost_breakdown", "list", {
+      perspective_id: "p1",
+      group_by: "region,product,cloudProvider,awsServicecode,env,team",
+    })).rejects.toThrow(/at most 5 dimensions/);
+    expect(request).not.toHaveBeenCalled();
+  });
+});
+
+describe("CCM query reuse", () => {
+  it("reuses an identical query within a session and refetches when it differs", async () => {
+    const registry = new Registry(makeConfig());
+    const request = vi.fn().mockResolvedValue({ data: { perspectiveGrid: { data: [] }, perspectiveTotalCount: 0 } });
+    const client = makeClient(request);
+
+    await registry.dispatch(client, "cost_breakdown", "list", { perspective_id: "p1", group_by: "region" });
+    await registry.dispatch(client, "cost_breakdown", "list", { perspective_id: "p1", group_by: "region" });
+    expect(request).toHaveBeenCalledTimes(1);
+
+    await registry.dispatch(client, "cost_breakdown", "list", { perspective_id: "p1", group_by: "product" });
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+
+  it("does not keep failed queries", async () => {
+    const registry = new Registry(makeConfig());
+    const request = vi.fn()
+      .mockRejectedValueOnce(new Error("rate limited"))
+      .mockResolvedValueOnce({ data: { perspectiveGrid: { data: [] }, perspectiveTotalCount: 0 } });
+    const client = makeClient(request);
+
+    await expect(registry.dispatch(client, "cost_breakdown", "list", { perspective_id: "p1" })).rejects.toThrow(/rate limited/);
+    await registry.dispatch(client, "cost_breakdown", "list", { perspective_id: "p1" });
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+});
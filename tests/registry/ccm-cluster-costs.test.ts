@@ -0,0 +1,96 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import { ccmClusterBreakdownExtract } from "../../src/registry/extractors.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  };
+}
+
+function makeClient(request: ReturnType<typeof vi.fn>): HarnessClient {
+  return { request, account: "test-account" } as unknown as HarnessClient;
+}
+
+const GRID_RESPONSE = {
+  data: {
+    perspectiveGrid: {
+      data: [{
+        name: "payments",
+        id: "payments",
+        cost: 1200.5,
+        costTrend: 4.2,
+        clusterData: { clusterName: "prod-eks", namespace: "payments", idleCost: 300, unallocatedCost: 50, utilizedCost: 850.5, efficiencyScore: 71 },
+      }],
+    },
+    perspectiveTotalCount: 12,
+  },
+};
+
+type GqlCall = { body: { operationName: string; variables: Record<string, unknown> } };
+
+describe("cost_cluster_breakdown", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "ccm" }));
+
+  it("groups by namespace on the given perspective and applies cluster filters", async () => {
+    const request = vi.fn().mockResolvedValue(GRID_RESPONSE);
+
+    const result = await registry.dispatch(makeClient(request), "cost_cluster_breakdown", "list", {
+      perspective_id: "cluster-view",
+      cluster_name: "prod-eks, stage-eks",
+    }) as { items: Array<Record<string, unknown>>; total: number };
+
+    expect(request).toHaveBeenCalledOnce();
+    const { variables } = (request.mock.calls[0]![0] as GqlCall).body;
+    expect(variables.groupBy).toEqual([{ entityGroupBy: expect.objectContaining({ fieldId: "namespace", identifier: "CLUSTER" }) }]);
+    expect(variables.filters).toEqual(expect.arrayContaining([
+      { viewMetadataFilter: { viewId: "cluster-view", isPreview: false } },
+      { idFilter: { field: expect.objectContaining({ fieldId: "clusterName" }), operator: "IN", values: ["prod-eks", "stage-eks"] } },
+    ]));
+    expect(result.total).toBe(12);
+    expect(result.items[0]).toMatchObject({ name: "payments", cluster: "prod-eks", idle_cost: 300, efficiency_score: 71 });
+  });
+
+  it("defaults to the account's cluster perspective from CCM metadata", async () => {
+    const request = vi.fn()
+      .mockResolvedValueOnce({ data: { ccmMetaData: { defaultClusterPerspectiveId: "default-cluster" } } })
+      .mockResolvedValueOnce(GRID_RESPONSE);
+
+    await registry.dispatch(makeClient(request), "cost_cluster_breakdown", "list", { group_by: "workload" });
+
+    const { operationName, variables } = (request.mock.calls[1]![0] as GqlCall).body;
+    expect(operationName).toBe("FetchClusterPerspectiveGrid");
+    expect(variables.filters).toContainEqual({ viewMetadataFilter: { viewId: "default-cluster", isPreview: false } });
+    expect(variables.groupBy).toEqual([{ entityGroupBy: expect.objectContaining({ fieldId: "workloadName" }) }]);
+  });
+
+  it("explains missing cluster data", async () => {
+    const request = vi.fn().mockResolvedValue({ data: { ccmMetaData: { clusterDataPresent: false } } });
+
+    await expect(registry.dispatch(makeClient(request), "cost_cluster_breakdown", "list", {}))
+      .rejects.toThrow(/No Kubernetes cluster cost data/);
+  });
+});
+
+describe("ccmClusterBreakdownExtract", () => {
+  it("tolerates rows without clusterData", () => {
+    const result = ccmClusterBreakdownExtract({ data: { perspectiveGrid: { data: [{ name: "Others", cost: 5, clusterData: null }] } } });
+    expect(result.items).toEqual([expect.objectContaining({ name: "Others", cost: 5, cluster: undefined })]);
+  });
+});
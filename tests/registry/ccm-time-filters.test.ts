@@ -258,6 +258,19 @@ describe("CCM custom time window — start_time/end_time override across perspec
     expect(before).toBe(Q4_END);
   });
 
+  it("accepts ISO dates and natural relative inputs", async () => {
+    await registry.dispatch(client, "cost_timeseries", "list", {
+      perspective_id: "test-perspective",
+      start_time: "last 7d",
+      end_time: "yesterday",
+      time_resolution: "DAY",
+      group_by: "none",
+    });
+    const { after, before } = extractTimeFilters(mockRequest.mock.calls[0][0] as Record<string, unknown>);
+    expect(after).toBe(FIXED_NOW.getTime() - 7 * 86_400_000);
+    expect(before).toBe(Date.UTC(2026, 4, 20, 23, 59, 59, 999));
+  });
+
   it("falls back to relative time_filter when only one bound is provided", async () => {
     await registry.dispatch(client, "cost_timeseries", "list", {
       perspective_id: "test-perspective",
@@ -0,0 +1,66 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    LOG_LEVEL: "info",
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    ...overrides,
+  };
+}
+
+function makeClient(request: ReturnType<typeof vi.fn>): HarnessClient {
+  return { request, account: "test-account" } as unknown as HarnessClient;
+}
+
+describe("chaos GameDays", () => {
+  const registry = new Registry(makeConfig());
+
+  it("lists GameDays with chaos scope params", async () => {
+    const request = vi.fn().mockResolvedValue({ data: [{ identity: "gd1", name: "Q3 failover" }], pagination: { totalItems: 1 } });
+
+    const result = await registry.dispatch(makeClient(request), "chaos_gameday", "list", { search: "failover" });
+
+    const call = request.mock.calls[0]![0];
+    expect(call.path).toBe("/chaos/manager/api/rest/v2/gamedays");
+    expect(call.params).toMatchObject({ search: "failover", organizationIdentifier: "default", projectIdentifier: "test-project" });
+    expect(result).toEqual({ items: [{ identity: "gd1", name: "Q3 failover" }], total: 1 });
+  });
+
+  it("schedules a run at a future time", async () => {
+    const request = vi.fn().mockResolvedValue({ runId: "run-1" });
+
+    await registry.dispatchExecute(makeClient(request), "chaos_gameday", "schedule_run", {
+      gameday_id: "gd1",
+      name: "October run",
+      scheduled_at: "1791000000000",
+    });
+
+    const call = request.mock.calls[0]![0];
+    expect(call.method).toBe("POST");
+    expect(call.path).toBe("/chaos/manager/api/rest/v2/gamedays/gd1/runs");
+    expect(call.body).toMatchObject({ name: "October run", scheduledAt: 1791000000000 });
+  });
+
+  it("gets a GameDay run by gameday_id and run_id", async () => {
+    const request = vi.fn().mockResolvedValue({ runId: "run-1", status: "COMPLETED" });
+
+    await registry.dispatch(makeClient(request), "chaos_gameday_run", "get", { gameday_id: "gd1", run_id: "run-1" });
+
+    expect(request.mock.calls[0]![0].path).toBe("/chaos/manager/api/rest/v2/gamedays/gd1/runs/run-1");
+  });
+});
@@ -0,0 +1,116 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  };
+}
+
+function makeClient(request: ReturnType<typeof vi.fn>): HarnessClient {
+  return { request, account: "test-account" } as unknown as HarnessClient;
+}
+
+type Call = { method: string; path: string; body: Record<string, unknown> };
+
+const KEYWORD_RESPONSE = {
+  file_matches: [
+    {
+      repo_path: "test-account/default/test-project/payments",
+      file_name: "src/retry.go",
+      repo_branch: "main",
+      language: "Go",
+      matches: [{ line_num: 42, fragments: [{ pre: "func ", match: "retryWithBackoff", post: "(ctx context.Context) {" }] }],
+    },
+  ],
+  stats: { total_files: 1, total_matches: 1 },
+};
+
+describe("code_search", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "repositories" }));
+
+  it("searches every repo in the configured project by keyword", async () => {
+    const request = vi.fn().mockResolvedValue(KEYWORD_RESPONSE);
+
+    const result = await registry.dispatch(makeClient(request), "code_search", "list", { query: "retryWithBackoff" });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.method).toBe("POST");
+    expect(call.path).toBe("/code/api/v1/search");
+    expect(call.body).toEqual({
+      query: "retryWithBackoff",
+      max_result_count: 50,
+      enable_regex: false,
+      space_paths: ["test-account/default/test-project"],
+      recursive: true,
+    });
+    expect(result).toEqual({
+      items: [{
+        repo_id: "payments",
+        repo_path: "test-account/default/test-project/payments",
+        file_path: "src/retry.go",
+        ref: "main",
+        language: "Go",
+        matches: [{ line: 42, snippet: "func retryWithBackoff(ctx context.Context) {" }],
+      }],
+      total: 1,
+      total_matches: 1,
+    });
+  });
+
+  it("narrows to one repo and widens to the org when only org_id is passed", async () => {
+    const request = vi.fn().mockResolvedValue({ file_matches: [] });
+
+    await registry.dispatch(makeClient(request), "code_search", "list", { query: "TODO", repo_id: "payments", regex: true, max_results: 10 });
+    await registry.dispatch(makeClient(request), "code_search", "list", { query: "TODO", org_id: "platform" });
+
+    expect((request.mock.calls[0]![0] as Call).body).toEqual({
+      query: "TODO",
+      max_result_count: 10,
+      enable_regex: true,
+      repo_paths: ["test-account/default/test-project/payments"],
+    });
+    expect((request.mock.calls[1]![0] as Call).body).toMatchObject({ space_paths: ["test-account/platform"] });
+  });
+
+  it("runs semantic search against a single repo", async () => {
+    const request = vi.fn().mockResolvedValue({
+      results: [{ repo_path: "test-account/default/test-project/payments", file_path: "src/retry.go", start_line: 40, end_line: 60, content: "func retryWithBackoff", score: 0.91 }],
+    });
+
+    const result = await registry.dispatch(makeClient(request), "code_search", "list", {
+      query: "where do we retry failed payments?",
+      repo_id: "payments",
+      search_type: "semantic",
+    }) as { items: Array<Record<string, unknown>> };
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.path).toBe("/code/api/v1/repos/payments/semantic/search");
+    expect(call.body).toEqual({ query: "where do we retry failed payments?", max_result_count: 50 });
+    expect(result.items[0]).toMatchObject({ repo_id: "payments", file_path: "src/retry.go", score: 0.91 });
+  });
+
+  it("requires query, and repo_id for semantic search", async () => {
+    const request = vi.fn();
+    await expect(registry.dispatch(makeClient(request), "code_search", "list", {})).rejects.toThrow(/query/);
+    await expect(registry.dispatch(makeClient(request), "code_search", "list", { query: "x", search_type: "semantic" }))
+      .rejects.toThrow(/pass repo_id/);
+    expect(request).not.toHaveBeenCalled();
+  });
+});
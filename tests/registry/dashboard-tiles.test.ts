@@ -0,0 +1,103 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  };
+}
+
+function makeClient(request: ReturnType<typeof vi.fn>): HarnessClient {
+  return { request, account: "test-account" } as unknown as HarnessClient;
+}
+
+
+type Call = { method: string; path: string; body: Record<string, unknown> };
+
+const QUERY = {
+  model: "CD",
+  view: "pipeline_execution_summary_cd",
+  fields: ["pipeline_execution_summary_cd.start_week", "pipeline_execution_summary_cd.count"],
+  filters: { "pipeline_execution_summary_cd.status": "SUCCESS" },
+};
+
+describe("dashboard tiles", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "dashboards" }));
+
+  it("creates a dashboard in the shared folder by default", async () => {
+    const request = vi.fn().mockResolvedValue({ resource: { id: "42", title: "Delivery" } });
+
+    const result = await registry.dispatch(makeClient(request), "dashboard", "create", { body: { title: "Delivery" } });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.path).toBe("/dashboard/v1/dashboards");
+    expect(call.body).toEqual({ title: "Delivery", resourceIdentifier: "shared" });
+    expect(result).toEqual({ id: "42", title: "Delivery" });
+  });
+
+  it("creates a tile from a query definition", async () => {
+    const request = vi.fn().mockResolvedValue({ resource: { id: "t1" } });
+
+    await registry.dispatch(makeClient(request), "dashboard_tile", "create", {
+      dashboard_id: "42",
+      body: { title: "Deployment frequency", type: "looker_line", query: QUERY },
+    });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.method).toBe("POST");
+    expect(call.path).toBe("/dashboard/v1/dashboards/42/tiles");
+    expect(call.body).toMatchObject({ title: "Deployment frequency", type: "looker_line", query: { ...QUERY, limit: 500 } });
+  });
+
+  it("updates a tile in place", async () => {
+    const request = vi.fn().mockResolvedValue({ resource: { id: "t1" } });
+
+    await registry.dispatch(makeClient(request), "dashboard_tile", "update", {
+      dashboard_id: "42",
+      tile_id: "t1",
+      body: { title: "Weekly deployments", query: { ...QUERY, limit: 52 } },
+    });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.method).toBe("PATCH");
+    expect(call.path).toBe("/dashboard/v1/dashboards/42/tiles/t1");
+    expect(call.body).toMatchObject({ type: "looker_column", query: { limit: 52 } });
+  });
+
+  it("rejects incomplete queries and unknown visualizations", async () => {
+    const request = vi.fn();
+    await expect(registry.dispatch(makeClient(request), "dashboard_tile", "create", {
+      dashboard_id: "42",
+      body: { title: "x", query: { model: "CD", view: "v" } },
+    })).rejects.toThrow(/requires query/);
+    await expect(registry.dispatch(makeClient(request), "dashboard_tile", "create", {
+      dashboard_id: "42",
+      body: { title: "x", type: "sankey", query: QUERY },
+    })).rejects.toThrow(/Unsupported tile type/);
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it("lists tiles", async () => {
+    const request = vi.fn().mockResolvedValue({ resource: [{ id: "t1", title: "Deployments", query: QUERY }] });
+
+    const result = await registry.dispatch(makeClient(request), "dashboard_tile", "list", { dashboard_id: "7" });
+
+    expect(result).toEqual({ items: [{ id: "t1", title: "Deployments", query: QUERY }], total: 1 });
+  });
+});
@@ -0,0 +1,162 @@
+import { describe, expect, it, vi } from "vitest";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+import { Registry } from "../../src/registry/index.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  } as Config;
+}
+
+function makeClient(requestFn: (...args: unknown[]) => unknown): HarnessClient {
+  return {
+    request: requestFn,
+    account: "test-account",
+  } as unknown as HarnessClient;
+}
+
+describe("execution list filters", () => {
+  it("maps status, branch, my_deployments, and sort to query params", async () => {
+    const registry = new Registry(makeConfig());
+    const mockRequest = vi.fn().mockResolvedValue({ data: { content: [], totalElements: 0 } });
+
+    await registry.dispatch(makeClient(mockRequest), "execution", "list", {
+      status: ["Failed", "Aborted"],
+      branch: "main",
+      my_deployments: true,
+      sort: "startTs,ASC",
+    });
+
+    const call = mockRequest.mock.calls[0]![0] as { path: string; params: Record<string, unknown>; body: Record<string, unknown> };
+    expect(call.path).toBe("/pipeline/api/pipelines/execution/summary");
+    expect(call.params).toMatchObject({ status: ["Failed", "Aborted"], branch: "main", myDeployments: true, sort: "startTs,ASC" });
+    expect(call.body).not.toHaveProperty("triggerTypes");
+    expect(call.body).not.toHaveProperty("timeRange");
+  });
+
+  it("builds trigger type and time range filters into the PipelineExecution body", async () => {
+    const registry = new Registry(makeConfig());
+    const mockRequest = vi.fn().mockResolvedValue({ data: { content: [] } });
+
+    await registry.dispatch(makeClient(mockRequest), "execution", "list", {
+      trigger_type: "WEBHOOK,SCHEDULER_CRON",
+      start_time: "2025-07-10T00:00:00Z",
+      end_time: 1752192000000,
+    });
+
+    const call = mockRequest.mock.calls[0]![0] as { body: Record<string, unknown> };
+    expect(call.body).toMatchObject({
+      filterType: "PipelineExecution",
+      triggerTypes: ["WEBHOOK", "SCHEDULER_CRON"],
+      timeRange: { startTime: Date.parse("2025-07-10T00:00:00Z"), endTime: 1752192000000 },
+    });
+  });
+
+  it("defaults the end of the time range to now when only start_time is given", async () => {
+    const registry = new Registry(makeConfig());
+    const mockRequest = vi.fn().mockResolvedValue({ data: { content: [] } });
+    const before = Date.now();
+
+    await registry.dispatch(makeClient(mockRequest), "execution", "list", { start_time: "2025-07-10T00:00:00Z" });
+
+    const timeRange = (mockRequest.mock.calls[0]![0] as { body: { timeRange: { endTime: number } } }).body.timeRange;
+    expect(timeRange.endTime).toBeGreaterThanOrEqual(before);
+  });
+});
+
+describe("execution notes", () => {
+  it("sends notes as a query param on PUT and accepts a bare string body", async () => {
+    const registry = new Registry(makeConfig());
+    const mockRequest = vi.fn().mockResolvedValue({ data: { notes: "root cause: expired connector secret" } });
+
+    const result = await registry.dispatch(makeClient(mockRequest), "execution_notes", "update", {
+      execution_id: "exec-1",
+      body: { notes: "root cause: expired connector secret" },
+    });
+    await registry.dispatch(makeClient(mockRequest), "execution_notes", "update", {
+      execution_id: "exec-1",
+      body: "second note",
+    });
+
+    expect(mockRequest.mock.calls[0]![0]).toMatchObject({
+      method: "PUT",
+      path: "/pipeline/api/pipelines/execution/exec-1/notes",
+      params: expect.objectContaining({ notes: "root cause: expired connector secret" }),
+      body: undefined,
+    });
+    expect((mockRequest.mock.calls[1]![0] as { params: Record<string, unknown> }).params.notes).toBe("second note");
+    expect(result).toMatchObject({ notes: "root cause: expired connector secret" });
+  });
+
+  it("rejects an update without notes instead of clearing them", async () => {
+    const registry = new Registry(makeConfig());
+    const mockRequest = vi.fn();
+
+    await expect(registry.dispatch(makeClient(mockRequest), "execution_notes", "update", {
+      execution_id: "exec-1",
+      body: {},
+    })).rejects.toThrow(/body\.notes/);
+    expect(mockRequest).not.toHaveBeenCalled();
+  });
+});
+
+describe("cross-project rollup", () => {
+  function rollupRequest(failingProject?: string) {
+    return vi.fn(async (opts: { path: string; params?: Record<string, unknown> }) => {
+      if (opts.path === "/ng/api/projects") {
+        return { data: { totalElements: 2, content: [
+          { project: { identifier: "web", orgIdentifier: "default" } },
+          { project: { identifier: "api", orgIdentifier: "default" } },
+        ] } };
+      }
+      const project = opts.params?.projectIdentifier;
+      if (project === failingProject) throw new Error("forbidden");
+      return { data: { content: [{ planExecutionId: `${String(project)}-1`, status: "Failed" }], totalElements: 1 } };
+    });
+  }
+
+  it("lists executions once per project in the org and merges them", async () => {
+    const registry = new Registry(makeConfig());
+    const mockRequest = rollupRequest();
+
+    const result = await registry.dispatch(makeClient(mockRequest), "execution", "list", {
+      resource_scope: "org",
+      org_id: "default",
+      status: "Failed",
+    }) as { items: Array<Record<string, unknown>>; total: number; rollup: Record<string, unknown> };
+
+    expect(mockRequest.mock.calls[0]![0]).toMatchObject({ path: "/ng/api/projects", params: { orgIdentifier: "default" } });
+    expect(result.items.map((i) => [i.projectIdentifier, i.planExecutionId])).toEqual([["web", "web-1"], ["api", "api-1"]]);
+    expect(result.total).toBe(2);
+    expect(result.rollup).toMatchObject({ scope: "org", org_id: "default", projects_queried: 2, truncated: false });
+    for (const call of mockRequest.mock.calls.slice(1)) {
+      expect((call[0] as { params: Record<string, unknown> }).params).toMatchObject({ orgIdentifier: "default" });
+    }
+  });
+
+  it("reports per-project failures without failing the rollup", async () => {
+    const registry = new Registry(makeConfig());
+
+    const result = await registry.dispatch(makeClient(rollupRequest("api")), "pipeline", "list", {
+      resource_scope: "account",
+    }) as { items: unknown[]; rollup: { errors?: Array<Record<string, unknown>> } };
+
+    expect(result.items).toHaveLength(1);
+    expect(result.rollup.errors).toEqual([{ org_id: "default", project_id: "api", error: expect.stringContaining("forbidden") }]);
+  });
+});
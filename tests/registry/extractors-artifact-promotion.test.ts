@@ -0,0 +1,36 @@
+import { describe, it, expect } from "vitest";
+import { artifactPromotionExtract } from "../../src/registry/extractors.js";
+
+const RAW = {
+  data: {
+    envBuildIdAndInstanceCountInfoList: [
+      { envId: "dev", envName: "Dev", buildIdAndInstanceCountList: [{ buildId: "413", count: 2 }] },
+      { envId: "qa", envName: "QA", buildIdAndInstanceCountList: [{ buildId: "412", count: 1 }, { buildId: "413", count: 3 }] },
+      { envId: "prod", envName: "Prod", buildIdAndInstanceCountList: [{ buildId: "registry/checkout:411", count: 6 }] },
+    ],
+  },
+};
+
+describe("artifactPromotionExtract", () => {
+  it("reports the current artifact per environment and flags mixed versions", () => {
+    const result = artifactPromotionExtract(RAW) as { items: Array<Record<string, unknown>>; total: number };
+    expect(result.total).toBe(3);
+    expect(result.items[1]).toEqual({
+      environment_id: "qa",
+      environment_name: "QA",
+      current_artifact: "413",
+      mixed_versions: true,
+      artifacts: [{ build_id: "413", instances: 3 }, { build_id: "412", instances: 1 }],
+    });
+    expect(result).not.toHaveProperty("deployed_in");
+  });
+
+  it("lists where a given build is running, matching image tags", () => {
+    expect(artifactPromotionExtract(RAW, { artifact: "412" })).toMatchObject({ deployed_in: ["QA"], not_deployed_in: ["Dev", "Prod"] });
+    expect(artifactPromotionExtract(RAW, { artifact: "411" })).toMatchObject({ deployed_in: ["Prod"] });
+  });
+
+  it("returns an empty list when the service runs nowhere", () => {
+    expect(artifactPromotionExtract({ data: {} })).toEqual({ items: [], total: 0 });
+  });
+});
@@ -0,0 +1,30 @@
+import { describe, it, expect } from "vitest";
+import { connectorCatalogueExtract } from "../../src/registry/extractors.js";
+
+describe("connectorCatalogueExtract", () => {
+  it("groups types by category with docs links and a total", () => {
+    const result = connectorCatalogueExtract({
+      status: "SUCCESS",
+      data: {
+        catalogue: [
+          { category: "CLOUD_PROVIDER", connectors: ["K8sCluster", "Aws", "Gcp"] },
+          { category: "CODE_REPO", connectors: ["Github", "Git"] },
+          { category: "SOMETHING_NEW", connectors: ["Novel"] },
+        ],
+      },
+    }) as { categories: Array<Record<string, unknown>>; total_types: number; _hint: string };
+
+    expect(result.total_types).toBe(6);
+    expect(result.categories[0]).toEqual({
+      category: "CLOUD_PROVIDER",
+      docs_url: "https://developer.harness.io/docs/platform/connectors/cloud-providers/",
+      types: ["K8sCluster", "Aws", "Gcp"],
+    });
+    expect(result.categories[2].docs_url).toBe("https://developer.harness.io/docs/category/connectors");
+    expect(result._hint).toContain("example_search");
+  });
+
+  it("falls back to ngExtract when the response has no catalogue", () => {
+    expect(connectorCatalogueExtract({ data: { other: 1 } })).toEqual({ other: 1 });
+  });
+});
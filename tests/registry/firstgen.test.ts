@@ -0,0 +1,95 @@
+import { describe, it, expect, vi } from "vitest";
+import { firstgenToolset } from "../../src/registry/toolsets/firstgen.js";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    LOG_LEVEL: "info",
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_AUTO_APPROVE_RISK: "none",
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    ...overrides,
+  } as Config;
+}
+
+function makeClient(response: unknown): { client: HarnessClient; request: ReturnType<typeof vi.fn> } {
+  const request = vi.fn().mockResolvedValue(response);
+  return { client: { request, account: "test-account" } as unknown as HarnessClient, request };
+}
+
+describe("firstgenToolset", () => {
+  it("is opt-in and read-only", () => {
+    expect(firstgenToolset.optIn).toBe(true);
+    for (const res of firstgenToolset.resources) {
+      for (const spec of Object.values(res.operations)) {
+        expect(spec?.operationPolicy.risk).toBe("read");
+      }
+    }
+  });
+
+  it("is not loaded by default and loads with +firstgen", () => {
+    expect(new Registry(makeConfig()).getAllResourceTypes()).not.toContain("cg_application");
+    expect(new Registry(makeConfig({ HARNESS_TOOLSETS: "+firstgen" })).getAllResourceTypes()).toContain("cg_application");
+  });
+});
+
+describe("FirstGen dispatch", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "firstgen" }));
+
+  it("lists applications via CG GraphQL with accountId and page variables", async () => {
+    const { client, request } = makeClient({
+      data: { applications: { nodes: [{ id: "app1", name: "Legacy" }], pageInfo: { total: 41, hasMore: true } } },
+    });
+
+    const result = await registry.dispatch(client, "cg_application", "list", { page: 2, size: 10 }) as Record<string, unknown>;
+
+    const call = request.mock.calls[0][0] as { method: string; path: string; body: { variables: Record<string, unknown> }; headerBasedScoping?: boolean };
+    expect(call.method).toBe("POST");
+    expect(call.path).toBe("/gateway/api/graphql?accountId=test-account");
+    expect(call.body.variables).toEqual({ limit: 10, offset: 20 });
+    expect(result.items).toEqual([{ id: "app1", name: "Legacy" }]);
+    expect(result.total).toBe(41);
+    expect(result.has_more).toBe(true);
+  });
+
+  it("filters workflows by app_id", async () => {
+    const { client, request } = makeClient({ data: { workflows: { nodes: [], pageInfo: { total: 0, hasMore: false } } } });
+
+    await registry.dispatch(client, "cg_workflow", "list", { app_id: "app1" });
+
+    const call = request.mock.calls[0][0] as { body: { variables: Record<string, unknown> } };
+    expect(call.body.variables.filters).toEqual([{ application: { operator: "EQUALS", values: ["app1"] } }]);
+  });
+
+  it("gets a deployment by execution_id", async () => {
+    const { client, request } = makeClient({
+      data: { execution: { id: "exec1", status: "FAILED", workflow: { id: "wf1", name: "Deploy" } } },
+    });
+
+    const result = await registry.dispatch(client, "cg_deployment", "get", { execution_id: "exec1" }) as Record<string, unknown>;
+
+    const call = request.mock.calls[0][0] as { body: { variables: Record<string, unknown> } };
+    expect(call.body.variables).toEqual({ executionId: "exec1" });
+    expect(result.status).toBe("FAILED");
+  });
+
+  it("surfaces GraphQL errors returned with HTTP 200", async () => {
+    const { client } = makeClient({ data: { execution: null }, errors: [{ message: "Execution does not exist" }] });
+
+    await expect(registry.dispatch(client, "cg_deployment", "get", { execution_id: "missing" }))
+      .rejects.toThrow(/Execution does not exist/);
+  });
+});
@@ -0,0 +1,87 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import { expandGitDetails } from "../../src/registry/git-details.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "web",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_TOOLSETS: "services,environments,infrastructure,pipelines,templates",
+    LOG_LEVEL: "info",
+  } as Config;
+}
+
+function makeClient() {
+  const request = vi.fn().mockResolvedValue({ status: "SUCCESS", data: {} });
+  return { client: { request, account: "test-account" } as unknown as HarnessClient, request };
+}
+
+describe("expandGitDetails", () => {
+  it("flattens snake_case and Harness camelCase keys without overriding explicit values", () => {
+    expect(expandGitDetails({
+      branch: "main",
+      git_details: { branch: "dev", repoName: "config", load_from_fallback: true },
+    })).toEqual({ branch: "main", repo_name: "config", load_from_fallback_branch: true });
+  });
+
+  it("rejects unknown keys and non-objects", () => {
+    expect(() => expandGitDetails({ git_details: { brnach: "dev" } })).toThrow(/Unknown git_details field 'brnach'/);
+    expect(() => expandGitDetails({ git_details: "dev" })).toThrow(/must be an object/);
+  });
+});
+
+describe("git details on remote entities", () => {
+  it("sends branch, repo, and fallback query params on service get", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient();
+
+    await registry.dispatch(client, "service", "get", {
+      service_id: "checkout",
+      git_details: { branch: "feature/canary", repo_name: "harness-config", load_from_fallback_branch: true },
+    });
+
+    expect(request.mock.calls[0]![0].params).toMatchObject({
+      branch: "feature/canary",
+      repoName: "harness-config",
+      loadFromFallbackBranch: true,
+    });
+  });
+
+  it("sends commit details on environment create", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient();
+
+    await registry.dispatch(client, "environment", "create", {
+      body: { identifier: "qa", name: "QA", type: "PreProduction" },
+      git_details: { store_type: "REMOTE", connector_ref: "github", repo_name: "config", branch: "main", file_path: ".harness/qa.yaml", commit_msg: "Add qa" },
+    });
+
+    expect(request.mock.calls[0]![0].params).toMatchObject({
+      storeType: "REMOTE",
+      connectorRef: "github",
+      repoName: "config",
+      branch: "main",
+      filePath: ".harness/qa.yaml",
+      commitMsg: "Add qa",
+    });
+  });
+
+  it("adds loadFromFallbackBranch to pipeline get", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient();
+
+    await registry.dispatch(client, "pipeline", "get", { pipeline_id: "deploy", branch: "dev", load_from_fallback_branch: true });
+
+    expect(request.mock.calls[0]![0].params).toMatchObject({ branch: "dev", loadFromFallbackBranch: true });
+  });
+});
@@ -0,0 +1,126 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  };
+}
+
+function makeClient(request: ReturnType<typeof vi.fn>): HarnessClient {
+  return { request, account: "test-account" } as unknown as HarnessClient;
+}
+
+
+type Call = { method: string; path: string; params: Record<string, unknown>; body: Record<string, unknown> };
+
+const EXISTING = {
+  status: "SUCCESS",
+  data: {
+    identifier: "docker-hub",
+    packageType: "DOCKER",
+    url: "https://pkg.harness.io/test-account/docker-hub",
+    registrySize: "1.2 GB",
+    config: { type: "UPSTREAM", source: "Dockerhub", authType: "Anonymous" },
+    cleanupPolicy: [{ name: "old", expireDays: 90 }],
+  },
+};
+
+describe("HAR upstream proxies", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "registries" }));
+
+  it("lists only UPSTREAM registries in the project space", async () => {
+    const request = vi.fn().mockResolvedValue({ data: { registries: [{ identifier: "docker-hub" }], itemCount: 1 } });
+
+    const result = await registry.dispatch(makeClient(request), "upstream_proxy", "list", { package_type: "DOCKER" });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.path).toBe("/har/api/v1/spaces/test-account/default/test-project/+/registries");
+    expect(call.params).toMatchObject({ type: "UPSTREAM", package_type: "DOCKER" });
+    expect(result).toMatchObject({ items: [{ identifier: "docker-hub" }], total: 1 });
+  });
+
+  it("creates a proxy from flat params with credentials in a secret", async () => {
+    const request = vi.fn().mockResolvedValue({ data: { identifier: "maven-central" } });
+
+    await registry.dispatch(makeClient(request), "upstream_proxy", "create", {
+      body: {
+        identifier: "maven-central",
+        package_type: "MAVEN",
+        source: "Custom",
+        url: "https://repo.acme.dev/maven2",
+        username: "ci-bot",
+        secret_identifier: "acme_maven_password",
+        cleanup_policies: [{ name: "snapshots", expireDays: 14, versionPrefix: ["SNAPSHOT"] }],
+      },
+    });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.method).toBe("POST");
+    expect(call.path).toBe("/har/api/v1/registry");
+    expect(call.params).toMatchObject({ space_ref: "test-account/default/test-project" });
+    expect(call.body).toMatchObject({
+      identifier: "maven-central",
+      packageType: "MAVEN",
+      parentRef: "test-account/default/test-project",
+      config: {
+        type: "UPSTREAM",
+        source: "Custom",
+        url: "https://repo.acme.dev/maven2",
+        authType: "UserPassword",
+        auth: { authType: "UserPassword", userName: "ci-bot", secretIdentifier: "acme_maven_password", secretSpacePath: "test-account/default/test-project" },
+      },
+      cleanupPolicy: [{ name: "snapshots", expireDays: 14, versionPrefix: ["SNAPSHOT"] }],
+    });
+  });
+});
+
+describe("HAR registry cleanup policies", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "registries" }));
+
+  it("lists cleanup policies from the registry", async () => {
+    const request = vi.fn().mockResolvedValue(EXISTING);
+
+    const result = await registry.dispatch(makeClient(request), "registry_cleanup_policy", "list", { registry_id: "docker-hub" });
+
+    expect((request.mock.calls[0]![0] as Call).path).toBe("/har/api/v1/registry/test-account/default/test-project/docker-hub/+");
+    expect(result).toEqual({ registry_id: "docker-hub", items: [{ name: "old", expireDays: 90 }], total: 1 });
+  });
+
+  it("replaces policies while preserving the rest of the registry", async () => {
+    const request = vi.fn()
+      .mockResolvedValueOnce(EXISTING)
+      .mockResolvedValueOnce({ data: { ...EXISTING.data, cleanupPolicy: [{ name: "new", expireDays: 30 }] } });
+
+    const result = await registry.dispatch(makeClient(request), "registry_cleanup_policy", "update", {
+      registry_id: "docker-hub",
+      body: { policies: [{ name: "new", expireDays: 30 }] },
+    });
+
+    const put = request.mock.calls[1]![0] as Call;
+    expect(put.method).toBe("PUT");
+    expect(put.body).toEqual({
+      identifier: "docker-hub",
+      packageType: "DOCKER",
+      config: { type: "UPSTREAM", source: "Dockerhub", authType: "Anonymous" },
+      cleanupPolicy: [{ name: "new", expireDays: 30 }],
+    });
+    expect(result).toMatchObject({ items: [{ name: "new", expireDays: 30 }] });
+  });
+});
@@ -313,3 +313,23 @@ describe("pr_comment bodyBuilder translation", () => {
     }));
   });
 });
+
+describe("pull_request merge_check", () => {
+  it("always sends a dry run with rule evaluation", async () => {
+    const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "pull-requests" }));
+    const mockRequest = vi.fn().mockResolvedValue({ mergeable: true });
+    const client = makeClient(mockRequest);
+
+    await registry.dispatchExecute(client, "pull_request", "merge_check", {
+      repo_id: "rc_tools",
+      pr_number: 42,
+      method: "squash",
+    });
+
+    expect(mockRequest).toHaveBeenCalledWith(expect.objectContaining({
+      method: "POST",
+      path: "/code/api/v1/repos/rc_tools/pullreq/42/merge",
+      body: { method: "squash", dry_run: true, dry_run_rules: true },
+    }));
+  });
+});
@@ -0,0 +1,117 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import { rawApiToolset } from "../../src/registry/toolsets/raw-api.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  };
+}
+
+function makeClient(request: ReturnType<typeof vi.fn>): HarnessClient {
+  return { request, account: "test-account" } as unknown as HarnessClient;
+}
+
+
+type Call = { method: string; path: string; params: Record<string, unknown> };
+
+describe("rawApiToolset", () => {
+  it("is opt-in and read-only", () => {
+    expect(rawApiToolset.optIn).toBe(true);
+    for (const res of rawApiToolset.resources) {
+      expect(Object.keys(res.operations)).toEqual(["get"]);
+      expect(res.operations.get?.method).toBe("GET");
+      expect(res.operations.get?.operationPolicy.risk).toBe("read");
+    }
+  });
+
+  it("is not loaded by default and loads with +raw-api", () => {
+    expect(new Registry(makeConfig()).getAllResourceTypes()).not.toContain("api_request");
+    expect(new Registry(makeConfig({ HARNESS_TOOLSETS: "+raw-api" })).getAllResourceTypes()).toContain("api_request");
+  });
+});
+
+describe("api_request dispatch", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "raw-api" }));
+
+  it("GETs the path with query params and project scope from config", async () => {
+    const request = vi.fn().mockResolvedValue({ data: { edition: "ENTERPRISE" } });
+
+    const result = await registry.dispatch(makeClient(request), "api_request", "get", {
+      path: "/ng/api/licenses/account",
+      query: { moduleType: "CD", tags: ["a", "b"] },
+    });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.method).toBe("GET");
+    expect(call.path).toBe("/ng/api/licenses/account?moduleType=CD&tags=a&tags=b");
+    expect(call.params).toMatchObject({ orgIdentifier: "default", projectIdentifier: "test-project" });
+    expect(result).toEqual({ data: { edition: "ENTERPRISE" } });
+  });
+
+  it("omits org and project at account scope", async () => {
+    const request = vi.fn().mockResolvedValue({});
+
+    await registry.dispatch(makeClient(request), "api_request", "get", { path: "/ng/api/accounts/x", resource_scope: "account" });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.params.orgIdentifier).toBeUndefined();
+    expect(call.params.projectIdentifier).toBeUndefined();
+  });
+
+  it.each([
+    ["https://evil.example.com/ng/api", /absolute path/],
+    ["//evil.example.com/x", /absolute path/],
+    ["ng/api/x", /absolute path/],
+    ["/ng/api/../../admin", /dot segments/],
+    ["/ng/.%2e/admin", /dot segments/],
+    ["/ng/%2e./admin", /dot segments/],
+    ["/ng/%2E%2e/admin", /dot segments/],
+    ["/ng/%2e/api", /dot segments/],
+    ["/ng/api%2f..%2fadmin", /dot segments/],
+    ["/ng/api%5Cadmin", /dot segments/],
+  ])("rejects path %s", async (path, message) => {
+    const request = vi.fn();
+    await expect(registry.dispatch(makeClient(request), "api_request", "get", { path })).rejects.toThrow(message);
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it("rejects account scope overrides in the query", async () => {
+    const request = vi.fn();
+    await expect(registry.dispatch(makeClient(request), "api_request", "get", {
+      path: "/ng/api/projects",
+      query: { accountIdentifier: "other" },
+    })).rejects.toThrow(/accountIdentifier/);
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it("truncates responses over max_response_chars", async () => {
+    const request = vi.fn().mockResolvedValue({ data: "x".repeat(5_000) });
+
+    const result = await registry.dispatch(makeClient(request), "api_request", "get", {
+      path: "/ng/api/big",
+      max_response_chars: 2_000,
+    }) as Record<string, unknown>;
+
+    expect(result.truncated).toBe(true);
+    expect(result.max_response_chars).toBe(2_000);
+    expect((result.preview as string).length).toBe(2_000);
+    expect(result.response_chars).toBeGreaterThan(5_000);
+  });
+});
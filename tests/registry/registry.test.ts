@@ -1,9 +1,13 @@
 import { describe, it, expect, vi, beforeEach } from "vitest";
-import { Registry } from "../../src/registry/index.js";
+import { Registry, ALL_TOOLSET_NAMES } from "../../src/registry/index.js";
 import type { Config } from "../../src/config.js";
 import type { HarnessClient } from "../../src/client/harness-client.js";
 import { HarnessApiError } from "../../src/utils/errors.js";
+import { requiresConfirmation } from "../../src/registry/types.js";
 import { registerAllTools } from "../../src/tools/index.js";
+import { mkdtempSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
 
 function makeConfig(overrides: Partial<Config> = {}): Config {
   return {
@@ -137,6 +141,40 @@ describe("Registry", () => {
       expect(ds.resourceType).toBe("eval_dataset");
     });
 
+    it("recommended preset enables its toolsets with reduced access", () => {
+      const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "recommended" }));
+      expect(registry.getAllToolsets().map((t) => t.name).sort()).toEqual(["ccm", "logs", "pipelines", "pull-requests"]);
+      // pipelines: reads and execute actions, no definition writes
+      expect(registry.supportsOperation("pipeline", "get")).toBe(true);
+      expect(registry.supportsOperation("pipeline", "create")).toBe(false);
+      expect(registry.getExecuteActions("pipeline")?.run).toBeDefined();
+      // ccm: read-only
+      expect(registry.supportsOperation("cost_perspective", "list")).toBe(true);
+      expect(registry.supportsOperation("cost_perspective", "delete")).toBe(false);
+      for (const spec of Object.values(registry.getExecuteActions("cost_perspective") ?? {})) {
+        expect(spec.operationPolicy.risk).toBe("read");
+      }
+      // the shared toolset definitions are untouched
+      expect(new Registry(makeConfig()).supportsOperation("pipeline", "create")).toBe(true);
+    });
+
+    it("naming a preset toolset directly restores full access", () => {
+      const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "recommended,pipelines" }));
+      expect(registry.supportsOperation("pipeline", "create")).toBe(true);
+      expect(registry.supportsOperation("cost_perspective", "create")).toBe(false);
+    });
+
+    it("a preset is the base for +/- modifiers", () => {
+      const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "recommended,-ccm,+services" }));
+      expect(registry.getAllToolsets().map((t) => t.name).sort()).toEqual(["logs", "pipelines", "pull-requests", "services"]);
+    });
+
+    it("all preset enables every toolset, opt-in ones included", () => {
+      const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "all" }));
+      expect(registry.getAllToolsets().map((t) => t.name).sort()).toEqual([...ALL_TOOLSET_NAMES].sort());
+      expect(registry.supportsOperation("pipeline", "create")).toBe(true);
+    });
+
     it("registers tools when enabled toolsets have no resources for some operations", () => {
       const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "logs" }));
       const server = {
@@ -1055,6 +1093,37 @@ describe("Registry", () => {
       expect(JSON.stringify(call.body)).toContain("feature/my-fix");
     });
 
+    it("pipeline execute with stage_identifiers posts a RunStageRequestDTO to /stages", async () => {
+      const mockRequest = vi.fn().mockResolvedValue({ data: { planExecution: { uuid: "exec-stages" } } });
+      const client = makeClient(mockRequest);
+
+      await registry.dispatchExecute(client, "pipeline", "run", {
+        pipeline_id: "my-pipeline",
+        stage_identifiers: "build, deploy_qa",
+        expression_values: { "<+pipeline.stages.ci.variables.tag>": "v1.2" },
+        inputs: "pipeline:\n  identifier: my-pipeline\n",
+      });
+
+      const call = mockRequest.mock.calls[0][0];
+      expect(call.path).toBe("/pipeline/api/pipeline/execute/my-pipeline/stages");
+      expect(JSON.parse(call.body)).toEqual({
+        runtimeInputYaml: "pipeline:\n  identifier: my-pipeline\n",
+        stageIdentifiers: ["build", "deploy_qa"],
+        expressionValues: { "<+pipeline.stages.ci.variables.tag>": "v1.2" },
+      });
+    });
+
+    it("pipeline execute rejects expression_values without stage_identifiers", async () => {
+      const mockRequest = vi.fn();
+      const client = makeClient(mockRequest);
+
+      await expect(registry.dispatchExecute(client, "pipeline", "run", {
+        pipeline_id: "my-pipeline",
+        expression_values: { "<+pipeline.variables.tag>": "v1" },
+      })).rejects.toThrow(/stage_identifiers/);
+      expect(mockRequest).not.toHaveBeenCalled();
+    });
+
     it("pipeline update with yamlPipeline sends raw YAML string as body with Content-Type header and returns openInHarness", async () => {
       const yaml = "pipeline:\n  name: Test\n  identifier: test_pipeline\n  stages: []";
       const mockRequest = vi.fn().mockResolvedValue({
@@ -1233,6 +1302,16 @@ describe("Registry", () => {
     });
   });
 
+  describe("role_assignment writes", () => {
+    it("require confirmation for both create and delete", () => {
+      const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "access_control" }));
+      const def = registry.getResource("role_assignment");
+      expect(def.operations.create!.operationPolicy.risk).toBe("high_write");
+      expect(requiresConfirmation(def.operations.create!.operationPolicy.risk)).toBe(true);
+      expect(requiresConfirmation(def.operations.delete!.operationPolicy.risk)).toBe(true);
+    });
+  });
+
   describe("resolved account ID propagation", () => {
     it("passes the resolved account ID to pathBuilder and deep links", async () => {
       const mockRequest = vi.fn().mockResolvedValue({
@@ -2020,4 +2099,31 @@ describe("Registry", () => {
     });
   });
 
+  describe("disk cache (diskCacheTtlMs)", () => {
+    it("serves cached catalog responses across registry instances when HARNESS_CACHE_DIR is set", async () => {
+      const dir = mkdtempSync(join(tmpdir(), "harness-registry-cache-"));
+      try {
+        const config = makeConfig({ HARNESS_TOOLSETS: "connectors", HARNESS_CACHE_DIR: dir });
+        const first = vi.fn().mockResolvedValue({ data: { catalogue: [{ category: "CLOUD_PROVIDER" }] } });
+        await new Registry(config).dispatch(makeClient(first), "connector_catalogue", "list", {});
+        expect(first).toHaveBeenCalledOnce();
+
+        // A fresh registry simulates a restart — the response must come from disk
+        const second = vi.fn().mockResolvedValue({ data: { catalogue: [] } });
+        const result = await new Registry(config).dispatch(makeClient(second), "connector_catalogue", "list", {});
+        expect(second).not.toHaveBeenCalled();
+        expect(result).toMatchObject({ categories: [{ category: "CLOUD_PROVIDER" }] });
+      } finally {
+        rmSync(dir, { recursive: true, force: true });
+      }
+    });
+
+    it("always hits the API when HARNESS_CACHE_DIR is unset", async () => {
+      const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "connectors" }));
+      const request = vi.fn().mockResolvedValue({ data: { catalogue: [] } });
+      await registry.dispatch(makeClient(request), "connector_catalogue", "list", {});
+      await registry.dispatch(makeClient(request), "connector_catalogue", "list", {});
+      expect(request).toHaveBeenCalledTimes(2);
+    });
+  });
 });
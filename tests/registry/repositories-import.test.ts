@@ -0,0 +1,95 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "test-project",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    LOG_LEVEL: "info",
+    ...overrides,
+  };
+}
+
+function makeClient(request: ReturnType<typeof vi.fn>): HarnessClient {
+  return { request, account: "test-account" } as unknown as HarnessClient;
+}
+
+
+type Call = { method: string; path: string; body: Record<string, unknown> };
+
+describe("repository import", () => {
+  const registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "repositories" }));
+
+  it("nests flat provider credentials under provider", async () => {
+    const request = vi.fn().mockResolvedValue({ identifier: "payments", importing: true });
+
+    const result = await registry.dispatchExecute(makeClient(request), "repository", "import", {
+      identifier: "payments",
+      provider: "GitHub",
+      provider_repo: "acme/payments",
+      username: "octo",
+      token: "ghp_example",
+    });
+
+    const call = request.mock.calls[0]![0] as Call;
+    expect(call.method).toBe("POST");
+    expect(call.path).toBe("/code/api/v1/repos/import");
+    expect(call.body).toMatchObject({
+      identifier: "payments",
+      provider: { type: "github", username: "octo", password: "ghp_example" },
+      provider_repo: "acme/payments",
+      pipelines: "ignore",
+    });
+    expect(result).toMatchObject({ importing: true });
+  });
+
+  it("accepts a full body with a provider object", async () => {
+    const request = vi.fn().mockResolvedValue({});
+
+    await registry.dispatchExecute(makeClient(request), "repository", "import", {
+      body: {
+        identifier: "infra",
+        provider: { type: "gitlab", host: "https://gitlab.acme.dev", password: "glpat" },
+        provider_repo: "platform/infra",
+        pipelines: "convert",
+      },
+    });
+
+    expect((request.mock.calls[0]![0] as Call).body).toMatchObject({
+      identifier: "infra",
+      provider: { type: "gitlab", host: "https://gitlab.acme.dev", password: "glpat" },
+      pipelines: "convert",
+    });
+  });
+
+  it("rejects unknown providers and missing provider_repo before calling the API", async () => {
+    const request = vi.fn();
+    await expect(registry.dispatchExecute(makeClient(request), "repository", "import", { identifier: "x", provider: "svn", provider_repo: "a/b" }))
+      .rejects.toThrow(/requires provider/);
+    await expect(registry.dispatchExecute(makeClient(request), "repository", "import", { identifier: "x", provider: "github" }))
+      .rejects.toThrow(/provider_repo/);
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it("reads import progress", async () => {
+    const request = vi.fn().mockResolvedValue({ state: "failed", failure: "authentication required" });
+
+    const result = await registry.dispatchExecute(makeClient(request), "repository", "import_status", { repo_id: "payments" });
+
+    expect((request.mock.calls[0]![0] as Call).path).toBe("/code/api/v1/repos/payments/import-progress");
+    expect(result).toEqual({ state: "failed", failure: "authentication required" });
+  });
+});
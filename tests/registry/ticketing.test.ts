@@ -0,0 +1,69 @@
+import { describe, it, expect, vi } from "vitest";
+import { parse as parseYaml } from "yaml";
+import { Registry } from "../../src/registry/index.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+const config = {
+  HARNESS_API_KEY: "pat.test",
+  HARNESS_ACCOUNT_ID: "test-account",
+  HARNESS_BASE_URL: "https://app.harness.io",
+  HARNESS_ORG: "default",
+  HARNESS_PROJECT: "ops",
+  HARNESS_TOOLSETS: "connectors",
+  LOG_LEVEL: "info",
+} as Config;
+
+function makeClient(response: unknown) {
+  const request = vi.fn().mockResolvedValue(response);
+  return { client: { request, account: "test-account" } as unknown as HarnessClient, request };
+}
+
+describe("jira_issue", () => {
+  it("gets an issue's status through the Jira connector", async () => {
+    const { client, request } = makeClient({
+      status: "SUCCESS",
+      data: { key: "OPS-7", url: "https://acme.atlassian.net/browse/OPS-7", fields: { Status: { name: "In Progress" }, Summary: "Checkout 500s" } },
+    });
+    const result = await new Registry(config).dispatch(client, "jira_issue", "get", { issue_key: "OPS-7", connector_ref: "jira" });
+    expect(result).toMatchObject({ key: "OPS-7", status: "In Progress", summary: "Checkout 500s" });
+    expect(request.mock.calls[0]![0]).toMatchObject({ path: "/ng/api/jira/issue", params: expect.objectContaining({ issueKey: "OPS-7", connectorRef: "jira" }) });
+  });
+
+  it("creates an issue by running a JiraCreate step dynamically", async () => {
+    const { client, request } = makeClient({ execution_details: { execution_id: "exec-1", status: "RUNNING" } });
+    const result = await new Registry(config).dispatchExecute(client, "jira_issue", "create", {
+      pipeline_id: "ticket_shell",
+      body: { connector_ref: "jira", project_key: "OPS", issue_type: "Incident", summary: "Checkout 500s", fields: { Priority: "High" } },
+    });
+    expect(result).toEqual({ execution_id: "exec-1", status: "RUNNING" });
+    const call = request.mock.calls[0]![0];
+    expect(call.path).toBe("/v1/orgs/default/projects/ops/pipelines/ticket_shell/execute/dynamic");
+    const step = parseYaml(call.body.yaml).pipeline.stages[0].stage.spec.execution.steps[0].step;
+    expect(step).toMatchObject({ type: "JiraCreate", spec: { connectorRef: "jira", projectKey: "OPS", issueType: "Incident" } });
+    expect(step.spec.fields).toEqual([{ name: "Summary", value: "Checkout 500s" }, { name: "Priority", value: "High" }]);
+  });
+
+  it("requires a dynamic-execution pipeline and the issue fields", async () => {
+    const { client, request } = makeClient({});
+    const registry = new Registry(config);
+    await expect(registry.dispatchExecute(client, "jira_issue", "create", { body: { connector_ref: "jira" } }))
+      .rejects.toThrow(/pipeline_id is required/);
+    await expect(registry.dispatchExecute(client, "jira_issue", "create", { pipeline_id: "p", body: { connector_ref: "jira" } }))
+      .rejects.toThrow(/body.project_key is required/);
+    expect(request).not.toHaveBeenCalled();
+  });
+});
+
+describe("servicenow_ticket", () => {
+  it("creates an incident by running a ServiceNowCreate step", async () => {
+    const { client, request } = makeClient({ execution_details: { execution_id: "exec-2", status: "RUNNING" } });
+    await new Registry(config).dispatchExecute(client, "servicenow_ticket", "create", {
+      pipeline_id: "ticket_shell",
+      body: { connector_ref: "snow", short_description: "Checkout 500s", fields: { urgency: "2" } },
+    });
+    const step = parseYaml(request.mock.calls[0]![0].body.yaml).pipeline.stages[0].stage.spec.execution.steps[0].step;
+    expect(step).toMatchObject({ type: "ServiceNowCreate", spec: { connectorRef: "snow", ticketType: "INCIDENT" } });
+    expect(step.spec.fields).toEqual([{ name: "short_description", value: "Checkout 500s" }, { name: "urgency", value: "2" }]);
+  });
+});
@@ -0,0 +1,168 @@
+import { describe, it, expect } from "vitest";
+import {
+  checkResourcesAgainstSpec,
+  compareBodySchema,
+  findOperation,
+  normalizeApiPath,
+  requestBodySchema,
+} from "../../scripts/openapi-drift-lib.js";
+
+const SPEC = {
+  openapi: "3.0.1",
+  paths: {
+    "/v1/orgs/{org}/projects": {
+      post: {
+        requestBody: { $ref: "#/components/requestBodies/ProjectBody" },
+      },
+    },
+    "/services/{serviceIdentifier}": {
+      put: {
+        requestBody: {
+          content: {
+            "application/json": {
+              schema: {
+                allOf: [
+                  { $ref: "#/components/schemas/Base" },
+                  { type: "object", properties: { yaml: { type: "string" } }, required: ["yaml"] },
+                ],
+              },
+            },
+          },
+        },
+      },
+    },
+  },
+  components: {
+    requestBodies: {
+      ProjectBody: {
+        content: { "application/json": { schema: { $ref: "#/components/schemas/ProjectRequest" } } },
+      },
+    },
+    schemas: {
+      ProjectRequest: {
+        type: "object",
+        properties: { project: { $ref: "#/components/schemas/Project" } },
+      },
+      Project: {
+        type: "object",
+        required: ["identifier", "name"],
+        properties: {
+          identifier: { type: "string" },
+          name: { type: "string" },
+          color: { type: "string" },
+          tags: { type: "object", additionalProperties: { type: "string" } },
+        },
+      },
+      Base: {
+        type: "object",
+        required: ["identifier"],
+        properties: { identifier: { type: "string" }, replicas: { type: "integer" } },
+      },
+    },
+  },
+};
+
+describe("normalizeApiPath", () => {
+  it("erases placeholder names and trailing slashes", () => {
+    expect(normalizeApiPath("/ng/api/services/{serviceId}/")).toBe("/ng/api/services/{}");
+  });
+});
+
+describe("findOperation", () => {
+  it("matches a spec path that is a suffix of the gateway-prefixed registry path", () => {
+    const match = findOperation(SPEC, "PUT", "/ng/api/services/{service_id}");
+    expect(match?.path).toBe("/services/{serviceIdentifier}");
+  });
+
+  it("returns undefined when the method is not defined for the path", () => {
+    expect(findOperation(SPEC, "DELETE", "/ng/api/services/{id}")).toBeUndefined();
+  });
+});
+
+describe("requestBodySchema", () => {
+  it("resolves request body refs and flattens allOf", () => {
+    const op = SPEC.paths["/services/{serviceIdentifier}"].put;
+    const schema = requestBodySchema(SPEC, op);
+    expect(Object.keys(schema.properties).sort()).toEqual(["identifier", "replicas", "yaml"]);
+    expect(schema.required.sort()).toEqual(["identifier", "yaml"]);
+  });
+
+  it("reads Swagger 2 body parameters", () => {
+    const swagger = {
+      definitions: { Thing: { type: "object", properties: { id: { type: "string" } } } },
+    };
+    const schema = requestBodySchema(swagger, { parameters: [{ in: "body", schema: { $ref: "#/definitions/Thing" } }] });
+    expect(schema.properties.id.type).toBe("string");
+  });
+});
+
+describe("compareBodySchema", () => {
+  it("reports missing, mistyped, and undeclared required fields", () => {
+    const schema = SPEC.components.schemas.Project;
+    const drift = compareBodySchema(SPEC, [
+      { name: "identifier", type: "string", required: true, description: "" },
+      { name: "tags", type: "array", required: false, description: "" },
+      { name: "colour", type: "string", required: false, description: "" },
+    ], schema);
+    expect(drift).toEqual([
+      { field: "tags", issue: "declared array, upstream object" },
+      { field: "colour", issue: "not in upstream schema" },
+      { field: "name", issue: "required upstream but not declared" },
+    ]);
+  });
+
+  it("treats integer as number and yaml as string", () => {
+    const drift = compareBodySchema(SPEC, [
+      { name: "identifier", type: "string", required: true, description: "" },
+      { name: "replicas", type: "number", required: false, description: "" },
+      { name: "yaml", type: "yaml", required: true, description: "" },
+    ], requestBodySchema(SPEC, SPEC.paths["/services/{serviceIdentifier}"].put));
+    expect(drift).toEqual([]);
+  });
+});
+
+describe("checkResourcesAgainstSpec", () => {
+  it("unwraps bodyWrapperKey and lists endpoints the spec does not cover", () => {
+    const resources = [
+      {
+        resourceType: "project",
+        operations: {
+          create: {
+            method: "POST",
+            path: "/ng/api/v1/orgs/{org}/projects",
+            bodyWrapperKey: "project",
+            bodySchema: {
+              description: "Project",
+              fields: [
+                { name: "identifier", type: "string", required: true, description: "" },
+                { name: "name", type: "string", required: true, description: "" },
+                { name: "color", type: "number", required: false, description: "" },
+              ],
+            },
+          },
+        },
+      },
+      {
+        resourceType: "widget",
+        operations: {
+          create: {
+            method: "POST",
+            path: "/widgets",
+            bodySchema: { description: "Widget", fields: [] },
+          },
+        },
+      },
+    ];
+    const { drift, unmatched } = checkResourcesAgainstSpec(SPEC, resources);
+    expect(drift).toEqual([
+      {
+        resourceType: "project",
+        operation: "create",
+        specPath: "/v1/orgs/{org}/projects",
+        field: "color",
+        issue: "declared number, upstream string",
+      },
+    ]);
+    expect(unmatched).toEqual([{ resourceType: "widget", operation: "create", path: "/widgets" }]);
+  });
+});
@@ -1,5 +1,5 @@
 import { describe, it, expect, vi, afterEach } from "vitest";
-import { SearchManager } from "../../src/search/manager.js";
+import { SearchManager, MIN_ENTITY_REFRESH_INTERVAL_MS } from "../../src/search/manager.js";
 import { NullSearchProvider } from "../../src/search/null-provider.js";
 import { LocalSearchProvider } from "../../src/search/local-provider.js";
 import { RemoteSearchProvider } from "../../src/search/remote-provider.js";
@@ -190,6 +190,88 @@ describe("SearchManager", () => {
     });
   });
 
+  describe("background entity refresh", () => {
+    function makeRegistry() {
+      return {
+        supportsOperation: () => true,
+        getResource: () => ({ scope: "project" }),
+        orgId: "default",
+        projectId: "proj",
+        dispatch: vi.fn().mockResolvedValue({ items: [{ identifier: "p1", name: "P1" }] }),
+      };
+    }
+
+    function availableNullProvider() {
+      vi.spyOn(NullSearchProvider.prototype, "isAvailable").mockReturnValue(true);
+      const indexSpy = vi.spyOn(NullSearchProvider.prototype, "index").mockResolvedValue();
+      const evictSpy = vi.spyOn(NullSearchProvider.prototype, "evictExpired").mockReturnValue();
+      return { indexSpy, evictSpy };
+    }
+
+    afterEach(() => {
+      vi.useRealTimers();
+    });
+
+    it("does nothing when the interval is 0", () => {
+      vi.useFakeTimers();
+      const { indexSpy } = availableNullProvider();
+      const mgr = new SearchManager(makeConfig() as never);
+      const registry = makeRegistry();
+
+      mgr.startEntityRefresh(registry as never, { account: "acct" } as never, 0);
+      vi.advanceTimersByTime(10 * 60_000);
+
+      expect(registry.dispatch).not.toHaveBeenCalled();
+      expect(indexSpy).not.toHaveBeenCalled();
+    });
+
+    it("re-lists tier-1 entities on each interval, evicting expired items first", async () => {
+      vi.useFakeTimers();
+      const { indexSpy, evictSpy } = availableNullProvider();
+      const mgr = new SearchManager(makeConfig() as never);
+      const registry = makeRegistry();
+
+      mgr.startEntityRefresh(registry as never, { account: "acct" } as never, 120_000);
+      await vi.advanceTimersByTimeAsync(120_000);
+
+      expect(evictSpy).toHaveBeenCalledOnce();
+      // pipeline, service, environment, connector
+      expect(registry.dispatch).toHaveBeenCalledTimes(4);
+      expect(indexSpy.mock.calls[0][0]).toMatchObject({ corpus: "entities", ttlMs: 240_000 });
+
+      mgr.stopEntityRefresh();
+      await vi.advanceTimersByTimeAsync(240_000);
+      expect(registry.dispatch).toHaveBeenCalledTimes(4);
+    });
+
+    it("raises intervals below the minimum", async () => {
+      vi.useFakeTimers();
+      availableNullProvider();
+      const mgr = new SearchManager(makeConfig() as never);
+      const registry = makeRegistry();
+
+      mgr.startEntityRefresh(registry as never, { account: "acct" } as never, 1_000);
+      await vi.advanceTimersByTimeAsync(MIN_ENTITY_REFRESH_INTERVAL_MS - 1);
+      expect(registry.dispatch).not.toHaveBeenCalled();
+      await vi.advanceTimersByTimeAsync(1);
+      expect(registry.dispatch).toHaveBeenCalled();
+      mgr.stopEntityRefresh();
+    });
+
+    it("shares an in-flight refresh between overlapping callers", async () => {
+      availableNullProvider();
+      const mgr = new SearchManager(makeConfig() as never);
+      const registry = makeRegistry();
+      const client = { account: "acct" } as never;
+
+      const first = mgr.refreshEntities(registry as never, client);
+      const second = mgr.refreshEntities(registry as never, client);
+      expect(second).toBe(first);
+      await first;
+      expect(registry.dispatch).toHaveBeenCalledTimes(4);
+    });
+  });
+
   afterEach(() => {
     vi.unstubAllGlobals();
     vi.restoreAllMocks();
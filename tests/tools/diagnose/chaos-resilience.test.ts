@@ -0,0 +1,75 @@
+import { describe, it, expect } from "vitest";
+import { chaosResilienceHandler } from "../../../src/tools/diagnose/chaos-resilience.js";
+import { makeContext } from "./helpers.js";
+
+const EXPERIMENTS = {
+  items: [
+    {
+      experimentID: "e1",
+      name: "pod-delete-payments",
+      recentExperimentRunDetails: [
+        { phase: "Completed", resiliencyScore: 100, updatedAt: "1767268800000" },
+        { phase: "Completed", resiliencyScore: 50, updatedAt: "1767182400000" },
+      ],
+    },
+    { experimentID: "e2", name: "network-loss-cart", recentExperimentRunDetails: [{ phase: "Completed", resiliencyScore: 40 }] },
+    { experimentID: "e3", name: "cpu-hog-search", recentExperimentRunDetails: [] },
+  ],
+  total: 3,
+};
+
+const PROBES = {
+  items: [
+    { probeId: "p1", name: "checkout-http", type: "httpProbe", isEnabled: true, referencedBy: 2, recentProbeRuns: [{ status: { verdict: "Failed", description: "503" }, executedByExperiment: { experimentName: "network-loss-cart" } }] },
+    { probeId: "p2", name: "legacy-cmd", type: "cmdProbe", isEnabled: false, referencedBy: 0, recentProbeRuns: [] },
+  ],
+  total: 2,
+};
+
+describe("chaosResilienceHandler", () => {
+  it("summarizes scores, gaps, and probe health", async () => {
+    const ctx = makeContext({
+      dispatchMap: { chaos_experiment: { list: EXPERIMENTS }, chaos_probe: { list: PROBES } },
+    });
+
+    const result = await chaosResilienceHandler.diagnose(ctx);
+
+    expect(result.experiments_reviewed).toBe(3);
+    expect(result.average_resiliency_score).toBe(70);
+    expect(result.below_threshold).toEqual([expect.objectContaining({ experiment_id: "e2", last_resiliency_score: 40 })]);
+    expect(result.never_run).toEqual([{ experiment_id: "e3", name: "cpu-hog-search" }]);
+    expect((result.experiments as Array<Record<string, unknown>>)[0]).toMatchObject({
+      average_resiliency_score: 75,
+      last_run_at: "2026-01-01T12:00:00.000Z",
+    });
+    expect(result.probes).toMatchObject({
+      total: 2,
+      disabled: ["legacy-cmd"],
+      unused: ["legacy-cmd"],
+      failing: [expect.objectContaining({ probe_id: "p1", last_verdict_description: "503" })],
+    });
+  });
+
+  it("keeps experiment scores when the probe list fails", async () => {
+    const ctx = makeContext({
+      dispatchMap: { chaos_experiment: { list: EXPERIMENTS }, chaos_probe: { list: new Error("HTTP 403: Forbidden") } },
+    });
+
+    const result = await chaosResilienceHandler.diagnose(ctx);
+
+    expect(result.experiments_reviewed).toBe(3);
+    expect(result.probes_error).toBe("HTTP 403: Forbidden");
+  });
+
+  it("reports a single probe's recent verdicts when probe_id is given", async () => {
+    const ctx = makeContext({
+      input: { probe_id: "p1" },
+      dispatchMap: { chaos_probe: { get: PROBES.items[0] } },
+    });
+
+    const result = await chaosResilienceHandler.diagnose(ctx);
+
+    expect(result.probe).toMatchObject({ probe_id: "p1", last_verdict: "Failed" });
+    expect(result.recent_runs).toEqual([expect.objectContaining({ verdict: "Failed", experiment: "network-loss-cart" })]);
+  });
+});
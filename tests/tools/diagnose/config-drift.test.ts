@@ -0,0 +1,250 @@
+import { describe, it, expect, vi } from "vitest";
+import { configDriftHandler } from "../../../src/tools/diagnose/config-drift.js";
+import { makeClient, makeContext, makeRegistry } from "./helpers.js";
+import type { HarnessClient } from "../../../src/client/harness-client.js";
+
+const SERVICE_YAML = [
+  "service:",
+  "  identifier: api",
+  "  serviceDefinition:",
+  "    type: Kubernetes",
+  "    spec:",
+  "      variables:",
+  "        - name: replicas",
+  "          type: String",
+  "          value: \"1\"",
+  "        - name: log_level",
+  "          type: String",
+  "          value: info",
+  "      manifests:",
+  "        - manifest:",
+  "            identifier: values",
+  "            type: Values",
+  "            spec:",
+  "              store:",
+  "                type: Harness",
+  "                spec:",
+  "                  files:",
+  "                    - /values/default.yaml",
+].join("\n");
+
+function override(type: string, env: string, spec: Record<string, unknown>, extra: Record<string, unknown> = {}) {
+  return { identifier: `${env}_${type}`, type, environmentRef: env, spec, ...extra };
+}
+
+function registryWith(overridesByEnv: Record<string, unknown[]>) {
+  const registry = makeRegistry();
+  (registry.dispatch as ReturnType<typeof vi.fn>).mockImplementation(
+    async (_c: HarnessClient, type: string, _op: string, input: Record<string, unknown>) => {
+      if (type === "service") return { service: { identifier: "api", yaml: SERVICE_YAML } };
+      if (type === "service_override") {
+        const items = overridesByEnv[input.environment_id as string];
+        if (!items) throw new Error(`Environment ${String(input.environment_id)} not found`);
+        return { items, total: items.length };
+      }
+      throw new Error(`unexpected ${type}`);
+    },
+  );
+  return registry;
+}
+
+describe("configDriftHandler", () => {
+  it("requires service_id", async () => {
+    const ctx = makeContext({ args: { environment_ids: ["dev", "prod"] } });
+    await expect(configDriftHandler.diagnose(ctx)).rejects.toThrow("service_id");
+  });
+
+  it("requires at least two environments", async () => {
+    const ctx = makeContext({ args: { service_id: "api", environment_ids: "dev" } });
+    await expect(configDriftHandler.diagnose(ctx)).rejects.toThrow("at least two");
+  });
+
+  it("reports no drift when environments render identically", async () => {
+    const ctx = makeContext({
+      registry: registryWith({ dev: [], prod: [] }),
+      args: { service_id: "api", environment_ids: "dev,prod" },
+    });
+
+    const result = await configDriftHandler.diagnose(ctx);
+
+    expect(result.drift_detected).toBe(false);
+    expect(result.variables).toEqual({ drifted: [], consistent_count: 2 });
+    expect(result.manifests).toEqual({ drifted: [], consistent_count: 1 });
+  });
+
+  it("applies overrides by precedence and reports drifted variables with sources", async () => {
+    const ctx = makeContext({
+      registry: registryWith({
+        dev: [override("ENV_GLOBAL_OVERRIDE", "dev", { variables: [{ name: "log_level", value: "debug" }] })],
+        prod: [
+          override("ENV_GLOBAL_OVERRIDE", "prod", { variables: [{ name: "replicas", value: "2" }] }),
+          override("ENV_SERVICE_OVERRIDE", "prod", { variables: [{ name: "replicas", value: "5" }] }, { serviceRef: "api" }),
+          // Service override for a different service must be ignored
+          override("ENV_SERVICE_OVERRIDE", "prod", { variables: [{ name: "log_level", value: "trace" }] }, { serviceRef: "web", identifier: "web_override" }),
+        ],
+      }),
+      args: { service_id: "api", environment_ids: ["dev", "prod"] },
+    });
+
+    const result = await configDriftHandler.diagnose(ctx);
+    const drifted = (result.variables as { drifted: Array<Record<string, unknown>> }).drifted;
+
+    expect(result.drift_detected).toBe(true);
+    expect(drifted).toEqual([
+      { name: "log_level", values: { dev: "debug", prod: "info" }, sources: { dev: "ENV_GLOBAL_OVERRIDE", prod: "service" } },
+      { name: "replicas", values: { dev: "1", prod: "5" }, sources: { dev: "service", prod: "ENV_SERVICE_OVERRIDE" } },
+    ]);
+    expect((result.applied_overrides as Record<string, string[]>).prod).not.toContain("web_override");
+  });
+
+  it("detects manifest source drift and entries missing from an environment", async () => {
+    const ctx = makeContext({
+      registry: registryWith({
+        dev: [],
+        prod: [override("ENV_GLOBAL_OVERRIDE", "prod", {
+          manifests: [{ manifest: { identifier: "values", type: "Values", spec: { store: { type: "Github", spec: { connectorRef: "gh", branch: "main", paths: ["prod.yaml"] } } } } }],
+          configFiles: [{ configFile: { identifier: "app_conf", spec: { store: { type: "Harness", spec: { files: ["/conf/prod.conf"] } } } } }],
+        })],
+      }),
+      args: { service_id: "api", environment_ids: ["dev", "prod"] },
+    });
+
+    const result = await configDriftHandler.diagnose(ctx);
+    const manifests = (result.manifests as { drifted: Array<Record<string, unknown>> }).drifted;
+    const configFiles = (result.config_files as { drifted: Array<Record<string, unknown>> }).drifted;
+
+    expect(manifests[0].name).toBe("values");
+    expect((manifests[0].values as Record<string, Record<string, unknown>>).prod.store_type).toBe("Github");
+    expect(configFiles[0].missing_in).toEqual(["dev"]);
+    expect(result.hint).toContain("file_store");
+  });
+
+  it("compares manifest and config file content rather than where it is stored", async () => {
+    const githubValues = (branch: string, path: string) => [{
+      manifest: { identifier: "values", type: "Values", spec: { store: { type: "Github", spec: { connectorRef: "gh", repoName: "deploy", branch, paths: [path] } } } },
+    }];
+    const configFile = (file: string) => [{ configFile: { identifier: "app_conf", spec: { store: { type: "Harness", spec: { files: [file] } } } } }];
+    const client = makeClient();
+    (client.request as ReturnType<typeof vi.fn>).mockImplementation(async (options: { path: string; params: Record<string, unknown> }) => {
+      if (options.path === "/ng/api/scm/fileContent") {
+        return { data: { content: options.params.branch === "release" ? "replicas: 3" : "replicas: 1" } };
+      }
+      return { data: { content: "port=8080" } };
+    });
+    const ctx = makeContext({
+      client,
+      registry: registryWith({
+        dev: [override("ENV_GLOBAL_OVERRIDE", "dev", { manifests: githubValues("main", "dev.yaml"), configFiles: configFile("/conf/dev.conf") })],
+        prod: [override("ENV_GLOBAL_OVERRIDE", "prod", { manifests: githubValues("release", "dev.yaml"), configFiles: configFile("/conf/prod.conf") })],
+      }),
+      args: { service_id: "api", environment_ids: ["dev", "prod"] },
+    });
+
+    const result = await configDriftHandler.diagnose(ctx);
+    const manifests = result.manifests as { drifted: Array<Record<string, unknown>>; consistent_count: number };
+
+    expect(manifests.drifted).toHaveLength(1);
+    expect(manifests.drifted[0].compared_by).toBe("content");
+    expect((manifests.drifted[0].values as Record<string, Record<string, unknown>>).prod.content_sha256).toMatch(/^[0-9a-f]{64}$/);
+    // Different File Store paths holding the same content do not drift
+    expect(result.config_files).toEqual({ drifted: [], consistent_count: 1 });
+    expect(client.request).toHaveBeenCalledWith(expect.objectContaining({ path: "/ng/api/file-store/files/%2Fconf%2Fprod.conf/content" }));
+    expect(result.unread_content).toBeUndefined();
+  });
+
+  it("falls back to comparing sources when content cannot be read", async () => {
+    const ctx = makeContext({
+      registry: registryWith({
+        dev: [],
+        prod: [override("ENV_GLOBAL_OVERRIDE", "prod", {
+          manifests: [{ manifest: { identifier: "values", type: "Values", spec: { store: { type: "S3", spec: { connectorRef: "aws", paths: ["v.yaml"] } } } } }],
+        })],
+      }),
+      args: { service_id: "api", environment_ids: ["dev", "prod"] },
+    });
+
+    const result = await configDriftHandler.diagnose(ctx);
+    const manifests = (result.manifests as { drifted: Array<Record<string, unknown>> }).drifted;
+
+    expect(manifests[0].compared_by).toBe("source");
+    expect((result.unread_content as Record<string, string>)["manifests.values@prod"]).toContain("S3 stores cannot be read");
+    expect(result.note).toContain("compared by source");
+  });
+
+  it("reads every page of overrides", async () => {
+    const filler = Array.from({ length: 150 }, (_, i) =>
+      override("ENV_SERVICE_OVERRIDE", "prod", {}, { serviceRef: "web", identifier: `web_${i}` }));
+    const all = [...filler, override("ENV_GLOBAL_OVERRIDE", "prod", { variables: [{ name: "replicas", value: "4" }] })];
+    const registry = makeRegistry();
+    (registry.dispatch as ReturnType<typeof vi.fn>).mockImplementation(
+      async (_c: HarnessClient, type: string, _op: string, input: Record<string, unknown>) => {
+        if (type === "service") return { service: { identifier: "api", yaml: SERVICE_YAML } };
+        const items = input.environment_id === "prod" ? all : [];
+        const size = input.size as number;
+        const page = input.page as number;
+        return { items: items.slice(page * size, (page + 1) * size), total: items.length };
+      },
+    );
+
+    const result = await configDriftHandler.diagnose(makeContext({
+      registry,
+      args: { service_id: "api", environment_ids: ["dev", "prod"] },
+    }));
+    const drifted = (result.variables as { drifted: Array<Record<string, unknown>> }).drifted;
+
+    expect(drifted.map((d) => d.name)).toEqual(["replicas"]);
+    expect(result.truncated_overrides).toBeUndefined();
+  });
+
+  it("reports environments whose overrides exceed the page limit", async () => {
+    const registry = makeRegistry();
+    (registry.dispatch as ReturnType<typeof vi.fn>).mockImplementation(
+      async (_c: HarnessClient, type: string, _op: string, input: Record<string, unknown>) => {
+        if (type === "service") return { service: { identifier: "api", yaml: SERVICE_YAML } };
+        if (input.environment_id === "dev") return { items: [], total: 0 };
+        const items = Array.from({ length: input.size as number }, (_, i) =>
+          override("ENV_SERVICE_OVERRIDE", "prod", {}, { serviceRef: "web", identifier: `web_${String(input.page)}_${i}` }));
+        return { items, total: 1_000_000 };
+      },
+    );
+
+    const result = await configDriftHandler.diagnose(makeContext({
+      registry,
+      args: { service_id: "api", environment_ids: ["dev", "prod"] },
+    }));
+
+    expect(result.truncated_overrides).toEqual(["prod"]);
+    expect(result.note).toContain("Only the first 2000 overrides were read for prod");
+  });
+
+  it("ignores infra overrides unless infra_id is given", async () => {
+    const overrides = {
+      dev: [],
+      prod: [override("INFRA_GLOBAL_OVERRIDE", "prod", { variables: [{ name: "replicas", value: "9" }] }, { infraIdentifier: "k8s" })],
+    };
+
+    const without = await configDriftHandler.diagnose(makeContext({
+      registry: registryWith(overrides),
+      args: { service_id: "api", environment_ids: ["dev", "prod"] },
+    }));
+    expect(without.drift_detected).toBe(false);
+
+    const withInfra = await configDriftHandler.diagnose(makeContext({
+      registry: registryWith(overrides),
+      args: { service_id: "api", environment_ids: ["dev", "prod"], infra_id: "k8s" },
+    }));
+    expect(withInfra.drift_detected).toBe(true);
+  });
+
+  it("records override fetch errors without failing the comparison", async () => {
+    const ctx = makeContext({
+      registry: registryWith({ dev: [] }),
+      args: { service_id: "api", environment_ids: ["dev", "missing"] },
+    });
+
+    const result = await configDriftHandler.diagnose(ctx);
+
+    expect(result.override_errors).toEqual({ missing: "Environment missing not found" });
+    expect(result.note).toContain("service definition only");
+  });
+});
@@ -1,5 +1,6 @@
 import { describe, it, expect } from "vitest";
-import { connectorHandler } from "../../../src/tools/diagnose/connector.js";
+import { collectSecretRefs, connectorHandler, type ProbableCause } from "../../../src/tools/diagnose/connector.js";
+import { HarnessApiError } from "../../../src/utils/errors.js";
 import { makeContext } from "./helpers.js";
 
 describe("connectorHandler", () => {
@@ -141,4 +142,76 @@ describe("connectorHandler", () => {
       "https://app.harness.io/ng/account/test-account/all/orgs/myorg/projects/myproj/setup/connectors/my-conn",
     );
   });
+
+  it("collects scoped secret refs at any depth and skips expressions and connector refs", () => {
+    const refs = collectSecretRefs({
+      connectorRef: "account.aws",
+      authentication: { spec: { spec: { passwordRef: "org.git_pw", usernameRef: "<+pipeline.variables.user>" } } },
+      apiKeyRef: "api_key",
+    });
+
+    expect(refs).toEqual([
+      { field: "authentication.spec.spec.passwordRef", ref: "org.git_pw", scope: "org", identifier: "git_pw" },
+      { field: "apiKeyRef", ref: "api_key", scope: "project", identifier: "api_key" },
+    ]);
+  });
+
+  it("ranks a missing secret and unresolvable delegate selectors ahead of network errors", async () => {
+    const ctx = makeContext({
+      input: { resource_id: "git-conn" },
+      dispatchMap: {
+        connector: {
+          get: {
+            connector: {
+              name: "Git",
+              identifier: "git-conn",
+              type: "Github",
+              spec: { url: "https://github.com/acme", tokenRef: "account.gh_token", delegateSelectors: ["build-farm", "ghost"] },
+            },
+          },
+        },
+        secret: { get: new HarnessApiError("Secret not found", 404) },
+        delegate: {
+          list: [
+            { name: "d1", tags: ["build-farm"], connected: false },
+            { name: "d2", tags: ["other"], connected: true },
+          ],
+        },
+      },
+      executeMap: {
+        connector: { test_connection: { status: "FAILURE", errorSummary: "Connection timed out" } },
+      },
+    });
+
+    const result = await connectorHandler.diagnose(ctx);
+    const causes = result.probable_causes as ProbableCause[];
+
+    expect(result.secrets).toEqual([{ field: "tokenRef", ref: "account.gh_token", scope: "account", exists: false }]);
+    expect(result.delegate_selectors).toEqual([
+      { selector: "build-farm", matching_delegates: ["d1"], connected_delegates: [] },
+      { selector: "ghost", matching_delegates: [], connected_delegates: [] },
+    ]);
+    expect(causes.map((c) => c.priority)).toEqual(["high", "high", "high", "medium"]);
+    expect(causes[0]!.cause).toContain("account.gh_token");
+    expect(causes[3]!.cause).toContain("not reachable");
+  });
+
+  it("classifies credential failures and reports no causes for a healthy connector", async () => {
+    const failing = makeContext({
+      input: { resource_id: "c1" },
+      dispatchMap: { connector: { get: { connector: { name: "C", identifier: "c1", type: "DockerRegistry" } } } },
+      executeMap: {
+        connector: { test_connection: { status: "FAILURE", errors: [{ message: "401 Unauthorized" }] } },
+      },
+    });
+    const failed = await connectorHandler.diagnose(failing);
+    expect((failed.probable_causes as ProbableCause[])[0]).toMatchObject({ priority: "high", cause: expect.stringContaining("Credentials") });
+
+    const healthy = makeContext({
+      input: { resource_id: "c1" },
+      dispatchMap: { connector: { get: { connector: { name: "C", identifier: "c1", type: "DockerRegistry" } } } },
+      executeMap: { connector: { test_connection: { status: "SUCCESS" } } },
+    });
+    expect((await connectorHandler.diagnose(healthy)).probable_causes).toEqual([]);
+  });
 });
@@ -0,0 +1,48 @@
+import { describe, it, expect, afterEach } from "vitest";
+import { mkdtempSync, readFileSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { costExportHandler } from "../../../src/tools/diagnose/cost-export.js";
+import { makeConfig, makeContext } from "./helpers.js";
+
+describe("costExportHandler", () => {
+  let dir: string | undefined;
+  afterEach(() => {
+    if (dir) rmSync(dir, { recursive: true, force: true });
+    dir = undefined;
+  });
+
+  it("requires HARNESS_OUTPUT_DIR", async () => {
+    const ctx = makeContext({ input: { perspective_id: "p1" } });
+    await expect(costExportHandler.diagnose(ctx)).rejects.toThrow("HARNESS_OUTPUT_DIR");
+  });
+
+  it("rejects unsupported formats", async () => {
+    const ctx = { ...makeContext({ input: { perspective_id: "p1" }, args: { format: "parquet" } }), config: makeConfig({ HARNESS_OUTPUT_DIR: "/tmp" }) };
+    await expect(costExportHandler.diagnose(ctx)).rejects.toThrow("Unsupported export format 'parquet'");
+  });
+
+  it("writes the breakdown as CSV and returns a resource link", async () => {
+    dir = mkdtempSync(join(tmpdir(), "harness-cost-export-"));
+    const base = makeContext({
+      input: { perspective_id: "p1", group_by: "region", time_filter: "LAST_MONTH" },
+      dispatchMap: {
+        cost_breakdown: {
+          list: { items: [{ name: "us-east-1", id: "us-east-1", cost: 100.25, costTrend: 3, __typename: "Row" }, { name: "eu-west-1", id: "eu-west-1", cost: 50 }], total: 2 },
+        },
+      },
+    });
+    const ctx = { ...base, config: makeConfig({ HARNESS_OUTPUT_DIR: dir }) };
+
+    const result = await costExportHandler.diagnose(ctx);
+
+    expect(result).toMatchObject({ rows: 2, total_cost: 150.25, columns: ["name", "id", "cost", "costTrend"] });
+    expect(readFileSync(result.file_path as string, "utf-8")).toBe(
+      "name,id,cost,costTrend\nus-east-1,us-east-1,100.25,3\neu-west-1,eu-west-1,50,\n",
+    );
+    expect(result.resource_links).toEqual([expect.objectContaining({ type: "resource_link", mimeType: "text/csv" })]);
+    const listCall = (base.registry.dispatch as unknown as { mock: { calls: unknown[][] } }).mock.calls[0]!;
+    expect(listCall[1]).toBe("cost_breakdown");
+    expect(listCall[3]).toMatchObject({ perspective_id: "p1", group_by: "region", limit: 500, offset: 0 });
+  });
+});
@@ -0,0 +1,113 @@
+import { describe, it, expect } from "vitest";
+import { delegateTaskLogsHandler } from "../../../src/tools/diagnose/delegate-task-logs.js";
+import { makeContext } from "./helpers.js";
+
+const STEP_START = Date.UTC(2026, 0, 1, 12, 0, 0);
+const STEP_END = STEP_START + 60_000;
+
+function executionWith(nodeMap: Record<string, unknown>) {
+  return { pipelineExecutionSummary: { status: "Failed" }, executionGraph: { nodeMap } };
+}
+
+const FAILED_STEP = {
+  uuid: "node-1",
+  identifier: "deploy",
+  name: "Deploy",
+  status: "Failed",
+  stepType: "K8sRollingDeploy",
+  baseFqn: "pipeline.stages.prod.spec.execution.steps.deploy",
+  startTs: STEP_START,
+  endTs: STEP_END,
+  failureInfo: { message: "Timed out waiting for rollout" },
+  delegateInfoList: [{ id: "d-1", name: "k8s-delegate", taskId: "task-1", taskName: "K8s Rolling Deploy" }],
+  executableResponses: [{ task: { taskId: "task-1" } }, { task: { taskId: "task-2", taskName: "Fetch manifests" } }],
+};
+
+describe("delegateTaskLogsHandler", () => {
+  it("requires execution_id", async () => {
+    const ctx = makeContext();
+    await expect(delegateTaskLogsHandler.diagnose(ctx)).rejects.toThrow("execution_id is required");
+  });
+
+  it("errors when the execution has no failed step", async () => {
+    const ctx = makeContext({
+      input: { execution_id: "exec-1" },
+      dispatchMap: { execution: { get: executionWith({ "node-1": { ...FAILED_STEP, status: "Success" } }) } },
+    });
+    await expect(delegateTaskLogsHandler.diagnose(ctx)).rejects.toThrow("no failed step");
+  });
+
+  it("correlates the failed step with its delegate, tasks, health, and logs", async () => {
+    const ctx = makeContext({
+      input: { execution_id: "exec-1" },
+      dispatchMap: {
+        execution: { get: executionWith({ "node-1": FAILED_STEP }) },
+        delegate: { list: [{ name: "k8s-delegate", connected: false, lastHeartBeat: STEP_END, delegateReplicas: [{}, {}] }] },
+        delegate_task_log: {
+          list: {
+            items: [{ ISOTime: "2026-01-01T12:00:30Z", severity: "ERROR", message: "rollout status timed out" }],
+            next_page_token: "tok-2",
+          },
+        },
+      },
+    });
+
+    const result = await delegateTaskLogsHandler.diagnose(ctx);
+
+    expect(result.step).toMatchObject({ identifier: "deploy", status: "Failed", failure_message: "Timed out waiting for rollout" });
+    expect(result.delegates).toEqual([
+      {
+        delegate_id: "d-1",
+        delegate_name: "k8s-delegate",
+        task_id: "task-1",
+        task_name: "K8s Rolling Deploy",
+        health: { connected: false, last_heartbeat: new Date(STEP_END).toISOString(), replicas: 2 },
+      },
+      { task_id: "task-2", task_name: "Fetch manifests", health: undefined },
+    ]);
+    expect(result.delegate_logs).toEqual({
+      line_count: 1,
+      lines: ["2026-01-01T12:00:30Z ERROR rollout status timed out"],
+      next_page_token: "tok-2",
+    });
+
+    const logCall = (ctx.registry.dispatch as unknown as { mock: { calls: unknown[][] } }).mock.calls
+      .find((c) => c[1] === "delegate_task_log")!;
+    expect(logCall[3]).toMatchObject({
+      task_ids: ["task-1", "task-2"],
+      start_time: STEP_START - 5 * 60_000,
+      end_time: STEP_END + 5 * 60_000,
+      size: 200,
+    });
+  });
+
+  it("targets step_id when provided", async () => {
+    const other = { ...FAILED_STEP, uuid: "node-2", identifier: "build", status: "Success", delegateInfoList: [], executableResponses: [] };
+    const ctx = makeContext({
+      input: { execution_id: "exec-1", step_id: "node-2" },
+      dispatchMap: { execution: { get: executionWith({ "node-1": FAILED_STEP, "node-2": other }) } },
+    });
+
+    const result = await delegateTaskLogsHandler.diagnose(ctx);
+
+    expect(result.step).toMatchObject({ identifier: "build" });
+    expect(result.delegates).toEqual([]);
+    expect(result.note).toMatch(/No delegate task was recorded/);
+  });
+
+  it("reports a log fetch failure without failing the diagnosis", async () => {
+    const ctx = makeContext({
+      input: { execution_id: "exec-1" },
+      dispatchMap: {
+        execution: { get: executionWith({ "node-1": FAILED_STEP }) },
+        delegate: { list: [] },
+        delegate_task_log: { list: new Error("HTTP 404: Not Found") },
+      },
+    });
+
+    const result = await delegateTaskLogsHandler.diagnose(ctx);
+
+    expect(result.delegate_logs_error).toBe("HTTP 404: Not Found");
+    expect(result.delegates).toHaveLength(2);
+  });
+});
@@ -0,0 +1,129 @@
+import { describe, it, expect } from "vitest";
+import { executionWaitsHandler, constraintKey } from "../../../src/tools/diagnose/execution-waits.js";
+import { constraintQueueExtract } from "../../../src/registry/extractors.js";
+import { makeContext } from "./helpers.js";
+
+const EXECUTION = {
+  pipelineExecutionSummary: { pipelineIdentifier: "deploy", status: "Running", layoutNodeMap: {} },
+  executionGraph: {
+    nodeMap: {
+      n1: {
+        uuid: "n1",
+        identifier: "rc",
+        name: "Resource Constraint",
+        stepType: "ResourceConstraint",
+        status: "ResourceWaiting",
+        startTs: Date.now() - 60_000,
+        stepParameters: { spec: { resourceUnit: "infra-key-1" } },
+      },
+      n2: { uuid: "n2", identifier: "sync", name: "Sync", stepType: "Barrier", status: "Running", stepParameters: { spec: { barrierRef: "db_ready" } } },
+      n3: { uuid: "n3", identifier: "migrate_done", name: "Migrate Done", stepType: "Barrier", status: "NotStarted", stepParameters: { spec: { barrierRef: "db_ready" } } },
+      n4: { uuid: "n4", identifier: "approve", name: "Approve", stepType: "HarnessApproval", status: "ApprovalWaiting" },
+      n5: { uuid: "n5", identifier: "build", name: "Build", stepType: "Run", status: "Success" },
+    },
+  },
+};
+
+const QUEUE = {
+  resourceUnit: "infra-key-1",
+  capacity: 1,
+  holders: [{ pipelineIdentifier: "other", planExecutionId: "exec-0", state: "ACTIVE" }],
+  queue: [
+    { position: 1, pipelineIdentifier: "deploy", planExecutionId: "exec-1", state: "BLOCKED" },
+  ],
+};
+
+const APPROVALS = {
+  items: [{
+    id: "appr-1",
+    type: "HarnessApproval",
+    details: {
+      approvers: { userGroups: ["release_managers"], minimumCount: 2 },
+      approvalActivities: [{ user: { name: "Sam" }, action: "APPROVE" }],
+    },
+  }],
+  total: 1,
+};
+
+describe("constraintKey", () => {
+  it("reads the resource unit of a constraint step or the key of a Queue step", () => {
+    expect(constraintKey({ stepParameters: { spec: { resourceUnit: "u1" } } })).toBe("u1");
+    expect(constraintKey({ stepParameters: { spec: { key: "deploy-prod" } } })).toBe("deploy-prod");
+    expect(constraintKey({})).toBeUndefined();
+  });
+});
+
+describe("executionWaitsHandler", () => {
+  it("resolves constraint holders, pending barrier participants, and approvers", async () => {
+    const ctx = makeContext({
+      input: { execution_id: "exec-1" },
+      dispatchMap: {
+        execution: { get: EXECUTION },
+        constraint_queue: { get: QUEUE },
+        approval_instance: { list: APPROVALS },
+      },
+    });
+
+    const result = await executionWaitsHandler.diagnose(ctx);
+    expect(result.waiting).toBe(true);
+    const waits = result.waits as Record<string, unknown>[];
+    expect(waits.map((w) => w.kind)).toEqual(["resource_constraint", "barrier", "approval"]);
+
+    expect(waits[0]).toMatchObject({ resource_unit: "infra-key-1", capacity: 1, queue_length: 1, queue_position: 1 });
+    expect((waits[0]!.step as Record<string, unknown>).waiting_seconds).toBeGreaterThanOrEqual(59);
+    expect(waits[1]).toMatchObject({ barrier_ref: "db_ready", waiting_on: ["migrate_done"] });
+    expect(waits[2]).toMatchObject({ approval_id: "appr-1", approver_user_groups: ["release_managers"], minimum_count: 2 });
+
+    const summary = result.summary as string[];
+    expect(summary[0]).toContain("held by 1 execution(s), position 1 in the queue");
+    expect(summary[1]).toContain("for 1 other step(s) to arrive");
+    expect(ctx.registry.dispatch).toHaveBeenCalledWith(ctx.client, "constraint_queue", "get", { resource_unit: "infra-key-1" }, ctx.signal);
+  });
+
+  it("reports a queued execution with no started steps", async () => {
+    const ctx = makeContext({
+      input: { execution_id: "exec-2" },
+      dispatchMap: {
+        execution: { get: { pipelineExecutionSummary: { status: "QueuedExecutionConcurrencyReached" }, executionGraph: { nodeMap: {} } } },
+      },
+    });
+
+    const result = await executionWaitsHandler.diagnose(ctx);
+    expect(result.waits).toEqual([{ kind: "queued", status: "QueuedExecutionConcurrencyReached" }]);
+    expect((result.summary as string[])[0]).toContain("concurrency or license limit");
+  });
+
+  it("says so when nothing is waiting", async () => {
+    const ctx = makeContext({
+      input: { execution_id: "exec-3" },
+      dispatchMap: { execution: { get: { pipelineExecutionSummary: { status: "Success" }, executionGraph: { nodeMap: {} } } } },
+    });
+
+    const result = await executionWaitsHandler.diagnose(ctx);
+    expect(result.waiting).toBe(false);
+    expect((result.summary as string[])[0]).toContain("not waiting on anything");
+  });
+
+  it("requires an execution_id", async () => {
+    await expect(executionWaitsHandler.diagnose(makeContext({}))).rejects.toThrow(/execution_id is required/);
+  });
+});
+
+describe("constraintQueueExtract", () => {
+  it("splits holders from the ordered queue", () => {
+    const result = constraintQueueExtract({
+      data: {
+        name: "Queuing",
+        capacity: 1,
+        resourceConstraints: [
+          { planExecutionId: "b", state: "BLOCKED", startTs: 20 },
+          { planExecutionId: "h", state: "ACTIVE", startTs: 5 },
+          { planExecutionId: "a", state: "BLOCKED", startTs: 10 },
+        ],
+      },
+    }, { resource_unit: "u1" }) as Record<string, unknown>;
+    expect(result.resourceUnit).toBe("u1");
+    expect((result.holders as Array<Record<string, unknown>>).map((h) => h.planExecutionId)).toEqual(["h"]);
+    expect((result.queue as Array<Record<string, unknown>>).map((q) => [q.position, q.planExecutionId])).toEqual([[1, "a"], [2, "b"]]);
+  });
+});
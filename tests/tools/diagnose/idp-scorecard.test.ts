@@ -0,0 +1,116 @@
+import { describe, it, expect } from "vitest";
+import { idpScorecardHandler } from "../../../src/tools/diagnose/idp-scorecard.js";
+import { makeContext } from "./helpers.js";
+
+const SCORECARD = {
+  scorecard: { identifier: "prod_ready", name: "Production Readiness", published: true },
+  checks: [
+    { identifier: "has_owner", name: "Has owner", custom: false },
+    { identifier: "has_runbook", name: "Has runbook", custom: true },
+  ],
+};
+
+describe("idpScorecardHandler", () => {
+  it("lists scorecards when no id is given", async () => {
+    const ctx = makeContext({
+      dispatchMap: {
+        scorecard: { list: { items: [{ identifier: "prod_ready", name: "Production Readiness", published: true, checks: [{}, {}] }], total: 1 } },
+      },
+    });
+
+    const result = await idpScorecardHandler.diagnose(ctx);
+
+    expect(result.total).toBe(1);
+    expect(result.scorecards).toEqual([expect.objectContaining({ scorecard_id: "prod_ready", checks: 2 })]);
+  });
+
+  it("reports failing entities per check, worst check first", async () => {
+    const ctx = makeContext({
+      input: { scorecard_id: "prod_ready" },
+      dispatchMap: {
+        scorecard: { get: SCORECARD },
+        scorecard_stats: {
+          get: { stats: [{ name: "payments", kind: "Component", namespace: "default", owner: "team-a", score: 40 }, { name: "cart", score: 100 }] },
+        },
+        // Both checks resolve to the same mock; the failing rows drive the assertion
+        scorecard_check_stats: {
+          get: { stats: [{ name: "payments", owner: "team-a", status: "FAIL" }, { name: "cart", status: "PASS" }] },
+        },
+      },
+    });
+
+    const result = await idpScorecardHandler.diagnose(ctx);
+
+    expect(result.name).toBe("Production Readiness");
+    expect(result.entities_evaluated).toBe(2);
+    expect(result.lowest_scoring).toEqual([
+      expect.objectContaining({ entity: "default/Component/payments", score: 40 }),
+      expect.objectContaining({ entity: "default/Component/cart", score: 100 }),
+    ]);
+    const checks = result.checks as Array<Record<string, unknown>>;
+    expect(checks).toHaveLength(2);
+    expect(checks[0]).toMatchObject({
+      passed: 1,
+      failed: 1,
+      failing_entities: [{ entity: "default/Component/payments", owner: "team-a" }],
+    });
+    const registry = ctx.registry as unknown as { dispatch: { mock: { calls: unknown[][] } } };
+    const checkCalls = registry.dispatch.mock.calls.filter((c) => c[1] === "scorecard_check_stats");
+    expect(checkCalls.map((c) => (c[3] as Record<string, unknown>).is_custom)).toEqual([false, true]);
+  });
+
+  it("keeps per-check results when scorecard stats fail", async () => {
+    const ctx = makeContext({
+      input: { scorecard_id: "prod_ready" },
+      dispatchMap: {
+        scorecard: { get: SCORECARD },
+        scorecard_stats: { get: new Error("HTTP 500: Internal Server Error") },
+        scorecard_check_stats: { get: { stats: [] } },
+      },
+    });
+
+    const result = await idpScorecardHandler.diagnose(ctx);
+
+    expect(result.stats_error).toContain("HTTP 500");
+    expect(result.entities_evaluated).toBeUndefined();
+    expect(result.checks).toHaveLength(2);
+  });
+
+  it("returns pass/fail and failure reason per check for an entity", async () => {
+    const ctx = makeContext({
+      input: { entity_identifier: "default/Component/payments" },
+      dispatchMap: {
+        idp_score: {
+          list: {
+            overall_score: 50,
+            items: [
+              {
+                scorecard_identifier: "prod_ready",
+                scorecard_name: "Production Readiness",
+                score: 50,
+                checks: [
+                  { check_name: "Has owner", status: "PASS" },
+                  { check_name: "Has runbook", status: "FAIL", reason: "annotation 'runbook-url' is missing" },
+                ],
+              },
+            ],
+            total: 1,
+          },
+        },
+      },
+    });
+
+    const result = await idpScorecardHandler.diagnose(ctx);
+
+    expect(result.overall_score).toBe(50);
+    expect(result.scorecards).toEqual([
+      expect.objectContaining({
+        scorecard_id: "prod_ready",
+        passed: 1,
+        failed: 1,
+        passing_checks: ["Has owner"],
+        failing_checks: [expect.objectContaining({ name: "Has runbook", reason: "annotation 'runbook-url' is missing" })],
+      }),
+    ]);
+  });
+});
@@ -0,0 +1,73 @@
+import { describe, it, expect } from "vitest";
+import { k8sWorkloadHandler } from "../../../src/tools/diagnose/k8s-workload.js";
+import { makeContext } from "./helpers.js";
+
+function pod(name: string, artifact: string, overrides: Record<string, unknown> = {}) {
+  return {
+    podName: name,
+    artifactName: artifact,
+    connectorRef: "account.prod_cluster",
+    infrastructureName: "prod-k8s",
+    infrastructureDetails: { namespace: "payments", releaseName: "release-abc" },
+    deployedAt: Date.UTC(2026, 0, 1),
+    pipelineExecutionName: "deploy-payments",
+    instanceInfoDTO: { podIP: "10.0.0.1", containerList: [{ name: "app", image: `registry/payments:${artifact}` }] },
+    ...overrides,
+  };
+}
+
+describe("k8sWorkloadHandler", () => {
+  it("requires service_id and env_id", async () => {
+    await expect(k8sWorkloadHandler.diagnose(makeContext({ input: { service_id: "payments" } })))
+      .rejects.toThrow("service_id and env_id are required");
+  });
+
+  it("reports healthy workloads grouped by namespace and release", async () => {
+    const ctx = makeContext({
+      input: { service_id: "payments", env_id: "prod" },
+      dispatchMap: { service_instance: { list: { items: [pod("p-1", "1.4.0"), pod("p-2", "1.4.0")], total: 2 } } },
+    });
+
+    const result = await k8sWorkloadHandler.diagnose(ctx);
+
+    expect(result.status).toBe("healthy");
+    expect(result.total_pods).toBe(2);
+    expect(result.workloads).toEqual([
+      expect.objectContaining({
+        infrastructure: "prod-k8s",
+        namespace: "payments",
+        release: "release-abc",
+        status: "healthy",
+        pod_count: 2,
+        artifacts: ["1.4.0"],
+        last_execution: "deploy-payments",
+      }),
+    ]);
+    const [first] = (result.workloads as Array<{ pods: unknown[] }>)[0]!.pods;
+    expect(first).toEqual({ pod: "p-1", pod_ip: "10.0.0.1", artifact: "1.4.0", images: ["registry/payments:1.4.0"] });
+  });
+
+  it("flags mixed artifact versions as degraded", async () => {
+    const ctx = makeContext({
+      input: { service_id: "payments", env_id: "prod" },
+      dispatchMap: { service_instance: { list: { items: [pod("p-1", "1.4.0"), pod("p-2", "1.3.9")], total: 2 } } },
+    });
+
+    const result = await k8sWorkloadHandler.diagnose(ctx);
+
+    expect(result.status).toBe("degraded");
+    expect((result.workloads as Array<Record<string, unknown>>)[0]!.reason).toMatch(/2 different artifacts/);
+  });
+
+  it("reports no_instances when instance sync is empty", async () => {
+    const ctx = makeContext({
+      input: { service_id: "payments", env_id: "prod" },
+      dispatchMap: { service_instance: { list: { items: [], total: 0 } } },
+    });
+
+    const result = await k8sWorkloadHandler.diagnose(ctx);
+
+    expect(result.status).toBe("no_instances");
+    expect(result.note).toMatch(/instance sync/);
+  });
+});
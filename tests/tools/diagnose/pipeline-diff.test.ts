@@ -0,0 +1,97 @@
+import { describe, it, expect, vi } from "vitest";
+import { pipelineDiffHandler } from "../../../src/tools/diagnose/pipeline-diff.js";
+import { makeContext, makeRegistry } from "./helpers.js";
+import type { HarnessClient } from "../../../src/client/harness-client.js";
+
+const MAIN_YAML = "pipeline:\n  name: deploy\n  identifier: deploy\n  timeout: 10m\n";
+const FEATURE_YAML = "pipeline:\n  name: deploy\n  identifier: deploy\n  timeout: 30m\n";
+
+describe("pipelineDiffHandler", () => {
+  it("requires pipeline_id", async () => {
+    const ctx = makeContext({ args: { base_branch: "main", head_branch: "feature" } });
+    await expect(pipelineDiffHandler.diagnose(ctx)).rejects.toThrow("pipeline_id");
+  });
+
+  it("requires both revisions", async () => {
+    const ctx = makeContext({ args: { pipeline_id: "deploy", base_branch: "main" } });
+    await expect(pipelineDiffHandler.diagnose(ctx)).rejects.toThrow("head_branch or head_commit");
+  });
+
+  it("diffs two branches via pipeline get", async () => {
+    const registry = makeRegistry();
+    const calls: Array<Record<string, unknown>> = [];
+    (registry.dispatch as ReturnType<typeof vi.fn>).mockImplementation(
+      async (_c: HarnessClient, _type: string, _op: string, input: Record<string, unknown>) => {
+        calls.push(input);
+        return {
+          yamlPipeline: input.branch === "main" ? MAIN_YAML : FEATURE_YAML,
+          gitDetails: { commitId: `sha-${input.branch}`, filePath: ".harness/deploy.yaml" },
+        };
+      },
+    );
+    const ctx = makeContext({
+      registry,
+      args: { pipeline_id: "deploy", base_branch: "main", head_branch: "feature" },
+    });
+
+    const result = await pipelineDiffHandler.diagnose(ctx);
+
+    expect(calls.map((c) => c.branch)).toEqual(["main", "feature"]);
+    expect(result.identical).toBe(false);
+    expect(result.additions).toBe(1);
+    expect(result.deletions).toBe(1);
+    expect(result.diff).toContain("--- deploy@main");
+    expect(result.diff).toContain("+++ deploy@feature");
+    expect(result.diff).toContain("-  timeout: 10m");
+    expect(result.diff).toContain("+  timeout: 30m");
+    expect(result.base).toEqual({ branch: "main", commit_id: "sha-main", file_path: ".harness/deploy.yaml" });
+  });
+
+  it("reports identical revisions without a diff body", async () => {
+    const ctx = makeContext({
+      dispatchMap: { pipeline: { get: { yamlPipeline: MAIN_YAML } } },
+      args: { pipeline_id: "deploy", base_branch: "main", head_branch: "main" },
+    });
+
+    const result = await pipelineDiffHandler.diagnose(ctx);
+
+    expect(result.identical).toBe(true);
+    expect(result.diff).toBeUndefined();
+  });
+
+  it("diffs commits via Harness Code file content", async () => {
+    const ctx = makeContext({
+      dispatchMap: {
+        pipeline: { get: { yamlPipeline: MAIN_YAML, gitDetails: { repoName: "infra", filePath: ".harness/deploy.yaml" } } },
+        file_content: { get: { type: "file", content: { encoding: "base64", data: Buffer.from(FEATURE_YAML).toString("base64") } } },
+      },
+      args: { pipeline_id: "deploy", base_branch: "main", head_commit: "abcdef1234567890" },
+    });
+
+    const result = await pipelineDiffHandler.diagnose(ctx);
+
+    expect(result.head).toEqual({ commit: "abcdef1234567890", commit_id: "abcdef1234567890", file_path: ".harness/deploy.yaml" });
+    expect(result.diff).toContain("+++ deploy@abcdef123456");
+    expect(result.additions).toBe(1);
+  });
+
+  it("rejects commit diffs for external Git providers", async () => {
+    const ctx = makeContext({
+      dispatchMap: {
+        pipeline: { get: { yamlPipeline: MAIN_YAML, connectorRef: "github", gitDetails: { repoName: "infra", filePath: "p.yaml" } } },
+      },
+      args: { pipeline_id: "deploy", base_commit: "aaa", head_commit: "bbb" },
+    });
+
+    await expect(pipelineDiffHandler.diagnose(ctx)).rejects.toThrow("Harness Code");
+  });
+
+  it("rejects commit diffs for inline pipelines", async () => {
+    const ctx = makeContext({
+      dispatchMap: { pipeline: { get: { yamlPipeline: MAIN_YAML } } },
+      args: { pipeline_id: "deploy", base_commit: "aaa", head_commit: "bbb" },
+    });
+
+    await expect(pipelineDiffHandler.diagnose(ctx)).rejects.toThrow("not stored in Git");
+  });
+});
@@ -0,0 +1,113 @@
+import { describe, it, expect, vi } from "vitest";
+import { pipelineHealthHandler, failedStageNames } from "../../../src/tools/diagnose/pipeline-health.js";
+import type { HarnessClient } from "../../../src/client/harness-client.js";
+import { makeContext } from "./helpers.js";
+
+const T0 = Date.parse("2026-03-01T00:00:00Z");
+
+function exec(pipelineIdentifier: string, status: string, minutes: number, failedStage?: string): Record<string, unknown> {
+  return {
+    pipelineIdentifier,
+    planExecutionId: `${pipelineIdentifier}-${status}-${minutes}`,
+    status,
+    startTs: T0,
+    endTs: T0 + minutes * 60_000,
+    modules: ["cd"],
+    ...(failedStage
+      ? {
+          layoutNodeMap: {
+            a: { nodeType: "Deployment", name: "Build", status: "Success" },
+            b: { nodeType: "Deployment", name: failedStage, status: "Failed" },
+          },
+          failureInfo: { message: `${failedStage} failed` },
+        }
+      : {}),
+  };
+}
+
+const EXECUTIONS = {
+  items: [
+    exec("deploy", "Failed", 10, "Deploy prod"),
+    exec("deploy", "Success", 6),
+    exec("deploy", "Failed", 8, "Deploy prod"),
+    exec("deploy", "Aborted", 1),
+    exec("build", "Success", 4),
+    exec("build", "Success", 2),
+    exec("build", "Success", 3),
+    exec("build", "Running", 0),
+    exec("lint", "Failed", 1, "Lint"),
+  ],
+  total: 9,
+};
+
+describe("failedStageNames", () => {
+  it("returns failed stages from the layout graph", () => {
+    expect(failedStageNames(exec("deploy", "Failed", 1, "Deploy prod"))).toEqual(["Deploy prod"]);
+    expect(failedStageNames(exec("deploy", "Success", 1))).toEqual([]);
+  });
+});
+
+describe("pipelineHealthHandler", () => {
+  it("ranks pipelines by failure rate when no pipeline_id is given", async () => {
+    const ctx = makeContext({ dispatchMap: { execution: { list: EXECUTIONS } } });
+
+    const result = await pipelineHealthHandler.diagnose(ctx);
+
+    expect(result.executions_sampled).toBe(9);
+    expect(result.overall_success_rate_percent).toBe(57.1);
+    expect(result.flakiest).toEqual([
+      expect.objectContaining({
+        pipeline_id: "deploy",
+        runs: 4,
+        failed: 2,
+        aborted: 1,
+        success_rate_percent: 33.3,
+        failures_by_stage: [{ stage: "Deploy prod", failures: 2 }],
+        last_failure: expect.objectContaining({ execution_id: "deploy-Failed-10", message: "Deploy prod failed" }),
+      }),
+    ]);
+    // lint has one run — below min_runs — so it is left out of the ranking
+    expect(result.healthiest).toEqual(["build"]);
+    expect((result as { flakiest: Array<Record<string, unknown>> }).flakiest[0]!.mean_duration_seconds).toBe(375);
+  });
+
+  it("adds dashboard totals for a single pipeline", async () => {
+    const request = vi.fn().mockResolvedValue({
+      data: { executions: { total: { count: 40, rate: 10 }, success: { percent: 75, rate: -5 }, meanInfo: { duration: 420000, rate: 2 }, medianInfo: { duration: 360000 } } },
+    });
+    const ctx = makeContext({
+      client: { request, account: "test-account" } as unknown as HarnessClient,
+      dispatchMap: { execution: { list: EXECUTIONS } },
+      input: { pipeline_id: "deploy" },
+      args: { start_time: "2026-02-01T00:00:00Z", end_time: "2026-03-01T00:00:00Z" },
+    });
+
+    const result = await pipelineHealthHandler.diagnose(ctx);
+
+    expect(result.pipeline).toMatchObject({ pipeline_id: "deploy", runs: 4, success_rate_percent: 33.3 });
+    expect(result.dashboard).toMatchObject({ runs: 40, success_rate_percent: 75, success_rate_change_percent: -5, mean_duration_seconds: 420, median_duration_seconds: 360 });
+    expect(request).toHaveBeenCalledWith(expect.objectContaining({
+      path: "/pipeline/api/dashboard/pipelineHealth",
+      params: expect.objectContaining({ pipelineIdentifier: "deploy", moduleInfo: "CD", startTime: Date.parse("2026-02-01T00:00:00Z") }),
+    }));
+  });
+
+  it("keeps computed stats when the dashboard call fails", async () => {
+    const request = vi.fn().mockRejectedValue(new Error("HTTP 403: Forbidden"));
+    const ctx = makeContext({
+      client: { request, account: "test-account" } as unknown as HarnessClient,
+      dispatchMap: { execution: { list: EXECUTIONS } },
+      input: { pipeline_id: "deploy" },
+    });
+
+    const result = await pipelineHealthHandler.diagnose(ctx);
+
+    expect(result.pipeline).toMatchObject({ failed: 2 });
+    expect(result.dashboard_error).toMatch(/403/);
+  });
+
+  it("rejects an inverted window", async () => {
+    const ctx = makeContext({ args: { start_time: "2026-03-02T00:00:00Z", end_time: "2026-03-01T00:00:00Z" } });
+    await expect(pipelineHealthHandler.diagnose(ctx)).rejects.toThrow(/start_time must be before end_time/);
+  });
+});
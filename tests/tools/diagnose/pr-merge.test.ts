@@ -0,0 +1,125 @@
+import { describe, it, expect } from "vitest";
+import { prMergeHandler, branchGlobMatch, ruleAppliesTo } from "../../../src/tools/diagnose/pr-merge.js";
+import { makeContext } from "./helpers.js";
+
+const PR = { number: 12, title: "Bump retries", state: "open", source_branch: "feature/x", target_branch: "main", merge_check_status: "mergeable" };
+
+const RULES = [
+  {
+    identifier: "protect-main",
+    state: "active",
+    pattern: { default: true },
+    definition: { pullreq: { approvals: { require_minimum_count: 2 }, status_checks: { require_identifiers: ["build", "security-scan"] } } },
+  },
+  { identifier: "release-only", state: "active", pattern: { include: ["release/**"] }, definition: { pullreq: { status_checks: { require_identifiers: ["e2e"] } } } },
+  { identifier: "old-rule", state: "disabled", pattern: {}, definition: {} },
+];
+
+describe("branchGlobMatch / ruleAppliesTo", () => {
+  it("matches single- and multi-segment globs", () => {
+    expect(branchGlobMatch("release/*", "release/1.2")).toBe(true);
+    expect(branchGlobMatch("release/*", "release/1.2/hotfix")).toBe(false);
+    expect(branchGlobMatch("release/**", "release/1.2/hotfix")).toBe(true);
+    expect(branchGlobMatch("main", "main")).toBe(true);
+  });
+
+  it("honors default, include, and exclude patterns", () => {
+    expect(ruleAppliesTo({ pattern: { default: true } }, "main", "main")).toBe(true);
+    expect(ruleAppliesTo({ pattern: { default: true } }, "develop", "main")).toBe(false);
+    expect(ruleAppliesTo({ pattern: { include: ["*"], exclude: ["main"] } }, "main", "main")).toBe(false);
+    expect(ruleAppliesTo({ pattern: {} }, "anything", "main")).toBe(true);
+  });
+});
+
+describe("prMergeHandler", () => {
+  it("lists rule violations and failing, pending, and missing required checks as blockers", async () => {
+    const ctx = makeContext({
+      input: { repo_id: "payments", pr_number: "12" },
+      dispatchMap: {
+        pull_request: { get: PR },
+        repository: { get: { identifier: "payments", default_branch: "main" } },
+        repo_rule: { list: RULES },
+        pr_check: {
+          list: {
+            commit_sha: "abc123",
+            checks: [
+              { required: false, check: { identifier: "build", status: "failure", summary: "2 tests failed" } },
+              { required: false, check: { identifier: "lint", status: "error" } },
+              { required: true, check: { identifier: "sonar", status: "running" } },
+            ],
+          },
+        },
+      },
+      executeMap: {
+        pull_request: {
+          merge_check: {
+            mergeable: false,
+            conflict_files: [],
+            minimum_required_approvals_count: 2,
+            rule_violations: [
+              { rule: { identifier: "protect-main", state: "active" }, bypassable: false, bypassed: false, violations: [{ code: "pullreq.approvals.require_minimum_count", message: "Insufficient number of approvals (0 of 2)." }] },
+              { rule: { identifier: "audit-only", state: "monitor" }, violations: [{ message: "Would block" }] },
+            ],
+          },
+        },
+      },
+    });
+
+    const result = await prMergeHandler.diagnose(ctx);
+
+    expect(result.can_merge).toBe(false);
+    expect(result.blockers).toEqual([
+      "Rule 'protect-main': Insufficient number of approvals (0 of 2).",
+      "Required check 'build' is failure: 2 tests failed.",
+      "Required check 'sonar' is still running.",
+      "Required check 'security-scan' has not reported on the latest commit.",
+    ]);
+    expect((result.rules as Array<Record<string, unknown>>).map((r) => r.identifier)).toEqual(["protect-main"]);
+    expect(result.checks).toMatchObject({
+      commit_sha: "abc123",
+      required_failing: ["build"],
+      required_pending: ["sonar"],
+      required_missing: ["security-scan"],
+      optional_failing: ["lint"],
+    });
+  });
+
+  it("reports a clean PR as mergeable and keeps going when rule listing fails", async () => {
+    const ctx = makeContext({
+      input: { repo_id: "payments", pr_number: "12" },
+      dispatchMap: {
+        pull_request: { get: PR },
+        repository: { get: { default_branch: "main" } },
+        repo_rule: { list: new Error("HTTP 403: Forbidden") },
+        pr_check: { list: { checks: [{ required: true, check: { identifier: "build", status: "success" } }] } },
+      },
+      executeMap: { pull_request: { merge_check: { mergeable: true, rule_violations: [] } } },
+    });
+
+    const result = await prMergeHandler.diagnose(ctx);
+
+    expect(result.rules_error).toContain("403");
+    expect(result.blockers).toEqual([]);
+    expect(result.can_merge).toBe(true);
+  });
+
+  it("flags drafts and conflicts", async () => {
+    const ctx = makeContext({
+      input: { repo_id: "payments", pr_number: "12" },
+      dispatchMap: {
+        pull_request: { get: { ...PR, is_draft: true } },
+        repository: { get: {} },
+        repo_rule: { list: [] },
+        pr_check: { list: { checks: [] } },
+      },
+      executeMap: { pull_request: { merge_check: { mergeable: false, conflict_files: ["go.mod"] } } },
+    });
+
+    const result = await prMergeHandler.diagnose(ctx);
+
+    expect(result.blockers).toEqual([
+      "Pull request is a draft — mark it ready for review first.",
+      "Merge conflicts in 1 file(s): go.mod.",
+    ]);
+  });
+});
@@ -0,0 +1,104 @@
+import { describe, it, expect } from "vitest";
+import { prReviewHandler, chunkDiffs } from "../../../src/tools/diagnose/pr-review.js";
+import { makeContext } from "./helpers.js";
+
+const PR = {
+  number: 7,
+  title: "Add retry budget",
+  description: "Caps retries per request.",
+  state: "open",
+  author: { display_name: "Dana" },
+  source_branch: "feature/retry",
+  target_branch: "main",
+  stats: { files_changed: 3, additions: 12, deletions: 2 },
+};
+
+function patchFor(path: string, hunks: number, linesPerHunk: number): string {
+  const header = `diff --git a/${path} b/${path}\n--- a/${path}\n+++ b/${path}`;
+  const body = Array.from({ length: hunks }, (_, h) =>
+    [`@@ -${h * 100 + 1},${linesPerHunk} +${h * 100 + 1},${linesPerHunk} @@`, ...Array.from({ length: linesPerHunk }, (_, i) => `+line ${h}-${i}`)].join("\n"));
+  return [header, ...body].join("\n");
+}
+
+describe("chunkDiffs", () => {
+  it("keeps small files together in one chunk", () => {
+    const chunks = chunkDiffs([
+      { path: "a.ts", patch: patchFor("a.ts", 1, 3) },
+      { path: "b.ts", patch: patchFor("b.ts", 1, 3) },
+    ], 10_000);
+
+    expect(chunks).toHaveLength(1);
+    expect(chunks[0]!.files).toEqual(["a.ts", "b.ts"]);
+  });
+
+  it("splits a large file at hunk boundaries and repeats the file header", () => {
+    const chunks = chunkDiffs([{ path: "big.ts", patch: patchFor("big.ts", 6, 40) }], 2_000);
+
+    expect(chunks.length).toBeGreaterThan(1);
+    for (const chunk of chunks) {
+      expect(chunk.chars).toBeLessThanOrEqual(2_000);
+      expect(chunk.files).toEqual(["big.ts"]);
+      for (const piece of chunk.pieces) {
+        expect(piece.text.startsWith("diff --git a/big.ts b/big.ts")).toBe(true);
+        expect(piece.text).toContain("\n@@ -");
+      }
+    }
+  });
+});
+
+describe("prReviewHandler", () => {
+  it("returns metadata, file list, and the first chunk with skipped files marked", async () => {
+    const ctx = makeContext({
+      input: { repo_id: "payments", pr_number: "7" },
+      dispatchMap: {
+        pull_request: { get: PR },
+        pr_file: {
+          list: {
+            items: [
+              { path: "src/retry.ts", status: "MODIFIED", additions: 10, deletions: 2, patch: "@@ -1,2 +1,10 @@\n+const budget = 3;" },
+              { path: "pnpm-lock.yaml", status: "MODIFIED", additions: 2, deletions: 0, patch: "@@ -1 +1 @@\n+lock" },
+              { path: "logo.png", status: "ADDED", is_binary: true },
+            ],
+            total: 3,
+          },
+        },
+      },
+    });
+
+    const result = await prReviewHandler.diagnose(ctx);
+
+    expect(result.pull_request).toMatchObject({ number: 7, title: "Add retry budget", author: "Dana", target_branch: "main" });
+    expect(result.totals).toEqual({ files: 3, additions: 12, deletions: 2, skipped: 2 });
+    expect(result.files).toEqual([
+      expect.objectContaining({ path: "src/retry.ts", chunks: [0] }),
+      expect.objectContaining({ path: "pnpm-lock.yaml", skipped: "generated" }),
+      expect.objectContaining({ path: "logo.png", skipped: "binary" }),
+    ]);
+    expect(result.chunk_count).toBe(1);
+    expect((result.chunk as Record<string, unknown>).diff).toBe("diff --git a/src/retry.ts b/src/retry.ts\n@@ -1,2 +1,10 @@\n+const budget = 3;");
+    expect(result._hint).toBeUndefined();
+  });
+
+  it("pages through chunks and rejects an out-of-range chunk", async () => {
+    const dispatchMap = {
+      pull_request: { get: PR },
+      pr_file: {
+        list: { items: [{ path: "a.ts", patch: patchFor("a.ts", 1, 200) }, { path: "b.ts", patch: patchFor("b.ts", 1, 200) }], total: 2 },
+      },
+    };
+
+    const first = await prReviewHandler.diagnose(makeContext({ input: { repo_id: "r", pr_number: "7" }, args: { max_chunk_chars: 3_000 }, dispatchMap }));
+    expect(first.chunk_count).toBe(2);
+    expect(first._hint).toContain("options.chunk=1");
+
+    const second = await prReviewHandler.diagnose(makeContext({ input: { repo_id: "r", pr_number: "7" }, args: { max_chunk_chars: 3_000, chunk: 1 }, dispatchMap }));
+    expect((second.chunk as Record<string, unknown>).files).toEqual(["b.ts"]);
+
+    await expect(prReviewHandler.diagnose(makeContext({ input: { repo_id: "r", pr_number: "7" }, args: { max_chunk_chars: 3_000, chunk: 5 }, dispatchMap })))
+      .rejects.toThrow(/out of range/);
+  });
+
+  it("requires repo_id and pr_number", async () => {
+    await expect(prReviewHandler.diagnose(makeContext({ input: { repo_id: "r" } }))).rejects.toThrow(/pr_number/);
+  });
+});
@@ -0,0 +1,77 @@
+import { describe, it, expect } from "vitest";
+import { resourceConstraintsHandler, constraintSteps } from "../../../src/tools/diagnose/resource-constraints.js";
+import { makeContext } from "./helpers.js";
+
+const EXECUTION = {
+  executionGraph: {
+    nodeMap: {
+      n1: { uuid: "n1", identifier: "rc", stepType: "ResourceConstraint", status: "Success", stepParameters: { spec: { resourceUnit: "infra-prod" } } },
+      n2: { uuid: "n2", identifier: "deploy", stepType: "K8sRollingDeploy", status: "Running" },
+    },
+  },
+};
+
+const QUEUE = {
+  resourceUnit: "infra-prod",
+  capacity: 1,
+  holders: [{ planExecutionId: "exec-1", state: "ACTIVE" }],
+  queue: [{ position: 1, planExecutionId: "exec-2", state: "BLOCKED" }],
+};
+
+describe("constraintSteps", () => {
+  it("returns constraint and Queue steps with their keys", () => {
+    expect(constraintSteps(EXECUTION).map((s) => s.key)).toEqual(["infra-prod"]);
+    expect(constraintSteps({})).toEqual([]);
+  });
+});
+
+describe("resourceConstraintsHandler", () => {
+  it("groups active executions by constraint key and fetches each queue once", async () => {
+    const ctx = makeContext({
+      args: { scope: "account" },
+      input: { scope: "account" },
+      dispatchMap: {
+        execution: {
+          list: {
+            items: [
+              { planExecutionId: "exec-1", pipelineIdentifier: "deploy", orgIdentifier: "o1", projectIdentifier: "p1", status: "Running" },
+              { planExecutionId: "exec-2", pipelineIdentifier: "deploy", orgIdentifier: "o1", projectIdentifier: "p2", status: "ResourceWaiting" },
+            ],
+            total: 2,
+          },
+          get: EXECUTION,
+        },
+        constraint_queue: { get: QUEUE },
+      },
+    });
+
+    const result = await resourceConstraintsHandler.diagnose(ctx);
+    expect(result.executions_scanned).toBe(2);
+    expect(result.contended).toBe(1);
+    const constraints = result.constraints as Array<Record<string, unknown>>;
+    expect(constraints).toHaveLength(1);
+    expect(constraints[0]).toMatchObject({ resourceUnit: "infra-prod", capacity: 1 });
+    expect((constraints[0]!.steps as unknown[]).length).toBe(2);
+
+    const dispatch = ctx.registry.dispatch as unknown as { mock: { calls: unknown[][] } };
+    const listInput = dispatch.mock.calls[0]![3] as Record<string, unknown>;
+    expect(listInput.resource_scope).toBe("account");
+    expect(listInput.status).toContain("ResourceWaiting");
+    expect(dispatch.mock.calls.filter((c) => c[1] === "constraint_queue")).toHaveLength(1);
+  });
+
+  it("returns a single queue for resource_unit", async () => {
+    const ctx = makeContext({
+      input: { resource_unit: "infra-prod" },
+      dispatchMap: { constraint_queue: { get: QUEUE } },
+    });
+
+    const result = await resourceConstraintsHandler.diagnose(ctx);
+    expect(result.constraints).toEqual([QUEUE]);
+  });
+
+  it("rejects an unknown scope", async () => {
+    const ctx = makeContext({ input: { scope: "galaxy" } });
+    await expect(resourceConstraintsHandler.diagnose(ctx)).rejects.toThrow(/Invalid scope/);
+  });
+});
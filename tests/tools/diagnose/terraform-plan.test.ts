@@ -0,0 +1,140 @@
+import { describe, it, expect, vi } from "vitest";
+import { terraformPlanHandler, parsePlanSummary } from "../../../src/tools/diagnose/terraform-plan.js";
+import { resolveLogContent } from "../../../src/utils/log-resolver.js";
+import { makeContext } from "./helpers.js";
+
+vi.mock("../../../src/utils/log-resolver.js", () => ({
+  resolveLogContent: vi.fn(),
+}));
+
+const PLAN_LOG = [
+  "Terraform will perform the following actions:",
+  "  # aws_s3_bucket.logs will be created",
+  "  # aws_iam_role.deployer will be updated in-place",
+  "  # aws_instance.web must be replaced",
+  "Plan: 2 to add, 1 to change, 1 to destroy.",
+].join("\n");
+
+const PLAN_STEP = {
+  uuid: "node-plan",
+  identifier: "tf_plan",
+  name: "TF Plan",
+  stepType: "TerraformPlan",
+  status: "Success",
+  baseFqn: "pipeline.stages.infra.spec.execution.steps.tf_plan",
+  logBaseKey: "acct/pipeline/infra/tf_plan",
+  startTs: 1000,
+  stepParameters: { spec: { provisionerIdentifier: "vpc", configuration: { command: "Apply", workspace: "prod" } } },
+  outcomes: { output: { detailedExitCode: 2 } },
+};
+
+const APPLY_STEP = {
+  uuid: "node-apply",
+  identifier: "tf_apply",
+  name: "TF Apply",
+  stepType: "TerraformApply",
+  status: "Failed",
+  baseFqn: "pipeline.stages.infra.spec.execution.steps.tf_apply",
+  logBaseKey: "acct/pipeline/infra/tf_apply",
+  startTs: 2000,
+  stepParameters: { spec: { provisionerIdentifier: "vpc" } },
+  failureInfo: { message: "Error acquiring the state lock" },
+};
+
+function executionWith(nodeMap: Record<string, unknown>) {
+  return { pipelineExecutionSummary: { pipelineIdentifier: "infra_pipeline" }, executionGraph: { nodeMap } };
+}
+
+describe("parsePlanSummary", () => {
+  it("parses totals and resource actions", () => {
+    expect(parsePlanSummary(PLAN_LOG)).toEqual({
+      has_changes: true,
+      add: 2,
+      change: 1,
+      destroy: 1,
+      resource_changes: [
+        { address: "aws_s3_bucket.logs", action: "create" },
+        { address: "aws_iam_role.deployer", action: "update" },
+        { address: "aws_instance.web", action: "replace" },
+      ],
+    });
+  });
+
+  it("recognizes a no-op plan", () => {
+    expect(parsePlanSummary("No changes. Your infrastructure matches the configuration.")).toMatchObject({ has_changes: false });
+  });
+
+  it("returns undefined when the log holds no plan", () => {
+    expect(parsePlanSummary("Initializing provider plugins...")).toBeUndefined();
+  });
+
+  it("caps the resource list", () => {
+    expect(parsePlanSummary(PLAN_LOG, 1)).toMatchObject({ resource_changes: [{ address: "aws_s3_bucket.logs" }], resource_changes_truncated: true });
+  });
+});
+
+describe("terraformPlanHandler", () => {
+  it("requires execution_id or pipeline_id", async () => {
+    await expect(terraformPlanHandler.diagnose(makeContext())).rejects.toThrow("execution_id or pipeline_id is required");
+  });
+
+  it("summarizes provisioner steps and parses plan logs for an execution", async () => {
+    vi.mocked(resolveLogContent).mockResolvedValueOnce(PLAN_LOG);
+    const ctx = makeContext({
+      input: { execution_id: "exec-1" },
+      dispatchMap: { execution: { get: executionWith({ b: APPLY_STEP, a: PLAN_STEP, c: { stepType: "ShellScript" } }) } },
+    });
+
+    const result = await terraformPlanHandler.diagnose(ctx);
+    const steps = result.provisioner_steps as Array<Record<string, unknown>>;
+
+    expect(result.pipeline_id).toBe("infra_pipeline");
+    expect(steps.map((s) => s.identifier)).toEqual(["tf_plan", "tf_apply"]);
+    expect(steps[0]).toMatchObject({
+      stage: "infra",
+      provisioner_identifier: "vpc",
+      command: "Apply",
+      workspace: "prod",
+      detailed_exit_code: 2,
+      has_changes: true,
+      plan: { add: 2, change: 1, destroy: 1 },
+    });
+    expect(steps[1]).toMatchObject({ status: "Failed", failure_message: "Error acquiring the state lock" });
+    expect(steps[1].plan).toBeUndefined();
+    expect(resolveLogContent).toHaveBeenCalledTimes(1);
+  });
+
+  it("records a plan log failure on the step", async () => {
+    vi.mocked(resolveLogContent).mockRejectedValueOnce(new Error("Log download failed: HTTP 403"));
+    const ctx = makeContext({
+      input: { execution_id: "exec-1" },
+      dispatchMap: { execution: { get: executionWith({ a: PLAN_STEP }) } },
+    });
+
+    const result = await terraformPlanHandler.diagnose(ctx);
+
+    expect((result.provisioner_steps as Array<Record<string, unknown>>)[0].plan_error).toBe("Log download failed: HTTP 403");
+  });
+
+  it("lists provisioner runs across recent executions for a pipeline", async () => {
+    const ctx = makeContext({
+      input: { pipeline_id: "infra_pipeline" },
+      args: { max_executions: 3 },
+      dispatchMap: {
+        execution: {
+          list: { items: [{ planExecutionId: "exec-1", status: "Failed", startTs: 1000 }] },
+          get: executionWith({ a: PLAN_STEP }),
+        },
+      },
+    });
+
+    const result = await terraformPlanHandler.diagnose(ctx);
+
+    expect(result.executions_scanned).toBe(1);
+    expect(result.executions).toEqual([
+      expect.objectContaining({ execution_id: "exec-1", provisioner_steps: [expect.objectContaining({ identifier: "tf_plan" })] }),
+    ]);
+    const listCall = (ctx.registry.dispatch as unknown as { mock: { calls: unknown[][] } }).mock.calls[0]!;
+    expect(listCall[3]).toMatchObject({ pipeline_id: "infra_pipeline", size: 3 });
+  });
+});
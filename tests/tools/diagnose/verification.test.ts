@@ -0,0 +1,106 @@
+import { describe, it, expect } from "vitest";
+import { verificationHandler, verifyStepExecutionId } from "../../../src/tools/diagnose/verification.js";
+import { makeContext } from "./helpers.js";
+
+const EXECUTION = {
+  executionGraph: {
+    nodeMap: {
+      n1: { uuid: "n1", identifier: "rollout", name: "Rollout", stepType: "K8sCanaryDeploy", status: "Success" },
+      n2: {
+        uuid: "n2",
+        identifier: "verify",
+        name: "Verify",
+        stepType: "Verify",
+        status: "Failed",
+        failureInfo: { message: "Verification failed" },
+        progressData: { activityId: "act-1" },
+      },
+    },
+  },
+};
+
+const OVERVIEW = {
+  spec: { analysisType: "CANARY", sensitivity: "MEDIUM", durationInMinutes: 10 },
+  appliedDeploymentAnalysisType: "CANARY",
+  verificationStartTimestamp: Date.parse("2026-04-01T10:00:00Z"),
+  metricsAnalysis: { healthy: 8, warning: 0, unhealthy: 1, noAnalysis: 0 },
+  logClusters: { knownClustersCount: 4, unknownClustersCount: 1, unexpectedFrequencyClustersCount: 0 },
+  testNodes: { nodes: [{ nodeIdentifier: "pod-canary" }] },
+  controlNodes: { nodes: [{ nodeIdentifier: "pod-a" }, { nodeIdentifier: "pod-b" }] },
+};
+
+const METRICS = {
+  items: [{
+    metricName: "p95 latency",
+    transactionGroup: "/checkout",
+    healthSource: { identifier: "prom", name: "Prometheus" },
+    analysisResult: "UNHEALTHY",
+    testDataNodes: [
+      { nodeIdentifier: "pod-canary", analysisResult: "UNHEALTHY", analysisReason: "ML_ANALYSIS", controlNodeIdentifier: "pod-a" },
+    ],
+  }],
+  total: 1,
+};
+
+const LOGS = { items: [{ clusterType: "UNKNOWN_EVENT", risk: "UNHEALTHY", count: 12, message: "NullPointerException at CartService" }], total: 1 };
+
+describe("verifyStepExecutionId", () => {
+  it("reads the activity from progressData or the step outcome", () => {
+    expect(verifyStepExecutionId({ progressData: { activityId: "a" } })).toBe("a");
+    expect(verifyStepExecutionId({ outcomes: { output: { activityId: "b" } } })).toBe("b");
+    expect(verifyStepExecutionId({})).toBeUndefined();
+  });
+});
+
+describe("verificationHandler", () => {
+  it("resolves the failed Verify step and explains the anomalies", async () => {
+    const ctx = makeContext({
+      input: { execution_id: "exec-1" },
+      dispatchMap: {
+        execution: { get: EXECUTION },
+        verification: { get: OVERVIEW },
+        verification_metric: { list: METRICS },
+        verification_log: { list: LOGS },
+      },
+    });
+
+    const result = await verificationHandler.diagnose(ctx);
+
+    expect(result.verify_step_execution_id).toBe("act-1");
+    expect(result.step).toMatchObject({ identifier: "verify", status: "Failed" });
+    expect(result.overview).toMatchObject({ analysis_type: "CANARY", sensitivity: "MEDIUM", test_nodes: 1, control_nodes: 2 });
+    expect(result.anomalous_metrics).toEqual([expect.objectContaining({
+      metric: "p95 latency",
+      health_source: "Prometheus",
+      anomalous_nodes: [expect.objectContaining({ node: "pod-canary", control_node: "pod-a" })],
+    })]);
+    expect(result.why).toEqual([
+      "1 metric(s) deviated from the baseline: p95 latency (/checkout).",
+      "1 new log cluster(s) appeared that the baseline does not have.",
+    ]);
+  });
+
+  it("keeps the overview when the log analysis call fails", async () => {
+    const ctx = makeContext({
+      input: { verify_step_execution_id: "act-1" },
+      dispatchMap: {
+        verification: { get: OVERVIEW },
+        verification_metric: { list: METRICS },
+        verification_log: { list: new Error("HTTP 500") },
+      },
+    });
+
+    const result = await verificationHandler.diagnose(ctx);
+
+    expect(result.anomalous_metrics).toHaveLength(1);
+    expect(result.logs_error).toMatch(/500/);
+  });
+
+  it("rejects an execution without a Verify step", async () => {
+    const ctx = makeContext({
+      input: { execution_id: "exec-2" },
+      dispatchMap: { execution: { get: { executionGraph: { nodeMap: {} } } } },
+    });
+    await expect(verificationHandler.diagnose(ctx)).rejects.toThrow(/has no Verify step/);
+  });
+});
@@ -0,0 +1,99 @@
+import { describe, it, expect, afterEach, vi } from "vitest";
+import { mkdtempSync, readFileSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { yamlExportHandler } from "../../../src/tools/diagnose/yaml-export.js";
+import { makeConfig, makeContext } from "./helpers.js";
+import type { Registry } from "../../../src/registry/index.js";
+import type { Extra } from "../../../src/tools/diagnose/types.js";
+
+describe("yamlExportHandler", () => {
+  let dir: string | undefined;
+  afterEach(() => {
+    if (dir) rmSync(dir, { recursive: true, force: true });
+    dir = undefined;
+  });
+
+  it("requires HARNESS_OUTPUT_DIR", async () => {
+    await expect(yamlExportHandler.diagnose(makeContext())).rejects.toThrow("HARNESS_OUTPUT_DIR");
+  });
+
+  it("rejects unsupported entity types", async () => {
+    const ctx = { ...makeContext({ args: { entity_types: ["pipeline", "secret"] } }), config: makeConfig({ HARNESS_OUTPUT_DIR: "/tmp" }) };
+    await expect(yamlExportHandler.diagnose(ctx)).rejects.toThrow("Unsupported entity_types: secret");
+  });
+
+  it("zips pipeline, input set, and template YAML and reports entities it could not read", async () => {
+    dir = mkdtempSync(join(tmpdir(), "harness-yaml-export-"));
+    const base = makeContext({
+      dispatchMap: {
+        pipeline: {
+          list: { items: [{ identifier: "deploy" }], total: 1 },
+          get: { yamlPipeline: "pipeline:\n  identifier: deploy\n" },
+        },
+        input_set: {
+          list: { items: [{ identifier: "prod" }], total: 1 },
+          get: { inputSetName: "prod" },
+        },
+        template: {
+          list: { items: [{ identifier: "std_deploy", versionLabel: "v1", yaml: "template:\n  identifier: std_deploy\n" }], total: 1 },
+        },
+      },
+    });
+    const ctx = { ...base, config: makeConfig({ HARNESS_OUTPUT_DIR: dir }) };
+
+    const result = await yamlExportHandler.diagnose(ctx);
+
+    expect(result.counts).toEqual({ pipeline: 1, template: 1 });
+    expect(result.errors).toEqual([{ entity: "input_set/deploy/prod", error: "No YAML in the API response." }]);
+    expect(result.resource_links).toEqual([expect.objectContaining({ type: "resource_link", mimeType: "application/zip" })]);
+    const zip = readFileSync(result.file_path as string);
+    expect(zip.readUInt32LE(0)).toBe(0x04034b50);
+    for (const name of ["pipelines/deploy.yaml", "templates/std_deploy/v1.yaml", "manifest.json"]) {
+      expect(zip.includes(Buffer.from(name))).toBe(true);
+    }
+  });
+
+  it("resumes an interrupted export from its last checkpoint", async () => {
+    dir = mkdtempSync(join(tmpdir(), "harness-yaml-export-"));
+    const ids = ["p1", "p2", "p3", "p4", "p5", "p6", "p7"];
+    const controller = new AbortController();
+    const gets: string[] = [];
+    const dispatch = vi.fn(async (_client: unknown, _type: string, op: string, input: Record<string, unknown>) => {
+      if (op === "list") return { items: ids.map((identifier) => ({ identifier })), total: ids.length };
+      const id = String(input.pipeline_id);
+      gets.push(id);
+      // The client goes away while the second batch is in flight
+      if (id === "p6" && !controller.signal.aborted) {
+        controller.abort();
+        throw new Error("This operation was aborted");
+      }
+      return { yamlPipeline: `pipeline:\n  identifier: ${id}\n` };
+    });
+    const registry = { dispatch, getAccountId: () => "test-account" } as unknown as Registry;
+    const sendNotification = vi.fn().mockResolvedValue(undefined);
+    const extra = { _meta: { progressToken: 1 }, sendNotification } as unknown as Extra;
+    const config = makeConfig({ HARNESS_OUTPUT_DIR: dir });
+    const args = { entity_types: ["pipeline"] };
+
+    await expect(yamlExportHandler.diagnose({ ...makeContext({ registry, extra, args }), config, signal: controller.signal }))
+      .rejects.toThrow("aborted");
+    const messages = sendNotification.mock.calls.map(([n]) => String(n.params.message));
+    const token = messages.map((m) => /resume_token: ([0-9a-f]+)/.exec(m)?.[1]).find(Boolean);
+    expect(token).toBeDefined();
+
+    gets.length = 0;
+    const result = await yamlExportHandler.diagnose({
+      ...makeContext({ registry, extra, args: { ...args, resume_token: token } }),
+      config,
+      signal: new AbortController().signal,
+    });
+    expect(gets.sort()).toEqual(["p6", "p7"]);
+    expect(result).toMatchObject({ resumed: true, counts: { pipeline: 7 }, files: 7 });
+  });
+
+  it("refuses a resume_token from a run with different arguments", async () => {
+    const ctx = { ...makeContext({ args: { resume_token: "0123456789abcdef01234567" } }), config: makeConfig({ HARNESS_OUTPUT_DIR: "/tmp" }) };
+    await expect(yamlExportHandler.diagnose(ctx)).rejects.toThrow("resume_token is unknown or has expired");
+  });
+});
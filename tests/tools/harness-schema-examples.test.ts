@@ -1,4 +1,5 @@
 import { describe, it, expect } from "vitest";
+import { parse } from "yaml";
 import { getExample, searchExamples, getExamplesForResource } from "../../src/data/examples/index.js";
 import "../../src/data/examples/load-all.js";
 
@@ -62,3 +63,20 @@ describe("pipeline v1 examples", () => {
     expect(results.every((r) => r.resourceType === "pipeline_v1")).toBe(true);
   });
 });
+
+describe("connector examples", () => {
+  it("every connector example parses to a connector with a type", () => {
+    const examples = getExamplesForResource("connector");
+    expect(examples.length).toBeGreaterThanOrEqual(10);
+    for (const ex of examples) {
+      const parsed = parse(ex.yaml) as { connector?: { type?: string; identifier?: string } };
+      expect(parsed.connector?.type, ex.name).toBeTruthy();
+      expect(parsed.connector?.identifier, ex.name).toBeDefined();
+    }
+  });
+
+  it("searchExamples finds skeletons by connector type", () => {
+    const results = searchExamples("K8sCluster", "connector");
+    expect(results.map((r) => r.name)).toContain("connector-k8s-cluster");
+  });
+});
@@ -16,6 +16,10 @@ import { listOutputSchema } from "../../src/tools/output-schemas.js";
 vi.mock("../../src/utils/log-resolver.js", () => ({
   resolveLogContent: vi.fn().mockResolvedValue("[2026-03-09T17:01:23Z] info: mvn clean install\n[2026-03-09T17:01:45Z] error: BUILD FAILURE"),
   resolveLogDownloadUrl: vi.fn().mockResolvedValue("https://storage.example.com/logs.zip?signed=1"),
+  resolveLogFiles: vi.fn().mockResolvedValue([
+    { key: "stage1/build", text: "mvn clean install\n[ERROR] Tests failed: 3\nBUILD FAILURE" },
+    { key: "stage1/lint", text: "eslint ok" },
+  ]),
 }));
 vi.mock("../../src/utils/log-prefix.js", () => ({
   buildLogPrefixFromExecution: vi.fn().mockResolvedValue("acct1/pipeline/my-pipe/42/-exec-123"),
@@ -448,6 +452,33 @@ describe("harness_get — execution_log", () => {
     expect(resolveLogContentMock).not.toHaveBeenCalled();
   });
 
+  it("greps logs server-side when log_search is set", async () => {
+    const result = await server.call("harness_get", {
+      resource_type: "execution_log",
+      resource_id: "exec-123",
+      log_search: "error",
+      log_context_lines: 1,
+    });
+    expect(result.isError).toBeUndefined();
+    const data = parseResult(result) as { total_matches: number; hunks: Array<{ log_key: string; lines: string[] }> };
+    expect(data.total_matches).toBe(1);
+    expect(data.hunks).toEqual([
+      { log_key: "stage1/build", lines: ["1- mvn clean install", "2: [ERROR] Tests failed: 3", "3- BUILD FAILURE"] },
+    ]);
+    expect(resolveLogContentMock).not.toHaveBeenCalled();
+  });
+
+  it("rejects an invalid log_search regex before downloading", async () => {
+    const result = await server.call("harness_get", {
+      resource_type: "execution_log",
+      resource_id: "exec-123",
+      log_search: "([",
+    });
+    expect(result.isError).toBe(true);
+    expect(JSON.stringify(result.content)).toContain("Invalid log_search pattern");
+    expect(buildLogPrefixMock).not.toHaveBeenCalled();
+  });
+
   it("maps resource_id to execution_id and auto-builds prefix", async () => {
     const result = await server.call("harness_get", {
       resource_type: "execution_log",
@@ -934,6 +965,36 @@ describe("harness_update", () => {
     expect(mockRequest).toHaveBeenCalledOnce();
   });
 
+  it("returns a change plan without writing when plan_only is set, and applies it by plan_hash", async () => {
+    const args = { resource_type: "pipeline", resource_id: "my-pipe", body: { yamlPipeline: "pipeline:\n  name: Planned" } };
+    const planned = await server.call("harness_update", { ...args, plan_only: true });
+    expect(planned.isError).toBeUndefined();
+    const data = parseResult(planned) as { plan_hash: string; plan: Record<string, unknown> };
+    expect(data.plan_hash).toMatch(/^[0-9a-f]{64}$/);
+    expect(data.plan).toMatchObject({ tool: "harness_update", operation: "update", resource_type: "pipeline", resource_id: "my-pipe", account_id: "test-account" });
+    expect(mockRequest).not.toHaveBeenCalled();
+
+    const changed = await server.call("harness_update", { ...args, body: { yamlPipeline: "pipeline:\n  name: Other" }, plan_hash: data.plan_hash });
+    expect(changed.isError).toBe(true);
+    expect(parseResult(changed)).toMatchObject({ error: expect.stringContaining("does not match") });
+    expect(mockRequest).not.toHaveBeenCalled();
+
+    const otherSession = makeMcpServer("accept");
+    const { registerUpdateTool } = await import("../../src/tools/harness-update.js");
+    registerUpdateTool(otherSession, registry, client, makeConfig());
+    const foreign = await otherSession.call("harness_update", { ...args, plan_hash: data.plan_hash });
+    expect(foreign.isError).toBe(true);
+    expect(parseResult(foreign)).toMatchObject({ error: expect.stringContaining("unknown") });
+
+    const applied = await server.call("harness_update", { ...args, plan_hash: data.plan_hash });
+    expect(applied.isError).toBeUndefined();
+    expect(mockRequest).toHaveBeenCalledOnce();
+
+    const reused = await server.call("harness_update", { ...args, plan_hash: data.plan_hash });
+    expect(reused.isError).toBe(true);
+    expect(parseResult(reused)).toMatchObject({ error: expect.stringContaining("already applied") });
+  });
+
   it("coerces JSON-string bodies before dispatch", async () => {
     registry = new Registry(makeConfig({ HARNESS_TOOLSETS: "platform" }));
     mockRequest = vi.fn().mockResolvedValue({ data: { identifier: "proj1" } });
@@ -1469,6 +1530,109 @@ describe("harness_delete", () => {
     expect(parseResult(result)).toMatchObject({ error: expect.stringContaining("Conflicting identifiers") });
     expect(mockRequest).not.toHaveBeenCalled();
   });
+
+  describe("two-phase delete", () => {
+    let twoPhaseServer: ReturnType<typeof makeMcpServer>;
+
+    beforeEach(async () => {
+      const { resetDeleteTokens } = await import("../../src/utils/delete-confirmation.js");
+      resetDeleteTokens();
+      mockRequest.mockImplementation(async (opts: { method?: string; path: string }) => {
+        if (opts.path.includes("entitySetupUsage")) {
+          return { data: { totalItems: 1, content: [{ referredByEntity: { type: "Triggers", name: "nightly", entityRef: { identifier: "nightly" } } }] } };
+        }
+        if (opts.path.includes("execution/summary")) {
+          return { data: { content: [{ planExecutionId: "exec-9", status: "Success", startTs: 1700000000000 }], totalElements: 1 } };
+        }
+        if (opts.method === "GET") return { data: { pipeline: { name: "My Pipe", lastUpdatedAt: 1690000000000 } } };
+        return { data: true };
+      });
+      twoPhaseServer = makeMcpServer("accept");
+      const { registerDeleteTool } = await import("../../src/tools/harness-delete.js");
+      registerDeleteTool(twoPhaseServer, registry, client, makeConfig({ HARNESS_TWO_PHASE_DELETE: true }));
+    });
+
+    it("returns an impact summary and token instead of deleting", async () => {
+      const result = await twoPhaseServer.call("harness_delete", { resource_type: "pipeline", resource_id: "my-pipe" });
+      expect(result.isError).toBeUndefined();
+      const data = parseResult(result) as { deleted: boolean; confirmation_token: string; impact: Record<string, unknown> };
+      expect(data.deleted).toBe(false);
+      expect(data.confirmation_token).toMatch(/^del_/);
+      expect(data.impact).toMatchObject({
+        references: { total: 1, referenced_by: [{ type: "Triggers", identifier: "nightly" }] },
+        last_execution: { execution_id: "exec-9", status: "Success" },
+      });
+      const methods = mockRequest.mock.calls.map((c) => (c[0] as { method?: string }).method);
+      expect(methods).not.toContain("DELETE");
+    });
+
+    it("deletes with the returned token, once", async () => {
+      const preview = parseResult(await twoPhaseServer.call("harness_delete", { resource_type: "pipeline", resource_id: "my-pipe" })) as { confirmation_token: string };
+      const args = { resource_type: "pipeline", resource_id: "my-pipe", confirmation_token: preview.confirmation_token };
+
+      const result = await twoPhaseServer.call("harness_delete", args);
+      expect(result.isError).toBeUndefined();
+      expect((parseResult(result) as { deleted: boolean }).deleted).toBe(true);
+
+      const reused = await twoPhaseServer.call("harness_delete", args);
+      expect(reused.isError).toBe(true);
+      expect(parseResult(reused)).toMatchObject({ error: expect.stringContaining("already used") });
+    });
+
+    it("puts the impact summary in the plan and still requires its confirmation token", async () => {
+      const args = { resource_type: "pipeline", resource_id: "my-pipe" };
+      const planned = parseResult(await twoPhaseServer.call("harness_delete", { ...args, plan_only: true })) as {
+        deleted: boolean; plan_hash: string; confirmation_token: string; impact: Record<string, unknown>;
+      };
+      expect(planned.deleted).toBe(false);
+      expect(planned.impact).toMatchObject({ references: { total: 1 } });
+      expect(planned.confirmation_token).toMatch(/^del_/);
+
+      const withoutToken = await twoPhaseServer.call("harness_delete", { ...args, plan_hash: planned.plan_hash });
+      expect(withoutToken.isError).toBe(true);
+      expect(parseResult(withoutToken)).toMatchObject({ error: expect.stringContaining("confirmation_token") });
+      const methods = mockRequest.mock.calls.map((c) => (c[0] as { method?: string }).method);
+      expect(methods).not.toContain("DELETE");
+
+      const result = await twoPhaseServer.call("harness_delete", { ...args, plan_hash: planned.plan_hash, confirmation_token: planned.confirmation_token });
+      expect(result.isError).toBeUndefined();
+      expect((parseResult(result) as { deleted: boolean }).deleted).toBe(true);
+    });
+
+    it("rejects a token issued for a different resource", async () => {
+      const preview = parseResult(await twoPhaseServer.call("harness_delete", { resource_type: "pipeline", resource_id: "my-pipe" })) as { confirmation_token: string };
+      const result = await twoPhaseServer.call("harness_delete", {
+        resource_type: "pipeline",
+        resource_id: "other-pipe",
+        confirmation_token: preview.confirmation_token,
+      });
+      expect(result.isError).toBe(true);
+      expect(parseResult(result)).toMatchObject({ error: expect.stringContaining("different resource") });
+    });
+
+    it("rejects a token issued to another session", async () => {
+      const preview = parseResult(await twoPhaseServer.call("harness_delete", { resource_type: "pipeline", resource_id: "my-pipe" })) as { confirmation_token: string };
+      const otherServer = makeMcpServer("accept");
+      const { registerDeleteTool } = await import("../../src/tools/harness-delete.js");
+      registerDeleteTool(otherServer, registry, client, makeConfig({ HARNESS_TWO_PHASE_DELETE: true }));
+
+      const result = await otherServer.call("harness_delete", {
+        resource_type: "pipeline",
+        resource_id: "my-pipe",
+        confirmation_token: preview.confirmation_token,
+      });
+      expect(result.isError).toBe(true);
+      expect(parseResult(result)).toMatchObject({ error: expect.stringContaining("Unknown or already used") });
+      const methods = mockRequest.mock.calls.map((c) => (c[0] as { method?: string }).method);
+      expect(methods).not.toContain("DELETE");
+    });
+
+    it("binds the token to the account", async () => {
+      const { deleteTokenKey } = await import("../../src/utils/delete-confirmation.js");
+      const config = makeConfig();
+      expect(deleteTokenKey("acct-a", "pipeline", "my-pipe", {}, config)).not.toBe(deleteTokenKey("acct-b", "pipeline", "my-pipe", {}, config));
+    });
+  });
 });
 
 describe("harness_execute", () => {
@@ -1523,6 +1687,7 @@ describe("harness_execute", () => {
       "resource_type", "url", "action", "resource_id", "org_id", "project_id",
       "resource_scope", "inputs", "input_set_ids", "body", "params", "confirm",
       "wait", "wait_timeout_seconds", "wait_poll_interval_seconds", "queries",
+      "plan_only", "plan_hash",
     ];
     for (const field of documented) {
       const desc = schema.inputSchema[field]?.description;
@@ -2834,6 +2999,16 @@ describe("harness_describe", () => {
     expect(data.hint).toContain("harness_describe");
   });
 
+  it("returns server status when server_status is set", async () => {
+    const result = await server.call("harness_describe", { server_status: true });
+    expect(result.isError).toBeUndefined();
+    const data = parseResult(result) as { uptime_seconds: number; toolsets: { enabled: string[] }; api: { calls: number }; memory_mb: { rss: number } };
+    expect(data.uptime_seconds).toBeGreaterThanOrEqual(0);
+    expect(data.toolsets.enabled).toEqual(["pipelines"]);
+    expect(typeof data.api.calls).toBe("number");
+    expect(data.memory_mb.rss).toBeGreaterThan(0);
+  });
+
   it("returns details for a specific resource_type", async () => {
     const result = await server.call("harness_describe", { resource_type: "pipeline" });
     expect(result.isError).toBeUndefined();
@@ -145,6 +145,9 @@ describe("Zod 4 input schema descriptions (PR #398 regression)", () => {
       "resource_scope",
       "params",
       "return_download_url",
+      "log_search",
+      "log_context_lines",
+      "log_max_matches",
     ]);
   });
 
@@ -162,4 +165,16 @@ describe("Zod 4 input schema descriptions (PR #398 regression)", () => {
       "example_search",
     ]);
   });
+
+  it("org_id and project_id descriptions name the configured defaults", async () => {
+    const { registerListTool } = await import("../../src/tools/harness-list.js");
+    registerListTool(server as never, registry, client);
+    const schema = server.schema("harness_list").inputSchema;
+    expect(schema.org_id?.description).toContain("Defaults to 'default' if omitted");
+    expect(schema.project_id?.description).toContain("Defaults to 'test-project' if omitted");
+
+    const bare = makeMcpServer();
+    registerListTool(bare as never, new Registry(makeConfig({ HARNESS_ORG: undefined, HARNESS_PROJECT: undefined })), client);
+    expect(bare.schema("harness_list").inputSchema.project_id?.description).toContain("No default is configured");
+  });
 });
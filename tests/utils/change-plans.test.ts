@@ -0,0 +1,60 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+  buildChangePlan,
+  ChangePlanStore,
+  digestChangePlan,
+  PLAN_TTL_MS,
+  type ChangePlanRequest,
+} from "../../src/utils/change-plans.js";
+
+function request(input: Record<string, unknown> = {}): ChangePlanRequest {
+  return {
+    tool: "harness_update",
+    operation: "update",
+    resourceType: "pipeline",
+    resourceId: "deploy",
+    risk: "medium_write",
+    input: { resource_type: "pipeline", resource_id: "deploy", org_id: "default", project_id: "payments", body: { yamlPipeline: "pipeline: {}" }, ...input },
+  };
+}
+
+describe("change plans", () => {
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it("digests the same change identically regardless of key order and contract fields", () => {
+    const a = buildChangePlan(request({ params: { x: 1, y: 2 } }), "acct");
+    const b = buildChangePlan(request({ params: { y: 2, x: 1 }, plan_only: true, confirm: true }), "acct");
+    expect(digestChangePlan(a)).toBe(digestChangePlan(b));
+    expect(a).toMatchObject({ resource_id: "deploy", org_id: "default", project_id: "payments", account_id: "acct" });
+    expect(digestChangePlan(buildChangePlan(request(), "other"))).not.toBe(digestChangePlan(buildChangePlan(request(), "acct")));
+  });
+
+  it("issues a hash that cannot be recomputed from the plan", () => {
+    const store = new ChangePlanStore();
+    const first = store.issue(request(), "acct") as { plan_hash: string };
+    const second = store.issue(request(), "acct") as { plan_hash: string };
+    expect(first.plan_hash).not.toBe(second.plan_hash);
+    expect(first.plan_hash).not.toBe(digestChangePlan(buildChangePlan(request(), "acct")));
+  });
+
+  it("applies an issued plan once", () => {
+    const store = new ChangePlanStore();
+    const { plan_hash: hash } = store.issue(request(), "acct") as { plan_hash: string };
+    expect(store.consume(request({ plan_hash: hash }), "acct", hash)).toBeUndefined();
+    expect(store.consume(request(), "acct", hash)).toContain("already applied");
+  });
+
+  it("refuses changed arguments, other accounts, other sessions, and expired plans", () => {
+    vi.useFakeTimers();
+    const store = new ChangePlanStore();
+    const { plan_hash: hash } = store.issue(request(), "acct") as { plan_hash: string };
+    expect(store.consume(request({ body: { yamlPipeline: "pipeline: {x: 1}" } }), "acct", hash)).toContain("does not match");
+    expect(store.consume(request(), "other", hash)).toContain("unknown");
+    expect(new ChangePlanStore().consume(request(), "acct", hash)).toContain("unknown");
+
+    vi.advanceTimersByTime(PLAN_TTL_MS + 1);
+    expect(store.consume(request(), "acct", hash)).toContain("expired");
+  });
+});
@@ -0,0 +1,56 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+import { beginOperation, CHECKPOINT_TTL_MS, resetCheckpoints } from "../../src/utils/checkpoints.js";
+
+describe("beginOperation", () => {
+  afterEach(() => {
+    resetCheckpoints();
+    vi.useRealTimers();
+  });
+
+  it("resumes from the last saved state", () => {
+    const first = beginOperation<{ page: number }>("export", "acct", { a: 1, b: 2 });
+    expect(first.resumed).toBeUndefined();
+    first.save({ page: 1 });
+    first.save({ page: 2 });
+
+    // Argument order does not matter
+    const second = beginOperation<{ page: number }>("export", "acct", { b: 2, a: 1 }, first.id);
+    expect(second.id).toBe(first.id);
+    expect(second.resumed).toEqual({ page: 2 });
+  });
+
+  it("rejects tokens from another account, another operation kind, or other arguments", () => {
+    const op = beginOperation("export", "acct", { a: 1 });
+    op.save({});
+    expect(() => beginOperation("export", "other", { a: 1 }, op.id)).toThrow("unknown or has expired");
+    expect(() => beginOperation("scan", "acct", { a: 1 }, op.id)).toThrow("unknown or has expired");
+    expect(() => beginOperation("export", "acct", { a: 2 }, op.id)).toThrow("different arguments");
+  });
+
+  it("forgets completed and expired operations", () => {
+    vi.useFakeTimers();
+    const done = beginOperation("export", "acct", {});
+    done.save({});
+    done.complete();
+    expect(() => beginOperation("export", "acct", {}, done.id)).toThrow("unknown or has expired");
+
+    const stale = beginOperation("export", "acct", {});
+    stale.save({});
+    vi.advanceTimersByTime(CHECKPOINT_TTL_MS + 1);
+    expect(() => beginOperation("export", "acct", {}, stale.id)).toThrow("unknown or has expired");
+  });
+
+  it("caps checkpoints per account without evicting other accounts'", () => {
+    const theirs = beginOperation("export", "other", { a: 1 });
+    theirs.save({ page: 3 });
+    const mine = Array.from({ length: 51 }, (_, i) => {
+      const op = beginOperation("export", "acct", { i });
+      op.save({});
+      return op;
+    });
+
+    expect(() => beginOperation("export", "acct", { i: 0 }, mine[0]!.id)).toThrow("unknown or has expired");
+    expect(beginOperation("export", "acct", { i: 1 }, mine[1]!.id).resumed).toEqual({});
+    expect(beginOperation("export", "other", { a: 1 }, theirs.id).resumed).toEqual({ page: 3 });
+  });
+});
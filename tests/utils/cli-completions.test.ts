@@ -0,0 +1,33 @@
+import { describe, expect, it } from "vitest";
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+import { Registry } from "../../src/registry/index.js";
+import { registerAllTools } from "../../src/tools/index.js";
+import { CLI_OPTIONS } from "../../src/utils/cli.js";
+import { TOOL_NAMES, generateCompletions } from "../../src/utils/cli-completions.js";
+import { listServerTools, loadCatalogConfig } from "../../src/utils/tool-catalog.js";
+
+describe("generateCompletions", () => {
+  it.each(["bash", "zsh", "fish"] as const)("%s covers every option, subcommand, and tool", (shell) => {
+    const script = generateCompletions(shell, "1.2.3");
+    for (const option of CLI_OPTIONS) expect(script).toContain(shell === "fish" ? `-l ${option.flag.slice(2)}` : option.flag);
+    for (const word of ["stdio", "http", "socket", "config", "tools", "call", "completions", "env", "schema"]) expect(script).toContain(word);
+    expect(script).toContain("harness_diagnose");
+  });
+
+  it("renders a man page with commands, options, and toolsets", () => {
+    const page = generateCompletions("man", "1.2.3");
+    expect(page).toMatch(/^\.TH HARNESS-MCP-V2 1 "" "harness-mcp-v2 1\.2\.3"/);
+    expect(page).toContain(".SH COMMANDS");
+    expect(page).toContain("\\-\\-socket\\-path");
+    expect(page).toMatch(/HARNESS_TOOLSETS.*pipelines/);
+  });
+
+  it("completes exactly the tools the server registers", async () => {
+    const config = loadCatalogConfig({});
+    const server = new McpServer({ name: "completions-test", version: "0.0.0" });
+    registerAllTools(server, new Registry(config), {} as HarnessClient, config);
+    const names = (await listServerTools(server)).map((t) => t.name);
+    expect([...TOOL_NAMES].sort()).toEqual(names.sort());
+  });
+});
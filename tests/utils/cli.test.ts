@@ -1,5 +1,5 @@
 import { describe, it, expect, beforeEach, afterEach, vi } from "vitest";
-import { parseArgs, resolvePort } from "../../src/utils/cli.js";
+import { assertTransportSupportsMode, parseArgs, resolvePort } from "../../src/utils/cli.js";
 
 describe("parseArgs", () => {
   let originalPort: string | undefined;
@@ -104,8 +104,8 @@ describe("parseArgs", () => {
   });
 
   it("throws on unknown transport", () => {
-    expect(() => parseArgs(["grpc"])).toThrow(
-      'Unknown transport: "grpc". Supported: stdio, http',
+    expect(() => parseArgs(["websocket"])).toThrow(
+      'Unknown transport: "websocket". Supported: stdio, http',
     );
   });
 
@@ -118,4 +118,101 @@ describe("parseArgs", () => {
     const args = parseArgs(["--port", "65535"]);
     expect(args.port).toBe(65535);
   });
+
+
+  it("defaults smp to false", () => {
+    expect(parseArgs([]).smp).toBe(false);
+  });
+
+  it("parses --smp alongside transport and port", () => {
+    const args = parseArgs(["http", "--smp", "--port", "8080"]);
+    expect(args.smp).toBe(true);
+    expect(args.transport).toBe("http");
+    expect(args.port).toBe(8080);
+  });
+
+  it("parses --strict-schemas (off by default)", () => {
+    expect(parseArgs([]).strictSchemas).toBe(false);
+    expect(parseArgs(["http", "--strict-schemas"])).toMatchObject({ transport: "http", strictSchemas: true });
+  });
+
+  it("parses --toolsets with a space or =, and keeps it out of the positionals", () => {
+    expect(parseArgs([]).toolsets).toBeUndefined();
+    expect(parseArgs(["--toolsets", "recommended", "http"])).toMatchObject({ transport: "http", toolsets: "recommended" });
+    expect(parseArgs(["--toolsets=pipelines,logs"]).toolsets).toBe("pipelines,logs");
+    expect(parseArgs(["tools", "list", "--toolsets", "recommended"])).toMatchObject({ command: { name: "tools-list" }, toolsets: "recommended" });
+  });
+
+  it("--socket-path selects the socket transport", () => {
+    expect(parseArgs(["--socket-path", "/tmp/harness.sock"])).toMatchObject({ transport: "socket", socketPath: "/tmp/harness.sock" });
+    expect(parseArgs(["socket", "--socket-path=/tmp/h.sock"])).toMatchObject({ transport: "socket", socketPath: "/tmp/h.sock" });
+  });
+
+  it("parses the grpc transport", () => {
+    expect(parseArgs(["grpc", "--port", "50051"])).toMatchObject({ transport: "grpc", port: 50051 });
+    expect(() => parseArgs(["grpc", "--socket-path", "/tmp/h.sock"])).toThrow(/grpc transport/);
+  });
+
+  it("rejects the socket transport without a path and a path with http", () => {
+    expect(() => parseArgs(["socket"])).toThrow(/--socket-path/);
+    expect(() => parseArgs(["http", "--socket-path", "/tmp/h.sock"])).toThrow(/http/);
+  });
+
+  it("parses the config env subcommand", () => {
+    expect(parseArgs(["config", "env", "--env-file", "prod.env"])).toMatchObject({ command: { name: "config-env" }, envFile: "prod.env" });
+    expect(parseArgs(["http"]).command).toBeUndefined();
+    expect(() => parseArgs(["config", "dump"])).toThrow(/Unknown config subcommand: "dump"/);
+  });
+
+  it("parses the tools subcommands", () => {
+    expect(parseArgs(["tools", "list"]).command).toEqual({ name: "tools-list" });
+    expect(parseArgs(["tools", "schema", "harness_get"]).command).toEqual({ name: "tools-schema", tool: "harness_get" });
+    expect(() => parseArgs(["tools", "schema"])).toThrow(/requires a tool name/);
+    expect(() => parseArgs(["tools", "run"])).toThrow(/Unknown tools subcommand/);
+  });
+
+  it("parses call with JSON arguments", () => {
+    expect(parseArgs(["call", "harness_list", "--args", '{"resource_type":"pipeline"}']).command).toEqual({
+      name: "call",
+      tool: "harness_list",
+      args: { resource_type: "pipeline" },
+    });
+    expect(parseArgs(["call", "harness_status"]).command).toEqual({ name: "call", tool: "harness_status", args: {} });
+    expect(parseArgs(["call", "harness_get", '--args={"resource_id":"x"}']).command).toMatchObject({ args: { resource_id: "x" } });
+  });
+
+  it("parses fixtures record with an output directory", () => {
+    expect(parseArgs(["fixtures", "record", "./fixtures", "--toolsets", "pipelines"])).toMatchObject({
+      command: { name: "fixtures-record", dir: "./fixtures" },
+      toolsets: "pipelines",
+    });
+    expect(() => parseArgs(["fixtures", "record"])).toThrow(/requires an output directory/);
+    expect(() => parseArgs(["fixtures", "replay", "x"])).toThrow(/Unknown fixtures subcommand/);
+  });
+
+  it("parses the self-test subcommand", () => {
+    expect(parseArgs(["self-test", "--toolsets", "pipelines"])).toMatchObject({ command: { name: "self-test" }, toolsets: "pipelines" });
+  });
+
+  it("parses the completions subcommand", () => {
+    expect(parseArgs(["completions", "zsh"]).command).toEqual({ name: "completions", shell: "zsh" });
+    expect(parseArgs(["completions", "man"]).command).toEqual({ name: "completions", shell: "man" });
+    expect(() => parseArgs(["completions", "powershell"])).toThrow(/bash, zsh, fish, man/);
+  });
+
+  it("rejects call without a tool or with invalid arguments", () => {
+    expect(() => parseArgs(["call"])).toThrow(/requires a tool name/);
+    expect(() => parseArgs(["call", "harness_list", "--args", "{"])).toThrow(/not valid JSON/);
+    expect(() => parseArgs(["call", "harness_list", "--args", "[1]"])).toThrow(/JSON object/);
+  });
+});
+
+describe("assertTransportSupportsMode", () => {
+  it("allows multi-user mode on the HTTP and gRPC transports only", () => {
+    expect(() => assertTransportSupportsMode("http", "multi-user")).not.toThrow();
+    expect(() => assertTransportSupportsMode("grpc", "multi-user")).not.toThrow();
+    expect(() => assertTransportSupportsMode("stdio", "multi-user")).toThrow(/HTTP or gRPC/);
+    expect(() => assertTransportSupportsMode("socket", "multi-user")).toThrow(/single-user for socket/);
+    expect(() => assertTransportSupportsMode("stdio", "single-user")).not.toThrow();
+  });
 });
@@ -0,0 +1,61 @@
+import { describe, expect, it, vi } from "vitest";
+import type { Config } from "../../src/config.js";
+import { ClientPool, clientPoolKey } from "../../src/utils/client-pool.js";
+
+function makeConfig(overrides?: Partial<Config>): Config {
+  return {
+    HARNESS_API_KEY: "pat.acct1.tok.sec",
+    HARNESS_ACCOUNT_ID: "acct1",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    ...overrides,
+  } as Config;
+}
+
+describe("clientPoolKey", () => {
+  it("never contains the raw API key", () => {
+    const key = clientPoolKey(makeConfig());
+    expect(key).not.toContain("pat.acct1.tok.sec");
+    expect(key.startsWith("acct1|https://app.harness.io|")).toBe(true);
+  });
+});
+
+describe("ClientPool", () => {
+  it("shares one client between sessions with the same account and credential", () => {
+    const factory = vi.fn((config: Config) => ({ account: config.HARNESS_ACCOUNT_ID }));
+    const pool = new ClientPool(factory);
+
+    const a = pool.acquire(makeConfig());
+    const b = pool.acquire(makeConfig());
+
+    expect(a).toBe(b);
+    expect(factory).toHaveBeenCalledTimes(1);
+  });
+
+  it("builds separate clients per account and per credential", () => {
+    const pool = new ClientPool((config: Config) => ({ account: config.HARNESS_ACCOUNT_ID }));
+
+    const a = pool.acquire(makeConfig());
+    const otherAccount = pool.acquire(makeConfig({ HARNESS_ACCOUNT_ID: "acct2", HARNESS_API_KEY: "pat.acct2.tok.sec" }));
+    const otherUser = pool.acquire(makeConfig({ HARNESS_API_KEY: "pat.acct1.tok2.sec" }));
+
+    expect(otherAccount).not.toBe(a);
+    expect(otherUser).not.toBe(a);
+    expect(pool.stats()).toEqual({ clients: 3, accounts: 2 });
+  });
+
+  it("discards a client once its last session releases it", () => {
+    const factory = vi.fn(() => ({}));
+    const pool = new ClientPool(factory);
+
+    pool.acquire(makeConfig());
+    pool.acquire(makeConfig());
+    pool.release(makeConfig());
+    expect(pool.stats().clients).toBe(1);
+
+    pool.release(makeConfig());
+    expect(pool.stats()).toEqual({ clients: 0, accounts: 0 });
+
+    pool.acquire(makeConfig());
+    expect(factory).toHaveBeenCalledTimes(2);
+  });
+});
@@ -140,4 +140,33 @@ describe("compactItems", () => {
     expect(result[0].name).toBe("[Incident 1](https://app.harness.io/inc/1)");
     expect(result[0]).not.toHaveProperty("openInHarness");
   });
+
+
+  describe("unknownFields option", () => {
+    it("strips non-whitelisted fields by default", () => {
+      const result = compactItems([{ identifier: "p1", yaml: "pipeline: {}" }]) as Record<string, unknown>[];
+      expect(result[0]).toEqual({ identifier: "p1" });
+    });
+
+    it("keeps non-whitelisted fields under _extra when unknownFields is include", () => {
+      const items = [{ identifier: "p1", yaml: "pipeline: {}", gitDetails: { branch: "main" } }];
+      const result = compactItems(items, undefined, { unknownFields: "include" }) as Record<string, unknown>[];
+      expect(result[0]).toEqual({
+        identifier: "p1",
+        _extra: { yaml: "pipeline: {}", gitDetails: { branch: "main" } },
+      });
+    });
+
+    it("omits _extra when nothing was dropped", () => {
+      const result = compactItems([{ identifier: "p1", name: "P1" }], undefined, { unknownFields: "include" }) as Record<string, unknown>[];
+      expect(result[0]).not.toHaveProperty("_extra");
+    });
+
+    it("applies to compactFn output and does not duplicate openInHarness", () => {
+      const compactFn = (item: Record<string, unknown>) => ({ name: item.name, openInHarness: item.openInHarness });
+      const items = [{ name: "Svc", openInHarness: "https://app.harness.io/s", owner_team: "payments" }];
+      const result = compactItems(items, compactFn, { unknownFields: "include" }) as Record<string, unknown>[];
+      expect(result[0]).toEqual({ name: "[Svc](https://app.harness.io/s)", _extra: { owner_team: "payments" } });
+    });
+  });
 });
@@ -0,0 +1,74 @@
+import { describe, it, expect, vi } from "vitest";
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { Client } from "@modelcontextprotocol/sdk/client/index.js";
+import { InMemoryTransport } from "@modelcontextprotocol/sdk/inMemory.js";
+import { createArgumentCompleter, rankCompletions, type ArgumentCompleter } from "../../src/utils/completions.js";
+import { registerAllPrompts } from "../../src/prompts/index.js";
+import type { Config } from "../../src/config.js";
+
+function makeRegistry(items: Record<string, Array<{ identifier: string }>>) {
+  return {
+    getResource: vi.fn((type: string) => {
+      if (!(type in items)) throw new Error(`Unknown resource type: ${type}`);
+      return {};
+    }),
+    dispatch: vi.fn(async (_client: unknown, type: string) => ({ items: items[type] })),
+  } as any;
+}
+
+const config = { HARNESS_ORG: "default", HARNESS_PROJECT: "payments" } as Config;
+
+async function connect(server: McpServer): Promise<Client> {
+  const [clientTransport, serverTransport] = InMemoryTransport.createLinkedPair();
+  const client = new Client({ name: "test-client", version: "0.0.1" });
+  await Promise.all([client.connect(clientTransport), server.connect(serverTransport)]);
+  return client;
+}
+
+describe("rankCompletions", () => {
+  it("puts prefix matches before substring matches, case-insensitively", () => {
+    expect(rankCompletions(["deploy_prod", "build", "Prod_hotfix", "ci"], "prod")).toEqual(["Prod_hotfix", "deploy_prod"]);
+  });
+});
+
+describe("createArgumentCompleter", () => {
+  it("completes entity arguments from a scoped list call and caches it", async () => {
+    const registry = makeRegistry({ pipeline: [{ identifier: "deploy_prod" }, { identifier: "build_main" }] });
+    const complete = createArgumentCompleter(registry, {} as any, config);
+
+    expect(await complete("pipelineId", "dep", { arguments: { projectId: "checkout" } })).toEqual(["deploy_prod"]);
+    expect(await complete("pipeline_id", "bu", { arguments: { project_id: "checkout" } })).toEqual(["build_main"]);
+
+    expect(registry.dispatch).toHaveBeenCalledTimes(1);
+    expect(registry.dispatch.mock.calls[0]![3]).toMatchObject({ org_id: "default", project_id: "checkout" });
+  });
+
+  it("returns nothing for unknown arguments, disabled toolsets, and failed lookups", async () => {
+    const registry = makeRegistry({ project: [] });
+    registry.dispatch.mockRejectedValueOnce(new Error("403"));
+    const complete = createArgumentCompleter(registry, {} as any, config);
+
+    expect(await complete("severity", "hi")).toEqual([]);
+    expect(await complete("repoId", "r")).toEqual([]);
+    expect(await complete("projectId", "p")).toEqual([]);
+  });
+});
+
+describe("prompt completions", () => {
+  it("serves completion/complete for prompt arguments and survives registration on multiple servers", async () => {
+    const complete: ArgumentCompleter = vi.fn(async (_arg, value) => ["payments", "platform"].filter((p) => p.startsWith(value)));
+    const servers = [0, 1].map(() => {
+      const server = new McpServer({ name: "test-server", version: "0.0.1" }, { capabilities: { prompts: {} } });
+      registerAllPrompts(server, complete);
+      return server;
+    });
+    const client = await connect(servers[1]!);
+
+    const result = await client.complete({
+      ref: { type: "ref/prompt", name: "debug-pipeline" },
+      argument: { name: "projectId", value: "pa" },
+    });
+    expect(result.completion.values).toEqual(["payments"]);
+    expect(complete).toHaveBeenCalledWith("projectId", "pa", expect.anything());
+  });
+});
@@ -0,0 +1,37 @@
+import { describe, expect, it } from "vitest";
+import { maskEnvValue, renderConfigEnv } from "../../src/utils/config-env.js";
+
+describe("maskEnvValue", () => {
+  it("hides credentials but shows ordinary values", () => {
+    expect(maskEnvValue("HARNESS_API_KEY", "pat.acc.tok.secret")).toBe("<set, 18 chars>");
+    expect(maskEnvValue("HARNESS_MCP_JWT_SECRET", "s3cr3t")).toBe("<set, 6 chars>");
+    expect(maskEnvValue("HARNESS_BASE_URL", "https://app.harness.io")).toBe("https://app.harness.io");
+  });
+});
+
+describe("renderConfigEnv", () => {
+  it("lists variables with their source and never prints secrets", () => {
+    const out = renderConfigEnv({ HARNESS_API_KEY: "pat.acc1.tok.verysecret", HARNESS_ORG: "default" });
+    expect(out).toMatch(/HARNESS_API_KEY\s+<set, 23 chars>\s+\[env\]/);
+    expect(out).toMatch(/HARNESS_ORG\s+default\s+\[env\]/);
+    expect(out).toMatch(/HARNESS_DEPLOYMENT\s+saas\s+\[default\]/);
+    expect(out).toMatch(/HARNESS_PROJECT\s+-\s+\[unset\]/);
+    expect(out).not.toContain("verysecret");
+  });
+
+  it("prints the effective service routes", () => {
+    const out = renderConfigEnv({
+      HARNESS_API_KEY: "pat.acc1.tok.secret",
+      HARNESS_BASE_URL: "https://smp.example.com",
+      HARNESS_DEPLOYMENT: "smp",
+      HARNESS_ROUTE_MAP: '{"/log-service":{"url":"https://logs.{cluster}.example.com"}}',
+    });
+    expect(out).toContain("Cluster:    smp");
+    expect(out).toMatch(/\/ng\/api\s+-> https:\/\/smp\.example\.com\/gateway\/ng\/api/);
+    expect(out).toMatch(/\/log-service\s+-> https:\/\/logs\.smp\.example\.com\/log-service/);
+  });
+
+  it("reports invalid configuration instead of throwing", () => {
+    expect(renderConfigEnv({ HARNESS_API_KEY: "pat.acc1.tok.secret", HARNESS_ROUTE_MAP: "{" })).toContain("Configuration is invalid");
+  });
+});
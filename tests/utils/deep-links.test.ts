@@ -1,5 +1,5 @@
 import { describe, it, expect } from "vitest";
-import { buildDeepLink } from "../../src/utils/deep-links.js";
+import { buildDeepLink, harnessUiBaseUrl } from "../../src/utils/deep-links.js";
 
 describe("buildDeepLink", () => {
   const baseUrl = "https://app.harness.io";
@@ -44,3 +44,18 @@ describe("buildDeepLink", () => {
     expect(url).toBe("https://app.harness.io/ng/account/abc123/home");
   });
 });
+
+describe("harnessUiBaseUrl", () => {
+  it("defaults to HARNESS_BASE_URL", () => {
+    expect(harnessUiBaseUrl({ HARNESS_BASE_URL: "https://app3.harness.io" })).toBe("https://app3.harness.io");
+  });
+
+  it("prefers HARNESS_UI_URL for vanity domains", () => {
+    expect(harnessUiBaseUrl({ HARNESS_BASE_URL: "https://harness-proxy.internal", HARNESS_UI_URL: "https://acme.harness.io/" }))
+      .toBe("https://acme.harness.io");
+  });
+
+  it("drops a /gateway ingress suffix", () => {
+    expect(harnessUiBaseUrl({ HARNESS_BASE_URL: "https://harness.example.com/gateway" })).toBe("https://harness.example.com");
+  });
+});
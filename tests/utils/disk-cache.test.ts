@@ -0,0 +1,76 @@
+import { describe, it, expect, afterEach, vi } from "vitest";
+import { mkdtempSync, readdirSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { DiskCache, createDiskCache } from "../../src/utils/disk-cache.js";
+
+const dirs: string[] = [];
+
+function tempDir(): string {
+  const dir = mkdtempSync(join(tmpdir(), "harness-disk-cache-"));
+  dirs.push(dir);
+  return dir;
+}
+
+afterEach(() => {
+  vi.useRealTimers();
+  for (const dir of dirs.splice(0)) rmSync(dir, { recursive: true, force: true });
+});
+
+describe("DiskCache", () => {
+  it("round-trips JSON values", async () => {
+    const cache = new DiskCache(tempDir(), "ns");
+    await cache.set("k", { a: [1, 2] }, 60_000);
+    expect(await cache.get("k")).toEqual({ a: [1, 2] });
+  });
+
+  it("returns undefined for missing keys", async () => {
+    const cache = new DiskCache(tempDir(), "ns");
+    expect(await cache.get("missing")).toBeUndefined();
+  });
+
+  it("expires entries after their TTL", async () => {
+    vi.useFakeTimers({ toFake: ["Date"] });
+    const cache = new DiskCache(tempDir(), "ns");
+    await cache.set("k", "v", 1_000);
+    vi.setSystemTime(Date.now() + 1_001);
+    expect(await cache.get("k")).toBeUndefined();
+  });
+
+  it("does not expose keys in file names", async () => {
+    const root = tempDir();
+    const cache = new DiskCache(root, "ns");
+    await cache.set(JSON.stringify(["acct-secret-id", "/ng/api"]), 1, 60_000);
+    const files = readdirSync(join(root, "ns"));
+    expect(files).toHaveLength(1);
+    expect(files[0]).toMatch(/^[0-9a-f]{64}\.json$/);
+  });
+
+  it("treats corrupt files as a miss", async () => {
+    const root = tempDir();
+    const cache = new DiskCache(root, "ns");
+    await cache.set("k", "v", 60_000);
+    const [file] = readdirSync(join(root, "ns"));
+    writeFileSync(join(root, "ns", file), "{not json");
+    expect(await cache.get("k")).toBeUndefined();
+  });
+
+  it("ignores zero TTL and delete removes entries", async () => {
+    const cache = new DiskCache(tempDir(), "ns");
+    await cache.set("zero", "v", 0);
+    expect(await cache.get("zero")).toBeUndefined();
+    await cache.set("k", "v", 60_000);
+    await cache.delete("k");
+    expect(await cache.get("k")).toBeUndefined();
+  });
+});
+
+describe("createDiskCache", () => {
+  it("returns undefined when no directory is configured", () => {
+    expect(createDiskCache(undefined, "ns")).toBeUndefined();
+  });
+
+  it("returns a cache when a directory is configured", () => {
+    expect(createDiskCache(tempDir(), "ns")).toBeInstanceOf(DiskCache);
+  });
+});
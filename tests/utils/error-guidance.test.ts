@@ -0,0 +1,29 @@
+import { describe, expect, it } from "vitest";
+import { guidanceFor, withGuidance } from "../../src/utils/error-guidance.js";
+
+describe("guidanceFor", () => {
+  it("explains log-service 403s", () => {
+    expect(guidanceFor({ status: 403, path: "/gateway/log-service/blob/download", message: "forbidden" })?.id).toBe("log_service_forbidden");
+  });
+
+  it("suggests a branch for Git-backed entities only when none was passed", () => {
+    const error = { status: 404, path: "/pipeline/api/pipelines/deploy", message: "Pipeline [deploy] not found" };
+    expect(guidanceFor(error)?.id).toBe("remote_entity_without_branch");
+    expect(guidanceFor({ ...error, params: { branch: "main" } })).toBeUndefined();
+    expect(guidanceFor({ ...error, path: "/ng/api/connectors/github" })).toBeUndefined();
+  });
+
+  it("recognizes missing module licenses before generic permission errors", () => {
+    expect(guidanceFor({ status: 403, path: "/ccm/api/perspective", message: "Not authorized: no valid license for CE" })?.id).toBe("license_missing");
+  });
+
+  it("points RBAC failures at role assignments", () => {
+    expect(guidanceFor({ status: 403, path: "/ng/api/connectors", message: "Missing permission core_connector_view on connector" })?.id).toBe("rbac_forbidden");
+  });
+
+  it("leaves unknown errors alone", () => {
+    const error = { status: 500, path: "/ng/api/connectors", message: "Internal error" };
+    expect(guidanceFor(error)).toBeUndefined();
+    expect(withGuidance(error)).toBe("Internal error");
+  });
+});
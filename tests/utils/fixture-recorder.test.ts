@@ -0,0 +1,90 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { mkdtempSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { FixtureRecorder, FixtureSanitizer, writeFixtureFiles } from "../../src/utils/fixture-recorder.js";
+import { MockHarnessApi, loadMockFixtureDir } from "../../src/client/mock-api.js";
+
+const BASE = "https://app.harness.io";
+
+function json(body: unknown, status = 200): Response {
+  return new Response(JSON.stringify(body), { status, headers: { "content-type": "application/json" } });
+}
+
+describe("FixtureSanitizer", () => {
+  it("replaces the account ID, aliases emails consistently, and redacts secrets", () => {
+    const sanitizer = new FixtureSanitizer("AbC123xyz");
+    const out = sanitizer.value({
+      link: "https://app.harness.io/ng/account/AbC123xyz/home",
+      owner: "Jane.Doe@corp.com",
+      reviewers: ["other@corp.com", "jane.doe@corp.com"],
+      spec: { password: "hunter2", tokenRef: "account.gh_pat", nested: { apiKey: "k-123" } },
+      note: "using pat.AbC123xyz.tokenid.secretvalue",
+    });
+    expect(out).toEqual({
+      link: "https://app.harness.io/ng/account/mock/home",
+      owner: "user1@example.com",
+      reviewers: ["user2@example.com", "user1@example.com"],
+      spec: { password: "[REDACTED]", tokenRef: "account.gh_pat", nested: { apiKey: "[REDACTED]" } },
+      note: "using [REDACTED]",
+    });
+  });
+});
+
+describe("FixtureRecorder", () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = mkdtempSync(join(tmpdir(), "fixture-recorder-"));
+  });
+
+  afterEach(() => {
+    rmSync(dir, { recursive: true, force: true });
+  });
+
+  it("records sanitized mappings that mock mode serves back", async () => {
+    const recorder = new FixtureRecorder("acct42", BASE);
+    recorder.record({
+      method: "GET",
+      url: `${BASE}/ng/api/servicesV2?accountIdentifier=acct42&routingId=acct42&orgIdentifier=default&projectIdentifier=web&page=0&size=20`,
+      response: json({ status: "SUCCESS", data: { content: [{ identifier: "api", createdBy: "dev@corp.com" }] } }),
+    });
+    // The same request again, and a transient failure — neither adds a mapping
+    recorder.record({ method: "GET", url: `${BASE}/ng/api/servicesV2?orgIdentifier=default&projectIdentifier=web`, response: json({}) });
+    recorder.record({ method: "GET", url: `${BASE}/ng/api/connectors/x`, response: json({}, 503) });
+
+    const mappings = await recorder.mappings();
+    expect(mappings).toHaveLength(1);
+    expect(mappings[0]!.request).toEqual({
+      method: "GET",
+      urlPath: "/ng/api/servicesV2",
+      queryParameters: { orgIdentifier: { equalTo: "default" }, projectIdentifier: { equalTo: "web" } },
+    });
+
+    const files = await writeFixtureFiles(dir, mappings);
+    expect(files.map((f) => f.slice(dir.length + 1))).toEqual(["get-ng-api-servicesv2.json"]);
+
+    const api = new MockHarnessApi(loadMockFixtureDir(dir));
+    const res = await api.fetch(`${BASE}/ng/api/servicesV2?accountIdentifier=mock&orgIdentifier=default&projectIdentifier=web&size=5`, { method: "GET" });
+    expect(await res.json()).toEqual({ status: "SUCCESS", data: { content: [{ identifier: "api", createdBy: "user1@example.com" }] } });
+  });
+
+  it("keeps text bodies as text and binary bodies as base64", async () => {
+    const recorder = new FixtureRecorder("acct42", BASE);
+    recorder.record({
+      method: "GET",
+      url: `${BASE}/log-service/blob?key=acct42/pipeline/run`,
+      response: new Response("step acct42 done\n", { headers: { "content-type": "text/plain" } }),
+    });
+    recorder.record({
+      method: "GET",
+      url: `${BASE}/log-service/download`,
+      response: new Response(Buffer.from([0x50, 0x4b, 0x03, 0x04]), { headers: { "content-type": "application/zip" } }),
+    });
+
+    const [text, binary] = await recorder.mappings();
+    expect(text!.request.queryParameters).toEqual({ key: { equalTo: "mock/pipeline/run" } });
+    expect(text!.response.body).toBe("step mock done\n");
+    expect(binary!.response.base64Body).toBe(Buffer.from([0x50, 0x4b, 0x03, 0x04]).toString("base64"));
+  });
+});
@@ -0,0 +1,169 @@
+import { afterEach, describe, expect, it } from "vitest";
+import { connect, type Http2Server } from "node:http2";
+import type { AddressInfo } from "node:net";
+import {
+  GrpcError,
+  GrpcStatus,
+  ProtoWriter,
+  createGrpcServer,
+  decodeCallToolRequest,
+  decodeProto,
+  frameMessage,
+  parseGrpcTimeout,
+  protoString,
+  unframeMessage,
+  type GrpcToolHandlers,
+} from "../../src/utils/grpc-server.js";
+import type { Config } from "../../src/config.js";
+import { assertTransportSupportsMode } from "../../src/utils/cli.js";
+import { mergeConfigWithSessionHeaders, MissingSessionCredentialsError } from "../../src/utils/session-headers.js";
+
+const TOOL = { name: "harness_list", description: "List resources", inputSchema: { type: "object" as const } };
+
+const handlers: GrpcToolHandlers = {
+  authorize: (metadata) => {
+    if (metadata.authorization !== "Bearer secret") throw new GrpcError(GrpcStatus.UNAUTHENTICATED, "Unauthorized");
+    return undefined;
+  },
+  listTools: async () => [TOOL],
+  callTool: async (name, args) => ({ content: [{ type: "text", text: JSON.stringify({ name, args }) }] }),
+};
+
+interface GrpcReply {
+  status: string | undefined;
+  message: string | undefined;
+  body: Buffer;
+}
+
+async function call(server: Http2Server, method: string, request: Buffer, metadata: Record<string, string> = {}): Promise<GrpcReply> {
+  const { port } = server.address() as AddressInfo;
+  const session = connect(`http://127.0.0.1:${port}`);
+  try {
+    return await new Promise<GrpcReply>((resolve, reject) => {
+      const stream = session.request({ ":method": "POST", ":path": method, "content-type": "application/grpc", te: "trailers", ...metadata });
+      const chunks: Buffer[] = [];
+      let headers: Record<string, unknown> = {};
+      stream.on("response", (h) => { headers = h; });
+      stream.on("data", (chunk: Buffer) => chunks.push(chunk));
+      stream.on("trailers", (t) => { headers = { ...headers, ...t }; });
+      stream.on("end", () => resolve({
+        status: headers["grpc-status"] as string | undefined,
+        message: headers["grpc-message"] !== undefined ? decodeURIComponent(String(headers["grpc-message"])) : undefined,
+        body: chunks.length > 0 ? unframeMessage(Buffer.concat(chunks)) : Buffer.alloc(0),
+      }));
+      stream.on("error", reject);
+      stream.end(frameMessage(request));
+    });
+  } finally {
+    session.close();
+  }
+}
+
+describe("protobuf codec", () => {
+  it("round-trips strings, bools, and nested messages", () => {
+    const buf = new ProtoWriter().string(1, "harness_get").bool(3, true).message(4, new ProtoWriter().string(1, "ü".repeat(100))).finish();
+    const fields = decodeProto(buf);
+    expect(protoString(fields, 1)).toBe("harness_get");
+    expect(fields.get(3)).toEqual([1]);
+    expect(protoString(decodeProto(fields.get(4)![0] as Buffer), 1)).toBe("ü".repeat(100));
+  });
+
+  it("validates CallToolRequest", () => {
+    const request = (args: string) => new ProtoWriter().string(1, "harness_list").string(2, args).finish();
+    expect(decodeCallToolRequest(request('{"resource_type":"pipeline"}'))).toEqual({ name: "harness_list", args: { resource_type: "pipeline" } });
+    expect(decodeCallToolRequest(new ProtoWriter().string(1, "harness_describe").finish()).args).toEqual({});
+    expect(() => decodeCallToolRequest(request("[1]"))).toThrow(/JSON object/);
+    expect(() => decodeCallToolRequest(Buffer.alloc(0))).toThrow(/name is required/);
+  });
+
+  it("parses grpc-timeout values", () => {
+    expect(parseGrpcTimeout("30S")).toBe(30_000);
+    expect(parseGrpcTimeout("500m")).toBe(500);
+    expect(parseGrpcTimeout("2M")).toBe(120_000);
+    expect(parseGrpcTimeout("10x")).toBeUndefined();
+    expect(parseGrpcTimeout(undefined)).toBeUndefined();
+  });
+});
+
+describe("createGrpcServer", () => {
+  let server: Http2Server | undefined;
+
+  afterEach(async () => {
+    await new Promise((resolve) => server?.close(resolve) ?? resolve(undefined));
+    server = undefined;
+  });
+
+  async function start(): Promise<Http2Server> {
+    server = createGrpcServer(handlers, { maxMessageBytes: 1024 });
+    await new Promise<void>((resolve) => server!.listen(0, "127.0.0.1", resolve));
+    return server;
+  }
+
+  it("answers ListTools and CallTool", async () => {
+    const srv = await start();
+    const auth = { authorization: "Bearer secret" };
+
+    const listed = await call(srv, "/harness.mcp.v1.ToolDispatch/ListTools", Buffer.alloc(0), auth);
+    expect(listed.status).toBe("0");
+    const tool = decodeProto(decodeProto(listed.body).get(1)![0] as Buffer);
+    expect(protoString(tool, 1)).toBe("harness_list");
+    expect(JSON.parse(protoString(tool, 3))).toEqual({ type: "object" });
+
+    const request = new ProtoWriter().string(1, "harness_list").string(2, '{"resource_type":"pipeline"}').finish();
+    const called = await call(srv, "/harness.mcp.v1.ToolDispatch/CallTool", request, auth);
+    expect(called.status).toBe("0");
+    const result = JSON.parse(protoString(decodeProto(called.body), 2));
+    expect(JSON.parse(result.content[0].text)).toEqual({ name: "harness_list", args: { resource_type: "pipeline" } });
+  });
+
+  it("maps failures to gRPC status codes", async () => {
+    const srv = await start();
+    expect((await call(srv, "/harness.mcp.v1.ToolDispatch/ListTools", Buffer.alloc(0))).status).toBe(String(GrpcStatus.UNAUTHENTICATED));
+
+    const unknown = await call(srv, "/harness.mcp.v1.ToolDispatch/StreamTools", Buffer.alloc(0), { authorization: "Bearer secret" });
+    expect(unknown).toMatchObject({ status: String(GrpcStatus.UNIMPLEMENTED), message: "Unknown method /harness.mcp.v1.ToolDispatch/StreamTools" });
+
+    const tooLarge = new ProtoWriter().string(1, "harness_list").string(2, JSON.stringify({ pad: "x".repeat(2048) })).finish();
+    expect((await call(srv, "/harness.mcp.v1.ToolDispatch/CallTool", tooLarge, { authorization: "Bearer secret" })).status).toBe(String(GrpcStatus.RESOURCE_EXHAUSTED));
+  });
+
+  it("serves multi-user mode with credentials from call metadata", async () => {
+    const config = { HARNESS_MCP_MODE: "multi-user", HARNESS_BASE_URL: "https://app.harness.io", HARNESS_ACCOUNT_ID: "" } as Config;
+    expect(() => assertTransportSupportsMode("grpc", config.HARNESS_MCP_MODE)).not.toThrow();
+    // Mirrors startGrpc: each call builds its config from the metadata
+    const srv = createGrpcServer({
+      ...handlers,
+      callTool: async (_name, _args, metadata) => {
+        let sessionConfig: Config;
+        try {
+          sessionConfig = mergeConfigWithSessionHeaders(config, metadata);
+        } catch (err) {
+          if (err instanceof MissingSessionCredentialsError) throw new GrpcError(GrpcStatus.UNAUTHENTICATED, err.message);
+          throw err;
+        }
+        return { content: [{ type: "text", text: sessionConfig.HARNESS_ACCOUNT_ID }] };
+      },
+    }, { maxMessageBytes: 1024 });
+    server = srv;
+    await new Promise<void>((resolve) => srv.listen(0, "127.0.0.1", resolve));
+    const request = new ProtoWriter().string(1, "harness_list").string(2, "{}").finish();
+
+    const called = await call(srv, "/harness.mcp.v1.ToolDispatch/CallTool", request, {
+      authorization: "Bearer secret",
+      "x-harness-api-key": "pat.acct-1.token.secret",
+      "x-harness-account-id": "acct-1",
+    });
+    expect(called.status).toBe("0");
+    expect(JSON.parse(protoString(decodeProto(called.body), 2)).content[0].text).toBe("acct-1");
+
+    const anonymous = await call(srv, "/harness.mcp.v1.ToolDispatch/CallTool", request, { authorization: "Bearer secret" });
+    expect(anonymous.status).toBe(String(GrpcStatus.UNAUTHENTICATED));
+  });
+
+  it("answers health checks without authentication", async () => {
+    const srv = await start();
+    const health = await call(srv, "/grpc.health.v1.Health/Check", Buffer.alloc(0));
+    expect(health.status).toBe("0");
+    expect(decodeProto(health.body).get(1)).toEqual([1]);
+  });
+});
@@ -0,0 +1,91 @@
+import { describe, expect, it } from "vitest";
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { Client } from "@modelcontextprotocol/sdk/client/index.js";
+import { InMemoryTransport } from "@modelcontextprotocol/sdk/inMemory.js";
+import { LoggingMessageNotificationSchema, ResourceUpdatedNotificationSchema } from "@modelcontextprotocol/sdk/types.js";
+import type { Config } from "../../src/config.js";
+import { RECENT_EXECUTIONS_URI, attachHarnessEvents, attachedEventListenerCount, parseHarnessEvent, publishHarnessEvent, type HarnessEvent } from "../../src/utils/harness-events.js";
+
+const message = (subject: string, body: unknown) => ({ subject, data: Buffer.from(JSON.stringify(body)) });
+
+describe("parseHarnessEvent", () => {
+  it("normalizes notification webhook bodies", () => {
+    const event = parseHarnessEvent(message("harness.events.abc", {
+      eventType: "PIPELINE_END",
+      eventData: { accountIdentifier: "abc", orgIdentifier: "default", projectIdentifier: "web", pipelineIdentifier: "deploy", planExecutionId: "Xy12", nodeStatus: "success" },
+    }));
+    expect(event).toEqual({
+      type: "execution_finished",
+      sourceType: "PIPELINE_END",
+      subject: "harness.events.abc",
+      accountId: "abc",
+      orgId: "default",
+      projectId: "web",
+      pipelineId: "deploy",
+      executionId: "Xy12",
+      status: "success",
+    });
+  });
+
+  it("takes the event type from the subject when the payload has none", () => {
+    expect(parseHarnessEvent(message("harness.events.abc.approval_required", { accountId: "abc" }))).toMatchObject({ type: "approval_required", accountId: "abc" });
+    expect(parseHarnessEvent(message("harness.events.abc.pipeline_start", {}))!.type).toBe("other");
+    expect(parseHarnessEvent({ subject: "x", data: Buffer.from("not json") })).toBeUndefined();
+  });
+});
+
+describe("attachHarnessEvents", () => {
+  async function connected(config: Partial<Config>) {
+    const server = new McpServer({ name: "events-test", version: "0.0.0" });
+    server.registerResource("recent", RECENT_EXECUTIONS_URI, { mimeType: "application/json" }, async (uri) => ({ contents: [{ uri: uri.href, text: "[]" }] }));
+    attachHarnessEvents(server, { HARNESS_ACCOUNT_ID: "abc", HARNESS_MCP_MODE: "single-user", ...config } as Config);
+    const [clientTransport, serverTransport] = InMemoryTransport.createLinkedPair();
+    await server.connect(serverTransport);
+    const client = new Client({ name: "events-client", version: "0.0.0" });
+    const notices: unknown[] = [];
+    const updates: string[] = [];
+    client.setNotificationHandler(LoggingMessageNotificationSchema, (n) => { notices.push(n.params.data); });
+    client.setNotificationHandler(ResourceUpdatedNotificationSchema, (n) => { updates.push(n.params.uri); });
+    await client.connect(clientTransport);
+    return { server, client, notices, updates };
+  }
+
+  const finished: HarnessEvent = { type: "execution_finished", sourceType: "PIPELINE_END", subject: "s", accountId: "abc", executionId: "e1" };
+
+  it("forwards the account's events and resource updates to subscribed clients", async () => {
+    const { client, notices, updates } = await connected({});
+    expect(client.getServerCapabilities()?.resources?.subscribe).toBe(true);
+    await client.subscribeResource({ uri: RECENT_EXECUTIONS_URI });
+
+    publishHarnessEvent(finished);
+    publishHarnessEvent({ ...finished, accountId: "other", executionId: "e2" });
+    await expect.poll(() => updates).toEqual([RECENT_EXECUTIONS_URI]);
+    expect(notices).toEqual([finished]);
+
+    await client.close();
+    publishHarnessEvent(finished);
+    expect(notices).toHaveLength(1);
+  });
+
+  it("drops events without an account in multi-user mode", async () => {
+    const { client, notices } = await connected({ HARNESS_MCP_MODE: "multi-user" });
+    publishHarnessEvent({ ...finished, accountId: undefined });
+    publishHarnessEvent({ ...finished, executionId: "e3" });
+    await expect.poll(() => notices.length).toBe(1);
+    expect(notices[0]).toMatchObject({ executionId: "e3" });
+    await client.close();
+  });
+
+  it("does not accumulate listeners across short-lived servers", async () => {
+    const before = attachedEventListenerCount();
+    for (let i = 0; i < 3; i++) {
+      const { server, client } = await connected({});
+      await client.close();
+      await server.close();
+    }
+    const unconnected = new McpServer({ name: "events-test", version: "0.0.0" });
+    attachHarnessEvents(unconnected, { HARNESS_ACCOUNT_ID: "abc", HARNESS_MCP_MODE: "single-user" } as Config);
+    await unconnected.close();
+    expect(attachedEventListenerCount()).toBe(before);
+  });
+});
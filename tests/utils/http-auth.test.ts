@@ -1,8 +1,11 @@
 import express from "express";
+import jwt from "jsonwebtoken";
 import { describe, expect, it, vi } from "vitest";
 import { request as httpRequest } from "node:http";
 import type { AddressInfo } from "node:net";
 import {
+  adminRoutesEnabled,
+  createAdminAuthMiddleware,
   createHttpAuthMiddleware,
   isAuthorizedHttpRequest,
   validateHttpAuthForBindHost,
@@ -107,6 +110,36 @@ describe("HTTP MCP auth", () => {
     });
   });
 
+  it("accepts a valid JWT and reports why an invalid one was rejected", async () => {
+    const app = express();
+    app.use(createHttpAuthMiddleware(undefined, {
+      key: "jwt-secret",
+      algorithms: ["HS256"],
+      audience: ["harness-mcp"],
+      leewaySeconds: 30,
+    }));
+    app.get("/mcp", (_req, res) => res.json({ sub: res.locals.jwtClaims?.sub }));
+
+    const sign = (claims: Record<string, unknown>): string =>
+      jwt.sign(claims, "jwt-secret", { algorithm: "HS256", expiresIn: 300 });
+
+    await withListeningApp(app, async (baseUrl) => {
+      const missing = await getWithAuth(baseUrl, "/mcp");
+      expect(missing.status).toBe(401);
+      expect(missing.body).toMatchObject({ error: { message: "Unauthorized: missing bearer token" } });
+
+      const accepted = await getWithAuth(baseUrl, "/mcp", `Bearer ${sign({ sub: "svc-a", aud: "harness-mcp" })}`);
+      expect(accepted.status).toBe(200);
+      expect(accepted.body).toEqual({ sub: "svc-a" });
+
+      const wrongAudience = await getWithAuth(baseUrl, "/mcp", `Bearer ${sign({ sub: "svc-a", aud: "billing" })}`);
+      expect(wrongAudience.status).toBe(401);
+      expect(wrongAudience.body).toMatchObject({
+        error: { message: 'Unauthorized: JWT audience "billing" does not include any of: harness-mcp' },
+      });
+    });
+  });
+
   it("fails closed for non-loopback binds without auth unless explicitly allowed", () => {
     expect(() =>
       validateHttpAuthForBindHost("0.0.0.0", {
@@ -134,6 +167,15 @@ describe("HTTP MCP auth", () => {
         HARNESS_API_KEY: "pat.test.abc.xyz",
       }),
     ).not.toThrow();
+
+    expect(() =>
+      validateHttpAuthForBindHost("0.0.0.0", {
+        HARNESS_MCP_AUTH_TOKEN: undefined,
+        HARNESS_MCP_ALLOW_UNAUTHENTICATED_HTTP: false,
+        HARNESS_MCP_MODE: "multi-user",
+        HARNESS_MCP_TLS_CA: "/etc/harness/ca.pem",
+      }),
+    ).not.toThrow();
   });
 
   it("warns for loopback single-user with no auth token (reverse-proxy risk)", () => {
@@ -196,3 +238,46 @@ describe("HTTP MCP auth", () => {
     warnSpy.mockRestore();
   });
 });
+
+describe("admin routes", () => {
+  const base = {
+    HARNESS_MCP_MODE: "single-user",
+    HARNESS_MCP_AUTH_TOKEN: undefined,
+    HARNESS_MCP_ADMIN_TOKEN: undefined,
+    HARNESS_MCP_JWT_SECRET: undefined,
+    HARNESS_MCP_JWT_PUBLIC_KEY: undefined,
+  } as unknown as Parameters<typeof adminRoutesEnabled>[0];
+
+  it("are only served when a credential gates them", () => {
+    expect(adminRoutesEnabled(base)).toBe(false);
+    expect(adminRoutesEnabled({ ...base, HARNESS_MCP_AUTH_TOKEN: "secret-token" })).toBe(true);
+    expect(adminRoutesEnabled({ ...base, HARNESS_MCP_JWT_SECRET: "jwt-secret" })).toBe(true);
+    expect(adminRoutesEnabled({ ...base, HARNESS_MCP_MODE: "multi-user", HARNESS_MCP_AUTH_TOKEN: "secret-token" })).toBe(false);
+    expect(adminRoutesEnabled({ ...base, HARNESS_MCP_MODE: "multi-user", HARNESS_MCP_ADMIN_TOKEN: "admin-token-0123456789" })).toBe(true);
+  });
+
+  it("require the admin token when one is configured", async () => {
+    const app = express();
+    app.use(createAdminAuthMiddleware("admin-token-0123456789"));
+    app.get("/admin/debug", (_req, res) => res.json({ ok: true }));
+
+    await withListeningApp(app, async (baseUrl) => {
+      const rejected = await getWithAuth(baseUrl, "/admin/debug", "Bearer admin-token-0123456789");
+      expect(rejected.status).toBe(401);
+
+      const accepted = await new Promise<number>((resolve, reject) => {
+        const url = new URL("/admin/debug", baseUrl);
+        const req = httpRequest(
+          { hostname: url.hostname, port: url.port, path: url.pathname, headers: { "X-Harness-Admin-Token": "admin-token-0123456789" } },
+          (res) => {
+            res.resume();
+            res.on("end", () => resolve(res.statusCode ?? 0));
+          },
+        );
+        req.on("error", reject);
+        req.end();
+      });
+      expect(accepted).toBe(200);
+    });
+  });
+});
@@ -0,0 +1,127 @@
+import express from "express";
+import { describe, expect, it } from "vitest";
+import { request as httpRequest } from "node:http";
+import type { AddressInfo } from "node:net";
+import { createCorsMiddleware, normalizeHttpOrigin, resolveAllowedOrigins } from "../../src/utils/http-cors.js";
+
+async function withListeningApp(app: express.Express, fn: (baseUrl: string) => Promise<void>): Promise<void> {
+  const server = app.listen(0, "127.0.0.1");
+  await new Promise<void>((resolve, reject) => {
+    server.once("listening", resolve);
+    server.once("error", reject);
+  });
+
+  try {
+    const address = server.address() as AddressInfo;
+    await fn(`http://127.0.0.1:${address.port}`);
+  } finally {
+    await new Promise<void>((resolve, reject) => {
+      server.close((err) => err ? reject(err) : resolve());
+    });
+  }
+}
+
+async function requestWithOrigin(
+  baseUrl: string,
+  method: string,
+  origin?: string,
+): Promise<{ status: number; headers: Record<string, unknown>; body: unknown }> {
+  const url = new URL("/mcp", baseUrl);
+  return new Promise((resolve, reject) => {
+    const req = httpRequest(
+      {
+        hostname: url.hostname,
+        port: url.port,
+        path: url.pathname,
+        method,
+        headers: origin ? { Origin: origin } : {},
+      },
+      (res) => {
+        let rawBody = "";
+        res.setEncoding("utf8");
+        res.on("data", (chunk) => { rawBody += chunk; });
+        res.on("end", () => {
+          resolve({
+            status: res.statusCode ?? 0,
+            headers: res.headers,
+            body: rawBody ? JSON.parse(rawBody) : undefined,
+          });
+        });
+      },
+    );
+    req.on("error", reject);
+    req.end();
+  });
+}
+
+function makeApp(): express.Express {
+  const app = express();
+  app.use(createCorsMiddleware(resolveAllowedOrigins("127.0.0.1", 3000, "https://app.example.com")));
+  app.get("/mcp", (_req, res) => {
+    res.json({ ok: true });
+  });
+  return app;
+}
+
+describe("normalizeHttpOrigin", () => {
+  it("accepts http(s) origins and strips default ports and trailing slashes", () => {
+    expect(normalizeHttpOrigin(" https://app.example.com/ ")).toBe("https://app.example.com");
+    expect(normalizeHttpOrigin("https://app.example.com:443")).toBe("https://app.example.com");
+    expect(normalizeHttpOrigin("http://localhost:5173")).toBe("http://localhost:5173");
+  });
+
+  it("rejects bare hosts, wildcards, and URLs with paths", () => {
+    expect(normalizeHttpOrigin("app.example.com")).toBeUndefined();
+    expect(normalizeHttpOrigin("*")).toBeUndefined();
+    expect(normalizeHttpOrigin("https://app.example.com/mcp")).toBeUndefined();
+  });
+});
+
+describe("resolveAllowedOrigins", () => {
+  it("allows every loopback spelling for localhost binds plus configured origins", () => {
+    expect(resolveAllowedOrigins("127.0.0.1", 3000, "https://app.example.com, http://localhost:3000")).toEqual([
+      "http://127.0.0.1:3000",
+      "http://localhost:3000",
+      "http://[::1]:3000",
+      "https://app.example.com",
+    ]);
+  });
+
+  it("uses the bind host for non-loopback binds", () => {
+    expect(resolveAllowedOrigins("10.0.0.5", 8080)).toEqual(["http://10.0.0.5:8080"]);
+  });
+});
+
+describe("createCorsMiddleware", () => {
+  it("passes requests without an Origin header untouched", async () => {
+    await withListeningApp(makeApp(), async (baseUrl) => {
+      const res = await requestWithOrigin(baseUrl, "GET");
+      expect(res.status).toBe(200);
+      expect(res.headers["access-control-allow-origin"]).toBeUndefined();
+    });
+  });
+
+  it("echoes an allowed origin and answers preflight with 204", async () => {
+    await withListeningApp(makeApp(), async (baseUrl) => {
+      const res = await requestWithOrigin(baseUrl, "GET", "https://app.example.com");
+      expect(res.status).toBe(200);
+      expect(res.headers["access-control-allow-origin"]).toBe("https://app.example.com");
+      expect(res.headers.vary).toBe("Origin");
+
+      const preflight = await requestWithOrigin(baseUrl, "OPTIONS", "http://localhost:3000");
+      expect(preflight.status).toBe(204);
+      expect(preflight.headers["access-control-allow-headers"]).toContain("mcp-session-id");
+    });
+  });
+
+  it("rejects other origins with 403", async () => {
+    await withListeningApp(makeApp(), async (baseUrl) => {
+      const res = await requestWithOrigin(baseUrl, "GET", "https://evil.example.com");
+      expect(res.status).toBe(403);
+      expect(res.body).toMatchObject({ error: { message: "Origin not allowed: https://evil.example.com" } });
+
+      const preflight = await requestWithOrigin(baseUrl, "OPTIONS", "https://evil.example.com");
+      expect(preflight.status).toBe(403);
+    });
+  });
+});
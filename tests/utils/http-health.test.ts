@@ -18,4 +18,17 @@ describe("HTTP health response", () => {
       search,
     });
   });
+
+  it("reports pooled API clients and accounts in multi-user mode", () => {
+    const search: SearchReadiness = { state: "disabled", configured: "none" };
+    const health = buildHttpHealthResponse(search, 4, { clients: 3, accounts: 2 });
+
+    expect(health.body).toEqual({
+      status: "ok",
+      sessions: 4,
+      accounts: 2,
+      api_clients: 3,
+      search,
+    });
+  });
 });
@@ -0,0 +1,81 @@
+import { describe, expect, it } from "vitest";
+import jwt from "jsonwebtoken";
+import {
+  loadJwtValidationOptions,
+  resolveJwtAlgorithms,
+  validateHttpJwtConfig,
+  verifyJwt,
+  type JwtValidationOptions,
+} from "../../src/utils/http-jwt.js";
+
+const NOW = 1_750_000_000;
+
+const options: JwtValidationOptions = {
+  key: "jwt-secret",
+  algorithms: ["HS256"],
+  issuer: "https://idp.internal",
+  audience: ["harness-mcp"],
+  leewaySeconds: 30,
+};
+
+function sign(claims: Record<string, unknown>, secret = "jwt-secret", algorithm: jwt.Algorithm = "HS256"): string {
+  return jwt.sign({ iat: NOW, exp: NOW + 300, iss: "https://idp.internal", aud: "harness-mcp", sub: "svc-a", ...claims }, secret, { algorithm });
+}
+
+describe("verifyJwt", () => {
+  it("accepts a valid token and returns its claims", () => {
+    const result = verifyJwt(sign({}), options, NOW);
+    expect(result).toMatchObject({ ok: true, claims: { sub: "svc-a" } });
+  });
+
+  it("tolerates clock skew within the leeway", () => {
+    expect(verifyJwt(sign({ exp: NOW - 10 }), options, NOW).ok).toBe(true);
+    expect(verifyJwt(sign({ nbf: NOW + 10 }), options, NOW).ok).toBe(true);
+  });
+
+  it("names each failure mode", () => {
+    const reason = (token: string, opts = options): string | undefined => {
+      const result = verifyJwt(token, opts, NOW);
+      return result.ok ? undefined : result.reason;
+    };
+    expect(reason("not-a-jwt")).toBe("JWT is malformed");
+    expect(reason(sign({ exp: NOW - 60 }))).toBe(`JWT expired at ${new Date((NOW - 60) * 1000).toISOString()} (leeway 30s)`);
+    expect(reason(sign({ nbf: NOW + 120 }))).toBe(`JWT is not valid before ${new Date((NOW + 120) * 1000).toISOString()} (leeway 30s)`);
+    expect(reason(sign({ iss: "https://other" }))).toBe('JWT issuer "https://other" does not match expected "https://idp.internal"');
+    expect(reason(sign({ aud: ["billing", "ci"] }))).toBe('JWT audience ["billing","ci"] does not include any of: harness-mcp');
+    expect(reason(sign({}, "wrong-secret"))).toBe("JWT signature is invalid");
+    expect(reason(sign({}, "jwt-secret", "HS512"))).toBe("JWT algorithm HS512 is not allowed (allowed: HS256)");
+    expect(reason(jwt.sign({ sub: "svc-a", aud: "harness-mcp", iss: "https://idp.internal" }, "jwt-secret"))).toBe("JWT has no exp claim");
+  });
+});
+
+describe("JWT config", () => {
+  it("defaults the algorithm to the key family and rejects cross-family algorithms", () => {
+    expect(resolveJwtAlgorithms({ HARNESS_MCP_JWT_SECRET: "s" })).toEqual(["HS256"]);
+    expect(resolveJwtAlgorithms({ HARNESS_MCP_JWT_PUBLIC_KEY: "/k.pem" })).toEqual(["RS256"]);
+    expect(resolveJwtAlgorithms({ HARNESS_MCP_JWT_PUBLIC_KEY: "/k.pem", HARNESS_MCP_JWT_ALGORITHMS: "ES256, RS256" })).toEqual(["ES256", "RS256"]);
+    expect(() => resolveJwtAlgorithms({ HARNESS_MCP_JWT_PUBLIC_KEY: "/k.pem", HARNESS_MCP_JWT_ALGORITHMS: "HS256" })).toThrow(/Invalid HARNESS_MCP_JWT_ALGORITHMS/);
+  });
+
+  it("rejects inconsistent settings", () => {
+    expect(() => validateHttpJwtConfig({ HARNESS_MCP_JWT_SECRET: "s", HARNESS_MCP_JWT_PUBLIC_KEY: "/k.pem" })).toThrow(/not both/);
+    expect(() => validateHttpJwtConfig({ HARNESS_MCP_JWT_AUDIENCE: "harness-mcp" })).toThrow(/require HARNESS_MCP_JWT_SECRET/);
+    expect(() => validateHttpJwtConfig({})).not.toThrow();
+  });
+
+  it("builds validation options from config", () => {
+    expect(loadJwtValidationOptions({})).toBeUndefined();
+    expect(loadJwtValidationOptions({
+      HARNESS_MCP_JWT_SECRET: "s",
+      HARNESS_MCP_JWT_ISSUER: "https://idp.internal",
+      HARNESS_MCP_JWT_AUDIENCE: "harness-mcp, harness-mcp-staging",
+      HARNESS_MCP_JWT_LEEWAY_SECONDS: 5,
+    })).toEqual({
+      key: "s",
+      algorithms: ["HS256"],
+      issuer: "https://idp.internal",
+      audience: ["harness-mcp", "harness-mcp-staging"],
+      leewaySeconds: 5,
+    });
+  });
+});
@@ -1,5 +1,17 @@
-import { describe, expect, it } from "vitest";
-import { beginSessionRequest, endSessionRequest, isSessionExpired, type HttpSessionActivity } from "../../src/utils/http-sessions.js";
+import { EventEmitter } from "node:events";
+import { describe, expect, it, vi } from "vitest";
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { Client } from "@modelcontextprotocol/sdk/client/index.js";
+import { InMemoryTransport } from "@modelcontextprotocol/sdk/inMemory.js";
+import {
+  beginSessionRequest,
+  endSessionRequest,
+  isSessionExpired,
+  pingClient,
+  startSseKeepalive,
+  SSE_KEEPALIVE_COMMENT,
+  type HttpSessionActivity,
+} from "../../src/utils/http-sessions.js";
 
 describe("HTTP session activity tracking", () => {
   it("does not expire sessions with active requests even when the TTL has elapsed", () => {
@@ -59,3 +71,65 @@ describe("HTTP session activity tracking", () => {
     });
   });
 });
+
+function fakeResponse(contentType: string) {
+  const res = Object.assign(new EventEmitter(), {
+    headersSent: true,
+    writableEnded: false,
+    destroyed: false,
+    getHeader: (name: string) => (name === "content-type" ? contentType : undefined),
+    write: vi.fn(),
+  });
+  return res as any;
+}
+
+describe("SSE keepalive", () => {
+  it("writes comment heartbeats to SSE streams until the response closes", () => {
+    vi.useFakeTimers();
+    try {
+      const res = fakeResponse("text/event-stream");
+      startSseKeepalive(res, 1_000);
+      vi.advanceTimersByTime(2_500);
+      expect(res.write).toHaveBeenCalledTimes(2);
+      expect(res.write).toHaveBeenCalledWith(SSE_KEEPALIVE_COMMENT);
+
+      res.emit("close");
+      vi.advanceTimersByTime(5_000);
+      expect(res.write).toHaveBeenCalledTimes(2);
+    } finally {
+      vi.useRealTimers();
+    }
+  });
+
+  it("leaves JSON responses alone and is disabled by 0", () => {
+    vi.useFakeTimers();
+    try {
+      const json = fakeResponse("application/json");
+      startSseKeepalive(json, 1_000);
+      const disabled = fakeResponse("text/event-stream");
+      startSseKeepalive(disabled, 0);
+      vi.advanceTimersByTime(5_000);
+      expect(json.write).not.toHaveBeenCalled();
+      expect(disabled.write).not.toHaveBeenCalled();
+    } finally {
+      vi.useRealTimers();
+    }
+  });
+});
+
+describe("pingClient", () => {
+  it("resolves true when the client answers and false when it does not", async () => {
+    const live = new McpServer({ name: "s", version: "0.0.1" });
+    const [clientTransport, serverTransport] = InMemoryTransport.createLinkedPair();
+    await Promise.all([
+      new Client({ name: "c", version: "0.0.1" }).connect(clientTransport),
+      live.connect(serverTransport),
+    ]);
+    expect(await pingClient(live.server, 1_000)).toBe(true);
+
+    const dead = new McpServer({ name: "s", version: "0.0.1" });
+    const [, orphanTransport] = InMemoryTransport.createLinkedPair();
+    await dead.connect(orphanTransport);
+    expect(await pingClient(dead.server, 50)).toBe(false);
+  });
+});
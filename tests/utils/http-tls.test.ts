@@ -0,0 +1,63 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { loadHttpTlsOptions, validateHttpTlsConfig } from "../../src/utils/http-tls.js";
+
+describe("validateHttpTlsConfig", () => {
+  it("accepts no TLS, cert+key, and cert+key+ca", () => {
+    expect(() => validateHttpTlsConfig({})).not.toThrow();
+    expect(() => validateHttpTlsConfig({ HARNESS_MCP_TLS_CERT: "c.pem", HARNESS_MCP_TLS_KEY: "k.pem" })).not.toThrow();
+    expect(() => validateHttpTlsConfig({ HARNESS_MCP_TLS_CERT: "c.pem", HARNESS_MCP_TLS_KEY: "k.pem", HARNESS_MCP_TLS_CA: "ca.pem" })).not.toThrow();
+  });
+
+  it("requires cert and key together", () => {
+    expect(() => validateHttpTlsConfig({ HARNESS_MCP_TLS_CERT: "c.pem" })).toThrow(/must be set together/);
+    expect(() => validateHttpTlsConfig({ HARNESS_MCP_TLS_KEY: "k.pem" })).toThrow(/must be set together/);
+  });
+
+  it("rejects a CA without a server certificate", () => {
+    expect(() => validateHttpTlsConfig({ HARNESS_MCP_TLS_CA: "ca.pem" })).toThrow(/requires HARNESS_MCP_TLS_CERT/);
+  });
+});
+
+describe("loadHttpTlsOptions", () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = mkdtempSync(join(tmpdir(), "harness-tls-"));
+    writeFileSync(join(dir, "cert.pem"), "CERT");
+    writeFileSync(join(dir, "key.pem"), "KEY");
+    writeFileSync(join(dir, "ca.pem"), "CA");
+  });
+
+  afterEach(() => {
+    rmSync(dir, { recursive: true, force: true });
+  });
+
+  it("returns undefined when TLS is not configured", () => {
+    expect(loadHttpTlsOptions({})).toBeUndefined();
+  });
+
+  it("loads the server certificate without requesting client certificates", () => {
+    const options = loadHttpTlsOptions({ HARNESS_MCP_TLS_CERT: join(dir, "cert.pem"), HARNESS_MCP_TLS_KEY: join(dir, "key.pem") });
+    expect(options?.cert?.toString()).toBe("CERT");
+    expect(options?.key?.toString()).toBe("KEY");
+    expect(options?.requestCert).toBeUndefined();
+  });
+
+  it("requires client certificates signed by the CA when one is configured", () => {
+    const options = loadHttpTlsOptions({
+      HARNESS_MCP_TLS_CERT: join(dir, "cert.pem"),
+      HARNESS_MCP_TLS_KEY: join(dir, "key.pem"),
+      HARNESS_MCP_TLS_CA: join(dir, "ca.pem"),
+    });
+    expect(options?.ca?.toString()).toBe("CA");
+    expect(options).toMatchObject({ requestCert: true, rejectUnauthorized: true });
+  });
+
+  it("names the setting when a file cannot be read", () => {
+    expect(() => loadHttpTlsOptions({ HARNESS_MCP_TLS_CERT: join(dir, "missing.pem"), HARNESS_MCP_TLS_KEY: join(dir, "key.pem") }))
+      .toThrow(/Cannot read HARNESS_MCP_TLS_CERT file/);
+  });
+});
@@ -0,0 +1,86 @@
+import express from "express";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import type { AddressInfo } from "node:net";
+import { createHarnessWebhookHandler, HARNESS_WEBHOOK_PATH } from "../../src/utils/http-webhooks.js";
+
+const { wakeExecutionWaiters } = vi.hoisted(() => ({ wakeExecutionWaiters: vi.fn(() => 0) }));
+vi.mock("../../src/utils/poll-execution.js", () => ({ wakeExecutionWaiters }));
+
+const SECRET = "webhook-secret-0123456789";
+
+async function withWebhookApp(fn: (baseUrl: string) => Promise<void>): Promise<void> {
+  const app = express();
+  app.post(HARNESS_WEBHOOK_PATH, express.json(), createHarnessWebhookHandler(SECRET));
+  const server = app.listen(0, "127.0.0.1");
+  await new Promise<void>((resolve) => server.once("listening", resolve));
+  try {
+    await fn(`http://127.0.0.1:${(server.address() as AddressInfo).port}`);
+  } finally {
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+  }
+}
+
+const pipelineEnd = {
+  eventType: "PIPELINE_END",
+  eventData: { accountIdentifier: "acct", planExecutionId: "exec-42", nodeStatus: "SUCCESS" },
+};
+
+describe("createHarnessWebhookHandler", () => {
+  afterEach(() => {
+    wakeExecutionWaiters.mockClear();
+  });
+
+  it("rejects requests without the secret", async () => {
+    await withWebhookApp(async (baseUrl) => {
+      const missing = await fetch(`${baseUrl}${HARNESS_WEBHOOK_PATH}`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(pipelineEnd),
+      });
+      expect(missing.status).toBe(401);
+      const wrong = await fetch(`${baseUrl}${HARNESS_WEBHOOK_PATH}?token=nope`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(pipelineEnd),
+      });
+      expect(wrong.status).toBe(401);
+    });
+    expect(wakeExecutionWaiters).not.toHaveBeenCalled();
+  });
+
+  it("accepts the secret as a query parameter and wakes waits on the execution", async () => {
+    await withWebhookApp(async (baseUrl) => {
+      const res = await fetch(`${baseUrl}${HARNESS_WEBHOOK_PATH}?token=${SECRET}`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(pipelineEnd),
+      });
+      expect(res.status).toBe(202);
+      expect(await res.json()).toEqual({ received: true, type: "execution_finished", executionId: "exec-42" });
+    });
+    expect(wakeExecutionWaiters).toHaveBeenCalledWith("exec-42");
+  });
+
+  it("accepts the secret in the X-Harness-Webhook-Token header", async () => {
+    await withWebhookApp(async (baseUrl) => {
+      const res = await fetch(`${baseUrl}${HARNESS_WEBHOOK_PATH}`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json", "X-Harness-Webhook-Token": SECRET },
+        body: JSON.stringify({ type: "approval", executionId: "exec-7" }),
+      });
+      expect(res.status).toBe(202);
+      expect(await res.json()).toMatchObject({ type: "approval_required", executionId: "exec-7" });
+    });
+  });
+
+  it("rejects bodies that are not JSON objects", async () => {
+    await withWebhookApp(async (baseUrl) => {
+      const res = await fetch(`${baseUrl}${HARNESS_WEBHOOK_PATH}`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json", "X-Harness-Webhook-Token": SECRET },
+        body: JSON.stringify([1, 2]),
+      });
+      expect(res.status).toBe(400);
+    });
+  });
+});
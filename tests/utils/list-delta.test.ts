@@ -0,0 +1,49 @@
+import { describe, it, expect } from "vitest";
+import { ListDeltaTracker, itemIdentity, listQueryKey } from "../../src/utils/list-delta.js";
+
+const running = { planExecutionId: "e1", status: "Running" };
+const queued = { planExecutionId: "e2", status: "Queued" };
+
+describe("ListDeltaTracker", () => {
+  it("returns the full page and a cursor on the first call", () => {
+    const tracker = new ListDeltaTracker();
+    const result = tracker.diff("q", [running, queued], "");
+    expect(result.items).toEqual([running, queued]);
+    expect(result.delta).toMatchObject({ reset: true, added: 2, changed: 0, removed: [] });
+    expect(result.delta.cursor).toEqual(expect.any(String));
+  });
+
+  it("returns only added and changed items since the cursor", () => {
+    const tracker = new ListDeltaTracker();
+    const first = tracker.diff("q", [running, queued], "");
+    const succeeded = { planExecutionId: "e1", status: "Success" };
+    const fresh = { planExecutionId: "e3", status: "Running" };
+
+    const second = tracker.diff("q", [succeeded, fresh], first.delta.cursor);
+    expect(second.items).toEqual([succeeded, fresh]);
+    expect(second.delta).toMatchObject({ reset: false, added: 1, changed: 1, unchanged: 0, removed: ["e2"] });
+
+    const third = tracker.diff("q", [succeeded, fresh], second.delta.cursor);
+    expect(third.items).toEqual([]);
+    expect(third.delta).toMatchObject({ added: 0, changed: 0, unchanged: 2, removed: [] });
+  });
+
+  it("resets when the cursor belongs to a different query or is unknown", () => {
+    const tracker = new ListDeltaTracker();
+    const first = tracker.diff("q1", [running], "");
+    expect(tracker.diff("q2", [running], first.delta.cursor).delta.reset).toBe(true);
+    expect(tracker.diff("q1", [running], "not-a-cursor").delta.reset).toBe(true);
+  });
+});
+
+describe("itemIdentity / listQueryKey", () => {
+  it("scopes identities by org and project", () => {
+    expect(itemIdentity({ identifier: "deploy", orgIdentifier: "o", projectIdentifier: "p" })).toBe("o/p/deploy");
+    expect(itemIdentity({ number: 42 })).toBe("42");
+  });
+
+  it("ignores key order and non-query inputs", () => {
+    expect(listQueryKey({ resource_type: "execution", status: "Running", since_cursor: "a" }))
+      .toBe(listQueryKey({ status: "Running", resource_type: "execution", compact: false }));
+  });
+});
@@ -0,0 +1,58 @@
+import { describe, it, expect, vi, afterEach } from "vitest";
+import {
+  beginInFlight,
+  inFlightSnapshot,
+  resetLiveDiagnostics,
+  toggleDebugLogging,
+} from "../../src/utils/live-diagnostics.js";
+import { getLogLevel, setLogLevel } from "../../src/utils/logger.js";
+import { withToolTimeout } from "../../src/utils/tool-timeouts.js";
+
+afterEach(() => {
+  resetLiveDiagnostics();
+  setLogLevel("info");
+});
+
+describe("in-flight tracking", () => {
+  it("lists entries oldest first with elapsed time until they end", () => {
+    const endApi = beginInFlight("api", "GET /ng/api/connectors");
+    const endTool = beginInFlight("tool", "harness_get", { resource_type: "connector" });
+    const snapshot = inFlightSnapshot(Date.now() + 50);
+    expect(snapshot.log_level).toBe("info");
+    expect(snapshot.in_flight.map((e) => e.label)).toEqual(["GET /ng/api/connectors", "harness_get"]);
+    expect(snapshot.in_flight[1]).toMatchObject({ kind: "tool", resource_type: "connector" });
+    expect(snapshot.in_flight[0].elapsed_ms).toBeGreaterThanOrEqual(50);
+
+    endApi();
+    endTool();
+    expect(inFlightSnapshot().in_flight).toEqual([]);
+  });
+
+  it("tracks tool calls for the duration of withToolTimeout", async () => {
+    const extra = { signal: new AbortController().signal, sendNotification: vi.fn(), requestId: 7 } as any;
+    let during: ReturnType<typeof inFlightSnapshot> | undefined;
+    await withToolTimeout("harness_list", { resource_type: "pipeline" }, extra, async () => {
+      during = inFlightSnapshot();
+      return { content: [] };
+    });
+    expect(during?.in_flight).toEqual([
+      expect.objectContaining({ kind: "tool", label: "harness_list", resource_type: "pipeline", request_id: 7 }),
+    ]);
+    expect(inFlightSnapshot().in_flight).toEqual([]);
+  });
+});
+
+describe("toggleDebugLogging", () => {
+  it("switches to debug and back to the previous level", () => {
+    setLogLevel("warn");
+    expect(toggleDebugLogging()).toBe("debug");
+    expect(getLogLevel()).toBe("debug");
+    expect(toggleDebugLogging()).toBe("warn");
+    expect(getLogLevel()).toBe("warn");
+  });
+
+  it("leaves a configured debug level alone", () => {
+    setLogLevel("debug");
+    expect(toggleDebugLogging()).toBe("debug");
+  });
+});
@@ -2,6 +2,10 @@ import { describe, it, expect, vi, beforeEach, afterEach } from "vitest";
 import { resolveLogContent, resolveLogDownloadUrl } from "../../src/utils/log-resolver.js";
 import { gzipSync, deflateRawSync } from "node:zlib";
 import type { HarnessClient } from "../../src/client/harness-client.js";
+import { configureSpillBuffers } from "../../src/utils/spill-buffer.js";
+import { existsSync, mkdtempSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
 
 function makeClient(
   requestFn: (...args: unknown[]) => unknown,
@@ -513,6 +517,27 @@ describe("resolveLogContent", () => {
     ).rejects.toThrow(/too large/);
   });
 
+  it("saves an oversized log archive to HARNESS_OUTPUT_DIR instead of buffering it", async () => {
+    const dir = mkdtempSync(join(tmpdir(), "log-spill-"));
+    configureSpillBuffers({ dir, thresholdBytes: 512 });
+    try {
+      const gzipped = gzipSync(Buffer.from("y".repeat(4096)), { level: 0 });
+      const streamFn = vi.fn().mockResolvedValue(new Response(gzipped, { status: 200 }));
+      const client = makeClient(
+        vi.fn().mockResolvedValue({ status: "success", link: "https://logs.example.com/big" }),
+        { requestStream: streamFn },
+      );
+
+      const err = await resolveLogContent(client, "prefix", { maxLogSizeBytes: 1024 }).catch((e: Error) => e);
+      expect(String(err)).toMatch(/too large.*saved to .*execution-log-.*\.gz/);
+      const path = String(err).match(/saved to (\S+)\.$/)![1]!;
+      expect(existsSync(path)).toBe(true);
+    } finally {
+      configureSpillBuffers({});
+      rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
   // ─── REGRESSION GUARD: blob hostname rewriting (breaks repeatedly) ──────────
   //
   // This logic has regressed multiple times. Every strategy change in
@@ -0,0 +1,41 @@
+import { describe, expect, it } from "vitest";
+import { compileLogPattern, searchLogFiles } from "../../src/utils/log-search.js";
+
+const files = [
+  { key: "build", text: ["a", "b", "ERROR one", "c", "error two", "d", "e", "f", "g", "Error three", "h"].join("\n") },
+  { key: "test", text: "all good" },
+];
+
+describe("searchLogFiles", () => {
+  it("returns grep-style hunks and merges overlapping context", () => {
+    const result = searchLogFiles(files, { pattern: compileLogPattern("error"), contextLines: 1, maxMatches: 50 });
+    expect(result).toMatchObject({ pattern: "error", files_searched: 2, lines_searched: 12, total_matches: 3, truncated: false });
+    expect(result.hunks).toEqual([
+      { log_key: "build", lines: ["2- b", "3: ERROR one", "4- c", "5: error two", "6- d"] },
+      { log_key: "build", lines: ["9- g", "10: Error three", "11- h"] },
+    ]);
+  });
+
+  it("caps returned matches but counts them all", () => {
+    const result = searchLogFiles(files, { pattern: compileLogPattern("error"), contextLines: 0, maxMatches: 1 });
+    expect(result.total_matches).toBe(3);
+    expect(result.truncated).toBe(true);
+    expect(result.hunks).toEqual([{ log_key: "build", lines: ["3: ERROR one"] }]);
+  });
+
+  it("honors case-sensitive patterns", () => {
+    const result = searchLogFiles(files, { pattern: compileLogPattern("ERROR", true), contextLines: 0, maxMatches: 50 });
+    expect(result.total_matches).toBe(1);
+  });
+
+  it("clips very long lines", () => {
+    const result = searchLogFiles([{ key: "", text: `x${"y".repeat(1000)}` }], { pattern: compileLogPattern("^x"), contextLines: 0, maxMatches: 5 });
+    expect(result.hunks[0]!.lines[0]!.length).toBeLessThan(520);
+  });
+});
+
+describe("compileLogPattern", () => {
+  it("rejects invalid regexes with a readable message", () => {
+    expect(() => compileLogPattern("([")).toThrow(/Invalid log_search pattern/);
+  });
+});
@@ -0,0 +1,79 @@
+import { describe, expect, it } from "vitest";
+import { cleanLogLine, fullLogPointer, summarizeLog } from "../../src/utils/log-summary.js";
+
+describe("cleanLogLine", () => {
+  it("strips ANSI sequences and leading timestamps", () => {
+    expect(cleanLogLine("[2026-03-09T17:01:23Z] info: \x1b[1;31mBUILD FAILURE\x1b[0m")).toBe("BUILD FAILURE");
+    expect(cleanLogLine("2026-03-09 17:01:23,123 Starting build")).toBe("Starting build");
+    expect(cleanLogLine("17:01:23 step done")).toBe("step done");
+    expect(cleanLogLine("\x1b]0;title\x07plain")).toBe("plain");
+  });
+
+  it("keeps only the last carriage-return segment", () => {
+    expect(cleanLogLine("Downloading 10%\rDownloading 50%\rDownloading 100%\r")).toBe("Downloading 100%");
+  });
+});
+
+describe("summarizeLog", () => {
+  it("collapses repeated lines and extracts error blocks with their stack traces", () => {
+    const log = [
+      "[2026-03-09T17:01:00Z] info: Starting",
+      ...Array.from({ length: 5 }, () => "[2026-03-09T17:01:01Z] info: Waiting for lock"),
+      "Compiling",
+      "Waiting for lock",
+      "Exception in thread \"main\" java.lang.IllegalStateException: boom",
+      "    at com.example.App.run(App.java:10)",
+      "    at com.example.App.main(App.java:3)",
+      "Caused by: java.io.IOException: disk full",
+      "Done",
+    ].join("\n");
+
+    const summary = summarizeLog(log);
+    expect(summary.condensed.split("\n")).toEqual([
+      "Starting",
+      "Waiting for lock [x5]",
+      "Compiling",
+      "Exception in thread \"main\" java.lang.IllegalStateException: boom",
+      "    at com.example.App.run(App.java:10)",
+      "    at com.example.App.main(App.java:3)",
+      "Caused by: java.io.IOException: disk full",
+      "Done",
+    ]);
+    expect(summary.error_blocks).toEqual([
+      {
+        line: 9,
+        text: [
+          "Exception in thread \"main\" java.lang.IllegalStateException: boom",
+          "    at com.example.App.run(App.java:10)",
+          "    at com.example.App.main(App.java:3)",
+          "Caused by: java.io.IOException: disk full",
+        ].join("\n"),
+      },
+    ]);
+    expect(summary.stats).toMatchObject({ total_lines: 13, condensed_lines: 8, duplicate_lines: 5 });
+  });
+
+  it("reports a repeated error once", () => {
+    const summary = summarizeLog("ERROR: connection refused\nretrying\nERROR: connection refused");
+    expect(summary.error_blocks).toHaveLength(1);
+  });
+
+  it("keeps the head and tail within the character budget", () => {
+    const log = Array.from({ length: 2000 }, (_, i) => `line ${i} ${"x".repeat(40)}`).join("\n");
+    const summary = summarizeLog(log, { maxChars: 2000 });
+    expect(summary.condensed.length).toBeLessThanOrEqual(2000);
+    expect(summary.condensed).toContain("line 0 ");
+    expect(summary.condensed).toContain("line 1999 ");
+    expect(summary.condensed).toMatch(/… \d+ lines omitted …/);
+    expect(summary.stats.summary_chars).toBeLessThan(summary.stats.original_chars / 10);
+  });
+});
+
+describe("fullLogPointer", () => {
+  it("names the harness_get call for the full log", () => {
+    expect(fullLogPointer("acct/pipeline/p/1/-exec")).toMatchObject({
+      tool: "harness_get",
+      arguments: { resource_type: "execution_log", params: { prefix: "acct/pipeline/p/1/-exec" } },
+    });
+  });
+});
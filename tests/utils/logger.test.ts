@@ -0,0 +1,65 @@
+import { describe, it, expect, vi, afterEach } from "vitest";
+import { mkdtempSync, readFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { createLogger, configureLogOutput, guardStdout } from "../../src/utils/logger.js";
+import { attachFrameDump } from "../../src/utils/frame-dump.js";
+
+afterEach(() => {
+  configureLogOutput({});
+  vi.restoreAllMocks();
+});
+
+describe("logger output", () => {
+  it("writes to stderr by default", () => {
+    const stderr = vi.spyOn(console, "error").mockImplementation(() => {});
+    createLogger("test").info("hello", { n: 1 });
+    expect(JSON.parse(stderr.mock.calls[0]![0] as string)).toMatchObject({ module: "test", msg: "hello", n: 1 });
+  });
+
+  it("appends to LOG_FILE instead of stderr when configured", () => {
+    const file = join(mkdtempSync(join(tmpdir(), "harness-log-")), "mcp.log");
+    const stderr = vi.spyOn(console, "error").mockImplementation(() => {});
+    configureLogOutput({ file });
+    createLogger("test").warn("to file");
+    expect(stderr).not.toHaveBeenCalled();
+    expect(JSON.parse(readFileSync(file, "utf8").trim())).toMatchObject({ level: "warn", msg: "to file" });
+  });
+
+  it("guardStdout reroutes console.log to stderr", () => {
+    const stdout = vi.spyOn(process.stdout, "write").mockImplementation(() => true);
+    const stderr = vi.spyOn(console, "error").mockImplementation(() => {});
+    const original = console.log;
+    try {
+      guardStdout();
+      console.log("stray %s", "output");
+      expect(stdout).not.toHaveBeenCalled();
+      expect(JSON.parse(stderr.mock.calls[0]![0] as string)).toMatchObject({ module: "stdout-guard", output: "stray output" });
+    } finally {
+      console.log = original;
+    }
+  });
+});
+
+describe("attachFrameDump", () => {
+  it("logs inbound and outbound frames with credentials redacted", async () => {
+    const stderr = vi.spyOn(console, "error").mockImplementation(() => {});
+    const received: unknown[] = [];
+    const transport = {
+      start: vi.fn(),
+      close: vi.fn(),
+      send: vi.fn().mockResolvedValue(undefined),
+      onmessage: (msg: unknown) => { received.push(msg); },
+    } as any;
+
+    attachFrameDump(transport);
+    transport.onmessage({ jsonrpc: "2.0", id: 1, method: "tools/call", params: { arguments: { api_key: "pat.acct.tok.secretvalue" } } });
+    await transport.send({ jsonrpc: "2.0", id: 1, result: { ok: true } });
+
+    expect(received).toHaveLength(1);
+    const lines = stderr.mock.calls.map((c) => JSON.parse(c[0] as string));
+    expect(lines[0]).toMatchObject({ module: "jsonrpc", direction: "in", id: 1, method: "tools/call" });
+    expect(lines[1]).toMatchObject({ direction: "out", id: 1, frame: { result: { ok: true } } });
+    expect(JSON.stringify(lines)).not.toContain("secretvalue");
+  });
+});
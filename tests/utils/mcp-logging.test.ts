@@ -0,0 +1,67 @@
+import { describe, it, expect } from "vitest";
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { Client } from "@modelcontextprotocol/sdk/client/index.js";
+import { InMemoryTransport } from "@modelcontextprotocol/sdk/inMemory.js";
+import { LoggingMessageNotificationSchema } from "@modelcontextprotocol/sdk/types.js";
+import { attachMcpLogging, shouldForwardLog } from "../../src/utils/mcp-logging.js";
+import { createLogger, setLogLevel } from "../../src/utils/logger.js";
+
+const log = createLogger("test-module");
+
+async function setup() {
+  const server = new McpServer({ name: "test-server", version: "0.0.1" }, { capabilities: { logging: {} } });
+  server.registerTool("noisy", { description: "logs at every level" }, async () => {
+    log.debug("debug line");
+    log.info("info line");
+    log.warn("Rate limited, retrying", { api_key: "pat.acct.tokn.secretvalue" });
+    return { content: [{ type: "text", text: "ok" }] };
+  });
+  const [clientTransport, serverTransport] = InMemoryTransport.createLinkedPair();
+  const client = new Client({ name: "test-client", version: "0.0.1" });
+  await Promise.all([client.connect(clientTransport), server.connect(serverTransport)]);
+  attachMcpLogging(server, serverTransport);
+
+  const messages: Array<{ level: string; logger?: string; data: unknown }> = [];
+  client.setNotificationHandler(LoggingMessageNotificationSchema, (n) => { messages.push(n.params); });
+  return { client, messages };
+}
+
+describe("shouldForwardLog", () => {
+  it("compares logger levels against MCP severities", () => {
+    expect(shouldForwardLog("warn", "warning")).toBe(true);
+    expect(shouldForwardLog("info", "warning")).toBe(false);
+    expect(shouldForwardLog("debug", "debug")).toBe(true);
+    expect(shouldForwardLog("error", "critical")).toBe(false);
+  });
+});
+
+describe("attachMcpLogging", () => {
+  it("forwards only warnings and errors until the client sets a level", async () => {
+    setLogLevel("error");
+    const { client, messages } = await setup();
+    await client.callTool({ name: "noisy", arguments: {} });
+    await new Promise((r) => setTimeout(r, 10));
+
+    expect(messages).toHaveLength(1);
+    expect(messages[0]).toMatchObject({ level: "warning", logger: "test-module", data: { msg: "Rate limited, retrying" } });
+    expect(JSON.stringify(messages)).not.toContain("secretvalue");
+  });
+
+  it("follows logging/setLevel, independent of LOG_LEVEL", async () => {
+    setLogLevel("error");
+    const { client, messages } = await setup();
+    await client.setLoggingLevel("debug");
+    await client.callTool({ name: "noisy", arguments: {} });
+    await new Promise((r) => setTimeout(r, 10));
+
+    expect(messages.map((m) => m.level)).toEqual(["debug", "info", "warning"]);
+    setLogLevel("info");
+  });
+
+  it("does not forward lines logged outside a request", async () => {
+    const { messages } = await setup();
+    log.error("background failure");
+    await new Promise((r) => setTimeout(r, 10));
+    expect(messages).toHaveLength(0);
+  });
+});
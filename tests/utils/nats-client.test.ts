@@ -0,0 +1,79 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+import { createServer, type Server, type Socket } from "node:net";
+import type { AddressInfo } from "node:net";
+import { NatsProtocolParser, NatsSubscriber, type NatsMessage } from "../../src/utils/nats-client.js";
+
+describe("NatsProtocolParser", () => {
+  it("parses INFO, PING, -ERR, and MSG payloads split across chunks", () => {
+    const seen: string[] = [];
+    const parser = new NatsProtocolParser({
+      info: (info) => seen.push(`info ${String(info.server_id)}`),
+      msg: (m) => seen.push(`msg ${m.subject} ${m.data.toString()}`),
+      ping: () => seen.push("ping"),
+      err: (e) => seen.push(`err ${e}`),
+    });
+    parser.push(Buffer.from('INFO {"server_id":"n1"}\r\nPING\r\nMSG harness.events.a 1 1'));
+    parser.push(Buffer.from("1\r\nhello\r\n"));
+    parser.push(Buffer.from("world\r\n+OK\r\nMSG x 1 reply.to 2\r\nhi\r\n-ERR 'Authorization Violation'\r\n"));
+    expect(seen).toEqual([
+      "info n1",
+      "ping",
+      "msg harness.events.a hello\r\nworld",
+      "msg x hi",
+      "err Authorization Violation",
+    ]);
+  });
+
+  it("throws on a MSG line with a malformed, negative, or oversized size", () => {
+    const handlers = { info: () => {}, msg: vi.fn(), ping: () => {}, err: () => {} };
+    for (const line of ["MSG x 1 abc\r\nhi\r\n", "MSG x 1 -5\r\nhi\r\n", "MSG x 1\r\n", `MSG x 1 ${2 * 1024 * 1024}\r\n`]) {
+      expect(() => new NatsProtocolParser(handlers).push(Buffer.from(line))).toThrow(/NATS/);
+    }
+    expect(handlers.msg).not.toHaveBeenCalled();
+  });
+
+  it("caps payloads at the server's max_payload", () => {
+    const parser = new NatsProtocolParser({ info: () => {}, msg: () => {}, ping: () => {}, err: () => {} });
+    parser.push(Buffer.from('INFO {"max_payload":4}\r\n'));
+    expect(() => parser.push(Buffer.from("MSG x 1 5\r\nhello\r\n"))).toThrow("exceeds max_payload 4");
+  });
+});
+
+describe("NatsSubscriber", () => {
+  let server: Server | undefined;
+  let subscriber: NatsSubscriber | undefined;
+
+  afterEach(async () => {
+    subscriber?.stop();
+    await new Promise((resolve) => server?.close(resolve) ?? resolve(undefined));
+  });
+
+  it("authenticates, subscribes, answers pings, and delivers messages", async () => {
+    const received: string[] = [];
+    let client: Socket | undefined;
+    server = createServer((socket) => {
+      client = socket;
+      socket.on("data", (chunk) => received.push(...chunk.toString().split("\r\n").filter(Boolean)));
+      socket.write('INFO {"server_id":"test","max_payload":1048576}\r\n');
+    });
+    await new Promise<void>((resolve) => server!.listen(0, "127.0.0.1", resolve));
+    const { port } = server.address() as AddressInfo;
+
+    const messages: NatsMessage[] = [];
+    subscriber = new NatsSubscriber({ url: `nats://s3cret@127.0.0.1:${port}`, subjects: ["harness.events.>", "audit.>"], onMessage: (m) => messages.push(m) });
+    subscriber.start();
+
+    await expect.poll(() => received.length).toBeGreaterThanOrEqual(4);
+    expect(JSON.parse(received[0]!.slice("CONNECT ".length))).toMatchObject({ auth_token: "s3cret", verbose: false });
+    expect(received.slice(1, 4)).toEqual(["SUB harness.events.> 1", "SUB audit.> 2", "PING"]);
+
+    client!.write("PING\r\nMSG harness.events.abc.pipeline_end 1 2\r\n{}\r\n");
+    await expect.poll(() => messages.length).toBe(1);
+    expect(messages[0]!.subject).toBe("harness.events.abc.pipeline_end");
+    await expect.poll(() => received.includes("PONG")).toBe(true);
+  });
+
+  it("rejects non-NATS URLs", () => {
+    expect(() => new NatsSubscriber({ url: "http://nats:4222", subjects: ["x"], onMessage: () => {} })).toThrow(/nats:\/\/ or tls:\/\//);
+  });
+});
@@ -0,0 +1,39 @@
+import { describe, it, expect, afterEach } from "vitest";
+import { mkdtempSync, readFileSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { sanitizeFileName, toCsv, writeOutputFile } from "../../src/utils/output-dir.js";
+
+describe("toCsv", () => {
+  it("writes a header and quotes fields that need it", () => {
+    const csv = toCsv(["name", "cost", "note"], [
+      { name: "EC2, compute", cost: 12.5, note: 'say "hi"' },
+      { name: "S3", cost: 3 },
+    ]);
+    expect(csv).toBe('name,cost,note\n"EC2, compute",12.5,"say ""hi"""\nS3,3,\n');
+  });
+});
+
+describe("sanitizeFileName", () => {
+  it("strips directories and unsafe characters", () => {
+    expect(sanitizeFileName("../../etc/passwd")).toBe("passwd");
+    expect(sanitizeFileName("cost report:2026.csv")).toBe("cost_report_2026.csv");
+    expect(sanitizeFileName("..")).toBe("output");
+  });
+});
+
+describe("writeOutputFile", () => {
+  let dir: string | undefined;
+  afterEach(() => {
+    if (dir) rmSync(dir, { recursive: true, force: true });
+  });
+
+  it("creates the directory and returns a file URI", async () => {
+    dir = mkdtempSync(join(tmpdir(), "harness-output-"));
+    const file = await writeOutputFile(join(dir, "nested"), "report.csv", "a,b\n");
+    expect(file.path).toBe(join(dir, "nested", "report.csv"));
+    expect(file.uri).toMatch(/^file:\/\//);
+    expect(file.bytes).toBe(4);
+    expect(readFileSync(file.path, "utf-8")).toBe("a,b\n");
+  });
+});
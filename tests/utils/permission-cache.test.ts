@@ -0,0 +1,68 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+import {
+  PERMISSION_CACHE_TTL_MS,
+  hasPermission,
+  invalidatePermissions,
+  permissionPrincipalKey,
+  resetPermissionCache,
+} from "../../src/utils/permission-cache.js";
+import { runWithPrincipal } from "../../src/utils/principal.js";
+
+function makeClient(permitted: boolean) {
+  const request = vi.fn().mockResolvedValue({ data: { accessControlList: [{ permitted }] } });
+  return { client: { account: "acc1", request } as unknown as HarnessClient, request };
+}
+
+const check = { resourceType: "PIPELINE", permission: "core_pipeline_execute", resourceIdentifier: "deploy", orgIdentifier: "default", projectIdentifier: "web" };
+
+describe("hasPermission", () => {
+  beforeEach(() => resetPermissionCache());
+
+  it("asks the ACL service once and serves repeats from the cache", async () => {
+    const { client, request } = makeClient(true);
+    expect(await hasPermission(client, check, undefined, 1000)).toBe(true);
+    expect(await hasPermission(client, check, undefined, 2000)).toBe(true);
+    expect(request).toHaveBeenCalledTimes(1);
+    expect(request.mock.calls[0][0]).toMatchObject({
+      method: "POST",
+      path: "/authz/api/acl",
+      body: {
+        permissions: [{
+          resourceScope: { accountIdentifier: "acc1", orgIdentifier: "default", projectIdentifier: "web" },
+          resourceType: "PIPELINE",
+          resourceIdentifier: "deploy",
+          permission: "core_pipeline_execute",
+        }],
+      },
+    });
+  });
+
+  it("caches denials and re-checks after the TTL", async () => {
+    const { client, request } = makeClient(false);
+    expect(await hasPermission(client, check, undefined, 1000)).toBe(false);
+    expect(await hasPermission(client, check, undefined, 1000 + PERMISSION_CACHE_TTL_MS - 1)).toBe(false);
+    expect(request).toHaveBeenCalledTimes(1);
+    await hasPermission(client, check, undefined, 1000 + PERMISSION_CACHE_TTL_MS);
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+
+  it("keeps decisions separate per principal", async () => {
+    const { client, request } = makeClient(true);
+    await runWithPrincipal({ id: "alice" }, () => hasPermission(client, check, undefined, 1000));
+    await runWithPrincipal({ id: "bob" }, () => hasPermission(client, check, undefined, 1000));
+    await runWithPrincipal({ id: "alice" }, () => hasPermission(client, check, undefined, 1000));
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+
+  it("drops only the invalidated principal's decisions", async () => {
+    const { client, request } = makeClient(true);
+    await runWithPrincipal({ id: "alice" }, () => hasPermission(client, check, undefined, 1000));
+    await runWithPrincipal({ id: "bob" }, () => hasPermission(client, check, undefined, 1000));
+    runWithPrincipal({ id: "alice" }, () => invalidatePermissions(permissionPrincipalKey("acc1")));
+
+    await runWithPrincipal({ id: "alice" }, () => hasPermission(client, check, undefined, 1000));
+    await runWithPrincipal({ id: "bob" }, () => hasPermission(client, check, undefined, 1000));
+    expect(request).toHaveBeenCalledTimes(3);
+  });
+});
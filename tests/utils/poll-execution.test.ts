@@ -7,6 +7,7 @@ import {
   TERMINAL_STATUSES,
   FAILURE_STATUSES,
   AbortError,
+  wakeExecutionWaiters,
 } from "../../src/utils/poll-execution.js";
 
 /**
@@ -295,4 +296,27 @@ describe("pollExecutionToTerminal", () => {
     expect(onPoll).toHaveBeenNthCalledWith(1, "Running", expect.any(Number), 1);
     expect(onPoll).toHaveBeenNthCalledWith(2, "Success", expect.any(Number), 2);
   });
+
+  it("re-checks immediately when woken for its execution", async () => {
+    const { registry } = makeRegistry([snapshot("Running"), snapshot("Success")]);
+
+    const promise = pollExecutionToTerminal(registry, fakeClient, {
+      executionId: "exec-9",
+      timeoutMs: 600_000,
+      initialIntervalMs: 60_000,
+      maxIntervalMs: 60_000,
+    });
+
+    await vi.advanceTimersByTimeAsync(0);
+    expect(wakeExecutionWaiters("other-exec")).toBe(0);
+    expect(wakeExecutionWaiters("exec-9")).toBe(1);
+    await vi.advanceTimersByTimeAsync(0);
+    expect(wakeExecutionWaiters("exec-9")).toBe(1);
+    const result = await promise;
+
+    expect(result.status).toBe("Success");
+    expect(result.poll_count).toBe(2);
+    expect(result.elapsed_ms).toBeLessThan(60_000);
+    expect(wakeExecutionWaiters("exec-9")).toBe(0);
+  });
 });
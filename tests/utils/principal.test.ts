@@ -0,0 +1,49 @@
+import { describe, expect, it } from "vitest";
+import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import type { JSONRPCMessage } from "@modelcontextprotocol/sdk/types.js";
+import { attachPrincipal, currentPrincipal, principalFromClaims, principalHeaders } from "../../src/utils/principal.js";
+
+describe("principalFromClaims", () => {
+  it("maps standard claims", () => {
+    expect(principalFromClaims({
+      sub: "user-42",
+      iss: "https://idp.internal",
+      email: "dev@example.com",
+      preferred_username: "dev",
+      principal_type: "USER",
+    })).toEqual({ id: "user-42", type: "USER", issuer: "https://idp.internal", email: "dev@example.com", name: "dev" });
+  });
+
+  it("requires a subject", () => {
+    expect(principalFromClaims({ iss: "https://idp.internal" })).toBeUndefined();
+    expect(principalFromClaims(undefined)).toBeUndefined();
+  });
+});
+
+describe("principalHeaders", () => {
+  it("is empty without a principal", () => {
+    expect(principalHeaders(undefined)).toEqual({});
+  });
+
+  it("strips control characters and percent-encodes non-ASCII values", () => {
+    expect(principalHeaders({ id: "svc\r\nX-Evil: 1", name: "Zoë" })).toEqual({
+      "X-Principal-Id": "svcX-Evil: 1",
+      "X-Principal-Name": "Zo%C3%AB",
+    });
+  });
+});
+
+describe("attachPrincipal", () => {
+  it("handles every inbound message on behalf of the principal", () => {
+    const seen: Array<string | undefined> = [];
+    const transport = {
+      onmessage: () => { seen.push(currentPrincipal()?.id); },
+    } as unknown as Transport;
+
+    attachPrincipal(transport, { id: "user-42" });
+    transport.onmessage?.({ jsonrpc: "2.0", id: 1, method: "ping" } as JSONRPCMessage);
+
+    expect(seen).toEqual(["user-42"]);
+    expect(currentPrincipal()).toBeUndefined();
+  });
+});
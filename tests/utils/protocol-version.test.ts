@@ -0,0 +1,90 @@
+import { describe, it, expect, vi } from "vitest";
+import { LATEST_PROTOCOL_VERSION } from "@modelcontextprotocol/sdk/types.js";
+import {
+  adaptMessageForProtocol,
+  attachProtocolAdapter,
+  negotiateProtocolVersion,
+  protocolFeatures,
+} from "../../src/utils/protocol-version.js";
+
+const toolsList = {
+  jsonrpc: "2.0" as const,
+  id: 2,
+  result: {
+    tools: [{
+      name: "harness_list",
+      title: "List Harness Resources",
+      inputSchema: { type: "object" },
+      outputSchema: { type: "object" },
+      annotations: { readOnlyHint: true },
+    }],
+  },
+};
+
+const toolCall = {
+  jsonrpc: "2.0" as const,
+  id: 3,
+  result: {
+    content: [
+      { type: "text", text: "{}" },
+      { type: "resource_link", uri: "file:///tmp/log.txt", name: "log.txt", description: "Step log" },
+    ],
+    structuredContent: { ok: true },
+  },
+};
+
+describe("negotiateProtocolVersion", () => {
+  it("echoes supported revisions and falls back to the latest", () => {
+    expect(negotiateProtocolVersion("2024-11-05")).toBe("2024-11-05");
+    expect(negotiateProtocolVersion("2025-03-26")).toBe("2025-03-26");
+    expect(negotiateProtocolVersion("2025-06-18")).toBe("2025-06-18");
+    expect(negotiateProtocolVersion("1999-01-01")).toBe(LATEST_PROTOCOL_VERSION);
+    expect(negotiateProtocolVersion(undefined)).toBe(LATEST_PROTOCOL_VERSION);
+  });
+});
+
+describe("adaptMessageForProtocol", () => {
+  it("passes messages through unchanged for 2025-06-18", () => {
+    const features = protocolFeatures("2025-06-18");
+    expect(adaptMessageForProtocol(toolsList, features)).toBe(toolsList);
+    expect(adaptMessageForProtocol(toolCall, features)).toBe(toolCall);
+  });
+
+  it("keeps annotations but drops structured output for 2025-03-26", () => {
+    const features = protocolFeatures("2025-03-26");
+    const tool = (adaptMessageForProtocol(toolsList, features) as any).result.tools[0];
+    expect(tool.annotations).toEqual({ readOnlyHint: true });
+    expect(tool.title).toBeUndefined();
+    expect(tool.outputSchema).toBeUndefined();
+
+    const result = (adaptMessageForProtocol(toolCall, features) as any).result;
+    expect(result.structuredContent).toBeUndefined();
+    expect(result.content[1]).toEqual({ type: "text", text: "Resource log.txt: file:///tmp/log.txt — Step log" });
+  });
+
+  it("drops annotations for 2024-11-05", () => {
+    const tool = (adaptMessageForProtocol(toolsList, protocolFeatures("2024-11-05")) as any).result.tools[0];
+    expect(tool.annotations).toBeUndefined();
+    expect(tool.name).toBe("harness_list");
+  });
+});
+
+describe("attachProtocolAdapter", () => {
+  it("records the version from initialize and adapts later responses", async () => {
+    const sent: unknown[] = [];
+    const transport = {
+      start: vi.fn(),
+      close: vi.fn(),
+      send: vi.fn(async (msg: unknown) => { sent.push(msg); }),
+      onmessage: vi.fn(),
+    } as any;
+
+    const negotiated = attachProtocolAdapter(transport);
+    expect(negotiated()).toBeUndefined();
+    transport.onmessage({ jsonrpc: "2.0", id: 1, method: "initialize", params: { protocolVersion: "2025-03-26", capabilities: {}, clientInfo: { name: "old", version: "1" } } });
+    await transport.send(toolCall);
+
+    expect(negotiated()).toBe("2025-03-26");
+    expect((sent[0] as any).result.structuredContent).toBeUndefined();
+  });
+});
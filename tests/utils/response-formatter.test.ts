@@ -54,6 +54,12 @@ describe("jsonResult", () => {
     expect(result.structuredContent).toBeUndefined();
   });
 
+  it("strips ANSI codes and control characters from text and structuredContent", () => {
+    const result = jsonResult({ log_content: "\x1b[31mFAILED\x1b[0m\r\nexit 1" });
+    expect(result.structuredContent).toEqual({ log_content: "FAILED\nexit 1" });
+    expect(result.content[0]).toEqual({ type: "text", text: JSON.stringify({ log_content: "FAILED\nexit 1" }) });
+  });
+
   it("does not set isError", () => {
     const result = jsonResult({ ok: true });
     expect(result.isError).toBeUndefined();
@@ -74,4 +80,15 @@ describe("errorResult", () => {
     const parsed = JSON.parse((result.content[0] as { type: "text"; text: string }).text);
     expect(parsed).toEqual({ error: "not found" });
   });
+
+  it("strips ANSI codes from the message", () => {
+    const result = errorResult("\x1b[1mStep failed\x1b[0m");
+    expect(JSON.parse((result.content[0] as { text: string }).text)).toEqual({ error: "Step failed" });
+  });
+
+  it("includes the Harness correlation ID when provided", () => {
+    const result = errorResult("not found", "corr-9");
+    const parsed = JSON.parse((result.content[0] as { type: "text"; text: string }).text);
+    expect(parsed).toEqual({ error: "not found", correlation_id: "corr-9" });
+  });
 });
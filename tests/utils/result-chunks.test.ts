@@ -0,0 +1,72 @@
+import { describe, it, expect, beforeEach, afterEach } from "vitest";
+import {
+  chunkedJsonResult,
+  configureResultChunking,
+  continueResult,
+  resetResultChunks,
+  RESULT_BUFFER_TTL_MS,
+} from "../../src/utils/result-chunks.js";
+import type { ToolResult } from "../../src/utils/response-formatter.js";
+
+const payload = { components: Array.from({ length: 40 }, (_, i) => ({ name: `pkg-${i}`, version: "1.0.0" })) };
+
+function chunkOf(result: ToolResult | string): Record<string, unknown> {
+  if (typeof result === "string") throw new Error(result);
+  return result.structuredContent!;
+}
+
+describe("result chunking", () => {
+  beforeEach(() => {
+    resetResultChunks();
+    configureResultChunking({ chunkChars: 200 });
+  });
+
+  afterEach(() => {
+    configureResultChunking({ chunkChars: 0 });
+    resetResultChunks();
+  });
+
+  it("returns small results unchanged", () => {
+    const result = chunkedJsonResult({ ok: true }, "acct");
+    expect(result.structuredContent).toEqual({ ok: true });
+  });
+
+  it("returns results unchanged when chunking is off", () => {
+    configureResultChunking({ chunkChars: 0 });
+    const result = chunkedJsonResult(payload, "acct");
+    expect(result.structuredContent).toEqual(payload);
+  });
+
+  it("delivers a large result in chunks that rebuild the original JSON", () => {
+    const first = chunkOf(chunkedJsonResult(payload, "acct"));
+    expect(first).toMatchObject({ chunked: true, chunk_index: 0, total_chars: JSON.stringify(payload).length });
+    expect((first.chunk as string).length).toBe(200);
+
+    let text = first.chunk as string;
+    let cursor = first.next_cursor as string | undefined;
+    let last = first;
+    while (cursor) {
+      last = chunkOf(continueResult(cursor, "acct"));
+      text += last.chunk as string;
+      cursor = last.next_cursor as string | undefined;
+    }
+    expect(last.chunk_index).toBe((first.total_chunks as number) - 1);
+    expect(JSON.parse(text)).toEqual(payload);
+  });
+
+  it("releases the buffer after the last chunk", () => {
+    const first = chunkOf(chunkedJsonResult({ data: "x".repeat(300) }, "acct"));
+    expect(first.total_chunks).toBe(2);
+    const cursor = first.next_cursor as string;
+    chunkOf(continueResult(cursor, "acct"));
+    expect(continueResult(cursor, "acct")).toMatch(/unknown or has expired/);
+  });
+
+  it("rejects cursors from another account, expired cursors, and malformed cursors", () => {
+    const now = 1_000_000;
+    const cursor = chunkOf(chunkedJsonResult(payload, "acct", now)).next_cursor as string;
+    expect(continueResult(cursor, "other")).toMatch(/unknown or has expired/);
+    expect(continueResult("not-a-cursor", "acct")).toMatch(/Invalid continue_result cursor/);
+    expect(continueResult(cursor, "acct", now + RESULT_BUFFER_TTL_MS)).toMatch(/has expired/);
+  });
+});
@@ -6,6 +6,7 @@ import {
   substituteInputs,
   substituteInputsIntoBaseYaml,
   fetchRuntimeInputTemplate,
+  parseStageIdentifiers,
   resolveRuntimeInputs,
   clearTemplateCache,
 } from "../../src/utils/runtime-input-resolver.js";
@@ -421,6 +422,32 @@ describe("fetchRuntimeInputTemplate", () => {
 
     expect(result).toBeNull();
   });
+
+  it("requests the template for selected stages only", async () => {
+    fetchSpy.mockResolvedValueOnce(
+      new Response(JSON.stringify({
+        status: "SUCCESS",
+        data: { inputSetTemplateYaml: SIMPLE_TEMPLATE_YAML },
+      }), { status: 200, headers: { "Content-Type": "application/json" } }),
+    );
+
+    const client = new HarnessClient(makeConfig());
+    await fetchRuntimeInputTemplate(client, {
+      pipelineId: "staged-pipeline",
+      stageIdentifiers: ["deploy_qa"],
+    });
+
+    const [, init] = fetchSpy.mock.calls[0]!;
+    expect(JSON.parse(String((init as RequestInit).body))).toEqual({ stageIdentifiers: ["deploy_qa"] });
+  });
+});
+
+describe("parseStageIdentifiers", () => {
+  it("accepts arrays and comma-separated strings", () => {
+    expect(parseStageIdentifiers(["build", " deploy "])).toEqual(["build", "deploy"]);
+    expect(parseStageIdentifiers("build,,deploy")).toEqual(["build", "deploy"]);
+    expect(parseStageIdentifiers(undefined)).toEqual([]);
+  });
 });
 
 describe("resolveRuntimeInputs", () => {
@@ -598,6 +625,50 @@ describe("fetchRuntimeInputTemplate — caching", () => {
     expect(fetchSpy).toHaveBeenCalledTimes(2);
   });
 
+  it("does not share cache entries across accounts", async () => {
+    fetchSpy
+      .mockResolvedValueOnce(
+        new Response(JSON.stringify({
+          status: "SUCCESS",
+          data: { inputSetTemplateYaml: SIMPLE_TEMPLATE_YAML },
+        }), { status: 200, headers: { "Content-Type": "application/json" } }),
+      )
+      .mockResolvedValueOnce(
+        new Response(JSON.stringify({
+          status: "SUCCESS",
+          data: { inputSetTemplateYaml: SAMPLE_TEMPLATE_YAML },
+        }), { status: 200, headers: { "Content-Type": "application/json" } }),
+      );
+
+    const opts = { pipelineId: "shared_id", orgId: "default", projectId: "proj" };
+    const first = await fetchRuntimeInputTemplate(new HarnessClient(makeConfig()), opts);
+    const second = await fetchRuntimeInputTemplate(new HarnessClient({ ...makeConfig(), HARNESS_ACCOUNT_ID: "otheraccount" }), opts);
+
+    expect(first).toBe(SIMPLE_TEMPLATE_YAML);
+    expect(second).toBe(SAMPLE_TEMPLATE_YAML);
+    expect(fetchSpy).toHaveBeenCalledTimes(2);
+  });
+
+  it("does not share cache entries across users of the same account", async () => {
+    fetchSpy
+      .mockResolvedValueOnce(
+        new Response(JSON.stringify({
+          status: "SUCCESS",
+          data: { inputSetTemplateYaml: SIMPLE_TEMPLATE_YAML },
+        }), { status: 200, headers: { "Content-Type": "application/json" } }),
+      )
+      .mockResolvedValueOnce(
+        new Response(JSON.stringify({ status: "ERROR", message: "Access denied" }), { status: 403, headers: { "Content-Type": "application/json" } }),
+      );
+
+    const opts = { pipelineId: "private_pipe", orgId: "default", projectId: "proj" };
+    await fetchRuntimeInputTemplate(new HarnessClient(makeConfig()), opts);
+    const otherUser = new HarnessClient({ ...makeConfig(), HARNESS_API_KEY: "pat.test.otherid.secret" });
+
+    await expect(fetchRuntimeInputTemplate(otherUser, opts)).rejects.toThrow();
+    expect(fetchSpy).toHaveBeenCalledTimes(2);
+  });
+
   it("caches null result for pipelines with no runtime inputs", async () => {
     fetchSpy.mockResolvedValue(
       new Response(JSON.stringify({
@@ -0,0 +1,85 @@
+import { describe, it, expect } from "vitest";
+import { describeRuntimeInputs, runtimeInputJsonSchema } from "../../src/utils/runtime-input-schema.js";
+import { pipelineInputSchemaExtract } from "../../src/registry/extractors.js";
+
+const TEMPLATE = `pipeline:
+  identifier: deploy
+  variables:
+    - name: env
+      type: String
+      value: <+input>.default(dev).allowedValues(dev,qa,prod)
+    - name: replicas
+      type: Number
+      value: <+input>
+  stages:
+    - stage:
+        identifier: build
+        spec:
+          execution:
+            steps:
+              - step:
+                  identifier: tag
+                  spec:
+                    tag: <+input>.regex(^v\\d+(\\.\\d+)*$)
+    - stage:
+        identifier: approve
+        spec:
+          approvers: <+input>.executionInput()
+          tag: <+input>
+`;
+
+describe("describeRuntimeInputs", () => {
+  it("describes each placeholder with its resolver key and modifiers", () => {
+    const fields = describeRuntimeInputs(TEMPLATE);
+    expect(fields).toEqual([
+      { key: "env", path: "pipeline.variables.0.value", type: "string", required: false, default: "dev", allowed_values: ["dev", "qa", "prod"] },
+      { key: "replicas", path: "pipeline.variables.1.value", type: "number", required: true },
+      { key: "tag", path: "pipeline.stages.0.stage.spec.execution.steps.0.step.spec.tag", type: "string", required: true, regex: "^v\\d+(\\.\\d+)*$" },
+      { key: "approvers", path: "pipeline.stages.1.stage.spec.approvers", type: "string", required: true, execution_input: true },
+      { key: "tag", path: "pipeline.stages.1.stage.spec.tag", type: "string", required: true },
+    ]);
+  });
+
+  it("returns no fields for an empty template", () => {
+    expect(describeRuntimeInputs(null)).toEqual([]);
+    expect(describeRuntimeInputs("  ")).toEqual([]);
+  });
+});
+
+describe("runtimeInputJsonSchema", () => {
+  it("merges repeated keys and lists required keys once", () => {
+    const schema = runtimeInputJsonSchema(describeRuntimeInputs(TEMPLATE));
+    expect(schema.required).toEqual(["replicas", "tag", "approvers"]);
+    const properties = schema.properties as Record<string, Record<string, unknown>>;
+    expect(properties.env).toEqual({
+      type: "string",
+      default: "dev",
+      enum: ["dev", "qa", "prod"],
+      "x-harness-paths": ["pipeline.variables.0.value"],
+    });
+    expect(properties.tag!["x-harness-paths"]).toHaveLength(2);
+    expect(properties.tag!.pattern).toBe("^v\\d+(\\.\\d+)*$");
+    expect(properties.approvers!["x-harness-execution-input"]).toBe(true);
+  });
+});
+
+describe("pipelineInputSchemaExtract", () => {
+  it("returns the template, fields, and schema", () => {
+    const result = pipelineInputSchemaExtract(
+      { status: "SUCCESS", data: { inputSetTemplateYaml: TEMPLATE, hasInputSets: true } },
+      { pipeline_id: "deploy" },
+    ) as Record<string, unknown>;
+    expect(result.pipelineIdentifier).toBe("deploy");
+    expect(result.inputSetTemplateYaml).toBe(TEMPLATE);
+    expect(result.hasInputSets).toBe(true);
+    expect(result.fields).toHaveLength(5);
+    expect(result._hint).toContain("Required: replicas, tag, approvers.");
+  });
+
+  it("reports a pipeline without runtime inputs", () => {
+    const result = pipelineInputSchemaExtract({ data: {} }) as Record<string, unknown>;
+    expect(result.fields).toEqual([]);
+    expect((result.jsonSchema as Record<string, unknown>).required).toEqual([]);
+    expect(result._hint).toContain("no runtime inputs");
+  });
+});
@@ -0,0 +1,38 @@
+import { describe, expect, it } from "vitest";
+import { sanitizeDeep, sanitizeText } from "../../src/utils/sanitize-text.js";
+
+describe("sanitizeText", () => {
+  it("strips ANSI colour, cursor, and OSC sequences", () => {
+    expect(sanitizeText("\x1b[1;32mPASS\x1b[0m \x1b[2Kdone")).toBe("PASS done");
+    expect(sanitizeText("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\")).toBe("link");
+  });
+
+  it("normalizes CRLF and keeps the last redraw of a progress line", () => {
+    expect(sanitizeText("a\r\nb")).toBe("a\nb");
+    expect(sanitizeText("10%\r50%\r100%\nnext")).toBe("100%\nnext");
+  });
+
+  it("drops control characters but keeps tabs and newlines", () => {
+    expect(sanitizeText("a\x00b\x07c\td\ne\x7f")).toBe("abc\td\ne");
+  });
+
+  it("drops replacement characters and lone surrogates but keeps valid pairs", () => {
+    expect(sanitizeText("bad�bytes \ud800x \udc00y 🚀")).toBe("badbytes x y 🚀");
+  });
+
+  it("returns clean strings unchanged", () => {
+    const text = "plain text with ünïcödé";
+    expect(sanitizeText(text)).toBe(text);
+  });
+});
+
+describe("sanitizeDeep", () => {
+  it("sanitizes nested strings and keys", () => {
+    expect(sanitizeDeep({ "k\x1b[0m": ["\x1b[31mred", 1, null, { deep: "ok\x00" }] })).toEqual({ k: ["red", 1, null, { deep: "ok" }] });
+  });
+
+  it("returns the same object when nothing changes", () => {
+    const value = { a: ["x", { b: 2 }] };
+    expect(sanitizeDeep(value)).toBe(value);
+  });
+});
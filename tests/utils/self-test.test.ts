@@ -0,0 +1,82 @@
+import { describe, expect, it, vi } from "vitest";
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+import { Registry } from "../../src/registry/index.js";
+import { registerAllTools } from "../../src/tools/index.js";
+import { HarnessApiError } from "../../src/utils/errors.js";
+import { loadCatalogConfig } from "../../src/utils/tool-catalog.js";
+import { renderSelfTestReport, runConnectivityChecks, runProtocolChecks, selfTestPassed } from "../../src/utils/self-test.js";
+
+describe("runProtocolChecks", () => {
+  it("passes against the registered tools", async () => {
+    const config = loadCatalogConfig({ HARNESS_TOOLSETS: "pipelines" });
+    const server = new McpServer({ name: "self-test-test", version: "0.0.0" });
+    registerAllTools(server, new Registry(config), {} as HarnessClient, config);
+
+    const checks = await runProtocolChecks((transport) => server.connect(transport));
+    expect(checks.map((c) => c.name)).toEqual(expect.arrayContaining(["initialize", "ping", "tools/list", "tools/call harness_describe", "tools/call unknown tool"]));
+    expect(checks.filter((c) => c.status !== "ok")).toEqual([]);
+    expect(checks.find((c) => c.name === "tools/list")!.detail).toBe("11 tools, schemas valid");
+  });
+
+  it("fails tools/list when the server exposes no tools", async () => {
+    const server = new McpServer({ name: "empty", version: "0.0.0" }, { capabilities: { tools: {} } });
+    const checks = await runProtocolChecks((transport) => server.connect(transport));
+    expect(checks.find((c) => c.name === "tools/list")).toMatchObject({ status: "fail", detail: "no tools" });
+  });
+});
+
+describe("runConnectivityChecks", () => {
+  it("maps API errors to warnings and failures per toolset", async () => {
+    const registry = {
+      getProbeTargets: () => [
+        { toolset: "pipelines", resourceType: "pipeline" },
+        { toolset: "ccm", resourceType: "cost_perspective" },
+        { toolset: "secrets", resourceType: "secret" },
+        { toolset: "delegates", resourceType: "delegate" },
+      ],
+      dispatch: vi.fn(async (_client: unknown, resourceType: string) => {
+        if (resourceType === "cost_perspective") throw new HarnessApiError("not found", 404);
+        if (resourceType === "secret") throw new HarnessApiError("forbidden", 403);
+        if (resourceType === "delegate") throw new HarnessApiError("unauthorized", 401);
+        return { items: [] };
+      }),
+    } as unknown as Registry;
+
+    const checks = await runConnectivityChecks(registry, {} as HarnessClient, 1000);
+    expect(checks.map((c) => [c.name, c.status])).toEqual([
+      ["pipelines", "ok"],
+      ["ccm", "warn"],
+      ["secrets", "warn"],
+      ["delegates", "fail"],
+    ]);
+    expect(checks[3]!.detail).toMatch(/HARNESS_API_KEY/);
+  });
+});
+
+describe("renderSelfTestReport", () => {
+  it("summarizes the checks and treats warnings as passing", () => {
+    const report = {
+      protocol: [{ name: "initialize", status: "ok" as const, detail: "protocol 2025-06-18" }],
+      connectivity: [{ name: "ccm", status: "warn" as const, detail: "module not deployed" }],
+      target: "https://app.harness.io, account abc",
+    };
+    expect(selfTestPassed(report)).toBe(true);
+    const out = renderSelfTestReport(report);
+    expect(out).toContain("Connectivity (https://app.harness.io, account abc):");
+    expect(out).toMatch(/\[warn\] ccm\s+module not deployed/);
+    expect(out.split("\n").at(-1)).toBe("PASS: 1 ok, 1 warnings, 0 failures");
+  });
+
+  it("fails when any check fails and notes skipped connectivity", () => {
+    const report = {
+      protocol: [{ name: "ping", status: "fail" as const, detail: "timed out" }],
+      target: "https://app.harness.io",
+      skipReason: "HARNESS_API_KEY is not set",
+    };
+    expect(selfTestPassed(report)).toBe(false);
+    const out = renderSelfTestReport(report);
+    expect(out).toContain("skipped: HARNESS_API_KEY is not set");
+    expect(out).toMatch(/^FAIL: 0 ok, 0 warnings, 1 failures$/m);
+  });
+});
@@ -0,0 +1,61 @@
+import { afterEach, describe, expect, it } from "vitest";
+import type { Config } from "../../src/config.js";
+import { Registry } from "../../src/registry/index.js";
+import {
+  recordApiCall,
+  recordCacheLookup,
+  resetServerMetrics,
+  serverStatus,
+} from "../../src/utils/server-metrics.js";
+
+function makeConfig(overrides: Partial<Config> = {}): Config {
+  return {
+    HARNESS_API_KEY: "pat.acct.tok.secretvalue",
+    HARNESS_ACCOUNT_ID: "acct",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_TOOLSETS: "pipelines,connectors",
+    HARNESS_MCP_MODE: "single-user",
+    HARNESS_READ_ONLY: false,
+    LOG_LEVEL: "info",
+    ...overrides,
+  } as Config;
+}
+
+describe("serverStatus", () => {
+  afterEach(() => resetServerMetrics());
+
+  it("reports API error counts by status and cache hit rates", () => {
+    recordApiCall();
+    recordApiCall();
+    recordApiCall(404);
+    recordApiCall(429);
+    recordApiCall(404);
+    recordCacheLookup("runtime_input_template", true);
+    recordCacheLookup("runtime_input_template", true);
+    recordCacheLookup("runtime_input_template", false);
+
+    const config = makeConfig();
+    const status = serverStatus(new Registry(config), config);
+
+    expect(status.api).toEqual({ calls: 5, errors: 3, error_rate: 0.6, errors_by_status: { 404: 2, 429: 1 } });
+    expect(status.caches).toEqual({ runtime_input_template: { hits: 2, misses: 1, hit_rate: 0.667 } });
+    expect(status.toolsets).toMatchObject({ enabled: expect.arrayContaining(["pipelines", "connectors"]) });
+  });
+
+  it("describes the auth mode without exposing the credential", () => {
+    const config = makeConfig();
+    const status = serverStatus(new Registry(config), config);
+
+    expect(status.auth).toEqual({ mode: "single-user", credential: "personal_access_token", http_bearer_token: false });
+    expect(JSON.stringify(status)).not.toContain("secretvalue");
+  });
+
+  it("reports per-session credentials in multi-user mode", () => {
+    const config = makeConfig({ HARNESS_MCP_MODE: "multi-user", HARNESS_API_KEY: "", HARNESS_MCP_AUTH_TOKEN: "gate" });
+    const status = serverStatus(new Registry(config), config);
+
+    expect(status.auth).toEqual({ mode: "multi-user", credential: "per_session", http_bearer_token: true });
+  });
+});
@@ -0,0 +1,130 @@
+import { describe, it, expect, vi } from "vitest";
+import { parse as parseYaml } from "yaml";
+import { Registry } from "../../src/registry/index.js";
+import {
+  buildScaffoldPipelineYaml,
+  buildScaffoldServiceYaml,
+  parseScaffoldOptions,
+  scaffoldServicePipeline,
+  toIdentifier,
+} from "../../src/utils/service-scaffold.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "web",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    HARNESS_TOOLSETS: "services,environments,overrides,pipelines",
+    LOG_LEVEL: "info",
+  } as Config;
+}
+
+const input = {
+  service_name: "Checkout API",
+  repo_connector_ref: "github",
+  repo_name: "checkout",
+  environment_id: "dev",
+  infrastructure_id: "dev_k8s",
+};
+
+function makeClient(failPath?: string) {
+  const request = vi.fn().mockImplementation(async (opts: { method: string; path: string }) => {
+    if (failPath && opts.path.startsWith(failPath)) throw new Error("Duplicate identifier");
+    return { status: "SUCCESS", data: { identifier: "x" } };
+  });
+  return { client: { request, account: "test-account" } as unknown as HarnessClient, request };
+}
+
+const audit = { tool: "harness_execute", action: "scaffold_pipeline" };
+
+describe("parseScaffoldOptions", () => {
+  it("fills defaults from the service name and configured scope", () => {
+    const opts = parseScaffoldOptions(input, { orgId: "default", projectId: "web" });
+    expect(opts).toMatchObject({
+      serviceId: "Checkout_API",
+      runtime: "kubernetes",
+      branch: "main",
+      manifestPath: "k8s/",
+      pipelineId: "Checkout_API_deploy",
+      pipelineName: "Deploy Checkout API",
+      orgId: "default",
+      projectId: "web",
+    });
+  });
+
+  it("rejects missing required fields and unknown runtimes", () => {
+    expect(() => parseScaffoldOptions({ ...input, environment_id: undefined }, { orgId: "o", projectId: "p" })).toThrow(/requires environment_id/);
+    expect(() => parseScaffoldOptions({ ...input, runtime: "ecs" }, { orgId: "o", projectId: "p" })).toThrow(/Unsupported runtime "ecs"/);
+    expect(() => parseScaffoldOptions(input, {})).toThrow(/provide org_id and project_id/);
+    expect(() => parseScaffoldOptions({ ...input, template_ref: "std_deploy" }, { orgId: "o", projectId: "p" })).toThrow(/requires template_version/);
+  });
+
+  it("derives identifiers Harness accepts", () => {
+    expect(toIdentifier("my-service v2")).toBe("my_service_v2");
+    expect(toIdentifier("2fa")).toBe("_2fa");
+  });
+});
+
+describe("scaffold YAML", () => {
+  it("builds a helm service with the chart folder and matching deploy steps", () => {
+    const opts = parseScaffoldOptions({ ...input, runtime: "helm" }, { orgId: "o", projectId: "p" });
+    const service = parseYaml(buildScaffoldServiceYaml(opts));
+    const manifest = service.service.serviceDefinition.spec.manifests[0].manifest;
+    expect(service.service.serviceDefinition.type).toBe("NativeHelm");
+    expect(manifest.type).toBe("HelmChart");
+    expect(manifest.spec.store.spec).toMatchObject({ connectorRef: "github", folderPath: "chart/", branch: "main" });
+
+    const stage = parseYaml(buildScaffoldPipelineYaml(opts)).pipeline.stages[0].stage;
+    expect(stage.spec.execution.steps[0].step.type).toBe("HelmDeploy");
+    expect(stage.spec.environment.infrastructureDefinitions).toEqual([{ identifier: "dev_k8s" }]);
+  });
+
+  it("uses the stage template when template_ref is given", () => {
+    const opts = parseScaffoldOptions({ ...input, template_ref: "std_deploy", template_version: "v1" }, { orgId: "o", projectId: "p" });
+    const stage = parseYaml(buildScaffoldPipelineYaml(opts)).pipeline.stages[0].stage;
+    expect(stage.template).toMatchObject({ templateRef: "std_deploy", versionLabel: "v1" });
+    expect(stage.template.templateInputs.spec.service.serviceRef).toBe("Checkout_API");
+  });
+});
+
+describe("scaffoldServicePipeline", () => {
+  it("checks the environment, then creates the service, binding, and pipeline in order", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient();
+    const result = await scaffoldServicePipeline(registry, client, input, audit);
+
+    const calls = request.mock.calls.map(([opts]) => `${opts.method} ${opts.path}`);
+    expect(calls).toEqual([
+      "GET /ng/api/environmentsV2/dev",
+      "POST /ng/api/servicesV2",
+      "POST /ng/api/serviceOverrides",
+      "POST /pipeline/api/pipelines/v2",
+    ]);
+    expect(request.mock.calls[2]![0].body).toMatchObject({ environmentRef: "dev", serviceRef: "Checkout_API", type: "ENV_SERVICE_OVERRIDE" });
+    expect(result.created).toEqual([
+      expect.objectContaining({ step: "service", identifier: "Checkout_API" }),
+      expect.objectContaining({ step: "environment binding", identifier: "dev_Checkout_API" }),
+      expect.objectContaining({ step: "pipeline", identifier: "Checkout_API_deploy" }),
+    ]);
+  });
+
+  it("names the failed step and what was already created", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient("/ng/api/serviceOverrides");
+    await expect(scaffoldServicePipeline(registry, client, input, audit))
+      .rejects.toThrow(/failed creating the environment binding: Duplicate identifier\. Already created: service "Checkout_API"/);
+    expect(request).toHaveBeenCalledTimes(3);
+  });
+});
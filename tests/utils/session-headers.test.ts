@@ -129,6 +129,15 @@ describe("multi-user session credentials", () => {
     expect(merged.HARNESS_ACCOUNT_ID).toBe("user1");
   });
 
+  it("accepts the Harness-Account header as an account ID alias", () => {
+    const base = makeConfig({ HARNESS_MCP_MODE: "multi-user", HARNESS_API_KEY: "", HARNESS_ACCOUNT_ID: "" });
+    const merged = mergeConfigWithSessionHeaders(base, {
+      "x-harness-api-key": "pat.user1.tok.sec",
+      "harness-account": "user1",
+    });
+    expect(merged.HARNESS_ACCOUNT_ID).toBe("user1");
+  });
+
   it("merges x-harness-org and x-harness-project as session defaults", () => {
     const base = makeConfig({ HARNESS_MCP_MODE: "multi-user", HARNESS_API_KEY: "", HARNESS_ACCOUNT_ID: "" });
     const merged = mergeConfigWithSessionHeaders(base, {
@@ -0,0 +1,120 @@
+import { describe, it, expect, afterEach } from "vitest";
+import { existsSync, mkdtempSync, readdirSync, readFileSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { SpillBuffer, bufferResponseBody, configureSpillBuffers } from "../../src/utils/spill-buffer.js";
+
+describe("SpillBuffer", () => {
+  let dir: string | undefined;
+  afterEach(() => {
+    configureSpillBuffers({});
+    if (dir) rmSync(dir, { recursive: true, force: true });
+    dir = undefined;
+  });
+
+  it("keeps small payloads in memory", async () => {
+    dir = mkdtempSync(join(tmpdir(), "spill-"));
+    const buffer = new SpillBuffer("small", dir, 16);
+    await buffer.write(Buffer.from("hello"));
+    expect(buffer.spilled).toBe(false);
+    expect(buffer.toBuffer().toString()).toBe("hello");
+    expect(readdirSync(dir)).toEqual([]);
+  });
+
+  it("spills to a temp file past the threshold and cleans up on dispose", async () => {
+    dir = mkdtempSync(join(tmpdir(), "spill-"));
+    const buffer = new SpillBuffer("big", dir, 4);
+    await buffer.write(Buffer.from("abc"));
+    await buffer.write(Buffer.from("defg"));
+    await buffer.write(Buffer.from("hij"));
+    expect(buffer.spilled).toBe(true);
+    expect(buffer.size).toBe(10);
+    expect(buffer.magic.toString()).toBe("abcd");
+    expect(() => buffer.toBuffer()).toThrow(/stream\(\)/);
+    const streamed: Buffer[] = [];
+    for await (const chunk of buffer.stream()) streamed.push(chunk as Buffer);
+    expect(Buffer.concat(streamed).toString()).toBe("abcdefghij");
+    expect(readdirSync(dir)).toHaveLength(1);
+    await buffer.dispose();
+    expect(readdirSync(dir)).toEqual([]);
+  });
+
+  it("persists the contents under a sanitized name", async () => {
+    dir = mkdtempSync(join(tmpdir(), "spill-"));
+    const buffer = new SpillBuffer("log", dir, 1024);
+    await buffer.write(Buffer.from("payload"));
+    const file = await buffer.persist("../run log.zip");
+    expect(file.name).toBe("run_log.zip");
+    expect(file.bytes).toBe(7);
+    expect(readFileSync(file.path, "utf-8")).toBe("payload");
+    await buffer.dispose();
+    expect(existsSync(file.path)).toBe(true);
+  });
+
+  it("stays in memory without an output dir", async () => {
+    const buffer = new SpillBuffer("nodir", undefined, 2);
+    await buffer.write(Buffer.from("abcdef"));
+    expect(buffer.canSpill).toBe(false);
+    expect(buffer.spilled).toBe(false);
+    await expect(buffer.persist()).rejects.toThrow(/HARNESS_OUTPUT_DIR/);
+  });
+});
+
+describe("bufferResponseBody", () => {
+  afterEach(() => configureSpillBuffers({}));
+
+  function chunkedResponse(chunks: string[]): Response {
+    const stream = new ReadableStream<Uint8Array>({
+      start(controller) {
+        for (const c of chunks) controller.enqueue(new TextEncoder().encode(c));
+        controller.close();
+      },
+    });
+    return new Response(stream);
+  }
+
+  it("stops reading once the in-memory limit is exceeded and spilling is off", async () => {
+    const buffer = await bufferResponseBody(chunkedResponse(["aaaa", "bbbb", "cccc"]), "body", { maxInMemoryBytes: 5 });
+    expect(buffer.size).toBe(8);
+  });
+
+  it("reads the whole body to disk when an output dir is configured", async () => {
+    const dir = mkdtempSync(join(tmpdir(), "spill-"));
+    try {
+      configureSpillBuffers({ dir, thresholdBytes: 4 });
+      const buffer = await bufferResponseBody(chunkedResponse(["aaaa", "bbbb", "cccc"]), "body", { maxInMemoryBytes: 5 });
+      expect(buffer.size).toBe(12);
+      expect(buffer.spilled).toBe(true);
+      await buffer.dispose();
+    } finally {
+      rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  it("keeps bodies within the caller's in-memory limit off disk", async () => {
+    const dir = mkdtempSync(join(tmpdir(), "spill-"));
+    try {
+      configureSpillBuffers({ dir, thresholdBytes: 4 });
+      const buffer = await bufferResponseBody(chunkedResponse(["aaaa", "bbbb"]), "body", { maxInMemoryBytes: 16 });
+      expect(buffer.spilled).toBe(false);
+      expect(buffer.toBuffer().toString()).toBe("aaaabbbb");
+      expect(readdirSync(dir)).toEqual([]);
+    } finally {
+      rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  it("stops reading at the disk cap and marks the body truncated", async () => {
+    const dir = mkdtempSync(join(tmpdir(), "spill-"));
+    try {
+      configureSpillBuffers({ dir, thresholdBytes: 4, maxDiskBytes: 10 });
+      const buffer = await bufferResponseBody(chunkedResponse(["aaaa", "bbbb", "cccc", "dddd", "eeee"]), "body");
+      expect(buffer.truncated).toBe(true);
+      expect(buffer.size).toBe(12);
+      await buffer.dispose();
+      expect(readdirSync(dir)).toEqual([]);
+    } finally {
+      rmSync(dir, { recursive: true, force: true });
+    }
+  });
+});
@@ -0,0 +1,122 @@
+import { describe, it, expect } from "vitest";
+import { PassThrough } from "node:stream";
+import { FrameReader, StdioTransport, frameMessage } from "../../src/utils/stdio-transport.js";
+import type { JSONRPCMessage } from "@modelcontextprotocol/sdk/types.js";
+
+const ping = (id: number): JSONRPCMessage => ({ jsonrpc: "2.0", id, method: "ping" });
+
+function contentLength(message: JSONRPCMessage): string {
+  const json = JSON.stringify(message);
+  return `Content-Length: ${Buffer.byteLength(json)}\r\n\r\n${json}`;
+}
+
+const flush = (): Promise<void> => new Promise((resolve) => setImmediate(resolve));
+
+function readAll(reader: FrameReader): Array<{ message: JSONRPCMessage; framing: string }> {
+  const frames = [];
+  for (let frame = reader.read(); frame; frame = reader.read()) frames.push(frame);
+  return frames;
+}
+
+describe("FrameReader", () => {
+  it("reads newline-delimited JSON, skipping blank lines and CRLF", () => {
+    const reader = new FrameReader();
+    reader.append(Buffer.from(`\n${JSON.stringify(ping(1))}\r\n\r\n\n${JSON.stringify(ping(2))}\n`));
+    expect(readAll(reader).map((f) => [f.message, f.framing])).toEqual([[ping(1), "ndjson"], [ping(2), "ndjson"]]);
+  });
+
+  it("reads Content-Length frames split across chunks", () => {
+    const reader = new FrameReader();
+    const frame = contentLength({ jsonrpc: "2.0", id: 3, method: "tools/call", params: { name: "héllo" } });
+    reader.append(Buffer.from(frame.slice(0, 10)));
+    expect(reader.read()).toBeUndefined();
+    reader.append(Buffer.from(frame.slice(10, frame.length - 3)));
+    expect(reader.read()).toBeUndefined();
+    reader.append(Buffer.from(frame.slice(frame.length - 3)));
+    expect(reader.read()).toMatchObject({ framing: "content-length", message: { id: 3, params: { name: "héllo" } } });
+  });
+
+  it("accepts extra headers and mixed framings in one stream", () => {
+    const reader = new FrameReader();
+    const json = JSON.stringify(ping(5));
+    reader.append(Buffer.from(`Content-Type: application/vscode-jsonrpc; charset=utf-8\r\ncontent-length: ${json.length}\r\n\r\n${json}\n${JSON.stringify(ping(6))}\n`));
+    expect(readAll(reader).map((f) => f.framing)).toEqual(["content-length", "ndjson"]);
+  });
+
+  it("throws on a malformed frame and keeps reading after it", () => {
+    const reader = new FrameReader();
+    reader.append(Buffer.from(`not json\n{"broken"\n${JSON.stringify(ping(7))}\n`));
+    expect(() => reader.read()).toThrow(/neither JSON nor a Content-Length header/);
+    expect(() => reader.read()).toThrow();
+    expect(reader.read()?.message).toEqual(ping(7));
+  });
+
+  it("rejects an oversized Content-Length without buffering its body", () => {
+    const reader = new FrameReader(1024);
+    reader.append(Buffer.from("Content-Length: 4096\r\n\r\n" + "x".repeat(1000)));
+    expect(() => reader.read()).toThrow(/4096 bytes exceeds the 1024-byte frame limit/);
+    reader.append(Buffer.from("x".repeat(3000)));
+    expect(reader.read()).toBeUndefined();
+    reader.append(Buffer.from("x".repeat(96) + contentLength(ping(8))));
+    expect(reader.read()?.message).toEqual(ping(8));
+  });
+
+  it("rejects an oversized JSON line and resumes after its newline", () => {
+    const reader = new FrameReader(1024);
+    reader.append(Buffer.from(`{"pad":"${"x".repeat(2000)}`));
+    expect(() => reader.read()).toThrow(/exceeds the 1024-byte frame limit/);
+    reader.append(Buffer.from(`${"x".repeat(2000)}"}\n${JSON.stringify(ping(9))}\n`));
+    expect(reader.read()?.message).toEqual(ping(9));
+  });
+
+  it("drops a header block that never terminates", () => {
+    const reader = new FrameReader();
+    reader.append(Buffer.from(`Content-Length: 10\r\nX-Pad: ${"x".repeat(20_000)}`));
+    expect(() => reader.read()).toThrow(/header block exceeds/);
+    reader.append(Buffer.from(`${JSON.stringify(ping(10))}\n`));
+    expect(reader.read()?.message).toEqual(ping(10));
+  });
+});
+
+describe("StdioTransport", () => {
+  it("replies in the framing of the most recent request", async () => {
+    const stdin = new PassThrough();
+    const stdout = new PassThrough();
+    const transport = new StdioTransport(stdin, stdout);
+    const received: JSONRPCMessage[] = [];
+    transport.onmessage = (message) => received.push(message);
+    await transport.start();
+
+    stdin.write(`${JSON.stringify(ping(1))}\n`);
+    await flush();
+    await transport.send({ jsonrpc: "2.0", id: 1, result: {} });
+    stdin.write(contentLength(ping(2)));
+    await flush();
+    await transport.send({ jsonrpc: "2.0", id: 2, result: {} });
+
+    expect(received).toEqual([ping(1), ping(2)]);
+    const output = stdout.read().toString();
+    expect(output).toBe(
+      frameMessage({ jsonrpc: "2.0", id: 1, result: {} }, "ndjson") +
+      frameMessage({ jsonrpc: "2.0", id: 2, result: {} }, "content-length"),
+    );
+    await transport.close();
+  });
+
+  it("reports malformed input through onerror without dropping later messages", async () => {
+    const stdin = new PassThrough();
+    const transport = new StdioTransport(stdin, new PassThrough());
+    const errors: Error[] = [];
+    const received: JSONRPCMessage[] = [];
+    transport.onerror = (err) => errors.push(err);
+    transport.onmessage = (message) => received.push(message);
+    await transport.start();
+
+    stdin.write(`{oops}\n${JSON.stringify(ping(9))}\n`);
+    await flush();
+
+    expect(errors).toHaveLength(1);
+    expect(received).toEqual([ping(9)]);
+    await transport.close();
+  });
+});
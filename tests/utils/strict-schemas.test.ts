@@ -0,0 +1,106 @@
+import { afterEach, describe, expect, it } from "vitest";
+import type { BodyFieldSpec, FilterFieldSpec } from "../../src/registry/types.js";
+import {
+  bodySchemaViolations,
+  checkToolOutput,
+  configureStrictSchemas,
+  filterViolations,
+} from "../../src/utils/strict-schemas.js";
+import { deleteOutputSchema, TOOL_OUTPUT_SCHEMAS } from "../../src/tools/output-schemas.js";
+import { jsonResult, errorResult } from "../../src/utils/response-formatter.js";
+
+const BODY_FIELDS: BodyFieldSpec[] = [
+  { name: "identifier", type: "string", required: true, description: "id" },
+  { name: "tags", type: "object", required: false, description: "tags" },
+  { name: "replicas", type: "number", required: false, description: "count" },
+  { name: "yaml", type: "yaml", required: false, description: "definition" },
+  {
+    name: "spec",
+    type: "object",
+    required: false,
+    description: "spec",
+    fields: [{ name: "type", type: "string", required: true, description: "kind" }],
+  },
+];
+
+describe("bodySchemaViolations", () => {
+  it("accepts values matching the declared types", () => {
+    expect(bodySchemaViolations(BODY_FIELDS, {
+      identifier: "svc",
+      tags: { team: "a" },
+      replicas: 2,
+      yaml: "service: {}",
+      spec: { type: "K8s" },
+      undeclared: 42,
+    })).toEqual([]);
+  });
+
+  it("reports type mismatches and missing nested required fields with paths", () => {
+    expect(bodySchemaViolations(BODY_FIELDS, {
+      identifier: 7,
+      tags: ["a"],
+      replicas: "2",
+      spec: {},
+    })).toEqual([
+      "identifier: expected string, got number",
+      "tags: expected object, got array",
+      "replicas: expected number, got string",
+      "spec.type: required",
+    ]);
+  });
+});
+
+describe("filterViolations", () => {
+  const FILTERS: FilterFieldSpec[] = [
+    { name: "status", description: "status", enum: ["Success", "Failed"] },
+    { name: "limit", description: "limit", type: "number" },
+    { name: "archived", description: "archived", type: "boolean" },
+  ];
+
+  it("accepts query-string equivalents and enum members", () => {
+    expect(filterViolations(FILTERS, { status: ["Success", "Failed"], limit: "10", archived: "true" })).toEqual([]);
+  });
+
+  it("reports enum and type mismatches", () => {
+    expect(filterViolations(FILTERS, { status: "Running", limit: "ten", archived: "yes" })).toEqual([
+      "status: \"Running\" is not one of Success, Failed",
+      "limit: expected number, got string",
+      "archived: expected boolean, got string",
+    ]);
+  });
+});
+
+describe("checkToolOutput", () => {
+  afterEach(() => configureStrictSchemas({ enabled: false }));
+
+  it("passes results that match the declared output schema", () => {
+    configureStrictSchemas({ enabled: true, outputSchemas: TOOL_OUTPUT_SCHEMAS });
+    const result = jsonResult({ deleted: true, resource_type: "service", resource_id: "svc" });
+    expect(checkToolOutput("harness_delete", result)).toBe(result);
+  });
+
+  it("turns schema drift into an OUTPUT_SCHEMA_MISMATCH error naming the paths", () => {
+    configureStrictSchemas({ enabled: true, outputSchemas: { harness_delete: deleteOutputSchema } });
+    const checked = checkToolOutput("harness_delete", jsonResult({ deleted: "yes", resource_type: "service" }));
+
+    expect(checked.isError).toBe(true);
+    const payload = JSON.parse((checked.content[0] as { text: string }).text);
+    expect(payload.code).toBe("OUTPUT_SCHEMA_MISMATCH");
+    expect(payload.issues.some((i: string) => i.startsWith("deleted:"))).toBe(true);
+    expect(payload.issues.some((i: string) => i.startsWith("resource_id:"))).toBe(true);
+  });
+
+  it("flags results without structured content", () => {
+    configureStrictSchemas({ enabled: true, outputSchemas: TOOL_OUTPUT_SCHEMAS });
+    const checked = checkToolOutput("harness_get", jsonResult(["not", "an", "object"]));
+    expect(checked.isError).toBe(true);
+  });
+
+  it("leaves error results and undeclared tools alone", () => {
+    configureStrictSchemas({ enabled: true, outputSchemas: TOOL_OUTPUT_SCHEMAS });
+    const error = errorResult("boom");
+    expect(checkToolOutput("harness_get", error)).toBe(error);
+    const other = jsonResult([1, 2]);
+    expect(checkToolOutput("not_a_tool", other)).toBe(other);
+  });
+});
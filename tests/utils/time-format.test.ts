@@ -0,0 +1,73 @@
+import { afterEach, describe, expect, it } from "vitest";
+import {
+  assertTimeZone,
+  configureTimeFormatting,
+  formatDuration,
+  formatTimestamp,
+  localizeTimes,
+} from "../../src/utils/time-format.js";
+import { jsonResult } from "../../src/utils/response-formatter.js";
+
+const TS = Date.UTC(2025, 6, 10, 8, 0, 0);
+
+describe("formatTimestamp", () => {
+  it("uses Z for UTC", () => {
+    expect(formatTimestamp(TS, "UTC")).toBe("2025-07-10T08:00:00.000Z");
+  });
+
+  it("renders wall-clock time with the zone's offset", () => {
+    expect(formatTimestamp(TS, "Asia/Kolkata")).toBe("2025-07-10T13:30:00.000+05:30");
+    expect(formatTimestamp(TS, "America/New_York")).toBe("2025-07-10T04:00:00.000-04:00");
+  });
+});
+
+describe("formatDuration", () => {
+  it("picks units by magnitude", () => {
+    expect(formatDuration(850)).toBe("850ms");
+    expect(formatDuration(42_000)).toBe("42s");
+    expect(formatDuration(185_000)).toBe("3m 5s");
+    expect(formatDuration(7_212_000)).toBe("2h 0m 12s");
+    expect(formatDuration(102_600_000)).toBe("1d 4h 30m");
+  });
+});
+
+describe("localizeTimes", () => {
+  afterEach(() => configureTimeFormatting({ enabled: false }));
+
+  it("is a no-op when disabled", () => {
+    const payload = { startTs: TS };
+    expect(localizeTimes(payload)).toBe(payload);
+  });
+
+  it("converts timestamp and duration fields at any depth", () => {
+    configureTimeFormatting({ enabled: true });
+    const payload = {
+      items: [{ startTs: TS, createdAt: TS, durationMs: 185_000, runSequence: 1720598400000 }],
+      total: 1,
+    };
+    expect(localizeTimes(payload)).toEqual({
+      items: [{ startTs: "2025-07-10T08:00:00.000Z", createdAt: "2025-07-10T08:00:00.000Z", durationMs: "3m 5s", runSequence: 1720598400000 }],
+      total: 1,
+    });
+    expect(payload.items[0]!.startTs).toBe(TS);
+  });
+
+  it("leaves small numbers in timestamp fields alone", () => {
+    configureTimeFormatting({ enabled: true });
+    expect(localizeTimes({ startTime: 0, endTime: 1700 })).toEqual({ startTime: 0, endTime: 1700 });
+  });
+
+  it("applies to jsonResult text and structured content", () => {
+    configureTimeFormatting({ enabled: true, timeZone: "Asia/Kolkata" });
+    const result = jsonResult({ lastUpdatedAt: TS });
+    expect(result.structuredContent).toEqual({ lastUpdatedAt: "2025-07-10T13:30:00.000+05:30" });
+    expect(result.content[0]).toMatchObject({ text: expect.stringContaining("13:30:00.000+05:30") });
+  });
+});
+
+describe("assertTimeZone", () => {
+  it("rejects unknown zones", () => {
+    expect(() => assertTimeZone("Mars/Olympus_Mons")).toThrow(/Invalid HARNESS_TIMEZONE/);
+    expect(() => assertTimeZone("Europe/Berlin")).not.toThrow();
+  });
+});
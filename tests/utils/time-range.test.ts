@@ -0,0 +1,41 @@
+import { describe, expect, it } from "vitest";
+import { parseTimeInput } from "../../src/utils/time-range.js";
+
+const NOW = Date.UTC(2026, 4, 21, 12, 0, 0);
+const DAY = 86_400_000;
+
+describe("parseTimeInput", () => {
+  it("passes epoch ms through, as numbers or numeric strings", () => {
+    expect(parseTimeInput(1720598400000)).toBe(1720598400000);
+    expect(parseTimeInput("1720598400000")).toBe(1720598400000);
+  });
+
+  it("parses ISO 8601 / RFC 3339", () => {
+    expect(parseTimeInput("2025-07-10T08:00:00Z")).toBe(Date.UTC(2025, 6, 10, 8));
+    expect(parseTimeInput("2025-07-10")).toBe(Date.UTC(2025, 6, 10));
+  });
+
+  it("parses relative durations in several spellings", () => {
+    for (const input of ["7d", "last 7d", "Last 7 days", "past 7 days", "7 days ago", "1 week ago"]) {
+      expect(parseTimeInput(input, { now: NOW }), input).toBe(NOW - 7 * DAY);
+    }
+    expect(parseTimeInput("24h", { now: NOW })).toBe(NOW - DAY);
+    expect(parseTimeInput("last 30 minutes", { now: NOW })).toBe(NOW - 30 * 60_000);
+  });
+
+  it("resolves day keywords to UTC day boundaries, by window edge", () => {
+    const todayStart = Date.UTC(2026, 4, 21);
+    expect(parseTimeInput("now", { now: NOW })).toBe(NOW);
+    expect(parseTimeInput("today", { now: NOW })).toBe(todayStart);
+    expect(parseTimeInput("yesterday", { now: NOW })).toBe(todayStart - DAY);
+    expect(parseTimeInput("yesterday", { now: NOW, edge: "end" })).toBe(todayStart - 1);
+  });
+
+  it("returns undefined for empty or unrecognized input", () => {
+    expect(parseTimeInput(undefined)).toBeUndefined();
+    expect(parseTimeInput("")).toBeUndefined();
+    expect(parseTimeInput("last 7 fortnights")).toBeUndefined();
+    expect(parseTimeInput("not a date")).toBeUndefined();
+    expect(parseTimeInput(-5)).toBeUndefined();
+  });
+});
@@ -0,0 +1,76 @@
+import { describe, expect, it } from "vitest";
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+import { Registry } from "../../src/registry/index.js";
+import { registerAllTools } from "../../src/tools/index.js";
+import { callServerTool, listServerTools, loadCatalogConfig, renderCallResult, renderToolList, renderToolSchema } from "../../src/utils/tool-catalog.js";
+
+async function catalog(env: NodeJS.ProcessEnv) {
+  const config = loadCatalogConfig(env);
+  const registry = new Registry(config);
+  const server = new McpServer({ name: "catalog-test", version: "0.0.0" });
+  registerAllTools(server, registry, {} as HarnessClient, config);
+  return { tools: await listServerTools(server), registry };
+}
+
+describe("loadCatalogConfig", () => {
+  it("validates without an API key", () => {
+    expect(loadCatalogConfig({ HARNESS_TOOLSETS: "pipelines" }).HARNESS_TOOLSETS).toBe("pipelines");
+  });
+
+  it("still reports invalid settings", () => {
+    expect(() => loadCatalogConfig({ HARNESS_TOOLSETS: "pipelines", HARNESS_ROUTE_MAP: "{" })).toThrow(/Invalid configuration/);
+  });
+});
+
+describe("renderToolList", () => {
+  it("lists every tool with access flags and the operations per toolset", async () => {
+    const { tools, registry } = await catalog({ HARNESS_TOOLSETS: "pipelines" });
+    expect(tools).toHaveLength(11);
+    const out = renderToolList(tools, registry);
+    expect(out).toMatch(/harness_list\s+\[read\]/);
+    expect(out).toMatch(/harness_delete\s+\[write, destructive\]/);
+    expect(out).toContain("  pipelines — ");
+    expect(out).toMatch(/ {4}pipeline {2}read: list, get; write: .*delete.*execute:run/);
+  });
+});
+
+describe("renderToolSchema", () => {
+  it("prints the tool's JSON schema", async () => {
+    const { tools } = await catalog({ HARNESS_TOOLSETS: "pipelines" });
+    const schema = JSON.parse(renderToolSchema(tools, "harness_get"));
+    expect(schema.name).toBe("harness_get");
+    expect(schema.inputSchema.properties.resource_type).toBeDefined();
+  });
+
+  it("names the available tools for an unknown name", async () => {
+    const { tools } = await catalog({ HARNESS_TOOLSETS: "pipelines" });
+    expect(() => renderToolSchema(tools, "harness_nope")).toThrow(/Unknown tool "harness_nope". Available: harness_list/);
+  });
+});
+
+describe("callServerTool", () => {
+  it("runs the tool and renders its result as JSON", async () => {
+    const config = loadCatalogConfig({ HARNESS_TOOLSETS: "pipelines" });
+    const server = new McpServer({ name: "catalog-test", version: "0.0.0" });
+    registerAllTools(server, new Registry(config), {} as HarnessClient, config);
+
+    const result = await callServerTool(server, "harness_describe", { resource_type: "pipeline" });
+    expect(result.isError).toBeFalsy();
+    expect(JSON.parse(renderCallResult(result))).toMatchObject({ resource_type: "pipeline" });
+  });
+
+  it("rejects unknown tools", async () => {
+    const config = loadCatalogConfig({ HARNESS_TOOLSETS: "pipelines" });
+    const server = new McpServer({ name: "catalog-test", version: "0.0.0" });
+    registerAllTools(server, new Registry(config), {} as HarnessClient, config);
+    await expect(callServerTool(server, "harness_nope", {})).rejects.toThrow(/Unknown tool "harness_nope"/);
+  });
+});
+
+describe("renderCallResult", () => {
+  it("wraps plain-text errors", () => {
+    expect(JSON.parse(renderCallResult({ isError: true, content: [{ type: "text", text: "resource_type is required" }] })))
+      .toEqual({ error: "resource_type is required" });
+  });
+});
@@ -0,0 +1,104 @@
+import { describe, it, expect, vi, afterEach } from "vitest";
+import {
+  configureToolTimeouts,
+  parseToolTimeouts,
+  resolveToolTimeout,
+  withToolTimeout,
+} from "../../src/utils/tool-timeouts.js";
+import { sendProgress } from "../../src/utils/progress.js";
+
+function makeExtra(signal = new AbortController().signal) {
+  return { signal, sendNotification: vi.fn().mockResolvedValue(undefined), _meta: { progressToken: "t" } } as any;
+}
+
+afterEach(() => {
+  configureToolTimeouts();
+  vi.useRealTimers();
+});
+
+describe("parseToolTimeouts", () => {
+  it("accepts tool, tool:resource_type, and default keys", () => {
+    expect(parseToolTimeouts('{"default":1000,"harness_list":0,"harness_get:execution_log":5000}'))
+      .toEqual({ default: 1000, harness_list: 0, "harness_get:execution_log": 5000 });
+  });
+
+  it("rejects malformed JSON, non-objects, and non-integer values", () => {
+    expect(() => parseToolTimeouts("{")).toThrow(/not valid JSON/);
+    expect(() => parseToolTimeouts("[1]")).toThrow(/JSON object/);
+    expect(() => parseToolTimeouts('{"harness_get":"30s"}')).toThrow(/harness_get/);
+    expect(() => parseToolTimeouts('{"default":-1}')).toThrow(/non-negative/);
+  });
+});
+
+describe("resolveToolTimeout", () => {
+  it("prefers tool:resource_type, then tool, then default", () => {
+    configureToolTimeouts({ default: 1000, harness_get: 2000, "harness_get:execution_log": 3000 });
+    expect(resolveToolTimeout("harness_get", { resource_type: "execution_log" })).toBe(3000);
+    expect(resolveToolTimeout("harness_get", { resource_type: "pipeline" })).toBe(2000);
+    expect(resolveToolTimeout("harness_list", { resource_type: "pipeline" })).toBe(1000);
+  });
+
+  it("keeps built-in overrides for log downloads and diagnose", () => {
+    expect(resolveToolTimeout("harness_get", { resource_type: "execution_log" })).toBe(300_000);
+    expect(resolveToolTimeout("harness_diagnose", {})).toBe(300_000);
+    expect(resolveToolTimeout("harness_list", {})).toBe(120_000);
+  });
+
+  it("extends harness_execute deadlines to cover wait_timeout_seconds", () => {
+    expect(resolveToolTimeout("harness_execute", { wait: true, wait_timeout_seconds: 1800 })).toBe(1_860_000);
+    expect(resolveToolTimeout("harness_execute", { wait: false })).toBe(120_000);
+  });
+});
+
+describe("withToolTimeout", () => {
+  it("returns the handler result when it finishes in time", async () => {
+    const result = await withToolTimeout("harness_list", {}, makeExtra(), async () => ({ content: [{ type: "text" as const, text: "ok" }] }));
+    expect(result.content[0]).toEqual({ type: "text", text: "ok" });
+  });
+
+  it("returns a structured timeout error with the last progress update and aborts the handler signal", async () => {
+    vi.useFakeTimers();
+    configureToolTimeouts({ harness_diagnose: 1000 });
+    let handlerSignal: AbortSignal | undefined;
+
+    const pending = withToolTimeout("harness_diagnose", { resource_type: "pipeline" }, makeExtra(), async (extra) => {
+      handlerSignal = extra.signal;
+      await sendProgress(extra, 2, 5, "Fetching step logs...");
+      return new Promise<never>(() => { /* never settles */ });
+    });
+    await vi.advanceTimersByTimeAsync(1000);
+    const result = await pending;
+
+    expect(result.isError).toBe(true);
+    const payload = JSON.parse((result.content[0] as { text: string }).text);
+    expect(payload).toMatchObject({
+      code: "TOOL_TIMEOUT",
+      tool: "harness_diagnose",
+      resource_type: "pipeline",
+      timeout_ms: 1000,
+      partial_progress: { progress: 2, total: 5, message: "Fetching step logs...", updates: 1 },
+    });
+    expect(payload.hint).toContain("harness_diagnose:pipeline");
+    expect(handlerSignal?.aborted).toBe(true);
+  });
+
+  it("forwards client cancellation to the handler signal", async () => {
+    const parent = new AbortController();
+    let handlerSignal: AbortSignal | undefined;
+    const pending = withToolTimeout("harness_list", {}, makeExtra(parent.signal), async (extra) => {
+      handlerSignal = extra.signal;
+      return "done";
+    });
+    parent.abort();
+    await pending;
+    expect(handlerSignal?.aborted).toBe(true);
+  });
+
+  it("runs without a deadline when the timeout is 0", async () => {
+    configureToolTimeouts({ harness_status: 0 });
+    const extra = makeExtra();
+    let received: unknown;
+    await withToolTimeout("harness_status", {}, extra, async (e) => { received = e; return "done"; });
+    expect(received).toBe(extra);
+  });
+});
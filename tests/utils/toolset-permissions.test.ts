@@ -0,0 +1,62 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+import type { Config } from "../../src/config.js";
+import { Registry } from "../../src/registry/index.js";
+import { resetPermissionCache } from "../../src/utils/permission-cache.js";
+import { hiddenToolsetsFor, parseToolsetPermissions } from "../../src/utils/toolset-permissions.js";
+
+const permissions = {
+  ccm: { resourceType: "CCM_PERSPECTIVE", permission: "ccm_perspective_view" },
+  chaos: { resourceType: "CHAOS_EXPERIMENT", permission: "chaos_chaosexperiment_view" },
+};
+
+function makeClient(permitted: Record<string, boolean | Error>, credentialId = "cred-a") {
+  const request = vi.fn().mockImplementation(async (opts: { body: { permissions: Array<{ resourceType: string }> } }) => {
+    const decision = permitted[opts.body.permissions[0].resourceType];
+    if (decision instanceof Error) throw decision;
+    return { data: { accessControlList: [{ permitted: decision === true }] } };
+  });
+  return { client: { account: "acc1", credentialId, request } as unknown as HarnessClient, request };
+}
+
+describe("parseToolsetPermissions", () => {
+  it("accepts toolset → {resourceType, permission}", () => {
+    expect(parseToolsetPermissions(JSON.stringify(permissions), ["ccm", "chaos"])).toEqual(permissions);
+  });
+
+  it("rejects malformed JSON, bad entries, and unknown toolsets", () => {
+    expect(() => parseToolsetPermissions("{")).toThrow(/not valid JSON/);
+    expect(() => parseToolsetPermissions('{"ccm":"ccm_perspective_view"}')).toThrow(/HARNESS_TOOLSET_PERMISSIONS\["ccm"\]/);
+    expect(() => parseToolsetPermissions(JSON.stringify(permissions), ["ccm"])).toThrow(/unknown toolset "chaos"/);
+  });
+});
+
+describe("hiddenToolsetsFor", () => {
+  beforeEach(() => resetPermissionCache());
+
+  it("hides toolsets whose permission the caller lacks", async () => {
+    const { client } = makeClient({ CCM_PERSPECTIVE: true, CHAOS_EXPERIMENT: false });
+    expect(await hiddenToolsetsFor(client, permissions)).toEqual(["chaos"]);
+  });
+
+  it("hides a toolset when its check fails", async () => {
+    const { client } = makeClient({ CCM_PERSPECTIVE: new Error("ACL service down"), CHAOS_EXPERIMENT: true });
+    expect(await hiddenToolsetsFor(client, permissions)).toEqual(["ccm"]);
+  });
+
+  it("does not share decisions between credentials in one account", async () => {
+    const alice = makeClient({ CCM_PERSPECTIVE: true, CHAOS_EXPERIMENT: true }, "cred-a");
+    const bob = makeClient({ CCM_PERSPECTIVE: false, CHAOS_EXPERIMENT: false }, "cred-b");
+    expect(await hiddenToolsetsFor(alice.client, permissions)).toEqual([]);
+    expect(await hiddenToolsetsFor(bob.client, permissions)).toEqual(["ccm", "chaos"]);
+    expect(bob.request).toHaveBeenCalledTimes(2);
+  });
+
+  it("drops hidden toolsets from the session registry", () => {
+    const config = { HARNESS_ACCOUNT_ID: "acc1", HARNESS_TOOLSETS: "connectors,ccm" } as Config;
+    const registry = new Registry(config, { hiddenToolsets: ["ccm"] });
+    const names = registry.getAllToolsets().map((t) => t.name);
+    expect(names).toContain("connectors");
+    expect(names).not.toContain("ccm");
+  });
+});
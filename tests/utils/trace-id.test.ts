@@ -0,0 +1,47 @@
+import { describe, it, expect, vi, afterEach } from "vitest";
+import { currentTraceId, runWithTraceId, withTraceMeta } from "../../src/utils/trace-id.js";
+import { createLogger } from "../../src/utils/logger.js";
+import { withToolTimeout } from "../../src/utils/tool-timeouts.js";
+
+function makeExtra() {
+  return { signal: new AbortController().signal, sendNotification: vi.fn() } as any;
+}
+
+afterEach(() => {
+  vi.restoreAllMocks();
+});
+
+describe("withTraceMeta", () => {
+  it("adds _meta.trace_id and keeps existing _meta", () => {
+    expect(withTraceMeta({ content: [], _meta: { cursor: "c" } }, "t1")).toEqual({ content: [], _meta: { cursor: "c", trace_id: "t1" } });
+    expect(withTraceMeta("done", "t1")).toBe("done");
+  });
+});
+
+describe("tool call tracing", () => {
+  it("returns the trace ID the handler ran under, for errors too", async () => {
+    let seen: string | undefined;
+    const result = await withToolTimeout("harness_get", {}, makeExtra(), async () => {
+      seen = currentTraceId();
+      return { content: [{ type: "text" as const, text: "{\"error\":\"nope\"}" }], isError: true };
+    });
+    expect(seen).toMatch(/^[0-9a-f-]{36}$/);
+    expect((result as { _meta?: { trace_id?: string } })._meta?.trace_id).toBe(seen);
+    expect(currentTraceId()).toBeUndefined();
+  });
+
+  it("gives each call its own trace ID", async () => {
+    const handler = async () => ({ content: [] });
+    const a = await withToolTimeout("harness_list", {}, makeExtra(), handler) as { _meta: { trace_id: string } };
+    const b = await withToolTimeout("harness_list", {}, makeExtra(), handler) as { _meta: { trace_id: string } };
+    expect(a._meta.trace_id).not.toBe(b._meta.trace_id);
+  });
+
+  it("stamps log lines written inside the call", () => {
+    const stderr = vi.spyOn(console, "error").mockImplementation(() => {});
+    runWithTraceId("trace-42", () => createLogger("test").warn("inside"));
+    createLogger("test").warn("outside");
+    expect(JSON.parse(stderr.mock.calls[0]![0] as string)).toMatchObject({ msg: "inside", trace_id: "trace-42" });
+    expect(JSON.parse(stderr.mock.calls[1]![0] as string).trace_id).toBeUndefined();
+  });
+});
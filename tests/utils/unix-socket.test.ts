@@ -0,0 +1,41 @@
+import { describe, it, expect, afterEach } from "vitest";
+import { mkdtempSync, statSync, writeFileSync } from "node:fs";
+import type { Server } from "node:net";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { listenOnUnixSocket, prepareSocketPath, removeSocketPath } from "../../src/utils/unix-socket.js";
+
+const servers: Server[] = [];
+
+afterEach(async () => {
+  await Promise.all(servers.splice(0).map((s) => new Promise((r) => s.close(r))));
+});
+
+function socketPath(): string {
+  return join(mkdtempSync(join(tmpdir(), "harness-sock-")), "mcp.sock");
+}
+
+describe.skipIf(process.platform === "win32")("unix socket transport", () => {
+  it("creates the socket owner-only", async () => {
+    const path = socketPath();
+    servers.push(await listenOnUnixSocket(path, (s) => s.end()));
+    expect(statSync(path).mode & 0o777).toBe(0o600);
+  });
+
+  it("refuses a socket another server is listening on", async () => {
+    const path = socketPath();
+    servers.push(await listenOnUnixSocket(path, (s) => s.end()));
+    await expect(prepareSocketPath(path)).rejects.toThrow(/already in use/);
+  });
+
+  it("is a no-op when nothing exists at the path", async () => {
+    await expect(prepareSocketPath(socketPath())).resolves.toBeUndefined();
+  });
+
+  it("refuses to replace a regular file", async () => {
+    const path = socketPath();
+    writeFileSync(path, "not a socket");
+    await expect(prepareSocketPath(path)).rejects.toThrow(/not a socket/);
+    removeSocketPath(path);
+  });
+});
@@ -0,0 +1,114 @@
+import { describe, it, expect, vi } from "vitest";
+import { Registry } from "../../src/registry/index.js";
+import { applyYamlBundle, orderBundle, parseYamlBundle } from "../../src/utils/yaml-apply.js";
+import { HarnessApiError } from "../../src/utils/errors.js";
+import type { Config } from "../../src/config.js";
+import type { HarnessClient } from "../../src/client/harness-client.js";
+
+function makeConfig(): Config {
+  return {
+    HARNESS_API_KEY: "pat.test",
+    HARNESS_ACCOUNT_ID: "test-account",
+    HARNESS_BASE_URL: "https://app.harness.io",
+    HARNESS_ORG: "default",
+    HARNESS_PROJECT: "web",
+    HARNESS_API_TIMEOUT_MS: 30000,
+    HARNESS_MAX_RETRIES: 3,
+    HARNESS_MAX_BODY_SIZE_MB: 10,
+    HARNESS_RATE_LIMIT_RPS: 10,
+    HARNESS_READ_ONLY: false,
+    HARNESS_SKIP_ELICITATION: false,
+    HARNESS_ALLOW_HTTP: false,
+    HARNESS_FME_BASE_URL: "https://api.split.io",
+    HARNESS_TOOLSETS: "services,connectors,pipelines,templates",
+    LOG_LEVEL: "info",
+  } as Config;
+}
+
+const BUNDLE = `
+pipeline:
+  name: Deploy
+  identifier: deploy
+  orgIdentifier: default
+  projectIdentifier: web
+  stages: []
+---
+inputSet:
+  name: prod
+  identifier: prod
+  pipeline:
+    identifier: deploy
+---
+connector:
+  name: GitHub
+  identifier: github
+  orgIdentifier: default
+  type: Github
+---
+secret:
+  identifier: token
+`;
+
+/** Existing entities answer GETs; everything else is a 404. */
+function makeClient(existing: string[]) {
+  const request = vi.fn().mockImplementation(async (opts: { method: string; path: string }) => {
+    if (opts.method === "GET" && !existing.some((p) => opts.path.startsWith(p))) {
+      throw new HarnessApiError("Entity not found", 404);
+    }
+    return { status: "SUCCESS", data: { identifier: "x" } };
+  });
+  return { client: { request, account: "test-account" } as unknown as HarnessClient, request };
+}
+
+const audit = { tool: "harness_execute", action: "apply" };
+
+describe("parseYamlBundle / orderBundle", () => {
+  it("classifies documents and orders them by dependency", () => {
+    const docs = orderBundle(parseYamlBundle(BUNDLE));
+    expect(docs.map((d) => `${d.kind}:${d.identifier ?? ""}`)).toEqual([
+      "connector:github",
+      "pipeline:deploy",
+      "inputSet:prod",
+      "secret:",
+    ]);
+    expect(docs[3]!.error).toMatch(/Unsupported document type/);
+  });
+
+  it("accepts an array of documents and rejects an empty bundle", () => {
+    expect(parseYamlBundle([{ service: { identifier: "api", name: "API" } }])[0]).toMatchObject({ kind: "service", identifier: "api" });
+    expect(() => parseYamlBundle("")).toThrow(/no documents/);
+  });
+});
+
+describe("applyYamlBundle", () => {
+  it("creates missing entities, updates existing ones, and reports each document", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient(["/pipeline/api/pipelines/deploy"]);
+
+    const result = await applyYamlBundle(registry, client, { documents: BUNDLE }, audit);
+
+    expect(result.summary).toEqual({ created: 2, updated: 1, invalid: 1 });
+    expect(result.results).toEqual([
+      expect.objectContaining({ kind: "connector", status: "created" }),
+      expect.objectContaining({ kind: "pipeline", status: "updated" }),
+      expect.objectContaining({ kind: "inputSet", status: "created" }),
+      expect.objectContaining({ kind: "secret", status: "invalid" }),
+    ]);
+    const writes = request.mock.calls.map(([opts]) => `${opts.method} ${opts.path}`).filter((c) => !c.startsWith("GET"));
+    expect(writes).toEqual([
+      "POST /ng/api/connectors",
+      "PUT /pipeline/api/pipelines/v2/deploy",
+      "POST /pipeline/api/inputSets",
+    ]);
+  });
+
+  it("writes nothing on dry_run", async () => {
+    const registry = new Registry(makeConfig());
+    const { client, request } = makeClient([]);
+
+    const result = await applyYamlBundle(registry, client, { documents: BUNDLE, dry_run: true }, audit);
+
+    expect(result.summary).toMatchObject({ would_create: 3 });
+    expect(request.mock.calls.every(([opts]) => opts.method === "GET")).toBe(true);
+  });
+});
@@ -0,0 +1,65 @@
+import { describe, it, expect } from "vitest";
+import { unifiedDiff } from "../../src/utils/yaml-diff.js";
+
+describe("unifiedDiff", () => {
+  it("returns an empty diff for identical inputs", () => {
+    const yaml = "pipeline:\n  name: a\n  identifier: a\n";
+    const result = unifiedDiff(yaml, yaml);
+    expect(result).toEqual({ diff: "", additions: 0, deletions: 0, hunks: 0 });
+  });
+
+  it("ignores CRLF vs LF line endings", () => {
+    const result = unifiedDiff("a\nb\n", "a\r\nb\r\n");
+    expect(result.hunks).toBe(0);
+  });
+
+  it("produces a single hunk for a modified line with headers", () => {
+    const from = "pipeline:\n  name: a\n  stages:\n    - stage: build\n";
+    const to = "pipeline:\n  name: b\n  stages:\n    - stage: build\n";
+    const result = unifiedDiff(from, to, { fromLabel: "p@main", toLabel: "p@feature" });
+
+    expect(result.additions).toBe(1);
+    expect(result.deletions).toBe(1);
+    expect(result.hunks).toBe(1);
+    expect(result.diff).toBe(
+      "--- p@main\n+++ p@feature\n@@ -1,4 +1,4 @@\n pipeline:\n-  name: a\n+  name: b\n   stages:\n     - stage: build\n",
+    );
+  });
+
+  it("splits distant changes into separate hunks", () => {
+    const lines = Array.from({ length: 20 }, (_, i) => `line${i}`);
+    const changed = [...lines];
+    changed[1] = "changed1";
+    changed[18] = "changed18";
+    const result = unifiedDiff(lines.join("\n"), changed.join("\n"), { context: 2 });
+
+    expect(result.hunks).toBe(2);
+    expect(result.diff).toContain("@@ -1,4 +1,4 @@");
+    expect(result.diff).toContain("@@ -17,4 +17,4 @@");
+  });
+
+  it("reports pure insertions with an empty source range", () => {
+    const result = unifiedDiff("a\nb\n", "a\nx\ny\nb\n", { context: 0 });
+
+    expect(result.additions).toBe(2);
+    expect(result.deletions).toBe(0);
+    expect(result.diff).toContain("@@ -1,0 +2,2 @@");
+    expect(result.diff).toContain("+x\n+y");
+  });
+
+  it("handles diffing from empty content", () => {
+    const result = unifiedDiff("", "a\nb\n");
+    expect(result.additions).toBe(2);
+    expect(result.diff).toContain("@@ -0,0 +1,2 @@");
+  });
+
+  it("summarizes a changed region too large to diff line by line", () => {
+    const from = ["head", ...Array.from({ length: 2500 }, (_, i) => `a${i}`), "tail"].join("\n");
+    const to = ["head", ...Array.from({ length: 2500 }, (_, i) => `b${i}`), "tail"].join("\n");
+    const result = unifiedDiff(from, to);
+
+    expect(result.diff).toBe("");
+    expect(result).toMatchObject({ additions: 2500, deletions: 2500, hunks: 1 });
+    expect(result.summary).toMatch(/^Changed from line 2: 2500 lines replaced by 2500\./);
+  });
+});
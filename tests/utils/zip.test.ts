@@ -0,0 +1,46 @@
+import { describe, it, expect } from "vitest";
+import { inflateRawSync } from "node:zlib";
+import { buildZip, crc32 } from "../../src/utils/zip.js";
+
+/** Read entries back by walking the local file headers. */
+function readZip(zip: Buffer): Record<string, string> {
+  const files: Record<string, string> = {};
+  let offset = 0;
+  while (zip.readUInt32LE(offset) === 0x04034b50) {
+    const size = zip.readUInt32LE(offset + 18);
+    const nameLength = zip.readUInt16LE(offset + 26);
+    const extraLength = zip.readUInt16LE(offset + 28);
+    const name = zip.subarray(offset + 30, offset + 30 + nameLength).toString("utf-8");
+    const start = offset + 30 + nameLength + extraLength;
+    files[name] = inflateRawSync(zip.subarray(start, start + size)).toString("utf-8");
+    offset = start + size;
+  }
+  return files;
+}
+
+describe("crc32", () => {
+  it("matches the standard check value", () => {
+    expect(crc32(Buffer.from("123456789"))).toBe(0xcbf43926);
+  });
+});
+
+describe("buildZip", () => {
+  it("round-trips entries with nested paths", () => {
+    const zip = buildZip([
+      { name: "pipelines/deploy.yaml", content: "pipeline:\n  identifier: deploy\n" },
+      { name: "manifest.json", content: "{}" },
+    ]);
+    expect(readZip(zip)).toEqual({
+      "pipelines/deploy.yaml": "pipeline:\n  identifier: deploy\n",
+      "manifest.json": "{}",
+    });
+  });
+
+  it("ends with a central directory record counting every entry", () => {
+    const zip = buildZip([{ name: "a.txt", content: "a" }, { name: "b.txt", content: "b" }]);
+    const end = zip.subarray(zip.length - 22);
+    expect(end.readUInt32LE(0)).toBe(0x06054b50);
+    expect(end.readUInt16LE(10)).toBe(2);
+    expect(zip.readUInt32LE(end.readUInt32LE(16))).toBe(0x02014b50);
+  });
+});